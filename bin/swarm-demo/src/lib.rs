@@ -630,7 +630,7 @@ fn describe_event(event: &TopologyEvent) -> (&'static str, String) {
                 None => format!("{error}"),
             },
         ),
-        TopologyEvent::PingCompleted { overlay, rtt } => (
+        TopologyEvent::PingCompleted { overlay, rtt, .. } => (
             "ping",
             format!(
                 "{} {}ms",
@@ -3,16 +3,19 @@
 use clap::{Parser, Subcommand};
 use eyre::Result;
 use vertex_node_builder::NodeBuilder;
-use vertex_node_commands::{HasLogs, HasTracing, InfraArgs, LogArgs, TracingArgs, run_cli};
+use vertex_node_commands::{
+    GrpcEndpointArgs, HasLogs, HasTracing, InfraArgs, LogArgs, TracingArgs, run_cli,
+};
 use vertex_node_core::config::FullNodeConfig;
 use vertex_node_core::dirs::DataDirs;
 use vertex_node_core::version;
 #[cfg(feature = "storer")]
 use vertex_swarm_builder::StorerConfig;
 use vertex_swarm_builder::{BootnodeConfig, ClientConfig};
-use vertex_swarm_node::ProtocolConfig;
 use vertex_swarm_node::args::ProtocolArgs;
+use vertex_swarm_node::{IssueSeverity, ProtocolConfig};
 use vertex_swarm_primitives::SwarmNodeType;
+use vertex_swarm_rpc::proto::node::{DumpRequest, node_client::NodeClient};
 use vertex_swarm_spec::SwarmSpec;
 use vertex_tasks::TaskExecutor;
 
@@ -50,6 +53,16 @@ impl HasTracing for SwarmCli {
 pub enum SwarmCommands {
     /// Run a Swarm node.
     Node(SwarmRunNodeArgs),
+    /// Print a diagnostic dump of a running node, queried over its gRPC server.
+    Dump(SwarmDumpArgs),
+}
+
+/// Arguments for the Swarm 'dump' command.
+#[derive(clap::Args)]
+pub struct SwarmDumpArgs {
+    /// The running node's gRPC server.
+    #[command(flatten)]
+    pub endpoint: GrpcEndpointArgs,
 }
 
 /// Combined arguments for the Swarm 'node' command.
@@ -67,7 +80,10 @@ pub struct SwarmRunNodeArgs {
 /// Run the Swarm CLI.
 pub async fn run() -> Result<()> {
     run_cli(|cli: SwarmCli| async move {
-        let SwarmCommands::Node(args) = cli.command;
+        let args = match cli.command {
+            SwarmCommands::Node(args) => args,
+            SwarmCommands::Dump(args) => return dump(args).await,
+        };
 
         // Spec and node type from ProtocolArgs
         let spec = args.protocol.spec.swarm.clone();
@@ -87,6 +103,19 @@ pub async fn run() -> Result<()> {
         config.apply_args(&args.infra, &args.protocol);
         config.protocol.override_node_type(node_type);
 
+        // Report every inconsistent setting at once, before anything is built.
+        let issues = config.protocol.validate(&spec);
+        for issue in &issues {
+            match issue.severity {
+                IssueSeverity::Warning => tracing::warn!(field = issue.field, "{}", issue.message),
+                IssueSeverity::Error => tracing::error!(field = issue.field, "{}", issue.message),
+            }
+        }
+        let errors = issues.iter().filter(|issue| issue.is_error()).count();
+        if errors > 0 {
+            eyre::bail!("configuration has {errors} error(s); see the log above");
+        }
+
         // Resolve database config from CLI args (in-memory unless persistence
         // is opted into via --db.path or --db.persist)
         let database_config = config
@@ -94,13 +123,23 @@ pub async fn run() -> Result<()> {
             .database
             .database_config(dirs.network.join("db").join("vertex.redb"));
 
-        // Build metrics config from CLI args
-        let metrics_config = args.infra.observability.metrics.metrics_config();
+        // Build metrics config from CLI args, labelling every metric with the
+        // network and node type so one scraper can serve several nodes.
+        let metrics_config = args
+            .infra
+            .observability
+            .metrics
+            .metrics_config()
+            .map(|cfg| {
+                cfg.with_global_label("network_id", spec.network_id().get().to_string())
+                    .with_global_label("node_type", node_type.to_string())
+            });
 
         // Collect histogram bucket configs from protocol crates
         let histogram_buckets = vertex_observability::HistogramRegistry::new()
             .register_all(vertex_swarm_net_headers::metrics::HISTOGRAM_BUCKETS)
             .register_all(vertex_swarm_topology::metrics::HISTOGRAM_BUCKETS)
+            .register_all(vertex_swarm_node::client_metrics::HISTOGRAM_BUCKETS)
             .register_all(vertex_swarm_net_handshake::metrics::HISTOGRAM_BUCKETS)
             .register_all(vertex_swarm_net_hive::metrics::HISTOGRAM_BUCKETS)
             .register_all(vertex_swarm_net_identify::metrics::HISTOGRAM_BUCKETS)
@@ -196,3 +235,11 @@ pub async fn run() -> Result<()> {
     })
     .await
 }
+
+/// Fetch the diagnostic dump from a running node and print it.
+async fn dump(args: SwarmDumpArgs) -> Result<()> {
+    let mut client = NodeClient::connect(args.endpoint.endpoint()).await?;
+    let dump = client.dump(DumpRequest {}).await?.into_inner();
+    print!("{}", crate::dump::render(&dump));
+    Ok(())
+}
@@ -0,0 +1,169 @@
+//! Plain-text rendering of the `vertex dump` report.
+
+use std::fmt::{self, Write};
+
+use vertex_swarm_rpc::proto::node::{
+    DumpConfig, DumpLedger, DumpPeer, DumpResponse, DumpStorage, DumpTopology,
+};
+
+/// Render `dump` as the sectioned report `vertex dump` prints.
+pub(crate) fn render(dump: &DumpResponse) -> String {
+    let mut out = String::new();
+    // Writing into a `String` cannot fail.
+    let _ = write_dump(&mut out, dump);
+    out
+}
+
+fn write_dump(out: &mut String, dump: &DumpResponse) -> fmt::Result {
+    if let Some(topology) = &dump.topology {
+        write_topology(out, topology)?;
+    }
+    write_peers(out, &dump.peers)?;
+    if !dump.accounting.is_empty() {
+        write_accounting(out, &dump.accounting)?;
+    }
+    if let Some(storage) = &dump.storage {
+        write_storage(out, storage)?;
+    }
+    if let Some(config) = &dump.config {
+        write_config(out, config)?;
+    }
+    Ok(())
+}
+
+fn write_topology(out: &mut String, topology: &DumpTopology) -> fmt::Result {
+    writeln!(out, "Topology")?;
+    writeln!(out, "  overlay              {}", topology.overlay_address)?;
+    writeln!(out, "  network id           {}", topology.network_id)?;
+    writeln!(
+        out,
+        "  depth                {}{}",
+        topology.depth,
+        if topology.neighbourhood_credible {
+            ""
+        } else {
+            " (not yet credible)"
+        }
+    )?;
+    writeln!(out, "  connected peers      {}", topology.connected_peers)?;
+    writeln!(out, "  known peers          {}", topology.known_peers)?;
+    writeln!(
+        out,
+        "  pending connections  {}",
+        topology.pending_connections
+    )?;
+    writeln!(out, "  stored peers         {}", topology.stored_peers)?;
+    writeln!(out, "  bins (po: connected/known)")?;
+    for bin in topology
+        .bins
+        .iter()
+        .filter(|bin| bin.connected_peers > 0 || bin.known_peers > 0)
+    {
+        writeln!(
+            out,
+            "    {:>2}: {}/{}",
+            bin.proximity_order, bin.connected_peers, bin.known_peers
+        )?;
+    }
+    writeln!(out)
+}
+
+fn write_peers(out: &mut String, peers: &[DumpPeer]) -> fmt::Result {
+    writeln!(out, "Peers ({})", peers.len())?;
+    for peer in peers {
+        let score = peer
+            .score
+            .map_or_else(|| "-".to_owned(), |score| format!("{score:.2}"));
+        let source = peer.discovery_source.as_deref().unwrap_or("-");
+        writeln!(
+            out,
+            "  po {:>2}  {}  score {score}  source {source}",
+            peer.proximity_order, peer.overlay
+        )?;
+        for addr in &peer.multiaddrs {
+            writeln!(out, "          {addr}")?;
+        }
+    }
+    writeln!(out)
+}
+
+fn write_accounting(out: &mut String, ledgers: &[DumpLedger]) -> fmt::Result {
+    writeln!(out, "Accounting (balance / reserved / disconnect / settle)")?;
+    for ledger in ledgers {
+        writeln!(
+            out,
+            "  {}  {} / {} / {} / {}",
+            ledger.overlay,
+            ledger.balance,
+            ledger.reserved,
+            ledger.disconnect_line,
+            ledger.settle_trigger
+        )?;
+    }
+    writeln!(out)
+}
+
+fn write_storage(out: &mut String, storage: &DumpStorage) -> fmt::Result {
+    writeln!(out, "Storage")?;
+    writeln!(out, "  storage radius       {}", storage.storage_radius)?;
+    let count = storage
+        .reserve_count
+        .map_or_else(|| "unknown".to_owned(), |count| count.to_string());
+    writeln!(
+        out,
+        "  reserve              {count} of {} chunks",
+        storage.reserve_capacity
+    )?;
+    writeln!(out)
+}
+
+fn write_config(out: &mut String, config: &DumpConfig) -> fmt::Result {
+    writeln!(out, "Config")?;
+    writeln!(out, "  node type            {}", config.node_type)?;
+    write_list(out, "listen", &config.listen_addrs)?;
+    write_list(out, "bootnodes", &config.bootnodes)?;
+    write_list(out, "trusted peers", &config.trusted_peers)?;
+    write_list(out, "nat addresses", &config.nat_addrs)?;
+    writeln!(out, "  max peers            {}", config.max_peers)?;
+    writeln!(out, "  idle timeout         {}s", config.idle_timeout_secs)?;
+    writeln!(
+        out,
+        "  discovery            {}",
+        on_off(config.discovery_enabled)
+    )?;
+    writeln!(
+        out,
+        "  auto nat             {}",
+        on_off(config.nat_auto_enabled)
+    )?;
+    writeln!(
+        out,
+        "  autonat v2           {}",
+        on_off(config.autonat_enabled)
+    )?;
+    writeln!(
+        out,
+        "  upnp                 {}",
+        on_off(config.upnp_enabled)
+    )?;
+    writeln!(
+        out,
+        "  mdns                 {}",
+        on_off(config.mdns_enabled)
+    )
+}
+
+fn write_list(out: &mut String, label: &str, items: &[String]) -> fmt::Result {
+    if items.is_empty() {
+        return writeln!(out, "  {label:<20} -");
+    }
+    for (i, item) in items.iter().enumerate() {
+        let label = if i == 0 { label } else { "" };
+        writeln!(out, "  {label:<20} {item}")?;
+    }
+    Ok(())
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled { "on" } else { "off" }
+}
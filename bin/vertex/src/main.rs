@@ -1,6 +1,7 @@
 //! Vertex Swarm node binary.
 
 mod cli;
+mod dump;
 
 // jemalloc is the default allocator wherever it is supported (Linux and macOS).
 // Windows (no msvc support) and wasm fall back to the system allocator.
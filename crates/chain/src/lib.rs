@@ -15,6 +15,8 @@
 //!   pending-transaction operations alloy has no built-in for (resend, cancel).
 //! - [`TxRequest`]: a newtype over `alloy_rpc_types_eth::TransactionRequest` that
 //!   attaches a static description for logs and metrics.
+//! - [`token_balance`]: the ERC-20 `balanceOf` read behind the node's reported
+//!   BZZ balance.
 //!
 //! Alloy providers run on `wasm32-unknown-unknown` with the right transport, so
 //! this crate stays wasm-compatible by depending on `alloy-provider` with
@@ -33,6 +35,7 @@ mod build;
 mod config;
 mod error;
 mod provider;
+mod token;
 mod tx;
 
 #[cfg(test)]
@@ -43,4 +46,5 @@ pub use build::{ProviderBuildError, SharedChainProvider, build_chain_provider};
 pub use config::ChainConfig;
 pub use error::{ChainError, TxError};
 pub use provider::ProviderExt;
+pub use token::token_balance;
 pub use tx::TxRequest;
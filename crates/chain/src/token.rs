@@ -0,0 +1,31 @@
+//! ERC-20 reads against the network's BZZ token.
+//!
+//! Only the balance read a node reports to its operator lives here. Transfers
+//! and approvals belong to the component that spends the tokens (postage,
+//! staking), which drives its own contract interface.
+
+use alloy_contract::CallBuilder;
+use alloy_primitives::{Address, U256};
+use alloy_provider::Provider;
+
+use crate::ChainError;
+
+alloy_sol_types::sol! {
+    /// The ERC-20 surface this crate reads.
+    interface IERC20 {
+        function balanceOf(address account) external view returns (uint256);
+    }
+}
+
+/// Read `owner`'s balance of the ERC-20 at `token`, in base units.
+pub async fn token_balance<P: Provider>(
+    provider: &P,
+    token: Address,
+    owner: Address,
+) -> Result<U256, ChainError> {
+    Ok(
+        CallBuilder::new_sol(provider, &token, &IERC20::balanceOfCall { account: owner })
+            .call()
+            .await?,
+    )
+}
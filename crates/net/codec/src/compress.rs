@@ -0,0 +1,353 @@
+//! Optional zstd compression of framed protocol messages.
+//!
+//! Whether a stream compresses is decided per connection, from capabilities
+//! both peers advertised when they connected. With [`Compression::None`] the
+//! [`Compressed`] wrapper is a pass-through and the wire is unchanged. With
+//! [`Compression::Zstd`] every message travels in an envelope:
+//!
+//! ```text
+//! flag (1 byte) | body length (u32, big-endian) | body
+//! ```
+//!
+//! The body is the inner codec's frame, zstd-compressed under flag `1`
+//! and verbatim under flag `0`. Frames below [`COMPRESSION_THRESHOLD`] and
+//! frames that do not shrink go raw, so small control messages and
+//! high-entropy chunk data cost only the five envelope bytes.
+//!
+//! zstd is native-only here, so a wasm build never advertises the capability
+//! ([`Compression::SUPPORTED`]) and always talks uncompressed.
+
+use asynchronous_codec::{Decoder, Encoder};
+use bytes::{Buf, BufMut, BytesMut};
+
+/// Smallest inner frame worth compressing, in bytes.
+pub const COMPRESSION_THRESHOLD: usize = 1024;
+
+/// Envelope flag: body is the inner frame as is.
+const FLAG_RAW: u8 = 0;
+
+/// Envelope flag: body is the zstd-compressed inner frame.
+const FLAG_ZSTD: u8 = 1;
+
+/// Flag byte plus the `u32` body length.
+const ENVELOPE_LEN: usize = 5;
+
+/// Room for the inner codec's varint length prefix on top of its payload cap.
+const MAX_LENGTH_PREFIX: usize = 10;
+
+/// zstd level: the library default, cheap enough for per-message use.
+#[cfg(not(target_arch = "wasm32"))]
+const ZSTD_LEVEL: i32 = 3;
+
+/// How a stream's messages are framed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum Compression {
+    /// Plain frames, byte-identical to the inner codec.
+    #[default]
+    None,
+    /// Enveloped frames, large ones zstd-compressed.
+    Zstd,
+}
+
+impl Compression {
+    /// Whether this build can compress at all.
+    pub const SUPPORTED: bool = cfg!(not(target_arch = "wasm32"));
+
+    /// Zstd when both ends support it, otherwise plain frames.
+    pub const fn negotiate(local: bool, remote: bool) -> Self {
+        if local && remote && Self::SUPPORTED {
+            Self::Zstd
+        } else {
+            Self::None
+        }
+    }
+}
+
+/// Why an enveloped frame could not be written or read.
+#[derive(Debug, thiserror::Error, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum CompressionError {
+    /// The envelope flag is not one we write.
+    #[error("unknown compression flag {0:#04x}")]
+    #[strum(serialize = "unknown_flag")]
+    UnknownFlag(u8),
+
+    /// The body is larger than the inner codec accepts.
+    #[error("compressed frame body of {0} bytes exceeds the limit")]
+    #[strum(serialize = "frame_too_large")]
+    FrameTooLarge(usize),
+
+    /// zstd failed to compress or decompress the body.
+    #[error("zstd: {0}")]
+    #[strum(serialize = "zstd")]
+    Zstd(std::io::Error),
+
+    /// The body did not hold exactly one inner frame.
+    #[error("compressed frame does not hold one complete message")]
+    TruncatedFrame,
+
+    /// Zstd framing was selected on a build without zstd.
+    #[error("compression is not supported on this platform")]
+    Unsupported,
+}
+
+/// Codec wrapper applying the negotiated [`Compression`] to an inner codec.
+pub struct Compressed<C> {
+    inner: C,
+    compression: Compression,
+    /// Largest body accepted on decode, before and after decompression.
+    max_body: usize,
+}
+
+impl<C> Compressed<C> {
+    /// Wrap `inner`, whose frames carry at most `max_packet_size` payload bytes.
+    pub fn new(inner: C, compression: Compression, max_packet_size: usize) -> Self {
+        Self {
+            inner,
+            compression,
+            max_body: max_packet_size.saturating_add(MAX_LENGTH_PREFIX),
+        }
+    }
+
+    /// The compression this codec applies.
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+}
+
+impl<C> Encoder for Compressed<C>
+where
+    C: Encoder,
+    C::Error: From<CompressionError>,
+{
+    type Item<'a> = C::Item<'a>;
+    type Error = C::Error;
+
+    fn encode(&mut self, item: Self::Item<'_>, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        if self.compression == Compression::None {
+            return self.inner.encode(item, dst);
+        }
+
+        let mut frame = BytesMut::new();
+        self.inner.encode(item, &mut frame)?;
+
+        let compressed = if frame.len() >= COMPRESSION_THRESHOLD {
+            Some(compress(&frame)?).filter(|body| body.len() < frame.len())
+        } else {
+            None
+        };
+        let (flag, body) = match &compressed {
+            Some(body) => (FLAG_ZSTD, body.as_slice()),
+            None => (FLAG_RAW, frame.as_ref()),
+        };
+        let len =
+            u32::try_from(body.len()).map_err(|_| CompressionError::FrameTooLarge(body.len()))?;
+
+        dst.reserve(ENVELOPE_LEN + body.len());
+        dst.put_u8(flag);
+        dst.put_u32(len);
+        dst.put_slice(body);
+        Ok(())
+    }
+}
+
+impl<C> Decoder for Compressed<C>
+where
+    C: Decoder,
+    C::Error: From<CompressionError>,
+{
+    type Item = C::Item;
+    type Error = C::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if self.compression == Compression::None {
+            return self.inner.decode(src);
+        }
+
+        let Some((&flag, rest)) = src.split_first() else {
+            return Ok(None);
+        };
+        let Some(len) = rest
+            .get(..ENVELOPE_LEN - 1)
+            .and_then(|bytes| <[u8; 4]>::try_from(bytes).ok())
+            .map(|bytes| u32::from_be_bytes(bytes) as usize)
+        else {
+            return Ok(None);
+        };
+        if flag != FLAG_RAW && flag != FLAG_ZSTD {
+            return Err(CompressionError::UnknownFlag(flag).into());
+        }
+        if len > self.max_body {
+            return Err(CompressionError::FrameTooLarge(len).into());
+        }
+        if src.len() < ENVELOPE_LEN + len {
+            src.reserve(ENVELOPE_LEN + len - src.len());
+            return Ok(None);
+        }
+
+        src.advance(ENVELOPE_LEN);
+        let body = src.split_to(len);
+        let mut frame = if flag == FLAG_ZSTD {
+            BytesMut::from(decompress(&body, self.max_body)?.as_slice())
+        } else {
+            body
+        };
+
+        let item = self
+            .inner
+            .decode(&mut frame)?
+            .ok_or(CompressionError::TruncatedFrame)?;
+        if !frame.is_empty() {
+            return Err(CompressionError::TruncatedFrame.into());
+        }
+        Ok(Some(item))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn compress(frame: &[u8]) -> Result<Vec<u8>, CompressionError> {
+    zstd::bulk::compress(frame, ZSTD_LEVEL).map_err(CompressionError::Zstd)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn decompress(body: &[u8], limit: usize) -> Result<Vec<u8>, CompressionError> {
+    zstd::bulk::decompress(body, limit).map_err(CompressionError::Zstd)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn compress(_frame: &[u8]) -> Result<Vec<u8>, CompressionError> {
+    Err(CompressionError::Unsupported)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn decompress(_body: &[u8], _limit: usize) -> Result<Vec<u8>, CompressionError> {
+    Err(CompressionError::Unsupported)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::indexing_slicing)]
+
+    use super::*;
+
+    /// Inner codec framing raw bytes behind a `u16` length, enough to exercise
+    /// the envelope without a protobuf type.
+    struct Raw;
+
+    #[derive(Debug, thiserror::Error)]
+    enum RawError {
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error(transparent)]
+        Compression(#[from] CompressionError),
+    }
+
+    impl Encoder for Raw {
+        type Item<'a> = Vec<u8>;
+        type Error = RawError;
+
+        fn encode(&mut self, item: Vec<u8>, dst: &mut BytesMut) -> Result<(), RawError> {
+            dst.put_u16(item.len() as u16);
+            dst.put_slice(&item);
+            Ok(())
+        }
+    }
+
+    impl Decoder for Raw {
+        type Item = Vec<u8>;
+        type Error = RawError;
+
+        fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Vec<u8>>, RawError> {
+            if src.len() < 2 {
+                return Ok(None);
+            }
+            let len = usize::from(u16::from_be_bytes([src[0], src[1]]));
+            if src.len() < 2 + len {
+                return Ok(None);
+            }
+            src.advance(2);
+            Ok(Some(src.split_to(len).to_vec()))
+        }
+    }
+
+    fn wire(compression: Compression, payload: &[u8]) -> BytesMut {
+        let mut buf = BytesMut::new();
+        Compressed::new(Raw, compression, 8192)
+            .encode(payload.to_vec(), &mut buf)
+            .unwrap();
+        buf
+    }
+
+    #[test]
+    fn only_a_pair_that_both_support_it_compresses() {
+        assert_eq!(Compression::negotiate(true, true), Compression::Zstd);
+        assert_eq!(Compression::negotiate(true, false), Compression::None);
+        assert_eq!(Compression::negotiate(false, true), Compression::None);
+    }
+
+    #[test]
+    fn plain_framing_is_the_inner_codec() {
+        let payload = vec![7u8; 4096];
+        let mut plain = BytesMut::new();
+        Raw.encode(payload.clone(), &mut plain).unwrap();
+        assert_eq!(wire(Compression::None, &payload), plain);
+    }
+
+    #[test]
+    fn small_and_incompressible_frames_go_raw() {
+        let mut small = wire(Compression::Zstd, b"ping");
+        assert_eq!(small.first(), Some(&FLAG_RAW));
+        let mut codec = Compressed::new(Raw, Compression::Zstd, 8192);
+        assert_eq!(codec.decode(&mut small).unwrap().unwrap(), b"ping");
+
+        // xorshift output: nothing for zstd to find, like encrypted chunk data.
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        let noise: Vec<u8> = (0..4096)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect();
+        let mut buf = wire(Compression::Zstd, &noise);
+        assert_eq!(buf.first(), Some(&FLAG_RAW));
+        assert_eq!(buf.len(), ENVELOPE_LEN + 2 + noise.len());
+        assert_eq!(codec.decode(&mut buf).unwrap().unwrap(), noise);
+    }
+
+    #[test]
+    fn decode_waits_for_the_whole_envelope() {
+        let payload = vec![1u8; 2048];
+        let full = wire(Compression::Zstd, &payload);
+        let mut codec = Compressed::new(Raw, Compression::Zstd, 8192);
+
+        let mut partial = BytesMut::from(&full[..full.len() - 1]);
+        assert!(codec.decode(&mut partial).unwrap().is_none());
+        partial.extend_from_slice(&full[full.len() - 1..]);
+        assert_eq!(codec.decode(&mut partial).unwrap().unwrap(), payload);
+        assert!(partial.is_empty());
+    }
+
+    #[test]
+    fn oversized_or_unknown_envelopes_are_refused() {
+        let mut codec = Compressed::new(Raw, Compression::Zstd, 16);
+
+        let mut big = BytesMut::new();
+        big.put_u8(FLAG_RAW);
+        big.put_u32(1 << 20);
+        assert!(matches!(
+            codec.decode(&mut big),
+            Err(RawError::Compression(CompressionError::FrameTooLarge(_)))
+        ));
+
+        let mut unknown = BytesMut::new();
+        unknown.put_u8(9);
+        unknown.put_u32(0);
+        assert!(matches!(
+            codec.decode(&mut unknown),
+            Err(RawError::Compression(CompressionError::UnknownFlag(9)))
+        ));
+    }
+}
@@ -1,5 +1,8 @@
 //! Codec utilities for protobuf-based network protocols.
 
+mod compress;
+pub use compress::{COMPRESSION_THRESHOLD, Compressed, Compression, CompressionError};
+
 mod framed;
 pub use framed::{FramedProto, StreamClosed};
 
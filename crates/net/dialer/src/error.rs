@@ -12,4 +12,6 @@ pub enum PrepareError {
     InBackoff,
     #[error("peer is banned")]
     Banned,
+    #[error("in-flight dial limit reached")]
+    AtCapacity,
 }
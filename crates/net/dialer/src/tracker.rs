@@ -3,6 +3,7 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::time::Duration;
 
 use hashlink::{LinkedHashMap, LruCache};
 use libp2p::swarm::dial_opts::DialOpts;
@@ -164,7 +165,8 @@ impl<Id: Clone + Eq + Hash + Debug, D: Debug> DialTracker<Id, D> {
     /// Filter addresses, build `DialOpts`, and register as in-flight in one step.
     ///
     /// Combines address preparation and in-flight tracking into a single call.
-    /// Returns `DialOpts` ready to pass to `ToSwarm::Dial`.
+    /// Returns `DialOpts` ready to pass to `ToSwarm::Dial`, or
+    /// [`PrepareError::AtCapacity`] while `max_in_flight` dials are outstanding.
     pub fn prepare_and_start(
         &mut self,
         id: Option<Id>,
@@ -181,7 +183,11 @@ impl<Id: Clone + Eq + Hash + Debug, D: Debug> DialTracker<Id, D> {
                 return Err(PrepareError::InBackoff);
             }
         }
+        let at_capacity = !self.has_capacity();
         self.try_start(peer_id, id, PrepareError::AlreadyTracked, |id| {
+            if at_capacity {
+                return Err(PrepareError::AtCapacity);
+            }
             let opts = prepare::prepare_dial_opts(peer_id, addrs, &mut filter)
                 .ok_or(PrepareError::NoReachableAddresses)?;
             let request = match id {
@@ -210,11 +216,29 @@ impl<Id: Clone + Eq + Hash + Debug, D: Debug> DialTracker<Id, D> {
         self.id_index.contains_key(id)
     }
 
+    /// Override the in-flight timeout of an in-flight dial, e.g. to give a
+    /// more valuable peer more patience than the tracker default. Returns
+    /// `false` if the peer is not in-flight.
+    pub fn set_in_flight_timeout(&mut self, peer_id: &PeerId, timeout: Duration) -> bool {
+        match self.in_flight.get_mut(peer_id) {
+            Some(request) => {
+                request.timeout = Some(timeout);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Check if PeerId is in-flight.
     pub fn is_in_flight(&self, peer_id: &PeerId) -> bool {
         self.in_flight.contains_key(peer_id)
     }
 
+    /// Whether another dial can start without exceeding `max_in_flight`.
+    pub fn has_capacity(&self) -> bool {
+        self.in_flight.len() < self.config.max_in_flight
+    }
+
     /// Number of pending requests in the queue.
     pub fn pending_count(&self) -> usize {
         self.pending.len()
@@ -242,11 +266,14 @@ impl<Id: Clone + Eq + Hash + Debug, D: Debug> DialTracker<Id, D> {
             }
         }
 
-        let timeout = self.config.in_flight_timeout;
+        let default_timeout = self.config.in_flight_timeout;
         let timed_out_peer_ids: Vec<PeerId> = self
             .in_flight
             .iter()
-            .filter(|(_, req)| req.started_at.is_some_and(|t| t.elapsed() > timeout))
+            .filter(|(_, req)| {
+                let timeout = req.timeout.unwrap_or(default_timeout);
+                req.started_at.is_some_and(|t| t.elapsed() > timeout)
+            })
             .map(|(peer_id, _)| *peer_id)
             .collect();
 
@@ -833,6 +860,23 @@ mod tests {
         assert!(!t.contains_id(&1));
     }
 
+    #[test]
+    fn test_cleanup_honours_per_request_timeout() {
+        let mut t = tracker_with(|c| c.in_flight_timeout = Duration::ZERO);
+        t.start_dial(request(1, 1).with_timeout(Duration::from_secs(3600)))
+            .unwrap();
+        t.start_dial(request(2, 2)).unwrap();
+        assert!(prepare(&mut t, Some(3), 3).is_ok());
+        assert!(t.set_in_flight_timeout(&peer(3), Duration::from_secs(3600)));
+        assert!(!t.set_in_flight_timeout(&peer(4), Duration::ZERO));
+
+        let r = t.cleanup_expired();
+        assert_eq!(r.timed_out_in_flight.len(), 1);
+        assert_eq!(r.timed_out_in_flight[0].id, Some(2));
+        assert!(t.is_in_flight(&peer(1)));
+        assert!(t.is_in_flight(&peer(3)));
+    }
+
     #[test]
     fn test_cleanup_preserves_request_data() {
         let mut t = tracker_with(|c| c.in_flight_timeout = Duration::ZERO);
@@ -884,6 +928,25 @@ mod tests {
         )); // same id
     }
 
+    #[test]
+    fn test_prepare_and_start_at_capacity() {
+        let mut t = tracker();
+        for i in 1..=3 {
+            prepare(&mut t, Some(i), i as u8).unwrap();
+        }
+        assert!(!t.has_capacity());
+        assert!(matches!(
+            prepare(&mut t, Some(4), 4),
+            Err(PrepareError::AtCapacity)
+        ));
+        assert!(!t.contains_peer(&peer(4)));
+        assert_counts(&t, 0, 3);
+
+        t.resolve(&peer(1));
+        assert!(t.has_capacity());
+        assert!(prepare(&mut t, Some(4), 4).is_ok());
+    }
+
     #[test]
     fn test_timestamps() {
         let mut t = tracker();
@@ -1,6 +1,7 @@
 //! Types for dial tracking.
 
 use std::fmt::Debug;
+use std::time::Duration;
 
 use libp2p::{Multiaddr, PeerId};
 use vertex_util_runtime::time::Instant;
@@ -36,6 +37,9 @@ pub struct DialRequest<Id, D> {
     pub(crate) queued_at: Instant,
     /// When this request moved to in-flight (`None` while still pending).
     pub(crate) started_at: Option<Instant>,
+    /// In-flight timeout for this request; `None` uses the tracker's
+    /// [`DialTrackerConfig::in_flight_timeout`](crate::DialTrackerConfig).
+    pub(crate) timeout: Option<Duration>,
 }
 
 impl<Id, D> DialRequest<Id, D> {
@@ -48,9 +52,16 @@ impl<Id, D> DialRequest<Id, D> {
             data,
             queued_at: Instant::now(),
             started_at: None,
+            timeout: None,
         }
     }
 
+    /// Override the tracker's in-flight timeout for this request.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// When this request was first created/enqueued.
     pub fn queued_at(&self) -> Instant {
         self.queued_at
@@ -70,6 +81,7 @@ impl<Id, D> DialRequest<Id, D> {
             data,
             queued_at: Instant::now(),
             started_at: None,
+            timeout: None,
         }
     }
 }
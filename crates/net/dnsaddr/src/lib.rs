@@ -1,9 +1,19 @@
 //! Recursive `/dnsaddr/` multiaddr resolution (resolves ALL TXT records, unlike libp2p's DNS transport).
+//!
+//! [`DnsaddrResolver`] runs over a configurable [`TxtLookup`] (the system
+//! resolver or specific servers through [`HickoryLookup`]) and keeps the last
+//! good resolution per address. Within the cache TTL an address is served
+//! from the cache; past it a failed or empty lookup falls back to the
+//! last-known-good addresses, so a flaky DNS server cannot block bootstrap.
 
-use std::collections::HashSet;
+mod lookup;
+
+pub use lookup::{DnsResolverConfig, HickoryLookup, TxtLookup, TxtLookupFuture};
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
 
-use hickory_resolver::Resolver;
-use hickory_resolver::proto::rr::RData;
 use libp2p::Multiaddr;
 use libp2p::multiaddr::Protocol;
 use tracing::{debug, warn};
@@ -17,39 +27,20 @@ pub fn is_dnsaddr(addr: &Multiaddr) -> bool {
     addr.iter().any(|p| matches!(p, Protocol::Dnsaddr(_)))
 }
 
-/// Resolve a batch of multiaddrs, expanding every `/dnsaddr/` entry.
+/// Resolve a batch of multiaddrs through the system resolver, without caching.
 ///
-/// - Non-dnsaddr inputs pass through unchanged.
-/// - A shared seen-set deduplicates across the whole batch.
-/// - On resolution failure the original address is kept as fallback.
+/// See [`DnsaddrResolver::resolve_all`]; use a long-lived [`DnsaddrResolver`]
+/// to fall back to earlier resolutions.
 pub async fn resolve_all(addrs: impl IntoIterator<Item = &Multiaddr>) -> Vec<Multiaddr> {
-    let mut resolved = Vec::new();
-    let mut seen = HashSet::new();
-
-    for addr in addrs {
-        if !is_dnsaddr(addr) {
-            resolved.push(addr.clone());
-            continue;
-        }
-
-        match resolve_one(addr, &mut seen).await {
-            Ok(addrs) => {
-                debug!(addr = %addr, resolved_count = addrs.len(), "Resolved dnsaddr");
-                resolved.extend(addrs);
-            }
-            Err(e) => {
-                warn!(addr = %addr, error = %e, "Failed to resolve dnsaddr, keeping original");
-                resolved.push(addr.clone());
-            }
-        }
-    }
-
-    resolved
+    DnsaddrResolver::from_config(DnsResolverConfig::System, Duration::ZERO)
+        .resolve_all(addrs)
+        .await
 }
 
-/// Internal dnsaddr resolution errors.
-#[derive(Debug, thiserror::Error)]
-enum ResolveError {
+/// dnsaddr resolution errors.
+#[derive(Debug, thiserror::Error, strum::IntoStaticStr)]
+#[non_exhaustive]
+pub enum ResolveError {
     #[error("DNS lookup failed: {0}")]
     DnsLookup(String),
 
@@ -57,12 +48,135 @@ enum ResolveError {
     MaxRecursionDepth,
 }
 
-/// Resolve a single dnsaddr, reusing a shared `seen` set.
-async fn resolve_one(
-    addr: &Multiaddr,
-    seen: &mut HashSet<String>,
-) -> Result<Vec<Multiaddr>, ResolveError> {
-    resolve_recursive(addr, seen, 0).await
+/// Last good resolution of one `/dnsaddr/` address.
+#[derive(Debug)]
+struct CachedResolution {
+    addrs: Vec<Multiaddr>,
+    resolved_at: Instant,
+}
+
+/// Caching `/dnsaddr/` resolver.
+///
+/// ```no_run
+/// # async fn example() {
+/// use std::time::Duration;
+/// use vertex_net_dnsaddr::{DnsResolverConfig, DnsaddrResolver};
+///
+/// let resolver = DnsaddrResolver::from_config(
+///     DnsResolverConfig::Servers(vec!["1.1.1.1".parse().unwrap()]),
+///     Duration::from_secs(3600),
+/// );
+/// let bootnode = "/dnsaddr/mainnet.ethswarm.org".parse().unwrap();
+/// let dialable = resolver.resolve_all([&bootnode]).await;
+/// # }
+/// ```
+pub struct DnsaddrResolver {
+    lookup: Arc<dyn TxtLookup>,
+    ttl: Duration,
+    cache: Mutex<HashMap<Multiaddr, CachedResolution>>,
+}
+
+impl DnsaddrResolver {
+    /// Resolver over `lookup`; a resolution is reused without a lookup for `ttl`.
+    pub fn new(lookup: Arc<dyn TxtLookup>, ttl: Duration) -> Self {
+        Self {
+            lookup,
+            ttl,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Resolver over hickory with the given server selection.
+    pub fn from_config(config: DnsResolverConfig, ttl: Duration) -> Self {
+        Self::new(Arc::new(HickoryLookup::new(config)), ttl)
+    }
+
+    /// Resolve a batch of multiaddrs, expanding every `/dnsaddr/` entry.
+    ///
+    /// - Non-dnsaddr inputs pass through unchanged.
+    /// - A shared seen-set deduplicates names and leaves across the whole batch.
+    /// - A resolution younger than the TTL is served from the cache.
+    /// - On failure the last-known-good addresses are used, whatever their
+    ///   age; without any the original address is kept.
+    pub async fn resolve_all(&self, addrs: impl IntoIterator<Item = &Multiaddr>) -> Vec<Multiaddr> {
+        let mut resolved = Vec::new();
+        let mut seen = HashSet::new();
+
+        for addr in addrs {
+            if !is_dnsaddr(addr) {
+                resolved.push(addr.clone());
+                continue;
+            }
+
+            if let Some(cached) = self.cached(addr, true) {
+                debug!(
+                    addr = %addr,
+                    resolved_count = cached.len(),
+                    "Using cached dnsaddr resolution"
+                );
+                resolved.extend(cached);
+                continue;
+            }
+
+            match resolve_recursive(&*self.lookup, addr, &mut seen, 0).await {
+                Ok(addrs) if !addrs.is_empty() => {
+                    debug!(addr = %addr, resolved_count = addrs.len(), "Resolved dnsaddr");
+                    self.store(addr, &addrs);
+                    resolved.extend(addrs);
+                }
+                Ok(_) => {
+                    // Empty answer, or every name was already expanded earlier
+                    // in this batch.
+                    resolved.extend(self.cached(addr, false).unwrap_or_default());
+                }
+                Err(e) => match self.cached(addr, false) {
+                    Some(cached) => {
+                        warn!(
+                            addr = %addr,
+                            error = %e,
+                            "Failed to resolve dnsaddr, using last-known-good addresses"
+                        );
+                        resolved.extend(cached);
+                    }
+                    None => {
+                        warn!(
+                            addr = %addr,
+                            error = %e,
+                            "Failed to resolve dnsaddr, keeping original"
+                        );
+                        resolved.push(addr.clone());
+                    }
+                },
+            }
+        }
+
+        // Cached and fresh expansions of overlapping names can repeat a leaf.
+        let mut unique = HashSet::new();
+        resolved.retain(|addr| unique.insert(addr.clone()));
+        resolved
+    }
+
+    /// Cached addresses for `addr`; with `fresh_only` only within the TTL.
+    fn cached(&self, addr: &Multiaddr, fresh_only: bool) -> Option<Vec<Multiaddr>> {
+        let cache = self.cache.lock().unwrap_or_else(PoisonError::into_inner);
+        cache
+            .get(addr)
+            .filter(|entry| !fresh_only || entry.resolved_at.elapsed() < self.ttl)
+            .map(|entry| entry.addrs.clone())
+    }
+
+    fn store(&self, addr: &Multiaddr, addrs: &[Multiaddr]) {
+        self.cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(
+                addr.clone(),
+                CachedResolution {
+                    addrs: addrs.to_vec(),
+                    resolved_at: Instant::now(),
+                },
+            );
+    }
 }
 
 /// Extract domain from the first `/dnsaddr/{domain}` component.
@@ -74,6 +188,7 @@ fn extract_domain(addr: &Multiaddr) -> Option<String> {
 }
 
 fn resolve_recursive<'a>(
+    lookup: &'a dyn TxtLookup,
     addr: &'a Multiaddr,
     seen: &'a mut HashSet<String>,
     depth: usize,
@@ -90,50 +205,33 @@ fn resolve_recursive<'a>(
             None => return Ok(vec![addr.clone()]),
         };
 
-        let cache_key = format!("_dnsaddr.{}", domain);
-        if seen.contains(&cache_key) {
+        let txt_name = format!("_dnsaddr.{}", domain);
+        if seen.contains(&txt_name) {
             debug!(domain = %domain, "Skipping already-seen dnsaddr domain");
             return Ok(vec![]);
         }
-        seen.insert(cache_key.clone());
-
-        let resolver = Resolver::builder_tokio()
-            .map_err(|e| ResolveError::DnsLookup(format!("failed to create resolver: {e}")))?
-            .build()
-            .map_err(|e| ResolveError::DnsLookup(format!("failed to build resolver: {e}")))?;
-
-        let txt_name = format!("_dnsaddr.{}", domain);
-        debug!(name = %txt_name, "Querying DNS TXT records");
+        seen.insert(txt_name.clone());
 
-        let txt_records = resolver
-            .txt_lookup(&txt_name)
-            .await
-            .map_err(|e| ResolveError::DnsLookup(format!("lookup {txt_name}: {e}")))?;
+        let txt_records = lookup.lookup_txt(&txt_name).await?;
 
         let mut results = Vec::new();
 
-        for record in txt_records.answers() {
-            let RData::TXT(txt) = &record.data else {
-                continue;
-            };
-            for bytes in txt.txt_data.iter() {
-                let txt_str = String::from_utf8_lossy(bytes);
-
-                if let Some(value) = txt_str.strip_prefix("dnsaddr=") {
-                    debug!(record = %value, "Found dnsaddr TXT record");
-
-                    match value.parse::<Multiaddr>() {
-                        Ok(resolved_addr) => {
-                            let nested = resolve_recursive(&resolved_addr, seen, depth + 1).await?;
-                            results.extend(nested);
-                        }
-                        Err(e) => {
-                            warn!(
-                                value = %value,
-                                error = %e,
-                                "Failed to parse multiaddr from TXT record"
-                            );
-                        }
+        for txt_str in &txt_records {
+            if let Some(value) = txt_str.strip_prefix("dnsaddr=") {
+                debug!(record = %value, "Found dnsaddr TXT record");
+
+                match value.parse::<Multiaddr>() {
+                    Ok(resolved_addr) => {
+                        let nested =
+                            resolve_recursive(lookup, &resolved_addr, seen, depth + 1).await?;
+                        results.extend(nested);
+                    }
+                    Err(e) => {
+                        warn!(
+                            value = %value,
+                            error = %e,
+                            "Failed to parse multiaddr from TXT record"
+                        );
                     }
                 }
             }
@@ -144,9 +242,46 @@ fn resolve_recursive<'a>(
 }
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
     use super::*;
 
+    const LEAF: &str = "/ip4/192.0.2.1/tcp/1634/p2p/QmYyQSo1c1Ym7orWxLYvCrM2EmxFTANf8wXmmE7DWjhx5N";
+
+    /// Answers `_dnsaddr.boot.example` with one leaf, failing every lookup
+    /// after the first `healthy` ones.
+    struct FlakyLookup {
+        healthy: usize,
+        calls: AtomicUsize,
+    }
+
+    impl FlakyLookup {
+        fn new(healthy: usize) -> Arc<Self> {
+            Arc::new(Self {
+                healthy,
+                calls: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    impl TxtLookup for FlakyLookup {
+        fn lookup_txt<'a>(&'a self, name: &'a str) -> TxtLookupFuture<'a> {
+            Box::pin(async move {
+                let call = self.calls.fetch_add(1, Ordering::SeqCst);
+                if call >= self.healthy {
+                    return Err(ResolveError::DnsLookup(format!("lookup {name}: timed out")));
+                }
+                Ok(vec![format!("dnsaddr={LEAF}")])
+            })
+        }
+    }
+
+    fn bootnode() -> Multiaddr {
+        "/dnsaddr/boot.example".parse().unwrap()
+    }
+
     #[test]
     fn is_dnsaddr_false_for_ip() {
         let addr: Multiaddr = "/ip4/127.0.0.1/tcp/1634".parse().unwrap();
@@ -180,6 +315,19 @@ mod tests {
         assert_eq!(extract_domain(&addr), None);
     }
 
+    #[test]
+    fn empty_server_list_means_system_resolver() {
+        assert_eq!(
+            DnsResolverConfig::from_servers(&[]),
+            DnsResolverConfig::System
+        );
+        let server = "9.9.9.9".parse().unwrap();
+        assert_eq!(
+            DnsResolverConfig::from_servers(&[server]),
+            DnsResolverConfig::Servers(vec![server])
+        );
+    }
+
     #[tokio::test]
     async fn resolve_all_passes_non_dnsaddr_through() {
         let addr: Multiaddr = "/ip4/127.0.0.1/tcp/1634".parse().unwrap();
@@ -188,10 +336,51 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn resolve_one_returns_non_dnsaddr_unchanged() {
+    async fn resolve_recursive_returns_non_dnsaddr_unchanged() {
+        let lookup = FlakyLookup::new(0);
         let addr: Multiaddr = "/ip4/127.0.0.1/tcp/1634".parse().unwrap();
         let mut seen = HashSet::new();
-        let resolved = resolve_one(&addr, &mut seen).await.unwrap();
+        let resolved = resolve_recursive(&*lookup, &addr, &mut seen, 0)
+            .await
+            .unwrap();
         assert_eq!(resolved, vec![addr]);
+        assert_eq!(lookup.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn failed_lookup_falls_back_to_last_known_good() {
+        // A zero TTL forces the second call to look up again, which fails.
+        let lookup = FlakyLookup::new(1);
+        let resolver = DnsaddrResolver::new(lookup.clone(), Duration::ZERO);
+        let leaf: Multiaddr = LEAF.parse().unwrap();
+
+        let first = resolver.resolve_all([&bootnode()]).await;
+        assert_eq!(first, vec![leaf.clone()]);
+
+        let second = resolver.resolve_all([&bootnode()]).await;
+        assert_eq!(lookup.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            second,
+            vec![leaf],
+            "the cached leaf is used, not the dnsaddr"
+        );
+    }
+
+    #[tokio::test]
+    async fn fresh_cache_skips_the_lookup() {
+        let lookup = FlakyLookup::new(1);
+        let resolver = DnsaddrResolver::new(lookup.clone(), Duration::from_secs(3600));
+
+        let first = resolver.resolve_all([&bootnode()]).await;
+        let second = resolver.resolve_all([&bootnode()]).await;
+        assert_eq!(first, second);
+        assert_eq!(lookup.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn failure_without_cache_keeps_original() {
+        let resolver = DnsaddrResolver::new(FlakyLookup::new(0), Duration::ZERO);
+        let resolved = resolver.resolve_all([&bootnode()]).await;
+        assert_eq!(resolved, vec![bootnode()]);
     }
 }
@@ -0,0 +1,118 @@
+//! TXT lookup backends for dnsaddr resolution.
+
+use std::future::Future;
+use std::net::IpAddr;
+use std::pin::Pin;
+use std::sync::OnceLock;
+
+use hickory_resolver::TokioResolver;
+use hickory_resolver::config::{NameServerConfig, ResolverConfig};
+use hickory_resolver::proto::rr::RData;
+use hickory_resolver::proto::runtime::TokioRuntimeProvider;
+use tracing::debug;
+
+use crate::ResolveError;
+
+/// Future returned by [`TxtLookup::lookup_txt`].
+pub type TxtLookupFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Vec<String>, ResolveError>> + Send + 'a>>;
+
+/// Which DNS servers answer dnsaddr TXT queries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum DnsResolverConfig {
+    /// The host's configured resolvers (`/etc/resolv.conf` or platform
+    /// equivalent).
+    #[default]
+    System,
+    /// Only these servers, queried on port 53 over UDP with TCP fallback.
+    Servers(Vec<IpAddr>),
+}
+
+impl DnsResolverConfig {
+    /// `System` for an empty list, `Servers` otherwise.
+    pub fn from_servers(servers: &[IpAddr]) -> Self {
+        if servers.is_empty() {
+            Self::System
+        } else {
+            Self::Servers(servers.to_vec())
+        }
+    }
+}
+
+/// Fetches the TXT strings published at a DNS name.
+pub trait TxtLookup: Send + Sync {
+    /// Every TXT character-string at `name` (already `_dnsaddr.`-prefixed).
+    fn lookup_txt<'a>(&'a self, name: &'a str) -> TxtLookupFuture<'a>;
+}
+
+/// [`TxtLookup`] over a hickory resolver.
+///
+/// The resolver is built on first lookup, so construction needs no runtime and
+/// a host without usable resolver configuration fails the lookup (and falls
+/// back to cached addresses) instead of the node build.
+#[derive(Debug)]
+pub struct HickoryLookup {
+    config: DnsResolverConfig,
+    resolver: OnceLock<TokioResolver>,
+}
+
+impl HickoryLookup {
+    /// Query the servers `config` names. No resolver is built until the first
+    /// lookup.
+    pub fn new(config: DnsResolverConfig) -> Self {
+        Self {
+            config,
+            resolver: OnceLock::new(),
+        }
+    }
+
+    fn resolver(&self) -> Result<&TokioResolver, ResolveError> {
+        if let Some(resolver) = self.resolver.get() {
+            return Ok(resolver);
+        }
+        let builder = match &self.config {
+            DnsResolverConfig::System => TokioResolver::builder_tokio()
+                .map_err(|e| ResolveError::DnsLookup(format!("failed to create resolver: {e}")))?,
+            DnsResolverConfig::Servers(servers) => {
+                let name_servers = servers
+                    .iter()
+                    .map(|ip| NameServerConfig::udp_and_tcp(*ip))
+                    .collect();
+                TokioResolver::builder_with_config(
+                    ResolverConfig::from_parts(None, Vec::new(), name_servers),
+                    TokioRuntimeProvider::default(),
+                )
+            }
+        };
+        let resolver = builder
+            .build()
+            .map_err(|e| ResolveError::DnsLookup(format!("failed to build resolver: {e}")))?;
+        Ok(self.resolver.get_or_init(|| resolver))
+    }
+}
+
+impl TxtLookup for HickoryLookup {
+    fn lookup_txt<'a>(&'a self, name: &'a str) -> TxtLookupFuture<'a> {
+        Box::pin(async move {
+            debug!(%name, "Querying DNS TXT records");
+            let records = self
+                .resolver()?
+                .txt_lookup(name)
+                .await
+                .map_err(|e| ResolveError::DnsLookup(format!("lookup {name}: {e}")))?;
+
+            let mut strings = Vec::new();
+            for record in records.answers() {
+                let RData::TXT(txt) = &record.data else {
+                    continue;
+                };
+                strings.extend(
+                    txt.txt_data
+                        .iter()
+                        .map(|bytes| String::from_utf8_lossy(bytes).into_owned()),
+                );
+            }
+            Ok(strings)
+        })
+    }
+}
@@ -7,7 +7,7 @@ use libp2p::multiaddr::Protocol;
 use tracing::trace;
 
 /// Classification of IP address scope.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AddressScope {
     /// Loopback addresses (127.0.0.0/8, ::1)
     Loopback,
@@ -208,6 +208,15 @@ impl<Id: Clone + Eq + Hash + Debug, R: Clone + Default + Send + Sync + 'static>
             .map(|s| s.peer_id())
     }
 
+    /// Resolve many IDs under one read lock, positionally: entry `i` is the
+    /// `PeerId` for `ids[i]`, or `None` if that ID is not tracked.
+    pub fn resolve_peer_ids(&self, ids: &[Id]) -> Vec<Option<PeerId>> {
+        let maps = self.maps.read();
+        ids.iter()
+            .map(|id| maps.by_key.get(&id.clone().into()).map(|s| s.peer_id()))
+            .collect()
+    }
+
     /// Register an outbound connection directly in Connected state.
     ///
     /// Used after dial tracking resolves externally (e.g., by a DialTracker).
@@ -615,6 +624,21 @@ mod tests {
         assert!(r.resolve_id(&p).is_none());
     }
 
+    #[test]
+    fn test_resolve_peer_ids_is_positional() {
+        let r = registry();
+        r.connected_outbound(peer(1), conn(1), Some(TestId(1)), Instant::now(), ());
+        r.connected_outbound(peer(2), conn(2), Some(TestId(2)), Instant::now(), ());
+        r.activate(peer(2), conn(2), TestId(2));
+
+        let resolved = r.resolve_peer_ids(&[TestId(2), TestId(9), TestId(1), TestId(2)]);
+        assert_eq!(
+            resolved,
+            vec![Some(peer(2)), None, Some(peer(1)), Some(peer(2))]
+        );
+        assert!(r.resolve_peer_ids(&[]).is_empty());
+    }
+
     #[test]
     fn test_activate_new_peer() {
         let r = registry();
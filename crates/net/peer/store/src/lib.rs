@@ -8,6 +8,8 @@
 
 pub mod error;
 
+use std::time::Duration;
+
 use auto_impl::auto_impl;
 use error::StoreError;
 
@@ -25,6 +27,35 @@ pub trait PeerSnapshotStore<R>: Send + Sync {
     fn store(&self, records: &[R]) -> Result<(), StoreError>;
 }
 
+/// A persisted record stamped with when its peer was last seen.
+pub trait LastSeen {
+    /// Unix seconds the peer was last seen healthy.
+    fn last_seen(&self) -> u64;
+}
+
+/// TTL expiry over any [`PeerSnapshotStore`] whose records carry a
+/// [`LastSeen`] stamp.
+pub trait PeerSnapshotStoreExt<R: LastSeen>: PeerSnapshotStore<R> {
+    /// Drop every record last seen more than `max_age` before `now_unix_secs`,
+    /// rewriting the snapshot only when something expired. Returns the number
+    /// of records removed.
+    fn expire_stale(&self, max_age: Duration, now_unix_secs: u64) -> Result<usize, StoreError> {
+        let records = self.load()?;
+        let total = records.len();
+        let fresh: Vec<R> = records
+            .into_iter()
+            .filter(|record| now_unix_secs.saturating_sub(record.last_seen()) <= max_age.as_secs())
+            .collect();
+        let expired = total - fresh.len();
+        if expired > 0 {
+            self.store(&fresh)?;
+        }
+        Ok(expired)
+    }
+}
+
+impl<R: LastSeen, S: PeerSnapshotStore<R> + ?Sized> PeerSnapshotStoreExt<R> for S {}
+
 #[cfg(any(test, feature = "test-utils"))]
 mod memory;
 
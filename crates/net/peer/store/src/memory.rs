@@ -56,6 +56,33 @@ mod tests {
         assert_eq!(store.load().unwrap(), vec![4]);
     }
 
+    #[derive(Debug, Clone, PartialEq)]
+    struct Seen(u64);
+
+    impl crate::LastSeen for Seen {
+        fn last_seen(&self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_expire_stale_removes_only_old_records() {
+        use std::time::Duration;
+
+        use crate::PeerSnapshotStoreExt;
+
+        let now = 10_000_000;
+        let week = Duration::from_secs(7 * 24 * 3600);
+        let store = MemoryPeerStore::new();
+        store
+            .store(&[Seen(now - 3 * week.as_secs()), Seen(now - 60)])
+            .unwrap();
+
+        assert_eq!(store.expire_stale(week, now).unwrap(), 1);
+        assert_eq!(store.load().unwrap(), vec![Seen(now - 60)]);
+        assert_eq!(store.expire_stale(week, now).unwrap(), 0);
+    }
+
     #[test]
     fn test_store_empty_clears() {
         let store = MemoryPeerStore::<u32>::new();
@@ -162,9 +162,10 @@ impl<A> WithLaunchContext<A> {
         histogram_buckets: &[vertex_observability::HistogramBucketConfig],
     ) -> eyre::Result<Self> {
         let recorder = if let Some(ref cfg) = config {
-            let recorder = vertex_observability::install_prometheus_recorder_with_buckets(
+            let recorder = vertex_observability::install_prometheus_recorder_with_labels(
                 cfg.prefix(),
                 histogram_buckets,
+                cfg.global_labels(),
             )?;
             recorder.spawn_upkeep(&self.ctx.executor, cfg.upkeep_interval_secs());
             Some(std::sync::Arc::new(recorder))
@@ -1,7 +1,7 @@
 //! Generic CLI infrastructure for Vertex nodes.
 
 pub use vertex_node_builder::LaunchContext;
-pub use vertex_node_core::args::{InfraArgs, LogArgs, TracingArgs};
+pub use vertex_node_core::args::{GrpcEndpointArgs, InfraArgs, LogArgs, TracingArgs};
 
 use std::future::Future;
 use std::time::Duration;
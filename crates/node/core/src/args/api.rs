@@ -46,3 +46,23 @@ impl NodeRpcConfig for ApiArgs {
         self.grpc_port
     }
 }
+
+/// Endpoint of a running node's gRPC server, for subcommands that query it.
+#[derive(Debug, Args, Clone)]
+#[command(next_help_heading = "API")]
+pub struct GrpcEndpointArgs {
+    /// gRPC server address of the running node.
+    #[arg(long = "grpc.addr", default_value = DEFAULT_LOCALHOST_ADDR)]
+    pub grpc_addr: String,
+
+    /// gRPC server port of the running node.
+    #[arg(long = "grpc.port", default_value_t = DEFAULT_GRPC_PORT)]
+    pub grpc_port: u16,
+}
+
+impl GrpcEndpointArgs {
+    /// The `http://` URI a tonic client connects to.
+    pub fn endpoint(&self) -> String {
+        format!("http://{}:{}", self.grpc_addr, self.grpc_port)
+    }
+}
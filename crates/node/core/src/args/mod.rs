@@ -61,7 +61,7 @@ mod metrics;
 mod observability;
 mod tracing;
 
-pub use api::ApiArgs;
+pub use api::{ApiArgs, GrpcEndpointArgs};
 pub use database::{DatabaseArgs, DatabaseConfig};
 pub use datadir::DataDirArgs;
 pub use log::LogArgs;
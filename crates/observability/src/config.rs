@@ -101,6 +101,7 @@ pub struct MetricsServerConfig {
     addr: SocketAddr,
     prefix: String,
     upkeep_interval_secs: u64,
+    global_labels: Vec<(String, String)>,
 }
 
 impl MetricsServerConfig {
@@ -110,9 +111,18 @@ impl MetricsServerConfig {
             addr,
             prefix: prefix.into(),
             upkeep_interval_secs,
+            global_labels: Vec::new(),
         }
     }
 
+    /// Attach `key="value"` to every exported metric, so one scraper can
+    /// tell several nodes apart.
+    #[must_use]
+    pub fn with_global_label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.global_labels.push((key.into(), value.into()));
+        self
+    }
+
     pub fn addr(&self) -> SocketAddr {
         self.addr
     }
@@ -124,4 +134,9 @@ impl MetricsServerConfig {
     pub fn upkeep_interval_secs(&self) -> u64 {
         self.upkeep_interval_secs
     }
+
+    /// Labels applied to every exported metric.
+    pub fn global_labels(&self) -> &[(String, String)] {
+        &self.global_labels
+    }
 }
@@ -53,8 +53,8 @@ pub use metrics::{
 #[cfg(feature = "prometheus")]
 pub use metrics::{
     HistogramRegistry, Hook, Hooks, HooksBuilder, PrometheusRecorder, install_prometheus_recorder,
-    install_prometheus_recorder_with_buckets, install_prometheus_recorder_with_prefix,
-    process_metrics_hook,
+    install_prometheus_recorder_with_buckets, install_prometheus_recorder_with_labels,
+    install_prometheus_recorder_with_prefix, process_metrics_hook,
 };
 // Axum metrics HTTP server.
 #[cfg(feature = "http-server")]
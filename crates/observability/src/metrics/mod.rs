@@ -34,7 +34,8 @@ pub use process::process_metrics_hook;
 #[cfg(feature = "prometheus")]
 pub use recorder::{
     HistogramRegistry, PrometheusRecorder, install_prometheus_recorder,
-    install_prometheus_recorder_with_buckets, install_prometheus_recorder_with_prefix,
+    install_prometheus_recorder_with_buckets, install_prometheus_recorder_with_labels,
+    install_prometheus_recorder_with_prefix,
 };
 #[cfg(feature = "http-server")]
 pub use server::MetricsServer;
@@ -29,16 +29,44 @@ pub fn install_prometheus_recorder_with_prefix(prefix: &str) -> eyre::Result<Pro
 pub fn install_prometheus_recorder_with_buckets(
     prefix: &str,
     histogram_buckets: &[HistogramBucketConfig],
+) -> eyre::Result<PrometheusRecorder> {
+    install_prometheus_recorder_with_labels(prefix, histogram_buckets, &[])
+}
+
+/// Install the prometheus recorder with specific prefix, custom histogram
+/// buckets, and labels attached to every exported metric.
+pub fn install_prometheus_recorder_with_labels(
+    prefix: &str,
+    histogram_buckets: &[HistogramBucketConfig],
+    global_labels: &[(String, String)],
 ) -> eyre::Result<PrometheusRecorder> {
     match PROMETHEUS_RECORDER.get() {
         Some(recorder) => Ok(recorder.clone()),
         None => {
-            let recorder = PrometheusRecorder::install(prefix, histogram_buckets)?;
+            let recorder = PrometheusRecorder::install(prefix, histogram_buckets, global_labels)?;
             Ok(PROMETHEUS_RECORDER.get_or_init(|| recorder).clone())
         }
     }
 }
 
+/// Build an uninstalled recorder with the histogram buckets and global labels
+/// applied.
+fn build_recorder(
+    histogram_buckets: &[HistogramBucketConfig],
+    global_labels: &[(String, String)],
+) -> eyre::Result<metrics_exporter_prometheus::PrometheusRecorder> {
+    // Note: Buckets are set BEFORE the prefix layer, so use unprefixed names.
+    let mut builder = PrometheusBuilder::new();
+    for config in histogram_buckets {
+        builder = builder
+            .set_buckets_for_metric(Matcher::Suffix(config.suffix.to_string()), config.buckets)?;
+    }
+    for (key, value) in global_labels {
+        builder = builder.add_global_label(key, value);
+    }
+    Ok(builder.build_recorder())
+}
+
 /// Handle to the prometheus metrics recorder.
 #[derive(Clone)]
 pub struct PrometheusRecorder {
@@ -58,17 +86,12 @@ impl std::fmt::Debug for PrometheusRecorder {
 }
 
 impl PrometheusRecorder {
-    fn install(prefix: &str, histogram_buckets: &[HistogramBucketConfig]) -> eyre::Result<Self> {
-        // Configure histogram buckets for specific metrics.
-        // Note: Buckets are set BEFORE the prefix layer, so use unprefixed names.
-        let mut builder = PrometheusBuilder::new();
-        for config in histogram_buckets {
-            builder = builder.set_buckets_for_metric(
-                Matcher::Suffix(config.suffix.to_string()),
-                config.buckets,
-            )?;
-        }
-        let recorder = builder.build_recorder();
+    fn install(
+        prefix: &str,
+        histogram_buckets: &[HistogramBucketConfig],
+        global_labels: &[(String, String)],
+    ) -> eyre::Result<Self> {
+        let recorder = build_recorder(histogram_buckets, global_labels)?;
 
         let handle = recorder.handle();
 
@@ -84,7 +107,7 @@ impl PrometheusRecorder {
 
     /// Install recorder using configuration.
     pub fn install_with_config(config: &MetricsServerConfig) -> eyre::Result<Self> {
-        install_prometheus_recorder_with_buckets(config.prefix(), &[])
+        install_prometheus_recorder_with_labels(config.prefix(), &[], config.global_labels())
     }
 
     pub fn handle(&self) -> &PrometheusHandle {
@@ -147,7 +170,7 @@ impl Default for HistogramRegistry {
 
 #[cfg(test)]
 mod tests {
-    #![allow(clippy::indexing_slicing)]
+    #![allow(clippy::indexing_slicing, clippy::unwrap_used)]
     use super::*;
 
     #[test]
@@ -171,6 +194,32 @@ mod tests {
         assert_eq!(result[1].suffix, "beta");
     }
 
+    #[test]
+    fn global_labels_tag_every_exported_family() {
+        let labels = [
+            ("network_id".to_string(), "10".to_string()),
+            ("node_type".to_string(), "storer".to_string()),
+        ];
+        let recorder = build_recorder(&[], &labels).unwrap();
+        let handle = recorder.handle();
+
+        metrics::with_local_recorder(&recorder, || {
+            metrics::gauge!("connected_peers").set(3.0);
+            metrics::counter!("chunks_pushed_total", "outcome" => "stored").increment(1);
+        });
+
+        let rendered = handle.render();
+        let samples: Vec<&str> = rendered
+            .lines()
+            .filter(|line| !line.starts_with('#') && !line.is_empty())
+            .collect();
+        assert_eq!(samples.len(), 2, "{rendered}");
+        for sample in samples {
+            assert!(sample.contains(r#"network_id="10""#), "{sample}");
+            assert!(sample.contains(r#"node_type="storer""#), "{sample}");
+        }
+    }
+
     #[test]
     #[should_panic(expected = "duplicate histogram suffix")]
     fn registry_panics_on_duplicate() {
@@ -5,9 +5,14 @@
 mod error;
 mod table;
 mod traits;
+mod versioned;
 
 mod codecs;
 
 pub use error::*;
 pub use table::*;
 pub use traits::*;
+pub use versioned::{
+    FormatError, HEADER_LEN, Unversioned, Versioned, VersionedFormat, decode_versioned,
+    encode_versioned,
+};
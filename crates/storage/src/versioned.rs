@@ -0,0 +1,349 @@
+//! Versioned envelope for persisted records.
+//!
+//! A record stored through [`Versioned`] is written as a one-byte magic tag
+//! naming its format, a one-byte format version, then the postcard body. On
+//! load the tag is checked, the current version decodes directly, an older one
+//! goes through [`VersionedFormat::migrate`], and anything else is refused with
+//! a [`FormatError`] instead of being misparsed as the current layout.
+//!
+//! Rows written before a table adopted the envelope carry no header. They are
+//! read through [`Unversioned`], a second table view over the same name, and
+//! decoded by [`VersionedFormat::deserialize_unversioned`].
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{self, DeserializeOwned, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use strum::IntoStaticStr;
+
+/// Bytes ahead of the body: magic tag and version.
+pub const HEADER_LEN: usize = 2;
+
+/// A record layout with a stable tag and a version bumped on every change.
+pub trait VersionedFormat: Serialize + DeserializeOwned {
+    /// Tag identifying this format; distinct per record kind.
+    const MAGIC: u8;
+
+    /// Version this build writes.
+    const VERSION: u8;
+
+    /// Decode a body written at an older `version`.
+    ///
+    /// The default knows no older layouts and refuses them.
+    fn migrate(version: u8, body: &[u8]) -> Result<Self, FormatError> {
+        let _ = body;
+        Err(FormatError::UnsupportedVersion {
+            found: version,
+            current: Self::VERSION,
+        })
+    }
+
+    /// Decode a row written before the envelope: the bare serde form of the
+    /// layout the table held then, with no header.
+    ///
+    /// The default refuses it; a format whose rows predate the envelope
+    /// overrides it.
+    fn deserialize_unversioned<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let _ = deserializer;
+        Err(de::Error::custom("record predates the versioned envelope"))
+    }
+}
+
+/// Why a versioned record could not be read or written.
+#[derive(Debug, thiserror::Error, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum FormatError {
+    /// Shorter than the header.
+    #[error("record truncated: {0} bytes, header needs {HEADER_LEN}")]
+    Truncated(usize),
+    /// The tag names another format, or the record predates the envelope.
+    #[error("wrong format tag {found:#04x}, expected {expected:#04x}")]
+    BadMagic { expected: u8, found: u8 },
+    /// Written by a newer build, or an older layout with no migration.
+    #[error("unsupported format version {found} (this build writes {current})")]
+    UnsupportedVersion { found: u8, current: u8 },
+    /// The body does not decode as the layout its version names.
+    #[error("malformed body: {0}")]
+    Body(String),
+}
+
+/// Encode `record` behind its format header.
+pub fn encode_versioned<T: VersionedFormat>(record: &T) -> Result<Vec<u8>, FormatError> {
+    let mut out = vec![T::MAGIC, T::VERSION];
+    let body = postcard::to_allocvec(record).map_err(|e| FormatError::Body(e.to_string()))?;
+    out.extend_from_slice(&body);
+    Ok(out)
+}
+
+/// Decode a record written by [`encode_versioned`], migrating older versions.
+pub fn decode_versioned<T: VersionedFormat>(bytes: &[u8]) -> Result<T, FormatError> {
+    let [magic, version, body @ ..] = bytes else {
+        return Err(FormatError::Truncated(bytes.len()));
+    };
+    if *magic != T::MAGIC {
+        return Err(FormatError::BadMagic {
+            expected: T::MAGIC,
+            found: *magic,
+        });
+    }
+    match (*version).cmp(&T::VERSION) {
+        std::cmp::Ordering::Equal => {
+            postcard::from_bytes(body).map_err(|e| FormatError::Body(e.to_string()))
+        }
+        std::cmp::Ordering::Less => T::migrate(*version, body),
+        std::cmp::Ordering::Greater => Err(FormatError::UnsupportedVersion {
+            found: *version,
+            current: T::VERSION,
+        }),
+    }
+}
+
+/// Table value wrapper persisting `T` through the versioned envelope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Versioned<T>(pub T);
+
+impl<T> Versioned<T> {
+    /// The wrapped record.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: VersionedFormat> Serialize for Versioned<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = encode_versioned(&self.0).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+impl<'de, T: VersionedFormat> Deserialize<'de> for Versioned<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_bytes(EnvelopeVisitor(PhantomData))
+    }
+}
+
+/// Read-only table value for rows written before the versioned envelope.
+///
+/// Declare a second table over the same name with this value type and fall
+/// back to it when the [`Versioned`] view fails to decode; the next full write
+/// replaces the rows with enveloped ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unversioned<T>(pub T);
+
+impl<T> Unversioned<T> {
+    /// The wrapped record.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Serialize for Unversioned<T> {
+    fn serialize<S: Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+        Err(serde::ser::Error::custom(
+            "unversioned rows are read-only; write through Versioned",
+        ))
+    }
+}
+
+impl<'de, T: VersionedFormat> Deserialize<'de> for Unversioned<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize_unversioned(deserializer).map(Unversioned)
+    }
+}
+
+struct EnvelopeVisitor<T>(PhantomData<T>);
+
+impl<'de, T: VersionedFormat> Visitor<'de> for EnvelopeVisitor<T> {
+    type Value = Versioned<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a versioned record")
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        decode_versioned(bytes).map(Versioned).map_err(E::custom)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+        self.visit_bytes(&bytes)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const MAGIC: u8 = 0x5e;
+
+    /// The layout as first shipped.
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct RecordV1 {
+        balance: u64,
+    }
+
+    impl VersionedFormat for RecordV1 {
+        const MAGIC: u8 = MAGIC;
+        const VERSION: u8 = 1;
+    }
+
+    /// The layout after a version bump that knows how to read v1.
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct RecordV2 {
+        balance: u64,
+        settled: bool,
+    }
+
+    impl VersionedFormat for RecordV2 {
+        const MAGIC: u8 = MAGIC;
+        const VERSION: u8 = 2;
+
+        fn deserialize_unversioned<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            RecordV1::deserialize(deserializer).map(|old| Self {
+                balance: old.balance,
+                settled: false,
+            })
+        }
+
+        fn migrate(version: u8, body: &[u8]) -> Result<Self, FormatError> {
+            match version {
+                1 => {
+                    let old: RecordV1 =
+                        postcard::from_bytes(body).map_err(|e| FormatError::Body(e.to_string()))?;
+                    Ok(Self {
+                        balance: old.balance,
+                        settled: false,
+                    })
+                }
+                other => Err(FormatError::UnsupportedVersion {
+                    found: other,
+                    current: Self::VERSION,
+                }),
+            }
+        }
+    }
+
+    /// The same bump with no migration path.
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct StrictV2 {
+        balance: u64,
+        settled: bool,
+    }
+
+    impl VersionedFormat for StrictV2 {
+        const MAGIC: u8 = MAGIC;
+        const VERSION: u8 = 2;
+    }
+
+    #[test]
+    fn current_version_roundtrips() {
+        let record = RecordV2 {
+            balance: 42,
+            settled: true,
+        };
+        let bytes = encode_versioned(&record).unwrap();
+        assert_eq!(bytes.get(..HEADER_LEN), Some(&[MAGIC, 2][..]));
+        assert_eq!(decode_versioned::<RecordV2>(&bytes).unwrap(), record);
+    }
+
+    #[test]
+    fn older_version_is_migrated() {
+        let v1 = encode_versioned(&RecordV1 { balance: 7 }).unwrap();
+        assert_eq!(
+            decode_versioned::<RecordV2>(&v1).unwrap(),
+            RecordV2 {
+                balance: 7,
+                settled: false,
+            }
+        );
+    }
+
+    #[test]
+    fn older_version_without_migration_is_refused() {
+        let v1 = encode_versioned(&RecordV1 { balance: 7 }).unwrap();
+        assert!(matches!(
+            decode_versioned::<StrictV2>(&v1),
+            Err(FormatError::UnsupportedVersion {
+                found: 1,
+                current: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn newer_version_is_refused() {
+        let v2 = encode_versioned(&RecordV2 {
+            balance: 7,
+            settled: true,
+        })
+        .unwrap();
+        assert!(matches!(
+            decode_versioned::<RecordV1>(&v2),
+            Err(FormatError::UnsupportedVersion {
+                found: 2,
+                current: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn foreign_or_unversioned_bytes_are_refused() {
+        // A record from before the envelope: a bare postcard body.
+        let raw = postcard::to_allocvec(&RecordV1 { balance: 300 }).unwrap();
+        assert!(matches!(
+            decode_versioned::<RecordV1>(&raw),
+            Err(FormatError::BadMagic {
+                expected: MAGIC,
+                ..
+            })
+        ));
+        assert!(matches!(
+            decode_versioned::<RecordV1>(&[MAGIC]),
+            Err(FormatError::Truncated(1))
+        ));
+    }
+
+    #[test]
+    fn unversioned_row_decodes_through_the_override() {
+        // A row from before the envelope: the bare postcard body.
+        let raw = postcard::to_allocvec(&RecordV1 { balance: 300 }).unwrap();
+        let legacy: Unversioned<RecordV2> = postcard::from_bytes(&raw).unwrap();
+        assert_eq!(
+            legacy.into_inner(),
+            RecordV2 {
+                balance: 300,
+                settled: false,
+            }
+        );
+        assert!(postcard::from_bytes::<Unversioned<StrictV2>>(&raw).is_err());
+        assert!(
+            postcard::to_allocvec(&Unversioned(StrictV2 {
+                balance: 1,
+                settled: true,
+            }))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn wrapper_roundtrips_through_postcard() {
+        let stored = postcard::to_allocvec(&Versioned(RecordV1 { balance: 9 })).unwrap();
+        let back: Versioned<RecordV2> = postcard::from_bytes(&stored).unwrap();
+        assert_eq!(
+            back.into_inner(),
+            RecordV2 {
+                balance: 9,
+                settled: false,
+            }
+        );
+    }
+}
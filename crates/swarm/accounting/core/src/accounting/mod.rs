@@ -16,20 +16,24 @@
 //! - [`PeerState`] - Atomic per-peer balance counters
 //! - [`Accounting`] - Factory with pluggable settlement providers
 //! - [`Reservation`] - Typed receive/provide reservation legs
+//! - [`PeerBalanceSnapshot`] - Persisted balances for crash recovery
 
 mod error;
 mod peer;
 mod reservation;
+mod snapshot;
 
 pub use error::AccountingError;
 pub use peer::PeerState;
 pub use reservation::{Provide, Receive, Reservation};
+pub use snapshot::{DbBalanceSnapshotStore, PeerBalanceSnapshot};
 
 use alloc::vec::Vec;
 use parking_lot::RwLock;
 use rustc_hash::FxBuildHasher;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use vertex_swarm_api::{
     AdmissionControl, Au, Debt, Direction, Ledger, LedgerSnapshot, SwarmAccountingConfig,
@@ -39,6 +43,9 @@ use vertex_swarm_primitives::OverlayAddress;
 
 use vertex_swarm_api::SwarmSettlementProvider;
 
+use crate::rate::{BandwidthLimiter, BandwidthRateLimits, SERVED_CHUNK_BYTES};
+use crate::reputation::ReputationScaling;
+
 /// Per-peer accounting with pluggable settlement providers.
 ///
 /// Manages balances and delegates settlement to configured providers.
@@ -50,6 +57,8 @@ pub struct Accounting<C, I: SwarmIdentity> {
     // Overlay keys are uniformly random, so a fast non-DoS hasher is safe here
     // and removes SipHash from the per-candidate selection hot path.
     peers: RwLock<HashMap<OverlayAddress, Arc<PeerState>, FxBuildHasher>>,
+    rate_limit: Option<BandwidthLimiter>,
+    reputation: Option<ReputationScaling>,
 }
 
 impl<C: SwarmAccountingConfig, I: SwarmIdentity> Accounting<C, I> {
@@ -60,6 +69,8 @@ impl<C: SwarmAccountingConfig, I: SwarmIdentity> Accounting<C, I> {
             identity,
             providers: Arc::from(Vec::new()),
             peers: RwLock::new(HashMap::default()),
+            rate_limit: None,
+            reputation: None,
         }
     }
 
@@ -77,9 +88,83 @@ impl<C: SwarmAccountingConfig, I: SwarmIdentity> Accounting<C, I> {
             identity,
             providers: Arc::from(providers),
             peers: RwLock::new(HashMap::default()),
+            rate_limit: None,
+            reputation: None,
+        }
+    }
+
+    /// Cap the byte rate served to each peer, independent of its balance.
+    pub fn with_rate_limits(mut self, limits: BandwidthRateLimits) -> Self {
+        self.rate_limit = BandwidthLimiter::new(limits);
+        self
+    }
+
+    /// Scale the credit extended to each peer by its reputation, as reported
+    /// through [`Self::observe_score`]. A disabled scaling leaves every peer at
+    /// the configured thresholds.
+    pub fn with_reputation_scaling(mut self, scaling: ReputationScaling) -> Self {
+        self.reputation = (!scaling.is_disabled()).then_some(scaling);
+        self
+    }
+
+    /// The active reputation scaling, `None` when thresholds are fixed.
+    pub fn reputation_scaling(&self) -> Option<ReputationScaling> {
+        self.reputation
+    }
+
+    /// Rescale the credit we extend to `peer` for its current peer score. A
+    /// no-op without reputation scaling.
+    ///
+    /// Only the serve side moves: [`Accounting::prepare_provide`] and the
+    /// debt-watch read the scaled lines. The line capping our own debt stays
+    /// at the configured value, since that is where the peer drops us whatever
+    /// we think of it. Both credit thresholds derive from the configured
+    /// values, never from the previous scaled ones, so repeated observations
+    /// do not compound. A peer already past its tightened line is caught by
+    /// the next provide or debt-watch tick, like any other peer over the line.
+    pub fn observe_score(&self, peer: &OverlayAddress, score: f64) {
+        let Some(scaling) = self.reputation else {
+            return;
+        };
+        self.get_or_create_peer(*peer).set_credit_thresholds(
+            scaling.scale(self.config.payment_threshold(), score),
+            scaling.scale(self.config.disconnect_threshold(), score),
+        );
+    }
+
+    /// Move `peer` onto (or off) the neighbour serve-rate bucket. A no-op
+    /// without rate limits.
+    pub fn set_neighbour(&self, peer: OverlayAddress, neighbour: bool) {
+        if let Some(limiter) = &self.rate_limit {
+            limiter.set_neighbour(peer, neighbour);
         }
     }
 
+    /// Freeze `peer`'s accounting pending a settlement dispute.
+    ///
+    /// Both reservation gates refuse the peer with
+    /// [`AccountingError::PeerFrozen`] and its balance stops moving, settlement
+    /// included, but nothing here closes the connection, so the peer stays
+    /// reachable for investigation.
+    pub fn freeze_peer(&self, peer: &OverlayAddress) {
+        self.get_or_create_peer(*peer).set_frozen(true);
+    }
+
+    /// Lift a freeze set by [`Self::freeze_peer`].
+    pub fn unfreeze_peer(&self, peer: &OverlayAddress) {
+        if let Some(state) = self.peers.read().get(peer) {
+            state.set_frozen(false);
+        }
+    }
+
+    /// Whether `peer`'s accounting is frozen.
+    pub fn is_frozen(&self, peer: &OverlayAddress) -> bool {
+        self.peers
+            .read()
+            .get(peer)
+            .is_some_and(|state| state.is_frozen())
+    }
+
     /// Returns the names of the active settlement providers.
     pub fn provider_names(&self) -> Vec<&str> {
         self.providers.iter().map(|p| p.name()).collect()
@@ -98,11 +183,14 @@ impl<C: SwarmAccountingConfig, I: SwarmIdentity> Accounting<C, I> {
         price: Au,
         _originated: bool,
     ) -> Result<Reservation<Receive>, AccountingError> {
+        if self.is_frozen(&peer) {
+            return Err(AccountingError::PeerFrozen(peer));
+        }
         if !AdmissionControl::admit(self, &peer, price).admits() {
             return Err(AccountingError::DisconnectThreshold {
                 peer,
                 balance: Ledger::balance(self, &peer),
-                threshold: self.config.disconnect_threshold(),
+                threshold: Ledger::disconnect_line(self, &peer),
             });
         }
 
@@ -120,14 +208,21 @@ impl<C: SwarmAccountingConfig, I: SwarmIdentity> Accounting<C, I> {
     /// per episode; the gate restores serve headroom only once the peer settles.
     /// The receive side keeps its own disconnect-threshold guard in
     /// [`Accounting::prepare_receive`].
+    ///
+    /// With rate limits configured, a provide that passes the balance gate is
+    /// also charged one chunk against the peer's byte-rate bucket and refused
+    /// with [`AccountingError::RateLimited`] when the bucket is empty.
     pub fn prepare_provide(
         &self,
         peer: OverlayAddress,
         price: Au,
     ) -> Result<Reservation<Provide>, AccountingError> {
         let state = self.get_or_create_peer(peer);
+        if state.is_frozen() {
+            return Err(AccountingError::PeerFrozen(peer));
+        }
 
-        let payment_threshold = state.payment_threshold();
+        let payment_threshold = state.credit_payment_threshold();
         // Projected debt the peer would owe us once this provide commits. The
         // ghost balance counts too: refused deliveries were served in full and
         // consume the same headroom, so a repeat refuser starves instead of
@@ -145,10 +240,29 @@ impl<C: SwarmAccountingConfig, I: SwarmIdentity> Accounting<C, I> {
             });
         }
 
+        if let Some(limiter) = &self.rate_limit {
+            limiter.try_consume(peer, SERVED_CHUNK_BYTES)?;
+        }
+
         state.add_shadow_reserved(price);
         Ok(Reservation::new(state, price))
     }
 
+    /// Peers that have owed us past the disconnect threshold for the whole of
+    /// `grace`, as of `now_unix_secs`.
+    ///
+    /// Called periodically; each call advances every peer's grace timer (see
+    /// [`PeerState::disconnect_due`]), so a burst that settles before the grace
+    /// runs out never surfaces here.
+    pub fn overdue_peers(&self, now_unix_secs: u64, grace: Duration) -> Vec<OverlayAddress> {
+        self.peers
+            .read()
+            .iter()
+            .filter(|(_, state)| state.disconnect_due(now_unix_secs, grace))
+            .map(|(peer, _)| *peer)
+            .collect()
+    }
+
     /// Get or create peer state (double-checked locking).
     pub fn get_or_create_peer(&self, peer: OverlayAddress) -> Arc<PeerState> {
         // Fast path: read lock
@@ -186,8 +300,6 @@ impl<C: SwarmAccountingConfig, I: SwarmIdentity> SwarmBandwidthAccounting for Ac
             peer,
             state,
             providers: Arc::clone(&self.providers),
-            disconnect_threshold: self.config.disconnect_threshold(),
-            payment_threshold: self.config.payment_threshold(),
         }
     }
 
@@ -197,6 +309,9 @@ impl<C: SwarmAccountingConfig, I: SwarmIdentity> SwarmBandwidthAccounting for Ac
 
     fn remove_peer(&self, peer: &OverlayAddress) {
         self.peers.write().remove(peer);
+        if let Some(limiter) = &self.rate_limit {
+            limiter.remove_peer(peer);
+        }
     }
 
     fn prepare_receive(
@@ -288,8 +403,6 @@ pub struct AccountingPeerHandle {
     peer: OverlayAddress,
     state: Arc<PeerState>,
     providers: Arc<[Box<dyn SwarmSettlementProvider>]>,
-    disconnect_threshold: Au,
-    payment_threshold: Au,
 }
 
 impl AccountingPeerHandle {
@@ -300,12 +413,12 @@ impl AccountingPeerHandle {
 
     /// Get the payment threshold in AU.
     pub fn payment_threshold(&self) -> Au {
-        self.payment_threshold
+        self.state.payment_threshold()
     }
 
     /// Get the disconnect threshold in AU.
     pub fn disconnect_threshold(&self) -> Au {
-        self.disconnect_threshold
+        self.state.disconnect_threshold()
     }
 
     /// Call `settle()` on providers in order until debt is below threshold.
@@ -319,11 +432,15 @@ impl AccountingPeerHandle {
             // threshold. Reasoning in `Debt` keeps the comparison sign-safe (both
             // sides non-negative); each provider re-reads `balance()` internally,
             // so the fresh committed debt drives the break.
-            if !Debt::committed(self.state.balance()).exceeds(self.payment_threshold) {
+            if !Debt::committed(self.state.balance()).exceeds(self.payment_threshold()) {
                 break;
             }
         }
 
+        if total > Au::ZERO {
+            self.state
+                .set_last_settled(vertex_util_runtime::time::now_unix_secs());
+        }
         Ok(total)
     }
 }
@@ -378,6 +495,34 @@ mod tests {
         assert_eq!(handle.balance(), au(500));
     }
 
+    #[test]
+    fn test_overdue_peers_wait_out_the_disconnect_grace() {
+        use vertex_swarm_test_utils::test_overlay;
+
+        let accounting = test_accounting();
+        let grace = Duration::from_secs(30);
+        let settles = test_overlay(1);
+        let lingers = test_overlay(2);
+        let over = accounting
+            .get_or_create_peer(settles)
+            .disconnect_threshold()
+            .saturating_add(au(1));
+        accounting.get_or_create_peer(settles).add_balance(over);
+        accounting.get_or_create_peer(lingers).add_balance(over);
+
+        // Crossing starts the timers; nobody is dropped yet.
+        let t0 = 1_000;
+        assert!(accounting.overdue_peers(t0, grace).is_empty());
+
+        // One peer settles inside the grace window.
+        accounting.get_or_create_peer(settles).add_balance(-over);
+        assert!(accounting.overdue_peers(t0 + 10, grace).is_empty());
+
+        // Once the grace elapses only the peer still over the line is due.
+        assert_eq!(accounting.overdue_peers(t0 + 30, grace), vec![lingers]);
+        assert!(accounting.overdue_peers(t0 + 31, grace).is_empty());
+    }
+
     #[test]
     fn test_prepare_receive() {
         let accounting = test_accounting();
@@ -660,6 +805,46 @@ mod tests {
         assert_eq!(handle.balance(), au(1000));
     }
 
+    #[test]
+    fn frozen_peer_is_refused_and_its_balance_holds_until_unfrozen() {
+        let accounting = test_accounting();
+        let peer = test_peer();
+        let handle = accounting.for_peer(peer);
+        handle.record(au(100), Direction::Upload);
+
+        // A reservation taken before the freeze commits nothing once frozen.
+        let in_flight = accounting
+            .prepare_provide(peer, au(50))
+            .expect("unfrozen peer is served");
+        accounting.freeze_peer(&peer);
+        assert!(accounting.is_frozen(&peer));
+        in_flight.apply();
+        assert!(matches!(
+            accounting.prepare_provide(peer, au(1)),
+            Err(AccountingError::PeerFrozen(frozen)) if frozen == peer
+        ));
+        assert!(matches!(
+            accounting.prepare_receive(peer, au(1), true),
+            Err(AccountingError::PeerFrozen(_))
+        ));
+        handle.record(au(500), Direction::Download);
+        assert_eq!(handle.balance(), au(100), "a frozen balance does not move");
+        assert_eq!(
+            accounting
+                .get_or_create_peer(peer)
+                .shadow_reserved_balance(),
+            Au::ZERO
+        );
+
+        accounting.unfreeze_peer(&peer);
+        assert!(!accounting.is_frozen(&peer));
+        accounting
+            .prepare_receive(peer, au(40), true)
+            .expect("unfrozen peer is served again")
+            .apply();
+        assert_eq!(handle.balance(), au(60));
+    }
+
     #[test]
     fn test_provide_refusal_counts_outstanding_reservations() {
         let accounting = Accounting::new(small_config(), test_identity());
@@ -758,6 +943,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn low_reputation_tightens_the_credit_we_extend() {
+        // Payment 1000, disconnect 1250 before scaling. The default scaling
+        // halves a warn-level peer's credit (payment 500, disconnect 625) and
+        // gives a peer at the mirrored score half again (payment 1500,
+        // disconnect 1875).
+        use vertex_swarm_test_utils::test_overlay;
+
+        let accounting = Accounting::new(small_config(), test_identity())
+            .with_reputation_scaling(ReputationScaling::default());
+        let distrusted = test_overlay(1);
+        let trusted = test_overlay(2);
+        accounting.observe_score(&distrusted, -50.0);
+        accounting.observe_score(&trusted, 50.0);
+
+        let low = accounting.get_or_create_peer(distrusted);
+        assert_eq!(low.credit_payment_threshold(), au(500));
+        assert_eq!(low.credit_disconnect_threshold(), au(625));
+
+        // The same 800 AU projected debt to us is refused for the distrusted
+        // peer and served for the trusted one.
+        for peer in [distrusted, trusted] {
+            accounting.for_peer(peer).record(au(700), Direction::Upload);
+        }
+        assert!(matches!(
+            accounting.prepare_provide(distrusted, au(100)),
+            Err(AccountingError::PaymentThreshold { threshold, .. }) if threshold == au(500)
+        ));
+        assert!(accounting.prepare_provide(trusted, au(100)).is_ok());
+
+        // Only the distrusted peer is past its credit disconnect line.
+        let grace = Duration::from_secs(30);
+        assert!(accounting.overdue_peers(1_000, grace).is_empty());
+        assert_eq!(accounting.overdue_peers(1_030, grace), vec![distrusted]);
+    }
+
+    #[test]
+    fn reputation_never_moves_our_own_debt_line() {
+        // A trusted peer still drops us at its announced line, so our debt to
+        // it stays capped at the configured 1250 rather than the scaled 1875.
+        use vertex_swarm_test_utils::test_overlay;
+
+        let accounting = Accounting::new(small_config(), test_identity())
+            .with_reputation_scaling(ReputationScaling::default());
+        let trusted = test_overlay(2);
+        accounting.observe_score(&trusted, 50.0);
+        assert_eq!(
+            Ledger::disconnect_line(&accounting, &trusted),
+            SMALL_DISCONNECT_THRESHOLD
+        );
+
+        accounting
+            .for_peer(trusted)
+            .record(au(1200), Direction::Download);
+        assert!(!accounting.admit(&trusted, au(100)).admits());
+        assert!(matches!(
+            accounting.prepare_receive(trusted, au(100), true),
+            Err(AccountingError::DisconnectThreshold { threshold, .. })
+                if threshold == SMALL_DISCONNECT_THRESHOLD
+        ));
+    }
+
+    #[test]
+    fn observe_score_is_a_no_op_without_scaling() {
+        let accounting = Accounting::new(small_config(), test_identity());
+        accounting.observe_score(&test_peer(), -50.0);
+        assert_eq!(
+            Ledger::disconnect_line(&accounting, &test_peer()),
+            SMALL_DISCONNECT_THRESHOLD
+        );
+    }
+
     #[test]
     fn admit_settles_once_the_request_crosses_the_payment_threshold() {
         // Payment 1000, disconnect 1250. A fresh request that lands the projected
@@ -856,4 +1113,37 @@ mod tests {
         drop(reservation);
         assert_eq!(Ledger::reserved(&accounting, &peer), Au::ZERO);
     }
+
+    #[test]
+    fn rate_limited_peer_is_throttled_while_another_is_not() {
+        // Two chunks per second per peer; balance headroom is ample, so only the
+        // byte-rate bucket can refuse.
+        let accounting = test_accounting().with_rate_limits(BandwidthRateLimits {
+            peer_bytes_per_sec: Some(2 * SERVED_CHUNK_BYTES),
+            ..Default::default()
+        });
+        let alice = OverlayAddress::from([1u8; 32]);
+        let bob = OverlayAddress::from([2u8; 32]);
+
+        for _ in 0..2 {
+            accounting
+                .prepare_provide(alice, au(1))
+                .expect("within rate")
+                .apply();
+        }
+        assert!(matches!(
+            accounting.prepare_provide(alice, au(1)),
+            Err(AccountingError::RateLimited { peer, .. }) if peer == alice
+        ));
+        // The refusal is not a balance verdict: nothing was reserved for it.
+        assert_eq!(accounting.for_peer(alice).balance(), au(2));
+        assert_eq!(
+            accounting.for_peer(alice).state.shadow_reserved_balance(),
+            au(0)
+        );
+
+        accounting
+            .prepare_provide(bob, au(1))
+            .expect("a separate peer has its own bucket");
+    }
 }
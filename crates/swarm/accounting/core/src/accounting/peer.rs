@@ -1,6 +1,7 @@
 //! Atomic per-peer balance tracking for lock-free bandwidth recording.
 
-use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use std::time::Duration;
 
 use vertex_swarm_api::{Au, SwarmPeerState};
 
@@ -47,8 +48,20 @@ pub struct PeerState {
     reserved_balance: AtomicU64,
     shadow_reserved_balance: AtomicU64,
     ghost_balance: AtomicU64,
+    /// Unix seconds of the last completed settlement, `0` if never.
+    last_settled: AtomicU64,
+    /// Unix seconds the balance was first seen past the disconnect threshold,
+    /// `0` while under it.
+    over_disconnect_since: AtomicU64,
+    /// Set while a settlement dispute is under investigation; the balance
+    /// does not move.
+    frozen: AtomicBool,
     payment_threshold: Au,
     disconnect_threshold: Au,
+    /// Credit we extend to the peer, in AU. Start at the thresholds above and
+    /// move with the peer's reputation when scaling is enabled.
+    credit_payment_threshold: AtomicI64,
+    credit_disconnect_threshold: AtomicI64,
 }
 
 impl PeerState {
@@ -59,8 +72,13 @@ impl PeerState {
             reserved_balance: AtomicU64::new(0),
             shadow_reserved_balance: AtomicU64::new(0),
             ghost_balance: AtomicU64::new(0),
+            last_settled: AtomicU64::new(0),
+            over_disconnect_since: AtomicU64::new(0),
+            frozen: AtomicBool::new(false),
             payment_threshold,
             disconnect_threshold,
+            credit_payment_threshold: AtomicI64::new(payment_threshold.get()),
+            credit_disconnect_threshold: AtomicI64::new(disconnect_threshold.get()),
         }
     }
 
@@ -71,10 +89,25 @@ impl PeerState {
 
     /// Add to the balance atomically, saturating at the [`i64`] bounds so an
     /// adversarial price or settlement sequence cannot wrap and flip owed/owes.
+    /// A no-op while the peer is [frozen](Self::is_frozen).
     pub fn add_balance(&self, amount: Au) {
+        if self.is_frozen() {
+            return;
+        }
         saturating_fetch_add(&self.balance, amount.get());
     }
 
+    /// Whether the peer's accounting is frozen: no new service either way and
+    /// no balance movement until it is unfrozen.
+    pub fn is_frozen(&self) -> bool {
+        self.frozen.load(Ordering::Relaxed)
+    }
+
+    /// Freeze or unfreeze the peer's accounting.
+    pub fn set_frozen(&self, frozen: bool) {
+        self.frozen.store(frozen, Ordering::Relaxed);
+    }
+
     /// Get the reserved balance in AU.
     pub fn reserved_balance(&self) -> Au {
         Au::from_amount(self.reserved_balance.load(Ordering::Relaxed))
@@ -120,6 +153,46 @@ impl PeerState {
             .fetch_add(amount.as_amount(), Ordering::Relaxed);
     }
 
+    /// Unix seconds of the last completed settlement, `0` if never settled.
+    pub fn last_settled(&self) -> u64 {
+        self.last_settled.load(Ordering::Relaxed)
+    }
+
+    /// Record a completed settlement at `unix_secs`.
+    pub fn set_last_settled(&self, unix_secs: u64) {
+        self.last_settled.store(unix_secs, Ordering::Relaxed);
+    }
+
+    /// Whether the peer has owed us past the credit disconnect threshold for
+    /// at least `grace`, as of `now_unix_secs`.
+    ///
+    /// The first check past the line starts the grace timer; a check back under
+    /// it clears the timer, so debt settled within the grace never counts. A
+    /// due result re-arms the timer, so a peer that lingers is reported once
+    /// per grace rather than on every check.
+    pub fn disconnect_due(&self, now_unix_secs: u64, grace: Duration) -> bool {
+        if self.balance() <= self.credit_disconnect_threshold() {
+            self.over_disconnect_since.store(0, Ordering::Relaxed);
+            return false;
+        }
+        // `max(1)` keeps a check at the epoch distinguishable from "under".
+        let now = now_unix_secs.max(1);
+        let since = match self.over_disconnect_since.compare_exchange(
+            0,
+            now,
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+        ) {
+            Ok(_) => now,
+            Err(since) => since,
+        };
+        if now.saturating_sub(since) < grace.as_secs() {
+            return false;
+        }
+        self.over_disconnect_since.store(0, Ordering::Relaxed);
+        true
+    }
+
     /// Get the payment threshold in AU.
     pub fn payment_threshold(&self) -> Au {
         self.payment_threshold
@@ -129,6 +202,29 @@ impl PeerState {
     pub fn disconnect_threshold(&self) -> Au {
         self.disconnect_threshold
     }
+
+    /// Debt the peer may run up with us before we stop serving it, in AU.
+    pub fn credit_payment_threshold(&self) -> Au {
+        Au::new(self.credit_payment_threshold.load(Ordering::Relaxed))
+    }
+
+    /// Debt past which the peer is dropped once the grace elapses, in AU.
+    pub fn credit_disconnect_threshold(&self) -> Au {
+        Au::new(self.credit_disconnect_threshold.load(Ordering::Relaxed))
+    }
+
+    /// Replace both credit thresholds, as the reputation observer does. The
+    /// payment and disconnect thresholds bounding our own debt stay put.
+    ///
+    /// The two stores are not one atomic update; a reader racing the change
+    /// may pair an old payment threshold with a new disconnect threshold for
+    /// one check, which only shifts that check by one observation.
+    pub fn set_credit_thresholds(&self, payment_threshold: Au, disconnect_threshold: Au) {
+        self.credit_payment_threshold
+            .store(payment_threshold.get(), Ordering::Relaxed);
+        self.credit_disconnect_threshold
+            .store(disconnect_threshold.get(), Ordering::Relaxed);
+    }
 }
 
 impl SwarmPeerState for PeerState {
@@ -209,5 +305,10 @@ mod tests {
 
         assert_eq!(state.payment_threshold(), au(1000));
         assert_eq!(state.disconnect_threshold(), au(10000));
+        assert_eq!(state.credit_payment_threshold(), au(1000));
+
+        state.set_credit_thresholds(au(500), au(5000));
+        assert_eq!(state.credit_disconnect_threshold(), au(5000));
+        assert_eq!(state.disconnect_threshold(), au(10000));
     }
 }
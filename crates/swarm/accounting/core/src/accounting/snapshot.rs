@@ -0,0 +1,269 @@
+//! Balance snapshots for crash recovery.
+//!
+//! Per-peer balances otherwise live only in memory, so an unclean shutdown
+//! would hand every debtor a clean slate on reconnect. The node persists a
+//! whole-set snapshot on an interval and restores it once at startup. A
+//! restored balance is only as good as its age: one older than the configured
+//! window is discarded, since the peer may have settled with us (or we with
+//! it) in a session we never recorded.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use vertex_net_peer_store::PeerSnapshotStore;
+use vertex_net_peer_store::error::StoreError;
+use vertex_storage::{
+    Database, DatabaseError, DbTx, DbTxMut, Table, Unversioned, Versioned, VersionedFormat, table,
+};
+use vertex_swarm_api::{Au, SwarmAccountingConfig, SwarmIdentity};
+use vertex_swarm_primitives::OverlayAddress;
+
+use super::Accounting;
+
+/// One peer's persisted balance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerBalanceSnapshot {
+    pub peer: OverlayAddress,
+    /// Committed balance; positive means the peer owes us.
+    pub balance: Au,
+    /// Unix seconds of the last completed settlement, `0` if never.
+    pub last_settled: u64,
+    /// Unix seconds the snapshot was taken.
+    pub taken_at: u64,
+}
+
+impl<C: SwarmAccountingConfig, I: SwarmIdentity> Accounting<C, I> {
+    /// Snapshot every peer with a non-zero balance or a recorded settlement.
+    ///
+    /// Reservations are in-flight and not persisted: after a crash the
+    /// requests they guarded are gone.
+    pub fn balance_snapshot(&self, now: u64) -> Vec<PeerBalanceSnapshot> {
+        self.peers
+            .read()
+            .iter()
+            .filter(|(_, state)| state.balance() != Au::ZERO || state.last_settled() != 0)
+            .map(|(peer, state)| PeerBalanceSnapshot {
+                peer: *peer,
+                balance: state.balance(),
+                last_settled: state.last_settled(),
+                taken_at: now,
+            })
+            .collect()
+    }
+
+    /// Seed balances from a persisted snapshot, dropping records older than
+    /// `max_age_secs`. Returns the number of peers restored.
+    ///
+    /// Call once at startup, before the node carries traffic: a restored
+    /// balance is added to whatever the peer has accrued since.
+    pub fn restore_balances(
+        &self,
+        records: &[PeerBalanceSnapshot],
+        now: u64,
+        max_age_secs: u64,
+    ) -> usize {
+        let mut restored = 0;
+        for record in records {
+            if now.saturating_sub(record.taken_at) > max_age_secs {
+                continue;
+            }
+            let state = self.get_or_create_peer(record.peer);
+            state.add_balance(record.balance);
+            state.set_last_settled(record.last_settled);
+            restored += 1;
+        }
+        restored
+    }
+}
+
+// OverlayAddress -> PeerBalanceSnapshot, replaced wholesale on every store.
+table!(pub(crate) BalanceSnapshotTable, "accounting_balances", OverlayAddress, Versioned<PeerBalanceSnapshot>);
+
+// The same table as written before the versioned envelope: bare records.
+table!(pub(crate) LegacyBalanceSnapshotTable, "accounting_balances", OverlayAddress, Unversioned<PeerBalanceSnapshot>);
+
+impl VersionedFormat for PeerBalanceSnapshot {
+    const MAGIC: u8 = 0x42;
+    const VERSION: u8 = 1;
+
+    /// Rows from before the envelope hold the version 1 layout unwrapped.
+    fn deserialize_unversioned<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        Self::deserialize(deserializer)
+    }
+}
+
+fn db_err(e: DatabaseError) -> StoreError {
+    StoreError::Storage(e.to_string())
+}
+
+/// Balance snapshot store over the vertex-storage `Database` trait.
+///
+/// Shares the node database with the peer snapshot store; `store` replaces the
+/// whole table in one transaction.
+pub struct DbBalanceSnapshotStore<DB: Database> {
+    db: Arc<DB>,
+}
+
+impl<DB: Database> DbBalanceSnapshotStore<DB> {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self { db }
+    }
+
+    /// Initialize the snapshot table (call once at startup).
+    pub fn init(&self) -> Result<(), StoreError> {
+        self.db
+            .update(|tx| tx.ensure_table(BalanceSnapshotTable::NAME))
+            .map_err(db_err)
+    }
+}
+
+impl<DB: Database> PeerSnapshotStore<PeerBalanceSnapshot> for DbBalanceSnapshotStore<DB> {
+    fn load(&self) -> Result<Vec<PeerBalanceSnapshot>, StoreError> {
+        self.db
+            .view(|tx| match tx.entries::<BalanceSnapshotTable>() {
+                Ok(entries) => Ok(entries.into_iter().map(|(_, v)| v.into_inner()).collect()),
+                // `store` replaces the whole table, so a row that fails the
+                // envelope means the table predates it; the next store
+                // rewrites it enveloped.
+                Err(_) => {
+                    let entries = tx.entries::<LegacyBalanceSnapshotTable>()?;
+                    Ok(entries.into_iter().map(|(_, v)| v.into_inner()).collect())
+                }
+            })
+            .map_err(db_err)
+    }
+
+    fn store(&self, records: &[PeerBalanceSnapshot]) -> Result<(), StoreError> {
+        self.db
+            .update(|tx| {
+                tx.clear::<BalanceSnapshotTable>()?;
+                for record in records {
+                    tx.put::<BalanceSnapshotTable>(record.peer, Versioned(*record))?;
+                }
+                Ok(())
+            })
+            .map_err(db_err)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::BandwidthConfig;
+    use vertex_net_peer_store::MemoryPeerStore;
+    use vertex_swarm_api::{Direction, Ledger, SwarmBandwidthAccounting, SwarmPeerBandwidth};
+    use vertex_swarm_test_utils::{Identity, test_identity, test_peer};
+
+    const NOW: u64 = 1_700_000_000;
+    const MAX_AGE: u64 = 3600;
+
+    fn accounting() -> Accounting<BandwidthConfig, Identity> {
+        Accounting::new(BandwidthConfig::default(), test_identity())
+    }
+
+    #[test]
+    fn debt_survives_a_persist_and_restore() {
+        let before = accounting();
+        before
+            .for_peer(test_peer())
+            .record(Au::new(4_000), Direction::Upload);
+        before
+            .for_peer(test_peer())
+            .state()
+            .set_last_settled(NOW - 10);
+
+        let store = MemoryPeerStore::new();
+        store.store(&before.balance_snapshot(NOW)).unwrap();
+
+        let after = accounting();
+        let restored = after.restore_balances(&store.load().unwrap(), NOW + 60, MAX_AGE);
+        assert_eq!(restored, 1);
+        assert_eq!(Ledger::balance(&after, &test_peer()), Au::new(4_000));
+        assert_eq!(after.for_peer(test_peer()).state().last_settled(), NOW - 10);
+    }
+
+    #[test]
+    fn stale_snapshot_is_discarded() {
+        let before = accounting();
+        before
+            .for_peer(test_peer())
+            .record(Au::new(4_000), Direction::Upload);
+        let records = before.balance_snapshot(NOW);
+
+        let after = accounting();
+        assert_eq!(
+            after.restore_balances(&records, NOW + MAX_AGE + 1, MAX_AGE),
+            0
+        );
+        assert_eq!(Ledger::balance(&after, &test_peer()), Au::ZERO);
+        assert!(
+            after.peers().is_empty(),
+            "a discarded record creates no peer"
+        );
+    }
+
+    #[test]
+    fn settled_up_peers_are_not_persisted() {
+        let accounting = accounting();
+        let handle = accounting.for_peer(test_peer());
+        handle.record(Au::new(500), Direction::Upload);
+        handle.record(Au::new(500), Direction::Download);
+        assert!(accounting.balance_snapshot(NOW).is_empty());
+    }
+
+    #[test]
+    fn redb_store_roundtrip_replaces_the_set() {
+        let db = vertex_storage_redb::RedbDatabase::in_memory()
+            .unwrap()
+            .into_arc();
+        let store = DbBalanceSnapshotStore::new(db);
+        store.init().unwrap();
+
+        let record = |byte: u8, balance: i64| PeerBalanceSnapshot {
+            peer: OverlayAddress::from([byte; 32]),
+            balance: Au::new(balance),
+            last_settled: 0,
+            taken_at: NOW,
+        };
+        store.store(&[record(1, 10), record(2, -20)]).unwrap();
+        store.store(&[record(3, 30)]).unwrap();
+
+        assert_eq!(store.load().unwrap(), vec![record(3, 30)]);
+    }
+
+    #[test]
+    fn rows_from_before_the_envelope_still_load() {
+        // The table as an older build wrote it: bare postcard records.
+        table!(
+            BareRows,
+            "accounting_balances",
+            OverlayAddress,
+            PeerBalanceSnapshot
+        );
+
+        let db = vertex_storage_redb::RedbDatabase::in_memory()
+            .unwrap()
+            .into_arc();
+        let store = DbBalanceSnapshotStore::new(Arc::clone(&db));
+        store.init().unwrap();
+
+        let record = PeerBalanceSnapshot {
+            peer: OverlayAddress::from([7; 32]),
+            balance: Au::new(-40),
+            last_settled: NOW - 5,
+            taken_at: NOW,
+        };
+        db.update(|tx| tx.put::<BareRows>(record.peer, record))
+            .unwrap();
+
+        assert_eq!(store.load().unwrap(), vec![record]);
+
+        // The next store rewrites the table enveloped.
+        store.store(&[record]).unwrap();
+        let enveloped = db.view(|tx| tx.entries::<BalanceSnapshotTable>()).unwrap();
+        assert_eq!(enveloped.len(), 1);
+    }
+}
@@ -23,7 +23,7 @@ pub struct BandwidthArgs {
     #[arg(long = "bandwidth.tolerance-percent", default_value_t = DEFAULT_PAYMENT_TOLERANCE_PERCENT)]
     pub payment_tolerance_percent: u64,
 
-    /// Pseudosettle refresh rate per second.
+    /// Pseudosettle refresh rate per second (clamped to 100x the default).
     #[arg(long = "bandwidth.refresh-rate", default_value_t = DEFAULT_REFRESH_RATE)]
     pub refresh_rate: u64,
 
@@ -35,6 +35,43 @@ pub struct BandwidthArgs {
     #[arg(long = "bandwidth.client-only-factor", default_value_t = DEFAULT_CLIENT_ONLY_FACTOR)]
     pub client_only_factor: u64,
 
+    /// Bytes per second served to one peer, regardless of its balance (0 = unlimited).
+    #[arg(long = "bandwidth.peer-rate", default_value_t = 0)]
+    pub peer_rate: u32,
+
+    /// Bytes per second served to one neighbour (0 = use the peer rate).
+    #[arg(long = "bandwidth.neighbour-rate", default_value_t = 0)]
+    pub neighbour_rate: u32,
+
+    /// Bytes per second served across all peers (0 = unlimited).
+    #[arg(long = "bandwidth.global-rate", default_value_t = 0)]
+    pub global_rate: u32,
+
+    /// Percent of the thresholds extended to the lowest-scored peers (100 = no scaling).
+    #[arg(long = "bandwidth.reputation-min-percent", default_value_t = 100)]
+    pub reputation_min_percent: u64,
+
+    /// Percent of the thresholds extended to the highest-scored peers (100 = no scaling).
+    #[arg(long = "bandwidth.reputation-max-percent", default_value_t = 100)]
+    pub reputation_max_percent: u64,
+
+    /// Seconds between balance snapshots to the node database (0 = never
+    /// persist or restore).
+    #[arg(long = "bandwidth.snapshot-interval", default_value_t = DEFAULT_SNAPSHOT_INTERVAL_SECS)]
+    pub snapshot_interval: u64,
+
+    /// Discard persisted balances older than this many seconds on restart.
+    #[arg(long = "bandwidth.snapshot-max-age", default_value_t = DEFAULT_SNAPSHOT_MAX_AGE_SECS)]
+    pub snapshot_max_age: u64,
+
+    /// Seconds a peer may stay past the disconnect threshold before it is dropped.
+    #[arg(long = "bandwidth.disconnect-grace", default_value_t = DEFAULT_DISCONNECT_GRACE_SECS)]
+    pub disconnect_grace: u64,
+
+    /// Seconds a pseudosettle ack timestamp may lag our clock and still be trusted.
+    #[arg(long = "bandwidth.clock-skew-tolerance", default_value_t = DEFAULT_CLOCK_SKEW_TOLERANCE_SECS)]
+    pub clock_skew_tolerance: u64,
+
     /// Chunk pricing configuration.
     #[command(flatten)]
     #[serde(default)]
@@ -49,6 +86,15 @@ impl Default for BandwidthArgs {
             refresh_rate: DEFAULT_REFRESH_RATE,
             early_payment_percent: DEFAULT_EARLY_PAYMENT_PERCENT,
             client_only_factor: DEFAULT_CLIENT_ONLY_FACTOR,
+            peer_rate: 0,
+            neighbour_rate: 0,
+            global_rate: 0,
+            reputation_min_percent: 100,
+            reputation_max_percent: 100,
+            snapshot_interval: DEFAULT_SNAPSHOT_INTERVAL_SECS,
+            snapshot_max_age: DEFAULT_SNAPSHOT_MAX_AGE_SECS,
+            disconnect_grace: DEFAULT_DISCONNECT_GRACE_SECS,
+            clock_skew_tolerance: DEFAULT_CLOCK_SKEW_TOLERANCE_SECS,
             pricing: FixedPricingArgs::default(),
         }
     }
@@ -59,4 +105,20 @@ impl BandwidthArgs {
     pub fn accounting_config(&self) -> crate::DefaultBandwidthConfig {
         crate::BandwidthConfig::from(self)
     }
+
+    /// The serve byte-rate limits, with `0` mapped to "off".
+    pub fn rate_limits(&self) -> crate::BandwidthRateLimits {
+        let nonzero = |rate: u32| (rate > 0).then_some(rate);
+        crate::BandwidthRateLimits {
+            peer_bytes_per_sec: nonzero(self.peer_rate),
+            neighbour_bytes_per_sec: nonzero(self.neighbour_rate),
+            global_bytes_per_sec: nonzero(self.global_rate),
+        }
+    }
+
+    /// The reputation scaling of per-peer thresholds over the default score
+    /// range.
+    pub fn reputation_scaling(&self) -> crate::ReputationScaling {
+        crate::ReputationScaling::new(self.reputation_min_percent, self.reputation_max_percent)
+    }
 }
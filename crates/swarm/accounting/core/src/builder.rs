@@ -8,7 +8,7 @@ use vertex_swarm_api::{
     SwarmSettlementProvider, SwarmSpec,
 };
 
-use crate::{Accounting, ClientAccounting};
+use crate::{Accounting, BandwidthRateLimits, ClientAccounting, ReputationScaling};
 
 /// Builder for bandwidth accounting with integrated pricing.
 ///
@@ -27,6 +27,8 @@ pub struct AccountingBuilder<C, P = NoPricer> {
     config: C,
     pricing: P,
     providers: Vec<Box<dyn SwarmSettlementProvider>>,
+    rate_limits: BandwidthRateLimits,
+    reputation: ReputationScaling,
 }
 
 impl<C: SwarmAccountingConfig> AccountingBuilder<C, NoPricer> {
@@ -36,6 +38,8 @@ impl<C: SwarmAccountingConfig> AccountingBuilder<C, NoPricer> {
             config,
             pricing: NoPricer,
             providers: Vec::new(),
+            rate_limits: BandwidthRateLimits::default(),
+            reputation: ReputationScaling::new(100, 100),
         }
     }
 }
@@ -50,6 +54,8 @@ impl<C, P> AccountingBuilder<C, P> {
             config: self.config,
             pricing,
             providers: self.providers,
+            rate_limits: self.rate_limits,
+            reputation: self.reputation,
         }
     }
 
@@ -71,6 +77,18 @@ impl<C, P> AccountingBuilder<C, P> {
         self
     }
 
+    /// Cap the byte rate served to each peer, independent of its balance.
+    pub fn with_rate_limits(mut self, rate_limits: BandwidthRateLimits) -> Self {
+        self.rate_limits = rate_limits;
+        self
+    }
+
+    /// Scale each peer's thresholds by its reputation.
+    pub fn with_reputation_scaling(mut self, reputation: ReputationScaling) -> Self {
+        self.reputation = reputation;
+        self
+    }
+
     /// Get a reference to the config.
     pub fn config(&self) -> &C {
         &self.config
@@ -103,7 +121,9 @@ impl<C: SwarmAccountingConfig + Clone + 'static, P: SwarmPricing + Clone + Send
         self,
         identity: &I,
     ) -> ClientAccounting<Arc<Accounting<C, I>>, P> {
-        let accounting = Accounting::with_providers(self.config, identity.clone(), self.providers);
+        let accounting = Accounting::with_providers(self.config, identity.clone(), self.providers)
+            .with_rate_limits(self.rate_limits)
+            .with_reputation_scaling(self.reputation);
         ClientAccounting::new(Arc::new(accounting), self.pricing)
     }
 }
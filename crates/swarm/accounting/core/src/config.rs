@@ -1,10 +1,13 @@
 //! Validated bandwidth accounting configuration.
 
+use std::time::Duration;
+
 use vertex_swarm_accounting_pricing::FixedPricingConfig;
 use vertex_swarm_api::{Au, SwarmAccountingConfig, SwarmPricingConfig};
 
 use crate::args::BandwidthArgs;
 use crate::constants::*;
+use crate::{BandwidthRateLimits, ReputationScaling};
 
 /// Bandwidth accounting configuration.
 ///
@@ -17,6 +20,12 @@ pub struct BandwidthConfig<P = FixedPricingConfig> {
     refresh_rate: u64,
     early_payment_percent: u64,
     client_only_factor: u64,
+    rate_limits: BandwidthRateLimits,
+    reputation: ReputationScaling,
+    snapshot_interval: u64,
+    snapshot_max_age: u64,
+    disconnect_grace: u64,
+    clock_skew_tolerance: u64,
     pricing: P,
 }
 
@@ -40,10 +49,84 @@ impl<P> BandwidthConfig<P> {
             refresh_rate,
             early_payment_percent,
             client_only_factor,
+            rate_limits: BandwidthRateLimits::default(),
+            reputation: ReputationScaling::new(100, 100),
+            snapshot_interval: DEFAULT_SNAPSHOT_INTERVAL_SECS,
+            snapshot_max_age: DEFAULT_SNAPSHOT_MAX_AGE_SECS,
+            disconnect_grace: DEFAULT_DISCONNECT_GRACE_SECS,
+            clock_skew_tolerance: DEFAULT_CLOCK_SKEW_TOLERANCE_SECS,
             pricing,
         }
     }
 
+    /// Replace the serve byte-rate limits.
+    pub fn with_rate_limits(self, rate_limits: BandwidthRateLimits) -> Self {
+        Self {
+            rate_limits,
+            ..self
+        }
+    }
+
+    /// The serve byte-rate limits, independent of the balance thresholds.
+    pub fn rate_limits(&self) -> BandwidthRateLimits {
+        self.rate_limits
+    }
+
+    /// Replace the reputation scaling of per-peer thresholds.
+    pub fn with_reputation_scaling(self, reputation: ReputationScaling) -> Self {
+        Self { reputation, ..self }
+    }
+
+    /// How peer scores scale the per-peer thresholds. Off (100% at every
+    /// score) unless configured.
+    pub fn reputation_scaling(&self) -> ReputationScaling {
+        self.reputation
+    }
+
+    /// Replace the balance snapshot cadence and staleness window, in seconds.
+    /// An interval of `0` disables persistence and the restore on startup.
+    pub fn with_snapshots(self, interval_secs: u64, max_age_secs: u64) -> Self {
+        Self {
+            snapshot_interval: interval_secs,
+            snapshot_max_age: max_age_secs,
+            ..self
+        }
+    }
+
+    /// How often balances are persisted, `None` when persistence is off.
+    pub fn snapshot_interval(&self) -> Option<Duration> {
+        (self.snapshot_interval > 0).then(|| Duration::from_secs(self.snapshot_interval))
+    }
+
+    /// Age past which a persisted balance is untrustworthy and dropped on
+    /// restore.
+    pub fn snapshot_max_age(&self) -> Duration {
+        Duration::from_secs(self.snapshot_max_age)
+    }
+
+    /// Replace the disconnect grace, in seconds. `0` drops a peer on the first
+    /// check that finds it past the disconnect threshold.
+    pub fn with_disconnect_grace(self, grace_secs: u64) -> Self {
+        Self {
+            disconnect_grace: grace_secs,
+            ..self
+        }
+    }
+
+    /// How long a peer may stay past the disconnect threshold before it is
+    /// dropped.
+    pub fn disconnect_grace(&self) -> Duration {
+        Duration::from_secs(self.disconnect_grace)
+    }
+
+    /// Replace the pseudosettle ack clock-skew tolerance, in seconds.
+    pub fn with_clock_skew_tolerance(self, tolerance_secs: u64) -> Self {
+        Self {
+            clock_skew_tolerance: tolerance_secs,
+            ..self
+        }
+    }
+
     /// Get the pricing configuration.
     pub fn pricing(&self) -> &P {
         &self.pricing
@@ -71,6 +154,12 @@ impl From<&BandwidthArgs> for BandwidthConfig<FixedPricingConfig> {
             refresh_rate: args.refresh_rate,
             early_payment_percent: args.early_payment_percent,
             client_only_factor: args.client_only_factor,
+            rate_limits: args.rate_limits(),
+            reputation: args.reputation_scaling(),
+            snapshot_interval: args.snapshot_interval,
+            snapshot_max_age: args.snapshot_max_age,
+            disconnect_grace: args.disconnect_grace,
+            clock_skew_tolerance: args.clock_skew_tolerance,
             pricing: FixedPricingConfig::from(&args.pricing),
         }
     }
@@ -84,6 +173,12 @@ impl Default for BandwidthConfig<FixedPricingConfig> {
             refresh_rate: DEFAULT_REFRESH_RATE,
             early_payment_percent: DEFAULT_EARLY_PAYMENT_PERCENT,
             client_only_factor: DEFAULT_CLIENT_ONLY_FACTOR,
+            rate_limits: BandwidthRateLimits::default(),
+            reputation: ReputationScaling::new(100, 100),
+            snapshot_interval: DEFAULT_SNAPSHOT_INTERVAL_SECS,
+            snapshot_max_age: DEFAULT_SNAPSHOT_MAX_AGE_SECS,
+            disconnect_grace: DEFAULT_DISCONNECT_GRACE_SECS,
+            clock_skew_tolerance: DEFAULT_CLOCK_SKEW_TOLERANCE_SECS,
             pricing: FixedPricingConfig::default(),
         }
     }
@@ -101,8 +196,9 @@ where
         self.payment_tolerance_percent
     }
 
+    /// The configured refresh rate, clamped to [`MAX_REFRESH_RATE`].
     fn refresh_rate(&self) -> Au {
-        Au::from_amount(self.refresh_rate)
+        Au::from_amount(self.refresh_rate.min(MAX_REFRESH_RATE))
     }
 
     fn early_payment_percent(&self) -> u64 {
@@ -112,6 +208,10 @@ where
     fn client_only_factor(&self) -> u64 {
         self.client_only_factor
     }
+
+    fn clock_skew_tolerance(&self) -> Duration {
+        Duration::from_secs(self.clock_skew_tolerance)
+    }
 }
 
 impl<P> SwarmPricingConfig for BandwidthConfig<P>
@@ -138,6 +238,20 @@ mod tests {
         );
         assert_eq!(config.refresh_rate().as_amount(), DEFAULT_REFRESH_RATE);
         assert_eq!(config.client_only_factor(), DEFAULT_CLIENT_ONLY_FACTOR);
+        assert_eq!(
+            config.clock_skew_tolerance(),
+            Duration::from_secs(DEFAULT_CLOCK_SKEW_TOLERANCE_SECS)
+        );
+    }
+
+    #[test]
+    fn refresh_rate_is_clamped_to_the_ceiling() {
+        let args = BandwidthArgs {
+            refresh_rate: u64::MAX,
+            ..BandwidthArgs::default()
+        };
+        let config = BandwidthConfig::from(&args);
+        assert_eq!(config.refresh_rate().as_amount(), MAX_REFRESH_RATE);
     }
 
     #[test]
@@ -163,6 +277,19 @@ mod tests {
         assert_eq!(client.client_only_factor(), factor);
     }
 
+    #[test]
+    fn from_args_maps_zero_rates_to_off() {
+        let args = BandwidthArgs {
+            peer_rate: 1_000_000,
+            ..BandwidthArgs::default()
+        };
+        let limits = BandwidthConfig::from(&args).rate_limits();
+        assert_eq!(limits.peer_bytes_per_sec, Some(1_000_000));
+        assert_eq!(limits.neighbour_bytes_per_sec, None);
+        assert_eq!(limits.global_bytes_per_sec, None);
+        assert!(BandwidthConfig::default().rate_limits().is_disabled());
+    }
+
     #[test]
     fn for_client_floors_at_one() {
         let cfg = BandwidthConfig {
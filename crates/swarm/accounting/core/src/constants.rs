@@ -1,10 +1,17 @@
 //! Default constants for bandwidth accounting.
 
+pub use vertex_swarm_api::DEFAULT_CLOCK_SKEW_TOLERANCE_SECS;
+
 /// Default refresh rate per second.
 pub(crate) const DEFAULT_REFRESH_RATE: u64 = 4_500_000;
 
+/// Ceiling on the configured refresh rate per second. A rate above this would
+/// forgive any debt a peer can run up between settlements, disabling debt
+/// tracking altogether, so larger values are clamped to it.
+pub const MAX_REFRESH_RATE: u64 = 100 * DEFAULT_REFRESH_RATE;
+
 /// Default payment threshold.
-pub(crate) const DEFAULT_PAYMENT_THRESHOLD: u64 = 13_500_000;
+pub const DEFAULT_PAYMENT_THRESHOLD: u64 = 13_500_000;
 
 /// Default payment tolerance as a percentage.
 pub(crate) const DEFAULT_PAYMENT_TOLERANCE_PERCENT: u64 = 25;
@@ -14,3 +21,20 @@ pub(crate) const DEFAULT_EARLY_PAYMENT_PERCENT: u64 = 50;
 
 /// Default scaling factor for client-only nodes.
 pub(crate) const DEFAULT_CLIENT_ONLY_FACTOR: u64 = 10;
+
+/// Default payment threshold for client-only (light) peers: the default
+/// threshold scaled down by the client-only factor, as
+/// [`BandwidthConfig::for_client`](crate::BandwidthConfig::for_client) does.
+pub const DEFAULT_LIGHT_PAYMENT_THRESHOLD: u64 =
+    DEFAULT_PAYMENT_THRESHOLD / DEFAULT_CLIENT_ONLY_FACTOR;
+
+/// Default interval between balance snapshots, in seconds.
+pub(crate) const DEFAULT_SNAPSHOT_INTERVAL_SECS: u64 = 60;
+
+/// Default age past which a persisted balance is discarded on restore, in
+/// seconds.
+pub(crate) const DEFAULT_SNAPSHOT_MAX_AGE_SECS: u64 = 24 * 60 * 60;
+
+/// Default time a peer may stay past the disconnect threshold before it is
+/// dropped, in seconds. Long enough for an in-flight settlement to land.
+pub(crate) const DEFAULT_DISCONNECT_GRACE_SECS: u64 = 30;
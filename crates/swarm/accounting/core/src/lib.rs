@@ -10,6 +10,9 @@
 //! - [`AccountingPeerHandle`] - Handle for recording bandwidth per peer
 //! - [`Reservation`] - Typed receive/provide reservation legs
 //! - [`NoSettlement`] - No-op settlement provider
+//! - [`BandwidthLimiter`] - Per-peer serve byte-rate buckets, independent of balance
+//! - [`ReputationScaling`] - Per-peer thresholds scaled by peer score
+//! - [`DbBalanceSnapshotStore`] - Periodic balance persistence restored on startup
 //!
 //! Settlement providers (`PseudosettleProvider`, `SwapProvider`) are in sibling crates.
 //!
@@ -28,15 +31,24 @@ mod client_accounting;
 mod config;
 mod constants;
 mod noop;
+mod rate;
+mod reputation;
 mod settlement;
 
 pub use accounting::{
-    Accounting, AccountingError, AccountingPeerHandle, PeerState, Provide, Receive, Reservation,
+    Accounting, AccountingError, AccountingPeerHandle, DbBalanceSnapshotStore, PeerBalanceSnapshot,
+    PeerState, Provide, Receive, Reservation,
 };
 pub use args::BandwidthArgs;
 pub use builder::{AccountingBuilder, NoAccountingBuilder};
 pub use client_accounting::ClientAccounting;
 pub use config::{BandwidthConfig, DefaultBandwidthConfig};
+pub use constants::{
+    DEFAULT_CLOCK_SKEW_TOLERANCE_SECS, DEFAULT_LIGHT_PAYMENT_THRESHOLD, DEFAULT_PAYMENT_THRESHOLD,
+    MAX_REFRESH_RATE,
+};
 pub use noop::{NoAccounting, NoPeerBandwidth, NoProvideAction, NoReceiveAction};
+pub use rate::{BandwidthLimiter, BandwidthRateLimits, SERVED_CHUNK_BYTES};
+pub use reputation::{DEFAULT_REPUTATION_HIGH_SCORE, ReputationScaling};
 pub use settlement::NoSettlement;
 pub use vertex_swarm_accounting_pricing::{FixedPricer, FixedPricingConfig, NoPricer};
@@ -0,0 +1,182 @@
+//! Per-peer serve-bandwidth rate limiting, independent of the accounting balance.
+//!
+//! The balance gate in [`Accounting::prepare_provide`](crate::Accounting::prepare_provide)
+//! bounds how much a peer may owe us, not how fast it may draw on us: a peer that
+//! settles promptly could still saturate our uplink. [`BandwidthLimiter`] caps the
+//! byte rate we serve each peer with a GCRA bucket per overlay, plus one global
+//! bucket across all peers. Neighbours get their own (typically wider) bucket
+//! because sync traffic inside the neighbourhood is expected to be heavier.
+
+use std::num::NonZeroU32;
+use std::time::Duration;
+
+use nectar_primitives::bmt::{DEFAULT_BODY_SIZE, SPAN_SIZE};
+use parking_lot::Mutex;
+use rustc_hash::FxHashSet;
+use vertex_net_ratelimiter::{KeyedRateLimiter, Quota, RateLimitedErr, RateLimiter};
+use vertex_swarm_api::AccountingError;
+use vertex_swarm_primitives::OverlayAddress;
+
+/// Bytes charged for one served chunk: a full span plus body. Every provide is
+/// one chunk, so the limiter charges the upper bound rather than the exact size.
+pub const SERVED_CHUNK_BYTES: u32 = (SPAN_SIZE + DEFAULT_BODY_SIZE) as u32;
+
+/// Byte-rate limits for serving peers. A `None` rate disables that bucket.
+///
+/// Each rate is floored at [`SERVED_CHUNK_BYTES`] per second so a single chunk
+/// always fits the bucket's burst.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BandwidthRateLimits {
+    /// Bytes per second served to any one non-neighbour peer.
+    pub peer_bytes_per_sec: Option<u32>,
+    /// Bytes per second served to any one neighbour. Falls back to
+    /// `peer_bytes_per_sec` when unset.
+    pub neighbour_bytes_per_sec: Option<u32>,
+    /// Bytes per second served across all peers combined.
+    pub global_bytes_per_sec: Option<u32>,
+}
+
+impl BandwidthRateLimits {
+    /// True when no bucket is configured.
+    pub fn is_disabled(&self) -> bool {
+        self.peer_bytes_per_sec.is_none()
+            && self.neighbour_bytes_per_sec.is_none()
+            && self.global_bytes_per_sec.is_none()
+    }
+}
+
+fn per_second(bytes: u32) -> Quota {
+    let bytes = NonZeroU32::new(bytes.max(SERVED_CHUNK_BYTES)).unwrap_or(NonZeroU32::MIN);
+    Quota::n_every(bytes, Duration::from_secs(1))
+}
+
+/// Token buckets enforcing [`BandwidthRateLimits`] on the serve path.
+pub struct BandwidthLimiter {
+    peer: Option<Quota>,
+    neighbour: Option<Quota>,
+    buckets: KeyedRateLimiter<OverlayAddress>,
+    neighbours: Mutex<FxHashSet<OverlayAddress>>,
+    global: Option<Mutex<RateLimiter>>,
+}
+
+impl BandwidthLimiter {
+    /// Build the buckets for `limits`. Returns `None` when every limit is off.
+    pub fn new(limits: BandwidthRateLimits) -> Option<Self> {
+        if limits.is_disabled() {
+            return None;
+        }
+        let peer = limits.peer_bytes_per_sec.map(per_second);
+        let neighbour = limits.neighbour_bytes_per_sec.map(per_second).or(peer);
+        // Every charged key is given its quota explicitly, so the keyed default
+        // only matters for sizing and is never relied on.
+        let default = peer.or(neighbour).unwrap_or(per_second(SERVED_CHUNK_BYTES));
+        Some(Self {
+            peer,
+            neighbour,
+            buckets: KeyedRateLimiter::new(default),
+            neighbours: Mutex::new(FxHashSet::default()),
+            global: limits
+                .global_bytes_per_sec
+                .map(|rate| Mutex::new(RateLimiter::new(per_second(rate)))),
+        })
+    }
+
+    /// Mark or unmark `peer` as a neighbour, moving it onto the neighbour bucket
+    /// on its next charge.
+    pub fn set_neighbour(&self, peer: OverlayAddress, neighbour: bool) {
+        let mut neighbours = self.neighbours.lock();
+        if neighbour {
+            neighbours.insert(peer);
+        } else {
+            neighbours.remove(&peer);
+        }
+    }
+
+    /// Charge `bytes` served to `peer` against its bucket and the global cap.
+    ///
+    /// The peer bucket is charged first so a throttled peer never drains the
+    /// shared global budget. A global refusal does not refund the peer charge:
+    /// the node is saturated and the peer is paced either way.
+    pub fn try_consume(&self, peer: OverlayAddress, bytes: u32) -> Result<(), AccountingError> {
+        let limited = |err: RateLimitedErr| AccountingError::RateLimited {
+            peer,
+            retry_after: match err {
+                RateLimitedErr::TooSoon(wait) => wait,
+                RateLimitedErr::TooLarge => Duration::from_secs(1),
+            },
+        };
+
+        let quota = if self.neighbours.lock().contains(&peer) {
+            self.neighbour
+        } else {
+            self.peer
+        };
+        if let Some(quota) = quota {
+            // Idempotent for an unchanged quota; re-clamps the bucket when the
+            // peer moved between neighbour and non-neighbour.
+            self.buckets.set_key_quota(peer, quota);
+            self.buckets.try_consume_n(peer, bytes).map_err(limited)?;
+        }
+
+        if let Some(global) = &self.global {
+            global.lock().try_consume_n(bytes).map_err(limited)?;
+        }
+        Ok(())
+    }
+
+    /// Release `peer`'s bucket and neighbour mark.
+    pub fn remove_peer(&self, peer: &OverlayAddress) {
+        self.neighbours.lock().remove(peer);
+        self.buckets.clear(peer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overlay(n: u8) -> OverlayAddress {
+        OverlayAddress::from([n; 32])
+    }
+
+    #[test]
+    fn all_limits_off_builds_nothing() {
+        assert!(BandwidthLimiter::new(BandwidthRateLimits::default()).is_none());
+    }
+
+    #[test]
+    fn neighbour_gets_the_wider_bucket() {
+        let limiter = BandwidthLimiter::new(BandwidthRateLimits {
+            peer_bytes_per_sec: Some(SERVED_CHUNK_BYTES),
+            neighbour_bytes_per_sec: Some(4 * SERVED_CHUNK_BYTES),
+            global_bytes_per_sec: None,
+        })
+        .unwrap();
+        let (near, far) = (overlay(1), overlay(2));
+        limiter.set_neighbour(near, true);
+
+        for _ in 0..4 {
+            limiter.try_consume(near, SERVED_CHUNK_BYTES).unwrap();
+        }
+        assert!(limiter.try_consume(near, SERVED_CHUNK_BYTES).is_err());
+
+        limiter.try_consume(far, SERVED_CHUNK_BYTES).unwrap();
+        assert!(limiter.try_consume(far, SERVED_CHUNK_BYTES).is_err());
+    }
+
+    #[test]
+    fn global_cap_throttles_across_peers() {
+        let limiter = BandwidthLimiter::new(BandwidthRateLimits {
+            global_bytes_per_sec: Some(2 * SERVED_CHUNK_BYTES),
+            ..Default::default()
+        })
+        .unwrap();
+
+        limiter.try_consume(overlay(1), SERVED_CHUNK_BYTES).unwrap();
+        limiter.try_consume(overlay(2), SERVED_CHUNK_BYTES).unwrap();
+        assert!(matches!(
+            limiter.try_consume(overlay(3), SERVED_CHUNK_BYTES),
+            Err(AccountingError::RateLimited { retry_after, .. }) if retry_after > Duration::ZERO
+        ));
+    }
+}
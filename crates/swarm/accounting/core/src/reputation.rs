@@ -0,0 +1,98 @@
+//! Reputation-scaled per-peer accounting thresholds.
+//!
+//! Every peer starts at the configured payment and disconnect thresholds. With
+//! [`ReputationScaling`] set, the node feeds each peer's score into
+//! [`Accounting::observe_score`](crate::Accounting::observe_score), which
+//! rescales the credit we extend by one percentage: a poorly scored peer may
+//! owe us less before we stop serving it or drop it, a long-trusted one more.
+//! Scaling both credit thresholds by one factor keeps the tolerance band
+//! between them. The line capping our own debt is never scaled; it is where
+//! the peer drops us, whatever its score.
+
+use vertex_swarm_api::{Au, DEFAULT_PEER_WARN_THRESHOLD};
+
+/// Default score at or above which a peer gets the full upward scaling; the
+/// warn threshold mirrored onto the positive side.
+pub const DEFAULT_REPUTATION_HIGH_SCORE: f64 = -DEFAULT_PEER_WARN_THRESHOLD;
+
+/// How a peer score maps onto a percentage of the configured thresholds.
+///
+/// Scores between `low_score` and `high_score` interpolate linearly between
+/// `min_percent` and `max_percent`; scores outside the range clamp to its ends.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReputationScaling {
+    /// Score at or below which a peer gets `min_percent`.
+    pub low_score: f64,
+    /// Score at or above which a peer gets `max_percent`.
+    pub high_score: f64,
+    /// Threshold percentage for the lowest-scored peers.
+    pub min_percent: u64,
+    /// Threshold percentage for the highest-scored peers.
+    pub max_percent: u64,
+}
+
+impl ReputationScaling {
+    /// Scaling between `min_percent` and `max_percent` over the default score
+    /// range, the warn threshold to its mirror, so a neutral peer sits at the
+    /// midpoint.
+    pub fn new(min_percent: u64, max_percent: u64) -> Self {
+        Self {
+            low_score: DEFAULT_PEER_WARN_THRESHOLD,
+            high_score: DEFAULT_REPUTATION_HIGH_SCORE,
+            min_percent,
+            max_percent,
+        }
+    }
+
+    /// True when every score maps to the unscaled thresholds.
+    pub fn is_disabled(&self) -> bool {
+        self.min_percent == 100 && self.max_percent == 100
+    }
+
+    /// The threshold percentage for `score`. A non-finite score or an empty
+    /// score range yields the lower bound, the cautious end.
+    pub fn percent(&self, score: f64) -> u64 {
+        let span = self.high_score - self.low_score;
+        if !score.is_finite() || span.is_nan() || span <= 0.0 {
+            return self.min_percent.min(self.max_percent);
+        }
+        let t = ((score - self.low_score) / span).clamp(0.0, 1.0);
+        let (min, max) = (self.min_percent as f64, self.max_percent as f64);
+        (min + t * (max - min)).round() as u64
+    }
+
+    /// `threshold` scaled for a peer with `score`.
+    pub fn scale(&self, threshold: Au, score: f64) -> Au {
+        threshold.scale_percent(self.percent(score))
+    }
+}
+
+impl Default for ReputationScaling {
+    /// Half the configured credit for the worst-scored peers, one and a half
+    /// times for the best.
+    fn default() -> Self {
+        Self::new(50, 150)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neutral_score_keeps_the_configured_threshold() {
+        let scaling = ReputationScaling::default();
+        assert_eq!(scaling.percent(0.0), 100);
+        assert_eq!(scaling.scale(Au::new(1000), 0.0), Au::new(1000));
+    }
+
+    #[test]
+    fn percent_clamps_to_the_configured_bounds() {
+        let scaling = ReputationScaling::default();
+        assert_eq!(scaling.percent(-1_000.0), 50);
+        assert_eq!(scaling.percent(DEFAULT_PEER_WARN_THRESHOLD), 50);
+        assert_eq!(scaling.percent(DEFAULT_REPUTATION_HIGH_SCORE), 150);
+        assert_eq!(scaling.percent(1_000.0), 150);
+        assert_eq!(scaling.percent(f64::NAN), 50);
+    }
+}
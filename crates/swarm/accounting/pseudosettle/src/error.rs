@@ -23,4 +23,14 @@ pub enum PseudosettleSettlementError {
     /// Peer rejected the settlement.
     #[error("peer rejected settlement")]
     Rejected,
+
+    /// An ack timestamp fell outside the clock-skew tolerance. `skew` is our
+    /// clock minus the ack's, in seconds (negative when the ack is ahead).
+    #[error("ack timestamp skewed {skew}s from our clock (tolerance {tolerance}s)")]
+    TimestampSkew {
+        /// Measured skew in seconds.
+        skew: i64,
+        /// Tolerance in force, in seconds.
+        tolerance: u64,
+    },
 }
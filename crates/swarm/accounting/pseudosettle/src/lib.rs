@@ -34,7 +34,10 @@ use vertex_swarm_primitives::OverlayAddress;
 
 pub use error::PseudosettleSettlementError;
 pub use handle::PseudosettleHandle;
-pub use service::{PseudosettleCommand, PseudosettleService};
+pub use service::{
+    DEFAULT_CLOCK_SKEW_TOLERANCE, DEFAULT_MAX_ALLOWANCE_WINDOW, PseudosettleCommand,
+    PseudosettleService,
+};
 pub use vertex_swarm_client_protocol::PseudosettleEvent;
 
 /// Debtor-initiated time-based settlement provider.
@@ -3,6 +3,7 @@
 use std::collections::HashMap;
 use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 use alloy_primitives::U256;
 use tokio::sync::{mpsc, oneshot};
@@ -17,15 +18,22 @@ use vertex_tasks::{GracefulShutdown, MaybeSend, SpawnableTask};
 
 use crate::error::PseudosettleSettlementError;
 
-/// Clock-skew tolerance, in seconds, for trusting a creditor's ack timestamp as
-/// our next-settle reference. An ack within this window below our clock (and not
-/// in the future) is trusted; anything outside falls back to our clock. 60s
-/// covers honest peer/NTP skew while denying two attacks: future-freeze, where a
-/// timestamp ahead of our clock pushes the gate beyond reach and stalls our
-/// settling until the creditor drops us; and rewind-spam, where an ever-older
-/// reported timestamp rewinds the gate to induce settle spam (countered by
-/// keeping the stored reference monotonic non-decreasing).
-const CLOCK_SKEW_WINDOW_SECS: u64 = 60;
+/// Default clock-skew tolerance for trusting a creditor's ack timestamp as our
+/// next-settle reference. An ack within this window below our clock (and not in
+/// the future) is trusted; anything outside falls back to our clock. The window
+/// denies two attacks: future-freeze, where a timestamp ahead of our clock
+/// pushes the gate beyond reach and stalls our settling until the creditor
+/// drops us; and rewind-spam, where an ever-older reported timestamp rewinds the
+/// gate to induce settle spam (countered by keeping the stored reference
+/// monotonic non-decreasing).
+pub const DEFAULT_CLOCK_SKEW_TOLERANCE: Duration =
+    Duration::from_secs(vertex_swarm_accounting::DEFAULT_CLOCK_SKEW_TOLERANCE_SECS);
+
+/// Default longest elapsed interval, in seconds, credited to a peer's
+/// time-based allowance. Also the span over which a peer's cumulative
+/// forgiveness is capped at `refresh_rate` times the window, so a peer cannot
+/// collect an unbounded grant after we were asleep or it reconnected.
+pub const DEFAULT_MAX_ALLOWANCE_WINDOW: Duration = Duration::from_secs(300);
 
 /// Commands from the handle to the service.
 pub enum PseudosettleCommand {
@@ -48,6 +56,14 @@ struct PendingSettlement {
     response_tx: oneshot::Sender<Result<Au, PseudosettleSettlementError>>,
 }
 
+/// Forgiveness granted to one peer within the current allowance window.
+struct ForgivenWindow {
+    /// Our clock when the window opened.
+    start: u64,
+    /// Total AU forgiven since `start`.
+    forgiven: Au,
+}
+
 /// Processes settlement commands from handles and network events.
 pub struct PseudosettleService<A: SwarmBandwidthAccounting> {
     /// Receive commands from handles.
@@ -77,6 +93,13 @@ pub struct PseudosettleService<A: SwarmBandwidthAccounting> {
     /// `last_settlement`, the grant is bounded by the genuine wall-clock elapsed
     /// since this instant, not by the absolute timestamp.
     first_seen: HashMap<OverlayAddress, u64>,
+    /// Longest elapsed interval, in seconds, the allowance credits; also the
+    /// span of each peer's cumulative forgiveness cap.
+    max_window_secs: u64,
+    /// Forgiveness granted per peer in its current window.
+    forgiven: HashMap<OverlayAddress, ForgivenWindow>,
+    /// How far behind our clock an ack timestamp may be and still be trusted.
+    clock_skew_tolerance: Duration,
     /// Optional reporter feeding settlement violations into peer scoring.
     reporter: Option<Arc<dyn PeerReporter>>,
 }
@@ -100,6 +123,9 @@ impl<A: SwarmBandwidthAccounting + 'static> PseudosettleService<A> {
             last_settlement: HashMap::new(),
             last_settle_ack: HashMap::new(),
             first_seen: HashMap::new(),
+            max_window_secs: DEFAULT_MAX_ALLOWANCE_WINDOW.as_secs(),
+            forgiven: HashMap::new(),
+            clock_skew_tolerance: DEFAULT_CLOCK_SKEW_TOLERANCE,
             reporter: None,
         }
     }
@@ -113,6 +139,23 @@ impl<A: SwarmBandwidthAccounting + 'static> PseudosettleService<A> {
         self
     }
 
+    /// Replace the allowance window (default [`DEFAULT_MAX_ALLOWANCE_WINDOW`]).
+    ///
+    /// A peer is credited at most this much elapsed time per settlement, and
+    /// at most `refresh_rate` times the window in total within any one window.
+    /// Rounded down to whole seconds, floored at one.
+    pub fn with_max_allowance_window(mut self, window: Duration) -> Self {
+        self.max_window_secs = window.as_secs().max(1);
+        self
+    }
+
+    /// Replace the ack clock-skew tolerance (default
+    /// [`DEFAULT_CLOCK_SKEW_TOLERANCE`]).
+    pub fn with_clock_skew_tolerance(mut self, tolerance: Duration) -> Self {
+        self.clock_skew_tolerance = tolerance;
+        self
+    }
+
     /// Report an accounting violation if a reporter is attached.
     fn report_violation(&self, peer: &OverlayAddress) {
         if let Some(reporter) = &self.reporter {
@@ -229,15 +272,21 @@ impl<A: SwarmBandwidthAccounting + 'static> PseudosettleService<A> {
                     // Pace the next outbound settle off the creditor's clock:
                     // it refreshes our allowance against the timestamp it
                     // reports here, so gating on that stops us re-sending before
-                    // it has anything to forgive. The timestamp is clamped to
-                    // our clock and kept monotonic to deny two attacks (see
-                    // CLOCK_SKEW_WINDOW_SECS).
+                    // it has anything to forgive. The timestamp is checked
+                    // against our clock and kept monotonic to deny two attacks
+                    // (see DEFAULT_CLOCK_SKEW_TOLERANCE).
                     let now = current_timestamp();
-                    let window = now.saturating_sub(CLOCK_SKEW_WINDOW_SECS)..=now;
-                    let effective = u64::try_from(ack.timestamp)
-                        .ok()
-                        .filter(|t| window.contains(t))
-                        .unwrap_or(now);
+                    let effective = match validate_ack_timestamp(
+                        ack.timestamp,
+                        now,
+                        self.clock_skew_tolerance,
+                    ) {
+                        Ok(timestamp) => timestamp,
+                        Err(error) => {
+                            warn!(%peer, %error, "Untrusted pseudosettle ack timestamp; pacing off our clock");
+                            now
+                        }
+                    };
                     self.last_settle_ack
                         .entry(peer)
                         .and_modify(|prev| *prev = (*prev).max(effective))
@@ -287,6 +336,7 @@ impl<A: SwarmBandwidthAccounting + 'static> PseudosettleService<A> {
                     // Credit peer's balance (they paid us)
                     handle.record(acceptable, Direction::Download);
                     self.last_settlement.insert(peer, now);
+                    self.record_forgiven(peer, now, acceptable);
                 }
 
                 // Ack with accepted amount. The timestamp is sampled here, at the
@@ -323,8 +373,26 @@ impl<A: SwarmBandwidthAccounting + 'static> PseudosettleService<A> {
         }
     }
 
-    /// Calculate acceptable amount, capped at what the peer owes us and the
-    /// time-based allowance since the last settlement.
+    /// Add `amount` to the peer's forgiveness in its current window, opening
+    /// a new window once the previous one has lapsed.
+    fn record_forgiven(&mut self, peer: OverlayAddress, now: u64, amount: Au) {
+        let max_window_secs = self.max_window_secs;
+        let window = self.forgiven.entry(peer).or_insert(ForgivenWindow {
+            start: now,
+            forgiven: Au::ZERO,
+        });
+        if now.saturating_sub(window.start) >= max_window_secs {
+            *window = ForgivenWindow {
+                start: now,
+                forgiven: Au::ZERO,
+            };
+        }
+        window.forgiven = window.forgiven.saturating_add(amount);
+    }
+
+    /// Calculate acceptable amount, capped at what the peer owes us, the
+    /// time-based allowance since the last settlement, and what remains of
+    /// the peer's forgiveness budget for the current window.
     fn calculate_acceptable(&self, peer: &OverlayAddress, handle: &A::Peer, requested: Au) -> Au {
         let balance = handle.balance();
 
@@ -342,7 +410,9 @@ impl<A: SwarmBandwidthAccounting + 'static> PseudosettleService<A> {
         // never seeded from `now`: that would treat the whole Unix epoch as
         // elapsed and overflow the scaling into an unbounded grant, defeating
         // the only anti-free-ride brake on first contact and after a reconnect.
-        // On overflow the allowance saturates, but the request and owed caps
+        // The interval is capped at the allowance window, so a long sleep or
+        // a stale anchor never credits more than one window's worth. On
+        // overflow the allowance saturates, but the request and owed caps
         // below still bound the result.
         let now = current_timestamp();
         let since = self
@@ -351,13 +421,27 @@ impl<A: SwarmBandwidthAccounting + 'static> PseudosettleService<A> {
             .or_else(|| self.first_seen.get(peer))
             .copied()
             .unwrap_or(now);
-        let elapsed = now.saturating_sub(since);
+        let elapsed = now.saturating_sub(since).min(self.max_window_secs);
+        let window_cap = self
+            .refresh_rate
+            .checked_scale(self.max_window_secs)
+            .unwrap_or(Au::from_amount(u64::MAX));
         let allowance = self
             .refresh_rate
             .checked_scale(elapsed)
             .unwrap_or(Au::from_amount(u64::MAX));
 
-        requested.min(owed).min(allowance)
+        // Cumulative cap: whatever was already forgiven in the peer's current
+        // window comes out of the same budget, however the elapsed anchors
+        // were reset in between.
+        let remaining = match self.forgiven.get(peer) {
+            Some(window) if now.saturating_sub(window.start) < self.max_window_secs => {
+                window_cap.saturating_sub(window.forgiven).max(Au::ZERO)
+            }
+            _ => window_cap,
+        };
+
+        requested.min(owed).min(allowance).min(remaining)
     }
 }
 
@@ -376,6 +460,27 @@ impl<A: SwarmBandwidthAccounting + 'static> SpawnableTask for PseudosettleServic
     }
 }
 
+/// Check an ack timestamp against our clock `now`, both in Unix seconds.
+///
+/// Trusted only when it is at most `tolerance` behind `now` and not ahead of
+/// it; otherwise the measured skew is returned for the operator to see.
+fn validate_ack_timestamp(
+    timestamp: i64,
+    now: u64,
+    tolerance: Duration,
+) -> Result<u64, PseudosettleSettlementError> {
+    let tolerance = tolerance.as_secs();
+    let skew = i64::try_from(now)
+        .unwrap_or(i64::MAX)
+        .saturating_sub(timestamp);
+    let skewed = || PseudosettleSettlementError::TimestampSkew { skew, tolerance };
+    let behind = u64::try_from(skew).map_err(|_| skewed())?;
+    if behind > tolerance {
+        return Err(skewed());
+    }
+    u64::try_from(timestamp).map_err(|_| skewed())
+}
+
 /// Get current timestamp in seconds.
 fn current_timestamp() -> u64 {
     vertex_util_runtime::time::now_unix_secs()
@@ -616,6 +721,52 @@ mod tests {
         assert_eq!(*svc.last_settle_ack.get(&peer).unwrap(), now);
     }
 
+    #[test]
+    fn ack_timestamp_within_tolerance_is_trusted() {
+        let now = 10_000;
+        let tolerance = Duration::from_secs(60);
+        assert_eq!(
+            validate_ack_timestamp(9_940, now, tolerance).unwrap(),
+            9_940
+        );
+        assert_eq!(
+            validate_ack_timestamp(10_000, now, tolerance).unwrap(),
+            10_000
+        );
+    }
+
+    #[test]
+    fn ack_timestamp_outside_tolerance_reports_the_skew() {
+        let now = 10_000;
+        let tolerance = Duration::from_secs(60);
+        assert!(matches!(
+            validate_ack_timestamp(9_939, now, tolerance),
+            Err(PseudosettleSettlementError::TimestampSkew {
+                skew: 61,
+                tolerance: 60
+            })
+        ));
+        // Any timestamp ahead of our clock is untrusted.
+        assert!(matches!(
+            validate_ack_timestamp(10_001, now, tolerance),
+            Err(PseudosettleSettlementError::TimestampSkew {
+                skew: -1,
+                tolerance: 60
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn skewed_ack_paces_off_our_clock() {
+        let (svc, _rx) = build_service_with_rx();
+        let mut svc = svc.with_clock_skew_tolerance(Duration::from_secs(5));
+        let peer = test_peer();
+
+        let now = current_timestamp();
+        receive_ack(&mut svc, peer, now as i64 - 30).await;
+        assert!(*svc.last_settle_ack.get(&peer).unwrap() >= now);
+    }
+
     #[tokio::test]
     async fn first_contact_settle_is_allowed() {
         let (mut svc, mut rx) = build_service_with_rx();
@@ -762,6 +913,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn long_elapsed_grant_is_capped_at_the_window() {
+        let peer = test_peer();
+        let refresh_rate = Au::from_amount(4_500_000);
+        let mut svc = service_with_large_debt(peer, refresh_rate)
+            .with_max_allowance_window(Duration::from_secs(60));
+
+        // The last settlement was a week ago, e.g. the node was asleep.
+        let now = current_timestamp();
+        svc.last_settlement.insert(peer, now - 7 * 24 * 60 * 60);
+
+        let handle = svc.accounting.for_peer(peer);
+        let acceptable =
+            svc.calculate_acceptable(&peer, &handle, Au::from_amount(1_000_000_000_000));
+
+        assert_eq!(acceptable, refresh_rate.checked_scale(60).unwrap());
+    }
+
+    #[test]
+    fn forgiveness_within_a_window_is_cumulative() {
+        let peer = test_peer();
+        let refresh_rate = Au::from_amount(4_500_000);
+        let mut svc = service_with_large_debt(peer, refresh_rate)
+            .with_max_allowance_window(Duration::from_secs(60));
+        let window_cap = refresh_rate.checked_scale(60).unwrap();
+
+        // Most of the window's budget was already forgiven, then the elapsed
+        // anchor was reset far into the past (a reconnect with stale state).
+        let now = current_timestamp();
+        svc.record_forgiven(peer, now, window_cap.saturating_sub(Au::from_amount(1_000)));
+        svc.last_settlement.insert(peer, now - 3_600);
+
+        let handle = svc.accounting.for_peer(peer);
+        let acceptable =
+            svc.calculate_acceptable(&peer, &handle, Au::from_amount(1_000_000_000_000));
+
+        assert_eq!(acceptable, Au::from_amount(1_000));
+    }
+
     #[tokio::test]
     async fn over_ack_is_clamped_to_offer() {
         let mut svc = build_service();
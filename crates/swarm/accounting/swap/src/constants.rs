@@ -1,7 +1,16 @@
 //! Default constants for SWAP settlement.
 
+use std::time::Duration;
+
 use alloy_primitives::U256;
 
 /// Default per-peer uncashed cheque exposure cap, ten times the payment
 /// threshold. Bounds free service while on-chain cashing is stubbed.
 pub const DEFAULT_BOUNCE_LIMIT: U256 = U256::from_limbs([135_000_000, 0, 0, 0]);
+
+/// Default capacity of the async settlement queue, in unresolved settlements.
+pub const DEFAULT_SETTLEMENT_QUEUE_CAPACITY: usize = 64;
+
+/// How long a shutting-down settlement worker waits for in-flight settlements
+/// before reversing their optimistic credit.
+pub const SETTLEMENT_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
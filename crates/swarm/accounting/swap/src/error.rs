@@ -15,6 +15,10 @@ pub enum SwapSettlementError {
     #[error("settlement already in progress")]
     SettlementInProgress,
 
+    /// The async settlement queue is at capacity.
+    #[error("settlement queue full")]
+    QueueFull,
+
     /// Network error.
     #[error("network error: {0}")]
     NetworkError(String),
@@ -16,6 +16,10 @@
 //! Use [`create_swap_actor`] to create a service/handle pair. Spawn the service
 //! as a background task and build a [`SwapProvider`] from the handle.
 //!
+//! To keep `settle()` off the accounting path when the settlement backend waits
+//! on the chain, build the provider with [`SwapProvider::with_queue`] over a
+//! [`settlement_queue`] and spawn a [`SettlementWorker`] for its receiver.
+//!
 //! [`SwarmSettlementProvider`]: vertex_swarm_api::SwarmSettlementProvider
 
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -27,6 +31,7 @@ pub mod cashout;
 pub mod constants;
 pub mod error;
 pub mod handle;
+pub mod queue;
 pub mod service;
 
 use std::sync::Arc;
@@ -44,6 +49,10 @@ use vertex_swarm_primitives::OverlayAddress;
 
 pub use error::SwapSettlementError;
 pub use handle::SwapHandle;
+pub use queue::{
+    SettlementBackend, SettlementQueue, SettlementQueueReceiver, SettlementQueueStats,
+    SettlementWorker, settlement_queue,
+};
 pub use service::{PeerSwapInfo, SwapCommand, SwapService};
 pub use vertex_swarm_client_protocol::SwapEvent;
 
@@ -52,11 +61,14 @@ pub use vertex_swarm_client_protocol::SwapEvent;
 /// On `settle()`, when the peer's debt crosses the payment threshold the
 /// provider delegates to the service, which issues and sends a signed cheque.
 /// Without a handle it is inert (it never issues cheques on its own), so it
-/// composes safely alongside pseudosettle.
+/// composes safely alongside pseudosettle. With a queue it enqueues instead,
+/// crediting the debt at once, and returns it as an optimistic result.
 pub struct SwapProvider<C> {
     config: C,
     /// Optional handle for delegating to the service.
     handle: Option<SwapHandle>,
+    /// Optional queue for settling in the background.
+    queue: Option<SettlementQueue>,
 }
 
 impl<C: SwarmAccountingConfig> SwapProvider<C> {
@@ -68,6 +80,7 @@ impl<C: SwarmAccountingConfig> SwapProvider<C> {
         Self {
             config,
             handle: None,
+            queue: None,
         }
     }
 
@@ -76,9 +89,24 @@ impl<C: SwarmAccountingConfig> SwapProvider<C> {
         Self {
             config,
             handle: Some(handle),
+            queue: None,
+        }
+    }
+
+    /// Create a new SWAP provider that settles through an async queue.
+    pub fn with_queue(config: C, queue: SettlementQueue) -> Self {
+        Self {
+            config,
+            handle: None,
+            queue: Some(queue),
         }
     }
 
+    /// Settlement queue statistics, if the provider settles through a queue.
+    pub fn queue_stats(&self) -> Option<SettlementQueueStats> {
+        self.queue.as_ref().map(SettlementQueue::stats)
+    }
+
     /// Get a reference to the configuration.
     pub fn config(&self) -> &C {
         &self.config
@@ -106,6 +134,15 @@ impl<C: SwarmAccountingConfig + 'static> SwarmSettlementProvider for SwapProvide
             return Ok(Au::ZERO);
         }
 
+        if let Some(queue) = &self.queue {
+            // The enqueue credits the debt and the worker reconciles once the
+            // backend resolves, so report it as settled now.
+            queue
+                .enqueue(peer, debt)
+                .map_err(SwarmError::payment_required)?;
+            return Ok(debt);
+        }
+
         let Some(handle) = &self.handle else {
             // Without a service handle the provider cannot issue cheques.
             return Ok(Au::ZERO);
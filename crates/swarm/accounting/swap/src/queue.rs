@@ -0,0 +1,495 @@
+//! Bounded async settlement queue.
+//!
+//! Settling through a backend that waits on the chain can take blocks, and
+//! `settle()` sits on the accounting path. With a queue attached the provider
+//! only enqueues and returns the debt as an optimistic result. The enqueue
+//! credits that amount against the peer's balance before it returns, so the
+//! accounting re-reads a settled balance. The [`SettlementWorker`] drives the
+//! backend in the background and reverses the optimistic credit once the
+//! backend resolves: on success the backend has recorded the confirmed payment
+//! itself, on failure the reversal re-credits the debt. On shutdown the worker
+//! drains: queued settlements are reversed, in-flight ones get
+//! [`SETTLEMENT_DRAIN_TIMEOUT`] to resolve before their credit is reversed too.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+
+use futures::future::BoxFuture;
+use futures::stream::{FuturesUnordered, StreamExt};
+use parking_lot::Mutex;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+use vertex_swarm_api::{Au, Direction, SwarmBandwidthAccounting, SwarmPeerBandwidth};
+use vertex_swarm_primitives::OverlayAddress;
+use vertex_tasks::time::timeout;
+use vertex_tasks::{GracefulShutdown, MaybeSend, SpawnableTask};
+
+use crate::constants::SETTLEMENT_DRAIN_TIMEOUT;
+use crate::error::SwapSettlementError;
+use crate::handle::SwapHandle;
+
+/// Backend that performs a settlement, possibly waiting on the chain.
+///
+/// On success the backend has already recorded the confirmed payment in
+/// accounting, as [`SwapService`](crate::SwapService) does when the cheque is
+/// acknowledged. The queue only ever undoes its own optimistic credit.
+#[async_trait::async_trait]
+pub trait SettlementBackend: Send + Sync + 'static {
+    /// Settle `amount` with `peer`, returning the amount actually paid.
+    async fn settle(&self, peer: OverlayAddress, amount: Au) -> Result<Au, SwapSettlementError>;
+}
+
+#[async_trait::async_trait]
+impl SettlementBackend for SwapHandle {
+    async fn settle(&self, peer: OverlayAddress, amount: Au) -> Result<Au, SwapSettlementError> {
+        SwapHandle::settle(self, peer, amount).await
+    }
+}
+
+/// Point-in-time view of the settlement queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SettlementQueueStats {
+    /// Settlements accepted and not yet resolved, queued or in flight.
+    pub depth: usize,
+    /// Maximum number of unresolved settlements.
+    pub capacity: usize,
+}
+
+/// A settlement waiting for the worker.
+struct QueuedSettlement {
+    peer: OverlayAddress,
+    amount: Au,
+}
+
+/// Records a balance change against accounting; installed by the worker.
+type Recorder = Arc<dyn Fn(OverlayAddress, Au, Direction) + Send + Sync>;
+
+/// State shared between the queue handles and the worker.
+#[derive(Default)]
+struct QueueShared {
+    /// Unresolved settlements, at most one per peer, with the amount credited.
+    pending: Mutex<HashMap<OverlayAddress, Au>>,
+    /// Set once a worker exists; until then nothing can be credited.
+    recorder: OnceLock<Recorder>,
+}
+
+impl QueueShared {
+    fn release(&self, peer: &OverlayAddress) {
+        self.pending.lock().remove(peer);
+    }
+}
+
+/// Cloneable producer side of the settlement queue.
+#[derive(Clone)]
+pub struct SettlementQueue {
+    tx: mpsc::Sender<QueuedSettlement>,
+    shared: Arc<QueueShared>,
+    capacity: usize,
+}
+
+impl SettlementQueue {
+    /// Credit `amount` to `peer` and enqueue the settlement without waiting for
+    /// it to be processed.
+    ///
+    /// Fails with [`SwapSettlementError::SettlementInProgress`] while the peer
+    /// already has an unresolved settlement, with
+    /// [`SwapSettlementError::QueueFull`] once `capacity` settlements are
+    /// outstanding, and with [`SwapSettlementError::ServiceStopped`] before a
+    /// worker exists or after it shut down. Nothing is credited on failure.
+    pub fn enqueue(&self, peer: OverlayAddress, amount: Au) -> Result<(), SwapSettlementError> {
+        let mut pending = self.shared.pending.lock();
+        if pending.contains_key(&peer) {
+            return Err(SwapSettlementError::SettlementInProgress);
+        }
+        if pending.len() >= self.capacity {
+            return Err(SwapSettlementError::QueueFull);
+        }
+        let Some(record) = self.shared.recorder.get() else {
+            return Err(SwapSettlementError::ServiceStopped);
+        };
+
+        // Credit before the worker can see the settlement, so its reversal
+        // never lands ahead of the credit.
+        record(peer, amount, Direction::Upload);
+        if let Err(e) = self.tx.try_send(QueuedSettlement { peer, amount }) {
+            record(peer, amount, Direction::Download);
+            return Err(match e {
+                mpsc::error::TrySendError::Full(_) => SwapSettlementError::QueueFull,
+                mpsc::error::TrySendError::Closed(_) => SwapSettlementError::ServiceStopped,
+            });
+        }
+
+        pending.insert(peer, amount);
+        Ok(())
+    }
+
+    /// Number of settlements accepted and not yet resolved.
+    pub fn depth(&self) -> usize {
+        self.shared.pending.lock().len()
+    }
+
+    /// Current queue statistics.
+    pub fn stats(&self) -> SettlementQueueStats {
+        SettlementQueueStats {
+            depth: self.depth(),
+            capacity: self.capacity,
+        }
+    }
+}
+
+/// Consumer side of the settlement queue, handed to [`SettlementWorker::new`].
+pub struct SettlementQueueReceiver {
+    rx: mpsc::Receiver<QueuedSettlement>,
+    shared: Arc<QueueShared>,
+    capacity: usize,
+}
+
+/// Create a settlement queue holding at most `capacity` unresolved settlements.
+///
+/// The queue backs a [`SwapProvider`](crate::SwapProvider) and can be created
+/// before accounting exists; the receiver is turned into a worker once it does.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+pub fn settlement_queue(capacity: usize) -> (SettlementQueue, SettlementQueueReceiver) {
+    let (tx, rx) = mpsc::channel(capacity);
+    let shared = Arc::new(QueueShared::default());
+    let queue = SettlementQueue {
+        tx,
+        shared: shared.clone(),
+        capacity,
+    };
+    (
+        queue,
+        SettlementQueueReceiver {
+            rx,
+            shared,
+            capacity,
+        },
+    )
+}
+
+type SettlementOutcome = (OverlayAddress, Au, Result<Au, SwapSettlementError>);
+
+/// Background task that drives queued settlements through the backend.
+///
+/// Settlements for different peers run concurrently, up to the queue capacity,
+/// so one slow confirmation does not hold up the rest of the queue.
+pub struct SettlementWorker<A, B> {
+    rx: mpsc::Receiver<QueuedSettlement>,
+    shared: Arc<QueueShared>,
+    capacity: usize,
+    accounting: Arc<A>,
+    backend: Arc<B>,
+    in_flight: FuturesUnordered<BoxFuture<'static, SettlementOutcome>>,
+}
+
+impl<A, B> SettlementWorker<A, B>
+where
+    A: SwarmBandwidthAccounting + 'static,
+    B: SettlementBackend,
+{
+    /// Create a worker and let the queue credit settlements against
+    /// `accounting`.
+    pub fn new(receiver: SettlementQueueReceiver, accounting: Arc<A>, backend: Arc<B>) -> Self {
+        let recorder_accounting = accounting.clone();
+        let recorder: Recorder = Arc::new(move |peer, amount, direction| {
+            recorder_accounting.for_peer(peer).record(amount, direction);
+        });
+        if receiver.shared.recorder.set(recorder).is_err() {
+            warn!("Settlement queue already has a worker; keeping the first");
+        }
+
+        Self {
+            rx: receiver.rx,
+            shared: receiver.shared,
+            capacity: receiver.capacity,
+            accounting,
+            backend,
+            in_flight: FuturesUnordered::new(),
+        }
+    }
+
+    async fn run(mut self, shutdown: GracefulShutdown) {
+        let mut shutdown = std::pin::pin!(shutdown);
+
+        loop {
+            tokio::select! {
+                guard = &mut shutdown => {
+                    debug!(in_flight = self.in_flight.len(), "Settlement worker received shutdown signal");
+                    self.drain().await;
+                    drop(guard);
+                    break;
+                }
+                Some(queued) = self.rx.recv(), if self.in_flight.len() < self.capacity => {
+                    self.start(queued);
+                }
+                Some((peer, amount, result)) = self.in_flight.next() => {
+                    self.finish(peer, amount, result);
+                }
+                else => break,
+            }
+        }
+        debug!("Settlement worker shutdown complete");
+    }
+
+    /// Hand a credited settlement to the backend.
+    fn start(&mut self, QueuedSettlement { peer, amount }: QueuedSettlement) {
+        debug!(%peer, %amount, "Processing queued settlement");
+        let backend = self.backend.clone();
+        self.in_flight.push(Box::pin(async move {
+            let result = backend.settle(peer, amount).await;
+            (peer, amount, result)
+        }));
+    }
+
+    /// Reverse the optimistic credit once the backend has resolved.
+    fn finish(&self, peer: OverlayAddress, amount: Au, result: Result<Au, SwapSettlementError>) {
+        self.reverse(peer, amount);
+        match result {
+            Ok(settled) => debug!(%peer, %settled, "Queued settlement confirmed"),
+            Err(e) => warn!(%peer, %amount, error = %e, "Queued settlement failed, debt restored"),
+        }
+    }
+
+    /// Undo the optimistic credit for `peer` and free its queue slot.
+    fn reverse(&self, peer: OverlayAddress, amount: Au) {
+        self.accounting
+            .for_peer(peer)
+            .record(amount, Direction::Download);
+        self.shared.release(&peer);
+    }
+
+    /// Stop accepting settlements and reconcile every outstanding credit.
+    async fn drain(&mut self) {
+        self.rx.close();
+        while let Ok(QueuedSettlement { peer, amount }) = self.rx.try_recv() {
+            debug!(%peer, %amount, "Settlement not started before shutdown, debt restored");
+            self.reverse(peer, amount);
+        }
+
+        let mut in_flight = std::mem::take(&mut self.in_flight);
+        let drained = timeout(SETTLEMENT_DRAIN_TIMEOUT, async {
+            while let Some((peer, amount, result)) = in_flight.next().await {
+                self.finish(peer, amount, result);
+            }
+        })
+        .await;
+
+        if drained.is_err() {
+            let abandoned: Vec<_> = self.shared.pending.lock().drain().collect();
+            for (peer, amount) in abandoned {
+                warn!(%peer, %amount, "Settlement unresolved at shutdown, debt restored");
+                self.reverse(peer, amount);
+            }
+        }
+    }
+}
+
+impl<A, B> SpawnableTask for SettlementWorker<A, B>
+where
+    A: SwarmBandwidthAccounting + 'static,
+    B: SettlementBackend,
+{
+    fn into_task(self, shutdown: GracefulShutdown) -> impl Future<Output = ()> + MaybeSend {
+        self.run(shutdown)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use tokio::sync::Semaphore;
+    use tokio::time::{sleep, timeout};
+    use vertex_swarm_accounting::{Accounting, BandwidthConfig};
+    use vertex_swarm_api::SwarmSettlementProvider;
+    use vertex_swarm_test_utils::{Identity, test_identity, test_overlay};
+    use vertex_tasks::TaskManager;
+
+    use crate::SwapProvider;
+    use crate::constants::DEFAULT_SETTLEMENT_QUEUE_CAPACITY;
+
+    type TestAccounting = Accounting<BandwidthConfig, Identity>;
+
+    /// Above the default early-payment trigger, so the provider settles it.
+    const DEBT: Au = Au::from_amount(10_000_000);
+
+    /// Chain stand-in whose confirmations wait until the test releases them.
+    struct SlowChain {
+        accounting: Arc<TestAccounting>,
+        confirmations: Semaphore,
+        reverts: OverlayAddress,
+    }
+
+    impl SlowChain {
+        fn confirm(&self, count: usize) {
+            self.confirmations.add_permits(count);
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl SettlementBackend for SlowChain {
+        async fn settle(
+            &self,
+            peer: OverlayAddress,
+            amount: Au,
+        ) -> Result<Au, SwapSettlementError> {
+            self.confirmations.acquire().await.unwrap().forget();
+            if peer == self.reverts {
+                return Err(SwapSettlementError::NetworkError("reverted".into()));
+            }
+            self.accounting
+                .for_peer(peer)
+                .record(amount, Direction::Upload);
+            Ok(amount)
+        }
+    }
+
+    async fn wait_until(mut condition: impl FnMut() -> bool) {
+        timeout(Duration::from_secs(5), async {
+            while !condition() {
+                sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn slow_chain_does_not_block_accounting() {
+        let accounting = Arc::new(TestAccounting::new(
+            BandwidthConfig::default(),
+            test_identity(),
+        ));
+        let peers = [test_overlay(1), test_overlay(2), test_overlay(3)];
+        for peer in peers {
+            accounting.for_peer(peer).record(DEBT, Direction::Download);
+        }
+
+        let chain = Arc::new(SlowChain {
+            accounting: accounting.clone(),
+            confirmations: Semaphore::new(0),
+            reverts: peers[2],
+        });
+        let (queue, receiver) = settlement_queue(DEFAULT_SETTLEMENT_QUEUE_CAPACITY);
+        let manager = TaskManager::current();
+        manager.executor().spawn_service(
+            "test.settlement_worker",
+            SettlementWorker::new(receiver, accounting.clone(), chain.clone()),
+        );
+        let provider = SwapProvider::with_queue(BandwidthConfig::default(), queue.clone());
+
+        // Every settle returns while the chain has confirmed nothing.
+        for peer in peers {
+            let handle = accounting.for_peer(peer);
+            let settled = timeout(
+                Duration::from_millis(100),
+                provider.settle(peer, handle.state().as_ref()),
+            )
+            .await
+            .expect("settle must not wait on the chain")
+            .unwrap();
+            assert_eq!(settled, DEBT);
+        }
+        assert_eq!(queue.stats().depth, 3);
+        assert_eq!(provider.queue_stats(), Some(queue.stats()));
+
+        // The debt is credited before settle returns, ahead of any confirmation.
+        let balance = |peer| accounting.for_peer(peer).balance();
+        assert!(peers.iter().all(|&peer| balance(peer) == Au::ZERO));
+        assert_eq!(queue.depth(), 3);
+
+        chain.confirm(peers.len());
+        wait_until(|| queue.depth() == 0).await;
+
+        // Confirmed settlements stay paid; the reverted one has its debt back.
+        assert_eq!(balance(peers[0]), Au::ZERO);
+        assert_eq!(balance(peers[1]), Au::ZERO);
+        assert_eq!(balance(peers[2]), -DEBT);
+    }
+
+    fn test_accounting() -> Arc<TestAccounting> {
+        Arc::new(TestAccounting::new(
+            BandwidthConfig::default(),
+            test_identity(),
+        ))
+    }
+
+    fn stalled_chain(accounting: &Arc<TestAccounting>) -> Arc<SlowChain> {
+        Arc::new(SlowChain {
+            accounting: accounting.clone(),
+            confirmations: Semaphore::new(0),
+            reverts: test_overlay(0),
+        })
+    }
+
+    #[test]
+    fn enqueue_rejects_duplicate_peer_and_full_queue() {
+        let accounting = test_accounting();
+        let (queue, receiver) = settlement_queue(1);
+        let _worker =
+            SettlementWorker::new(receiver, accounting.clone(), stalled_chain(&accounting));
+
+        queue.enqueue(test_overlay(1), DEBT).unwrap();
+        assert!(matches!(
+            queue.enqueue(test_overlay(1), DEBT),
+            Err(SwapSettlementError::SettlementInProgress)
+        ));
+        assert!(matches!(
+            queue.enqueue(test_overlay(2), DEBT),
+            Err(SwapSettlementError::QueueFull)
+        ));
+        assert_eq!(
+            queue.stats(),
+            SettlementQueueStats {
+                depth: 1,
+                capacity: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn enqueue_without_worker_credits_nothing() {
+        let (queue, _receiver) = settlement_queue(1);
+
+        assert!(matches!(
+            queue.enqueue(test_overlay(1), DEBT),
+            Err(SwapSettlementError::ServiceStopped)
+        ));
+        assert_eq!(queue.depth(), 0);
+    }
+
+    #[tokio::test]
+    async fn drain_reverses_unresolved_settlements() {
+        let accounting = test_accounting();
+        let peers = [test_overlay(1), test_overlay(2)];
+        for peer in peers {
+            accounting.for_peer(peer).record(DEBT, Direction::Download);
+        }
+        let (queue, receiver) = settlement_queue(DEFAULT_SETTLEMENT_QUEUE_CAPACITY);
+        let mut worker =
+            SettlementWorker::new(receiver, accounting.clone(), stalled_chain(&accounting));
+
+        for peer in peers {
+            queue.enqueue(peer, DEBT).unwrap();
+            assert_eq!(accounting.for_peer(peer).balance(), Au::ZERO);
+        }
+
+        worker.drain().await;
+
+        assert_eq!(queue.depth(), 0);
+        for peer in peers {
+            assert_eq!(accounting.for_peer(peer).balance(), -DEBT);
+        }
+        assert!(matches!(
+            queue.enqueue(peers[0], DEBT),
+            Err(SwapSettlementError::ServiceStopped)
+        ));
+        assert_eq!(accounting.for_peer(peers[0]).balance(), -DEBT);
+    }
+}
@@ -10,6 +10,7 @@
 //! - **Swap**: Chequebook-based real payments
 
 use core::future::Future;
+use core::time::Duration;
 use std::vec::Vec;
 
 use nectar_primitives::ChunkAddress;
@@ -70,6 +71,12 @@ pub trait SwarmAccountingConfig: Send + Sync {
     /// Scaling factor for client-only nodes (divides thresholds).
     fn client_only_factor(&self) -> u64;
 
+    /// How far behind our clock a pseudosettle ack timestamp may be and still
+    /// be trusted.
+    fn clock_skew_tolerance(&self) -> Duration {
+        Duration::from_secs(crate::DEFAULT_CLOCK_SKEW_TOLERANCE_SECS)
+    }
+
     /// The disconnect threshold in AU: the payment threshold plus the tolerance
     /// markup, saturating so an overlarge threshold or tolerance cannot wrap.
     fn disconnect_threshold(&self) -> Au {
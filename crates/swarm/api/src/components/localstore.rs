@@ -1,8 +1,10 @@
 //! Local chunk storage.
 
-use crate::SwarmResult;
-use nectar_primitives::ChunkAddress;
-use vertex_swarm_primitives::CachedChunk;
+use std::sync::Arc;
+
+use crate::{SwarmError, SwarmResult, SwarmSpec};
+use nectar_primitives::{AnyChunk, ChunkAddress, ChunkTypeSet, StandardChunkSet};
+use vertex_swarm_primitives::{CachedChunk, Stamp, ValidatedChunk};
 
 /// Configuration for a local chunk store.
 ///
@@ -46,3 +48,123 @@ pub trait SwarmLocalStore: Send + Sync {
     /// Remove a chunk from local storage.
     fn remove(&self, address: &ChunkAddress) -> SwarmResult<()>;
 }
+
+/// Validated inserts over any [`SwarmLocalStore`].
+///
+/// [`put_validated`](Self::put_validated) takes a [`ValidatedChunk`] and so
+/// cannot skip the chunk-set check; [`put_network`](Self::put_network)
+/// validates a chunk received from the network and inserts it. Network code
+/// holds a [`NetworkStore`], which offers only the validated insert.
+pub trait SwarmLocalStoreExt: SwarmLocalStore {
+    /// Insert a chunk validated against `C`, with its optional stamp.
+    fn put_validated<C: ChunkTypeSet>(
+        &self,
+        chunk: ValidatedChunk<C>,
+        stamp: Option<Stamp>,
+    ) -> SwarmResult<()> {
+        self.put(CachedChunk::new(chunk.into_inner(), stamp))
+    }
+
+    /// Validate a chunk received from the network against `C` and insert it.
+    fn put_network<C: ChunkTypeSet>(
+        &self,
+        chunk: AnyChunk,
+        stamp: Option<Stamp>,
+    ) -> SwarmResult<()> {
+        let address = *chunk.address();
+        let chunk = ValidatedChunk::<C>::new(chunk).map_err(|e| SwarmError::InvalidChunk {
+            address: Some(address),
+            reason: e.to_string(),
+        })?;
+        self.put_validated(chunk, stamp)
+    }
+}
+
+impl<S: SwarmLocalStore + ?Sized> SwarmLocalStoreExt for S {}
+
+/// The network's chunk set, erased so non-generic ingest paths can validate
+/// against it.
+#[derive(Debug, Clone, Copy)]
+pub struct NetworkIngest {
+    put: fn(&dyn SwarmLocalStore, AnyChunk, Option<Stamp>) -> SwarmResult<()>,
+}
+
+impl NetworkIngest {
+    /// Validate against the chunk set `C`.
+    #[must_use]
+    pub fn for_chunk_set<C: ChunkTypeSet>() -> Self {
+        Self {
+            put: put_network::<C>,
+        }
+    }
+
+    /// Validate against the chunk set of the network spec `S`.
+    #[must_use]
+    pub fn for_spec<S: SwarmSpec>() -> Self {
+        Self::for_chunk_set::<S::ChunkSet>()
+    }
+
+    /// Validate `chunk` and insert it into `store`.
+    pub fn put(
+        &self,
+        store: &dyn SwarmLocalStore,
+        chunk: AnyChunk,
+        stamp: Option<Stamp>,
+    ) -> SwarmResult<()> {
+        (self.put)(store, chunk, stamp)
+    }
+}
+
+impl Default for NetworkIngest {
+    /// The standard chunk set: content and single-owner chunks.
+    fn default() -> Self {
+        Self::for_chunk_set::<StandardChunkSet>()
+    }
+}
+
+fn put_network<C: ChunkTypeSet>(
+    store: &dyn SwarmLocalStore,
+    chunk: AnyChunk,
+    stamp: Option<Stamp>,
+) -> SwarmResult<()> {
+    store.put_network::<C>(chunk, stamp)
+}
+
+/// A store as network code sees it: reads, and inserts validated by a
+/// [`NetworkIngest`]. It has no raw put, so a chunk a peer sent cannot reach
+/// the store unchecked.
+#[derive(Clone)]
+pub struct NetworkStore {
+    store: Arc<dyn SwarmLocalStore>,
+    ingest: NetworkIngest,
+}
+
+impl NetworkStore {
+    /// Wrap `store`, validating inserts with `ingest`.
+    pub fn new(store: Arc<dyn SwarmLocalStore>, ingest: NetworkIngest) -> Self {
+        Self { store, ingest }
+    }
+
+    /// Validate a chunk received from the network and insert it.
+    pub fn put(&self, chunk: AnyChunk, stamp: Option<Stamp>) -> SwarmResult<()> {
+        self.ingest.put(&*self.store, chunk, stamp)
+    }
+
+    /// Fetch a stored chunk and its optional stamp, or `None` on a miss.
+    pub fn get(&self, address: &ChunkAddress) -> SwarmResult<Option<CachedChunk>> {
+        self.store.get(address)
+    }
+
+    /// Check if a chunk exists locally.
+    pub fn contains(&self, address: &ChunkAddress) -> bool {
+        self.store.contains(address)
+    }
+}
+
+impl std::fmt::Debug for NetworkStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NetworkStore")
+            .field("ingest", &self.ingest)
+            .finish_non_exhaustive()
+    }
+}
@@ -5,6 +5,7 @@ mod localstore;
 mod peers;
 mod pricing;
 mod pullsync;
+mod pushsync;
 mod reserve;
 mod topology;
 
@@ -13,17 +14,21 @@ pub use self::bandwidth::{
     SwarmBandwidthAccounting, SwarmClientAccounting, SwarmPeerBandwidth, SwarmPeerState,
     SwarmSettlementProvider,
 };
-pub use self::localstore::{SwarmLocalStore, SwarmLocalStoreConfig};
+pub use self::localstore::{
+    NetworkIngest, NetworkStore, SwarmLocalStore, SwarmLocalStoreConfig, SwarmLocalStoreExt,
+};
 pub use self::peers::SwarmPeerResolver;
 pub use self::pricing::{SwarmPricing, SwarmPricingBuilder, SwarmPricingConfig};
 pub use self::pullsync::{IntervalStore, PullChunkVerifier, PullStorage, VerifyError};
+pub use self::pushsync::{StampPolicy, StampVerifier};
 pub use self::reserve::{BinCursorStore, BinScanItem, ReserveStore, SettableRadius};
 pub use self::topology::{
+    BinDepth, ConnectionAuditRecord, ConnectionAuditStage, DepthExplanation, DepthLimit,
     SwarmTopology, SwarmTopologyBins, SwarmTopologyCommands, SwarmTopologyPeers,
     SwarmTopologyReporting, SwarmTopologyRouting, SwarmTopologyState, SwarmTopologyStats,
 };
 
-use crate::SwarmIdentity;
+use crate::{DiagnosticsHandle, FinanceHandle, SwarmIdentity};
 
 /// Topology access.
 #[auto_impl::auto_impl(&, Arc, Box)]
@@ -78,17 +83,35 @@ pub trait HasReserve: HasStore {
     fn reserve(&self) -> &Self::Reserve;
 }
 
+/// Diagnostic snapshot access (all levels).
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait HasDiagnostics: Send + Sync {
+    /// Get the diagnostics source.
+    fn diagnostics(&self) -> &DiagnosticsHandle;
+}
+
+/// On-chain funds access (chain-connected client/storer levels).
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait HasFinance: Send + Sync {
+    /// Get the finance source, or `None` when the node has no chain.
+    fn finance(&self) -> Option<&FinanceHandle>;
+}
+
 /// Bootnode components (topology only). Identity via `topology().identity()`.
 ///
 /// Construction is builder-exclusive; see [`construct`].
 #[derive(Debug, Clone)]
 pub struct BootnodeComponents<T> {
     topology: T,
+    diagnostics: DiagnosticsHandle,
 }
 
 impl<T> BootnodeComponents<T> {
-    pub(crate) fn new(topology: T) -> Self {
-        Self { topology }
+    pub(crate) fn new(topology: T, diagnostics: DiagnosticsHandle) -> Self {
+        Self {
+            topology,
+            diagnostics,
+        }
     }
 }
 
@@ -100,7 +123,20 @@ impl<T: Send + Sync> HasTopology for BootnodeComponents<T> {
     }
 }
 
-/// Client components (topology + chunk client).
+impl<T: Send + Sync> HasDiagnostics for BootnodeComponents<T> {
+    fn diagnostics(&self) -> &DiagnosticsHandle {
+        &self.diagnostics
+    }
+}
+
+/// Bootnodes hold no wallet funds.
+impl<T: Send + Sync> HasFinance for BootnodeComponents<T> {
+    fn finance(&self) -> Option<&FinanceHandle> {
+        None
+    }
+}
+
+/// Client components (topology + chunk client, plus finance with a chain).
 ///
 /// Accounting is intentionally absent: it is a builder-wired internal of the
 /// network chunk client, not a served capability.
@@ -110,13 +146,22 @@ impl<T: Send + Sync> HasTopology for BootnodeComponents<T> {
 pub struct ClientComponents<T, C> {
     topology: T,
     chunk_client: C,
+    diagnostics: DiagnosticsHandle,
+    finance: Option<FinanceHandle>,
 }
 
 impl<T, C> ClientComponents<T, C> {
-    pub(crate) fn new(topology: T, chunk_client: C) -> Self {
+    pub(crate) fn new(
+        topology: T,
+        chunk_client: C,
+        diagnostics: DiagnosticsHandle,
+        finance: Option<FinanceHandle>,
+    ) -> Self {
         Self {
             topology,
             chunk_client,
+            diagnostics,
+            finance,
         }
     }
 }
@@ -137,6 +182,18 @@ impl<T: Send + Sync, C: Send + Sync> HasChunkClient for ClientComponents<T, C> {
     }
 }
 
+impl<T: Send + Sync, C: Send + Sync> HasDiagnostics for ClientComponents<T, C> {
+    fn diagnostics(&self) -> &DiagnosticsHandle {
+        &self.diagnostics
+    }
+}
+
+impl<T: Send + Sync, C: Send + Sync> HasFinance for ClientComponents<T, C> {
+    fn finance(&self) -> Option<&FinanceHandle> {
+        self.finance.as_ref()
+    }
+}
+
 /// Storer components (client + local store + reserve).
 ///
 /// `S` is the retrieval-serve view ([`HasStore`]); `R` is the proximity-ordered
@@ -150,9 +207,16 @@ pub struct StorerComponents<T, C, S, R> {
 }
 
 impl<T, C, S, R> StorerComponents<T, C, S, R> {
-    pub(crate) fn new(topology: T, chunk_client: C, store: S, reserve: R) -> Self {
+    pub(crate) fn new(
+        topology: T,
+        chunk_client: C,
+        store: S,
+        reserve: R,
+        diagnostics: DiagnosticsHandle,
+        finance: Option<FinanceHandle>,
+    ) -> Self {
         Self {
-            client: ClientComponents::new(topology, chunk_client),
+            client: ClientComponents::new(topology, chunk_client, diagnostics, finance),
             store,
             reserve,
         }
@@ -179,6 +243,22 @@ impl<T: Send + Sync, C: Send + Sync, S: Send + Sync, R: Send + Sync> HasChunkCli
     }
 }
 
+impl<T: Send + Sync, C: Send + Sync, S: Send + Sync, R: Send + Sync> HasDiagnostics
+    for StorerComponents<T, C, S, R>
+{
+    fn diagnostics(&self) -> &DiagnosticsHandle {
+        self.client.diagnostics()
+    }
+}
+
+impl<T: Send + Sync, C: Send + Sync, S: Send + Sync, R: Send + Sync> HasFinance
+    for StorerComponents<T, C, S, R>
+{
+    fn finance(&self) -> Option<&FinanceHandle> {
+        self.client.finance()
+    }
+}
+
 impl<T: Send + Sync, C: Send + Sync, S: Send + Sync, R: Send + Sync> HasStore
     for StorerComponents<T, C, S, R>
 {
@@ -208,13 +288,19 @@ impl<T: Send + Sync, C: Send + Sync, S: Send + Sync, R: BinCursorStore> HasReser
 #[doc(hidden)]
 pub mod construct {
     use super::{BootnodeComponents, ClientComponents, StorerComponents};
+    use crate::{DiagnosticsHandle, FinanceHandle};
 
-    pub fn bootnode<T>(topology: T) -> BootnodeComponents<T> {
-        BootnodeComponents::new(topology)
+    pub fn bootnode<T>(topology: T, diagnostics: DiagnosticsHandle) -> BootnodeComponents<T> {
+        BootnodeComponents::new(topology, diagnostics)
     }
 
-    pub fn client<T, C>(topology: T, chunk_client: C) -> ClientComponents<T, C> {
-        ClientComponents::new(topology, chunk_client)
+    pub fn client<T, C>(
+        topology: T,
+        chunk_client: C,
+        diagnostics: DiagnosticsHandle,
+        finance: Option<FinanceHandle>,
+    ) -> ClientComponents<T, C> {
+        ClientComponents::new(topology, chunk_client, diagnostics, finance)
     }
 
     pub fn storer<T, C, S, R>(
@@ -222,7 +308,9 @@ pub mod construct {
         chunk_client: C,
         store: S,
         reserve: R,
+        diagnostics: DiagnosticsHandle,
+        finance: Option<FinanceHandle>,
     ) -> StorerComponents<T, C, S, R> {
-        StorerComponents::new(topology, chunk_client, store, reserve)
+        StorerComponents::new(topology, chunk_client, store, reserve, diagnostics, finance)
     }
 }
@@ -0,0 +1,43 @@
+//! Pushsync ingest trait surface: the stamp verification seam and the policy
+//! for deliveries that arrive without a stamp.
+
+use nectar_primitives::AnyChunk;
+use vertex_swarm_primitives::Stamp;
+
+use crate::{SwarmSpec, VerifyError};
+
+/// Stamp check a storer runs on a pushed chunk before taking custody.
+///
+/// Runs after the stamp signature is recovered, so an impl only adds the
+/// checks it cares about (batch ownership, funding, bucket bounds). Rejections
+/// share the pull-sync [`VerifyError`] taxonomy, so an unknown batch reads the
+/// same on both ingest paths.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait StampVerifier: Send + Sync {
+    /// Verify `stamp` for `chunk`.
+    fn verify(&self, chunk: &AnyChunk, stamp: &Stamp) -> Result<(), VerifyError>;
+}
+
+/// How a storer treats the stamp on a pushed chunk.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StampPolicy {
+    /// Verify every stamp and refuse unstamped deliveries.
+    #[default]
+    Required,
+    /// Storage is free: store deliveries with or without a stamp and verify
+    /// none of them.
+    Optional,
+}
+
+impl StampPolicy {
+    /// [`Optional`](Self::Optional) when the spec waives storage payment
+    /// ([`SwarmSpec::requires_storage_payment`]), otherwise
+    /// [`Required`](Self::Required).
+    pub fn for_spec(spec: &impl SwarmSpec) -> Self {
+        if spec.requires_storage_payment() {
+            Self::Required
+        } else {
+            Self::Optional
+        }
+    }
+}
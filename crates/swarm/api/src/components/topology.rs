@@ -4,10 +4,10 @@ use core::future::Future;
 use std::sync::Arc;
 use std::vec::Vec;
 
-use nectar_primitives::{ChunkAddress, NetworkId};
+use nectar_primitives::{ChunkAddress, NetworkId, recompute_neighborhood_depth};
 
-use crate::PeerReporter;
-use vertex_swarm_primitives::{Bin, NeighborhoodDepth, OverlayAddress};
+use crate::{DEFAULT_NEIGHBORHOOD_LOW_WATERMARK, DEFAULT_SATURATION_PEERS, PeerReporter};
+use vertex_swarm_primitives::{Bin, NeighborhoodDepth, OverlayAddress, all_bins};
 
 /// Bin sizes for topology routing (one per proximity order).
 #[auto_impl::auto_impl(&, Arc)]
@@ -95,6 +95,184 @@ pub trait SwarmTopologyStats: SwarmTopologyBins {
 
     /// Total peers persisted in the backing store.
     fn stored_peers_count(&self) -> usize;
+
+    /// Connects plus disconnects per minute over the recent window.
+    fn churn_rate(&self) -> f64;
+
+    /// Why the neighbourhood depth sits where it does.
+    ///
+    /// The default derives it from [`SwarmTopologyBins::bin_sizes`] with the
+    /// default saturation and [`DEFAULT_NEIGHBORHOOD_LOW_WATERMARK`]; a routing
+    /// table that knows its own parameters should override it.
+    fn depth_explanation(&self) -> DepthExplanation {
+        let connected: Vec<usize> = self.bin_sizes().iter().map(|(c, _)| *c).collect();
+        DepthExplanation::new(
+            &connected,
+            DEFAULT_SATURATION_PEERS,
+            DEFAULT_NEIGHBORHOOD_LOW_WATERMARK,
+            Bin::new(nectar_primitives::MAX_PO).unwrap_or(Bin::MAX),
+        )
+    }
+
+    /// The most recent connection audit records, at most `limit`, oldest
+    /// first.
+    ///
+    /// The default keeps no audit log and returns nothing.
+    fn connection_audit(&self, _limit: usize) -> Vec<ConnectionAuditRecord> {
+        Vec::new()
+    }
+}
+
+/// A step in a connection's lifecycle, as recorded in the audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum ConnectionAuditStage {
+    /// An outbound dial was started.
+    DialStarted,
+    /// An outbound dial exhausted its addresses without connecting.
+    DialFailed,
+    /// A transport connection was established, in either direction.
+    Connected,
+    /// The handshake authenticated the peer's overlay.
+    HandshakeCompleted,
+    /// The handshake failed; the connection is torn down.
+    HandshakeFailed,
+    /// The peer was admitted into the routing table.
+    Activated,
+    /// The last connection to the peer closed.
+    Closed,
+}
+
+/// One structured entry in the connection audit log.
+///
+/// Kept apart from general logs so the history of a single connection can be
+/// reconstructed after the fact: each record names the peer, the stage it
+/// reached and, where the stage has one, why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionAuditRecord {
+    /// Wall-clock time of the step, in Unix milliseconds.
+    pub at_unix_ms: u64,
+    /// The remote peer.
+    pub peer_id: libp2p::PeerId,
+    /// The peer's overlay, once known (dialed record or handshake).
+    pub overlay: Option<OverlayAddress>,
+    /// The lifecycle step.
+    pub stage: ConnectionAuditStage,
+    /// Stage detail in snake_case: the dial reason, connection direction,
+    /// failure kind or close reason.
+    pub detail: Option<&'static str>,
+}
+
+/// One bin's contribution to the depth calculation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BinDepth {
+    /// The bin's proximity order.
+    pub bin: Bin,
+    /// Connected peers counted toward depth in this bin.
+    pub connected: usize,
+    /// Connected peers in this bin and every deeper bin, the count the low
+    /// watermark is checked against.
+    pub cumulative: usize,
+    /// Whether the bin holds at least the saturation target.
+    pub saturated: bool,
+}
+
+/// What holds the neighbourhood depth at its current value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum DepthLimit {
+    /// The depth bin is the shallowest unsaturated bin: depth cannot pass it
+    /// until it holds `missing` more connected peers.
+    Unsaturated {
+        /// The unsaturated bin the depth stops at.
+        bin: Bin,
+        /// Peers short of the saturation target.
+        missing: usize,
+    },
+    /// The bins deeper than the depth hold too few peers to anchor a deeper
+    /// neighbourhood: they need `missing` more to reach the low watermark.
+    LowWatermark {
+        /// Peers the deeper bins are short of the low watermark.
+        missing: usize,
+    },
+    /// Depth is at the maximum proximity order.
+    MaxPo,
+}
+
+/// A breakdown of the depth calculation, for operators asking why depth is
+/// not increasing.
+///
+/// Depth is the shallowest unsaturated bin, pulled shallower when the bins
+/// deeper than it hold fewer than the low watermark. `bins` lists every bin
+/// from the maximum proximity order down to zero, and `limit` names which of
+/// the two rules binds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepthExplanation {
+    /// The depth the bin counts produce, before any lowering hysteresis.
+    pub depth: NeighborhoodDepth,
+    /// Connected peers a bin needs to count as saturated.
+    pub saturation: u8,
+    /// Connected peers the bins at and beyond the depth must hold.
+    pub low_watermark: u8,
+    /// Per-bin counts, deepest first.
+    pub bins: Vec<BinDepth>,
+    /// The rule holding depth where it is.
+    pub limit: DepthLimit,
+}
+
+impl DepthExplanation {
+    /// Explain the depth `connected` (per-bin connected counts, shallowest
+    /// first) produces under `saturation` and `low_watermark`, capped at
+    /// `max`. Bins missing from `connected` count as empty.
+    pub fn new(connected: &[usize], saturation: u8, low_watermark: u8, max: Bin) -> Self {
+        let count = |bin: Bin| connected.get(bin.as_index()).copied().unwrap_or(0);
+
+        let mut counts = [0u8; 32];
+        for (slot, size) in counts.iter_mut().zip(connected) {
+            *slot = u8::try_from(*size).unwrap_or(u8::MAX);
+        }
+        let computed = recompute_neighborhood_depth(&counts, saturation, low_watermark);
+        let depth = NeighborhoodDepth::new(Bin::new(computed.get().min(max.get())).unwrap_or(max));
+
+        let mut cumulative = 0;
+        let bins: Vec<BinDepth> = all_bins(max)
+            .rev()
+            .map(|bin| {
+                cumulative += count(bin);
+                BinDepth {
+                    bin,
+                    connected: count(bin),
+                    cumulative,
+                    saturated: count(bin) >= usize::from(saturation),
+                }
+            })
+            .collect();
+
+        let limit = if depth.bin() == max {
+            DepthLimit::MaxPo
+        } else if count(depth.bin()) < usize::from(saturation) {
+            DepthLimit::Unsaturated {
+                bin: depth.bin(),
+                missing: usize::from(saturation) - count(depth.bin()),
+            }
+        } else {
+            let deeper: usize = all_bins(max)
+                .filter(|bin| depth.contains(*bin) && *bin != depth.bin())
+                .map(count)
+                .sum();
+            DepthLimit::LowWatermark {
+                missing: usize::from(low_watermark).saturating_sub(deeper),
+            }
+        };
+
+        Self {
+            depth,
+            saturation,
+            low_watermark,
+            bins,
+            limit,
+        }
+    }
 }
 
 /// Write operations for topology control.
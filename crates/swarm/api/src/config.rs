@@ -1,11 +1,12 @@
 //! Configuration traits for Swarm protocol components.
 
 use core::future::Future;
+use core::net::IpAddr;
 use core::time::Duration;
 
 use libp2p::Multiaddr;
 use vertex_node_api::InfrastructureContext;
-use vertex_swarm_primitives::ConnectionProfile;
+use vertex_swarm_primitives::{ConnectionProfile, OverlayAddress, SwarmNodeType};
 
 use crate::components::{SwarmAccountingConfig, SwarmLocalStoreConfig, SwarmPricingConfig};
 use crate::{SwarmClientTypes, SwarmNetworkTypes, SwarmStorerTypes};
@@ -17,6 +18,18 @@ pub use vertex_tasks::{NodeTask, NodeTaskFn};
 pub trait SwarmStorageConfig {
     /// Whether this node participates in redistribution.
     fn redistribution_enabled(&self) -> bool;
+
+    /// Inbound chunk validations run at once off the event loop; the rest
+    /// queue.
+    fn validation_concurrency(&self) -> usize {
+        DEFAULT_VALIDATION_CONCURRENCY
+    }
+
+    /// Whether a push for a chunk outside the storage radius is refused
+    /// rather than relayed towards its neighbourhood (default: relayed).
+    fn refuse_out_of_range(&self) -> bool {
+        false
+    }
 }
 
 /// Configuration for Swarm routing.
@@ -62,6 +75,22 @@ const _: () = {
 /// Default maximum peers per proximity bin in the index.
 pub const DEFAULT_PEER_MAX_PER_BIN: usize = 128;
 
+/// Default time a resolved `/dnsaddr/` bootnode is reused without a new lookup.
+pub const DEFAULT_DNS_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Default capacity of the node's client command channel. A request that finds
+/// it full waits for room rather than queueing without bound.
+pub const DEFAULT_COMMAND_CHANNEL_CAPACITY: usize = 256;
+
+/// Default cap on concurrent inbound chunk validations (stamp signature
+/// recovery on the pushsync ingest path).
+pub const DEFAULT_VALIDATION_CONCURRENCY: usize = 4;
+
+/// Default clock-skew tolerance for a pseudosettle ack timestamp, in seconds.
+/// Covers honest peer and NTP skew; see the pseudosettle service for the
+/// attacks a tighter window denies.
+pub const DEFAULT_CLOCK_SKEW_TOLERANCE_SECS: u64 = 60;
+
 /// Configuration for peer management (scoring, limits).
 pub trait SwarmPeerConfig {
     /// The peer management configuration type.
@@ -146,14 +175,42 @@ pub trait SwarmNetworkConfig {
         &[]
     }
 
+    /// Static peers (parsed): each overlay with the address it is dialed on.
+    /// The topology keeps them connected and exempt from bin limits.
+    fn static_peers(&self) -> &[(OverlayAddress, Multiaddr)] {
+        &[]
+    }
+
+    /// DNS servers for `/dnsaddr/` resolution; empty uses the system resolver.
+    fn dns_servers(&self) -> &[IpAddr] {
+        &[]
+    }
+
+    /// How long a `/dnsaddr/` resolution is reused before looking it up
+    /// again. An expired entry still backs a failed lookup.
+    fn dns_cache_ttl(&self) -> Duration {
+        DEFAULT_DNS_CACHE_TTL
+    }
+
     /// Whether peer discovery is enabled.
     fn discovery_enabled(&self) -> bool;
 
-    /// Maximum number of established peer connections.
+    /// Explicitly configured cap on established peer connections, if any.
+    ///
+    /// `None` means the cap follows the node type; see
+    /// [`Self::effective_max_peers`].
+    fn max_peers(&self) -> Option<usize>;
+
+    /// Cap on established peer connections for a node of `node_type`.
     ///
     /// Enforced at the transport layer as a hard cap on total established
-    /// connections, independent of the topology's per-bin accounting.
-    fn max_peers(&self) -> usize;
+    /// connections, independent of the topology's per-bin accounting. An
+    /// explicit [`Self::max_peers`] wins; otherwise
+    /// [`SwarmNodeType::default_max_peers`] applies.
+    fn effective_max_peers(&self, node_type: SwarmNodeType) -> usize {
+        self.max_peers()
+            .unwrap_or_else(|| node_type.default_max_peers())
+    }
 
     /// Connection idle timeout.
     fn idle_timeout(&self) -> Duration;
@@ -182,6 +239,13 @@ pub trait SwarmNetworkConfig {
         false
     }
 
+    /// Whether to offer zstd compression of large protocol payloads to peers
+    /// (default: false). Used only with peers that offer it too, so enabling it
+    /// never changes what a peer without it sees on the wire.
+    fn compression_enabled(&self) -> bool {
+        false
+    }
+
     /// Whether mDNS local peer discovery is enabled (default: true). Lets two
     /// nodes on the same LAN discover and connect to each other without
     /// bootnodes or NAT configuration. The multicast traffic stays link-local.
@@ -199,6 +263,12 @@ pub trait SwarmNetworkConfig {
         true
     }
 
+    /// Capacity of the client command channel between request callers and the
+    /// network event loop (default: [`DEFAULT_COMMAND_CHANNEL_CAPACITY`]).
+    fn command_channel_capacity(&self) -> usize {
+        DEFAULT_COMMAND_CHANNEL_CAPACITY
+    }
+
     /// Explicitly selected connection pacing profile, if any (default: none).
     ///
     /// `None` means the topology derives the profile from the node type via
@@ -206,6 +276,43 @@ pub trait SwarmNetworkConfig {
     fn connection_profile(&self) -> Option<ConnectionProfile> {
         None
     }
+
+    /// Explicit cap on outbound dials in flight at once, if any (default:
+    /// none). `None` keeps the connection profile's dial concurrency.
+    fn max_concurrent_dials(&self) -> Option<usize> {
+        None
+    }
+
+    /// Connects plus disconnects per minute above which the topology warns
+    /// of peer churn, if any (default: none, which disables the alert).
+    fn churn_threshold(&self) -> Option<f64> {
+        None
+    }
+
+    /// Cadence at which the node re-signs its own record and sends it to
+    /// connected neighbors, if any (default: none, which announces it only
+    /// in the handshake).
+    fn self_announce_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Activated peers required before an inbound retrieval that misses the
+    /// local store is forwarded (default: 0, which disables the gate).
+    fn min_peers_to_serve(&self) -> usize {
+        0
+    }
+
+    /// Gossip exchanges with newly activated peers that may go out back to
+    /// back, if set (default: none, which keeps the gossip default).
+    fn exchange_burst(&self) -> Option<usize> {
+        None
+    }
+
+    /// Time to earn one more gossip exchange once the burst is spent, if set
+    /// (default: none, which keeps the gossip default).
+    fn exchange_interval(&self) -> Option<Duration> {
+        None
+    }
 }
 
 /// Configuration for Swarm node identity.
@@ -0,0 +1,258 @@
+//! Point-in-time diagnostic snapshot of a running node.
+//!
+//! [`DiagnosticDump`] aggregates the observable state an operator attaches to a
+//! bug report: topology, connected peers with their multiaddrs and scores, the
+//! accounting ledger for those peers, storage occupancy, and the effective
+//! network config. Addresses are rendered as strings so the dump serializes
+//! (behind the `serde` feature) without pulling serde support into the address
+//! types. The gRPC `Dump` method and the `vertex dump` subcommand serve it.
+
+use core::fmt;
+use std::string::{String, ToString};
+use std::sync::Arc;
+use std::vec::Vec;
+
+use vertex_swarm_primitives::{OverlayAddress, SwarmAddressExt, SwarmNodeType};
+
+use crate::{
+    Au, LedgerSnapshot, Multiaddr, ReserveStore, SwarmNetworkConfig, SwarmTopologyState,
+    SwarmTopologyStats,
+};
+
+/// Full diagnostic snapshot of a node.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiagnosticDump {
+    /// Kademlia table summary.
+    pub topology: TopologyDiagnostics,
+    /// Connected peers, ascending by proximity order.
+    pub peers: Vec<PeerDiagnostics>,
+    /// Ledger state for each connected peer. Empty on bootnodes, which run no
+    /// accounting.
+    pub accounting: Vec<LedgerDiagnostics>,
+    /// Reserve occupancy. `None` on nodes without a reserve.
+    pub storage: Option<StorageDiagnostics>,
+    /// Effective network configuration.
+    pub config: ConfigDiagnostics,
+}
+
+/// Kademlia table summary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TopologyDiagnostics {
+    /// Local overlay address (hex).
+    pub overlay: String,
+    /// Network id the node participates in.
+    pub network_id: u64,
+    /// Current neighborhood depth.
+    pub depth: u8,
+    /// Whether the observed depth is credible (neighbourhood saturated).
+    pub neighbourhood_credible: bool,
+    /// Connected peers.
+    pub connected_peers: usize,
+    /// Peers in the routing table.
+    pub routing_peers: usize,
+    /// Pending connection attempts.
+    pub pending_connections: usize,
+    /// Peers persisted in the backing store.
+    pub stored_peers: usize,
+    /// Per-bin occupancy, one entry per proximity order.
+    pub bins: Vec<BinDiagnostics>,
+}
+
+impl TopologyDiagnostics {
+    /// Read the summary from a topology.
+    pub fn collect<T: SwarmTopologyState + SwarmTopologyStats + ?Sized>(topology: &T) -> Self {
+        let bins = topology
+            .bin_sizes()
+            .into_iter()
+            .enumerate()
+            .map(|(po, (connected, known))| BinDiagnostics {
+                proximity_order: po as u8,
+                connected,
+                known,
+            })
+            .collect();
+
+        Self {
+            overlay: topology.overlay_address().to_hex(),
+            network_id: topology.network_id().get(),
+            depth: topology.depth().get(),
+            neighbourhood_credible: topology.neighbourhood_credible(),
+            connected_peers: topology.connected_peers_count(),
+            routing_peers: topology.routing_peers_count(),
+            pending_connections: topology.pending_connections_count(),
+            stored_peers: topology.stored_peers_count(),
+            bins,
+        }
+    }
+}
+
+/// Occupancy of one Kademlia bin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinDiagnostics {
+    /// Proximity order of the bin.
+    pub proximity_order: u8,
+    /// Connected peers in the bin.
+    pub connected: usize,
+    /// Known (discovered) peers in the bin.
+    pub known: usize,
+}
+
+/// A connected peer.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeerDiagnostics {
+    /// Overlay address (hex).
+    pub overlay: String,
+    /// Proximity order relative to the local overlay.
+    pub proximity_order: u8,
+    /// Multiaddrs, including the `/p2p/<peer_id>` suffix.
+    pub multiaddrs: Vec<String>,
+    /// Current peer score, or `None` when the peer manager has no record.
+    pub score: Option<f64>,
+    /// How the peer was first learned (`hive`, `bootnode`, `static`, ...), or
+    /// `None` when no source was recorded.
+    pub discovery_source: Option<String>,
+}
+
+/// Ledger state for one peer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LedgerDiagnostics {
+    /// Overlay address (hex).
+    pub overlay: String,
+    /// Signed balance: positive means the peer owes us.
+    pub balance: Au,
+    /// Outstanding receive reservation.
+    pub reserved: Au,
+    /// Raw disconnect threshold.
+    pub disconnect_line: Au,
+    /// Raw early-payment settle trigger.
+    pub settle_trigger: Au,
+}
+
+impl LedgerDiagnostics {
+    /// Render a ledger snapshot for `peer`.
+    pub fn new(peer: &OverlayAddress, snapshot: LedgerSnapshot) -> Self {
+        Self {
+            overlay: peer.to_hex(),
+            balance: snapshot.balance,
+            reserved: snapshot.reserved,
+            disconnect_line: snapshot.disconnect_line,
+            settle_trigger: snapshot.settle_trigger,
+        }
+    }
+}
+
+/// Reserve occupancy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageDiagnostics {
+    /// Current storage radius.
+    pub storage_radius: u8,
+    /// Chunks held in the reserve, or `None` when the count could not be read.
+    pub reserve_count: Option<u64>,
+    /// Reserve capacity in chunks.
+    pub reserve_capacity: u64,
+}
+
+impl StorageDiagnostics {
+    /// Read occupancy from a reserve.
+    pub fn from_reserve<R: ReserveStore + ?Sized>(reserve: &R) -> Self {
+        Self {
+            storage_radius: reserve.storage_radius().get(),
+            reserve_count: reserve.count().ok(),
+            reserve_capacity: reserve.capacity(),
+        }
+    }
+}
+
+/// Effective network configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigDiagnostics {
+    /// Node type the node launched as.
+    pub node_type: SwarmNodeType,
+    /// Listen multiaddrs.
+    pub listen_addrs: Vec<String>,
+    /// Bootnode multiaddrs.
+    pub bootnodes: Vec<String>,
+    /// Trusted peer multiaddrs.
+    pub trusted_peers: Vec<String>,
+    /// Advertised NAT multiaddrs.
+    pub nat_addrs: Vec<String>,
+    /// Whether peer discovery is enabled.
+    pub discovery_enabled: bool,
+    /// Cap on established connections.
+    pub max_peers: usize,
+    /// Connection idle timeout in seconds.
+    pub idle_timeout_secs: u64,
+    /// Whether auto-NAT from observed addresses is enabled.
+    pub nat_auto_enabled: bool,
+    /// Whether AutoNAT v2 is enabled.
+    pub autonat_enabled: bool,
+    /// Whether UPnP port mapping is enabled.
+    pub upnp_enabled: bool,
+    /// Whether mDNS discovery is enabled.
+    pub mdns_enabled: bool,
+}
+
+impl ConfigDiagnostics {
+    /// Render the effective network config for a node of `node_type`.
+    pub fn from_network<N: SwarmNetworkConfig + ?Sized>(
+        node_type: SwarmNodeType,
+        network: &N,
+    ) -> Self {
+        Self {
+            node_type,
+            listen_addrs: render(network.listen_addrs()),
+            bootnodes: render(network.bootnodes()),
+            trusted_peers: render(network.trusted_peers()),
+            nat_addrs: render(network.nat_addrs()),
+            discovery_enabled: network.discovery_enabled(),
+            max_peers: network.effective_max_peers(node_type),
+            idle_timeout_secs: network.idle_timeout().as_secs(),
+            nat_auto_enabled: network.nat_auto_enabled(),
+            autonat_enabled: network.autonat_enabled(),
+            upnp_enabled: network.upnp_enabled(),
+            mdns_enabled: network.mdns_enabled(),
+        }
+    }
+}
+
+fn render(addrs: &[Multiaddr]) -> Vec<String> {
+    addrs.iter().map(ToString::to_string).collect()
+}
+
+/// Source of [`DiagnosticDump`] snapshots.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait SwarmDiagnostics: Send + Sync {
+    /// Capture the node's current observable state.
+    fn diagnostic_dump(&self) -> DiagnosticDump;
+}
+
+/// Shared, type-erased [`SwarmDiagnostics`] source held by the component
+/// containers.
+#[derive(Clone)]
+pub struct DiagnosticsHandle(Arc<dyn SwarmDiagnostics>);
+
+impl DiagnosticsHandle {
+    /// Wrap a diagnostics source.
+    pub fn new(source: impl SwarmDiagnostics + 'static) -> Self {
+        Self(Arc::new(source))
+    }
+}
+
+impl SwarmDiagnostics for DiagnosticsHandle {
+    fn diagnostic_dump(&self) -> DiagnosticDump {
+        self.0.diagnostic_dump()
+    }
+}
+
+impl fmt::Debug for DiagnosticsHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DiagnosticsHandle").finish_non_exhaustive()
+    }
+}
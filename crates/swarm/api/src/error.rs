@@ -3,7 +3,7 @@
 use libp2p::multiaddr;
 use nectar_primitives::ChunkAddress;
 use std::string::String;
-use vertex_swarm_primitives::OverlayAddress;
+use vertex_swarm_primitives::{AddressParseError, OverlayAddress};
 
 use crate::Au;
 
@@ -33,6 +33,21 @@ pub enum AccountingError {
         threshold: Au,
     },
 
+    /// Serving the peer would exceed its byte-rate bucket or the global cap.
+    ///
+    /// Independent of the balance: a well-settled peer can still be throttled.
+    #[error("peer {peer} rate limited, retry after {retry_after:?}")]
+    RateLimited {
+        /// The peer that was throttled.
+        peer: OverlayAddress,
+        /// Earliest moment a retry would be admitted.
+        retry_after: core::time::Duration,
+    },
+
+    /// The peer's accounting is frozen pending a settlement dispute.
+    #[error("peer {0} accounting is frozen")]
+    PeerFrozen(OverlayAddress),
+
     /// Peer not found.
     #[error("peer {0} not found")]
     PeerNotFound(OverlayAddress),
@@ -50,6 +65,32 @@ impl AccountingError {
     vertex_metrics::impl_record_error!("accounting_errors_total");
 }
 
+/// Why a retrieval exhausted its peers without serving the chunk.
+///
+/// When attempts failed in different ways the most specific reason wins:
+/// an invalid delivery over a miss, a miss over a timeout, and any answer
+/// from a peer over a request this node never sent. None of these claims the
+/// chunk is absent from the network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum RetrievalFailure {
+    /// No peer was known or admissible for the address, so nothing was asked.
+    NoPeers,
+    /// Every peer that answered failed to serve the chunk.
+    NotFound,
+    /// Every peer asked timed out, or the deadline ran out with none answering.
+    Timeout,
+    /// A peer delivered bytes that failed chunk validation.
+    InvalidChunk,
+    /// Our own credit gate refused every peer at its disconnect line, so no
+    /// request went out; once the settlements it triggered land, a retry may
+    /// pass.
+    Refused,
+    /// This node could not send the request: its command channel was full or
+    /// closed, or the request was cancelled.
+    Unavailable,
+}
+
 /// Error type for Swarm API operations.
 #[derive(Debug, thiserror::Error, strum::IntoStaticStr)]
 #[strum(serialize_all = "snake_case")]
@@ -60,10 +101,26 @@ pub enum SwarmError {
     /// not a claim of absence: the reachable entry points were tried and none
     /// served it, so a later request after reconnection or a topology change may
     /// still succeed.
-    #[error("retrieval exhausted all reachable peers for chunk: {address}")]
+    #[error("retrieval exhausted all reachable peers for chunk {address}: {reason}")]
     RetrievalExhausted {
         /// The address of the chunk that could not be retrieved.
         address: ChunkAddress,
+        /// The most specific reason across the attempts made.
+        reason: RetrievalFailure,
+    },
+
+    /// Retrieval ran out of its caller-set latency budget before any peer
+    /// served the chunk.
+    ///
+    /// Like [`RetrievalExhausted`](Self::RetrievalExhausted) this is no claim of
+    /// absence: the caller chose a tight deadline over exhaustive retries, so a
+    /// retrieval with a wider budget may still succeed.
+    #[error("retrieval timed out after {deadline:?} for chunk: {address}")]
+    RetrievalTimeout {
+        /// The address of the chunk that could not be retrieved in time.
+        address: ChunkAddress,
+        /// The latency budget the retrieval ran out of.
+        deadline: core::time::Duration,
     },
 
     /// No storer found for the chunk in proximity range.
@@ -274,6 +331,8 @@ pub enum ConfigAddressKind {
     NatAddr,
     /// Trusted peer address.
     TrustedPeer,
+    /// Static peer address.
+    StaticPeer,
 }
 
 impl core::fmt::Display for ConfigAddressKind {
@@ -283,6 +342,7 @@ impl core::fmt::Display for ConfigAddressKind {
             Self::Bootnode => write!(f, "bootnode address"),
             Self::NatAddr => write!(f, "NAT address"),
             Self::TrustedPeer => write!(f, "trusted peer address"),
+            Self::StaticPeer => write!(f, "static peer address"),
         }
     }
 }
@@ -308,6 +368,24 @@ pub enum ConfigError {
     /// connection and isolate the node.
     #[error("max peers must be at least 1; 0 would deny every connection")]
     ZeroMaxPeers,
+
+    /// The churn alert threshold was not a positive, finite rate.
+    #[error("churn threshold must be a positive number of events per minute, got {0}")]
+    InvalidChurnThreshold(f64),
+
+    /// A static peer entry was not an overlay and a multiaddr joined by `@`.
+    #[error("invalid static peer '{0}': expected <overlay>@<multiaddr>")]
+    MalformedStaticPeer(String),
+
+    /// A static peer entry named an overlay that is not a Swarm address.
+    #[error("invalid static peer overlay '{overlay}': {source}")]
+    InvalidStaticPeerOverlay {
+        /// The overlay half of the entry.
+        overlay: String,
+        /// The parse error.
+        #[source]
+        source: AddressParseError,
+    },
 }
 
 /// Result type for configuration operations.
@@ -333,4 +411,24 @@ pub enum IdentityError {
         /// The node type whose persistence requirement was violated.
         node_type: crate::SwarmNodeType,
     },
+
+    /// A node-type change was requested that the identity cannot make: only
+    /// a client may be promoted to storer, and only a storer demoted back.
+    #[error("cannot change node type from {from} to {to}")]
+    NodeTypeChangeNotAllowed {
+        /// The current node type.
+        from: crate::SwarmNodeType,
+        /// The requested node type.
+        to: crate::SwarmNodeType,
+    },
+
+    /// No nonce within the attempt budget produced an overlay at the target
+    /// proximity order.
+    #[error("no overlay at proximity {target_po} found in {attempts} attempts")]
+    OverlayNotMined {
+        /// The proximity order to the base the overlay had to land at.
+        target_po: u8,
+        /// The nonces tried.
+        attempts: u64,
+    },
 }
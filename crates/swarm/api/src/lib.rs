@@ -8,6 +8,9 @@
 //! Runtime containers mirror the chain: [`BootnodeComponents`] (topology),
 //! [`ClientComponents`] (+ chunk client), [`StorerComponents`] (+ store), accessed
 //! through [`HasTopology`], [`HasChunkClient`], [`HasStore`], [`HasIdentity`].
+//! Every container carries a [`DiagnosticsHandle`] ([`HasDiagnostics`]) for the
+//! operator dump; chain-connected nodes also carry a [`FinanceHandle`]
+//! ([`HasFinance`]).
 //! Accounting is not a component: it is wired into the network chunk client and
 //! shared through an `Arc` at launch; bootnodes run a listen-only pricing handler.
 //!
@@ -28,6 +31,7 @@ extern crate alloc;
 mod accounting;
 mod components;
 mod config;
+mod diagnostics;
 mod error;
 mod identity;
 mod providers;
@@ -38,38 +42,48 @@ mod types;
 
 pub use self::accounting::{Admission, Au, AuConversionError, Debt};
 pub use self::components::{
-    BandwidthDebit, BinCursorStore, BinScanItem, BootnodeComponents, ClientComponents, Commit,
-    CommitOnWrite, Direction, HasChunkClient, HasIdentity, HasReserve, HasStore, HasTopology,
-    IntervalStore, PullChunkVerifier, PullStorage, ReserveStore, SettableRadius, StorerComponents,
+    BandwidthDebit, BinCursorStore, BinDepth, BinScanItem, BootnodeComponents, ClientComponents,
+    Commit, CommitOnWrite, ConnectionAuditRecord, ConnectionAuditStage, DepthExplanation,
+    DepthLimit, Direction, HasChunkClient, HasDiagnostics, HasFinance, HasIdentity, HasReserve,
+    HasStore, HasTopology, IntervalStore, NetworkIngest, NetworkStore, PullChunkVerifier,
+    PullStorage, ReserveStore, SettableRadius, StampPolicy, StampVerifier, StorerComponents,
     SwarmAccountingConfig, SwarmBandwidthAccounting, SwarmClientAccounting, SwarmLocalStore,
-    SwarmLocalStoreConfig, SwarmPeerBandwidth, SwarmPeerResolver, SwarmPeerState, SwarmPricing,
-    SwarmPricingBuilder, SwarmPricingConfig, SwarmSettlementProvider, SwarmTopology,
-    SwarmTopologyBins, SwarmTopologyCommands, SwarmTopologyPeers, SwarmTopologyReporting,
-    SwarmTopologyRouting, SwarmTopologyState, SwarmTopologyStats, VerifyError, construct,
+    SwarmLocalStoreConfig, SwarmLocalStoreExt, SwarmPeerBandwidth, SwarmPeerResolver,
+    SwarmPeerState, SwarmPricing, SwarmPricingBuilder, SwarmPricingConfig, SwarmSettlementProvider,
+    SwarmTopology, SwarmTopologyBins, SwarmTopologyCommands, SwarmTopologyPeers,
+    SwarmTopologyReporting, SwarmTopologyRouting, SwarmTopologyState, SwarmTopologyStats,
+    VerifyError, construct,
 };
 pub use self::config::{
+    DEFAULT_CLOCK_SKEW_TOLERANCE_SECS, DEFAULT_COMMAND_CHANNEL_CAPACITY, DEFAULT_DNS_CACHE_TTL,
     DEFAULT_PEER_BAN_THRESHOLD, DEFAULT_PEER_DISCONNECT_THRESHOLD, DEFAULT_PEER_MAX_PER_BIN,
-    DEFAULT_PEER_WARN_THRESHOLD, DefaultPeerConfig, DefaultStorageConfig, METADATA_OVERHEAD_FACTOR,
-    NodeTask, NodeTaskFn, PeerConfigValues, SwarmBootnodeConfig, SwarmClientConfig,
-    SwarmClientLaunchConfig, SwarmIdentityConfig, SwarmLaunchConfig, SwarmNetworkConfig,
-    SwarmPeerConfig, SwarmRoutingConfig, SwarmStorageConfig, SwarmStorerConfig,
-    SwarmStorerLaunchConfig, estimate_chunks_for_bytes, estimate_storage_bytes,
+    DEFAULT_PEER_WARN_THRESHOLD, DEFAULT_VALIDATION_CONCURRENCY, DefaultPeerConfig,
+    DefaultStorageConfig, METADATA_OVERHEAD_FACTOR, NodeTask, NodeTaskFn, PeerConfigValues,
+    SwarmBootnodeConfig, SwarmClientConfig, SwarmClientLaunchConfig, SwarmIdentityConfig,
+    SwarmLaunchConfig, SwarmNetworkConfig, SwarmPeerConfig, SwarmRoutingConfig, SwarmStorageConfig,
+    SwarmStorerConfig, SwarmStorerLaunchConfig, estimate_chunks_for_bytes, estimate_storage_bytes,
+};
+pub use self::diagnostics::{
+    BinDiagnostics, ConfigDiagnostics, DiagnosticDump, DiagnosticsHandle, LedgerDiagnostics,
+    PeerDiagnostics, StorageDiagnostics, SwarmDiagnostics, TopologyDiagnostics,
 };
 pub use self::error::{
-    AccountingError, ConfigAddressKind, ConfigError, ConfigResult, IdentityError, SwarmError,
-    SwarmResult,
+    AccountingError, ConfigAddressKind, ConfigError, ConfigResult, IdentityError, RetrievalFailure,
+    SwarmError, SwarmResult,
 };
 pub use self::identity::SwarmIdentity;
 pub use self::providers::{
-    ChunkRetrievalResult, PushReceipt, SwarmChunkProvider, SwarmChunkSender,
+    ChunkRetrievalResult, FinanceHandle, NodeFinance, PeerPinger, PushReceipt, SwarmChunkProvider,
+    SwarmChunkSender, TokenBalanceClient,
 };
 pub use self::reporting::{
     AdmissionControl, BanCause, DisconnectReason, Ledger, LedgerSnapshot, PeerLifecycleEvent,
     PeerReporter, ReportSource, SwarmScoringEvent,
 };
 pub use self::spec::{
+    DEFAULT_ADDRESS_SIZE, DEFAULT_MIN_PAYMENT_THRESHOLD, DEFAULT_PROTOCOL_PREFIX,
     DEFAULT_SATURATION_PEERS, StaticSwarmSpecProvider, SwarmSpec, SwarmSpecParser,
-    SwarmSpecProvider, SwarmToken,
+    SwarmSpecProvider, SwarmToken, max_po_for_address_size,
 };
 pub use self::swarm::{SwarmClient, SwarmStorer};
 pub use self::types::{
@@ -2,7 +2,11 @@
 //!
 //! Data interfaces for RPC services, abstracting over concrete implementations.
 
-use alloy_primitives::Signature;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy_primitives::{Address, Signature, U256};
 use nectar_primitives::{AnyChunk, ChunkAddress, Nonce};
 use vertex_swarm_primitives::{OverlayAddress, Stamp, StampedChunk, StorageRadius};
 
@@ -24,6 +28,13 @@ pub struct ChunkRetrievalResult {
     pub stamp: Option<Stamp>,
     /// Overlay address of the peer that served this chunk.
     pub served_by: OverlayAddress,
+    /// Attempts dispatched beyond the first before the chunk was served.
+    ///
+    /// A race dispatches several attempts before any answers, so this counts
+    /// every peer asked, not only the ones that failed first.
+    pub retries: usize,
+    /// Wall time from dispatching the retrieval to receiving the chunk.
+    pub elapsed: Duration,
 }
 
 /// Provider trait for chunk retrieval operations.
@@ -33,6 +44,20 @@ pub trait SwarmChunkProvider: Send + Sync + 'static {
     /// Retrieve a chunk by its address from the Swarm network.
     async fn retrieve_chunk(&self, address: &ChunkAddress) -> SwarmResult<ChunkRetrievalResult>;
 
+    /// Retrieve a chunk with a single attempt at the best peer, failing with
+    /// [`SwarmError::RetrievalTimeout`](crate::SwarmError::RetrievalTimeout)
+    /// once `max_latency` passes instead of running the full retry policy.
+    ///
+    /// A provider without a fast path runs the full retrieval.
+    async fn retrieve_chunk_fast(
+        &self,
+        address: &ChunkAddress,
+        max_latency: Duration,
+    ) -> SwarmResult<ChunkRetrievalResult> {
+        let _ = max_latency;
+        self.retrieve_chunk(address).await
+    }
+
     /// Check if a chunk exists locally.
     ///
     /// Returns false for Clients, which have no local storage.
@@ -86,3 +111,60 @@ pub trait SwarmChunkSender: Send + Sync + 'static {
     /// Returns `SwarmError::InvalidSignature` if the stamp doesn't match the chunk.
     async fn send_chunk(&self, chunk: StampedChunk) -> SwarmResult<PushReceipt>;
 }
+
+/// Reads ERC-20 balances from the settlement chain.
+///
+/// The chain-client seam behind [`NodeFinance`]: the node implements it over
+/// its shared chain provider, tests over a canned value.
+#[async_trait::async_trait]
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait TokenBalanceClient: Send + Sync + 'static {
+    /// `owner`'s balance of the token contract at `token`, in base units.
+    async fn token_balance(&self, token: Address, owner: Address) -> SwarmResult<U256>;
+}
+
+/// The node's on-chain funds, as reported to operators.
+#[async_trait::async_trait]
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait NodeFinance: Send + Sync + 'static {
+    /// The node wallet's balance of the network's BZZ token, in PLUR.
+    async fn balance(&self) -> SwarmResult<U256>;
+}
+
+/// Shared, type-erased [`NodeFinance`] source held by the component
+/// containers of chain-connected nodes.
+#[derive(Clone)]
+pub struct FinanceHandle(Arc<dyn NodeFinance>);
+
+impl FinanceHandle {
+    /// Wrap a finance source.
+    pub fn new(source: impl NodeFinance) -> Self {
+        Self(Arc::new(source))
+    }
+}
+
+#[async_trait::async_trait]
+impl NodeFinance for FinanceHandle {
+    async fn balance(&self) -> SwarmResult<U256> {
+        self.0.balance().await
+    }
+}
+
+impl fmt::Debug for FinanceHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FinanceHandle").finish_non_exhaustive()
+    }
+}
+
+/// Operator spot check of a single peer's round-trip time.
+#[async_trait::async_trait]
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait PeerPinger: Send + Sync + 'static {
+    /// Send one ping to the connected `peer` and return the measured RTT.
+    ///
+    /// Fails with [`SwarmError::PeerUnavailable`](crate::SwarmError::PeerUnavailable)
+    /// when `peer` is not connected, and with
+    /// [`SwarmError::Network`](crate::SwarmError::Network) when the ping is
+    /// sent but no round trip completes.
+    async fn ping(&self, peer: &OverlayAddress) -> SwarmResult<Duration>;
+}
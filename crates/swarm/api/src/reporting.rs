@@ -299,38 +299,48 @@ impl<T: Ledger> AdmissionControl for T {}
 /// [`RemoteClose`]: Self::RemoteClose
 /// [`IdleTimeout`]: Self::IdleTimeout
 /// [`LocalClose`]: Self::LocalClose
-#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::IntoStaticStr)]
+///
+/// The discriminant is the reason's wire code in the goodbye message sent to
+/// the peer before a local close; codes are append-only.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::FromRepr, strum::IntoStaticStr,
+)]
 #[strum(serialize_all = "snake_case")]
+#[repr(u8)]
 pub enum DisconnectReason {
     /// Evicted to rebalance an overpopulated bin after a depth change.
-    BinTrimmed,
+    BinTrimmed = 0,
     /// Inbound connection refused because its bin was saturated.
-    BinSaturated,
+    BinSaturated = 1,
+    /// Evicted at the connection limit to admit a peer filling a gap bin.
+    Displaced = 2,
+    /// Inbound connection refused at the connection limit.
+    ConnectionLimit = 3,
     /// Connection to a banned peer was closed.
-    Banned,
+    Banned = 4,
     /// Score fell below the disconnect threshold.
-    LowScore,
+    LowScore = 5,
     /// A protocol handler reported a violation.
-    ProtocolViolation,
+    ProtocolViolation = 6,
     /// The peer exhausted its bandwidth allowance.
-    AllowanceExceeded,
+    AllowanceExceeded = 7,
     /// Disconnect requested by an operator over the RPC surface.
-    Requested,
+    Requested = 8,
     /// Replaced by a newer connection from the same peer.
-    DuplicateConnection,
+    DuplicateConnection = 9,
     /// Bootnode dropped after its initial hive gossip batch.
-    BootnodeRotation,
+    BootnodeRotation = 10,
     /// The node is shutting down.
-    ShuttingDown,
+    ShuttingDown = 11,
     /// Local idle teardown (libp2p keep-alive timeout). Not a peer fault.
-    IdleTimeout,
+    IdleTimeout = 12,
     /// A local close with no recorded intent. Rare; indicates a close path
     /// that did not record its reason.
-    LocalClose,
+    LocalClose = 13,
     /// The peer or the transport closed the connection. A graceful remote
     /// close and a transport reset are indistinguishable at this layer; this
     /// is the only peer-attributable reason.
-    RemoteClose,
+    RemoteClose = 14,
 }
 
 impl DisconnectReason {
@@ -343,6 +353,19 @@ impl DisconnectReason {
     pub fn is_locally_initiated(self) -> bool {
         !matches!(self, Self::RemoteClose)
     }
+
+    /// Wire code carried by the goodbye message.
+    #[must_use]
+    pub fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// Reason for a received wire code, `None` for a code this node does not
+    /// know (sent by a newer peer).
+    #[must_use]
+    pub fn from_code(code: u8) -> Option<Self> {
+        Self::from_repr(code)
+    }
 }
 
 /// Why a peer was banned.
@@ -571,6 +594,8 @@ mod tests {
         assert!(!DisconnectReason::RemoteClose.is_locally_initiated());
         for reason in [
             DisconnectReason::BinTrimmed,
+            DisconnectReason::Displaced,
+            DisconnectReason::ConnectionLimit,
             DisconnectReason::Banned,
             DisconnectReason::LowScore,
             DisconnectReason::BootnodeRotation,
@@ -582,6 +607,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn disconnect_codes_round_trip() {
+        assert_eq!(DisconnectReason::AllowanceExceeded.code(), 7);
+        for code in 0..=DisconnectReason::RemoteClose.code() {
+            let reason = DisconnectReason::from_code(code).unwrap();
+            assert_eq!(reason.code(), code);
+        }
+        assert_eq!(DisconnectReason::from_code(u8::MAX), None);
+    }
+
     #[test]
     fn lifecycle_event_is_cloneable() {
         let event = PeerLifecycleEvent::Banned {
@@ -3,9 +3,11 @@
 use alloc::{string::String, sync::Arc, vec::Vec};
 use alloy_chains::Chain;
 use alloy_primitives::Address;
+use core::num::NonZeroUsize;
 use nectar_primitives::{ChunkTypeSet, NetworkId};
 use nectar_swarms::{NamedSwarm, Swarm};
 use vertex_swarm_forks::{ForkCondition, ForkDigest, SwarmHardfork, SwarmHardforks};
+use vertex_swarm_primitives::SwarmNodeType;
 
 /// Default per-bin saturation target driving the neighborhood-depth frontier.
 ///
@@ -22,6 +24,42 @@ pub const DEFAULT_SATURATION_PEERS: u8 = 8;
 /// [`recompute_neighborhood_depth`]: nectar_primitives::recompute_neighborhood_depth
 pub const DEFAULT_NEIGHBORHOOD_LOW_WATERMARK: u8 = 3;
 
+/// Default protocol-name prefix, the namespace mainnet and testnet protocol
+/// names are negotiated under.
+pub const DEFAULT_PROTOCOL_PREFIX: &str = "/swarm";
+
+/// Default floor on the payment threshold a peer may announce, in accounting
+/// units.
+///
+/// Twice the default light-node refresh rate of 450 000 units per second
+/// (the 4 500 000 default refresh rate over the client-only factor of 10),
+/// so a light peer's scaled-down threshold still clears it while a peer
+/// announcing a threshold it could exceed within one refresh does not.
+pub const DEFAULT_MIN_PAYMENT_THRESHOLD: u64 = 900_000;
+
+/// Default overlay and chunk address length, in bytes: 256-bit addresses, as
+/// on mainnet and testnet.
+pub const DEFAULT_ADDRESS_SIZE: NonZeroUsize = match NonZeroUsize::new(32) {
+    Some(size) => size,
+    None => unreachable!(),
+};
+
+/// Deepest proximity order between two addresses of `bytes` bytes.
+///
+/// An address of `n` bits can share at most `n - 1` leading bits with a
+/// different address, so that is the deepest bin. The result is capped at
+/// [`nectar_primitives::MAX_PO`], the widest range the bin types represent:
+/// the default 32-byte addresses sit at the cap, and only narrower research
+/// address spaces (under four bytes) change the bin count.
+pub const fn max_po_for_address_size(bytes: NonZeroUsize) -> u8 {
+    let deepest = bytes.get().saturating_mul(8) - 1;
+    if deepest < nectar_primitives::MAX_PO as usize {
+        deepest as u8
+    } else {
+        nectar_primitives::MAX_PO
+    }
+}
+
 /// Parser for Swarm network specifications.
 ///
 /// Handles both preset names ("mainnet", "testnet") and file paths via a single
@@ -99,6 +137,14 @@ pub trait SwarmSpec: Send + Sync + 'static {
     /// This defines which BZZ token this network uses and where it's deployed.
     fn token(&self) -> &Self::Token;
 
+    /// Returns the address of the token contract.
+    ///
+    /// Shorthand for `self.token().address()`, the contract a balance query
+    /// reads.
+    fn token_address(&self) -> Address {
+        self.token().address()
+    }
+
     /// Returns the hardforks configuration.
     fn hardforks(&self) -> &SwarmHardforks;
 
@@ -155,9 +201,24 @@ pub trait SwarmSpec: Send + Sync + 'static {
         self.network_id().get() == NamedSwarm::Testnet as u64
     }
 
-    /// Returns the maximum proximity order for addresses in this network.
+    /// Returns the length of overlay and chunk addresses in this network, in
+    /// bytes.
+    ///
+    /// Defaults to [`DEFAULT_ADDRESS_SIZE`]. Addresses stay 32 bytes on the
+    /// wire; a narrower size only bounds how deep two addresses are judged to
+    /// share a prefix, through [`Self::max_po`].
+    fn address_size(&self) -> NonZeroUsize {
+        DEFAULT_ADDRESS_SIZE
+    }
+
+    /// Returns the maximum proximity order for addresses in this network,
+    /// derived from [`Self::address_size`].
+    ///
+    /// Every bin array, the depth calculation and bin iteration are sized from
+    /// this, so a network declaring a different address size gets
+    /// `max_po() + 1` bins throughout.
     fn max_po(&self) -> u8 {
-        nectar_primitives::MAX_PO
+        max_po_for_address_size(self.address_size())
     }
 
     /// Per-bin saturation target driving the neighborhood-depth frontier.
@@ -178,10 +239,48 @@ pub trait SwarmSpec: Send + Sync + 'static {
         DEFAULT_NEIGHBORHOOD_LOW_WATERMARK
     }
 
+    /// Returns the namespace every wire protocol name is built under.
+    ///
+    /// Defaults to [`DEFAULT_PROTOCOL_PREFIX`]. A private network that sets
+    /// its own prefix (e.g. `/myswarm`) advertises `/myswarm/handshake/...`
+    /// and so cannot negotiate any protocol with nodes of another namespace,
+    /// whatever their network ID.
+    fn protocol_prefix(&self) -> &str {
+        DEFAULT_PROTOCOL_PREFIX
+    }
+
+    /// Returns the lowest payment threshold a peer may announce.
+    ///
+    /// Defaults to [`DEFAULT_MIN_PAYMENT_THRESHOLD`]. A peer announcing less is
+    /// rejected: it would demand settlement faster than the network's
+    /// accounting is tuned to pay.
+    fn min_payment_threshold(&self) -> u64 {
+        DEFAULT_MIN_PAYMENT_THRESHOLD
+    }
+
     /// Returns whether this is a development network.
     fn is_dev(&self) -> bool {
         !self.is_mainnet() && !self.is_testnet()
     }
+
+    /// Returns whether storing a chunk requires a postage stamp.
+    ///
+    /// The single switch for "free storage": when `false`, stamps are neither
+    /// required nor verified on ingest (see [`crate::StampPolicy::for_spec`]).
+    /// Defaults to paid storage everywhere except development networks.
+    fn requires_storage_payment(&self) -> bool {
+        !self.is_dev()
+    }
+
+    /// Returns whether SWAP settlement is on for a `node_type` whose operator
+    /// left `--swap` unset.
+    ///
+    /// Off on development networks, which rarely deploy a chequebook factory
+    /// to settle against; pseudosettle alone covers their traffic. Elsewhere
+    /// the node type decides (see [`SwarmNodeType::swap_default`]).
+    fn default_swap_enabled(&self, node_type: SwarmNodeType) -> bool {
+        !self.is_dev() && node_type.swap_default()
+    }
 }
 
 /// Trait for types that can provide a SwarmSpec.
@@ -72,7 +72,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .await
     {
         Ok(result) => {
-            println!("Served by {}", result.served_by);
+            println!(
+                "Served by {} after {} retries in {:?}",
+                result.served_by, result.retries, result.elapsed
+            );
             println!("Chunk address {}", result.chunk.address());
         }
         Err(err) => println!("Retrieval failed: {err}"),
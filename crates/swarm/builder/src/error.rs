@@ -36,4 +36,15 @@ pub enum SwarmNodeError {
         /// The node type that hard-failed for want of a chain.
         node_type: SwarmNodeType,
     },
+
+    /// The builder configures a capability the node type cannot have, or
+    /// lacks one it requires. Raised before anything is opened or spawned.
+    #[error("node type {node_type} conflicts with the configured {conflicting_feature}")]
+    CapabilityConflict {
+        /// The node type the conflicting capability was configured for.
+        node_type: SwarmNodeType,
+        /// The capability that conflicts, e.g. `local store` or
+        /// `ephemeral identity`.
+        conflicting_feature: &'static str,
+    },
 }
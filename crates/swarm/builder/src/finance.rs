@@ -0,0 +1,46 @@
+//! The chain-backed balance reader behind the node's `GetBalance` RPC.
+//!
+//! Composed here rather than in `vertex-swarm-node` so the chain dependency
+//! stays a launch-time choice: the node crate's [`SpecFinance`] reads through
+//! any [`TokenBalanceClient`], and only the builder, which resolves the chain
+//! provider, binds it to one.
+
+use std::sync::Arc;
+
+use alloy_primitives::{Address, U256};
+use async_trait::async_trait;
+use vertex_chain::SharedChainProvider;
+use vertex_swarm_api::{FinanceHandle, SwarmError, SwarmIdentity, SwarmResult, TokenBalanceClient};
+use vertex_swarm_identity::Identity;
+use vertex_swarm_node::SpecFinance;
+use vertex_swarm_spec::Spec;
+
+/// [`TokenBalanceClient`] over the node's shared chain provider.
+#[derive(Clone)]
+struct ChainBalanceClient(SharedChainProvider);
+
+#[async_trait]
+impl TokenBalanceClient for ChainBalanceClient {
+    async fn token_balance(&self, token: Address, owner: Address) -> SwarmResult<U256> {
+        vertex_chain::token_balance(self.0.provider(), token, owner)
+            .await
+            .map_err(|e| SwarmError::Network {
+                message: format!("token balance read failed: {e}"),
+                source: Some(Box::new(e)),
+            })
+    }
+}
+
+/// The node wallet's balance source, or `None` without a chain provider.
+pub(crate) fn node_finance(
+    spec: &Arc<Spec>,
+    identity: &Arc<Identity>,
+    provider: Option<&SharedChainProvider>,
+) -> Option<FinanceHandle> {
+    let client = ChainBalanceClient(provider?.clone());
+    Some(FinanceHandle::new(SpecFinance::new(
+        client,
+        &**spec,
+        identity.ethereum_address(),
+    )))
+}
@@ -3,8 +3,8 @@
 use std::sync::Arc;
 
 use vertex_swarm_api::{
-    BinCursorStore, BootnodeComponents, ClientComponents, HasTopology, StorerComponents,
-    SwarmLocalStore,
+    BinCursorStore, BootnodeComponents, ClientComponents, DiagnosticDump, HasDiagnostics,
+    HasTopology, StorerComponents, SwarmDiagnostics, SwarmLocalStore,
 };
 use vertex_swarm_identity::Identity;
 use vertex_swarm_topology::TopologyHandle;
@@ -53,6 +53,13 @@ impl<P: HasTopology> BuiltNode<P> {
     }
 }
 
+impl<P: HasDiagnostics> BuiltNode<P> {
+    /// Capture the node's topology, peers, accounting, storage, and config.
+    pub fn diagnostic_dump(&self) -> DiagnosticDump {
+        self.providers.diagnostics().diagnostic_dump()
+    }
+}
+
 /// Built bootnode (topology only).
 pub type BuiltBootnode = BuiltNode<BootnodeComponents<TopologyHandle<Arc<Identity>>>>;
 
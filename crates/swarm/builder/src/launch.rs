@@ -8,15 +8,20 @@ use tracing::{info, warn};
 use vertex_net_peer_store::PeerSnapshotStore;
 use vertex_node_api::InfrastructureContext;
 use vertex_storage_redb::RedbDatabase;
-use vertex_swarm_accounting::{Accounting, ClientAccounting, DefaultBandwidthConfig, FixedPricer};
+use vertex_swarm_accounting::{
+    Accounting, ClientAccounting, DbBalanceSnapshotStore, DefaultBandwidthConfig, FixedPricer,
+    PeerBalanceSnapshot,
+};
 use vertex_swarm_api::{
-    BootnodeComponents, ClientComponents, SwarmLaunchConfig, SwarmNodeType, construct,
+    BootnodeComponents, ClientComponents, ConfigDiagnostics, DiagnosticsHandle, DisconnectReason,
+    FinanceHandle, Ledger, SwarmBandwidthAccounting, SwarmClientAccounting as _, SwarmLaunchConfig,
+    SwarmNodeType, construct,
 };
 use vertex_swarm_identity::Identity;
 use vertex_swarm_node::args::NetworkConfig;
 use vertex_swarm_node::{
-    BootNode, ClientNode, ClientNodeParts, ClientTailParams, NativeChunkProvider, NodeRunParts,
-    RunTaskFn, build_client_core_tail, single_task,
+    BootNode, ClientNode, ClientNodeParts, ClientTailParams, NativeChunkProvider, NodeDiagnostics,
+    NodeRunParts, PricingAnnouncement, RunTaskFn, build_client_core_tail, single_task,
 };
 use vertex_swarm_peer_manager::{
     DEFAULT_TICK_INTERVAL, DbPeerSnapshotStore, PeerSnapshot, spawn_peer_manager_task,
@@ -27,7 +32,10 @@ use vertex_tasks::NodeTaskFn;
 
 use crate::config::{BootnodeConfig, ClientConfig};
 use crate::error::SwarmNodeError;
+use crate::node::check_capabilities;
 
+#[cfg(feature = "swap")]
+use vertex_swarm_api::SwarmSpec as _;
 #[cfg(feature = "swap")]
 use vertex_swarm_node::args::ChainConfig;
 #[cfg(feature = "swap")]
@@ -37,6 +45,12 @@ use vertex_swarm_node::{ClientSwapParams, NodeChainError, node_chain_provider};
 
 pub(crate) type PeerStore = Arc<dyn PeerSnapshotStore<PeerSnapshot>>;
 
+/// Persisted per-peer balances, restored at startup for crash recovery.
+type BalanceStore = Arc<dyn PeerSnapshotStore<PeerBalanceSnapshot>>;
+
+/// The node's bandwidth accounting, as shared by the client core.
+type NodeBandwidth = Arc<Accounting<DefaultBandwidthConfig, Arc<Identity>>>;
+
 /// Stats collection interval for database metrics.
 const DB_METRICS_INTERVAL: Duration = Duration::from_secs(30);
 
@@ -118,6 +132,127 @@ fn create_peer_store(db: &Option<Arc<RedbDatabase>>) -> Option<PeerStore> {
     }
 }
 
+fn create_balance_store(db: &Option<Arc<RedbDatabase>>) -> Option<BalanceStore> {
+    let db = db.as_ref()?;
+    let store = Arc::new(DbBalanceSnapshotStore::new(db.clone()));
+    match store.init() {
+        Ok(()) => Some(store as BalanceStore),
+        Err(e) => {
+            warn!(error = %e, "Failed to init balance snapshot table");
+            None
+        }
+    }
+}
+
+/// Seed `accounting` from the last persisted balance snapshot, dropping
+/// records older than `max_age`.
+fn restore_balances(store: &BalanceStore, accounting: &NodeBandwidth, max_age: Duration) {
+    match store.load() {
+        Ok(records) => {
+            let now = vertex_util_runtime::time::now_unix_secs();
+            let restored = accounting.restore_balances(&records, now, max_age.as_secs());
+            info!(
+                restored,
+                discarded = records.len() - restored,
+                "Restored peer balances"
+            );
+        }
+        Err(e) => warn!(error = %e, "Failed to load balance snapshot"),
+    }
+}
+
+fn persist_balances(store: &BalanceStore, accounting: &NodeBandwidth) {
+    let records = accounting.balance_snapshot(vertex_util_runtime::time::now_unix_secs());
+    if let Err(e) = store.store(&records) {
+        warn!(error = %e, "Failed to persist balance snapshot");
+    }
+}
+
+/// Persist balances every `interval`, and once more on graceful shutdown.
+fn spawn_balance_snapshot_task(
+    ctx: &dyn InfrastructureContext,
+    store: BalanceStore,
+    accounting: NodeBandwidth,
+    interval: Duration,
+) {
+    ctx.executor().spawn_with_graceful_shutdown_signal(
+        "accounting.snapshot",
+        move |shutdown| async move {
+            let mut shutdown = std::pin::pin!(shutdown);
+            let mut interval = vertex_tasks::time::interval(interval);
+
+            loop {
+                tokio::select! {
+                    guard = &mut shutdown => {
+                        persist_balances(&store, &accounting);
+                        tracing::debug!("balance snapshot task shutting down");
+                        drop(guard);
+                        break;
+                    }
+                    _ = interval.tick() => persist_balances(&store, &accounting),
+                }
+            }
+        },
+    );
+}
+
+/// Disconnect peers whose debt has stayed past the disconnect threshold for
+/// longer than `grace`.
+///
+/// With reputation scaling enabled, each tick first feeds every accounted
+/// peer's current score into the accounting, so the credit thresholds checked
+/// here and on provide track the peer manager's view.
+fn spawn_debt_watch_task(
+    ctx: &dyn InfrastructureContext,
+    accounting: NodeBandwidth,
+    topology: TopologyHandle<Arc<Identity>>,
+    grace: Duration,
+) {
+    let period = (grace / 2).max(Duration::from_secs(1));
+    ctx.executor().spawn_with_graceful_shutdown_signal(
+        "accounting.debt_watch",
+        move |shutdown| async move {
+            let mut shutdown = std::pin::pin!(shutdown);
+            let mut interval = vertex_tasks::time::interval(period);
+
+            loop {
+                tokio::select! {
+                    guard = &mut shutdown => {
+                        tracing::debug!("debt watch task shutting down");
+                        drop(guard);
+                        break;
+                    }
+                    _ = interval.tick() => {
+                        if accounting.reputation_scaling().is_some() {
+                            observe_scores(&accounting, &topology);
+                        }
+                        let now = vertex_util_runtime::time::now_unix_secs();
+                        for peer in accounting.overdue_peers(now, grace) {
+                            info!(%peer, ?grace, "Disconnecting peer over the disconnect threshold");
+                            if let Err(e) = topology
+                                .disconnect_with_reason(peer, DisconnectReason::AllowanceExceeded)
+                                .await
+                            {
+                                warn!(%peer, error = %e, "Failed to disconnect indebted peer");
+                            }
+                        }
+                    }
+                }
+            }
+        },
+    );
+}
+
+/// Rescale each accounted peer's thresholds for its current peer score.
+fn observe_scores(accounting: &NodeBandwidth, topology: &TopologyHandle<Arc<Identity>>) {
+    let peer_manager = topology.peer_manager();
+    for peer in SwarmBandwidthAccounting::peers(accounting.as_ref()) {
+        if let Some(score) = peer_manager.get_peer_score(&peer) {
+            accounting.observe_score(&peer, score);
+        }
+    }
+}
+
 macro_rules! define_launch_types {
     ($(#[$attr:meta])* $name:ident) => {
         $(#[$attr])*
@@ -208,6 +343,8 @@ pub(crate) struct AssemblyInputs<'a> {
     pub(crate) identity: &'a Arc<Identity>,
     pub(crate) network: &'a NetworkConfig<KademliaConfig>,
     pub(crate) peer_store: Option<PeerStore>,
+    /// Payment thresholds announced to peers, from the unscaled bandwidth config.
+    pub(crate) pricing: PricingAnnouncement,
     pub(crate) pseudosettle_event_sender:
         tokio::sync::mpsc::UnboundedSender<vertex_swarm_node::PseudosettleEvent>,
     #[cfg(feature = "swap")]
@@ -283,6 +420,7 @@ impl NodeAssembly for ClientAssembly {
             inputs.network,
             node_store,
             inputs.peer_store,
+            inputs.pricing,
             inputs.pseudosettle_event_sender,
             #[cfg(feature = "swap")]
             inputs.swap_event_sender,
@@ -299,11 +437,13 @@ impl NodeAssembly for ClientAssembly {
 /// chunk provider, service spawning) to [`build_client_core_tail`]. The
 /// node-type-specific local store and node assembly are injected through
 /// `assembly`, invoked by the tail over the prepared settlement event sinks.
+/// Alongside the parts it returns the wallet balance source, present when a
+/// chain provider was resolved.
 pub(crate) async fn build_client_backed_node<F: NodeAssembly>(
     ctx: &dyn InfrastructureContext,
     params: ClientNodeParams<'_>,
     assembly: F,
-) -> Result<ClientNodeParts<F::ProviderStore>, SwarmNodeError> {
+) -> Result<(ClientNodeParts<F::ProviderStore>, Option<FinanceHandle>), SwarmNodeError> {
     let node_type = F::NODE_TYPE;
     log_build_start(node_type, params.spec);
 
@@ -317,11 +457,14 @@ pub(crate) async fn build_client_backed_node<F: NodeAssembly>(
         &scaled_bandwidth
     };
 
-    // SWAP defaults on for storers (maximum support) and off for clients; an
-    // explicit --swap overrides. The tail derives the same value for its swap
+    // SWAP defaults on for storers (maximum support) and off for clients and
+    // on dev networks; an explicit --swap overrides. The tail derives the same value for its swap
     // wiring; here it gates the chain precondition.
     #[cfg(feature = "swap")]
-    let swap_enabled = params.swap.enable.unwrap_or(node_type.swap_default());
+    let swap_enabled = params
+        .swap
+        .enable
+        .unwrap_or_else(|| params.spec.default_swap_enabled(node_type));
     #[cfg(not(feature = "swap"))]
     let swap_enabled = false;
 
@@ -344,15 +487,24 @@ pub(crate) async fn build_client_backed_node<F: NodeAssembly>(
     if node_type.needs_chain(swap_enabled) {
         return Err(SwarmNodeError::ChainRequired { node_type });
     }
+    #[cfg(feature = "swap")]
+    let finance =
+        crate::finance::node_finance(params.spec, params.identity, chain_provider.as_ref());
+    #[cfg(not(feature = "swap"))]
+    let finance = None;
 
     let db = open_shared_database(ctx);
     let peer_store = create_peer_store(&db);
+    let balance_store = create_balance_store(&db);
 
     let tail_params = ClientTailParams {
         node_type,
         spec: params.spec,
         identity: params.identity,
         bandwidth,
+        selection: params.network.retrieval_selector(),
+        selector: None,
+        read_your_writes: params.network.read_your_writes(),
         #[cfg(feature = "swap")]
         swap: ClientSwapParams {
             enable: params.swap.enable,
@@ -360,6 +512,7 @@ pub(crate) async fn build_client_backed_node<F: NodeAssembly>(
             beneficiary: params.swap.beneficiary,
             deploy: params.swap.deploy,
             bounce_limit: params.swap.bounce_limit,
+            settlement_queue: params.swap.settlement_queue,
         },
     };
 
@@ -378,6 +531,7 @@ pub(crate) async fn build_client_backed_node<F: NodeAssembly>(
                     identity: params.identity,
                     network: params.network,
                     peer_store,
+                    pricing: PricingAnnouncement::from_config(params.bandwidth),
                     pseudosettle_event_sender: events.pseudosettle,
                     #[cfg(feature = "swap")]
                     swap_event_sender: events.swap,
@@ -387,8 +541,23 @@ pub(crate) async fn build_client_backed_node<F: NodeAssembly>(
     )
     .await?;
 
+    // Restore before the returned run task is spawned, so no traffic has been
+    // accounted yet. With persistence off nothing keeps the snapshot current, so
+    // a leftover one from an earlier run is not restored either.
+    if let (Some(store), Some(interval)) = (balance_store, bandwidth.snapshot_interval()) {
+        let accounting = Arc::clone(parts.accounting.bandwidth());
+        restore_balances(&store, &accounting, bandwidth.snapshot_max_age());
+        spawn_balance_snapshot_task(ctx, store, accounting, interval);
+    }
+    spawn_debt_watch_task(
+        ctx,
+        Arc::clone(parts.accounting.bandwidth()),
+        parts.topology.clone(),
+        bandwidth.disconnect_grace(),
+    );
+
     info!(%node_type, "Node built successfully");
-    Ok(parts)
+    Ok((parts, finance))
 }
 
 /// Assemble a bare `ClientNode` and its run-task factory.
@@ -397,6 +566,7 @@ async fn assemble_client_node(
     network: &NetworkConfig<KademliaConfig>,
     node_store: Arc<dyn vertex_swarm_api::SwarmLocalStore>,
     peer_store: Option<PeerStore>,
+    pricing: PricingAnnouncement,
     pseudosettle_event_sender: tokio::sync::mpsc::UnboundedSender<
         vertex_swarm_node::PseudosettleEvent,
     >,
@@ -406,6 +576,7 @@ async fn assemble_client_node(
 ) -> Result<NodeRunParts, SwarmNodeError> {
     let node_builder = ClientNode::builder(identity.clone())
         .with_store(node_store)
+        .with_pricing(pricing)
         .with_pseudosettle_events(pseudosettle_event_sender);
     #[cfg(feature = "swap")]
     let node_builder = match swap_event_sender {
@@ -466,7 +637,11 @@ impl SwarmLaunchConfig for BootnodeConfig {
             DEFAULT_TICK_INTERVAL,
             ctx.executor(),
         );
-        let providers = construct::bootnode(topology);
+        let diagnostics = NodeDiagnostics::new(
+            topology.clone(),
+            ConfigDiagnostics::from_network(SwarmNodeType::Bootnode, self.network()),
+        );
+        let providers = construct::bootnode(topology, DiagnosticsHandle::new(diagnostics));
 
         let task = single_task(move |shutdown| async move {
             if let Err(e) = node.start_and_run(shutdown).await {
@@ -494,6 +669,7 @@ impl SwarmLaunchConfig for ClientConfig {
 
 /// Build a client node. `cache == None` builds the default in-memory cache, no
 /// reserve, so every pushsync relays and the opened database handle is ignored.
+/// An identity that contradicts the client capabilities is refused first.
 pub(crate) async fn build_client(
     config: ClientConfig,
     ctx: &dyn InfrastructureContext,
@@ -505,9 +681,12 @@ pub(crate) async fn build_client(
     ),
     SwarmNodeError,
 > {
+    check_capabilities(SwarmNodeType::Client, config.identity(), true)?;
     let cache_budget = config.local_store().cache_budget_bytes();
     let soc_ttl = config.local_store().soc_cache_ttl();
-    let parts = build_client_backed_node(
+    let config_diagnostics =
+        ConfigDiagnostics::from_network(SwarmNodeType::Client, config.network());
+    let (parts, finance) = build_client_backed_node(
         ctx,
         ClientNodeParams {
             spec: config.spec(),
@@ -527,10 +706,18 @@ pub(crate) async fn build_client(
         task,
         topology,
         chunks,
+        accounting,
         provider_store: (),
         ..
     } = parts;
-    let providers = construct::client(topology, chunks);
+    let diagnostics = NodeDiagnostics::new(topology.clone(), config_diagnostics)
+        .with_ledger(accounting.bandwidth().clone() as Arc<dyn Ledger>);
+    let providers = construct::client(
+        topology,
+        chunks,
+        DiagnosticsHandle::new(diagnostics),
+        finance,
+    );
     Ok((task, providers))
 }
 
@@ -38,6 +38,8 @@
 mod chain;
 pub mod config;
 mod error;
+#[cfg(feature = "swap")]
+mod finance;
 mod handle;
 mod launch;
 mod node;
@@ -9,11 +9,11 @@ use vertex_node_api::InfrastructureContext;
 use vertex_storage_redb::RedbDatabase;
 use vertex_swarm_accounting::DefaultBandwidthConfig;
 use vertex_swarm_api::{
-    SwarmAccountingConfig, SwarmIdentity, SwarmLaunchConfig, SwarmLocalStore, SwarmNetworkConfig,
-    SwarmPeerConfig, SwarmPricingConfig, SwarmRoutingConfig,
+    SwarmAccountingConfig, SwarmIdentity, SwarmIdentityConfig, SwarmLaunchConfig, SwarmLocalStore,
+    SwarmNetworkConfig, SwarmNodeType, SwarmPeerConfig, SwarmPricingConfig, SwarmRoutingConfig,
 };
 use vertex_swarm_identity::Identity;
-use vertex_swarm_localstore::LocalStoreConfig;
+use vertex_swarm_localstore::{DEFAULT_SOC_CACHE_TTL_NS, LocalStoreConfig};
 use vertex_swarm_node::args::{ChainConfig, NetworkConfig, SwapConfig};
 use vertex_swarm_spec::Spec;
 use vertex_swarm_topology::KademliaConfig;
@@ -151,6 +151,31 @@ where
     }
 }
 
+/// Reject an identity that contradicts the node being built.
+///
+/// A bootnode identity cannot back a node with a local store (`local_store`
+/// is whether the builder configures one), and a node type that needs a
+/// stable overlay cannot run under an ephemeral identity.
+pub(crate) fn check_capabilities(
+    node_type: SwarmNodeType,
+    identity: &Identity,
+    local_store: bool,
+) -> Result<(), SwarmNodeError> {
+    if local_store && identity.node_type() == SwarmNodeType::Bootnode {
+        return Err(SwarmNodeError::CapabilityConflict {
+            node_type: SwarmNodeType::Bootnode,
+            conflicting_feature: "local store",
+        });
+    }
+    if identity.ephemeral() && node_type.requires_persistent_identity() {
+        return Err(SwarmNodeError::CapabilityConflict {
+            node_type,
+            conflicting_feature: "ephemeral identity",
+        });
+    }
+    Ok(())
+}
+
 /// Default bootnode builder.
 pub type DefaultNodeBuilder = NodeBuilder<Arc<Identity>, NetworkConfig<KademliaConfig>>;
 
@@ -193,6 +218,26 @@ impl DefaultClientBuilder {
         NodeBuilder::new(spec, identity, network).with_accounting(bandwidth)
     }
 
+    /// Read-only retrieval node.
+    ///
+    /// Runs under a fresh ephemeral client identity, so no pullsync or storage
+    /// protocols are advertised and no reserve is opened. The cache budget is
+    /// zero, so retrieved chunks are never kept, and SWAP is forced off, so
+    /// settlement is pseudosettle only.
+    pub fn light(
+        spec: Arc<Spec>,
+        network: NetworkConfig<KademliaConfig>,
+        bandwidth: DefaultBandwidthConfig,
+    ) -> Self {
+        let identity = Arc::new(Identity::random(spec.clone(), SwarmNodeType::Client));
+        Self::from_parts(spec, identity, network, bandwidth)
+            .with_local_store(LocalStoreConfig::new(0, DEFAULT_SOC_CACHE_TTL_NS))
+            .with_swap(SwapConfig {
+                enable: Some(false),
+                ..SwapConfig::default()
+            })
+    }
+
     pub fn from_config(config: ClientConfig) -> Self {
         Self::from_parts(
             config.spec().clone(),
@@ -205,6 +250,12 @@ impl DefaultClientBuilder {
         .with_swap(config.swap().clone())
     }
 
+    /// Check the identity against the client capabilities, as every client
+    /// build does first.
+    pub fn validate(&self) -> Result<(), SwarmNodeError> {
+        check_capabilities(SwarmNodeType::Client, self.base.identity(), true)
+    }
+
     /// Convert to config for building. Drops any store seam, since [`ClientConfig`]
     /// is `Clone`; prefer [`build`](Self::build), which consumes the seam directly.
     pub fn into_config(self) -> ClientConfig {
@@ -242,3 +293,61 @@ impl From<ClientConfig> for DefaultClientBuilder {
         Self::from_config(config)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use vertex_swarm_node::args::NetworkArgs;
+    use vertex_swarm_spec::init_dev;
+
+    fn test_network() -> NetworkConfig<KademliaConfig> {
+        NetworkConfig::try_from(&NetworkArgs {
+            port: 0,
+            mdns: false,
+            disable_discovery: true,
+            ..Default::default()
+        })
+        .expect("test network args are valid")
+    }
+
+    #[test]
+    fn light_node_is_ephemeral_retrieval_only() {
+        let builder = DefaultClientBuilder::light(
+            init_dev(),
+            test_network(),
+            DefaultBandwidthConfig::default(),
+        );
+
+        let identity = builder.base.identity();
+        assert!(identity.ephemeral(), "light nodes use an ephemeral nonce");
+        let node_type = identity.node_type();
+        assert!(!node_type.requires_pullsync());
+        assert!(!node_type.requires_storage());
+
+        assert_eq!(builder.local_store.cache_budget_bytes(), 0);
+        assert_eq!(builder.swap.enable, Some(false));
+        builder.validate().expect("an ephemeral client is valid");
+    }
+
+    #[test]
+    fn bootnode_identity_with_a_local_store_conflicts() {
+        let spec = init_dev();
+        let identity = Arc::new(Identity::new(
+            alloy_signer_local::PrivateKeySigner::random(),
+            nectar_primitives::Nonce::random(),
+            spec.clone(),
+            SwarmNodeType::Bootnode,
+        ));
+        let builder =
+            DefaultClientBuilder::from_parts(spec, identity, test_network(), Default::default());
+
+        assert!(matches!(
+            builder.validate(),
+            Err(SwarmNodeError::CapabilityConflict {
+                node_type: SwarmNodeType::Bootnode,
+                conflicting_feature: "local store",
+            })
+        ));
+    }
+}
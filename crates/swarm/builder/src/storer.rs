@@ -18,15 +18,17 @@ use vertex_node_api::{InfrastructureContext, NodeBuildsProtocol};
 use vertex_storage_redb::RedbDatabase;
 use vertex_swarm_accounting::DefaultBandwidthConfig;
 use vertex_swarm_api::{
-    BinCursorStore, PeerReporter, PullChunkVerifier, PullStorage, ReserveStore, StorageRadius,
-    StorerComponents, SwarmAccountingConfig, SwarmIdentity, SwarmLaunchConfig, SwarmLocalStore,
-    SwarmLocalStoreConfig, SwarmNetworkConfig, SwarmNodeType, SwarmPeerConfig, SwarmPricingConfig,
-    SwarmRoutingConfig, SwarmStorageConfig, construct,
+    BinCursorStore, ConfigDiagnostics, DiagnosticsHandle, Ledger, NetworkIngest, NetworkStore,
+    PeerReporter, PullChunkVerifier, PullStorage, ReserveStore, StampVerifier, StorageDiagnostics,
+    StorageRadius, StorerComponents, SwarmAccountingConfig, SwarmClientAccounting as _,
+    SwarmIdentity, SwarmLaunchConfig, SwarmLocalStore, SwarmLocalStoreConfig, SwarmNetworkConfig,
+    SwarmNodeType, SwarmPeerConfig, SwarmPricingConfig, SwarmRoutingConfig, SwarmStorageConfig,
+    construct,
 };
 use vertex_swarm_identity::Identity;
 use vertex_swarm_localstore::LocalStoreConfig;
 use vertex_swarm_node::args::{ChainConfig, NetworkConfig, SwapConfig};
-use vertex_swarm_node::{StorerNode, StorerPullsyncControl};
+use vertex_swarm_node::{PricingAnnouncement, StorerNode, StorerPullsyncControl};
 use vertex_swarm_postage::{AdmissionValidator, DbBatchStore};
 use vertex_swarm_puller::{
     FundingVerifier, PullerConfig, PullerHandle, PullerSeams, SignatureVerifier, spawn_puller,
@@ -43,9 +45,11 @@ use crate::launch::{
     AssemblyInputs, CacheSeam, ClientLaunchTypes, ClientNodeParams, NodeAssembly,
     build_client_backed_node, resolve_cache,
 };
-use crate::node::{ClientNodeBuilder, NodeBuilder};
+use crate::node::{ClientNodeBuilder, NodeBuilder, check_capabilities};
 use crate::protocol::SwarmProtocol;
-use vertex_swarm_node::{NativeChunkProvider, NodeRunParts, RunTaskFn, single_task};
+use vertex_swarm_node::{
+    NativeChunkProvider, NodeDiagnostics, NodeRunParts, RunTaskFn, single_task,
+};
 
 /// A reserve override supplied through the builder. With no seam the storer launch
 /// path builds the default admission-gated [`DbReserve`] over the shared database.
@@ -266,6 +270,12 @@ impl DefaultStorerBuilder {
         builder
     }
 
+    /// Check the identity against the storer capabilities, as every storer
+    /// build does first.
+    pub fn validate(&self) -> Result<(), SwarmNodeError> {
+        check_capabilities(SwarmNodeType::Storer, self.client.base.identity(), true)
+    }
+
     /// Convert to config for building.
     pub fn into_config(self) -> StorerConfig {
         StorerConfig::new(
@@ -328,13 +338,15 @@ type StorerProviders = StorerComponents<
 /// Both `None` reproduces the default: the admission-gated [`DbReserve`] is the
 /// pushsync-ingest reserve, layered under a default in-memory forwarding cache for
 /// the retrieval-serve view. A reserve seam replaces the reserve; a cache seam
-/// replaces the forwarding cache.
+/// replaces the forwarding cache. An identity that contradicts the storer
+/// capabilities is refused first.
 pub(crate) async fn build_storer(
     config: StorerConfig,
     ctx: &dyn InfrastructureContext,
     cache: Option<CacheSeam>,
     reserve: Option<ReserveSeam>,
 ) -> Result<(NodeTaskFn, StorerProviders), SwarmNodeError> {
+    check_capabilities(SwarmNodeType::Storer, config.identity(), true)?;
     // Reserve capacity is a consensus quantity read from the spec, not local disk:
     // a fixed power-of-two chunk count from which the redistribution game derives
     // storage radius and committed depth, so nodes covering one neighbourhood must
@@ -344,8 +356,12 @@ pub(crate) async fn build_storer(
     let identity = config.identity().clone();
     let cache_budget = config.local_store().cache_budget_bytes();
     let soc_ttl = config.local_store().soc_cache_ttl();
+    let validation_concurrency = config.storage().validation_concurrency();
+    let refuse_out_of_range = config.storage().refuse_out_of_range();
+    let config_diagnostics =
+        ConfigDiagnostics::from_network(SwarmNodeType::Storer, config.network());
 
-    let parts = build_client_backed_node(
+    let (parts, finance) = build_client_backed_node(
         ctx,
         ClientNodeParams {
             spec: config.spec(),
@@ -357,12 +373,32 @@ pub(crate) async fn build_storer(
             #[cfg(feature = "swap")]
             swap: config.swap(),
         },
-        StorerAssembly::new(cache, reserve, identity, capacity, cache_budget, soc_ttl),
+        StorerAssembly::new(
+            cache,
+            reserve,
+            identity,
+            capacity,
+            cache_budget,
+            soc_ttl,
+            validation_concurrency,
+            refuse_out_of_range,
+        ),
     )
     .await?;
 
     let (store, reserve) = parts.provider_store;
-    let providers = construct::storer(parts.topology, parts.chunks, store, reserve);
+    let probe = Arc::clone(&reserve);
+    let diagnostics = NodeDiagnostics::new(parts.topology.clone(), config_diagnostics)
+        .with_ledger(parts.accounting.bandwidth().clone() as Arc<dyn Ledger>)
+        .with_storage(Arc::new(move || StorageDiagnostics::from_reserve(&*probe)));
+    let providers = construct::storer(
+        parts.topology,
+        parts.chunks,
+        store,
+        reserve,
+        DiagnosticsHandle::new(diagnostics),
+        finance,
+    );
     Ok((parts.task, providers))
 }
 
@@ -383,6 +419,8 @@ struct StorerAssembly {
     capacity: u64,
     cache_budget_bytes: u64,
     soc_cache_ttl: u64,
+    validation_concurrency: usize,
+    refuse_out_of_range: bool,
 }
 
 impl StorerAssembly {
@@ -393,6 +431,8 @@ impl StorerAssembly {
         capacity: u64,
         cache_budget_bytes: u64,
         soc_cache_ttl: u64,
+        validation_concurrency: usize,
+        refuse_out_of_range: bool,
     ) -> Self {
         Self {
             cache,
@@ -401,6 +441,8 @@ impl StorerAssembly {
             capacity,
             cache_budget_bytes,
             soc_cache_ttl,
+            validation_concurrency,
+            refuse_out_of_range,
         }
     }
 }
@@ -436,6 +478,9 @@ impl NodeAssembly for StorerAssembly {
             serve.reserve,
             serve.pullsync,
             serve.batches,
+            self.validation_concurrency,
+            self.refuse_out_of_range,
+            inputs.pricing,
             inputs.pseudosettle_event_sender,
             #[cfg(feature = "swap")]
             inputs.swap_event_sender,
@@ -504,6 +549,9 @@ async fn assemble_storer_node(
     reserve: Arc<dyn BinCursorStore>,
     pullsync: Option<Arc<dyn PullStorage>>,
     batches: Option<DbBatchStore<RedbDatabase>>,
+    validation_concurrency: usize,
+    refuse_out_of_range: bool,
+    pricing: PricingAnnouncement,
     pseudosettle_event_sender: tokio::sync::mpsc::UnboundedSender<
         vertex_swarm_node::PseudosettleEvent,
     >,
@@ -519,6 +567,7 @@ async fn assemble_storer_node(
     let node_builder = StorerNode::builder(identity.clone())
         .with_store(node_store)
         .with_pullsync_storage(pullsync_storage)
+        .with_pricing(pricing)
         .with_pseudosettle_events(pseudosettle_event_sender);
     #[cfg(feature = "swap")]
     let node_builder = match swap_event_sender {
@@ -531,6 +580,15 @@ async fn assemble_storer_node(
         .map_err(|e| SwarmNodeError::Build(e.into()))?;
     let topology = node.topology_handle().clone();
 
+    // Pushed chunks pass the same batch-funding check as pulled ones. A reserve
+    // seam carries no batch set, leaving the pushsync path signature-only.
+    let stamp_verifier = batches.clone().map(|batches| {
+        Arc::new(FundingVerifier::new(
+            batches,
+            AdmissionValidator::new(RESERVE_CONFIRMATION_THRESHOLD),
+        )) as Arc<dyn StampVerifier>
+    });
+
     // The reserve is the puller's admit seam (a `SwarmLocalStore` put) and the
     // pushsync ingest store; the interval store persists per-peer sync progress.
     let intervals = open_interval_store(db)?;
@@ -554,7 +612,12 @@ async fn assemble_storer_node(
             Arc::clone(&accounting),
             client_handle,
         );
-        node.enable_storage(reserve as Arc<dyn ReserveStore>);
+        node.enable_storage(
+            reserve as Arc<dyn ReserveStore>,
+            stamp_verifier,
+            validation_concurrency,
+            refuse_out_of_range,
+        );
         single_task(move |shutdown| async move {
             let _accounting = accounting;
             if let Err(e) = node.start_and_run(shutdown).await {
@@ -619,9 +682,12 @@ fn spawn_storer_puller(
         control,
         intervals,
         verifier,
-        // The reserve admits through its `SwarmLocalStore` put (the blanket
-        // `ReserveAdmit` impl).
-        admit: reserve as Arc<dyn SwarmLocalStore>,
+        // Pulled chunks reach the reserve only through the spec's chunk-set
+        // check.
+        admit: NetworkStore::new(
+            reserve as Arc<dyn SwarmLocalStore>,
+            NetworkIngest::for_spec::<Spec>(),
+        ),
         readiness: pullsync::TopologyReadiness::new(topology.clone()),
         neighbours: pullsync::TopologyNeighbours::new(topology),
         reporter,
@@ -840,4 +906,35 @@ mod tests {
             "a put through the serve view must not reach the built reserve"
         );
     }
+
+    #[test]
+    fn storer_with_an_ephemeral_identity_conflicts() {
+        use vertex_swarm_node::args::NetworkArgs;
+
+        let spec = vertex_swarm_spec::init_dev();
+        let identity = Arc::new(Identity::random(spec.clone(), SwarmNodeType::Storer));
+        let network = NetworkConfig::try_from(&NetworkArgs {
+            port: 0,
+            mdns: false,
+            disable_discovery: true,
+            ..Default::default()
+        })
+        .expect("test network args are valid");
+        let builder = DefaultStorerBuilder::from_parts(
+            spec,
+            identity,
+            network,
+            DefaultBandwidthConfig::default(),
+            LocalStoreConfig::default(),
+            StorageConfig::new(false),
+        );
+
+        assert!(matches!(
+            builder.validate(),
+            Err(SwarmNodeError::CapabilityConflict {
+                node_type: SwarmNodeType::Storer,
+                conflicting_feature: "ephemeral identity",
+            })
+        ));
+    }
 }
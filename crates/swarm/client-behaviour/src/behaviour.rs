@@ -4,7 +4,10 @@
 
 use std::{
     collections::{HashMap, VecDeque},
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
     task::{Context, Poll},
 };
 
@@ -19,9 +22,11 @@ use libp2p::{
 };
 use tokio::sync::mpsc;
 use tracing::{debug, warn};
-use vertex_swarm_api::{Au, SwarmLocalStore};
+use vertex_swarm_api::{Au, NetworkIngest, SwarmAccountingConfig, SwarmLocalStore};
+use vertex_swarm_forks::ForkCondition;
+use vertex_swarm_net_headers::ProtocolPrefix;
 use vertex_swarm_net_pseudosettle::PaymentAck;
-use vertex_swarm_primitives::OverlayAddress;
+use vertex_swarm_primitives::{OverlayAddress, SwarmNodeType};
 
 #[cfg(feature = "swap")]
 use vertex_swarm_client_protocol::SwapEvent;
@@ -32,16 +37,60 @@ use vertex_swarm_client_protocol::{
 use super::{
     forward::Forwarder,
     handler::{ClientHandler, Config as HandlerConfig, HandlerCommand, HandlerEvent},
+    metrics::{LatencyHistograms, LatencySnapshot},
     storer::StorerCapability,
+    validation::ValidationStats,
 };
 
 const DEFAULT_MAX_PENDING_EVENTS: usize = 4096;
 
+/// Payment thresholds announced to a peer on activation, by its node type.
+///
+/// A client peer gets the lower light threshold, a storer the full one. A
+/// bootnode runs no accounting and is not announced to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PricingAnnouncement {
+    /// Threshold announced to storer peers.
+    pub full: U256,
+    /// Threshold announced to client peers.
+    pub light: U256,
+}
+
+impl PricingAnnouncement {
+    /// Announce `full` to storer peers and `light` to client peers.
+    pub fn new(full: U256, light: U256) -> Self {
+        Self { full, light }
+    }
+
+    /// Announce the configured payment threshold to storers and that threshold
+    /// divided by the client-only factor to clients.
+    ///
+    /// `config` is the unscaled node config: a client node's own accounting runs
+    /// on the already-divided threshold, but what it tells peers does not.
+    pub fn from_config<C: SwarmAccountingConfig>(config: &C) -> Self {
+        let full = config.payment_threshold().as_amount();
+        let light = (full / config.client_only_factor().max(1)).max(1);
+        Self::new(U256::from(full), U256::from(light))
+    }
+
+    /// The threshold to announce to a peer of `node_type`, if any.
+    pub fn threshold_for(&self, node_type: SwarmNodeType) -> Option<U256> {
+        match node_type {
+            SwarmNodeType::Bootnode => None,
+            SwarmNodeType::Client => Some(self.light),
+            SwarmNodeType::Storer => Some(self.full),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub handler: HandlerConfig,
     /// Pending-event queue cap; events past it are dropped.
     pub max_pending_events: usize,
+    /// When set, every activated peer is sent the threshold for its
+    /// handshake-advertised node type.
+    pub pricing: Option<PricingAnnouncement>,
 }
 
 impl Default for Config {
@@ -49,6 +98,7 @@ impl Default for Config {
         Self {
             handler: HandlerConfig::default(),
             max_pending_events: DEFAULT_MAX_PENDING_EVENTS,
+            pricing: None,
         }
     }
 }
@@ -56,11 +106,50 @@ impl Default for Config {
 impl Config {
     /// The handler's inbound protocol set is narrowed by role: bootnodes
     /// advertise pricing only, clients and storers the full set.
-    pub fn for_role(local_role: vertex_swarm_primitives::SwarmNodeType) -> Self {
+    pub fn for_role(local_role: SwarmNodeType) -> Self {
         let mut cfg = Self::default();
         cfg.handler.local_role = local_role;
         cfg
     }
+
+    /// Announce payment thresholds to peers as they activate.
+    #[must_use]
+    pub fn with_pricing(mut self, pricing: PricingAnnouncement) -> Self {
+        self.pricing = Some(pricing);
+        self
+    }
+
+    /// Refuse to forward inbound retrieval misses until at least `min_peers`
+    /// peers are activated. Local hits are served regardless.
+    #[must_use]
+    pub fn with_min_peers_to_serve(mut self, min_peers: usize) -> Self {
+        self.handler.min_peers_to_serve = min_peers;
+        self
+    }
+
+    /// Speak the Accord retrieval fields once `accord`, the spec's
+    /// activation of that hardfork, is reached.
+    #[must_use]
+    pub fn with_accord(mut self, accord: ForkCondition) -> Self {
+        self.handler.accord = accord;
+        self
+    }
+
+    /// Validate chunks received from peers against the spec's chunk set
+    /// instead of the standard one.
+    #[must_use]
+    pub fn with_ingest(mut self, ingest: NetworkIngest) -> Self {
+        self.handler.ingest = ingest;
+        self
+    }
+
+    /// Advertise and request every client protocol under the spec's protocol
+    /// prefix instead of `/swarm`.
+    #[must_use]
+    pub fn with_protocol_prefix(mut self, prefix: &str) -> Self {
+        self.handler.protocol_prefix = ProtocolPrefix::new(prefix);
+        self
+    }
 }
 
 /// Creates dormant handlers per connection and activates them on an
@@ -81,6 +170,10 @@ pub struct ClientBehaviour {
     storer: Option<StorerCapability>,
     peer_overlays: HashMap<PeerId, OverlayAddress>,
     overlay_peers: HashMap<OverlayAddress, PeerId>,
+    /// Mirrors `peer_overlays.len()` for the handlers' serve gate.
+    active_peers: Arc<AtomicUsize>,
+    /// Cloned into each handler, which records its completed exchanges.
+    latency: LatencyHistograms,
     pending_events: VecDeque<ToSwarm<ClientEvent, HandlerCommand>>,
     pseudosettle_event_tx: Option<mpsc::UnboundedSender<PseudosettleEvent>>,
     #[cfg(feature = "swap")]
@@ -100,6 +193,8 @@ impl ClientBehaviour {
             storer: None,
             peer_overlays: HashMap::new(),
             overlay_peers: HashMap::new(),
+            active_peers: Arc::new(AtomicUsize::new(0)),
+            latency: LatencyHistograms::default(),
             pending_events: VecDeque::new(),
             pseudosettle_event_tx: None,
             #[cfg(feature = "swap")]
@@ -116,6 +211,22 @@ impl ClientBehaviour {
         self.storer = Some(storer);
     }
 
+    /// Ingest validation queue depth and concurrency; `None` without a storer.
+    pub fn validation_stats(&self) -> Option<ValidationStats> {
+        self.storer.as_ref().map(StorerCapability::validation_stats)
+    }
+
+    /// Per-protocol latency distributions of completed outbound exchanges.
+    pub fn latency_stats(&self) -> LatencySnapshot {
+        self.latency.snapshot()
+    }
+
+    /// A handle on the latency histograms that stays readable once the
+    /// behaviour has moved into the swarm.
+    pub fn latency(&self) -> LatencyHistograms {
+        self.latency.clone()
+    }
+
     /// Install the multi-hop relay forwarder, replacing the default stub.
     ///
     /// Must run before any peer connects: handlers clone it at connection setup.
@@ -138,6 +249,8 @@ impl ClientBehaviour {
             Arc::clone(&self.store),
             Arc::clone(&self.forward),
             self.storer.clone(),
+            Arc::clone(&self.active_peers),
+            self.latency.clone(),
         )
     }
 
@@ -169,15 +282,34 @@ impl ClientBehaviour {
                 peer_id,
                 overlay,
                 node_type,
+                capabilities,
             } => {
-                debug!(%peer_id, %overlay, ?node_type, "Activating peer");
+                debug!(%peer_id, %overlay, ?node_type, ?capabilities, "Activating peer");
                 self.peer_overlays.insert(peer_id, overlay);
                 self.overlay_peers.insert(overlay, peer_id);
+                self.active_peers
+                    .store(self.peer_overlays.len(), Ordering::Relaxed);
                 self.push_event(ToSwarm::NotifyHandler {
                     peer_id,
                     handler: libp2p::swarm::NotifyHandler::Any,
-                    event: HandlerCommand::Activate { overlay, node_type },
+                    event: HandlerCommand::Activate {
+                        overlay,
+                        node_type,
+                        capabilities,
+                    },
                 });
+                if let Some(threshold) = self
+                    .config
+                    .pricing
+                    .and_then(|pricing| pricing.threshold_for(node_type))
+                {
+                    debug!(%peer_id, %overlay, %threshold, "Announcing pricing on activation");
+                    self.push_event(ToSwarm::NotifyHandler {
+                        peer_id,
+                        handler: libp2p::swarm::NotifyHandler::Any,
+                        event: HandlerCommand::AnnouncePricing { threshold },
+                    });
+                }
             }
             ClientCommand::AnnouncePricing { peer, threshold } => {
                 if let Some(&peer_id) = self.overlay_peers.get(&peer) {
@@ -196,6 +328,8 @@ impl ClientBehaviour {
                 address,
                 response,
                 originated,
+                ttl,
+                deadline,
             } => {
                 if let Some(&peer_id) = self.overlay_peers.get(&peer) {
                     debug!(%peer_id, %peer, %address, "Retrieving chunk");
@@ -206,6 +340,8 @@ impl ClientBehaviour {
                             address,
                             response,
                             originated,
+                            ttl,
+                            deadline,
                         },
                     });
                 } else {
@@ -331,6 +467,12 @@ impl ClientBehaviour {
                     address,
                 }));
             }
+            HandlerEvent::InboundNotReady { overlay, address } => {
+                self.push_event(ToSwarm::GenerateEvent(ClientEvent::InboundNotReady {
+                    peer: overlay,
+                    address,
+                }));
+            }
             HandlerEvent::InboundRelayed { overlay } => {
                 self.push_event(ToSwarm::GenerateEvent(ClientEvent::InboundRelayed {
                     peer: overlay,
@@ -347,6 +489,12 @@ impl ClientBehaviour {
                     address,
                 }));
             }
+            HandlerEvent::InboundStalled { overlay, protocol } => {
+                self.push_event(ToSwarm::GenerateEvent(ClientEvent::InboundStalled {
+                    peer: overlay,
+                    protocol,
+                }));
+            }
             HandlerEvent::ChunkReceived {
                 overlay,
                 address,
@@ -556,6 +704,8 @@ impl NetworkBehaviour for ClientBehaviour {
             && let Some(overlay) = self.peer_overlays.remove(&info.peer_id)
         {
             self.overlay_peers.remove(&overlay);
+            self.active_peers
+                .store(self.peer_overlays.len(), Ordering::Relaxed);
             debug!(peer_id = %info.peer_id, %overlay, "Peer disconnected");
             // A full disconnect may never surface as a substream error, so
             // release any pending settle for this peer here too.
@@ -625,7 +775,7 @@ fn domain_ack(ack: PaymentAck) -> PseudosettleAck {
 mod tests {
     use libp2p::PeerId;
     use vertex_swarm_api::{ChunkAddress, SwarmResult};
-    use vertex_swarm_primitives::CachedChunk;
+    use vertex_swarm_primitives::{CachedChunk, PeerCapabilities};
     use vertex_swarm_test_utils::test_peer;
 
     use super::*;
@@ -696,6 +846,66 @@ mod tests {
         assert!(rx.try_recv().is_err());
     }
 
+    /// The threshold handed to the handler on activation for a peer of
+    /// `node_type`.
+    fn announced_threshold(node_type: SwarmNodeType) -> Option<U256> {
+        let pricing = PricingAnnouncement::new(U256::from(13_500_000), U256::from(1_350_000));
+        let mut behaviour = ClientBehaviour::new(
+            Config::default().with_pricing(pricing),
+            Arc::new(NoopStore),
+            Arc::new(StubForwarder),
+        );
+        behaviour.on_command(ClientCommand::ActivatePeer {
+            peer_id: PeerId::random(),
+            overlay: test_peer(),
+            node_type,
+            capabilities: PeerCapabilities::NONE,
+        });
+
+        behaviour
+            .pending_events
+            .iter()
+            .find_map(|event| match event {
+                ToSwarm::NotifyHandler {
+                    event: HandlerCommand::AnnouncePricing { threshold },
+                    ..
+                } => Some(*threshold),
+                _ => None,
+            })
+    }
+
+    #[test]
+    fn pricing_announcement_follows_peer_node_type() {
+        assert_eq!(
+            announced_threshold(SwarmNodeType::Client),
+            Some(U256::from(1_350_000))
+        );
+        assert_eq!(
+            announced_threshold(SwarmNodeType::Storer),
+            Some(U256::from(13_500_000))
+        );
+        assert_eq!(announced_threshold(SwarmNodeType::Bootnode), None);
+    }
+
+    #[test]
+    fn no_pricing_announcement_without_config() {
+        let mut behaviour = build_behaviour();
+        behaviour.on_command(ClientCommand::ActivatePeer {
+            peer_id: PeerId::random(),
+            overlay: test_peer(),
+            node_type: SwarmNodeType::Storer,
+            capabilities: PeerCapabilities::NONE,
+        });
+
+        assert!(!behaviour.pending_events.iter().any(|event| matches!(
+            event,
+            ToSwarm::NotifyHandler {
+                event: HandlerCommand::AnnouncePricing { .. },
+                ..
+            }
+        )));
+    }
+
     #[cfg(feature = "swap")]
     #[test]
     fn swap_substream_error_routes_failed() {
@@ -23,6 +23,8 @@
 //! fails, the handler drops the action, releasing the reservation, so the
 //! requester is never charged for a delivery it did not receive.
 
+use std::time::Duration;
+
 use futures::future::BoxFuture;
 use nectar_primitives::{AnyChunk, ChunkAddress};
 use vertex_swarm_api::{CommitOnWrite, SwarmTopologyRouting};
@@ -38,8 +40,8 @@ use vertex_swarm_primitives::{OverlayAddress, Stamp, StampedChunk};
 /// of the chain comes from the strictly-closer rule (every hop must hand the
 /// request to a peer strictly closer to the target by XOR distance than both the
 /// requester and that node), which makes proximity monotonically increase toward
-/// the target and is bounded by the address width, so no per-request hop counter
-/// or visited set is needed.
+/// the target and is bounded by the address width. Retrievals also carry a hop
+/// TTL on the wire as a backstop against peers that do not apply that rule.
 pub(crate) const MAX_FORWARD_CANDIDATES: usize = 3;
 
 /// Why a forward could not complete.
@@ -92,6 +94,27 @@ pub enum ForwardError {
     /// on drop.
     #[error("accounting refused the relay")]
     AccountingRefused,
+
+    /// The inbound retrieval arrived with no hops left
+    /// ([`RetrievalError::TtlExceeded`](vertex_swarm_net_retrieval::RetrievalError::TtlExceeded)),
+    /// so it is not relayed.
+    #[error("retrieval TTL exceeded")]
+    TtlExceeded,
+
+    /// The requester's deadline ran out before a relay answered, so the relay
+    /// was abandoned: nobody is waiting for the chunk any more.
+    #[error("retrieval deadline exceeded")]
+    DeadlineExceeded,
+
+    /// The inbound push carried no stamp, so it cannot be relayed: every
+    /// downstream peer requires one.
+    #[error("pushed chunk carries no stamp")]
+    Unstamped,
+
+    /// Too few peers are activated to forward a retrieval miss (under the
+    /// handler's `min_peers_to_serve`), so the relay was not attempted.
+    #[error("not enough peers to forward")]
+    NotReady,
 }
 
 /// Relays a retrieval or a pushsync to a closer peer on behalf of an inbound
@@ -104,7 +127,10 @@ pub enum ForwardError {
 /// is `Send` on both native and wasm (the browser `Stream` is itself `Send`), so
 /// the inbound serving futures are `Send` too.
 pub trait Forwarder: Send + Sync {
-    /// Retrieve `address` from a closer peer, excluding `exclude`.
+    /// Retrieve `address` from a closer peer, excluding `exclude`, sending the
+    /// request on with `ttl` hops left and, when the requester set one,
+    /// `deadline` of time left. The caller abandons the returned future once
+    /// that time is up; a forwarder should not start a leg it cannot finish.
     ///
     /// On success the downstream `receive` leg is already committed (we did
     /// receive the chunk), and the un-applied upstream `provide` action is
@@ -115,6 +141,8 @@ pub trait Forwarder: Send + Sync {
         &self,
         address: ChunkAddress,
         exclude: OverlayAddress,
+        ttl: u8,
+        deadline: Option<Duration>,
     ) -> BoxFuture<'static, Result<ForwardedChunk, ForwardError>>;
 
     /// Push `chunk` to a closer peer, excluding `exclude`, returning the
@@ -208,6 +236,8 @@ impl Forwarder for StubForwarder {
         &self,
         _address: ChunkAddress,
         _exclude: OverlayAddress,
+        _ttl: u8,
+        _deadline: Option<Duration>,
     ) -> BoxFuture<'static, Result<ForwardedChunk, ForwardError>> {
         Box::pin(async { Err(ForwardError::NoCloserPeer) })
     }
@@ -12,13 +12,17 @@
 //! cannot be folded inline.
 
 use std::{
+    cmp::Reverse,
     collections::{HashMap, VecDeque},
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
     task::{Context, Poll},
     time::Duration,
 };
 
-use vertex_util_runtime::time::Instant;
+use vertex_util_runtime::time::{Instant, now_unix_secs};
 
 use alloy_primitives::U256;
 use futures::future::BoxFuture;
@@ -33,24 +37,32 @@ use libp2p::swarm::{
 };
 use nectar_primitives::{AnyChunk, ChunkAddress, NetworkId};
 use tracing::{debug, warn};
-use vertex_swarm_api::SwarmLocalStore;
+use vertex_net_codec::Compression;
+use vertex_swarm_api::{NetworkIngest, NetworkStore, StampPolicy, SwarmLocalStore};
+use vertex_swarm_forks::ForkCondition;
+use vertex_swarm_net_headers::ProtocolPrefix;
 use vertex_swarm_net_pseudosettle::PaymentAck;
 use vertex_swarm_net_pushsync::Receipt;
 #[cfg(feature = "swap")]
 use vertex_swarm_net_swap::SignedCheque;
-use vertex_swarm_primitives::{OverlayAddress, Stamp, StampedChunk, SwarmNodeType};
+use vertex_swarm_primitives::{
+    OverlayAddress, PeerCapabilities, Stamp, StampedChunk, SwarmNodeType,
+};
 
 use super::events::{PushResponseTx, RetrievalResponseTx};
 use super::forward::Forwarder;
+use super::metrics::{LatencyHistograms, LatencyProtocol};
 use super::serve::{self, PushServe, RetrieveServe};
 use super::storer::StorerCapability;
 use super::upgrade::{
     ClientInboundOutput, ClientInboundUpgrade, ClientOutboundInfo, ClientOutboundOutput,
-    ClientOutboundUpgrade, ClientUpgradeError, FailureKind,
+    ClientOutboundUpgrade, ClientUpgradeError, FailureKind, InboundDeadlines, dial_failure_kind,
 };
 use vertex_swarm_client_protocol::{ChunkTransferError, RetrievalResult};
 use vertex_swarm_net_pushsync::PROTOCOL_NAME as PUSHSYNC_PROTOCOL;
-use vertex_swarm_net_retrieval::PROTOCOL_NAME as RETRIEVAL_PROTOCOL;
+use vertex_swarm_net_retrieval::{
+    DEFAULT_RETRIEVAL_TTL, PROTOCOL_NAME as RETRIEVAL_PROTOCOL, Request as RetrievalRequest,
+};
 
 const DEFAULT_MAX_PENDING_COMMANDS: usize = 256;
 const DEFAULT_MAX_PENDING_EVENTS: usize = 256;
@@ -66,6 +78,10 @@ const RESPONDER_STALE_TIMEOUT: Duration = Duration::from_secs(60);
 /// `listen_protocol` stops advertising inbound serving so the muxer
 /// back-pressures the peer.
 const MAX_INBOUND_SERVING: usize = 32;
+/// Headroom for protocol negotiation on top of the longest inbound deadline, so
+/// the per-protocol deadline fires (and is attributed) before libp2p's silent
+/// listen timeout does.
+const INBOUND_NEGOTIATION_GRACE: Duration = Duration::from_secs(5);
 
 /// Outcome of serving one inbound retrieval or pushsync request. The response is
 /// already sent (or the substream reset) inside the future; this carries only the
@@ -81,6 +97,12 @@ pub(crate) enum InboundOutcome {
         overlay: OverlayAddress,
         address: ChunkAddress,
     },
+    /// Retrieval missed locally while under `min_peers_to_serve`, so it was
+    /// not forwarded; substream reset.
+    NotReady {
+        overlay: OverlayAddress,
+        address: ChunkAddress,
+    },
     /// Pushsync forwarded and the storer's receipt relayed verbatim.
     Relayed { overlay: OverlayAddress },
     /// Pushsync the node is responsible for: stored into the reserve and
@@ -91,6 +113,54 @@ pub(crate) enum InboundOutcome {
         overlay: OverlayAddress,
         address: ChunkAddress,
     },
+    /// The peer stopped reading before the response landed within the inbound
+    /// deadline; substream dropped.
+    Stalled {
+        overlay: OverlayAddress,
+        protocol: &'static str,
+    },
+}
+
+/// Scheduling class of an outbound substream.
+///
+/// Commands queue in the handler between polls; each poll opens the
+/// highest-priority queued substream first, oldest first within a class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OutboundPriority {
+    /// Opened only once nothing more urgent is queued.
+    Low,
+    /// The default class.
+    Normal,
+    /// Opened ahead of every lower class.
+    High,
+}
+
+/// Per-protocol [`OutboundPriority`], configurable on [`Config`].
+///
+/// Settlement (pricing, pseudosettle, swap) defaults above chunk transfer so a
+/// retrieval burst cannot starve the payments that keep the peer willing to
+/// serve, and pushsync defaults above opportunistic retrieval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutboundPriorities {
+    /// Payment-threshold announcement.
+    pub pricing: OutboundPriority,
+    /// Pseudosettle payments and swap cheques.
+    pub settlement: OutboundPriority,
+    /// Chunk pushes, originated or relayed.
+    pub pushsync: OutboundPriority,
+    /// Chunk retrievals, originated or relayed.
+    pub retrieval: OutboundPriority,
+}
+
+impl Default for OutboundPriorities {
+    fn default() -> Self {
+        Self {
+            pricing: OutboundPriority::High,
+            settlement: OutboundPriority::High,
+            pushsync: OutboundPriority::Normal,
+            retrieval: OutboundPriority::Low,
+        }
+    }
 }
 
 /// Configuration for the client handler.
@@ -102,22 +172,55 @@ pub(crate) enum InboundOutcome {
 /// rather than stalling the caller. This is the only liveness boundary against a
 /// withholding peer. Do not collapse them into the shared `timeout` (used by
 /// pricing, pseudosettle, and swap); tuning one must not move settlement.
+///
+/// The inbound side mirrors this: `inbound_retrieval_timeout` and
+/// `inbound_pushsync_timeout` bound reading the peer's request and, separately,
+/// writing our response. A peer that opens a substream and stalls is dropped
+/// and reported rather than holding the substream and its buffers. Inbound
+/// pricing, pseudosettle, and swap reads are bounded by `timeout`.
 #[derive(Debug, Clone)]
 pub struct Config {
     /// Shared deadline for pricing, pseudosettle, and swap.
     pub timeout: Duration,
-    /// Outbound retrieval deadline; see the type-level note.
+    /// Outbound retrieval deadline; see the type-level note. From Accord on it
+    /// is also sent as the request's time budget, so relays stop once we stop
+    /// waiting.
     pub retrieval_timeout: Duration,
     /// Outbound pushsync deadline; see the type-level note.
     pub pushsync_timeout: Duration,
+    /// Inbound retrieval read and write deadline; see the type-level note.
+    pub inbound_retrieval_timeout: Duration,
+    /// Inbound pushsync read and write deadline; see the type-level note.
+    pub inbound_pushsync_timeout: Duration,
+    /// Hop budget put on retrievals we originate, and assumed for inbound
+    /// requests that carry none.
+    pub retrieval_ttl: u8,
+    /// Activation of [`SwarmHardfork::Accord`](vertex_swarm_forks::SwarmHardfork)
+    /// for the spec. Before it the hop and time budgets are neither sent nor
+    /// read, so the retrieval wire matches pre-Accord peers.
+    pub accord: ForkCondition,
+    /// Activated peers required before an inbound retrieval miss is
+    /// forwarded. Below it the node cannot reach enough of the network to
+    /// forward, so a miss is refused at once as not ready (the substream
+    /// resets) instead of failing slowly; a local hit is still served. Zero
+    /// disables the gate.
+    pub min_peers_to_serve: usize,
     pub max_pending_commands: usize,
     pub max_pending_events: usize,
+    /// Order in which queued outbound substreams are opened.
+    pub outbound_priorities: OutboundPriorities,
     /// Controls which protocols are advertised on inbound upgrades and which
     /// outbound commands are honoured. Bootnodes only speak pricing.
     pub local_role: SwarmNodeType,
     /// Used to recover the signer overlay of an inbound custody receipt at decode
     /// (`compute_overlay(eth, network_id, nonce)`).
     pub network_id: NetworkId,
+    /// Namespace every client protocol is advertised and requested under,
+    /// from the spec's protocol prefix.
+    pub protocol_prefix: ProtocolPrefix,
+    /// The network's chunk set; chunks received from peers are validated
+    /// against it before they are cached or taken into custody.
+    pub ingest: NetworkIngest,
     /// Advertised swap exchange rate sent in the swap headers exchange.
     #[cfg(feature = "swap")]
     pub swap_exchange_rate: U256,
@@ -129,10 +232,18 @@ impl Default for Config {
             timeout: Duration::from_secs(30),
             retrieval_timeout: Duration::from_secs(30),
             pushsync_timeout: Duration::from_secs(30),
+            inbound_retrieval_timeout: Duration::from_secs(30),
+            inbound_pushsync_timeout: Duration::from_secs(30),
+            retrieval_ttl: DEFAULT_RETRIEVAL_TTL,
+            accord: ForkCondition::Never,
+            min_peers_to_serve: 0,
             max_pending_commands: DEFAULT_MAX_PENDING_COMMANDS,
             max_pending_events: DEFAULT_MAX_PENDING_EVENTS,
+            outbound_priorities: OutboundPriorities::default(),
             local_role: SwarmNodeType::Client,
             network_id: NetworkId::MAINNET,
+            protocol_prefix: ProtocolPrefix::DEFAULT,
+            ingest: NetworkIngest::default(),
             #[cfg(feature = "swap")]
             swap_exchange_rate: U256::ZERO,
         }
@@ -147,6 +258,7 @@ pub enum HandlerCommand {
     Activate {
         overlay: OverlayAddress,
         node_type: SwarmNodeType,
+        capabilities: PeerCapabilities,
     },
     /// Announce our payment threshold to the peer.
     AnnouncePricing { threshold: U256 },
@@ -156,6 +268,11 @@ pub enum HandlerCommand {
         response: RetrievalResponseTx,
         /// True for our own request, false for a forwarder relay leg.
         originated: bool,
+        /// Hop budget to send; `None` uses the configured TTL.
+        ttl: Option<u8>,
+        /// Time budget to send, also bounding the substream; `None` uses
+        /// the configured retrieval timeout.
+        deadline: Option<Duration>,
     },
     /// Push a chunk to the peer for storage.
     PushChunk {
@@ -173,6 +290,22 @@ pub enum HandlerCommand {
     SendCheque { cheque: SignedCheque },
 }
 
+impl HandlerCommand {
+    /// Priority of the outbound substream this command opens, or `None` for a
+    /// command that opens none.
+    fn outbound_priority(&self, priorities: &OutboundPriorities) -> Option<OutboundPriority> {
+        match self {
+            Self::Activate { .. } | Self::AckPseudosettle { .. } => None,
+            Self::AnnouncePricing { .. } => Some(priorities.pricing),
+            Self::RetrieveChunk { .. } => Some(priorities.retrieval),
+            Self::PushChunk { .. } => Some(priorities.pushsync),
+            Self::SendPseudosettle { .. } => Some(priorities.settlement),
+            #[cfg(feature = "swap")]
+            Self::SendCheque { .. } => Some(priorities.settlement),
+        }
+    }
+}
+
 /// Events emitted by the handler to the behaviour.
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
@@ -195,6 +328,12 @@ pub enum HandlerEvent {
         overlay: OverlayAddress,
         address: ChunkAddress,
     },
+    /// Refused to forward an inbound retrieval miss while under
+    /// `min_peers_to_serve`; substream reset.
+    InboundNotReady {
+        overlay: OverlayAddress,
+        address: ChunkAddress,
+    },
     /// Relayed a storer's receipt for an inbound pushsync.
     InboundRelayed { overlay: OverlayAddress },
     /// Took custody of an inbound pushsync: stored and acknowledged with our own
@@ -205,6 +344,12 @@ pub enum HandlerEvent {
         overlay: OverlayAddress,
         address: ChunkAddress,
     },
+    /// A peer made no progress on an inbound substream within the protocol's
+    /// deadline, reading its request or taking our response; substream dropped.
+    InboundStalled {
+        overlay: OverlayAddress,
+        protocol: &'static str,
+    },
     /// Received a chunk from peer. `latency` is request-to-delivery, for scoring.
     ChunkReceived {
         overlay: OverlayAddress,
@@ -227,7 +372,7 @@ pub enum HandlerEvent {
     },
     /// An outbound retrieval failed. The requester is already resolved through
     /// its response channel; this feeds scoring and metrics. `kind` distinguishes
-    /// a malformed chunk from a plain failure.
+    /// a malformed chunk, a timeout and a lost connection from a plain failure.
     RetrievalFailed {
         overlay: OverlayAddress,
         address: ChunkAddress,
@@ -287,7 +432,12 @@ enum State {
     /// Waiting for activation command.
     Dormant,
     /// Active and processing protocols.
-    Active { overlay: OverlayAddress },
+    Active {
+        overlay: OverlayAddress,
+        /// Framing for retrieval and pushsync streams, from the capabilities
+        /// both ends advertised.
+        compression: Compression,
+    },
 }
 
 /// A pending inbound pseudosettle response awaiting the application's ack.
@@ -302,8 +452,8 @@ pub struct ClientHandler {
     config: Config,
     state: State,
     /// Client cache: inbound retrievals serve from it, forwarded deliveries cache
-    /// into it.
-    store: Arc<dyn SwarmLocalStore>,
+    /// into it through the config's chunk-set check.
+    store: NetworkStore,
     /// Forwards a retrieval cache miss or a pushsync this node is not responsible
     /// for. Stubbed in the cache-only client.
     forward: Arc<dyn Forwarder>,
@@ -311,6 +461,12 @@ pub struct ClientHandler {
     /// responsible for is stored and acknowledged with a signed custody receipt;
     /// when absent, every delivery takes the verbatim-relay path.
     storer: Option<StorerCapability>,
+    /// Activated peers across all connections, kept by the behaviour; gates
+    /// inbound retrievals against `min_peers_to_serve`.
+    active_peers: Arc<AtomicUsize>,
+    /// Per-protocol latency of completed outbound exchanges, shared with the
+    /// behaviour.
+    latency: LatencyHistograms,
     next_request_id: u64,
     pending_commands: VecDeque<HandlerCommand>,
     pending_events: VecDeque<HandlerEvent>,
@@ -344,13 +500,17 @@ impl ClientHandler {
         store: Arc<dyn SwarmLocalStore>,
         forward: Arc<dyn Forwarder>,
         storer: Option<StorerCapability>,
+        active_peers: Arc<AtomicUsize>,
+        latency: LatencyHistograms,
     ) -> Self {
         Self {
+            store: NetworkStore::new(store, config.ingest),
             config,
             state: State::Dormant,
-            store,
             forward,
             storer,
+            active_peers,
+            latency,
             next_request_id: 0,
             pending_commands: VecDeque::new(),
             pending_events: VecDeque::new(),
@@ -365,6 +525,31 @@ impl ClientHandler {
         }
     }
 
+    /// Take the next command to run: commands that open no substream first
+    /// (activation must precede every outbound request), then by outbound
+    /// priority, oldest first within a priority.
+    fn next_command(&mut self) -> Option<HandlerCommand> {
+        let priorities = &self.config.outbound_priorities;
+        let index = self
+            .pending_commands
+            .iter()
+            .enumerate()
+            .max_by_key(|(index, cmd)| {
+                let priority = cmd.outbound_priority(priorities);
+                (priority.is_none(), priority, Reverse(*index))
+            })
+            .map(|(index, _)| index)?;
+        self.pending_commands.remove(index)
+    }
+
+    fn inbound_deadlines(&self) -> InboundDeadlines {
+        InboundDeadlines {
+            settlement: self.config.timeout,
+            retrieval: self.config.inbound_retrieval_timeout,
+            pushsync: self.config.inbound_pushsync_timeout,
+        }
+    }
+
     fn overlay(&self) -> Option<OverlayAddress> {
         match &self.state {
             State::Active { overlay, .. } => Some(*overlay),
@@ -372,6 +557,18 @@ impl ClientHandler {
         }
     }
 
+    fn compression(&self) -> Compression {
+        match &self.state {
+            State::Active { compression, .. } => *compression,
+            State::Dormant => Compression::None,
+        }
+    }
+
+    /// Whether the Accord retrieval fields are spoken right now.
+    fn accord_active(&self) -> bool {
+        self.config.accord.active_at_timestamp(now_unix_secs())
+    }
+
     fn next_request_id(&mut self) -> u64 {
         let id = self.next_request_id;
         self.next_request_id = self.next_request_id.wrapping_add(1);
@@ -423,11 +620,24 @@ impl ClientHandler {
             .map(|s| s.response)
     }
 
-    fn activate(&mut self, overlay: OverlayAddress, node_type: SwarmNodeType) {
+    fn activate(
+        &mut self,
+        overlay: OverlayAddress,
+        node_type: SwarmNodeType,
+        capabilities: PeerCapabilities,
+    ) {
         match &self.state {
             State::Dormant => {
-                debug!(%overlay, ?node_type, "Handler activated");
-                self.state = State::Active { overlay };
+                // `capabilities` is already what both ends advertised.
+                let compression = Compression::negotiate(
+                    true,
+                    capabilities.contains(PeerCapabilities::COMPRESSION),
+                );
+                debug!(%overlay, ?node_type, ?compression, "Handler activated");
+                self.state = State::Active {
+                    overlay,
+                    compression,
+                };
                 self.pending_events
                     .push_back(HandlerEvent::Activated { overlay });
             }
@@ -472,15 +682,33 @@ impl ClientHandler {
             return;
         };
         let address = request.address;
-        debug!(%overlay, %address, "Received retrieval request");
+        let forward_ready =
+            self.active_peers.load(Ordering::Relaxed) >= self.config.min_peers_to_serve;
+        let (ttl, deadline) = if self.accord_active() {
+            (
+                request.forward_ttl(self.config.retrieval_ttl),
+                request.deadline,
+            )
+        } else {
+            (
+                RetrievalRequest::new(address).forward_ttl(self.config.retrieval_ttl),
+                None,
+            )
+        };
+        debug!(%overlay, %address, ?ttl, ?deadline, forward_ready, "Received retrieval request");
 
         let op = RetrieveServe {
-            store: Arc::clone(&self.store),
+            store: self.store.clone(),
             forward: Arc::clone(&self.forward),
             overlay,
             address,
+            ttl,
+            expires_at: deadline.map(|deadline| Instant::now() + deadline),
+            forward_ready,
         };
-        self.inbound.push(Box::pin(serve::drive(op, responder)));
+        let deadline = self.config.inbound_retrieval_timeout;
+        self.inbound
+            .push(Box::pin(serve::drive(op, responder, deadline)));
     }
 
     /// Handle an inbound pushsync delivery.
@@ -489,6 +717,10 @@ impl ClientHandler {
     /// Otherwise the delivery is forwarded to a closer peer and the storer's
     /// receipt relayed verbatim; this node never signs for a chunk it does not
     /// store. A store or forward failure resets the substream.
+    ///
+    /// An unstamped delivery is refused up front unless this node is a storer
+    /// whose [`StampPolicy`] waives stamps: nothing else can store or relay it,
+    /// so it is invalid data from the pusher.
     fn on_pushsync_delivery(
         &mut self,
         delivery: vertex_swarm_net_pushsync::Delivery,
@@ -501,17 +733,34 @@ impl ClientHandler {
             );
             return;
         };
-        let chunk = *delivery.chunk;
+        let (chunk, stamp) = delivery.into_parts();
         let address = *chunk.address();
-        debug!(%overlay, %address, "Received pushsync delivery");
+        debug!(%overlay, %address, stamped = stamp.is_some(), "Received pushsync delivery");
+
+        let stamp_optional = self
+            .storer
+            .as_ref()
+            .is_some_and(|storer| storer.stamp_policy == StampPolicy::Optional);
+        if stamp.is_none() && !stamp_optional {
+            debug!(%overlay, %address, "Unstamped delivery where a stamp is required; resetting");
+            self.push_event(HandlerEvent::InboundInvalidData {
+                overlay,
+                protocol: PUSHSYNC_PROTOCOL,
+            });
+            return;
+        }
 
         let op = PushServe {
             storer: self.storer.clone(),
+            ingest: self.config.ingest,
             forward: Arc::clone(&self.forward),
             overlay,
             chunk,
+            stamp,
         };
-        self.inbound.push(Box::pin(serve::drive(op, responder)));
+        let deadline = self.config.inbound_pushsync_timeout;
+        self.inbound
+            .push(Box::pin(serve::drive(op, responder, deadline)));
     }
 
     /// Turn a resolved inbound outcome into a scoring/metrics event.
@@ -522,11 +771,17 @@ impl ClientHandler {
             InboundOutcome::Missed { overlay, address } => {
                 HandlerEvent::InboundMissed { overlay, address }
             }
+            InboundOutcome::NotReady { overlay, address } => {
+                HandlerEvent::InboundNotReady { overlay, address }
+            }
             InboundOutcome::Relayed { overlay } => HandlerEvent::InboundRelayed { overlay },
             InboundOutcome::Stored { overlay } => HandlerEvent::InboundStored { overlay },
             InboundOutcome::PushFailed { overlay, address } => {
                 HandlerEvent::InboundPushFailed { overlay, address }
             }
+            InboundOutcome::Stalled { overlay, protocol } => {
+                HandlerEvent::InboundStalled { overlay, protocol }
+            }
         };
         self.push_event(event);
     }
@@ -675,14 +930,20 @@ impl ConnectionHandler for ClientHandler {
         // substreams until we drain.
         let upgrade = match &self.state {
             State::Active { .. } if self.inbound.len() < MAX_INBOUND_SERVING => {
-                let upgrade = ClientInboundUpgrade::active_for(self.config.local_role);
+                let upgrade = ClientInboundUpgrade::active_for(self.config.local_role)
+                    .with_deadlines(self.inbound_deadlines())
+                    .with_prefix(self.config.protocol_prefix)
+                    .with_compression(self.compression());
                 #[cfg(feature = "swap")]
                 let upgrade = upgrade.with_swap_rate(self.config.swap_exchange_rate);
                 upgrade
             }
             State::Active { .. } | State::Dormant => ClientInboundUpgrade::new(),
         };
-        SubstreamProtocol::new(upgrade, ()).with_timeout(self.config.timeout)
+        // The upgrade enforces each protocol's own deadline and reports the
+        // stall; this outer timeout is only the negotiation backstop.
+        let backstop = self.inbound_deadlines().max() + INBOUND_NEGOTIATION_GRACE;
+        SubstreamProtocol::new(upgrade, ()).with_timeout(backstop)
     }
 
     fn poll(
@@ -731,10 +992,14 @@ impl ConnectionHandler for ClientHandler {
             }
         }
 
-        while let Some(cmd) = self.pending_commands.pop_front() {
+        while let Some(cmd) = self.next_command() {
             match cmd {
-                HandlerCommand::Activate { overlay, node_type } => {
-                    self.activate(overlay, node_type);
+                HandlerCommand::Activate {
+                    overlay,
+                    node_type,
+                    capabilities,
+                } => {
+                    self.activate(overlay, node_type, capabilities);
                     if let Some(event) = self.pending_events.pop_front() {
                         return Poll::Ready(ConnectionHandlerEvent::NotifyBehaviour(event));
                     }
@@ -744,7 +1009,8 @@ impl ConnectionHandler for ClientHandler {
                         self.pricing_outbound_pending = true;
                         let announce =
                             vertex_swarm_net_pricing::AnnouncePaymentThreshold::new(threshold);
-                        let upgrade = ClientOutboundUpgrade::pricing(announce);
+                        let upgrade = ClientOutboundUpgrade::pricing(announce)
+                            .with_prefix(self.config.protocol_prefix);
                         return Poll::Ready(ConnectionHandlerEvent::OutboundSubstreamRequest {
                             protocol: SubstreamProtocol::new(upgrade, ClientOutboundInfo::Pricing)
                                 .with_timeout(self.config.timeout),
@@ -755,8 +1021,23 @@ impl ConnectionHandler for ClientHandler {
                     address,
                     response,
                     originated,
+                    ttl,
+                    deadline,
                 } => {
-                    let upgrade = ClientOutboundUpgrade::retrieval(address);
+                    // A relay leg never outlives the budget it was handed, nor
+                    // the local retrieval timeout.
+                    let deadline = deadline.map_or(self.config.retrieval_timeout, |deadline| {
+                        deadline.min(self.config.retrieval_timeout)
+                    });
+                    let mut request = RetrievalRequest::new(address);
+                    if self.accord_active() {
+                        request = request
+                            .with_ttl(ttl.unwrap_or(self.config.retrieval_ttl))
+                            .with_deadline(deadline);
+                    }
+                    let upgrade = ClientOutboundUpgrade::retrieval(request)
+                        .with_prefix(self.config.protocol_prefix)
+                        .with_compression(self.compression());
                     return Poll::Ready(ConnectionHandlerEvent::OutboundSubstreamRequest {
                         protocol: SubstreamProtocol::new(
                             upgrade,
@@ -767,7 +1048,7 @@ impl ConnectionHandler for ClientHandler {
                                 originated,
                             },
                         )
-                        .with_timeout(self.config.retrieval_timeout),
+                        .with_timeout(deadline),
                     });
                 }
                 HandlerCommand::PushChunk {
@@ -777,7 +1058,9 @@ impl ConnectionHandler for ClientHandler {
                 } => {
                     let address = *chunk.address();
                     let delivery = vertex_swarm_net_pushsync::Delivery::new(chunk);
-                    let upgrade = ClientOutboundUpgrade::pushsync(delivery);
+                    let upgrade = ClientOutboundUpgrade::pushsync(delivery)
+                        .with_prefix(self.config.protocol_prefix)
+                        .with_compression(self.compression());
                     return Poll::Ready(ConnectionHandlerEvent::OutboundSubstreamRequest {
                         protocol: SubstreamProtocol::new(
                             upgrade,
@@ -793,11 +1076,15 @@ impl ConnectionHandler for ClientHandler {
                 }
                 HandlerCommand::SendPseudosettle { amount } => {
                     let payment = vertex_swarm_net_pseudosettle::Payment::new(amount);
-                    let upgrade = ClientOutboundUpgrade::pseudosettle(payment);
+                    let upgrade = ClientOutboundUpgrade::pseudosettle(payment)
+                        .with_prefix(self.config.protocol_prefix);
                     return Poll::Ready(ConnectionHandlerEvent::OutboundSubstreamRequest {
                         protocol: SubstreamProtocol::new(
                             upgrade,
-                            ClientOutboundInfo::Pseudosettle { amount },
+                            ClientOutboundInfo::Pseudosettle {
+                                amount,
+                                requested_at: Instant::now(),
+                            },
                         )
                         .with_timeout(self.config.timeout),
                     });
@@ -805,7 +1092,8 @@ impl ConnectionHandler for ClientHandler {
                 #[cfg(feature = "swap")]
                 HandlerCommand::SendCheque { cheque } => {
                     let upgrade =
-                        ClientOutboundUpgrade::swap(cheque, self.config.swap_exchange_rate);
+                        ClientOutboundUpgrade::swap(cheque, self.config.swap_exchange_rate)
+                            .with_prefix(self.config.protocol_prefix);
                     return Poll::Ready(ConnectionHandlerEvent::OutboundSubstreamRequest {
                         protocol: SubstreamProtocol::new(upgrade, ClientOutboundInfo::Swap)
                             .with_timeout(self.config.timeout),
@@ -873,15 +1161,13 @@ impl ConnectionHandler for ClientHandler {
             ConnectionEvent::DialUpgradeError(e) => {
                 // Classify from the typed error while concrete: a malformed chunk
                 // arrives as an `Apply` error we downcast, not a parsed string.
-                let apply_error = match &e.error {
-                    libp2p::swarm::StreamUpgradeError::Apply(err) => Some(err),
-                    _ => None,
-                };
                 // Timeout means the per-protocol deadline fired: the substream
                 // negotiated but the response frame never arrived. The
                 // chunk-transfer arms resolve the caller with the typed
-                // `ChunkTransferError::TimedOut` while still scoring as
-                // `FailureKind::Protocol`.
+                // `ChunkTransferError::TimedOut` and report
+                // `FailureKind::TimedOut`, kept apart from the
+                // `FailureKind::ConnectionLost` of a transport that failed under
+                // the substream.
                 let timed_out = matches!(&e.error, libp2p::swarm::StreamUpgradeError::Timeout);
                 let error = e.error.to_string();
                 match e.info {
@@ -902,8 +1188,8 @@ impl ConnectionHandler for ClientHandler {
                     } => {
                         // A timeout is never a malformed chunk; an `Apply` error
                         // may be a malformed delivery.
-                        let kind = apply_error
-                            .map_or(FailureKind::Protocol, |e| e.retrieval_failure_kind());
+                        let kind =
+                            dial_failure_kind(&e.error, ClientUpgradeError::retrieval_failure_kind);
                         if timed_out {
                             // Sole emission site for the retrieval timeout counter.
                             metrics::counter!("swarm.client.retrieval_timeouts_total").increment(1);
@@ -925,6 +1211,8 @@ impl ConnectionHandler for ClientHandler {
                         }
                         let outcome = if timed_out {
                             ChunkTransferError::TimedOut
+                        } else if kind == FailureKind::InvalidChunk {
+                            ChunkTransferError::InvalidChunk(error)
                         } else {
                             ChunkTransferError::Protocol(error)
                         };
@@ -936,8 +1224,8 @@ impl ConnectionHandler for ClientHandler {
                         requested_at,
                         originated: _,
                     } => {
-                        let kind = apply_error
-                            .map_or(FailureKind::Protocol, |e| e.pushsync_failure_kind());
+                        let kind =
+                            dial_failure_kind(&e.error, ClientUpgradeError::pushsync_failure_kind);
                         if timed_out {
                             // Sole emission site for the pushsync timeout counter.
                             metrics::counter!("swarm.client.pushsync_timeouts_total").increment(1);
@@ -985,6 +1273,15 @@ impl ConnectionHandler for ClientHandler {
             }
 
             ConnectionEvent::ListenUpgradeError(e) => {
+                // The peer negotiated a substream and then stalled the request
+                // read past the protocol's deadline; the substream is dropped.
+                if let ClientUpgradeError::TimedOut { protocol, deadline } = e.error {
+                    debug!(peer_overlay = ?self.overlay(), %protocol, ?deadline, "Inbound substream stalled");
+                    if let Some(overlay) = self.overlay() {
+                        self.push_event(HandlerEvent::InboundStalled { overlay, protocol });
+                    }
+                    return;
+                }
                 // A malformed inbound chunk or retrieval request fails
                 // reconstruction at decode and surfaces here; classify so the
                 // offending peer is scored. The chunk is already rejected.
@@ -1073,6 +1370,7 @@ impl ClientHandler {
                 },
             ) => {
                 let latency = requested_at.elapsed();
+                self.latency.record(LatencyProtocol::Retrieval, latency);
                 self.on_retrieval_response(delivery, address, response, latency, originated);
             }
             (
@@ -1085,13 +1383,19 @@ impl ClientHandler {
                 },
             ) => {
                 let latency = requested_at.elapsed();
+                self.latency.record(LatencyProtocol::Pushsync, latency);
                 debug!(%address, "Received pushsync receipt");
                 self.on_pushsync_receipt(receipt, address, response, latency, originated);
             }
             (
                 ClientOutboundOutput::Pseudosettle(ack),
-                ClientOutboundInfo::Pseudosettle { amount },
+                ClientOutboundInfo::Pseudosettle {
+                    amount,
+                    requested_at,
+                },
             ) => {
+                self.latency
+                    .record(LatencyProtocol::Pseudosettle, requested_at.elapsed());
                 if let Some(overlay) = self.overlay() {
                     if ack.amount != amount {
                         warn!(
@@ -1127,8 +1431,12 @@ impl ClientHandler {
 mod tests {
     use alloy_primitives::{B256, Signature};
     use nectar_postage::Stamp;
-    use nectar_primitives::{AnyChunk, ContentChunk};
-    use vertex_swarm_primitives::{StampedChunk, StampedChunkExt};
+    use nectar_primitives::ContentChunk;
+    use vertex_swarm_api::SwarmResult;
+    use vertex_swarm_primitives::{CachedChunk, StampedChunkExt};
+
+    use super::*;
+    use crate::forward::StubForwarder;
 
     fn stamped(payload: &'static [u8]) -> StampedChunk {
         let sig = Signature::from_raw(&[1u8; 65]).expect("valid signature");
@@ -1139,6 +1447,66 @@ mod tests {
         StampedChunk::new(chunk, stamp)
     }
 
+    struct NoopStore;
+
+    impl SwarmLocalStore for NoopStore {
+        fn put(&self, _chunk: CachedChunk) -> SwarmResult<()> {
+            Ok(())
+        }
+        fn get(&self, _address: &ChunkAddress) -> SwarmResult<Option<CachedChunk>> {
+            Ok(None)
+        }
+        fn contains(&self, _address: &ChunkAddress) -> bool {
+            false
+        }
+        fn remove(&self, _address: &ChunkAddress) -> SwarmResult<()> {
+            Ok(())
+        }
+    }
+
+    /// Poll the handler once and return the info of the substream it opens.
+    fn next_outbound(handler: &mut ClientHandler) -> ClientOutboundInfo {
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+        match handler.poll(&mut cx) {
+            Poll::Ready(ConnectionHandlerEvent::OutboundSubstreamRequest { protocol }) => {
+                protocol.into_upgrade().1
+            }
+            _ => panic!("expected an outbound substream request"),
+        }
+    }
+
+    #[test]
+    fn queued_settlement_opens_before_an_earlier_retrieval() {
+        let mut handler = ClientHandler::new(
+            Config::default(),
+            Arc::new(NoopStore),
+            Arc::new(StubForwarder),
+            None,
+            Arc::new(AtomicUsize::new(0)),
+            LatencyHistograms::default(),
+        );
+        let (response, _rx) = tokio::sync::oneshot::channel();
+        handler.on_behaviour_event(HandlerCommand::RetrieveChunk {
+            address: ChunkAddress::new([0x42; 32]),
+            response,
+            originated: true,
+            ttl: None,
+            deadline: None,
+        });
+        handler.on_behaviour_event(HandlerCommand::SendPseudosettle {
+            amount: U256::from(10),
+        });
+
+        assert!(matches!(
+            next_outbound(&mut handler),
+            ClientOutboundInfo::Pseudosettle { .. }
+        ));
+        assert!(matches!(
+            next_outbound(&mut handler),
+            ClientOutboundInfo::Retrieval { .. }
+        ));
+    }
+
     #[test]
     fn verify_answers_gate_accepts_matching_chunk() {
         let chunk = stamped(b"serve gate payload");
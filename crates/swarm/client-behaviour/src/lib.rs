@@ -6,7 +6,7 @@
 //! composition of sub-behaviours, because the client protocols are intended to
 //! be unified at the wire level into one protocol; keeping them unified here
 //! mirrors that. The handler's substream multiplexing, back-pressure, and the
-//! three per-protocol timeouts are load-bearing.
+//! per-protocol inbound and outbound timeouts are load-bearing.
 //!
 //! The behaviour is accounting-agnostic. It relays a cache miss or a pushsync
 //! through the [`Forwarder`] seam; the concrete network forwarder couples to
@@ -19,13 +19,20 @@ mod behaviour;
 mod events;
 mod forward;
 mod handler;
+pub mod metrics;
 mod serve;
 mod storer;
 pub mod upgrade;
+mod validation;
 
-pub use behaviour::{ClientBehaviour, Config as BehaviourConfig};
+pub use behaviour::{ClientBehaviour, Config as BehaviourConfig, PricingAnnouncement};
 pub use forward::{
     ForwardError, ForwardedChunk, ForwardedReceipt, Forwarder, StubForwarder, closer_candidates,
 };
-pub use handler::{ClientHandler, Config as HandlerConfig, HandlerCommand, HandlerEvent};
-pub use storer::StorerCapability;
+pub use handler::{
+    ClientHandler, Config as HandlerConfig, HandlerCommand, HandlerEvent, OutboundPriorities,
+    OutboundPriority,
+};
+pub use metrics::{LatencyHistogram, LatencyHistograms, LatencySnapshot};
+pub use storer::{OutOfRangePolicy, PushError, StorerCapability};
+pub use validation::{ChunkValidator, ValidationError, ValidationStats};
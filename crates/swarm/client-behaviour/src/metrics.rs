@@ -0,0 +1,289 @@
+//! Per-protocol message latency histograms.
+//!
+//! Counters say how many exchanges ran; operators also want the shape of their
+//! latency. The handler records each completed outbound exchange (retrieval
+//! request to delivery, pushsync push to receipt, pseudosettle payment to ack)
+//! twice: into the `swarm.client.message_latency_seconds` Prometheus histogram
+//! labelled by protocol, bucketed by [`HISTOGRAM_BUCKETS`], and into the
+//! in-process [`LatencyHistograms`]. Their [`LatencySnapshot`] uses the same
+//! bucket bounds and carries p50/p90/p99 estimates, so the node can report
+//! latency without scraping its own exporter.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use metrics::histogram;
+use vertex_metrics::{DURATION_NETWORK, HistogramBucketConfig};
+
+/// Histogram bucket configurations for client protocol metrics.
+pub const HISTOGRAM_BUCKETS: &[HistogramBucketConfig] = &[HistogramBucketConfig {
+    suffix: "message_latency_seconds",
+    buckets: LATENCY_BUCKETS,
+}];
+
+/// Upper bounds, in seconds, shared by the exported and in-process histograms.
+const LATENCY_BUCKETS: &[f64] = DURATION_NETWORK;
+
+/// The exchange a latency sample was taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum LatencyProtocol {
+    Retrieval,
+    Pushsync,
+    Pseudosettle,
+}
+
+/// One protocol's latency distribution, in Prometheus form.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LatencyHistogram {
+    /// `(upper bound in seconds, samples at or below it)`, cumulative like
+    /// Prometheus `le` buckets. Samples above the last bound appear only in
+    /// `count`.
+    pub buckets: Vec<(f64, u64)>,
+    /// Samples recorded.
+    pub count: u64,
+    /// Sum of every recorded sample.
+    pub sum: Duration,
+    /// Median estimate; see [`Self::percentile`].
+    pub p50: Option<Duration>,
+    /// 90th percentile estimate.
+    pub p90: Option<Duration>,
+    /// 99th percentile estimate.
+    pub p99: Option<Duration>,
+}
+
+impl LatencyHistogram {
+    /// Upper bound of the first bucket holding the `q` quantile (`0.0..=1.0`)
+    /// of the samples, so the estimate never undershoots by more than one
+    /// bucket. `None` with no samples or when the quantile lies past the last
+    /// bound.
+    pub fn percentile(&self, q: f64) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+        let rank = (q.clamp(0.0, 1.0) * self.count as f64).ceil().max(1.0) as u64;
+        self.buckets
+            .iter()
+            .find(|(_, at_or_below)| *at_or_below >= rank)
+            .map(|(bound, _)| Duration::from_secs_f64(*bound))
+    }
+}
+
+/// Point-in-time latency distributions per protocol.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LatencySnapshot {
+    /// Retrieval request to delivery.
+    pub retrieval: LatencyHistogram,
+    /// Pushsync push to receipt.
+    pub pushsync: LatencyHistogram,
+    /// Pseudosettle payment to ack: the settlement round trip.
+    pub pseudosettle: LatencyHistogram,
+}
+
+#[derive(Debug)]
+struct Histogram {
+    /// Samples per bucket: slot `i` holds those above bound `i - 1` and at or
+    /// below bound `i`; the extra last slot those above every bound.
+    counts: Box<[AtomicU64]>,
+    sum_micros: AtomicU64,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Self {
+            counts: (0..=LATENCY_BUCKETS.len())
+                .map(|_| AtomicU64::new(0))
+                .collect(),
+            sum_micros: AtomicU64::new(0),
+        }
+    }
+
+    fn record(&self, latency: Duration) {
+        let secs = latency.as_secs_f64();
+        let slot = LATENCY_BUCKETS.partition_point(|bound| *bound < secs);
+        if let Some(count) = self.counts.get(slot) {
+            count.fetch_add(1, Ordering::Relaxed);
+        }
+        let micros = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX);
+        self.sum_micros.fetch_add(micros, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> LatencyHistogram {
+        let mut cumulative = 0;
+        let buckets = LATENCY_BUCKETS
+            .iter()
+            .zip(self.counts.iter())
+            .map(|(bound, count)| {
+                cumulative += count.load(Ordering::Relaxed);
+                (*bound, cumulative)
+            })
+            .collect();
+        let mut histogram = LatencyHistogram {
+            buckets,
+            count: self.counts.iter().map(|c| c.load(Ordering::Relaxed)).sum(),
+            sum: Duration::from_micros(self.sum_micros.load(Ordering::Relaxed)),
+            ..Default::default()
+        };
+        histogram.p50 = histogram.percentile(0.50);
+        histogram.p90 = histogram.percentile(0.90);
+        histogram.p99 = histogram.percentile(0.99);
+        histogram
+    }
+}
+
+#[derive(Debug)]
+struct Histograms {
+    retrieval: Histogram,
+    pushsync: Histogram,
+    pseudosettle: Histogram,
+}
+
+/// Latency histograms shared by the behaviour and every handler; Arc-cheap to
+/// clone.
+#[derive(Debug, Clone)]
+pub struct LatencyHistograms {
+    inner: Arc<Histograms>,
+}
+
+impl Default for LatencyHistograms {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Histograms {
+                retrieval: Histogram::new(),
+                pushsync: Histogram::new(),
+                pseudosettle: Histogram::new(),
+            }),
+        }
+    }
+}
+
+impl LatencyHistograms {
+    fn histogram(&self, protocol: LatencyProtocol) -> &Histogram {
+        match protocol {
+            LatencyProtocol::Retrieval => &self.inner.retrieval,
+            LatencyProtocol::Pushsync => &self.inner.pushsync,
+            LatencyProtocol::Pseudosettle => &self.inner.pseudosettle,
+        }
+    }
+
+    /// Record one completed exchange, exported and in-process.
+    pub(crate) fn record(&self, protocol: LatencyProtocol, latency: Duration) {
+        histogram!(
+            "swarm.client.message_latency_seconds",
+            "protocol" => <&'static str>::from(protocol),
+        )
+        .record(latency.as_secs_f64());
+        self.histogram(protocol).record(latency);
+    }
+
+    /// Current distribution per protocol.
+    pub fn snapshot(&self) -> LatencySnapshot {
+        LatencySnapshot {
+            retrieval: self.inner.retrieval.snapshot(),
+            pushsync: self.inner.pushsync.snapshot(),
+            pseudosettle: self.inner.pseudosettle.snapshot(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use metrics_exporter_prometheus::{Matcher, PrometheusBuilder};
+
+    use super::*;
+
+    /// Cumulative count of the `le` bucket for `protocol` in the rendered
+    /// exposition.
+    fn at_or_below(rendered: &str, protocol: &str, le: &str) -> Option<u64> {
+        let prefix = format!(
+            "swarm_client_message_latency_seconds_bucket{{protocol=\"{protocol}\",le=\"{le}\"}} "
+        );
+        rendered
+            .lines()
+            .find_map(|line| line.strip_prefix(&prefix))
+            .and_then(|count| count.parse().ok())
+    }
+
+    fn bucket(histogram: &LatencyHistogram, bound: f64) -> Option<u64> {
+        histogram
+            .buckets
+            .iter()
+            .find(|(le, _)| *le == bound)
+            .map(|(_, count)| *count)
+    }
+
+    #[test]
+    fn snapshot_reflects_the_distribution() {
+        let latency = LatencyHistograms::default();
+        for millis in [5, 20, 20, 80, 400, 45_000] {
+            latency.record(LatencyProtocol::Retrieval, Duration::from_millis(millis));
+        }
+        latency.record(LatencyProtocol::Pseudosettle, Duration::from_millis(30));
+
+        let snapshot = latency.snapshot();
+        let retrieval = &snapshot.retrieval;
+        assert_eq!(retrieval.count, 6);
+        assert_eq!(retrieval.sum, Duration::from_millis(45_525));
+        assert_eq!(retrieval.buckets.len(), LATENCY_BUCKETS.len());
+        assert_eq!(bucket(retrieval, 0.010), Some(1));
+        assert_eq!(bucket(retrieval, 0.025), Some(3));
+        assert_eq!(bucket(retrieval, 0.100), Some(4));
+        assert_eq!(bucket(retrieval, 0.500), Some(5));
+        assert_eq!(
+            bucket(retrieval, 30.0),
+            Some(5),
+            "a sample past the last bound counts only in the total"
+        );
+
+        assert_eq!(retrieval.p50, Some(Duration::from_millis(25)));
+        assert_eq!(
+            retrieval.p90, None,
+            "the slowest sample is past every bound"
+        );
+        assert_eq!(retrieval.percentile(0.8), Some(Duration::from_millis(500)));
+
+        assert_eq!(snapshot.pseudosettle.count, 1);
+        assert_eq!(bucket(&snapshot.pseudosettle, 0.025), Some(0));
+        assert_eq!(bucket(&snapshot.pseudosettle, 0.050), Some(1));
+        assert_eq!(snapshot.pseudosettle.p99, Some(Duration::from_millis(50)));
+        assert_eq!(snapshot.pushsync.count, 0);
+        assert_eq!(snapshot.pushsync.p50, None);
+    }
+
+    #[test]
+    fn exported_samples_land_in_their_buckets() {
+        let mut builder = PrometheusBuilder::new();
+        for config in HISTOGRAM_BUCKETS {
+            builder = builder
+                .set_buckets_for_metric(Matcher::Suffix(config.suffix.to_string()), config.buckets)
+                .unwrap();
+        }
+        let recorder = builder.build_recorder();
+        let handle = recorder.handle();
+
+        let latency = LatencyHistograms::default();
+        metrics::with_local_recorder(&recorder, || {
+            for millis in [5, 20, 20, 80, 400, 45_000] {
+                latency.record(LatencyProtocol::Retrieval, Duration::from_millis(millis));
+            }
+            latency.record(LatencyProtocol::Pseudosettle, Duration::from_millis(30));
+        });
+
+        let rendered = handle.render();
+        assert_eq!(at_or_below(&rendered, "retrieval", "0.01"), Some(1));
+        assert_eq!(at_or_below(&rendered, "retrieval", "0.025"), Some(3));
+        assert_eq!(at_or_below(&rendered, "retrieval", "0.1"), Some(4));
+        assert_eq!(at_or_below(&rendered, "retrieval", "0.5"), Some(5));
+        assert_eq!(
+            at_or_below(&rendered, "retrieval", "30"),
+            Some(5),
+            "a sample past the last bound counts only in +Inf"
+        );
+        assert_eq!(at_or_below(&rendered, "retrieval", "+Inf"), Some(6));
+
+        assert_eq!(at_or_below(&rendered, "pseudosettle", "0.025"), Some(0));
+        assert_eq!(at_or_below(&rendered, "pseudosettle", "0.05"), Some(1));
+        assert_eq!(at_or_below(&rendered, "pushsync", "+Inf"), None);
+    }
+}
@@ -6,21 +6,38 @@
 //! the peer refuses delivery, while every failure on our side releases without
 //! a trace. [`drive`] owns that shape once; a [`ServeOp`] carries the
 //! operation-specific fulfilment, delegation, payload and outcome vocabulary.
+//!
+//! The wire write is bounded by the protocol's inbound deadline: a peer that
+//! stops reading mid-response is treated like one that refused delivery, and
+//! reported as stalled.
+//!
+//! A retrieval relay is bounded by the requester's own deadline when the
+//! request carries one: the relay is abandoned the moment the requester stops
+//! waiting, and the next hop is handed what is left of the budget less a
+//! slice kept for the delivery's trip back (see [`RELAY_RETURN_SHARE`]).
 
 use std::fmt::Display;
 use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 use nectar_primitives::{AnyChunk, ChunkAddress};
 use tracing::debug;
-use vertex_swarm_api::{CommitOnWrite, SwarmLocalStore};
+use vertex_swarm_api::{CommitOnWrite, NetworkIngest, NetworkStore, StampPolicy};
 use vertex_swarm_net_pushsync::{PushsyncError, PushsyncResponder, Receipt, WireReceipt};
 use vertex_swarm_net_retrieval::{RetrievalError, RetrievalResponder};
-use vertex_swarm_primitives::{CachedChunk, OverlayAddress, Stamp, StampedChunk};
+use vertex_swarm_primitives::{Cacheability, OverlayAddress, Stamp, StampedChunk};
+use vertex_util_runtime::time::Instant;
 
 use super::forward::{ForwardError, Forwarder};
 use super::handler::InboundOutcome;
 use super::storer::StorerCapability;
+use super::upgrade::within;
+
+/// Share of a relayed request's remaining deadline kept back for the return
+/// leg: the next hop is handed `left - left / RELAY_RETURN_SHARE`, so its
+/// delivery can still reach the requester before it stops waiting.
+pub(crate) const RELAY_RETURN_SHARE: u32 = 10;
 
 /// An answer in hand together with its un-applied upstream credit.
 pub(crate) struct Fulfilment<P> {
@@ -48,6 +65,9 @@ pub(crate) trait ServeOp: Send + Sync + Sized + 'static {
     /// The wire write error, logged on a refused delivery.
     type SendError: Display;
 
+    /// The protocol id, attributed when the peer stalls the write.
+    const PROTOCOL: &'static str;
+
     /// Try to fulfil from what this node already holds. A `Fulfilled` answer
     /// carries its billed provide; `Refuse` means the request must reset
     /// without delegating.
@@ -82,15 +102,24 @@ pub(crate) trait ServeOp: Send + Sync + Sized + 'static {
 
     /// Outcome when nothing was delivered; the substream was reset.
     fn failed(&self) -> InboundOutcome;
+
+    /// Outcome when delegation failed with `error`; the substream was reset.
+    fn unforwarded(&self, _error: &ForwardError) -> InboundOutcome {
+        self.failed()
+    }
 }
 
 /// Serve one inbound request: local fulfilment or delegation, then the shared
-/// respond-and-commit tail.
-pub(crate) async fn drive<Op: ServeOp>(op: Op, responder: Op::Responder) -> InboundOutcome {
+/// respond-and-commit tail, whose write must land within `write_deadline`.
+pub(crate) async fn drive<Op: ServeOp>(
+    op: Op,
+    responder: Op::Responder,
+    write_deadline: Duration,
+) -> InboundOutcome {
     match op.local().await {
         Local::Fulfilled(fulfilment) => {
             let success = op.fulfilled();
-            respond_and_commit(&op, responder, fulfilment, success).await
+            respond_and_commit(&op, responder, fulfilment, success, write_deadline).await
         }
         Local::Refuse => {
             Op::refuse(responder);
@@ -99,11 +128,11 @@ pub(crate) async fn drive<Op: ServeOp>(op: Op, responder: Op::Responder) -> Inbo
         Local::Delegate => match op.delegate().await {
             Ok(fulfilment) => {
                 let success = op.delegated();
-                respond_and_commit(&op, responder, fulfilment, success).await
+                respond_and_commit(&op, responder, fulfilment, success, write_deadline).await
             }
-            Err(_) => {
+            Err(e) => {
                 Op::refuse(responder);
-                op.failed()
+                op.unforwarded(&e)
             }
         },
     }
@@ -111,19 +140,34 @@ pub(crate) async fn drive<Op: ServeOp>(op: Op, responder: Op::Responder) -> Inbo
 
 /// Write the answer back and settle its provide: commit on a landed write,
 /// forfeit when the peer refused delivery of an answer in hand (the ghost
-/// trace that starves repeat refusers).
+/// trace that starves repeat refusers). A write that outlives `deadline` is a
+/// refusal too; dropping it drops the responder, which resets the substream.
 async fn respond_and_commit<Op: ServeOp>(
     op: &Op,
     responder: Op::Responder,
     fulfilment: Fulfilment<Op::Payload>,
     success: InboundOutcome,
+    deadline: Duration,
 ) -> InboundOutcome {
-    match Op::respond(responder, fulfilment.payload).await {
-        Ok(()) => {
+    match within(deadline, Op::respond(responder, fulfilment.payload)).await {
+        Some(Ok(())) => {
             fulfilment.provide.apply_boxed();
             success
         }
-        Err(e) => {
+        None => {
+            debug!(
+                peer = %op.peer(),
+                address = %op.address(),
+                ?deadline,
+                "serve write stalled by the peer"
+            );
+            fulfilment.provide.forfeit_boxed();
+            InboundOutcome::Stalled {
+                overlay: op.peer(),
+                protocol: Op::PROTOCOL,
+            }
+        }
+        Some(Err(e)) => {
             debug!(
                 peer = %op.peer(),
                 address = %op.address(),
@@ -139,10 +183,17 @@ async fn respond_and_commit<Op: ServeOp>(
 /// Inbound retrieval: cache hit (content indefinitely, single-owner while
 /// fresh), else forward to a closer peer.
 pub(crate) struct RetrieveServe {
-    pub store: Arc<dyn SwarmLocalStore>,
+    pub store: NetworkStore,
     pub forward: Arc<dyn Forwarder>,
     pub overlay: OverlayAddress,
     pub address: ChunkAddress,
+    /// Hops left for the relayed request, or why it may not be relayed.
+    pub ttl: Result<u8, RetrievalError>,
+    /// When the requester stops waiting; `None` when it set no deadline.
+    pub expires_at: Option<Instant>,
+    /// Whether enough peers are activated to forward a miss. When not, a
+    /// local hit is still served but a miss is refused as not ready.
+    pub forward_ready: bool,
 }
 
 impl ServeOp for RetrieveServe {
@@ -150,6 +201,8 @@ impl ServeOp for RetrieveServe {
     type Responder = RetrievalResponder;
     type SendError = RetrievalError;
 
+    const PROTOCOL: &'static str = vertex_swarm_net_retrieval::PROTOCOL_NAME;
+
     async fn local(&self) -> Local<Self::Payload> {
         // Cache hit: the store applies the single-owner TTL on `get`. Serve
         // whichever stamp the cache held. A terminal serve is billed like a
@@ -175,21 +228,46 @@ impl ServeOp for RetrieveServe {
     }
 
     async fn delegate(&self) -> Result<Fulfilment<Self::Payload>, ForwardError> {
-        let forwarded = self.forward.retrieve(self.address, self.overlay).await?;
+        if !self.forward_ready {
+            return Err(ForwardError::NotReady);
+        }
+        let Ok(ttl) = self.ttl else {
+            return Err(ForwardError::TtlExceeded);
+        };
+        let forwarded = match self.expires_at {
+            Some(at) => {
+                let left = at.saturating_duration_since(Instant::now());
+                let downstream = left.saturating_sub(left / RELAY_RETURN_SHARE);
+                if downstream.is_zero() {
+                    return Err(ForwardError::DeadlineExceeded);
+                }
+                // Dropping the relay on expiry cancels its in-flight legs.
+                let relay =
+                    self.forward
+                        .retrieve(self.address, self.overlay, ttl, Some(downstream));
+                within(left, relay)
+                    .await
+                    .ok_or(ForwardError::DeadlineExceeded)??
+            }
+            None => {
+                self.forward
+                    .retrieve(self.address, self.overlay, ttl, None)
+                    .await?
+            }
+        };
         if *forwarded.chunk.address() != self.address {
             // Wrong address means a relay bug, not the requester's fault;
             // release the credit without a trace and reset.
             drop(forwarded.provide);
             return Err(ForwardError::UnverifiedRelay);
         }
-        // Only content chunks are cached (immutable, address-keyed); a
+        // Only immutable chunks are cached (address-keyed, no expiry); a
         // retrieved SOC has no version signal so it is relayed but never
         // stored.
-        if forwarded.chunk.is_content() {
-            let _ = self.store.put(CachedChunk::new(
-                forwarded.chunk.clone(),
-                forwarded.stamp.clone(),
-            ));
+        if Cacheability::of(&forwarded.chunk).is_immutable() {
+            let _ = self
+                .store
+                .put(forwarded.chunk.clone(), forwarded.stamp.clone());
         }
         Ok(Fulfilment {
             payload: (forwarded.chunk, forwarded.stamp),
@@ -234,15 +312,37 @@ impl ServeOp for RetrieveServe {
             address: self.address,
         }
     }
+
+    fn unforwarded(&self, error: &ForwardError) -> InboundOutcome {
+        match error {
+            ForwardError::NotReady => InboundOutcome::NotReady {
+                overlay: self.overlay,
+                address: self.address,
+            },
+            _ => self.failed(),
+        }
+    }
 }
 
 /// Inbound pushsync: take custody when responsible (store, sign, acknowledge),
 /// else forward to a closer peer and relay the storer's receipt verbatim.
 pub(crate) struct PushServe {
     pub storer: Option<StorerCapability>,
+    /// Validates the chunk against the network's chunk set before custody.
+    pub ingest: NetworkIngest,
     pub forward: Arc<dyn Forwarder>,
     pub overlay: OverlayAddress,
-    pub chunk: StampedChunk,
+    pub chunk: AnyChunk,
+    /// `None` for an unstamped delivery; see [`StampPolicy`].
+    pub stamp: Option<Stamp>,
+}
+
+impl PushServe {
+    fn stamped(&self) -> Option<StampedChunk> {
+        self.stamp
+            .clone()
+            .map(|stamp| StampedChunk::new(self.chunk.clone(), stamp))
+    }
 }
 
 impl ServeOp for PushServe {
@@ -250,17 +350,41 @@ impl ServeOp for PushServe {
     type Responder = PushsyncResponder;
     type SendError = PushsyncError;
 
+    const PROTOCOL: &'static str = vertex_swarm_net_pushsync::PROTOCOL_NAME;
+
     async fn local(&self) -> Local<WireReceipt> {
         let address = *self.chunk.address();
         // Storer ingest: only when responsible for the chunk. Absent on a
-        // client.
-        let Some(storer) = self
-            .storer
-            .as_ref()
-            .filter(|storer| storer.reserve.is_responsible_for(&address))
-        else {
+        // client, which always relays.
+        let Some(storer) = self.storer.as_ref() else {
             return Local::Delegate;
         };
+        match storer.custody(&address) {
+            Ok(true) => {}
+            Ok(false) => return Local::Delegate,
+            Err(e) => {
+                debug!(peer = %self.overlay, error = %e, "Refusing push");
+                let reason: &'static str = (&e).into();
+                metrics::counter!("swarm.client.pushes_refused", "reason" => reason).increment(1);
+                return Local::Refuse;
+            }
+        }
+
+        // Check the stamp off the event loop, bounded by the storer's
+        // validation pool; a forged or rejected stamp is refused before any
+        // credit is reserved. Where storage is free no stamp is checked and an
+        // unstamped delivery is stored as is.
+        if storer.stamp_policy == StampPolicy::Required {
+            let Some(stamped) = self.stamped() else {
+                debug!(peer = %self.overlay, %address, "Unstamped delivery; refusing custody");
+                return Local::Refuse;
+            };
+            let verifier = storer.verifier.clone();
+            if let Err(e) = storer.validator.validate(stamped, verifier).await {
+                debug!(peer = %self.overlay, %address, error = %e, "Chunk validation failed; refusing custody");
+                return Local::Refuse;
+            }
+        }
 
         // Custody is billed like any serve: reserve the upstream credit
         // before the storage work; a gate refusal refuses custody.
@@ -273,7 +397,10 @@ impl ServeOp for PushServe {
         // a chunk that is not durably in the reserve. Both failure arms below
         // drop `provide`, releasing without a trace: they are our failures,
         // not the pusher's.
-        if let Err(e) = storer.reserve.put(CachedChunk::from(self.chunk.clone())) {
+        if let Err(e) = self
+            .ingest
+            .put(&*storer.reserve, self.chunk.clone(), self.stamp.clone())
+        {
             debug!(peer = %self.overlay, %address, error = %e, "Reserve put failed; not acknowledging");
             return Local::Refuse;
         }
@@ -298,7 +425,8 @@ impl ServeOp for PushServe {
     }
 
     async fn delegate(&self) -> Result<Fulfilment<WireReceipt>, ForwardError> {
-        let forwarded = self.forward.push(self.chunk.clone(), self.overlay).await?;
+        let chunk = self.stamped().ok_or(ForwardError::Unstamped)?;
+        let forwarded = self.forward.push(chunk, self.overlay).await?;
         // Relay the storer's receipt verbatim: we never sign. The signer was
         // verified at decode, so the wire bytes reproduce the storer's
         // signature, nonce, and radius unchanged.
@@ -352,7 +480,14 @@ mod tests {
     use std::sync::Mutex;
     use std::sync::atomic::{AtomicBool, Ordering};
 
+    use vertex_swarm_api::SwarmLocalStore;
+    use vertex_swarm_primitives::CachedChunk;
+
     use super::*;
+    use crate::forward::{ForwardedChunk, ForwardedReceipt};
+    use crate::storer::{OutOfRangePolicy, PushError};
+
+    const WRITE_DEADLINE: Duration = Duration::from_secs(5);
 
     /// Records whether the provide was committed or forfeited.
     #[derive(Default)]
@@ -377,6 +512,8 @@ mod tests {
     struct TestResponder {
         refused: Arc<AtomicBool>,
         send_ok: bool,
+        /// Never complete the write, as a peer that stopped reading.
+        stall: bool,
         sent: Arc<Mutex<Vec<&'static str>>>,
     }
 
@@ -412,6 +549,8 @@ mod tests {
         type Responder = TestResponder;
         type SendError = &'static str;
 
+        const PROTOCOL: &'static str = "test";
+
         async fn local(&self) -> Local<&'static str> {
             self.local
                 .lock()
@@ -432,6 +571,9 @@ mod tests {
             responder: TestResponder,
             payload: &'static str,
         ) -> Result<(), &'static str> {
+            if responder.stall {
+                futures::future::pending::<()>().await;
+            }
             if responder.send_ok {
                 responder.sent.lock().unwrap().push(payload);
                 Ok(())
@@ -498,7 +640,7 @@ mod tests {
         let responder = TestResponder::sending(true);
         let sent = Arc::clone(&responder.sent);
 
-        let outcome = drive(op, responder).await;
+        let outcome = drive(op, responder, WRITE_DEADLINE).await;
 
         assert!(matches!(outcome, InboundOutcome::Served { .. }));
         assert_eq!(*sent.lock().unwrap(), vec!["cached"]);
@@ -514,7 +656,7 @@ mod tests {
             Err(ForwardError::NoCloserPeer),
         );
 
-        let outcome = drive(op, TestResponder::sending(false)).await;
+        let outcome = drive(op, TestResponder::sending(false), WRITE_DEADLINE).await;
 
         assert!(matches!(outcome, InboundOutcome::Missed { .. }));
         assert!(!applied.load(Ordering::SeqCst));
@@ -527,7 +669,7 @@ mod tests {
         let responder = TestResponder::sending(true);
         let refused = Arc::clone(&responder.refused);
 
-        let outcome = drive(op, responder).await;
+        let outcome = drive(op, responder, WRITE_DEADLINE).await;
 
         assert!(matches!(outcome, InboundOutcome::Missed { .. }));
         assert!(refused.load(Ordering::SeqCst));
@@ -540,7 +682,7 @@ mod tests {
         let responder = TestResponder::sending(true);
         let sent = Arc::clone(&responder.sent);
 
-        let outcome = drive(op, responder).await;
+        let outcome = drive(op, responder, WRITE_DEADLINE).await;
 
         assert!(matches!(outcome, InboundOutcome::Forwarded { .. }));
         assert_eq!(*sent.lock().unwrap(), vec!["relayed"]);
@@ -553,9 +695,519 @@ mod tests {
         let responder = TestResponder::sending(true);
         let refused = Arc::clone(&responder.refused);
 
-        let outcome = drive(op, responder).await;
+        let outcome = drive(op, responder, WRITE_DEADLINE).await;
 
         assert!(matches!(outcome, InboundOutcome::Missed { .. }));
         assert!(refused.load(Ordering::SeqCst));
     }
+
+    #[tokio::test]
+    async fn stalled_write_forfeits_and_reports_the_stall() {
+        let (fulfilment, applied, forfeited) = fulfilment("cached");
+        let op = TestOp::new(
+            Local::Fulfilled(fulfilment),
+            Err(ForwardError::NoCloserPeer),
+        );
+        let responder = TestResponder {
+            stall: true,
+            ..TestResponder::sending(true)
+        };
+
+        let outcome = drive(op, responder, Duration::from_millis(20)).await;
+
+        assert!(matches!(
+            outcome,
+            InboundOutcome::Stalled {
+                protocol: "test",
+                ..
+            }
+        ));
+        assert!(!applied.load(Ordering::SeqCst));
+        assert!(forfeited.load(Ordering::SeqCst));
+    }
+
+    struct EmptyStore;
+
+    impl SwarmLocalStore for EmptyStore {
+        fn put(&self, _chunk: CachedChunk) -> vertex_swarm_api::SwarmResult<()> {
+            Ok(())
+        }
+        fn get(
+            &self,
+            _address: &ChunkAddress,
+        ) -> vertex_swarm_api::SwarmResult<Option<CachedChunk>> {
+            Ok(None)
+        }
+        fn contains(&self, _address: &ChunkAddress) -> bool {
+            false
+        }
+        fn remove(&self, _address: &ChunkAddress) -> vertex_swarm_api::SwarmResult<()> {
+            Ok(())
+        }
+    }
+
+    /// Records the TTL and time budget of every relay and answers none of
+    /// them, after `relay_delay` when set (a slow next hop).
+    #[derive(Default)]
+    struct TtlForwarder {
+        relayed: Mutex<Vec<u8>>,
+        budgets: Mutex<Vec<Option<Duration>>>,
+        relay_delay: Option<Duration>,
+        /// Set when a relay future is dropped before it finished.
+        abandoned: Arc<AtomicBool>,
+    }
+
+    /// Flags the relay abandoned unless disarmed by finishing.
+    struct AbandonGuard(Option<Arc<AtomicBool>>);
+
+    impl Drop for AbandonGuard {
+        fn drop(&mut self) {
+            if let Some(flag) = self.0.take() {
+                flag.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+
+    impl Forwarder for TtlForwarder {
+        fn retrieve(
+            &self,
+            _address: ChunkAddress,
+            _exclude: OverlayAddress,
+            ttl: u8,
+            deadline: Option<Duration>,
+        ) -> futures::future::BoxFuture<'static, Result<ForwardedChunk, ForwardError>> {
+            self.relayed.lock().unwrap().push(ttl);
+            self.budgets.lock().unwrap().push(deadline);
+            let delay = self.relay_delay;
+            let mut guard = AbandonGuard(Some(Arc::clone(&self.abandoned)));
+            Box::pin(async move {
+                if let Some(delay) = delay {
+                    tokio::time::sleep(delay).await;
+                }
+                guard.0 = None;
+                Err(ForwardError::NoCloserPeer)
+            })
+        }
+
+        fn push(
+            &self,
+            _chunk: StampedChunk,
+            _exclude: OverlayAddress,
+        ) -> futures::future::BoxFuture<'static, Result<ForwardedReceipt, ForwardError>> {
+            Box::pin(async { Err(ForwardError::NoCloserPeer) })
+        }
+
+        fn prepare_serve(
+            &self,
+            _peer: OverlayAddress,
+            _address: &ChunkAddress,
+        ) -> Result<Box<dyn CommitOnWrite>, ForwardError> {
+            Err(ForwardError::AccountingRefused)
+        }
+    }
+
+    fn retrieve_serve(
+        forward: &Arc<TtlForwarder>,
+        ttl: Result<u8, RetrievalError>,
+    ) -> RetrieveServe {
+        RetrieveServe {
+            store: NetworkStore::new(Arc::new(EmptyStore), NetworkIngest::default()),
+            forward: Arc::clone(forward) as Arc<dyn Forwarder>,
+            overlay: OverlayAddress::from([0xaa; 32]),
+            address: ChunkAddress::from([0xbb; 32]),
+            ttl,
+            expires_at: None,
+            forward_ready: true,
+        }
+    }
+
+    /// A request sent with TTL 1 is relayed by the first hop with TTL 0; the
+    /// second hop, with nothing left, refuses to relay it.
+    #[tokio::test]
+    async fn retrieval_ttl_stops_relaying_at_zero() {
+        let forward = Arc::new(TtlForwarder::default());
+        let address = ChunkAddress::from([0xbb; 32]);
+
+        let first = vertex_swarm_net_retrieval::Request::new(address).with_ttl(1);
+        let next = first.forward_ttl(16);
+        let err = retrieve_serve(&forward, next).delegate().await.err();
+        assert!(matches!(err, Some(ForwardError::NoCloserPeer)));
+        assert_eq!(*forward.relayed.lock().unwrap(), vec![0]);
+
+        let second = vertex_swarm_net_retrieval::Request::new(address).with_ttl(0);
+        let next = second.forward_ttl(16);
+        assert!(matches!(next, Err(RetrievalError::TtlExceeded)));
+        let err = retrieve_serve(&forward, next).delegate().await.err();
+        assert!(matches!(err, Some(ForwardError::TtlExceeded)));
+        assert_eq!(
+            *forward.relayed.lock().unwrap(),
+            vec![0],
+            "second hop relayed"
+        );
+    }
+
+    /// A requester with a short deadline in front of a slow next hop: the relay
+    /// is handed the requester's budget less the return share and abandoned
+    /// when it runs out, rather than running on for a requester that stopped
+    /// waiting.
+    #[tokio::test]
+    async fn retrieval_relay_is_abandoned_when_the_deadline_passes() {
+        let forward = Arc::new(TtlForwarder {
+            relay_delay: Some(Duration::from_secs(30)),
+            ..TtlForwarder::default()
+        });
+        let budget = Duration::from_millis(50);
+        let op = RetrieveServe {
+            expires_at: Some(Instant::now() + budget),
+            ..retrieve_serve(&forward, Ok(3))
+        };
+
+        let started = Instant::now();
+        let err = op.delegate().await.err();
+        assert!(matches!(err, Some(ForwardError::DeadlineExceeded)));
+        assert!(
+            started.elapsed() < Duration::from_secs(5),
+            "the relay stopped at the deadline, not when the slow hop answered"
+        );
+        assert!(forward.abandoned.load(Ordering::SeqCst), "relay dropped");
+        let budgets = forward.budgets.lock().unwrap().clone();
+        assert!(
+            matches!(
+                budgets.as_slice(),
+                [Some(left)] if *left <= budget - budget / RELAY_RETURN_SHARE
+            ),
+            "the next hop's budget leaves room for the return leg: {budgets:?}"
+        );
+
+        // Already expired on arrival: nothing is relayed at all.
+        let op = RetrieveServe {
+            expires_at: Some(Instant::now()),
+            ..retrieve_serve(&forward, Ok(3))
+        };
+        assert!(matches!(
+            op.delegate().await.err(),
+            Some(ForwardError::DeadlineExceeded)
+        ));
+        assert_eq!(forward.relayed.lock().unwrap().len(), 1);
+    }
+
+    /// Under `min_peers_to_serve` a miss is refused as not ready without
+    /// being relayed.
+    #[tokio::test]
+    async fn retrieval_miss_is_not_forwarded_when_not_ready() {
+        let forward = Arc::new(TtlForwarder::default());
+        let op = RetrieveServe {
+            forward_ready: false,
+            ..retrieve_serve(&forward, Ok(3))
+        };
+
+        let err = op.delegate().await.err();
+        assert!(matches!(err, Some(ForwardError::NotReady)));
+        assert!(forward.relayed.lock().unwrap().is_empty(), "miss relayed");
+        assert!(matches!(
+            op.unforwarded(&ForwardError::NotReady),
+            InboundOutcome::NotReady { .. }
+        ));
+        assert!(matches!(
+            op.unforwarded(&ForwardError::NoCloserPeer),
+            InboundOutcome::Missed { .. }
+        ));
+    }
+
+    /// Reserve responsible for every chunk unless `out_of_range` is set,
+    /// recording what it stores.
+    #[derive(Default)]
+    struct TestReserve {
+        chunks: Mutex<Vec<CachedChunk>>,
+        out_of_range: bool,
+    }
+
+    impl SwarmLocalStore for TestReserve {
+        fn put(&self, chunk: CachedChunk) -> vertex_swarm_api::SwarmResult<()> {
+            self.chunks.lock().unwrap().push(chunk);
+            Ok(())
+        }
+        fn get(
+            &self,
+            address: &ChunkAddress,
+        ) -> vertex_swarm_api::SwarmResult<Option<CachedChunk>> {
+            Ok(self
+                .chunks
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|chunk| chunk.address() == address)
+                .cloned())
+        }
+        fn contains(&self, address: &ChunkAddress) -> bool {
+            self.chunks
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|chunk| chunk.address() == address)
+        }
+        fn remove(&self, _address: &ChunkAddress) -> vertex_swarm_api::SwarmResult<()> {
+            Ok(())
+        }
+    }
+
+    impl vertex_swarm_api::ReserveStore for TestReserve {
+        fn storage_radius(&self) -> vertex_swarm_api::StorageRadius {
+            vertex_swarm_api::StorageRadius::ZERO
+        }
+        fn is_responsible_for(&self, _address: &ChunkAddress) -> bool {
+            !self.out_of_range
+        }
+        fn count(&self) -> vertex_swarm_api::SwarmResult<u64> {
+            Ok(self.chunks.lock().unwrap().len() as u64)
+        }
+        fn capacity(&self) -> u64 {
+            u64::MAX
+        }
+        fn count_in(
+            &self,
+            _po: nectar_primitives::ProximityOrder,
+        ) -> vertex_swarm_api::SwarmResult<u64> {
+            Ok(0)
+        }
+        fn evict_furthest(&self) -> vertex_swarm_api::SwarmResult<Option<ChunkAddress>> {
+            Ok(None)
+        }
+        fn evict_from_bin(
+            &self,
+            _bin: nectar_primitives::Bin,
+            _max: u64,
+        ) -> vertex_swarm_api::SwarmResult<u64> {
+            Ok(0)
+        }
+        fn evict_batch(
+            &self,
+            _batch: vertex_swarm_primitives::BatchId,
+            _up_to_bin: Option<nectar_primitives::Bin>,
+            _max: u64,
+        ) -> vertex_swarm_api::SwarmResult<u64> {
+            Ok(0)
+        }
+    }
+
+    /// Rejects every stamp, standing in for an unknown or unfunded batch.
+    struct RejectAll;
+
+    impl vertex_swarm_api::StampVerifier for RejectAll {
+        fn verify(
+            &self,
+            _chunk: &AnyChunk,
+            _stamp: &Stamp,
+        ) -> Result<(), vertex_swarm_api::VerifyError> {
+            Err(vertex_swarm_api::VerifyError::UnknownBatch)
+        }
+    }
+
+    /// A content chunk under a genuinely signed stamp.
+    fn signed_chunk() -> StampedChunk {
+        use alloy_signer::SignerSync;
+        use nectar_postage::{StampDigest, StampIndex};
+
+        let chunk: AnyChunk = nectar_primitives::ContentChunk::new(&b"pushed for custody"[..])
+            .expect("valid content chunk")
+            .into();
+        let batch = alloy_primitives::B256::repeat_byte(0xaa);
+        let index = StampIndex::new(3, 7);
+        let prehash = StampDigest::new(*chunk.address(), batch, index, 42).to_prehash();
+        let sig = vertex_swarm_test_utils::test_identity()
+            .sign_message_sync(prehash.as_slice())
+            .expect("stamp signature");
+        StampedChunk::new(chunk, Stamp::with_index(batch, index, 42, sig))
+    }
+
+    fn push_serve(
+        reserve: &Arc<TestReserve>,
+        configure: impl FnOnce(StorerCapability) -> StorerCapability,
+        chunk: AnyChunk,
+        stamp: Option<Stamp>,
+    ) -> PushServe {
+        let storer = StorerCapability::new(
+            Arc::clone(reserve) as Arc<dyn vertex_swarm_api::ReserveStore>,
+            Arc::new(vertex_swarm_test_utils::test_identity()),
+        );
+        PushServe {
+            storer: Some(configure(storer)),
+            ingest: NetworkIngest::default(),
+            forward: Arc::new(crate::forward::StubForwarder),
+            overlay: OverlayAddress::from([0xaa; 32]),
+            chunk,
+            stamp,
+        }
+    }
+
+    #[tokio::test]
+    async fn validly_stamped_push_is_stored() {
+        let reserve = Arc::new(TestReserve::default());
+        let (chunk, stamp) = signed_chunk().into_parts();
+        let address = *chunk.address();
+
+        let local = push_serve(&reserve, |storer| storer, chunk, Some(stamp))
+            .local()
+            .await;
+
+        assert!(matches!(local, Local::Fulfilled(_)));
+        assert!(reserve.contains(&address));
+    }
+
+    /// Custody validates the chunk against the network's chunk set: a
+    /// single-owner chunk pushed to a content-only network is refused and
+    /// never reaches the reserve.
+    #[tokio::test]
+    async fn push_outside_the_chunk_set_is_refused() {
+        let soc: AnyChunk = nectar_primitives::SingleOwnerChunk::new(
+            alloy_primitives::B256::repeat_byte(0x22),
+            &b"not on this network"[..],
+            &vertex_swarm_test_utils::test_identity(),
+        )
+        .expect("valid soc")
+        .into();
+        let address = *soc.address();
+        let optional = |storer: StorerCapability| storer.with_stamp_policy(StampPolicy::Optional);
+
+        let reserve = Arc::new(TestReserve::default());
+        let mut op = push_serve(&reserve, optional, soc.clone(), None);
+        op.ingest = NetworkIngest::for_chunk_set::<nectar_primitives::ContentOnlyChunkSet>();
+        assert!(matches!(op.local().await, Local::Refuse));
+        assert!(!reserve.contains(&address));
+
+        let local = push_serve(&reserve, optional, soc, None).local().await;
+        assert!(matches!(local, Local::Fulfilled(_)));
+        assert!(reserve.contains(&address));
+    }
+
+    #[tokio::test]
+    async fn rejected_stamp_is_refused() {
+        let reserve = Arc::new(TestReserve::default());
+        let (chunk, stamp) = signed_chunk().into_parts();
+        let address = *chunk.address();
+
+        let local = push_serve(
+            &reserve,
+            |storer| storer.with_stamp_verifier(Arc::new(RejectAll)),
+            chunk,
+            Some(stamp),
+        )
+        .local()
+        .await;
+
+        assert!(matches!(local, Local::Refuse));
+        assert!(!reserve.contains(&address));
+    }
+
+    #[tokio::test]
+    async fn unstamped_push_is_stored_only_when_optional() {
+        let reserve = Arc::new(TestReserve::default());
+        let (chunk, _) = signed_chunk().into_parts();
+        let address = *chunk.address();
+
+        let required = push_serve(&reserve, |storer| storer, chunk.clone(), None)
+            .local()
+            .await;
+        assert!(matches!(required, Local::Refuse));
+        assert!(!reserve.contains(&address));
+
+        let optional = push_serve(
+            &reserve,
+            |storer| storer.with_stamp_policy(StampPolicy::Optional),
+            chunk,
+            None,
+        )
+        .local()
+        .await;
+        assert!(matches!(optional, Local::Fulfilled(_)));
+        assert!(reserve.contains(&address));
+    }
+
+    #[tokio::test]
+    async fn free_storage_spec_accepts_unstamped_pushes_and_mainnet_refuses_them() {
+        let free = vertex_swarm_spec::SpecBuilder::testnet()
+            .storage_payment(false)
+            .build();
+        let mainnet = vertex_swarm_spec::init_mainnet();
+        let (chunk, stamp) = signed_chunk().into_parts();
+        let address = *chunk.address();
+
+        let paid = Arc::new(TestReserve::default());
+        let refused = push_serve(
+            &paid,
+            |storer| storer.with_stamp_policy(StampPolicy::for_spec(&*mainnet)),
+            chunk.clone(),
+            None,
+        )
+        .local()
+        .await;
+        assert!(matches!(refused, Local::Refuse));
+        assert!(!paid.contains(&address));
+
+        let unpaid = Arc::new(TestReserve::default());
+        let stored = push_serve(
+            &unpaid,
+            |storer| storer.with_stamp_policy(StampPolicy::for_spec(&free)),
+            chunk.clone(),
+            None,
+        )
+        .local()
+        .await;
+        assert!(matches!(stored, Local::Fulfilled(_)));
+        assert!(unpaid.contains(&address));
+
+        // Free storage verifies no stamp, even one the verifier would reject.
+        let unchecked = Arc::new(TestReserve::default());
+        let stored = push_serve(
+            &unchecked,
+            |storer| {
+                storer
+                    .with_stamp_policy(StampPolicy::for_spec(&free))
+                    .with_stamp_verifier(Arc::new(RejectAll))
+            },
+            chunk,
+            Some(stamp),
+        )
+        .local()
+        .await;
+        assert!(matches!(stored, Local::Fulfilled(_)));
+        assert!(unchecked.contains(&address));
+    }
+
+    #[tokio::test]
+    async fn out_of_range_push_is_refused_only_under_the_refuse_policy() {
+        let refuse =
+            |storer: StorerCapability| storer.with_out_of_range_policy(OutOfRangePolicy::Refuse);
+
+        let far = Arc::new(TestReserve {
+            out_of_range: true,
+            ..TestReserve::default()
+        });
+        let (chunk, stamp) = signed_chunk().into_parts();
+        let address = *chunk.address();
+        let refusing = push_serve(&far, refuse, chunk.clone(), Some(stamp.clone()));
+        let storer = refusing.storer.as_ref().expect("storer capability");
+        assert_eq!(
+            storer.custody(&address),
+            Err(PushError::OutOfRange {
+                address,
+                radius: vertex_swarm_api::StorageRadius::ZERO,
+            })
+        );
+        assert!(matches!(refusing.local().await, Local::Refuse));
+        assert!(!far.contains(&address));
+
+        let relayed = push_serve(&far, |storer| storer, chunk.clone(), Some(stamp.clone()))
+            .local()
+            .await;
+        assert!(
+            matches!(relayed, Local::Delegate),
+            "the default policy relays"
+        );
+
+        let near = Arc::new(TestReserve::default());
+        let stored = push_serve(&near, refuse, chunk, Some(stamp)).local().await;
+        assert!(matches!(stored, Local::Fulfilled(_)));
+        assert!(near.contains(&address));
+    }
 }
@@ -8,9 +8,47 @@
 
 use std::sync::Arc;
 
-use vertex_swarm_api::ReserveStore;
+use nectar_primitives::ChunkAddress;
+use strum::IntoStaticStr;
+use vertex_swarm_api::{
+    DEFAULT_VALIDATION_CONCURRENCY, ReserveStore, StampPolicy, StampVerifier, StorageRadius,
+};
 use vertex_swarm_primitives::OverlaySigner;
 
+use crate::validation::{ChunkValidator, ValidationStats};
+
+/// What a storer does with a push for a chunk outside its storage radius.
+///
+/// Pushsync has no failure frame (see `PushsyncResponder::send_error`), so a
+/// refusal resets the stream; the pusher reads that as a failed push and moves
+/// on to its next, closer candidate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutOfRangePolicy {
+    /// Relay it towards the chunk's neighbourhood like any forwarding hop.
+    #[default]
+    Forward,
+    /// Refuse it rather than spend bandwidth relaying it.
+    Refuse,
+}
+
+/// Why a storer refused custody of a pushed chunk.
+///
+/// The wire carries no reason (the stream is reset); the error names the
+/// refusal in the serve log and its metric.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum PushError {
+    /// The chunk lies outside the storage radius, so a closer peer is
+    /// responsible for it.
+    #[error("chunk {address} is outside storage radius {radius}")]
+    OutOfRange {
+        /// The refused chunk.
+        address: ChunkAddress,
+        /// The storage radius the chunk fell outside.
+        radius: StorageRadius,
+    },
+}
+
 /// Reserve plus the node's overlay-signing identity, shared into each handler.
 ///
 /// The identity is erased to `Arc<dyn OverlaySigner>` so the non-generic client
@@ -24,6 +62,14 @@ use vertex_swarm_primitives::OverlaySigner;
 pub struct StorerCapability {
     pub(crate) reserve: Arc<dyn ReserveStore>,
     pub(crate) signer: Arc<dyn OverlaySigner + Send + Sync>,
+    /// Bounded pool checking each delivery's stamp before custody.
+    pub(crate) validator: ChunkValidator,
+    /// Extra stamp check run in the pool after signature recovery.
+    pub(crate) verifier: Option<Arc<dyn StampVerifier>>,
+    /// Whether stamps are required and verified, or storage is free.
+    pub(crate) stamp_policy: StampPolicy,
+    /// Whether a delivery outside the storage radius is relayed or refused.
+    pub(crate) out_of_range: OutOfRangePolicy,
 }
 
 impl StorerCapability {
@@ -31,7 +77,65 @@ impl StorerCapability {
         reserve: Arc<dyn ReserveStore>,
         signer: Arc<dyn OverlaySigner + Send + Sync>,
     ) -> Self {
-        Self { reserve, signer }
+        Self {
+            reserve,
+            signer,
+            validator: ChunkValidator::new(DEFAULT_VALIDATION_CONCURRENCY),
+            verifier: None,
+            stamp_policy: StampPolicy::Required,
+            out_of_range: OutOfRangePolicy::Forward,
+        }
+    }
+
+    /// Run at most `limit` ingest validations at once; the rest queue.
+    #[must_use]
+    pub fn with_validation_concurrency(mut self, limit: usize) -> Self {
+        self.validator = ChunkValidator::new(limit);
+        self
+    }
+
+    /// Run `verifier` over every stamped delivery before custody.
+    #[must_use]
+    pub fn with_stamp_verifier(mut self, verifier: Arc<dyn StampVerifier>) -> Self {
+        self.verifier = Some(verifier);
+        self
+    }
+
+    /// Require and verify stamps, or take deliveries for free; stamps are
+    /// required by default.
+    #[must_use]
+    pub fn with_stamp_policy(mut self, policy: StampPolicy) -> Self {
+        self.stamp_policy = policy;
+        self
+    }
+
+    /// Relay or refuse deliveries outside the storage radius; relayed by
+    /// default.
+    #[must_use]
+    pub fn with_out_of_range_policy(mut self, policy: OutOfRangePolicy) -> Self {
+        self.out_of_range = policy;
+        self
+    }
+
+    /// Whether to take custody of `address`: `Ok(true)` inside the storage
+    /// radius, `Ok(false)` to relay it, or [`PushError::OutOfRange`] when the
+    /// policy refuses chunks outside the radius.
+    pub(crate) fn custody(&self, address: &ChunkAddress) -> Result<bool, PushError> {
+        if self.reserve.is_responsible_for(address) {
+            return Ok(true);
+        }
+        match self.out_of_range {
+            OutOfRangePolicy::Forward => Ok(false),
+            OutOfRangePolicy::Refuse => Err(PushError::OutOfRange {
+                address: *address,
+                radius: self.reserve.storage_radius(),
+            }),
+        }
+    }
+
+    /// Queue depth and concurrency of the ingest validation pool.
+    pub fn validation_stats(&self) -> ValidationStats {
+        self.validator.stats()
     }
 }
 
@@ -41,6 +145,9 @@ impl std::fmt::Debug for StorerCapability {
         f.debug_struct("StorerCapability")
             .field("network_id", &self.signer.network_id())
             .field("nonce", &self.signer.nonce())
+            .field("validation", &self.validator.stats())
+            .field("stamp_policy", &self.stamp_policy)
+            .field("out_of_range", &self.out_of_range)
             .finish_non_exhaustive()
     }
 }
@@ -14,12 +14,19 @@
 //! We use a custom `ClientInboundUpgrade` that implements `UpgradeInfo`
 //! with all protocol names and dispatches based on the negotiated protocol.
 
+use std::future::Future;
+use std::pin::pin;
+use std::time::Duration;
+
 use alloy_primitives::U256;
-use futures::future::BoxFuture;
+use futures::future::{BoxFuture, Either, select};
+use futures_timer::Delay;
+use libp2p::swarm::StreamUpgradeError;
 use libp2p::{InboundUpgrade, OutboundUpgrade, Stream, core::UpgradeInfo};
 use nectar_primitives::ChunkAddress;
 use thiserror::Error;
-use vertex_swarm_net_headers::ProtocolError;
+use vertex_net_codec::Compression;
+use vertex_swarm_net_headers::{ProtocolError, ProtocolPrefix};
 use vertex_swarm_net_pricing::{
     AnnouncePaymentThreshold, PROTOCOL_NAME as PRICING_PROTOCOL, PricingInboundProtocol,
     PricingOutboundProtocol,
@@ -41,6 +48,7 @@ use vertex_swarm_net_swap::{
     PROTOCOL_NAME as SWAP_PROTOCOL, SettlementHeaders, SignedCheque, SwapInboundProtocol,
     SwapOutboundProtocol,
 };
+
 /// Errors from client protocol upgrades.
 #[derive(Debug, Error)]
 pub enum ClientUpgradeError {
@@ -65,11 +73,56 @@ pub enum ClientUpgradeError {
     #[error("swap error: {0}")]
     Swap(#[source] ProtocolError),
 
+    /// The peer made no progress on a negotiated inbound substream within the
+    /// protocol's deadline; the substream is dropped.
+    #[error("{protocol} substream stalled past {deadline:?}")]
+    TimedOut {
+        /// The stalled protocol.
+        protocol: &'static str,
+        /// The deadline that elapsed.
+        deadline: Duration,
+    },
+
     /// Unknown protocol negotiated.
     #[error("unknown protocol: {0}")]
     UnknownProtocol(String),
 }
 
+/// Resolve `fut`, or `None` once `deadline` elapses first.
+pub(crate) async fn within<F: Future>(deadline: Duration, fut: F) -> Option<F::Output> {
+    match select(pin!(fut), Delay::new(deadline)).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right(((), _)) => None,
+    }
+}
+
+/// Per-protocol deadlines for reading an inbound request once the substream
+/// has negotiated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct InboundDeadlines {
+    /// Pricing, pseudosettle, and swap.
+    pub(crate) settlement: Duration,
+    pub(crate) retrieval: Duration,
+    pub(crate) pushsync: Duration,
+}
+
+impl Default for InboundDeadlines {
+    fn default() -> Self {
+        Self {
+            settlement: Duration::from_secs(30),
+            retrieval: Duration::from_secs(30),
+            pushsync: Duration::from_secs(30),
+        }
+    }
+}
+
+impl InboundDeadlines {
+    /// The longest deadline, so the listen-side backstop can sit above it.
+    pub(crate) fn max(&self) -> Duration {
+        self.settlement.max(self.retrieval).max(self.pushsync)
+    }
+}
+
 pub(crate) use super::events::FailureKind;
 
 impl ClientUpgradeError {
@@ -118,6 +171,23 @@ impl ClientUpgradeError {
     }
 }
 
+/// Classify a failed outbound substream.
+///
+/// A fired deadline and a failed transport are told apart before the
+/// protocol-specific `classify` sees a negotiated (`Apply`) failure;
+/// anything else, such as a failed negotiation, is a plain protocol failure.
+pub(crate) fn dial_failure_kind(
+    error: &StreamUpgradeError<ClientUpgradeError>,
+    classify: fn(&ClientUpgradeError) -> FailureKind,
+) -> FailureKind {
+    match error {
+        StreamUpgradeError::Timeout => FailureKind::TimedOut,
+        StreamUpgradeError::Io(_) => FailureKind::ConnectionLost,
+        StreamUpgradeError::Apply(e) => classify(e),
+        _ => FailureKind::Protocol,
+    }
+}
+
 /// Output from a client inbound upgrade.
 pub enum ClientInboundOutput {
     /// Received pricing threshold.
@@ -177,6 +247,10 @@ impl std::fmt::Debug for ClientInboundOutput {
 #[derive(Clone, Debug, Default)]
 pub struct ClientInboundUpgrade {
     advertised: ProtocolSet,
+    deadlines: InboundDeadlines,
+    prefix: ProtocolPrefix,
+    /// Framing for retrieval and pushsync streams.
+    compression: Compression,
     /// Our advertised swap exchange rate, sent in the headers exchange.
     #[cfg(feature = "swap")]
     swap_rate: U256,
@@ -198,6 +272,9 @@ impl ClientInboundUpgrade {
     pub(crate) fn new() -> Self {
         Self {
             advertised: ProtocolSet::None,
+            deadlines: InboundDeadlines::default(),
+            prefix: ProtocolPrefix::DEFAULT,
+            compression: Compression::None,
             #[cfg(feature = "swap")]
             swap_rate: U256::ZERO,
         }
@@ -214,11 +291,32 @@ impl ClientInboundUpgrade {
         };
         Self {
             advertised,
+            deadlines: InboundDeadlines::default(),
+            prefix: ProtocolPrefix::DEFAULT,
+            compression: Compression::None,
             #[cfg(feature = "swap")]
             swap_rate: U256::ZERO,
         }
     }
 
+    /// Bound each protocol's request read by `deadlines`.
+    pub(crate) fn with_deadlines(mut self, deadlines: InboundDeadlines) -> Self {
+        self.deadlines = deadlines;
+        self
+    }
+
+    /// Advertise every protocol under `prefix` instead of `/swarm`.
+    pub(crate) fn with_prefix(mut self, prefix: ProtocolPrefix) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Frame retrieval and pushsync streams with `compression`.
+    pub(crate) fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
     /// Set the swap exchange rate advertised in the headers exchange.
     #[cfg(feature = "swap")]
     pub(crate) fn with_swap_rate(mut self, rate: U256) -> Self {
@@ -232,21 +330,22 @@ impl UpgradeInfo for ClientInboundUpgrade {
     type InfoIter = std::vec::IntoIter<Self::Info>;
 
     fn protocol_info(&self) -> Self::InfoIter {
-        match self.advertised {
-            ProtocolSet::None => Vec::new().into_iter(),
-            ProtocolSet::PricingOnly => vec![PRICING_PROTOCOL].into_iter(),
-            ProtocolSet::Full => {
-                let protocols = vec![
-                    PRICING_PROTOCOL,
-                    RETRIEVAL_PROTOCOL,
-                    PUSHSYNC_PROTOCOL,
-                    PSEUDOSETTLE_PROTOCOL,
-                    #[cfg(feature = "swap")]
-                    SWAP_PROTOCOL,
-                ];
-                protocols.into_iter()
-            }
+        let mut protocols = match self.advertised {
+            ProtocolSet::None => Vec::new(),
+            ProtocolSet::PricingOnly => vec![PRICING_PROTOCOL],
+            ProtocolSet::Full => vec![
+                PRICING_PROTOCOL,
+                RETRIEVAL_PROTOCOL,
+                PUSHSYNC_PROTOCOL,
+                PSEUDOSETTLE_PROTOCOL,
+                #[cfg(feature = "swap")]
+                SWAP_PROTOCOL,
+            ],
+        };
+        for name in &mut protocols {
+            *name = self.prefix.apply(name);
         }
+        protocols.into_iter()
     }
 }
 
@@ -258,62 +357,97 @@ impl InboundUpgrade<Stream> for ClientInboundUpgrade {
     fn upgrade_inbound(self, socket: Stream, info: Self::Info) -> Self::Future {
         #[cfg(feature = "swap")]
         let swap_rate = self.swap_rate;
+        let deadlines = self.deadlines;
+        let compression = self.compression;
+        let canonical = self.prefix.canonical(info);
         Box::pin(async move {
-            match info {
+            match canonical.as_ref() {
                 PRICING_PROTOCOL => {
                     let pricing: PricingInboundProtocol = vertex_swarm_net_pricing::inbound();
-                    let threshold = pricing
-                        .upgrade_inbound(socket, info)
-                        .await
-                        .map_err(ClientUpgradeError::Pricing)?;
+                    let threshold = read_within(
+                        deadlines.settlement,
+                        info,
+                        pricing.upgrade_inbound(socket, info),
+                        ClientUpgradeError::Pricing,
+                    )
+                    .await?;
                     Ok(ClientInboundOutput::Pricing(threshold))
                 }
                 RETRIEVAL_PROTOCOL => {
-                    let retrieval: RetrievalInboundProtocol = vertex_swarm_net_retrieval::inbound();
-                    let (request, responder) = retrieval
-                        .upgrade_inbound(socket, info)
-                        .await
-                        .map_err(ClientUpgradeError::Retrieval)?;
+                    let retrieval: RetrievalInboundProtocol =
+                        vertex_swarm_net_retrieval::inbound(compression);
+                    let (request, responder) = read_within(
+                        deadlines.retrieval,
+                        info,
+                        retrieval.upgrade_inbound(socket, info),
+                        ClientUpgradeError::Retrieval,
+                    )
+                    .await?;
                     Ok(ClientInboundOutput::Retrieval(request, responder))
                 }
                 PUSHSYNC_PROTOCOL => {
-                    let pushsync: PushsyncInboundProtocol = vertex_swarm_net_pushsync::inbound();
-                    let (delivery, responder) = pushsync
-                        .upgrade_inbound(socket, info)
-                        .await
-                        .map_err(ClientUpgradeError::Pushsync)?;
+                    let pushsync: PushsyncInboundProtocol =
+                        vertex_swarm_net_pushsync::inbound(compression);
+                    let (delivery, responder) = read_within(
+                        deadlines.pushsync,
+                        info,
+                        pushsync.upgrade_inbound(socket, info),
+                        ClientUpgradeError::Pushsync,
+                    )
+                    .await?;
                     Ok(ClientInboundOutput::Pushsync(delivery, responder))
                 }
                 PSEUDOSETTLE_PROTOCOL => {
                     let protocol = vertex_swarm_net_pseudosettle::inbound();
-                    let result = protocol
-                        .upgrade_inbound(socket, info)
-                        .await
-                        .map_err(ClientUpgradeError::Pseudosettle)?;
+                    let result = read_within(
+                        deadlines.settlement,
+                        info,
+                        protocol.upgrade_inbound(socket, info),
+                        ClientUpgradeError::Pseudosettle,
+                    )
+                    .await?;
                     Ok(ClientInboundOutput::Pseudosettle(result))
                 }
                 #[cfg(feature = "swap")]
                 SWAP_PROTOCOL => {
                     let protocol: SwapInboundProtocol = vertex_swarm_net_swap::inbound(swap_rate);
-                    let (cheque, headers) = protocol
-                        .upgrade_inbound(socket, info)
-                        .await
-                        .map_err(ClientUpgradeError::Swap)?;
+                    let (cheque, headers) = read_within(
+                        deadlines.settlement,
+                        info,
+                        protocol.upgrade_inbound(socket, info),
+                        ClientUpgradeError::Swap,
+                    )
+                    .await?;
                     Ok(ClientInboundOutput::Swap(cheque, headers))
                 }
-                other => Err(ClientUpgradeError::UnknownProtocol(other.to_string())),
+                _ => Err(ClientUpgradeError::UnknownProtocol(info.to_string())),
             }
         })
     }
 }
 
+/// Run one protocol's inbound read under its deadline. A stall surfaces as
+/// [`ClientUpgradeError::TimedOut`] rather than libp2p's silent listen timeout,
+/// so the handler can attribute it to the peer.
+async fn read_within<T>(
+    deadline: Duration,
+    protocol: &'static str,
+    read: impl Future<Output = Result<T, ProtocolError>>,
+    wrap: fn(ProtocolError) -> ClientUpgradeError,
+) -> Result<T, ClientUpgradeError> {
+    within(deadline, read)
+        .await
+        .ok_or(ClientUpgradeError::TimedOut { protocol, deadline })?
+        .map_err(wrap)
+}
+
 /// Type of outbound request for client protocols.
 #[derive(Debug, Clone)]
 pub(crate) enum ClientOutboundRequest {
     /// Announce payment threshold.
     Pricing(AnnouncePaymentThreshold),
     /// Request a chunk.
-    Retrieval(ChunkAddress),
+    Retrieval(RetrievalRequest),
     /// Push a chunk for storage.
     Pushsync(PushsyncDelivery),
     /// Send pseudosettle payment.
@@ -345,6 +479,9 @@ pub enum ClientOutboundOutput {
 #[derive(Clone, Debug)]
 pub struct ClientOutboundUpgrade {
     request: ClientOutboundRequest,
+    prefix: ProtocolPrefix,
+    /// Framing for retrieval and pushsync streams.
+    compression: Compression,
 }
 
 impl ClientOutboundUpgrade {
@@ -352,13 +489,17 @@ impl ClientOutboundUpgrade {
     pub(crate) fn pricing(threshold: AnnouncePaymentThreshold) -> Self {
         Self {
             request: ClientOutboundRequest::Pricing(threshold),
+            prefix: ProtocolPrefix::DEFAULT,
+            compression: Compression::None,
         }
     }
 
     /// Create a new retrieval outbound upgrade.
-    pub(crate) fn retrieval(address: ChunkAddress) -> Self {
+    pub(crate) fn retrieval(request: RetrievalRequest) -> Self {
         Self {
-            request: ClientOutboundRequest::Retrieval(address),
+            request: ClientOutboundRequest::Retrieval(request),
+            prefix: ProtocolPrefix::DEFAULT,
+            compression: Compression::None,
         }
     }
 
@@ -366,6 +507,8 @@ impl ClientOutboundUpgrade {
     pub(crate) fn pushsync(delivery: PushsyncDelivery) -> Self {
         Self {
             request: ClientOutboundRequest::Pushsync(delivery),
+            prefix: ProtocolPrefix::DEFAULT,
+            compression: Compression::None,
         }
     }
 
@@ -373,6 +516,8 @@ impl ClientOutboundUpgrade {
     pub(crate) fn pseudosettle(payment: Payment) -> Self {
         Self {
             request: ClientOutboundRequest::Pseudosettle(payment),
+            prefix: ProtocolPrefix::DEFAULT,
+            compression: Compression::None,
         }
     }
 
@@ -381,9 +526,23 @@ impl ClientOutboundUpgrade {
     pub(crate) fn swap(cheque: SignedCheque, our_rate: U256) -> Self {
         Self {
             request: ClientOutboundRequest::Swap(cheque, our_rate),
+            prefix: ProtocolPrefix::DEFAULT,
+            compression: Compression::None,
         }
     }
 
+    /// Request the protocol under `prefix` instead of `/swarm`.
+    pub(crate) fn with_prefix(mut self, prefix: ProtocolPrefix) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Frame a retrieval or pushsync stream with `compression`.
+    pub(crate) fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
     /// Get the protocol name for this request.
     fn protocol_name(&self) -> &'static str {
         match &self.request {
@@ -402,7 +561,7 @@ impl UpgradeInfo for ClientOutboundUpgrade {
     type InfoIter = std::iter::Once<Self::Info>;
 
     fn protocol_info(&self) -> Self::InfoIter {
-        std::iter::once(self.protocol_name())
+        std::iter::once(self.prefix.apply(self.protocol_name()))
     }
 }
 
@@ -423,9 +582,9 @@ impl OutboundUpgrade<Stream> for ClientOutboundUpgrade {
                         .map_err(ClientUpgradeError::Pricing)?;
                     Ok(ClientOutboundOutput::Pricing)
                 }
-                ClientOutboundRequest::Retrieval(address) => {
+                ClientOutboundRequest::Retrieval(request) => {
                     let retrieval: RetrievalOutboundProtocol =
-                        vertex_swarm_net_retrieval::outbound(address);
+                        vertex_swarm_net_retrieval::outbound(request, self.compression);
                     let delivery = retrieval
                         .upgrade_outbound(socket, info)
                         .await
@@ -434,7 +593,7 @@ impl OutboundUpgrade<Stream> for ClientOutboundUpgrade {
                 }
                 ClientOutboundRequest::Pushsync(delivery) => {
                     let pushsync: PushsyncOutboundProtocol =
-                        vertex_swarm_net_pushsync::outbound(delivery);
+                        vertex_swarm_net_pushsync::outbound(delivery, self.compression);
                     let receipt = pushsync
                         .upgrade_outbound(socket, info)
                         .await
@@ -495,7 +654,12 @@ pub enum ClientOutboundInfo {
         originated: bool,
     },
     /// Pseudosettle payment with amount.
-    Pseudosettle { amount: U256 },
+    Pseudosettle {
+        amount: U256,
+        /// When the outbound substream was requested, for the settlement
+        /// round-trip latency.
+        requested_at: vertex_util_runtime::time::Instant,
+    },
     /// Swap cheque emission.
     #[cfg(feature = "swap")]
     Swap,
@@ -548,6 +712,36 @@ mod tests {
         assert_eq!(err.inbound_failure_kind(), FailureKind::Protocol);
     }
 
+    #[test]
+    fn substream_timeout_and_connection_reset_classify_apart() {
+        let timeout = StreamUpgradeError::Timeout;
+        let reset =
+            StreamUpgradeError::Io(std::io::Error::from(std::io::ErrorKind::ConnectionReset));
+        for classify in [
+            ClientUpgradeError::retrieval_failure_kind,
+            ClientUpgradeError::pushsync_failure_kind,
+        ] {
+            assert_eq!(dial_failure_kind(&timeout, classify), FailureKind::TimedOut);
+            assert_eq!(
+                dial_failure_kind(&reset, classify),
+                FailureKind::ConnectionLost
+            );
+            assert_eq!(
+                dial_failure_kind(&StreamUpgradeError::NegotiationFailed, classify),
+                FailureKind::Protocol
+            );
+        }
+
+        let malformed =
+            StreamUpgradeError::Apply(ClientUpgradeError::Retrieval(retrieval_protocol_err(
+                vertex_swarm_net_retrieval::RetrievalError::InvalidAddressLength(0),
+            )));
+        assert_eq!(
+            dial_failure_kind(&malformed, ClientUpgradeError::retrieval_failure_kind),
+            FailureKind::InvalidChunk
+        );
+    }
+
     #[test]
     fn dormant_advertises_nothing() {
         let upgrade = ClientInboundUpgrade::new();
@@ -596,6 +790,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn custom_prefix_shares_no_name_with_reference() {
+        let reference: Vec<_> = ClientInboundUpgrade::active_for(SwarmNodeType::Client)
+            .protocol_info()
+            .collect();
+        let custom: Vec<_> = ClientInboundUpgrade::active_for(SwarmNodeType::Client)
+            .with_prefix(ProtocolPrefix::new("/myswarm"))
+            .protocol_info()
+            .collect();
+
+        assert_eq!(custom.len(), reference.len());
+        assert!(custom.contains(&"/myswarm/retrieval/1.4.0/retrieval"));
+        assert!(custom.iter().all(|name| !reference.contains(name)));
+
+        let outbound =
+            ClientOutboundUpgrade::retrieval(RetrievalRequest::new(ChunkAddress::new([0x42; 32])))
+                .with_prefix(ProtocolPrefix::new("/myswarm"));
+        assert_eq!(
+            outbound.protocol_info().collect::<Vec<_>>(),
+            vec!["/myswarm/retrieval/1.4.0/retrieval"]
+        );
+    }
+
     #[cfg(feature = "swap")]
     #[test]
     fn full_set_includes_swap_when_enabled() {
@@ -0,0 +1,246 @@
+//! Bounded off-loop validation for inbound chunks.
+//!
+//! Recovering a stamp signer is an ECDSA recovery per chunk. Run inline on the
+//! inbound serve path, a pushsync burst would hold runtime workers for the
+//! whole batch and starve the swarm event loop. [`ChunkValidator`] moves each
+//! check onto the blocking pool behind a semaphore: at most `limit` run at
+//! once, the rest wait their turn, and the wait is visible in
+//! [`ValidationStats`].
+//!
+//! Address integrity is not re-checked here: the pushsync codec reconstructs
+//! the chunk from its wire bytes against the claimed address, so a delivery
+//! that reaches the serve path already hashes to its address.
+//!
+//! The browser build has no blocking pool; work runs inline there, still
+//! bounded by the semaphore.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use strum::IntoStaticStr;
+use tokio::sync::Semaphore;
+use vertex_swarm_api::{StampVerifier, VerifyError};
+use vertex_swarm_primitives::StampedChunk;
+
+/// Why an inbound chunk failed validation.
+#[derive(Debug, thiserror::Error, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum ValidationError {
+    /// The stamp signature does not recover a signer over the chunk address.
+    #[error("invalid stamp signature: {0}")]
+    InvalidSignature(String),
+    /// The storer's stamp verifier rejected the stamp.
+    #[error("stamp rejected: {0}")]
+    Rejected(VerifyError),
+    /// The worker running the check was cancelled or panicked.
+    #[error("validation worker failed")]
+    WorkerFailed,
+}
+
+/// Point-in-time view of the validation pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ValidationStats {
+    /// Validations waiting for a permit.
+    pub queued: usize,
+    /// Validations running now; never above `limit`.
+    pub active: usize,
+    /// Concurrency cap.
+    pub limit: usize,
+}
+
+struct Pool {
+    permits: Semaphore,
+    limit: usize,
+    queued: AtomicUsize,
+    active: AtomicUsize,
+}
+
+/// Semaphore-gated validation pool, Arc-cheap to clone into each handler.
+#[derive(Clone)]
+pub struct ChunkValidator {
+    pool: Arc<Pool>,
+}
+
+impl ChunkValidator {
+    /// A pool running at most `limit` validations at once (at least one).
+    pub fn new(limit: usize) -> Self {
+        let limit = limit.max(1);
+        Self {
+            pool: Arc::new(Pool {
+                permits: Semaphore::new(limit),
+                limit,
+                queued: AtomicUsize::new(0),
+                active: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Current queue depth and concurrency.
+    pub fn stats(&self) -> ValidationStats {
+        ValidationStats {
+            queued: self.pool.queued.load(Ordering::Relaxed),
+            active: self.pool.active.load(Ordering::Relaxed),
+            limit: self.pool.limit,
+        }
+    }
+
+    /// Check that the chunk's stamp is signed over its address, then run
+    /// `verifier` over it when one is installed.
+    pub async fn validate(
+        &self,
+        chunk: StampedChunk,
+        verifier: Option<Arc<dyn StampVerifier>>,
+    ) -> Result<(), ValidationError> {
+        self.run(move || {
+            let address = *chunk.address();
+            chunk
+                .stamp()
+                .recover_signer(&address)
+                .map_err(|e| ValidationError::InvalidSignature(e.to_string()))?;
+            match verifier {
+                Some(verifier) => verifier
+                    .verify(chunk.chunk(), chunk.stamp())
+                    .map_err(ValidationError::Rejected),
+                None => Ok(()),
+            }
+        })
+        .await?
+    }
+
+    /// Run `work` once a permit is free, off the calling task where the
+    /// platform allows.
+    pub(crate) async fn run<T, F>(&self, work: F) -> Result<T, ValidationError>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let queued = Gauge::enter(&self.pool.queued, "swarm.client.validation_queued");
+        let _permit = self
+            .pool
+            .permits
+            .acquire()
+            .await
+            .map_err(|_| ValidationError::WorkerFailed)?;
+        drop(queued);
+
+        let _active = Gauge::enter(&self.pool.active, "swarm.client.validation_active");
+        offload(work).await
+    }
+}
+
+impl std::fmt::Debug for ChunkValidator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChunkValidator")
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn offload<T, F>(work: F) -> Result<T, ValidationError>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|_| ValidationError::WorkerFailed)
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn offload<T, F>(work: F) -> Result<T, ValidationError>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    Ok(work())
+}
+
+/// Counter held for a scope, mirrored to a gauge; released on drop so a
+/// cancelled validation does not leak a slot.
+struct Gauge<'a> {
+    count: &'a AtomicUsize,
+    name: &'static str,
+}
+
+impl<'a> Gauge<'a> {
+    fn enter(count: &'a AtomicUsize, name: &'static str) -> Self {
+        let now = count.fetch_add(1, Ordering::Relaxed) + 1;
+        metrics::gauge!(name).set(now as f64);
+        Self { count, name }
+    }
+}
+
+impl Drop for Gauge<'_> {
+    fn drop(&mut self) {
+        let now = self.count.fetch_sub(1, Ordering::Relaxed) - 1;
+        metrics::gauge!(self.name).set(now as f64);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    /// A burst far above the cap completes with at most `limit` checks in
+    /// flight, the excess visibly queued, while the runtime thread keeps
+    /// ticking.
+    #[tokio::test(flavor = "current_thread")]
+    async fn burst_is_bounded_and_loop_stays_responsive() {
+        const LIMIT: usize = 2;
+        const JOBS: usize = 16;
+
+        let validator = ChunkValidator::new(LIMIT);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let jobs: Vec<_> = (0..JOBS)
+            .map(|_| {
+                let validator = validator.clone();
+                let running = Arc::clone(&running);
+                let peak = Arc::clone(&peak);
+                tokio::spawn(async move {
+                    validator
+                        .run(move || {
+                            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                            peak.fetch_max(now, Ordering::SeqCst);
+                            // CPU-bound stand-in: blocks its worker thread.
+                            std::thread::sleep(Duration::from_millis(20));
+                            running.fetch_sub(1, Ordering::SeqCst);
+                        })
+                        .await
+                })
+            })
+            .collect();
+
+        // Let the jobs reach the semaphore, then sample the queue.
+        tokio::task::yield_now().await;
+        let stats = validator.stats();
+        assert_eq!(stats.limit, LIMIT);
+        assert!(stats.queued > 0, "excess validations must queue: {stats:?}");
+
+        // The single runtime thread is free while validations run.
+        let mut ticks = 0;
+        while running.load(Ordering::SeqCst) > 0 || validator.stats().queued > 0 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+            ticks += 1;
+        }
+        assert!(ticks >= 10, "event loop starved: {ticks} ticks");
+
+        for job in jobs {
+            job.await.unwrap().unwrap();
+        }
+        assert!(peak.load(Ordering::SeqCst) <= LIMIT);
+        assert_eq!(
+            validator.stats(),
+            ValidationStats {
+                queued: 0,
+                active: 0,
+                limit: LIMIT,
+            }
+        );
+    }
+}
@@ -19,7 +19,9 @@ use vertex_swarm_api::Au;
 use vertex_swarm_net_pushsync::Receipt;
 #[cfg(feature = "swap")]
 use vertex_swarm_net_swap::SignedCheque;
-use vertex_swarm_primitives::{OverlayAddress, Stamp, StampedChunk, SwarmNodeType};
+use vertex_swarm_primitives::{
+    OverlayAddress, PeerCapabilities, Stamp, StampedChunk, SwarmNodeType,
+};
 
 /// Channel on which an outbound retrieval request resolves.
 ///
@@ -38,7 +40,7 @@ pub type PushResponseTx = oneshot::Sender<Result<Receipt, ChunkTransferError>>;
 /// The chunk is address-validated at decode, so it answers the request
 /// regardless of the stamp. The stamp is optional: a storer may omit it from the
 /// delivery, and it is never re-read on this path.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RetrievalResult {
     pub chunk: AnyChunk,
     /// The postage stamp the responder attached, if any.
@@ -66,7 +68,7 @@ pub struct PseudosettleAck {
 ///
 /// Both retrieval and push resolve through this type; most variants surface from
 /// either path.
-#[derive(Debug, thiserror::Error, strum::IntoStaticStr)]
+#[derive(Debug, Clone, thiserror::Error, strum::IntoStaticStr)]
 #[strum(serialize_all = "snake_case")]
 pub enum ChunkTransferError {
     #[error("Network channel closed")]
@@ -93,28 +95,41 @@ pub enum ChunkTransferError {
     #[error("Chunk not found: {0}")]
     NotFound(ChunkAddress),
 
+    /// Retrieval only. The delivery failed address or stamp reconstruction;
+    /// another candidate may still serve valid bytes.
+    #[error("Invalid chunk delivered: {0}")]
+    InvalidChunk(String),
+
     /// The local credit gate refused the request at the peer's disconnect line.
     /// No bytes were sent and a settle was triggered so the peer drains; another
     /// candidate should be tried.
     #[error("Admission refused at the disconnect line")]
     Refused,
+
+    /// The network command channel stayed full for the whole send timeout.
+    /// The request was never dispatched; the node is saturated, so another
+    /// candidate would queue behind the same channel.
+    #[error("Network command channel overloaded")]
+    Overloaded,
 }
 
 impl ChunkTransferError {
     /// Whether retrying the request against another candidate may succeed.
     ///
-    /// Timeout, remote failure, transient protocol error, not-found, and a local
-    /// credit refusal are retryable (another candidate may hold the chunk or be
-    /// affordable); a cancelled or channel-closed request reflects a local
-    /// teardown that another attempt cannot fix.
+    /// Timeout, remote failure, transient protocol error, not-found, an invalid
+    /// delivery, and a local credit refusal are retryable (another candidate may
+    /// hold the chunk or be affordable); a cancelled or channel-closed request
+    /// reflects a local teardown that another attempt cannot fix, and an
+    /// overloaded command channel is shared by every candidate.
     pub fn is_retryable(&self) -> bool {
         match self {
             Self::TimedOut
             | Self::Remote
             | Self::Protocol(_)
             | Self::NotFound(_)
+            | Self::InvalidChunk(_)
             | Self::Refused => true,
-            Self::ChannelClosed | Self::NotConnected | Self::Cancelled => false,
+            Self::ChannelClosed | Self::NotConnected | Self::Cancelled | Self::Overloaded => false,
         }
     }
 
@@ -132,8 +147,10 @@ impl ChunkTransferError {
             | Self::Cancelled
             | Self::TimedOut
             | Self::Protocol(_)
+            | Self::InvalidChunk(_)
             | Self::Remote
-            | Self::Refused => false,
+            | Self::Refused
+            | Self::Overloaded => false,
         }
     }
 }
@@ -144,8 +161,17 @@ pub enum FailureKind {
     /// The peer delivered or pushed a chunk that failed address or stamp
     /// reconstruction. Scored as invalid data.
     InvalidChunk,
-    /// A transport, negotiation, timeout, or storer-reported failure that is
-    /// not evidence of malformed data. Scored as a plain failure.
+    /// The per-protocol deadline fired before the peer answered: the peer is
+    /// slow or withholding, which may be transient. Scored as a plain
+    /// retrieval or push failure that decays.
+    TimedOut,
+    /// The connection under the substream failed (reset, closed, or another
+    /// I/O error): the peer is gone. Not scored by the client, since the
+    /// disconnect itself is scored by topology.
+    ConnectionLost,
+    /// A negotiation, decode, or storer-reported failure that is not evidence
+    /// of malformed data, such as the peer resetting the substream on a miss.
+    /// Blameless and not scored.
     Protocol,
 }
 
@@ -189,6 +215,15 @@ pub enum ClientEvent {
         address: ChunkAddress,
     },
 
+    /// We refused to forward an inbound retrieval miss because too few peers
+    /// are connected; the substream reset.
+    InboundNotReady {
+        /// The peer that asked.
+        peer: OverlayAddress,
+        /// The requested chunk address.
+        address: ChunkAddress,
+    },
+
     /// We relayed a storer's receipt for an inbound pushsync (never signed it).
     InboundRelayed {
         /// The peer that pushed.
@@ -212,6 +247,16 @@ pub enum ClientEvent {
         address: ChunkAddress,
     },
 
+    /// A peer opened an inbound substream and then made no progress reading its
+    /// request or taking our response within the protocol's deadline; the
+    /// substream was dropped.
+    InboundStalled {
+        /// The stalling peer.
+        peer: OverlayAddress,
+        /// The stalled protocol.
+        protocol: &'static str,
+    },
+
     /// Received a chunk from a peer (response to our request).
     ChunkReceived {
         /// The peer that sent the chunk.
@@ -366,6 +411,8 @@ pub enum ClientCommand {
         overlay: OverlayAddress,
         /// The peer's node type.
         node_type: SwarmNodeType,
+        /// Optional features negotiated in the handshake.
+        capabilities: PeerCapabilities,
     },
 
     /// Announce our payment threshold to a peer.
@@ -387,6 +434,11 @@ pub enum ClientCommand {
         /// True for our own request, false for a forwarder relay leg. Echoed
         /// back on the completion event so only origin requests are debited.
         originated: bool,
+        /// Hop budget to put on the request; `None` uses the configured TTL.
+        ttl: Option<u8>,
+        /// Time budget to put on the request; `None` uses the configured
+        /// retrieval timeout.
+        deadline: Option<core::time::Duration>,
     },
 
     /// Push a chunk to a peer.
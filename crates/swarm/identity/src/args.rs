@@ -1,14 +1,17 @@
 //! Identity and keystore CLI arguments.
 
 use crate::Identity;
-use crate::keystore::{create_and_save_signer, load_signer_from_keystore, resolve_password};
+use crate::keystore::{
+    create_and_save_signer, load_node_type, load_signer_from_keystore, resolve_password,
+    save_node_type,
+};
 use alloy_primitives::B256;
 use clap::Args;
 use eyre::Result;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tracing::debug;
+use tracing::{debug, info};
 use vertex_swarm_api::{IdentityError, SwarmIdentityConfig};
 use vertex_swarm_primitives::{Nonce, SwarmNodeType};
 use vertex_swarm_spec::Spec;
@@ -63,18 +66,50 @@ impl IdentityArgs {
             return Err(IdentityError::EphemeralWhenPersistent { node_type }.into());
         }
 
-        let use_ephemeral = self.ephemeral || !node_type.requires_persistent_identity();
-
-        if use_ephemeral {
+        if self.ephemeral {
             return Ok(Arc::new(Identity::random(spec, node_type)));
         }
 
-        // Persistent identity
-        let keystore_path = self
-            .keystore_dir
+        // The node type the last launch ran as is recorded next to the
+        // keystore. A client relaunched as a storer is promoted, and a storer
+        // relaunched as a client is demoted and keeps its keystore key; the
+        // reserve stays on disk for a later promotion.
+        let keystore_path = self.keystore_path(network_dir);
+        let last = load_node_type(&keystore_path);
+        let identity = match (last, node_type) {
+            (Some(SwarmNodeType::Client), SwarmNodeType::Storer) => self
+                .keystore_identity(spec, &keystore_path, SwarmNodeType::Client)?
+                .promote_to_storer()?,
+            (Some(SwarmNodeType::Storer), SwarmNodeType::Client) => self
+                .keystore_identity(spec, &keystore_path, SwarmNodeType::Storer)?
+                .demote_to_client()?,
+            _ if node_type.requires_persistent_identity() => {
+                self.keystore_identity(spec, &keystore_path, node_type)?
+            }
+            _ => Identity::random(spec, node_type),
+        };
+        if let Some(from) = last.filter(|from| *from != node_type) {
+            info!(%from, to = %node_type, "Switched node type");
+        }
+        save_node_type(&keystore_path, node_type)?;
+
+        Ok(Arc::new(identity))
+    }
+
+    /// Where the keystore lives: `--keystore-dir`, else under `network_dir`.
+    fn keystore_path(&self, network_dir: &Path) -> PathBuf {
+        self.keystore_dir
             .clone()
-            .unwrap_or_else(|| network_dir.join("keystore").join("swarm"));
+            .unwrap_or_else(|| network_dir.join("keystore").join("swarm"))
+    }
 
+    /// Build a keystore-backed identity, creating the keystore on first use.
+    fn keystore_identity(
+        &self,
+        spec: Arc<Spec>,
+        keystore_path: &Path,
+        node_type: SwarmNodeType,
+    ) -> Result<Identity> {
         let password = resolve_password(
             self.password.as_deref(),
             self.password_file
@@ -84,9 +119,9 @@ impl IdentityArgs {
         )?;
 
         let signer = if keystore_path.exists() {
-            load_signer_from_keystore(&keystore_path, &password)?
+            load_signer_from_keystore(keystore_path, &password)?
         } else {
-            create_and_save_signer(&keystore_path, &password)?
+            create_and_save_signer(keystore_path, &password)?
         };
 
         let nonce = self.nonce.map(Nonce::from).unwrap_or_else(|| {
@@ -94,7 +129,7 @@ impl IdentityArgs {
             crate::random_nonce()
         });
 
-        Ok(Arc::new(Identity::new(signer, nonce, spec, node_type)))
+        Ok(Identity::new(signer, nonce, spec, node_type))
     }
 }
 
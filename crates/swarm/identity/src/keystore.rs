@@ -10,7 +10,8 @@
 use alloy_signer_local::PrivateKeySigner;
 use eyre::{Result, WrapErr};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use vertex_swarm_primitives::SwarmNodeType;
 
 #[cfg(not(target_arch = "wasm32"))]
 use tracing::info;
@@ -44,6 +45,33 @@ pub fn resolve_password(password: Option<&str>, password_file: Option<&str>) ->
     Ok(String::new())
 }
 
+/// The node type the last launch recorded next to `keystore_path`, if any.
+///
+/// A missing or unreadable record reads as `None`: the node has no earlier
+/// role to switch from.
+pub fn load_node_type(keystore_path: &Path) -> Option<SwarmNodeType> {
+    fs::read_to_string(node_type_path(keystore_path))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Record `node_type` next to `keystore_path` for the next launch to compare
+/// against.
+pub fn save_node_type(keystore_path: &Path, node_type: SwarmNodeType) -> Result<()> {
+    let path = node_type_path(keystore_path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, node_type.to_string())
+        .wrap_err_with(|| format!("Failed to record node type at {}", path.display()))
+}
+
+fn node_type_path(keystore_path: &Path) -> PathBuf {
+    keystore_path.with_extension("node-type")
+}
+
 /// Load a signer from an Ethereum keystore file.
 ///
 /// Native-only: keystore decryption goes through the alloy eth-keystore decoder,
@@ -7,6 +7,8 @@
 
 pub mod args;
 pub mod keystore;
+#[cfg(any(test, feature = "test-utils"))]
+mod mine;
 
 use alloy_primitives::{Address, B256, ChainId, Signature};
 use alloy_signer::SignerSync;
@@ -105,6 +107,51 @@ impl Identity {
         }
     }
 
+    /// Re-issue a client identity as a storer, keeping the overlay.
+    ///
+    /// Storers need a keystore-backed key, so an ephemeral client is refused
+    /// with [`IdentityError::EphemeralWhenPersistent`]; any other node type
+    /// with [`IdentityError::NodeTypeChangeNotAllowed`]. The node type
+    /// selects the protocol set and is announced in every handshake, so the
+    /// node applies this at launch, when [`IdentityArgs::identity`] finds the
+    /// last launch ran as a client, and comes up with pullsync and local
+    /// storage enabled.
+    pub fn promote_to_storer(&self) -> Result<Self, IdentityError> {
+        self.change_node_type(SwarmNodeType::Client, SwarmNodeType::Storer)?;
+        self.assert_persistent_identity(SwarmNodeType::Storer)?;
+        Ok(Self {
+            node_type: SwarmNodeType::Storer,
+            ..self.clone()
+        })
+    }
+
+    /// Re-issue a storer identity as a client, keeping the overlay.
+    ///
+    /// The rebuilt node drops pullsync and local storage; the reserve on disk
+    /// is left in place for a later promotion. Any other node type is refused
+    /// with [`IdentityError::NodeTypeChangeNotAllowed`].
+    pub fn demote_to_client(&self) -> Result<Self, IdentityError> {
+        self.change_node_type(SwarmNodeType::Storer, SwarmNodeType::Client)?;
+        Ok(Self {
+            node_type: SwarmNodeType::Client,
+            ..self.clone()
+        })
+    }
+
+    fn change_node_type(
+        &self,
+        from: SwarmNodeType,
+        to: SwarmNodeType,
+    ) -> Result<(), IdentityError> {
+        if self.node_type != from {
+            return Err(IdentityError::NodeTypeChangeNotAllowed {
+                from: self.node_type,
+                to,
+            });
+        }
+        Ok(())
+    }
+
     /// Sets a custom welcome message.
     pub fn with_welcome_message(mut self, message: impl Into<String>) -> Self {
         self.welcome_message = Some(message.into());
@@ -231,6 +278,76 @@ mod tests {
         assert_ne!(id1.overlay_address(), id2.overlay_address());
     }
 
+    #[test]
+    fn promoting_a_client_enables_storage_and_announces_storer() {
+        let spec = init_testnet();
+        let client = Identity::new(
+            LocalSigner::random(),
+            Nonce::new([7u8; 32]),
+            spec,
+            SwarmNodeType::Client,
+        );
+        assert!(!client.node_type().requires_pullsync());
+
+        let storer = client.promote_to_storer().expect("persistent client");
+        // The handshake announces `node_type()`, and the protocol set the
+        // rebuilt node mounts follows it.
+        assert_eq!(storer.node_type(), SwarmNodeType::Storer);
+        assert!(storer.node_type().requires_pullsync());
+        assert!(storer.node_type().requires_storage());
+        assert_eq!(storer.overlay_address(), client.overlay_address());
+
+        let demoted = storer.demote_to_client().expect("storers may demote");
+        assert_eq!(demoted.node_type(), SwarmNodeType::Client);
+        assert!(!demoted.node_type().requires_storage());
+        assert!(matches!(
+            demoted.demote_to_client(),
+            Err(IdentityError::NodeTypeChangeNotAllowed { .. })
+        ));
+    }
+
+    #[test]
+    fn ephemeral_clients_cannot_be_promoted() {
+        let spec = init_testnet();
+        let client = Identity::random(spec, SwarmNodeType::Client);
+
+        assert!(matches!(
+            client.promote_to_storer(),
+            Err(IdentityError::EphemeralWhenPersistent {
+                node_type: SwarmNodeType::Storer
+            })
+        ));
+    }
+
+    #[test]
+    fn the_recorded_node_type_drives_promotion_and_demotion() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let args = IdentityArgs {
+            password: Some("test".into()),
+            nonce: Some(B256::repeat_byte(0x07)),
+            ..Default::default()
+        };
+        let launch = |node_type| {
+            args.identity(init_testnet(), dir.path(), node_type)
+                .expect("identity")
+        };
+
+        let client = launch(SwarmNodeType::Client);
+        assert_eq!(client.node_type(), SwarmNodeType::Client);
+
+        // The last launch ran as a client, so this one is a promotion: the
+        // storer keeps the keystore key the promotion created.
+        let storer = launch(SwarmNodeType::Storer);
+        assert_eq!(storer.node_type(), SwarmNodeType::Storer);
+
+        // Demotion keeps the storer's key and overlay instead of drawing a
+        // fresh ephemeral client.
+        let demoted = launch(SwarmNodeType::Client);
+        assert_eq!(demoted.node_type(), SwarmNodeType::Client);
+        assert_eq!(demoted.overlay_address(), storer.overlay_address());
+        assert_ne!(demoted.overlay_address(), client.overlay_address());
+    }
+
     #[test]
     fn welcome_message() {
         let spec = init_testnet();
@@ -21,8 +21,8 @@
 //!   latest revision. On insert, a SOC with a newer stamp timestamp replaces an
 //!   older cached one (last-write-wins by timestamp), so an update refreshes the
 //!   cache rather than being dropped behind a stale entry. A SOC always carries a
-//!   stamp in the cache: the retrieval path never caches a stampless SOC, since
-//!   without a timestamp it has no version signal and could serve a stale copy.
+//!   stamp in the cache: a stampless SOC has no version signal and could serve a
+//!   stale copy, so `put` drops it (see [`CachedChunk::is_cacheable`]).
 
 use nectar_primitives::ChunkAddress;
 use vertex_store::ByteSized;
@@ -146,6 +146,11 @@ impl<C: Clock, B: CacheBackend> ChunkStore<C, B> {
 
 impl<C: Clock, B: CacheBackend> SwarmLocalStore for ChunkStore<C, B> {
     fn put(&self, chunk: CachedChunk) -> SwarmResult<()> {
+        // A mutable chunk without a stamp has no freshness bound; not caching it
+        // is the only safe answer.
+        if !chunk.is_cacheable() {
+            return Ok(());
+        }
         let address = *chunk.address();
         // Last-write-wins by timestamp for single-owner chunks: a forwarded SOC
         // older than the cached one for this address must not overwrite the
@@ -201,8 +206,14 @@ mod tests {
     use alloy_primitives::{B256, Signature};
     use alloy_signer_local::PrivateKeySigner;
     use nectar_postage::Stamp;
-    use nectar_primitives::{AnyChunk, ContentChunk, SingleOwnerChunk};
-    use std::sync::atomic::{AtomicI64, Ordering};
+    use nectar_primitives::{
+        AnyChunk, ContentChunk, ContentOnlyChunkSet, SingleOwnerChunk, StandardChunkSet,
+    };
+    use std::sync::{
+        Arc,
+        atomic::{AtomicI64, Ordering},
+    };
+    use vertex_swarm_api::{NetworkIngest, NetworkStore};
 
     /// A clock returning whatever value the test last set.
     #[derive(Default)]
@@ -256,6 +267,31 @@ mod tests {
         CachedChunk::new(chunk, Some(stamp_at(stamp_ns)))
     }
 
+    /// A chunk from the network reaches the store only through a
+    /// `NetworkStore`, which refuses a type the network's set does not carry.
+    #[test]
+    fn network_store_validates_against_the_chunk_set() {
+        let store = Arc::new(ChunkStore::with_budget(1 << 20, u64::MAX));
+        let content_only = NetworkStore::new(
+            store.clone(),
+            NetworkIngest::for_chunk_set::<ContentOnlyChunkSet>(),
+        );
+
+        let (received, stamp) = soc(b"from a peer", 0).into_parts();
+        assert!(
+            content_only.put(received.clone(), stamp.clone()).is_err(),
+            "a single-owner chunk is outside a content-only set"
+        );
+        assert!(!store.contains(received.address()));
+
+        let standard = NetworkStore::new(
+            store.clone(),
+            NetworkIngest::for_chunk_set::<StandardChunkSet>(),
+        );
+        standard.put(received.clone(), stamp).unwrap();
+        assert!(store.contains(received.address()));
+    }
+
     #[test]
     fn round_trips_a_content_chunk() {
         let store = ChunkStore::with_budget(1 << 20, 1_000);
@@ -366,4 +402,27 @@ mod tests {
         assert!(served.stamp().is_none(), "served without a stamp");
         assert!(served.chunk().is_content());
     }
+
+    #[test]
+    fn stampless_soc_is_not_cached() {
+        // A retrieved chunk arrives stampless. The immutable content chunk is
+        // cached with no expiry; the mutable SOC has no freshness bound and is
+        // dropped rather than cached.
+        let clock = FixedClock::new(0);
+        let store = ChunkStore::with_budget_and_clock(1 << 20, 1, &clock);
+        let immutable = stampless_content(b"retrieved immutable payload");
+        let mutable = CachedChunk::new(soc(b"retrieved feed", 0).into_parts().0, None);
+        assert!(immutable.cacheability().is_immutable());
+        assert!(!mutable.cacheability().is_immutable());
+
+        store.put(immutable.clone()).unwrap();
+        store.put(mutable.clone()).unwrap();
+
+        assert!(
+            !store.contains(mutable.address()),
+            "mutable chunk not cached"
+        );
+        clock.set(1_000_000_000_000);
+        assert_eq!(store.get(immutable.address()).unwrap(), Some(immutable));
+    }
 }
@@ -0,0 +1,192 @@
+//! Per-chunk-type routing over several local store backends.
+//!
+//! [`RoutingChunkStore`] lets one node keep content chunks in a fast memory
+//! cache while single-owner chunks go to a durable backend, behind a single
+//! [`SwarmLocalStore`]. Writes dispatch on the chunk's [`ChunkTypeId`]; types
+//! without a route go to the default backend.
+//!
+//! An address does not reveal its chunk type, so reads probe the routed
+//! backends in the order the routes were added and then the default, returning
+//! the first hit. A chunk is written to exactly one backend, so at most one
+//! answers.
+
+use std::sync::Arc;
+
+use nectar_primitives::{ChunkAddress, ChunkTypeId};
+use vertex_swarm_api::{SwarmLocalStore, SwarmResult};
+use vertex_swarm_primitives::CachedChunk;
+
+/// A [`SwarmLocalStore`] dispatching each chunk to a backend by chunk type.
+pub struct RoutingChunkStore {
+    /// Backend for every chunk type without a route.
+    default: Arc<dyn SwarmLocalStore>,
+    /// `(type id, backend)` in insertion order; at most one entry per type.
+    routes: Vec<(u8, Arc<dyn SwarmLocalStore>)>,
+}
+
+impl RoutingChunkStore {
+    /// Route every chunk type to `default` until routes are added.
+    #[must_use]
+    pub fn new(default: Arc<dyn SwarmLocalStore>) -> Self {
+        Self {
+            default,
+            routes: Vec::new(),
+        }
+    }
+
+    /// Store chunks of `chunk_type` in `backend`, replacing any earlier route
+    /// for that type.
+    #[must_use]
+    pub fn with_route(
+        mut self,
+        chunk_type: ChunkTypeId,
+        backend: Arc<dyn SwarmLocalStore>,
+    ) -> Self {
+        let id = chunk_type.as_u8();
+        match self.routes.iter_mut().find(|(routed, _)| *routed == id) {
+            Some(route) => route.1 = backend,
+            None => self.routes.push((id, backend)),
+        }
+        self
+    }
+
+    /// The backend that stores chunks of `chunk_type`.
+    pub fn backend_for(&self, chunk_type: ChunkTypeId) -> &Arc<dyn SwarmLocalStore> {
+        let id = chunk_type.as_u8();
+        self.routes
+            .iter()
+            .find(|(routed, _)| *routed == id)
+            .map_or(&self.default, |(_, backend)| backend)
+    }
+
+    /// Every backend in read-probe order: routed ones, then the default.
+    fn backends(&self) -> impl Iterator<Item = &Arc<dyn SwarmLocalStore>> {
+        self.routes
+            .iter()
+            .map(|(_, backend)| backend)
+            .chain(std::iter::once(&self.default))
+    }
+}
+
+impl SwarmLocalStore for RoutingChunkStore {
+    fn put(&self, chunk: CachedChunk) -> SwarmResult<()> {
+        self.backend_for(chunk.chunk().type_id()).put(chunk)
+    }
+
+    fn get(&self, address: &ChunkAddress) -> SwarmResult<Option<CachedChunk>> {
+        for backend in self.backends() {
+            if let Some(chunk) = backend.get(address)? {
+                return Ok(Some(chunk));
+            }
+        }
+        Ok(None)
+    }
+
+    fn contains(&self, address: &ChunkAddress) -> bool {
+        self.backends().any(|backend| backend.contains(address))
+    }
+
+    fn remove(&self, address: &ChunkAddress) -> SwarmResult<()> {
+        for backend in self.backends() {
+            backend.remove(address)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    use alloy_primitives::{B256, Signature};
+    use alloy_signer_local::PrivateKeySigner;
+    use nectar_postage::Stamp;
+    use nectar_primitives::{AnyChunk, ContentChunk, SingleOwnerChunk};
+
+    use crate::{ChunkStore, Clock};
+
+    /// Fixed at the epoch so a stamped SOC stays within the cache TTL.
+    struct EpochClock;
+
+    impl Clock for EpochClock {
+        fn now_ns(&self) -> i64 {
+            0
+        }
+    }
+
+    fn backend() -> Arc<dyn SwarmLocalStore> {
+        Arc::new(ChunkStore::with_budget_and_clock(
+            1 << 20,
+            1_000_000_000,
+            EpochClock,
+        ))
+    }
+
+    fn content() -> CachedChunk {
+        let chunk: AnyChunk = ContentChunk::new(&b"routed content"[..])
+            .expect("valid content chunk")
+            .into();
+        CachedChunk::new(chunk, None)
+    }
+
+    fn soc() -> CachedChunk {
+        let signer = PrivateKeySigner::from_bytes(&B256::repeat_byte(0x11)).expect("signer");
+        let chunk: AnyChunk =
+            SingleOwnerChunk::new(B256::repeat_byte(0x22), &b"routed soc"[..], &signer)
+                .expect("valid soc")
+                .into();
+        let sig = Signature::from_raw(&[1u8; 65]).expect("valid signature");
+        CachedChunk::new(
+            chunk,
+            Some(Stamp::new(B256::repeat_byte(0xaa), 3, 7, 0, sig)),
+        )
+    }
+
+    #[test]
+    fn puts_and_gets_route_by_chunk_type() {
+        let memory = backend();
+        let disk = backend();
+        let content = content();
+        let soc = soc();
+        let store = RoutingChunkStore::new(Arc::clone(&memory))
+            .with_route(content.chunk().type_id(), Arc::clone(&memory))
+            .with_route(soc.chunk().type_id(), Arc::clone(&disk));
+
+        let content_address = *content.address();
+        let soc_address = *soc.address();
+        store.put(content).unwrap();
+        store.put(soc).unwrap();
+
+        assert!(memory.contains(&content_address));
+        assert!(!disk.contains(&content_address));
+        assert!(disk.contains(&soc_address));
+        assert!(!memory.contains(&soc_address));
+
+        let got = store.get(&content_address).unwrap().expect("content hit");
+        assert_eq!(*got.address(), content_address);
+        let got = store.get(&soc_address).unwrap().expect("soc hit");
+        assert_eq!(*got.address(), soc_address);
+
+        store.remove(&soc_address).unwrap();
+        assert!(!store.contains(&soc_address));
+        assert!(store.contains(&content_address));
+    }
+
+    #[test]
+    fn unrouted_types_use_the_default_backend() {
+        let default = backend();
+        let disk = backend();
+        let soc = soc();
+        let store = RoutingChunkStore::new(Arc::clone(&default))
+            .with_route(soc.chunk().type_id(), Arc::clone(&disk));
+
+        let content = content();
+        let address = *content.address();
+        store.put(content).unwrap();
+
+        assert!(default.contains(&address));
+        assert!(!disk.contains(&address));
+        assert!(store.get(&address).unwrap().is_some());
+    }
+}
@@ -0,0 +1,421 @@
+//! NetworkBehaviour for goodbye protocol.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use libp2p::{
+    Multiaddr, PeerId,
+    swarm::{
+        CloseConnection, ConnectionId, FromSwarm, NetworkBehaviour, NotifyHandler, THandler,
+        THandlerInEvent, THandlerOutEvent, ToSwarm,
+        behaviour::{DialFailure, ListenFailure},
+    },
+};
+use metrics::counter;
+use strum::IntoStaticStr;
+use tracing::debug;
+use vertex_swarm_api::{DisconnectReason, SwarmIdentity, SwarmSpec};
+use vertex_swarm_forks::SwarmHardfork;
+use vertex_util_runtime::time::now_unix_secs;
+
+use crate::codec::Goodbye;
+use crate::handler::{GoodbyeCommand, GoodbyeHandler, GoodbyeHandlerEvent};
+use crate::metrics::{GOODBYE_RECEIVED_TOTAL, GOODBYE_SENT_TOTAL};
+
+/// Events emitted by GoodbyeBehaviour.
+#[derive(Debug, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum GoodbyeEvent {
+    /// The remote announced it is closing the connection.
+    Received {
+        peer_id: PeerId,
+        connection_id: ConnectionId,
+        goodbye: Goodbye,
+    },
+}
+
+/// A goodbye in flight to a peer.
+#[derive(Debug, Clone, Copy)]
+struct Closing {
+    reason: DisconnectReason,
+    /// The connection carrying the goodbye.
+    connection_id: ConnectionId,
+}
+
+/// Behaviour for the Swarm goodbye protocol.
+///
+/// [`Self::close`] replaces a bare `CloseConnection`: the close is emitted
+/// once the goodbye is written or has failed, bounded by
+/// [`GOODBYE_TIMEOUT`](crate::GOODBYE_TIMEOUT). Whether a connection speaks
+/// goodbye is fixed when it is established, by whether
+/// [`SwarmHardfork::Accord`] is active for the identity's spec.
+pub struct GoodbyeBehaviour<I: SwarmIdentity> {
+    identity: Arc<I>,
+    events: VecDeque<ToSwarm<GoodbyeEvent, GoodbyeCommand>>,
+    /// Every established connection per peer, and whether its handler speaks
+    /// goodbye.
+    connections: HashMap<PeerId, HashMap<ConnectionId, bool>>,
+    /// Peers with a goodbye in flight, awaiting their close.
+    closing: HashMap<PeerId, Closing>,
+}
+
+impl<I: SwarmIdentity> GoodbyeBehaviour<I> {
+    /// Create the behaviour, gating the protocol on `identity`'s spec.
+    pub fn new(identity: Arc<I>) -> Self {
+        Self {
+            identity,
+            events: VecDeque::new(),
+            connections: HashMap::new(),
+            closing: HashMap::new(),
+        }
+    }
+
+    /// Send `reason` to the peer, then close every connection to it.
+    ///
+    /// A peer with no connection that speaks goodbye is closed immediately; a
+    /// second call while a goodbye is in flight is a no-op.
+    pub fn close(&mut self, peer_id: PeerId, reason: DisconnectReason) {
+        if self.closing.contains_key(&peer_id) {
+            return;
+        }
+        let carrier = self.connections.get(&peer_id).and_then(|connections| {
+            connections
+                .iter()
+                .find_map(|(connection_id, speaks)| speaks.then_some(*connection_id))
+        });
+        let Some(connection_id) = carrier else {
+            self.push_close(peer_id);
+            return;
+        };
+        self.closing.insert(
+            peer_id,
+            Closing {
+                reason,
+                connection_id,
+            },
+        );
+        self.events.push_back(ToSwarm::NotifyHandler {
+            peer_id,
+            handler: NotifyHandler::One(connection_id),
+            event: GoodbyeCommand::Send(Goodbye::new(reason)),
+        });
+    }
+
+    /// Whether a connection opened now speaks goodbye.
+    fn active(&self) -> bool {
+        self.identity
+            .spec()
+            .is_fork_active_at_timestamp(SwarmHardfork::Accord, now_unix_secs())
+    }
+
+    fn new_handler(&mut self, peer_id: PeerId, connection_id: ConnectionId) -> GoodbyeHandler {
+        let enabled = self.active();
+        self.connections
+            .entry(peer_id)
+            .or_default()
+            .insert(connection_id, enabled);
+        GoodbyeHandler::new(enabled)
+    }
+
+    /// Forget a connection. If it was carrying a goodbye, the goodbye is lost
+    /// with it, so close the peer's remaining connections now.
+    fn remove_connection(&mut self, peer_id: PeerId, connection_id: ConnectionId) {
+        if let Some(connections) = self.connections.get_mut(&peer_id) {
+            connections.remove(&connection_id);
+            if connections.is_empty() {
+                self.connections.remove(&peer_id);
+            }
+        }
+        if self
+            .closing
+            .get(&peer_id)
+            .is_some_and(|closing| closing.connection_id == connection_id)
+        {
+            self.closing.remove(&peer_id);
+            if self.connections.contains_key(&peer_id) {
+                self.push_close(peer_id);
+            }
+        }
+    }
+
+    /// Resolve the goodbye in flight on `connection_id`, if any.
+    fn finish(&mut self, peer_id: PeerId, connection_id: ConnectionId) -> Option<DisconnectReason> {
+        let closing = self.closing.get(&peer_id)?;
+        if closing.connection_id != connection_id {
+            return None;
+        }
+        let reason = closing.reason;
+        self.closing.remove(&peer_id);
+        self.push_close(peer_id);
+        Some(reason)
+    }
+
+    fn push_close(&mut self, peer_id: PeerId) {
+        self.events.push_back(ToSwarm::CloseConnection {
+            peer_id,
+            connection: CloseConnection::All,
+        });
+    }
+}
+
+impl<I: SwarmIdentity + 'static> NetworkBehaviour for GoodbyeBehaviour<I> {
+    type ConnectionHandler = GoodbyeHandler;
+    type ToSwarm = GoodbyeEvent;
+
+    fn handle_established_inbound_connection(
+        &mut self,
+        connection_id: ConnectionId,
+        peer: PeerId,
+        _local_addr: &Multiaddr,
+        _remote_addr: &Multiaddr,
+    ) -> Result<THandler<Self>, libp2p::swarm::ConnectionDenied> {
+        Ok(self.new_handler(peer, connection_id))
+    }
+
+    fn handle_established_outbound_connection(
+        &mut self,
+        connection_id: ConnectionId,
+        peer: PeerId,
+        _addr: &Multiaddr,
+        _role_override: libp2p::core::Endpoint,
+        _port_use: libp2p::core::transport::PortUse,
+    ) -> Result<THandler<Self>, libp2p::swarm::ConnectionDenied> {
+        Ok(self.new_handler(peer, connection_id))
+    }
+
+    fn on_swarm_event(&mut self, event: FromSwarm) {
+        match event {
+            FromSwarm::ConnectionClosed(closed) => {
+                self.remove_connection(closed.peer_id, closed.connection_id);
+            }
+            // Another behaviour denied a connection we already built a handler
+            // for; it never becomes established.
+            FromSwarm::DialFailure(DialFailure {
+                peer_id: Some(peer_id),
+                connection_id,
+                ..
+            }) => self.remove_connection(peer_id, connection_id),
+            FromSwarm::ListenFailure(ListenFailure {
+                peer_id: Some(peer_id),
+                connection_id,
+                ..
+            }) => self.remove_connection(peer_id, connection_id),
+            _ => {}
+        }
+    }
+
+    fn on_connection_handler_event(
+        &mut self,
+        peer_id: PeerId,
+        connection_id: ConnectionId,
+        event: THandlerOutEvent<Self>,
+    ) {
+        match event {
+            GoodbyeHandlerEvent::Received(goodbye) => {
+                let label: &'static str = goodbye.reason().map_or("unknown", Into::into);
+                counter!(GOODBYE_RECEIVED_TOTAL, "reason" => label).increment(1);
+                self.events
+                    .push_back(ToSwarm::GenerateEvent(GoodbyeEvent::Received {
+                        peer_id,
+                        connection_id,
+                        goodbye,
+                    }));
+            }
+            GoodbyeHandlerEvent::Sent => {
+                if let Some(reason) = self.finish(peer_id, connection_id) {
+                    let label: &'static str = reason.into();
+                    counter!(GOODBYE_SENT_TOTAL, "reason" => label).increment(1);
+                }
+            }
+            GoodbyeHandlerEvent::Failed(error) => {
+                debug!(%peer_id, %error, "goodbye not delivered, closing anyway");
+                self.finish(peer_id, connection_id);
+            }
+        }
+    }
+
+    fn poll(
+        &mut self,
+        _cx: &mut Context<'_>,
+    ) -> Poll<ToSwarm<Self::ToSwarm, THandlerInEvent<Self>>> {
+        if let Some(event) = self.events.pop_front() {
+            return Poll::Ready(event);
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    use libp2p::core::{ConnectedPoint, Endpoint, transport::PortUse};
+    use libp2p::swarm::behaviour::ConnectionClosed;
+    use vertex_swarm_api::SwarmNodeType;
+    use vertex_swarm_identity::Identity;
+
+    fn endpoint() -> ConnectedPoint {
+        ConnectedPoint::Dialer {
+            address: "/ip4/127.0.0.1/tcp/1634".parse().unwrap(),
+            role_override: Endpoint::Dialer,
+            port_use: PortUse::Reuse,
+        }
+    }
+
+    /// A behaviour on the dev network, where Accord is active.
+    fn accord_behaviour() -> GoodbyeBehaviour<Identity> {
+        GoodbyeBehaviour::new(Arc::new(Identity::random(
+            vertex_swarm_spec::init_dev(),
+            SwarmNodeType::Client,
+        )))
+    }
+
+    fn connected(behaviour: &mut GoodbyeBehaviour<Identity>, peer_id: PeerId, id: usize) {
+        behaviour
+            .handle_established_outbound_connection(
+                ConnectionId::new_unchecked(id),
+                peer_id,
+                &"/ip4/127.0.0.1/tcp/1634".parse().unwrap(),
+                Endpoint::Dialer,
+                PortUse::Reuse,
+            )
+            .unwrap();
+    }
+
+    fn disconnected(
+        behaviour: &mut GoodbyeBehaviour<Identity>,
+        peer_id: PeerId,
+        connection_id: ConnectionId,
+        remaining_established: usize,
+    ) {
+        behaviour.on_swarm_event(FromSwarm::ConnectionClosed(ConnectionClosed {
+            peer_id,
+            connection_id,
+            endpoint: &endpoint(),
+            cause: None,
+            remaining_established,
+        }));
+    }
+
+    fn next(behaviour: &mut GoodbyeBehaviour<Identity>) -> ToSwarm<GoodbyeEvent, GoodbyeCommand> {
+        let waker = futures::task::noop_waker();
+        match behaviour.poll(&mut Context::from_waker(&waker)) {
+            Poll::Ready(event) => event,
+            Poll::Pending => panic!("behaviour must emit an action"),
+        }
+    }
+
+    fn is_close_all(event: &ToSwarm<GoodbyeEvent, GoodbyeCommand>, peer_id: PeerId) -> bool {
+        matches!(
+            event,
+            ToSwarm::CloseConnection { peer_id: closed, connection: CloseConnection::All }
+                if *closed == peer_id
+        )
+    }
+
+    #[test]
+    fn goodbye_carries_reason_then_closes() {
+        let mut behaviour = accord_behaviour();
+        let peer_id = PeerId::random();
+        connected(&mut behaviour, peer_id, 1);
+
+        behaviour.close(peer_id, DisconnectReason::AllowanceExceeded);
+        // A repeat close while the goodbye is in flight sends nothing more.
+        behaviour.close(peer_id, DisconnectReason::LowScore);
+
+        match next(&mut behaviour) {
+            ToSwarm::NotifyHandler {
+                peer_id: notified,
+                handler: NotifyHandler::One(connection_id),
+                event: GoodbyeCommand::Send(goodbye),
+            } => {
+                assert_eq!(notified, peer_id);
+                assert_eq!(connection_id, ConnectionId::new_unchecked(1));
+                assert_eq!(goodbye.code, 7);
+                assert_eq!(goodbye.reason(), Some(DisconnectReason::AllowanceExceeded));
+            }
+            _ => panic!("expected the goodbye before the close"),
+        }
+        assert!(behaviour.events.is_empty());
+
+        behaviour.on_connection_handler_event(
+            peer_id,
+            ConnectionId::new_unchecked(1),
+            GoodbyeHandlerEvent::Sent,
+        );
+        assert!(is_close_all(&next(&mut behaviour), peer_id));
+    }
+
+    #[test]
+    fn unconnected_peer_is_closed_immediately() {
+        let mut behaviour = accord_behaviour();
+        let peer_id = PeerId::random();
+        connected(&mut behaviour, peer_id, 1);
+        disconnected(&mut behaviour, peer_id, ConnectionId::new_unchecked(1), 0);
+
+        behaviour.close(peer_id, DisconnectReason::Banned);
+        assert!(is_close_all(&next(&mut behaviour), peer_id));
+    }
+
+    /// Before Accord no connection speaks goodbye, so a close is a bare close.
+    #[test]
+    fn pre_accord_close_sends_no_goodbye() {
+        let mut behaviour = GoodbyeBehaviour::new(Arc::new(Identity::random(
+            vertex_swarm_spec::init_testnet(),
+            SwarmNodeType::Client,
+        )));
+        let peer_id = PeerId::random();
+        connected(&mut behaviour, peer_id, 1);
+
+        behaviour.close(peer_id, DisconnectReason::LowScore);
+        assert!(is_close_all(&next(&mut behaviour), peer_id));
+        assert!(behaviour.closing.is_empty());
+    }
+
+    /// Losing the connection that carries the goodbye closes the peer's other
+    /// connections and clears the in-flight state, so a later close works.
+    #[test]
+    fn carrier_loss_closes_remaining_connections() {
+        let mut behaviour = accord_behaviour();
+        let peer_id = PeerId::random();
+        connected(&mut behaviour, peer_id, 1);
+        connected(&mut behaviour, peer_id, 2);
+
+        behaviour.close(peer_id, DisconnectReason::Requested);
+        let carrier = match next(&mut behaviour) {
+            ToSwarm::NotifyHandler {
+                handler: NotifyHandler::One(connection_id),
+                ..
+            } => connection_id,
+            _ => panic!("expected the goodbye"),
+        };
+        let other = if carrier == ConnectionId::new_unchecked(1) {
+            ConnectionId::new_unchecked(2)
+        } else {
+            ConnectionId::new_unchecked(1)
+        };
+
+        // The other connection closing leaves the goodbye in flight.
+        disconnected(&mut behaviour, peer_id, other, 1);
+        assert!(behaviour.events.is_empty());
+        assert!(behaviour.closing.contains_key(&peer_id));
+
+        // A fresh connection arrives, then the carrier drops before reporting.
+        connected(&mut behaviour, peer_id, 3);
+        disconnected(&mut behaviour, peer_id, carrier, 1);
+        assert!(is_close_all(&next(&mut behaviour), peer_id));
+        assert!(behaviour.closing.is_empty());
+
+        // A late outcome from another connection does not close twice.
+        behaviour.on_connection_handler_event(
+            peer_id,
+            ConnectionId::new_unchecked(3),
+            GoodbyeHandlerEvent::Sent,
+        );
+        assert!(behaviour.events.is_empty());
+    }
+}
@@ -0,0 +1,71 @@
+//! Codec for goodbye protocol messages.
+//!
+//! # Wire Format
+//!
+//! The reason is the [`DisconnectReason::code`] as a protobuf `uint32`. Codes
+//! this node does not know are kept verbatim so a newer peer's reason is still
+//! logged.
+
+use vertex_net_codec::{Codec, ProtoMessage};
+use vertex_swarm_api::DisconnectReason;
+
+use crate::error::GoodbyeError;
+
+/// Codec for goodbye protocol messages.
+pub type GoodbyeCodec = Codec<Goodbye, GoodbyeError>;
+
+/// Close reason sent ahead of a local disconnect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Goodbye {
+    /// Wire code of the close reason.
+    pub code: u32,
+}
+
+impl Goodbye {
+    /// Create a goodbye carrying `reason`.
+    pub fn new(reason: DisconnectReason) -> Self {
+        Self {
+            code: u32::from(reason.code()),
+        }
+    }
+
+    /// The decoded reason, `None` for a code this node does not know.
+    pub fn reason(&self) -> Option<DisconnectReason> {
+        u8::try_from(self.code)
+            .ok()
+            .and_then(DisconnectReason::from_code)
+    }
+}
+
+impl ProtoMessage for Goodbye {
+    type Proto = vertex_swarm_net_proto::goodbye::Goodbye;
+    type EncodeError = std::convert::Infallible;
+    type DecodeError = GoodbyeError;
+
+    fn into_proto(self) -> Result<Self::Proto, Self::EncodeError> {
+        Ok(vertex_swarm_net_proto::goodbye::Goodbye { reason: self.code })
+    }
+
+    fn from_proto(proto: Self::Proto) -> Result<Self, Self::DecodeError> {
+        Ok(Self { code: proto.reason })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vertex_net_codec::assert_proto_roundtrip;
+
+    #[test]
+    fn test_roundtrip() {
+        assert_proto_roundtrip!(Goodbye::new(DisconnectReason::AllowanceExceeded));
+    }
+
+    #[test]
+    fn test_unknown_code_is_kept() {
+        let goodbye =
+            Goodbye::from_proto(vertex_swarm_net_proto::goodbye::Goodbye { reason: 1000 }).unwrap();
+        assert_eq!(goodbye.code, 1000);
+        assert_eq!(goodbye.reason(), None);
+    }
+}
@@ -0,0 +1,6 @@
+//! Error types for goodbye protocol.
+
+vertex_net_codec::protocol_error! {
+    /// Goodbye protocol errors.
+    pub enum GoodbyeError {}
+}
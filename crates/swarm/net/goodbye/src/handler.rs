@@ -0,0 +1,184 @@
+//! Per-connection handler for goodbye protocol.
+//!
+//! Sends at most one goodbye per connection and reports the outcome so the
+//! behaviour can close the connection. Only the first inbound goodbye is
+//! surfaced; a peer cannot use repeated goodbyes to flood the log. A handler
+//! built before the fork is disabled: it denies inbound goodbyes and never
+//! opens an outbound one.
+
+use std::{
+    collections::VecDeque,
+    task::{Context, Poll},
+};
+
+use either::Either;
+use futures::future;
+use libp2p::core::upgrade::DeniedUpgrade;
+use libp2p::swarm::{
+    SubstreamProtocol,
+    handler::{
+        ConnectionEvent, ConnectionHandler, ConnectionHandlerEvent, DialUpgradeError,
+        FullyNegotiatedInbound, FullyNegotiatedOutbound, ListenUpgradeError,
+    },
+};
+use tracing::debug;
+use vertex_metrics::labels::direction;
+use vertex_swarm_net_headers::{Inbound, Outbound, ProtocolStreamError, UpgradeError};
+
+use crate::GOODBYE_TIMEOUT;
+use crate::codec::Goodbye;
+use crate::protocol::{
+    GoodbyeInboundProtocol, GoodbyeInner, GoodbyeOutboundInner, GoodbyeOutboundProtocol,
+};
+
+/// Commands from behaviour to handler.
+#[derive(Debug)]
+pub enum GoodbyeCommand {
+    /// Send this goodbye on the connection.
+    Send(Goodbye),
+}
+
+/// Events from handler to behaviour.
+#[derive(Debug)]
+pub enum GoodbyeHandlerEvent {
+    /// The remote told us why it is closing.
+    Received(Goodbye),
+    /// Our goodbye was written.
+    Sent,
+    /// Our goodbye could not be delivered (unsupported, timed out, or reset).
+    Failed(ProtocolStreamError),
+}
+
+/// Per-connection handler for goodbye protocol.
+pub struct GoodbyeHandler {
+    /// Whether the fork gating goodbye was active when the connection opened.
+    enabled: bool,
+    /// Goodbye waiting for an outbound stream.
+    pending: Option<Goodbye>,
+    /// An outbound stream has been requested and not yet resolved.
+    sending: bool,
+    /// A goodbye was already sent; later commands are ignored.
+    sent: bool,
+    /// The remote already said goodbye; later ones are dropped.
+    received: bool,
+    events: VecDeque<GoodbyeHandlerEvent>,
+}
+
+impl GoodbyeHandler {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            pending: None,
+            sending: false,
+            sent: false,
+            received: false,
+            events: VecDeque::new(),
+        }
+    }
+}
+
+impl ConnectionHandler for GoodbyeHandler {
+    type FromBehaviour = GoodbyeCommand;
+    type ToBehaviour = GoodbyeHandlerEvent;
+    type InboundProtocol = Either<GoodbyeInboundProtocol, DeniedUpgrade>;
+    type OutboundProtocol = GoodbyeOutboundProtocol;
+    type InboundOpenInfo = ();
+    type OutboundOpenInfo = ();
+
+    fn listen_protocol(&self) -> SubstreamProtocol<Self::InboundProtocol, Self::InboundOpenInfo> {
+        let upgrade = if self.enabled {
+            Either::Left(Inbound::new(GoodbyeInner))
+        } else {
+            Either::Right(DeniedUpgrade)
+        };
+        SubstreamProtocol::new(upgrade, ()).with_timeout(GOODBYE_TIMEOUT)
+    }
+
+    fn connection_keep_alive(&self) -> bool {
+        self.pending.is_some() || self.sending
+    }
+
+    fn poll(
+        &mut self,
+        _cx: &mut Context<'_>,
+    ) -> Poll<
+        ConnectionHandlerEvent<Self::OutboundProtocol, Self::OutboundOpenInfo, Self::ToBehaviour>,
+    > {
+        if let Some(event) = self.events.pop_front() {
+            return Poll::Ready(ConnectionHandlerEvent::NotifyBehaviour(event));
+        }
+
+        if let Some(goodbye) = self.pending.take() {
+            self.sending = true;
+            let protocol = Outbound::new(GoodbyeOutboundInner::new(goodbye));
+            return Poll::Ready(ConnectionHandlerEvent::OutboundSubstreamRequest {
+                protocol: SubstreamProtocol::new(protocol, ()).with_timeout(GOODBYE_TIMEOUT),
+            });
+        }
+
+        Poll::Pending
+    }
+
+    fn on_behaviour_event(&mut self, event: Self::FromBehaviour) {
+        match event {
+            GoodbyeCommand::Send(goodbye) => {
+                if !self.enabled || self.sent {
+                    return;
+                }
+                self.sent = true;
+                self.pending = Some(goodbye);
+            }
+        }
+    }
+
+    fn on_connection_event(
+        &mut self,
+        event: ConnectionEvent<
+            Self::InboundProtocol,
+            Self::OutboundProtocol,
+            Self::InboundOpenInfo,
+            Self::OutboundOpenInfo,
+        >,
+    ) {
+        match event {
+            ConnectionEvent::FullyNegotiatedInbound(FullyNegotiatedInbound {
+                protocol: future::Either::Left(goodbye),
+                ..
+            }) => {
+                if self.received {
+                    return;
+                }
+                self.received = true;
+                self.events
+                    .push_back(GoodbyeHandlerEvent::Received(goodbye));
+            }
+
+            ConnectionEvent::FullyNegotiatedOutbound(FullyNegotiatedOutbound { .. }) => {
+                self.sending = false;
+                self.events.push_back(GoodbyeHandlerEvent::Sent);
+            }
+
+            ConnectionEvent::FullyNegotiatedInbound(FullyNegotiatedInbound {
+                protocol: future::Either::Right(never),
+                ..
+            }) => match never {},
+
+            ConnectionEvent::ListenUpgradeError(ListenUpgradeError { error, .. }) => {
+                let error = match error {
+                    Either::Left(error) => error,
+                    Either::Right(never) => match never {},
+                };
+                let error = UpgradeError::record_and_convert(error, "goodbye", direction::INBOUND);
+                debug!(%error, "Goodbye inbound stream error");
+            }
+
+            ConnectionEvent::DialUpgradeError(DialUpgradeError { error, .. }) => {
+                self.sending = false;
+                let error = UpgradeError::record_and_convert(error, "goodbye", direction::OUTBOUND);
+                self.events.push_back(GoodbyeHandlerEvent::Failed(error));
+            }
+
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,38 @@
+//! Goodbye: tell a peer why we are closing its connection.
+//!
+//! Before a locally-initiated close the node opens one outbound stream carrying
+//! a [`Goodbye`] with the [`DisconnectReason`] wire code, then closes every
+//! connection to the peer once the write completes or fails. The receiver logs
+//! the reason; it takes no action, since the connection is about to go anyway.
+//!
+//! The protocol is vertex's own: it lives under the `/vertex` namespace rather
+//! than `/swarm`, and is spoken only from [`SwarmHardfork::Accord`] on. Before
+//! the fork a connection neither offers nor sends it, so a close is a bare
+//! close. A peer that does not speak the protocol fails negotiation and is
+//! closed straight away, so the message never delays a close by more than
+//! [`GOODBYE_TIMEOUT`]. The frames are pinned by the vectors in
+//! `tests/wire_conformance.rs`.
+//!
+//! [`DisconnectReason`]: vertex_swarm_api::DisconnectReason
+//! [`SwarmHardfork::Accord`]: vertex_swarm_forks::SwarmHardfork::Accord
+
+use std::time::Duration;
+
+mod behaviour;
+mod codec;
+mod error;
+mod handler;
+pub mod metrics;
+mod protocol;
+
+pub use behaviour::{GoodbyeBehaviour, GoodbyeEvent};
+pub use codec::{Goodbye, GoodbyeCodec};
+pub use error::GoodbyeError;
+pub use handler::{GoodbyeCommand, GoodbyeHandler, GoodbyeHandlerEvent};
+
+/// Protocol name for goodbye. Outside the `/swarm` namespace, so a network's
+/// protocol prefix leaves it unchanged.
+pub const PROTOCOL_NAME: &str = "/vertex/goodbye/1.0.0/goodbye";
+
+/// Upper bound on negotiating and writing a goodbye before the close proceeds.
+pub const GOODBYE_TIMEOUT: Duration = Duration::from_secs(2);
@@ -0,0 +1,21 @@
+//! Goodbye-specific metrics.
+//!
+//! Exchange-level metrics (exchanges_total, outcomes, duration) are emitted
+//! by the headers crate's `ProtocolMetrics`.
+
+/// Counter of goodbyes written before a local close.
+///
+/// Labels:
+/// - `reason` - the [`DisconnectReason`] label, e.g. `allowance_exceeded`.
+///
+/// [`DisconnectReason`]: vertex_swarm_api::DisconnectReason
+pub const GOODBYE_SENT_TOTAL: &str = "goodbye_sent_total";
+
+/// Counter of goodbyes received from peers.
+///
+/// Labels:
+/// - `reason` - the [`DisconnectReason`] label, or `unknown` for a code this
+///   node does not know.
+///
+/// [`DisconnectReason`]: vertex_swarm_api::DisconnectReason
+pub const GOODBYE_RECEIVED_TOTAL: &str = "goodbye_received_total";
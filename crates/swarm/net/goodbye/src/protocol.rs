@@ -0,0 +1,85 @@
+//! Protocol upgrade for goodbye.
+//!
+//! Implements HeaderedInbound/HeaderedOutbound traits - headers are automatic.
+
+use asynchronous_codec::Framed;
+use futures::{SinkExt, TryStreamExt, future::BoxFuture};
+use tracing::debug;
+use vertex_swarm_net_headers::{
+    HeaderedInbound, HeaderedOutbound, HeaderedStream, Inbound, Outbound,
+};
+
+use crate::{
+    PROTOCOL_NAME,
+    codec::{Goodbye, GoodbyeCodec},
+    error::GoodbyeError,
+};
+
+/// Maximum size of a goodbye message.
+const MAX_MESSAGE_SIZE: usize = 64;
+
+/// Goodbye inbound: receives the remote's close reason.
+#[derive(Debug, Clone, Default)]
+pub struct GoodbyeInner;
+
+impl HeaderedInbound for GoodbyeInner {
+    type Output = Goodbye;
+    type Error = GoodbyeError;
+
+    fn protocol_name(&self) -> &'static str {
+        PROTOCOL_NAME
+    }
+
+    fn read(self, stream: HeaderedStream) -> BoxFuture<'static, Result<Self::Output, Self::Error>> {
+        Box::pin(async move {
+            let codec = GoodbyeCodec::new(MAX_MESSAGE_SIZE);
+            let mut framed = Framed::new(stream.into_inner(), codec);
+
+            debug!("Goodbye: Reading close reason");
+            framed
+                .try_next()
+                .await?
+                .ok_or(GoodbyeError::ConnectionClosed)
+        })
+    }
+}
+
+/// Goodbye outbound: sends our close reason.
+#[derive(Debug, Clone)]
+pub struct GoodbyeOutboundInner {
+    goodbye: Goodbye,
+}
+
+impl GoodbyeOutboundInner {
+    pub fn new(goodbye: Goodbye) -> Self {
+        Self { goodbye }
+    }
+}
+
+impl HeaderedOutbound for GoodbyeOutboundInner {
+    type Output = ();
+    type Error = GoodbyeError;
+
+    fn protocol_name(&self) -> &'static str {
+        PROTOCOL_NAME
+    }
+
+    fn write(
+        self,
+        stream: HeaderedStream,
+    ) -> BoxFuture<'static, Result<Self::Output, Self::Error>> {
+        Box::pin(async move {
+            let codec = GoodbyeCodec::new(MAX_MESSAGE_SIZE);
+            let mut framed = Framed::new(stream.into_inner(), codec);
+
+            debug!(code = self.goodbye.code, "Goodbye: Sending close reason");
+            framed.send(self.goodbye).await?;
+            framed.close().await?;
+            Ok(())
+        })
+    }
+}
+
+// Type aliases for handler
+pub type GoodbyeInboundProtocol = Inbound<GoodbyeInner>;
+pub type GoodbyeOutboundProtocol = Outbound<GoodbyeOutboundInner>;
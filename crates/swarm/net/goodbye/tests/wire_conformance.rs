@@ -0,0 +1,62 @@
+//! Wire-conformance vectors for the goodbye frame.
+//!
+//! `/vertex/goodbye/1.0.0/goodbye` carries one length-delimited protobuf frame
+//! over a headered stream: `Goodbye { uint32 reason = 1 }`, where `reason` is
+//! the `DisconnectReason` wire code. The framing is an unsigned-varint length
+//! prefix followed by the protobuf message. The expected bytes are written out
+//! by hand, so a change to the field number, its wire type, or the framing
+//! fails the assertion rather than quietly moving the vector.
+#![allow(clippy::unwrap_used)]
+
+use asynchronous_codec::{Decoder, Encoder};
+use bytes::BytesMut;
+use vertex_swarm_api::DisconnectReason;
+use vertex_swarm_net_goodbye::{Goodbye, GoodbyeCodec};
+
+fn encode(goodbye: Goodbye) -> Vec<u8> {
+    let mut codec = GoodbyeCodec::new(64);
+    let mut buf = BytesMut::new();
+    codec.encode(goodbye, &mut buf).unwrap();
+    buf.to_vec()
+}
+
+fn decode(wire: &[u8]) -> Goodbye {
+    let mut codec = GoodbyeCodec::new(64);
+    let mut buf = BytesMut::from(wire);
+    codec.decode(&mut buf).unwrap().unwrap()
+}
+
+#[test]
+fn allowance_exceeded_frame_matches_pinned_bytes() {
+    // Length 2, then field 1 varint (0x08) with code 7.
+    let expected = [0x02, 0x08, 0x07];
+    assert_eq!(
+        encode(Goodbye::new(DisconnectReason::AllowanceExceeded)),
+        expected
+    );
+    assert_eq!(
+        decode(&expected).reason(),
+        Some(DisconnectReason::AllowanceExceeded)
+    );
+}
+
+#[test]
+fn zero_code_frame_is_empty_body() {
+    // proto3 omits a zero scalar, so code 0 is an empty message.
+    let expected = [0x00];
+    assert_eq!(encode(Goodbye::new(DisconnectReason::BinTrimmed)), expected);
+    assert_eq!(
+        decode(&expected).reason(),
+        Some(DisconnectReason::BinTrimmed)
+    );
+}
+
+#[test]
+fn multi_byte_code_decodes_verbatim() {
+    // Code 300 as a two-byte varint (0xac 0x02): unknown here, kept as sent.
+    let wire = [0x03, 0x08, 0xac, 0x02];
+    let goodbye = decode(&wire);
+    assert_eq!(goodbye.code, 300);
+    assert_eq!(goodbye.reason(), None);
+    assert_eq!(encode(goodbye), wire);
+}
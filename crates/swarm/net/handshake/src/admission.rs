@@ -47,6 +47,9 @@ pub enum AdmissionRejection {
     /// Neighborhood is oversaturated and this peer falls outside the
     /// configured headroom.
     OversaturatedNeighborhood,
+    /// The total connection limit is reached and the peer fills no gap
+    /// bin worth displacing another peer for.
+    ConnectionLimit,
 }
 
 /// Result of evaluating a peer for handshake admission.
@@ -1,7 +1,7 @@
 //! NetworkBehaviour for handshake protocol.
 
 use std::{
-    collections::VecDeque,
+    collections::{HashMap, VecDeque},
     sync::Arc,
     task::{Context, Poll},
 };
@@ -15,15 +15,22 @@ use libp2p::{
 };
 use parking_lot::RwLock;
 use tracing::debug;
+use vertex_net_codec::Compression;
+use vertex_net_local::{AddressScope, classify_multiaddr};
 use vertex_swarm_api::SwarmIdentity;
 use vertex_swarm_peer::{SwarmPeer, Timestamp};
+use vertex_swarm_primitives::PeerCapabilities;
+use vertex_util_runtime::time::{Duration, Instant};
 
 use vertex_net_peer_registry::ConnectionDirection;
 
 use crate::{
-    AddressProvider, HandshakeError, HandshakeInfo, SharedAdmissionControl,
+    AddressProvider, HandshakeError, HandshakeInfo, HandshakeLimiter, HandshakeSlots,
+    SharedAdmissionControl,
     admission::default_admission_control,
-    cache::{CachedSelfRecord, SELF_RECORD_REFRESH_INTERVAL, fingerprint, needs_resign},
+    cache::{
+        CachedSelfRecord, SELF_RECORD_REFRESH_INTERVAL, change_settling, fingerprint, needs_resign,
+    },
     handler::{HandshakeCommand, HandshakeConfig, HandshakeHandler, HandshakeHandlerEvent},
 };
 
@@ -59,8 +66,9 @@ pub struct HandshakeBehaviour<I, A> {
     connection_directions: std::collections::HashMap<ConnectionId, ConnectionDirection>,
     /// Self record signed once per address-set change (plus a periodic
     /// refresh), reused byte-identically across handshakes with an unchanged
-    /// advertised set. See [`crate::cache`].
-    cached_record: RwLock<Option<CachedSelfRecord>>,
+    /// advertised set. Keyed by the remote's scope, since the advertised set
+    /// is scope-filtered. See [`crate::cache`].
+    cached_records: RwLock<HashMap<AddressScope, CachedSelfRecord>>,
 }
 
 impl<I, A> HandshakeBehaviour<I, A>
@@ -77,7 +85,7 @@ where
             admission_control: default_admission_control(),
             events: VecDeque::new(),
             connection_directions: std::collections::HashMap::new(),
-            cached_record: RwLock::new(None),
+            cached_records: RwLock::new(HashMap::new()),
         }
     }
 
@@ -85,7 +93,9 @@ where
     /// `remote_addr`, reusing the cache when the advertised address set is
     /// unchanged and fresh.
     ///
-    /// Resolves the scope-filtered, ordered advertised set for the peer. An
+    /// Resolves the scope-filtered, ordered advertised set for the peer. Each
+    /// remote scope has its own cache slot, so a record built for a LAN peer is
+    /// never served to a public one. An
     /// empty set yields `None`: the protocol then signs a last-resort record
     /// over just the peer-observed address during the exchange. A non-empty set
     /// is fingerprinted; if the fingerprint matches a cached record still inside
@@ -93,17 +103,28 @@ where
     /// timestamp, same signature), otherwise the record is re-signed with a
     /// current timestamp and cached. Concurrent misses single-flight under the
     /// write lock.
+    ///
+    /// A changed set is held back until it has settled for the configured
+    /// announce debounce; the previous record is served meanwhile.
     fn cached_self_record(&self, remote_addr: &Multiaddr) -> Option<SwarmPeer> {
+        self.cached_self_record_at(remote_addr, Instant::now())
+    }
+
+    fn cached_self_record_at(&self, remote_addr: &Multiaddr, at: Instant) -> Option<SwarmPeer> {
         let addrs = self.address_provider.addresses_for_peer(remote_addr);
         if addrs.is_empty() {
             return None;
         }
 
+        // Mirror the provider, which treats an unclassifiable peer as public.
+        let scope = classify_multiaddr(remote_addr).unwrap_or(AddressScope::Public);
         let fp = fingerprint(&addrs);
         let now = Timestamp::now();
 
-        // Fast path: a fresh cache hit needs only a read lock.
-        if let Some(cached) = self.cached_record.read().as_ref()
+        // Fast path: a fresh cache hit with no pending change needs only a
+        // read lock.
+        if let Some(cached) = self.cached_records.read().get(&scope)
+            && cached.changed_at.is_none()
             && !needs_resign(Some(cached), fp, now, SELF_RECORD_REFRESH_INTERVAL)
         {
             return Some(cached.record.clone());
@@ -111,21 +132,29 @@ where
 
         // Slow path: re-sign under the write lock, double-checking so a
         // concurrent miss that already signed is not duplicated.
-        let mut guard = self.cached_record.write();
-        if !needs_resign(guard.as_ref(), fp, now, SELF_RECORD_REFRESH_INTERVAL) {
-            // Safe: `needs_resign` returns false only when the cache is present.
-            if let Some(cached) = guard.as_ref() {
+        let mut guard = self.cached_records.write();
+        if let Some(cached) = guard.get_mut(&scope) {
+            if !needs_resign(Some(cached), fp, now, SELF_RECORD_REFRESH_INTERVAL) {
+                // The set flapped back to the signed one: the change is over.
+                cached.changed_at = None;
+                return Some(cached.record.clone());
+            }
+            if change_settling(cached, fp, at, self.config.announce_debounce) {
                 return Some(cached.record.clone());
             }
         }
 
         match self.sign_self_record(addrs, now) {
             Ok(record) => {
-                *guard = Some(CachedSelfRecord {
-                    fingerprint: fp,
-                    signed_at: now,
-                    record: record.clone(),
-                });
+                guard.insert(
+                    scope,
+                    CachedSelfRecord {
+                        fingerprint: fp,
+                        signed_at: now,
+                        record: record.clone(),
+                        changed_at: None,
+                    },
+                );
                 Some(record)
             }
             Err(error) => {
@@ -155,6 +184,43 @@ where
         self
     }
 
+    /// Cap the handshakes in progress at once across every connection,
+    /// inbound and outbound. Past the cap a new handshake waits for a slot;
+    /// the default is
+    /// [`DEFAULT_MAX_CONCURRENT_HANDSHAKES`](crate::DEFAULT_MAX_CONCURRENT_HANDSHAKES).
+    pub fn with_max_concurrent_handshakes(mut self, limit: usize) -> Self {
+        Arc::make_mut(&mut self.config).limiter = HandshakeLimiter::new(limit);
+        self
+    }
+
+    /// Wait `debounce` after the advertised address set changes before
+    /// re-signing the self record, so a burst of listen-address changes costs
+    /// one signature. The default is
+    /// [`DEFAULT_ANNOUNCE_DEBOUNCE`](crate::DEFAULT_ANNOUNCE_DEBOUNCE); zero
+    /// re-signs on every change.
+    pub fn with_announce_debounce(mut self, debounce: Duration) -> Self {
+        Arc::make_mut(&mut self.config).announce_debounce = debounce;
+        self
+    }
+
+    /// Advertise `capabilities` to peers once Accord is active. Features this
+    /// build cannot provide are dropped, so a wasm node never offers
+    /// compression.
+    pub fn with_capabilities(mut self, capabilities: PeerCapabilities) -> Self {
+        let capabilities = if Compression::SUPPORTED {
+            capabilities
+        } else {
+            capabilities.without(PeerCapabilities::COMPRESSION)
+        };
+        Arc::make_mut(&mut self.config).capabilities = capabilities;
+        self
+    }
+
+    /// Current handshake slot usage.
+    pub fn handshake_slots(&self) -> HandshakeSlots {
+        self.config.limiter.slots()
+    }
+
     /// Install an admission control gate, replacing any previously
     /// installed gate (the default is [`AlwaysAccept`](crate::AlwaysAccept)).
     ///
@@ -310,6 +376,50 @@ mod tests {
         }
     }
 
+    /// Address provider whose advertised set the test swaps, standing in for
+    /// listen addresses coming and going.
+    #[derive(Default)]
+    struct FlappingAddresses {
+        addrs: parking_lot::Mutex<Vec<Multiaddr>>,
+    }
+
+    impl FlappingAddresses {
+        fn set(&self, addrs: Vec<Multiaddr>) {
+            *self.addrs.lock() = addrs;
+        }
+    }
+
+    impl AddressProvider for FlappingAddresses {
+        fn addresses_for_peer(&self, _peer_addr: &Multiaddr) -> Vec<Multiaddr> {
+            self.addrs.lock().clone()
+        }
+
+        fn local_peer_id(&self) -> Option<&PeerId> {
+            None
+        }
+    }
+
+    /// Address provider advertising a LAN address only to private peers, the
+    /// way the real provider scope-filters its set.
+    struct ScopedAddresses {
+        lan: Multiaddr,
+        public: parking_lot::Mutex<Vec<Multiaddr>>,
+    }
+
+    impl AddressProvider for ScopedAddresses {
+        fn addresses_for_peer(&self, peer_addr: &Multiaddr) -> Vec<Multiaddr> {
+            let mut addrs = self.public.lock().clone();
+            if classify_multiaddr(peer_addr) == Some(AddressScope::Private) {
+                addrs.push(self.lan.clone());
+            }
+            addrs
+        }
+
+        fn local_peer_id(&self) -> Option<&PeerId> {
+            None
+        }
+    }
+
     fn addr(s: &str) -> Multiaddr {
         s.parse().expect("valid multiaddr")
     }
@@ -380,6 +490,103 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rapid_address_changes_resign_once_after_debounce() {
+        let remote = addr("/ip4/198.51.100.4/tcp/1634");
+        let provider = Arc::new(FlappingAddresses::default());
+        provider.set(vec![addr("/ip4/8.8.4.4/tcp/1634")]);
+        let behaviour = HandshakeBehaviour::new(test_identity_arc(), provider.clone(), "test")
+            .with_announce_debounce(Duration::from_secs(2));
+
+        let start = Instant::now();
+        let original = behaviour
+            .cached_self_record_at(&remote, start)
+            .expect("non-empty set signs a record");
+
+        // A burst of changes inside the window keeps serving the original.
+        for (ms, a) in [
+            (100, "/ip4/1.1.1.1/tcp/1634"),
+            (300, "/ip4/1.0.0.1/tcp/1634"),
+            (600, "/ip4/9.9.9.9/tcp/1634"),
+        ] {
+            provider.set(vec![addr(a)]);
+            let served = behaviour
+                .cached_self_record_at(&remote, start + Duration::from_millis(ms))
+                .expect("cached record is served");
+            assert_eq!(served, original, "no re-sign inside the debounce window");
+        }
+
+        // Past the window the settled set is signed once.
+        let after = start + Duration::from_millis(2_100);
+        let resigned = behaviour
+            .cached_self_record_at(&remote, after)
+            .expect("settled set signs a record");
+        assert_ne!(resigned, original);
+        assert_eq!(resigned.multiaddrs(), &[addr("/ip4/9.9.9.9/tcp/1634")][..]);
+
+        let again = behaviour
+            .cached_self_record_at(&remote, after + Duration::from_millis(100))
+            .expect("cached record is reused");
+        assert_eq!(again, resigned, "the settled set is signed only once");
+    }
+
+    #[test]
+    fn public_peer_never_gets_a_lan_record() {
+        let lan_peer = addr("/ip4/192.168.1.20/tcp/1634");
+        let public_peer = addr("/ip4/198.51.100.4/tcp/1634");
+        let behaviour = HandshakeBehaviour::new(
+            test_identity_arc(),
+            Arc::new(ScopedAddresses {
+                lan: addr("/ip4/192.168.1.10/tcp/1634"),
+                public: parking_lot::Mutex::new(vec![addr("/ip4/8.8.4.4/tcp/1634")]),
+            }),
+            "test",
+        )
+        .with_announce_debounce(Duration::from_secs(2));
+
+        let start = Instant::now();
+        let lan = behaviour
+            .cached_self_record_at(&lan_peer, start)
+            .expect("non-empty set signs a record");
+        // Inside the debounce window of the LAN slot, a public peer still gets
+        // its own record, without the LAN address.
+        let public = behaviour
+            .cached_self_record_at(&public_peer, start + Duration::from_millis(100))
+            .expect("non-empty set signs a record");
+
+        assert_eq!(lan.multiaddrs().len(), 2);
+        assert_eq!(public.multiaddrs(), &[addr("/ip4/8.8.4.4/tcp/1634")][..]);
+    }
+
+    #[test]
+    fn reverted_change_clears_the_pending_debounce() {
+        let remote = addr("/ip4/198.51.100.4/tcp/1634");
+        let provider = Arc::new(FlappingAddresses::default());
+        let original_set = vec![addr("/ip4/8.8.4.4/tcp/1634")];
+        provider.set(original_set.clone());
+        let behaviour = HandshakeBehaviour::new(test_identity_arc(), provider.clone(), "test")
+            .with_announce_debounce(Duration::from_secs(2));
+
+        let start = Instant::now();
+        let original = behaviour
+            .cached_self_record_at(&remote, start)
+            .expect("non-empty set signs a record");
+
+        // A bounce that comes back inside the window leaves no pending change.
+        provider.set(vec![addr("/ip4/1.1.1.1/tcp/1634")]);
+        behaviour.cached_self_record_at(&remote, start + Duration::from_millis(100));
+        provider.set(original_set);
+        behaviour.cached_self_record_at(&remote, start + Duration::from_millis(200));
+
+        // A later change starts a fresh window instead of re-signing at once.
+        let later = start + Duration::from_secs(10);
+        provider.set(vec![addr("/ip4/9.9.9.9/tcp/1634")]);
+        let served = behaviour
+            .cached_self_record_at(&remote, later)
+            .expect("cached record is served");
+        assert_eq!(served, original, "a new change is debounced from scratch");
+    }
+
     #[test]
     fn empty_address_set_yields_no_cached_record() {
         // An empty advertised set defers to the protocol's last-resort sign.
@@ -387,4 +594,49 @@ mod tests {
         let behaviour = behaviour(Vec::new());
         assert!(behaviour.cached_self_record(&remote).is_none());
     }
+
+    /// Protocol names the listen side of a fresh inbound handler advertises.
+    fn advertised<I: SwarmIdentity + 'static>(
+        behaviour: &mut HandshakeBehaviour<I, StubAddresses>,
+    ) -> Vec<&'static str> {
+        use libp2p::core::UpgradeInfo;
+        use libp2p::swarm::ConnectionHandler;
+
+        let handler = behaviour
+            .handle_established_inbound_connection(
+                ConnectionId::new_unchecked(0),
+                PeerId::random(),
+                &addr("/ip4/127.0.0.1/tcp/1634"),
+                &addr("/ip4/198.51.100.4/tcp/1634"),
+            )
+            .expect("handler is created");
+        handler
+            .listen_protocol()
+            .upgrade()
+            .protocol_info()
+            .collect()
+    }
+
+    #[test]
+    fn protocol_name_follows_spec_prefix() {
+        use vertex_swarm_api::SwarmNodeType;
+        use vertex_swarm_identity::Identity;
+        use vertex_swarm_spec::SpecBuilder;
+
+        let mut reference = behaviour(Vec::new());
+        let custom_spec = Arc::new(SpecBuilder::testnet().protocol_prefix("/myswarm").build());
+        let mut custom = HandshakeBehaviour::new(
+            Arc::new(Identity::random(custom_spec, SwarmNodeType::Storer)),
+            Arc::new(StubAddresses { addrs: Vec::new() }),
+            "test",
+        );
+
+        let reference = advertised(&mut reference);
+        let custom = advertised(&mut custom);
+        assert_eq!(reference, vec![crate::PROTOCOL]);
+        assert_eq!(custom, vec!["/myswarm/handshake/15.0.0/handshake"]);
+
+        // Multistream-select needs one name in common; there is none.
+        assert!(custom.iter().all(|name| !reference.contains(name)));
+    }
 }
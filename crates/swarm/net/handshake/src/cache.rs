@@ -7,11 +7,18 @@
 //! otherwise-identical record churns the network for no semantic gain.
 //!
 //! This module caches the signed record keyed by a fingerprint of the address
-//! set. While the set is unchanged the same byte-identical record (same
+//! set, one record per remote address scope (the advertised set is filtered by
+//! the remote's scope). While the set is unchanged the same byte-identical record (same
 //! timestamp, same signature) is reused across handshakes. The record is
 //! re-signed only when the set changes or when [`SELF_RECORD_REFRESH_INTERVAL`]
 //! elapses, so the cached timestamp means "when the advertised set last changed
 //! or was last refreshed", not "now at each handshake".
+//!
+//! Listen addresses flap (an interface bouncing emits a burst of
+//! `NewListenAddr`/`ExpiredListenAddr`), so a changed set is not signed at once.
+//! The cached record keeps being served until [`DEFAULT_ANNOUNCE_DEBOUNCE`] has
+//! passed since the first change, then one record is signed over whatever the
+//! set has settled to.
 
 use std::{
     hash::{Hash, Hasher},
@@ -20,6 +27,13 @@ use std::{
 
 use libp2p::Multiaddr;
 use vertex_swarm_peer::{SwarmPeer, Timestamp};
+use vertex_util_runtime::time::Instant;
+
+/// How long a changed address set waits before the self record is re-signed.
+///
+/// Long enough to absorb an interface bounce, short enough that a genuinely
+/// new address is advertised within a couple of handshakes.
+pub const DEFAULT_ANNOUNCE_DEBOUNCE: Duration = Duration::from_secs(2);
 
 /// How often a stable node re-signs its self record to advance the timestamp.
 ///
@@ -41,6 +55,9 @@ pub(crate) struct CachedSelfRecord {
     pub(crate) signed_at: Timestamp,
     /// The signed record, reused byte-identically while the set is unchanged.
     pub(crate) record: SwarmPeer,
+    /// When a set differing from `fingerprint` was first seen since this
+    /// record was signed, or `None` while the set is unchanged.
+    pub(crate) changed_at: Option<Instant>,
 }
 
 /// Fingerprint an already-ordered address set.
@@ -76,6 +93,24 @@ pub(crate) fn needs_resign(
     elapsed >= refresh_secs
 }
 
+/// Whether a changed address set is still settling, so the cached record should
+/// be served rather than re-signed.
+///
+/// Marks the change on first sight. The window runs from that first change, not
+/// the latest, so a set that keeps flapping is still signed once per window.
+pub(crate) fn change_settling(
+    cached: &mut CachedSelfRecord,
+    fingerprint: u64,
+    now: Instant,
+    debounce: Duration,
+) -> bool {
+    if cached.fingerprint == fingerprint {
+        return false;
+    }
+    let since = *cached.changed_at.get_or_insert(now);
+    now.saturating_duration_since(since) < debounce
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,6 +135,7 @@ mod tests {
             fingerprint,
             signed_at: Timestamp::from_seconds(signed_at),
             record,
+            changed_at: None,
         }
     }
 
@@ -169,4 +205,27 @@ mod tests {
             "the same ordered set produces the same fingerprint"
         );
     }
+
+    #[test]
+    fn change_settles_from_first_sight() {
+        let debounce = Duration::from_secs(2);
+        let mut cached = record_at(42, 1_000);
+        let start = Instant::now();
+
+        assert!(!change_settling(&mut cached, 42, start, debounce));
+        assert!(change_settling(&mut cached, 43, start, debounce));
+        // A further change inside the window does not restart it.
+        assert!(change_settling(
+            &mut cached,
+            44,
+            start + Duration::from_secs(1),
+            debounce
+        ));
+        assert!(!change_settling(
+            &mut cached,
+            45,
+            start + debounce,
+            debounce
+        ));
+    }
 }
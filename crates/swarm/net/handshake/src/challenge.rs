@@ -0,0 +1,81 @@
+//! Dialer freshness challenge (Accord).
+//!
+//! The responder's signed record is peer-independent and cached across
+//! handshakes, so on its own it proves nothing about freshness: a captured
+//! SYNACK can be replayed to any dialer and passes identity verification. From
+//! [`SwarmHardfork::Accord`] on, the dialer puts a random [`Challenge`] in its
+//! SYN and the responder answers with an EIP-191 signature over it in the ack
+//! half of the SYNACK. The dialer requires that signature to recover the same
+//! key that signed the record, so a replayed ack, which answers an old
+//! challenge, is rejected.
+//!
+//! Both fields use new high field numbers that no deployed version assigns.
+//! A pre-Accord node never sets them and ignores them when received, so the
+//! pre-Accord wire is unchanged.
+
+use alloy_primitives::Signature;
+use alloy_signer::SignerSync;
+use vertex_swarm_peer::SwarmPeer;
+use vertex_swarm_spec::{SwarmHardfork, SwarmSpec};
+use vertex_util_runtime::time::now_unix_secs;
+
+use crate::HandshakeError;
+
+/// Domain separator so a challenge signature cannot double as any other
+/// EIP-191 message this key signs.
+const CHALLENGE_DOMAIN: &[u8] = b"swarm-handshake-challenge";
+
+/// Length of a challenge in bytes.
+pub(crate) const CHALLENGE_LEN: usize = 32;
+
+/// Whether the challenge-response step is active for `spec` right now.
+pub(crate) fn challenge_active<S: SwarmSpec>(spec: &S) -> bool {
+    spec.is_fork_active_at_timestamp(SwarmHardfork::Accord, now_unix_secs())
+}
+
+/// Random per-handshake value the responder must sign.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Challenge([u8; CHALLENGE_LEN]);
+
+impl Challenge {
+    /// Draw a fresh challenge from the OS CSPRNG.
+    pub(crate) fn random() -> Self {
+        let mut bytes = [0u8; CHALLENGE_LEN];
+        vertex_util_runtime::rand::fill_bytes(&mut bytes);
+        Self(bytes)
+    }
+
+    /// Parse the wire field. Empty means the peer sent no challenge.
+    pub(crate) fn from_wire(bytes: &[u8]) -> Result<Option<Self>, HandshakeError> {
+        if bytes.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(Self(bytes.try_into()?)))
+    }
+
+    pub(crate) fn as_bytes(&self) -> &[u8; CHALLENGE_LEN] {
+        &self.0
+    }
+
+    fn sign_data(&self) -> Vec<u8> {
+        [CHALLENGE_DOMAIN, &self.0].concat()
+    }
+
+    /// Sign the challenge with the responder's key.
+    pub(crate) fn respond(&self, signer: &impl SignerSync) -> Result<Signature, HandshakeError> {
+        Ok(signer.sign_message_sync(&self.sign_data())?)
+    }
+
+    /// Check that `response` signs this challenge with the key behind `peer`.
+    pub(crate) fn verify(&self, response: &[u8], peer: &SwarmPeer) -> Result<(), HandshakeError> {
+        if response.is_empty() {
+            return Err(HandshakeError::MissingField("challenge_response"));
+        }
+        let signature = Signature::try_from(response)?;
+        let signer = signature.recover_address_from_msg(self.sign_data())?;
+        if &signer != peer.ethereum_address() {
+            return Err(HandshakeError::ChallengeMismatch);
+        }
+        Ok(())
+    }
+}
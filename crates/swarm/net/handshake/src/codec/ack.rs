@@ -34,6 +34,8 @@ pub(crate) fn encode_ack(
         network_id: network_id.get(),
         storer: node_type_to_wire(node_type),
         welcome_message: welcome_message.to_string(),
+        challenge_response: Vec::new(),
+        capabilities: 0,
     }
 }
 
@@ -142,6 +144,78 @@ mod tests {
         assert_eq!(welcome, decoded_welcome);
     }
 
+    /// A promoted client announces itself as a storer under its old overlay,
+    /// so peers start syncing to it and counting it as storage.
+    #[test]
+    fn test_promoted_node_announces_storer() {
+        use alloy_signer_local::LocalSigner;
+        use vertex_swarm_api::SwarmIdentity;
+        use vertex_swarm_primitives::Nonce;
+
+        let spec = test_spec();
+        let client = Identity::new(
+            LocalSigner::random(),
+            Nonce::new([7u8; 32]),
+            spec.clone(),
+            SwarmNodeType::Client,
+        );
+        let storer = client.promote_to_storer().expect("persistent client");
+        let multiaddr: Multiaddr = "/ip4/127.0.0.1/tcp/1234".parse().unwrap();
+        let peer = SwarmPeer::sign(&storer, vec![multiaddr], Timestamp::now(), None)
+            .expect("should sign peer");
+
+        let before = encode_ack(&peer, client.node_type(), "", spec.network_id());
+        let (_, announced, _) = decode_ack(before, spec.network_id()).unwrap();
+        assert!(!announced.requires_pullsync());
+
+        let after = encode_ack(&peer, storer.node_type(), "", spec.network_id());
+        let (decoded_peer, announced, _) = decode_ack(after, spec.network_id()).unwrap();
+        assert_eq!(announced, SwarmNodeType::Storer);
+        assert!(announced.requires_pullsync());
+        assert!(announced.requires_storage());
+        assert_eq!(*decoded_peer.overlay(), client.overlay_address());
+    }
+
+    #[test]
+    fn test_capabilities_add_nothing_to_the_wire_until_advertised() {
+        use quick_protobuf::MessageWrite;
+        use vertex_swarm_primitives::PeerCapabilities;
+
+        let spec = test_spec();
+        let peer = create_test_peer();
+        let mut proto = encode_ack(&peer, SwarmNodeType::Storer, "hi", spec.network_id());
+        assert_eq!(proto.capabilities, 0);
+        let legacy_size = proto.get_size();
+
+        proto.capabilities = PeerCapabilities::COMPRESSION.bits();
+        assert!(proto.get_size() > legacy_size);
+        assert!(decode_ack(proto, spec.network_id()).is_ok());
+    }
+
+    #[test]
+    fn test_capabilities_wire_vectors() {
+        use quick_protobuf::{MessageWrite, Writer};
+        use vertex_swarm_primitives::PeerCapabilities;
+
+        fn bytes(capabilities: u64) -> Vec<u8> {
+            let proto = vertex_swarm_net_proto::handshake::Ack {
+                capabilities,
+                ..Default::default()
+            };
+            let mut out = Vec::new();
+            proto.write_message(&mut Writer::new(&mut out)).unwrap();
+            out
+        }
+
+        // Unset capabilities are omitted entirely.
+        assert!(bytes(0).is_empty());
+        // Field 101 varint (tag 0xa8 0x06) with the compression bit.
+        assert_eq!(
+            bytes(PeerCapabilities::COMPRESSION.bits()),
+            [0xa8, 0x06, 0x01]
+        );
+    }
+
     #[test]
     fn test_wire_format_storer_flag() {
         assert_eq!(node_type_from_wire(true), SwarmNodeType::Storer);
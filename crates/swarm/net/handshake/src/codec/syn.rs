@@ -4,23 +4,32 @@ use libp2p::Multiaddr;
 use vertex_swarm_peer::deserialize_multiaddrs;
 
 use crate::HandshakeError;
+use crate::challenge::Challenge;
 
-/// Decode a Syn proto message, returning the validated observed multiaddr.
+/// Decode a Syn proto message, returning the validated observed multiaddr and
+/// the dialer's challenge, if it sent one.
 pub(crate) fn decode_syn(
     proto: vertex_swarm_net_proto::handshake::Syn,
-) -> Result<Multiaddr, HandshakeError> {
+) -> Result<(Multiaddr, Option<Challenge>), HandshakeError> {
     let multiaddrs = deserialize_multiaddrs(&proto.observed_multiaddr)?;
 
-    multiaddrs
+    let observed = multiaddrs
         .into_iter()
         .next()
-        .ok_or(HandshakeError::MissingField("observed_multiaddr"))
+        .ok_or(HandshakeError::MissingField("observed_multiaddr"))?;
+    let challenge = Challenge::from_wire(&proto.challenge)?;
+
+    Ok((observed, challenge))
 }
 
-/// Encode an observed multiaddr into a Syn proto message.
-pub(crate) fn encode_syn(observed: &Multiaddr) -> vertex_swarm_net_proto::handshake::Syn {
+/// Encode an observed multiaddr and optional challenge into a Syn proto message.
+pub(crate) fn encode_syn(
+    observed: &Multiaddr,
+    challenge: Option<&Challenge>,
+) -> vertex_swarm_net_proto::handshake::Syn {
     vertex_swarm_net_proto::handshake::Syn {
         observed_multiaddr: observed.to_vec(),
+        challenge: challenge.map(|c| c.as_bytes().to_vec()).unwrap_or_default(),
     }
 }
 
@@ -35,15 +44,35 @@ mod tests {
     #[test]
     fn test_syn_roundtrip() {
         let addr = test_multiaddr();
-        let proto = encode_syn(&addr);
-        let decoded = decode_syn(proto).unwrap();
+        let proto = encode_syn(&addr, None);
+        let (decoded, challenge) = decode_syn(proto).unwrap();
         assert_eq!(addr, decoded);
+        assert_eq!(challenge, None);
+    }
+
+    #[test]
+    fn test_syn_challenge_roundtrip() {
+        let addr = test_multiaddr();
+        let challenge = Challenge::random();
+        let (_, decoded) = decode_syn(encode_syn(&addr, Some(&challenge))).unwrap();
+        assert_eq!(decoded, Some(challenge));
+    }
+
+    #[test]
+    fn test_syn_rejects_short_challenge() {
+        let mut proto = encode_syn(&test_multiaddr(), None);
+        proto.challenge = vec![0xAA; 16];
+        assert!(matches!(
+            decode_syn(proto),
+            Err(HandshakeError::InvalidData(_))
+        ));
     }
 
     #[test]
     fn test_syn_rejects_malformed_multiaddr() {
         let proto = vertex_swarm_net_proto::handshake::Syn {
             observed_multiaddr: vec![0x01, 0x02, 0x03],
+            challenge: vec![],
         };
         let result = decode_syn(proto);
         assert!(matches!(result, Err(HandshakeError::InvalidMultiaddr(_))));
@@ -53,6 +82,7 @@ mod tests {
     fn test_syn_rejects_empty_multiaddr() {
         let proto = vertex_swarm_net_proto::handshake::Syn {
             observed_multiaddr: vec![],
+            challenge: vec![],
         };
         let result = decode_syn(proto);
         assert!(matches!(
@@ -1,5 +1,6 @@
 //! SynAck message encoding/decoding for handshake protocol.
 
+use alloy_primitives::Signature;
 use libp2p::Multiaddr;
 use nectar_primitives::NetworkId;
 use vertex_swarm_peer::{SwarmNodeType, SwarmPeer};
@@ -9,36 +10,50 @@ use super::ack::{
 };
 use super::syn_msg::{decode_syn, encode_syn};
 use crate::HandshakeError;
+use crate::challenge::Challenge;
 
 /// Decode a SynAck proto message, returning validated components.
+///
+/// When `challenge` is set, the ack must carry a response signing it with the
+/// key behind the returned peer record.
 pub(crate) fn decode_synack(
     proto: vertex_swarm_net_proto::handshake::SynAck,
     expected_network_id: NetworkId,
+    challenge: Option<&Challenge>,
 ) -> Result<(Multiaddr, SwarmPeer, SwarmNodeType, String), HandshakeError> {
-    let observed = decode_syn(proto.syn.ok_or(HandshakeError::MissingField("syn"))?)?;
+    let (observed, _) = decode_syn(proto.syn.ok_or(HandshakeError::MissingField("syn"))?)?;
 
     let proto_ack = proto.ack.ok_or(HandshakeError::MissingField("ack"))?;
     if proto_ack.network_id != expected_network_id.get() {
         return Err(HandshakeError::NetworkIdMismatch);
     }
     let peer = swarm_peer_from_proto(proto_ack.address.as_ref(), expected_network_id)?;
+    if let Some(challenge) = challenge {
+        challenge.verify(&proto_ack.challenge_response, &peer)?;
+    }
     let welcome_message = welcome_message_from_proto(&proto_ack)?;
     let node_type = node_type_from_wire(proto_ack.storer);
 
     Ok((observed, peer, node_type, welcome_message))
 }
 
-/// Encode components into a SynAck proto message.
+/// Encode components into a SynAck proto message, attaching
+/// `challenge_response` to the ack when answering a dialer's challenge.
 pub(crate) fn encode_synack(
     observed: &Multiaddr,
     peer: &SwarmPeer,
     node_type: SwarmNodeType,
     welcome_message: &str,
     network_id: NetworkId,
+    challenge_response: Option<&Signature>,
 ) -> vertex_swarm_net_proto::handshake::SynAck {
+    let mut ack = encode_ack(peer, node_type, welcome_message, network_id);
+    if let Some(signature) = challenge_response {
+        ack.challenge_response = signature.as_bytes().to_vec();
+    }
     vertex_swarm_net_proto::handshake::SynAck {
-        syn: Some(encode_syn(observed)),
-        ack: Some(encode_ack(peer, node_type, welcome_message, network_id)),
+        syn: Some(encode_syn(observed, None)),
+        ack: Some(ack),
     }
 }
 
@@ -50,14 +65,36 @@ mod tests {
     use vertex_swarm_peer::Timestamp;
     use vertex_swarm_test_utils::test_spec_isolated as test_spec;
 
-    fn create_test_data() -> (Multiaddr, SwarmPeer, NetworkId) {
+    fn create_test_identity() -> (Identity, SwarmPeer, NetworkId) {
         let spec = test_spec();
         let identity = Identity::random(spec.clone(), SwarmNodeType::Storer);
-        let observed: Multiaddr = "/ip4/127.0.0.1/tcp/1234".parse().unwrap();
         let peer_addr: Multiaddr = "/ip4/192.168.1.1/tcp/5678".parse().unwrap();
         let peer = SwarmPeer::sign(&identity, vec![peer_addr], Timestamp::now(), None)
             .expect("should sign peer");
-        (observed, peer, spec.network_id())
+        (identity, peer, spec.network_id())
+    }
+
+    fn create_test_data() -> (Multiaddr, SwarmPeer, NetworkId) {
+        let (_, peer, network_id) = create_test_identity();
+        let observed: Multiaddr = "/ip4/127.0.0.1/tcp/1234".parse().unwrap();
+        (observed, peer, network_id)
+    }
+
+    fn encode_answering(
+        identity: &Identity,
+        peer: &SwarmPeer,
+        network_id: NetworkId,
+        challenge: &Challenge,
+    ) -> vertex_swarm_net_proto::handshake::SynAck {
+        let response = challenge.respond(identity).expect("should sign challenge");
+        encode_synack(
+            &"/ip4/127.0.0.1/tcp/1234".parse().unwrap(),
+            peer,
+            SwarmNodeType::Storer,
+            "test",
+            network_id,
+            Some(&response),
+        )
     }
 
     #[test]
@@ -66,9 +103,9 @@ mod tests {
         let node_type = SwarmNodeType::Storer;
         let welcome = "test";
 
-        let proto = encode_synack(&observed, &peer, node_type, welcome, network_id);
+        let proto = encode_synack(&observed, &peer, node_type, welcome, network_id, None);
         let (dec_observed, dec_peer, dec_type, dec_welcome) =
-            decode_synack(proto, network_id).unwrap();
+            decode_synack(proto, network_id, None).unwrap();
 
         assert_eq!(observed, dec_observed);
         assert_eq!(peer, dec_peer);
@@ -85,10 +122,11 @@ mod tests {
             SwarmNodeType::Client,
             "test",
             network_id,
+            None,
         );
         proto.syn = None;
 
-        let result = decode_synack(proto, network_id);
+        let result = decode_synack(proto, network_id, None);
         assert!(matches!(result, Err(HandshakeError::MissingField("syn"))));
     }
 
@@ -101,10 +139,65 @@ mod tests {
             SwarmNodeType::Client,
             "test",
             network_id,
+            None,
         );
         proto.ack = None;
 
-        let result = decode_synack(proto, network_id);
+        let result = decode_synack(proto, network_id, None);
         assert!(matches!(result, Err(HandshakeError::MissingField("ack"))));
     }
+
+    #[test]
+    fn test_synack_fresh_challenge_accepted() {
+        let (identity, peer, network_id) = create_test_identity();
+        let challenge = Challenge::random();
+
+        let proto = encode_answering(&identity, &peer, network_id, &challenge);
+        let (_, dec_peer, _, _) = decode_synack(proto, network_id, Some(&challenge)).unwrap();
+        assert_eq!(peer, dec_peer);
+    }
+
+    #[test]
+    fn test_synack_replayed_challenge_rejected() {
+        // A captured SYNACK answers the challenge of the handshake it was
+        // captured from; replaying it against a fresh challenge must fail.
+        let (identity, peer, network_id) = create_test_identity();
+        let old = Challenge::random();
+        let replayed = encode_answering(&identity, &peer, network_id, &old);
+
+        let fresh = Challenge::random();
+        let result = decode_synack(replayed, network_id, Some(&fresh));
+        assert!(matches!(result, Err(HandshakeError::ChallengeMismatch)));
+    }
+
+    #[test]
+    fn test_synack_response_from_other_key_rejected() {
+        let (_, peer, network_id) = create_test_identity();
+        let (other, _, _) = create_test_identity();
+        let challenge = Challenge::random();
+
+        let proto = encode_answering(&other, &peer, network_id, &challenge);
+        let result = decode_synack(proto, network_id, Some(&challenge));
+        assert!(matches!(result, Err(HandshakeError::ChallengeMismatch)));
+    }
+
+    #[test]
+    fn test_synack_missing_challenge_response() {
+        // A pre-Accord (legacy) SYNACK carries no response; once the dialer
+        // has challenged, it is rejected rather than accepted unchecked.
+        let (observed, peer, network_id) = create_test_data();
+        let proto = encode_synack(
+            &observed,
+            &peer,
+            SwarmNodeType::Storer,
+            "test",
+            network_id,
+            None,
+        );
+        let result = decode_synack(proto, network_id, Some(&Challenge::random()));
+        assert!(matches!(
+            result,
+            Err(HandshakeError::MissingField("challenge_response"))
+        ));
+    }
 }
@@ -51,6 +51,15 @@ pub enum HandshakeError {
     #[error("invalid peer: {0}")]
     InvalidPeer(#[from] SwarmPeerError),
 
+    /// Challenge response was not signed by the key behind the peer record,
+    /// e.g. a replayed ack answering an earlier challenge.
+    #[error("challenge response mismatch")]
+    ChallengeMismatch,
+
+    /// Signing the challenge response failed.
+    #[error("signing error: {0}")]
+    Signing(#[from] alloy_signer::Error),
+
     /// Invalid overlay address.
     #[error("invalid overlay")]
     InvalidOverlay,
@@ -73,6 +82,11 @@ pub enum HandshakeError {
     #[error("upgrade error: {0}")]
     UpgradeError(String),
 
+    /// The connection was established but the peer does not support the
+    /// handshake protocol.
+    #[error("protocol negotiation failed")]
+    NegotiationFailed,
+
     /// Admission control rejected the peer.
     ///
     /// Surfaces the structured reason from
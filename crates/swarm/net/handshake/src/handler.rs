@@ -19,12 +19,15 @@ use libp2p::{
     },
 };
 use tracing::{debug, warn};
-use vertex_swarm_api::SwarmIdentity;
+use vertex_swarm_api::{SwarmIdentity, SwarmSpec};
+use vertex_swarm_net_headers::ProtocolPrefix;
 use vertex_swarm_peer::SwarmPeer;
+use vertex_swarm_primitives::PeerCapabilities;
 
 use crate::{
-    AddressProvider, ConnectionDirection, HANDSHAKE_TIMEOUT, HandshakeError, HandshakeInfo,
-    PROTOCOL, SharedAdmissionControl, protocol::HandshakeProtocol,
+    AddressProvider, ConnectionDirection, DEFAULT_ANNOUNCE_DEBOUNCE, HANDSHAKE_TIMEOUT,
+    HandshakeError, HandshakeInfo, HandshakeLimiter, PROTOCOL, SharedAdmissionControl,
+    protocol::HandshakeProtocol,
 };
 
 /// Configuration for handshake handler.
@@ -34,6 +37,13 @@ pub struct HandshakeConfig {
     pub timeout: Duration,
     /// Label for metrics to distinguish handshake contexts (e.g. "topology" vs "verifier").
     pub purpose: &'static str,
+    /// Slots shared by every handshake on the behaviour.
+    pub limiter: HandshakeLimiter,
+    /// Optional features advertised to peers once Accord is active.
+    pub capabilities: PeerCapabilities,
+    /// How long a changed advertised address set settles before the self
+    /// record is re-signed.
+    pub announce_debounce: Duration,
 }
 
 impl HandshakeConfig {
@@ -42,10 +52,18 @@ impl HandshakeConfig {
         Self {
             timeout: HANDSHAKE_TIMEOUT,
             purpose,
+            limiter: HandshakeLimiter::default(),
+            capabilities: PeerCapabilities::NONE,
+            announce_debounce: DEFAULT_ANNOUNCE_DEBOUNCE,
         }
     }
 }
 
+/// [`PROTOCOL`] under the protocol prefix of `identity`'s spec.
+fn protocol_name<I: SwarmIdentity>(identity: &I) -> &'static str {
+    ProtocolPrefix::new(identity.spec().protocol_prefix()).apply(PROTOCOL)
+}
+
 /// Commands from behaviour to handler.
 #[derive(Debug)]
 pub enum HandshakeCommand {
@@ -97,6 +115,8 @@ pub struct HandshakeHandler<I, A> {
     /// advertised set is empty, in which case the protocol signs a last-resort
     /// record over the peer-observed address.
     self_record: Option<SwarmPeer>,
+    /// [`PROTOCOL`] under the identity's spec prefix.
+    protocol: &'static str,
     state: State,
     pending_event: Option<HandshakeHandlerEvent>,
     should_initiate: bool,
@@ -120,6 +140,7 @@ where
     ) -> Self {
         Self {
             config,
+            protocol: protocol_name(&*identity),
             identity,
             peer_id,
             remote_addr,
@@ -145,6 +166,7 @@ where
     ) -> Self {
         Self {
             config,
+            protocol: protocol_name(&*identity),
             identity,
             peer_id,
             remote_addr,
@@ -168,6 +190,9 @@ where
             self_record: self.self_record.clone(),
             direction,
             purpose: self.config.purpose,
+            protocol: self.protocol,
+            limiter: self.config.limiter.clone(),
+            capabilities: self.config.capabilities,
         }
     }
 }
@@ -289,9 +314,7 @@ fn extract_error(error: StreamUpgradeError<HandshakeError>) -> HandshakeError {
         StreamUpgradeError::Timeout => HandshakeError::Timeout,
         StreamUpgradeError::Io(e) => HandshakeError::Io(e),
         StreamUpgradeError::Apply(e) => e,
-        StreamUpgradeError::NegotiationFailed => {
-            HandshakeError::UpgradeError("protocol negotiation failed".into())
-        }
+        StreamUpgradeError::NegotiationFailed => HandshakeError::NegotiationFailed,
     }
 }
 
@@ -314,6 +337,10 @@ pub struct HandshakeUpgrade<I, A> {
     /// protocol runs and which side the admission gate sees.
     direction: ConnectionDirection,
     purpose: &'static str,
+    protocol: &'static str,
+    /// Slot the exchange waits for before sending or reading its first frame.
+    limiter: HandshakeLimiter,
+    capabilities: PeerCapabilities,
 }
 
 impl<I, A> Clone for HandshakeUpgrade<I, A> {
@@ -327,6 +354,9 @@ impl<I, A> Clone for HandshakeUpgrade<I, A> {
             self_record: self.self_record.clone(),
             direction: self.direction,
             purpose: self.purpose,
+            protocol: self.protocol,
+            limiter: self.limiter.clone(),
+            capabilities: self.capabilities,
         }
     }
 }
@@ -340,7 +370,7 @@ where
     type InfoIter = std::iter::Once<Self::Info>;
 
     fn protocol_info(&self) -> Self::InfoIter {
-        std::iter::once(PROTOCOL)
+        std::iter::once(self.protocol)
     }
 }
 
@@ -359,7 +389,8 @@ where
             self.self_record,
             self.purpose,
         )
-        .with_admission_control(self.admission_control, self.direction);
+        .with_admission_control(self.admission_control, self.direction)
+        .with_capabilities(self.capabilities);
         if let Some(local_peer_id) = local_peer_id {
             protocol = protocol.with_local_peer_id(local_peer_id);
         }
@@ -377,7 +408,12 @@ where
     type Future = BoxFuture<'static, Result<Self::Output, Self::Error>>;
 
     fn upgrade_inbound(self, socket: Stream, _: Self::Info) -> Self::Future {
-        Box::pin(self.build_protocol().handle_inbound(socket))
+        let limiter = self.limiter.clone();
+        let protocol = self.build_protocol();
+        Box::pin(async move {
+            let _slot = limiter.acquire().await;
+            protocol.handle_inbound(socket).await
+        })
     }
 }
 
@@ -391,6 +427,11 @@ where
     type Future = BoxFuture<'static, Result<Self::Output, Self::Error>>;
 
     fn upgrade_outbound(self, socket: Stream, _: Self::Info) -> Self::Future {
-        Box::pin(self.build_protocol().handle_outbound(socket))
+        let limiter = self.limiter.clone();
+        let protocol = self.build_protocol();
+        Box::pin(async move {
+            let _slot = limiter.acquire().await;
+            protocol.handle_outbound(socket).await
+        })
     }
 }
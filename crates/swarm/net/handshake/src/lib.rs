@@ -8,8 +8,9 @@
 //! # Protocol assumptions not in the Book of Swarm
 //!
 //! - [`HANDSHAKE_TIMEOUT`] = 15 seconds bounds the whole exchange. The handler
-//!   arms it per operation, and the topology reuses it as the dialer's in-flight
-//!   timeout and the stale-pending cleanup window. A peer that upgrades the
+//!   arms it per operation, and the topology reuses it as the dialer's default
+//!   in-flight timeout (dials to a known overlay use the routing table's
+//!   per-bin timeouts instead) and the stale-pending cleanup window. A peer that upgrades the
 //!   transport but does not finish the handshake within this window is
 //!   disconnected and its slot freed, so a stalled or half-open peer cannot pin
 //!   a connection indefinitely.
@@ -19,11 +20,26 @@
 //!   message fails the handshake with a validation error rather than being
 //!   truncated. Bounding it stops an untrusted peer from spending our memory on
 //!   a field that carries no protocol meaning.
+//! - From Accord on, the dialer's SYN carries a random 32-byte challenge and
+//!   the SYNACK's ack must sign it with the responder's record key, so a
+//!   replayed SYNACK is rejected. Pre-Accord both fields are left empty, so
+//!   the exchange is unchanged on the wire.
+//! - From Accord on, each ack also carries the sender's optional
+//!   [`PeerCapabilities`] (field 101). A connection uses only what both ends
+//!   advertised ([`HandshakeInfo::capabilities`]); pre-Accord the field is zero
+//!   and absent on the wire.
+//! - At most [`DEFAULT_MAX_CONCURRENT_HANDSHAKES`] exchanges run at once per
+//!   behaviour unless overridden; the rest wait for a slot inside the timeout
+//!   (see [`HandshakeLimiter`]).
+//! - A change to the advertised address set is re-signed into the self record
+//!   only after it has settled for [`DEFAULT_ANNOUNCE_DEBOUNCE`]; until then
+//!   peers receive the previous record.
 
 use std::time::Duration;
 
 use libp2p::{Multiaddr, PeerId};
 use vertex_swarm_peer::{SwarmNodeType, SwarmPeer};
+use vertex_swarm_primitives::PeerCapabilities;
 
 mod behaviour;
 pub use behaviour::{HandshakeBehaviour, HandshakeEvent};
@@ -31,6 +47,9 @@ pub use behaviour::{HandshakeBehaviour, HandshakeEvent};
 mod handler;
 
 mod cache;
+pub use cache::DEFAULT_ANNOUNCE_DEBOUNCE;
+
+mod challenge;
 
 mod codec;
 
@@ -42,6 +61,11 @@ pub use error::HandshakeError;
 pub mod metrics;
 pub use metrics::HandshakeStage;
 
+mod limit;
+pub use limit::{
+    DEFAULT_MAX_CONCURRENT_HANDSHAKES, HandshakeLimiter, HandshakePermit, HandshakeSlots,
+};
+
 mod address;
 pub use address::{AddressProvider, NoAddresses};
 
@@ -56,8 +80,8 @@ pub const PROTOCOL: &str = "/swarm/handshake/15.0.0/handshake";
 
 /// Timeout for the full handshake exchange.
 ///
-/// The topology reuses this as the dialer in-flight timeout and the
-/// stale-pending cleanup window; see the crate-level docs.
+/// The topology reuses this as the dialer's default in-flight timeout and
+/// the stale-pending cleanup window; see the crate-level docs.
 pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(15);
 
 /// Maximum welcome-message length, in Unicode scalar values.
@@ -75,4 +99,7 @@ pub struct HandshakeInfo {
     pub welcome_message: String,
     /// Can be reported to an AddressManager for NAT discovery.
     pub observed_multiaddr: Multiaddr,
+    /// Features both ends advertised, and so may use on this connection.
+    /// Empty before Accord and with peers that advertise nothing.
+    pub capabilities: PeerCapabilities,
 }
@@ -0,0 +1,203 @@
+//! Global cap on handshakes in progress.
+//!
+//! The connection limits bound how many connections exist, not how many are
+//! mid-handshake at once; each of those holds a handler and runs signature
+//! work. [`HandshakeLimiter`] gates the exchange itself behind a semaphore
+//! shared by every handler of a behaviour, inbound and outbound alike: past
+//! the limit a new handshake waits for a slot before reading or writing its
+//! first frame. The wait counts against [`HANDSHAKE_TIMEOUT`], so a handshake
+//! still queued when the timeout elapses fails as a timeout.
+//!
+//! [`HANDSHAKE_TIMEOUT`]: crate::HANDSHAKE_TIMEOUT
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use metrics::gauge;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Default cap on concurrent handshakes.
+pub const DEFAULT_MAX_CONCURRENT_HANDSHAKES: usize = 64;
+
+/// Point-in-time view of the handshake slots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HandshakeSlots {
+    /// Handshakes waiting for a slot.
+    pub queued: usize,
+    /// Handshakes running now; never above `limit`.
+    pub active: usize,
+    /// Concurrency cap.
+    pub limit: usize,
+}
+
+#[derive(Debug)]
+struct Slots {
+    permits: Arc<Semaphore>,
+    limit: usize,
+    queued: AtomicUsize,
+    active: AtomicUsize,
+}
+
+/// Semaphore-gated handshake slots, Arc-cheap to clone into each upgrade.
+#[derive(Debug, Clone)]
+pub struct HandshakeLimiter {
+    slots: Arc<Slots>,
+}
+
+impl HandshakeLimiter {
+    /// Allow at most `limit` handshakes at once (at least one).
+    pub fn new(limit: usize) -> Self {
+        let limit = limit.max(1);
+        Self {
+            slots: Arc::new(Slots {
+                permits: Arc::new(Semaphore::new(limit)),
+                limit,
+                queued: AtomicUsize::new(0),
+                active: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Current queue depth and concurrency.
+    pub fn slots(&self) -> HandshakeSlots {
+        HandshakeSlots {
+            queued: self.slots.queued.load(Ordering::Relaxed),
+            active: self.slots.active.load(Ordering::Relaxed),
+            limit: self.slots.limit,
+        }
+    }
+
+    /// Wait for a free slot; the handshake holds it until the permit drops.
+    pub async fn acquire(&self) -> HandshakePermit {
+        let queued = Counted::enter(&self.slots, Slot::Queued);
+        // The semaphore is never closed, so acquisition only fails if it were.
+        let permit = Arc::clone(&self.slots.permits).acquire_owned().await.ok();
+        drop(queued);
+        HandshakePermit {
+            _permit: permit,
+            _active: Counted::enter(&self.slots, Slot::Active),
+        }
+    }
+}
+
+impl Default for HandshakeLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENT_HANDSHAKES)
+    }
+}
+
+/// A held handshake slot, released on drop.
+#[derive(Debug)]
+pub struct HandshakePermit {
+    _permit: Option<OwnedSemaphorePermit>,
+    _active: Counted,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Slot {
+    Queued,
+    Active,
+}
+
+impl Slot {
+    fn gauge(self) -> &'static str {
+        match self {
+            Self::Queued => "handshake_queued",
+            Self::Active => "handshake_in_progress",
+        }
+    }
+}
+
+/// Counter held for a scope, mirrored to a gauge; released on drop so a
+/// cancelled handshake does not leak a slot.
+#[derive(Debug)]
+struct Counted {
+    slots: Arc<Slots>,
+    slot: Slot,
+}
+
+impl Counted {
+    fn enter(slots: &Arc<Slots>, slot: Slot) -> Self {
+        let now = Self::count(slots, slot).fetch_add(1, Ordering::Relaxed) + 1;
+        gauge!(slot.gauge()).set(now as f64);
+        Self {
+            slots: Arc::clone(slots),
+            slot,
+        }
+    }
+
+    fn count(slots: &Slots, slot: Slot) -> &AtomicUsize {
+        match slot {
+            Slot::Queued => &slots.queued,
+            Slot::Active => &slots.active,
+        }
+    }
+}
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        let now = Self::count(&self.slots, self.slot).fetch_sub(1, Ordering::Relaxed) - 1;
+        gauge!(self.slot.gauge()).set(now as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// More handshakes than slots: the excess queue, and each released slot
+    /// admits exactly one queued handshake.
+    #[tokio::test(flavor = "current_thread")]
+    async fn excess_handshakes_queue_until_slots_free() {
+        const LIMIT: usize = 2;
+        const STARTED: usize = 5;
+
+        let limiter = HandshakeLimiter::new(LIMIT);
+        let mut releases = Vec::new();
+        let mut handshakes = Vec::new();
+        for _ in 0..STARTED {
+            let limiter = limiter.clone();
+            let (release, released) = tokio::sync::oneshot::channel::<()>();
+            releases.push(release);
+            handshakes.push(tokio::spawn(async move {
+                let _permit = limiter.acquire().await;
+                let _ = released.await;
+            }));
+        }
+
+        tokio::task::yield_now().await;
+        assert_eq!(
+            limiter.slots(),
+            HandshakeSlots {
+                queued: STARTED - LIMIT,
+                active: LIMIT,
+                limit: LIMIT,
+            }
+        );
+
+        // Finishing one running handshake admits one queued one.
+        let first = releases.remove(0);
+        first.send(()).expect("handshake waiting");
+        handshakes.remove(0).await.expect("handshake task");
+        tokio::task::yield_now().await;
+        assert_eq!(limiter.slots().queued, STARTED - LIMIT - 1);
+        assert_eq!(limiter.slots().active, LIMIT);
+
+        for release in releases {
+            let _ = release.send(());
+        }
+        for handshake in handshakes {
+            handshake.await.expect("handshake task");
+        }
+        assert_eq!(
+            limiter.slots(),
+            HandshakeSlots {
+                queued: 0,
+                active: 0,
+                limit: LIMIT,
+            }
+        );
+    }
+}
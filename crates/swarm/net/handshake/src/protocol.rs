@@ -5,9 +5,11 @@ use vertex_net_codec::FramedProto;
 use vertex_net_utils::extract_peer_id;
 use vertex_swarm_api::SwarmIdentity;
 use vertex_swarm_peer::{SwarmPeer, Timestamp};
+use vertex_swarm_primitives::PeerCapabilities;
 use vertex_swarm_spec::SwarmSpec;
 
 use crate::admission::{AdmissionDecision, ConnectionDirection};
+use crate::challenge::{Challenge, challenge_active};
 use crate::codec::{decode_ack, decode_syn, decode_synack, encode_ack, encode_syn, encode_synack};
 use crate::metrics::HandshakeMetrics;
 use crate::{HandshakeError, HandshakeInfo, SharedAdmissionControl};
@@ -127,6 +129,8 @@ pub(crate) struct HandshakeProtocol<I: SwarmIdentity> {
     /// soon as the remote peer's identity is verified and aborts with
     /// [`HandshakeError::AdmissionRejected`] on a `Reject` decision.
     admission_control: Option<(SharedAdmissionControl, ConnectionDirection)>,
+    /// Optional features we support; advertised only once Accord is active.
+    capabilities: PeerCapabilities,
     purpose: &'static str,
 }
 
@@ -145,6 +149,7 @@ impl<I: SwarmIdentity> HandshakeProtocol<I> {
             remote_addr,
             self_record,
             admission_control: None,
+            capabilities: PeerCapabilities::NONE,
             purpose,
         }
     }
@@ -169,6 +174,23 @@ impl<I: SwarmIdentity> HandshakeProtocol<I> {
         self
     }
 
+    /// Set the optional features to advertise.
+    pub(crate) fn with_capabilities(mut self, capabilities: PeerCapabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// What we put in our ack. Pre-Accord the field stays zero and proto3
+    /// omits it, so the ack carries no capabilities bytes at all (pinned by
+    /// the capabilities vectors in `codec::ack`).
+    fn advertised_capabilities(&self) -> PeerCapabilities {
+        if challenge_active(self.identity.spec()) {
+            self.capabilities
+        } else {
+            PeerCapabilities::NONE
+        }
+    }
+
     /// Evaluate admission control if installed.
     fn evaluate_admission(&self, info: &HandshakeInfo) -> Result<(), HandshakeError> {
         let Some((ref ac, direction)) = self.admission_control else {
@@ -221,7 +243,7 @@ impl<I: SwarmIdentity> HandshakeProtocol<I> {
         let (syn, stream) = Framed::recv::<Syn, HandshakeError, _>(stream)
             .instrument(debug_span!("recv_syn"))
             .await?;
-        let observed_multiaddr = decode_syn(syn)?;
+        let (observed_multiaddr, challenge) = decode_syn(syn)?;
         metrics.syn_exchanged();
 
         if let Some(local_peer_id) = &self.local_peer_id {
@@ -247,14 +269,28 @@ impl<I: SwarmIdentity> HandshakeProtocol<I> {
         }
         let dialer_observed = dialer_observed.with(Protocol::P2p(self.peer_id));
 
+        // Answer the dialer's freshness challenge. Pre-Accord a stray
+        // challenge is ignored so the SYNACK keeps its pre-Accord bytes.
+        let challenge_response = match challenge {
+            Some(challenge) if challenge_active(self.identity.spec()) => {
+                Some(challenge.respond(&self.identity)?)
+            }
+            _ => None,
+        };
+
         // Send SYNACK: our identity + the dialer's address as we observe it.
-        let synack = encode_synack(
+        let advertised = self.advertised_capabilities();
+        let mut synack = encode_synack(
             &dialer_observed,
             &local_peer,
             self.identity.node_type(),
             self.identity.welcome_message().unwrap_or_default(),
             network_id,
+            challenge_response.as_ref(),
         );
+        if let Some(ack) = synack.ack.as_mut() {
+            ack.capabilities = advertised.bits();
+        }
         let stream = Framed::send::<_, HandshakeError, _>(stream, synack)
             .instrument(debug_span!("send_synack"))
             .await?;
@@ -264,6 +300,7 @@ impl<I: SwarmIdentity> HandshakeProtocol<I> {
         let (ack, mut stream) = Framed::recv::<Ack, HandshakeError, _>(stream)
             .instrument(debug_span!("recv_ack"))
             .await?;
+        let remote_capabilities = PeerCapabilities::from_bits(ack.capabilities);
         let (swarm_peer, node_type, welcome_message) = decode_ack(ack, network_id)?;
 
         let info = HandshakeInfo {
@@ -272,6 +309,7 @@ impl<I: SwarmIdentity> HandshakeProtocol<I> {
             node_type,
             welcome_message,
             observed_multiaddr,
+            capabilities: advertised.intersection(remote_capabilities),
         };
 
         // Consult admission control now that the peer's identity is
@@ -332,19 +370,24 @@ impl<I: SwarmIdentity> HandshakeProtocol<I> {
         }
         let their_observed_multiaddr = their_observed_multiaddr.with(Protocol::P2p(self.peer_id));
 
+        // From Accord on, challenge the responder so a replayed SYNACK fails.
+        let challenge = challenge_active(self.identity.spec()).then(Challenge::random);
+
         // Send SYN: tell peer what address we see them at.
-        let stream =
-            Framed::send::<_, HandshakeError, _>(stream, encode_syn(&their_observed_multiaddr))
-                .instrument(debug_span!("send_syn"))
-                .await?;
+        let syn = encode_syn(&their_observed_multiaddr, challenge.as_ref());
+        let stream = Framed::send::<_, HandshakeError, _>(stream, syn)
+            .instrument(debug_span!("send_syn"))
+            .await?;
         metrics.syn_exchanged();
 
         // Receive SYNACK: peer echoes our observed addr + their identity.
         let (synack, stream) = Framed::recv::<SynAck, HandshakeError, _>(stream)
             .instrument(debug_span!("recv_synack"))
             .await?;
+        let remote_capabilities =
+            PeerCapabilities::from_bits(synack.ack.as_ref().map_or(0, |ack| ack.capabilities));
         let (observed_multiaddr, swarm_peer, node_type, welcome_message) =
-            decode_synack(synack, network_id)?;
+            decode_synack(synack, network_id, challenge.as_ref())?;
         metrics.synack_exchanged();
 
         if let Some(local_peer_id) = &self.local_peer_id {
@@ -361,22 +404,25 @@ impl<I: SwarmIdentity> HandshakeProtocol<I> {
 
         // Consult admission control before sending ACK so a reject
         // aborts cleanly without committing to the exchange.
+        let advertised = self.advertised_capabilities();
         let info = HandshakeInfo {
             peer_id: self.peer_id,
             swarm_peer,
             node_type,
             welcome_message,
             observed_multiaddr,
+            capabilities: advertised.intersection(remote_capabilities),
         };
         self.evaluate_admission(&info)?;
 
         // Send ACK: our identity.
-        let ack = encode_ack(
+        let mut ack = encode_ack(
             &local_peer,
             self.identity.node_type(),
             self.identity.welcome_message().unwrap_or_default(),
             network_id,
         );
+        ack.capabilities = advertised.bits();
         let mut stream = Framed::send::<_, HandshakeError, _>(stream, ack)
             .instrument(debug_span!("send_ack"))
             .await?;
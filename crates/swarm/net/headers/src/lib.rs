@@ -3,6 +3,7 @@
 mod codec;
 mod error;
 pub mod metrics;
+mod prefix;
 mod stream;
 // Trace-context propagation has a native implementation over OpenTelemetry and a
 // no-op wasm sibling (the browser client has no OTLP backend). Both export the
@@ -15,10 +16,12 @@ mod upgrade;
 // Re-exports
 pub use codec::{Headers, HeadersCodec};
 pub use error::{HeadersError, ProtocolError, ProtocolStreamError, UpgradeError};
+pub use prefix::ProtocolPrefix;
 pub use stream::HeaderedStream;
 pub use tracing::{
-    HEADER_NAME_TRACING_SPAN_CONTEXT, HeaderExtractor, HeaderInjector, PeerContext,
-    extract_trace_context, has_trace_context, inject_trace_context, span_from_headers,
+    HEADER_NAME_TRACING_SPAN_CONTEXT, HeaderExtractor, HeaderInjector,
+    MAX_TRACE_CONTEXT_HEADER_SIZE, PeerContext, extract_trace_context, has_trace_context,
+    inject_trace_context, span_from_headers,
 };
 pub use traits::{HeaderedInbound, HeaderedOutbound};
 pub use upgrade::{Inbound, Outbound};
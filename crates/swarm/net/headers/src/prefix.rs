@@ -0,0 +1,139 @@
+//! Spec-configurable protocol-name namespace.
+//!
+//! Every protocol constant is written under the default `/swarm` namespace.
+//! A network whose spec sets another prefix advertises the same protocols
+//! under its own namespace, so multistream-select never agrees a protocol with
+//! a node of a different namespace, independent of the network-id check.
+//!
+//! libp2p upgrades advertise `&'static str` names, so rewritten names are
+//! interned once per (prefix, protocol) pair. The set is bounded by the
+//! handful of prefixes a process uses times the protocol count.
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+/// Default namespace of every protocol name on the wire, matching the
+/// `SwarmSpec::protocol_prefix` default.
+const DEFAULT_PREFIX: &str = "/swarm";
+
+/// Return the interned copy of `s`, leaking it on first sight.
+fn intern(s: &str) -> &'static str {
+    static INTERNED: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    let mut interned = INTERNED
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    if let Some(existing) = interned.get(s) {
+        return existing;
+    }
+    let leaked: &'static str = Box::leak(s.to_owned().into_boxed_str());
+    interned.insert(leaked);
+    leaked
+}
+
+/// Namespace that protocol names are advertised and negotiated under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProtocolPrefix(&'static str);
+
+impl Default for ProtocolPrefix {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl ProtocolPrefix {
+    /// The default `/swarm` namespace; names are used unchanged.
+    pub const DEFAULT: Self = Self(DEFAULT_PREFIX);
+
+    /// Prefix from a spec value such as `/myswarm`. A missing leading slash
+    /// is added and trailing slashes are dropped.
+    pub fn new(prefix: &str) -> Self {
+        let trimmed = prefix.trim_matches('/');
+        if trimmed == DEFAULT_PREFIX.trim_start_matches('/') {
+            return Self::DEFAULT;
+        }
+        Self(intern(&format!("/{trimmed}")))
+    }
+
+    /// The prefix, with its leading slash.
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+
+    /// Whether this is the default `/swarm` namespace.
+    pub fn is_default(&self) -> bool {
+        self.0 == DEFAULT_PREFIX
+    }
+
+    /// Rewrite a `/swarm/...` protocol name under this prefix.
+    ///
+    /// Names outside the `/swarm` namespace (e.g. `/ipfs/id/1.0.0`) are
+    /// returned unchanged.
+    pub fn apply(self, name: &'static str) -> &'static str {
+        if self.is_default() {
+            return name;
+        }
+        match default_suffix(name) {
+            Some(rest) => intern(&format!("{}{rest}", self.0)),
+            None => name,
+        }
+    }
+
+    /// Map a name negotiated under this prefix back to its `/swarm/...` form,
+    /// so it can be matched against the protocol constants.
+    pub fn canonical<'a>(self, negotiated: &'a str) -> Cow<'a, str> {
+        if self.is_default() {
+            return Cow::Borrowed(negotiated);
+        }
+        match negotiated
+            .strip_prefix(self.0)
+            .filter(|rest| rest.starts_with('/'))
+        {
+            Some(rest) => Cow::Owned(format!("{DEFAULT_PREFIX}{rest}")),
+            None => Cow::Borrowed(negotiated),
+        }
+    }
+}
+
+/// The part of `name` after the default prefix, including its leading
+/// slash, or `None` if `name` is not in the default namespace.
+fn default_suffix(name: &str) -> Option<&str> {
+    name.strip_prefix(DEFAULT_PREFIX)
+        .filter(|rest| rest.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HIVE: &str = "/swarm/hive/2.0.0/peers";
+
+    #[test]
+    fn default_prefix_leaves_names_unchanged() {
+        assert_eq!(ProtocolPrefix::new("/swarm"), ProtocolPrefix::DEFAULT);
+        assert_eq!(ProtocolPrefix::DEFAULT.apply(HIVE), HIVE);
+        assert_eq!(ProtocolPrefix::DEFAULT.canonical(HIVE), HIVE);
+    }
+
+    #[test]
+    fn custom_prefix_rewrites_and_round_trips() {
+        let prefix = ProtocolPrefix::new("myswarm/");
+        assert_eq!(prefix.as_str(), "/myswarm");
+
+        let name = prefix.apply(HIVE);
+        assert_eq!(name, "/myswarm/hive/2.0.0/peers");
+        assert_eq!(prefix.canonical(name), HIVE);
+
+        // Interned: the same &'static str is handed out every time.
+        assert!(std::ptr::eq(name, prefix.apply(HIVE)));
+    }
+
+    #[test]
+    fn foreign_names_pass_through() {
+        let prefix = ProtocolPrefix::new("/myswarm");
+        assert_eq!(prefix.apply("/ipfs/id/1.0.0"), "/ipfs/id/1.0.0");
+        assert_eq!(prefix.apply("/swarmish/x/1.0.0"), "/swarmish/x/1.0.0");
+        assert_eq!(prefix.canonical("/ipfs/id/1.0.0"), "/ipfs/id/1.0.0");
+    }
+}
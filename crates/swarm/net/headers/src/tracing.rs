@@ -14,16 +14,25 @@
 //! 3. Use `tracing-opentelemetry` layer in your subscriber
 //!
 //! This module will then automatically propagate trace context in headers.
+//!
+//! # Untrusted input
+//!
+//! Trace headers come from remote peers. Values longer than
+//! [`MAX_TRACE_CONTEXT_HEADER_SIZE`] or containing anything but visible ASCII
+//! are never handed to the propagator, and a context that does not decode to a
+//! valid remote span is discarded: the exchange proceeds under a fresh root
+//! span rather than failing or joining a trace the peer made up.
 
 use std::collections::HashMap;
 
 use bytes::Bytes;
 use libp2p::PeerId;
 use opentelemetry::{
-    global,
-    propagation::{Extractor, Injector},
+    Context, global,
+    propagation::{Extractor, Injector, TextMapPropagator},
+    trace::TraceContextExt,
 };
-use tracing::Span;
+use tracing::{Span, debug};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 use vertex_swarm_primitives::OverlayAddress;
 
@@ -43,6 +52,11 @@ pub struct PeerContext {
 /// (W3C TraceContext, Jaeger, B3, etc.) depends on the configured propagator.
 pub const HEADER_NAME_TRACING_SPAN_CONTEXT: &str = "tracing-span-context";
 
+/// Largest trace header value read from a peer, in bytes.
+///
+/// Matches the W3C `tracestate` limit; a `traceparent` is 55 bytes.
+pub const MAX_TRACE_CONTEXT_HEADER_SIZE: usize = 512;
+
 /// Wrapper around headers map that implements OpenTelemetry's `Injector` trait.
 ///
 /// Used to inject trace context into outgoing request headers.
@@ -56,13 +70,18 @@ impl Injector for HeaderInjector<'_> {
 
 /// Wrapper around headers map that implements OpenTelemetry's `Extractor` trait.
 ///
-/// Used to extract trace context from incoming request headers.
+/// Used to extract trace context from incoming request headers. Values over
+/// [`MAX_TRACE_CONTEXT_HEADER_SIZE`] or outside visible ASCII read as absent.
 pub struct HeaderExtractor<'a>(pub &'a HashMap<String, Bytes>);
 
 impl Extractor for HeaderExtractor<'_> {
     fn get(&self, key: &str) -> Option<&str> {
         self.0
             .get(key)
+            .filter(|bytes| {
+                bytes.len() <= MAX_TRACE_CONTEXT_HEADER_SIZE
+                    && bytes.iter().all(|b| matches!(b, b' '..=b'~'))
+            })
             .and_then(|bytes| std::str::from_utf8(bytes).ok())
     }
 
@@ -103,7 +122,9 @@ pub fn inject_trace_context(headers: &mut HashMap<String, Bytes>) {
 ///
 /// Uses the globally configured propagator to deserialize trace context from
 /// the headers. If valid context is found, it's set as the parent of the
-/// current span, linking this span to the distributed trace.
+/// current span, linking this span to the distributed trace. Headers that carry
+/// trace context which fails validation make the current span a new root
+/// instead; the exchange itself is unaffected.
 ///
 /// # Example
 ///
@@ -114,14 +135,36 @@ pub fn inject_trace_context(headers: &mut HashMap<String, Bytes>) {
 /// extract_trace_context(&headers); // Links span to remote trace
 /// ```
 pub fn extract_trace_context(headers: &HashMap<String, Bytes>) {
-    let parent_context =
-        global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)));
+    if !has_trace_context(headers) {
+        return;
+    }
+
+    let parent_context = global::get_text_map_propagator(|propagator| {
+        remote_parent(propagator, headers).unwrap_or_else(|| {
+            debug!("discarding malformed trace context from peer");
+            metrics::counter!("protocol_trace_context_rejected_total").increment(1);
+            Context::new()
+        })
+    });
 
     // Set the extracted context as the parent of the current span
     // Ignore the result - if the span isn't recording, that's fine
     let _ = Span::current().set_parent(parent_context);
 }
 
+/// Decode the remote parent carried in `headers`, if it is a valid span.
+///
+/// Extraction starts from an empty context so the caller's own active span can
+/// never pass for a remote parent.
+fn remote_parent(
+    propagator: &dyn TextMapPropagator,
+    headers: &HashMap<String, Bytes>,
+) -> Option<Context> {
+    let context = propagator.extract_with_context(&Context::new(), &HeaderExtractor(headers));
+    let span_context = context.span().span_context().clone();
+    (span_context.is_valid() && span_context.is_remote()).then_some(context)
+}
+
 /// Check if headers contain trace context.
 pub fn has_trace_context(headers: &HashMap<String, Bytes>) -> bool {
     // Check for common trace context headers
@@ -184,6 +227,8 @@ pub(crate) fn span_from_headers_with_context(
 
 #[cfg(test)]
 mod tests {
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+
     use super::*;
 
     #[test]
@@ -228,4 +273,41 @@ mod tests {
         headers.insert("uber-trace-id".to_string(), Bytes::from("..."));
         assert!(has_trace_context(&headers));
     }
+
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    fn traceparent(value: impl Into<Bytes>) -> HashMap<String, Bytes> {
+        HashMap::from([("traceparent".to_string(), value.into())])
+    }
+
+    #[test]
+    fn valid_trace_context_links_to_the_remote_parent() {
+        let parent = remote_parent(&TraceContextPropagator::new(), &traceparent(TRACEPARENT))
+            .expect("well-formed traceparent");
+        assert_eq!(
+            parent.span().span_context().trace_id().to_string(),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+    }
+
+    #[test]
+    fn garbage_trace_context_falls_back_to_a_root_span() {
+        let propagator = TraceContextPropagator::new();
+        let oversized = format!("{TRACEPARENT}{}", " ".repeat(MAX_TRACE_CONTEXT_HEADER_SIZE));
+
+        for garbage in [
+            Bytes::from_static(b"not-a-trace-context"),
+            Bytes::from_static(b"00-\xff\xfe-\x00-01"),
+            Bytes::from(vec![0xff; 64]),
+            Bytes::from(oversized),
+        ] {
+            let headers = traceparent(garbage);
+            assert!(has_trace_context(&headers));
+            assert!(remote_parent(&propagator, &headers).is_none());
+
+            // The exchange still gets its span; extraction just leaves it a root.
+            let span = span_from_headers("/swarm/test/1.0.0/test", "inbound", &headers);
+            drop(span);
+        }
+    }
 }
@@ -30,6 +30,11 @@ pub struct PeerContext {
 /// on the configured propagator, which is native-only.
 pub const HEADER_NAME_TRACING_SPAN_CONTEXT: &str = "tracing-span-context";
 
+/// Largest trace header value read from a peer, in bytes.
+///
+/// Kept for parity with the native sibling; wasm never reads trace headers.
+pub const MAX_TRACE_CONTEXT_HEADER_SIZE: usize = 512;
+
 /// Wrapper around headers map, mirroring the native injector shape.
 ///
 /// On wasm there is no propagator, so this carries no behavior.
@@ -11,6 +11,7 @@ use crate::{
     codec::{Headers, HeadersCodec},
     error::{HeadersError, ProtocolError},
     metrics::ProtocolMetrics,
+    prefix::ProtocolPrefix,
     stream::HeaderedStream,
     tracing::{
         PeerContext, inject_trace_context, span_from_headers, span_from_headers_with_context,
@@ -39,6 +40,7 @@ fn protocol_short_name(protocol: &'static str) -> &'static str {
 pub struct Inbound<P> {
     inner: P,
     peer_context: Option<PeerContext>,
+    prefix: ProtocolPrefix,
 }
 
 impl<P> Inbound<P> {
@@ -47,6 +49,7 @@ impl<P> Inbound<P> {
         Self {
             inner,
             peer_context: None,
+            prefix: ProtocolPrefix::DEFAULT,
         }
     }
 
@@ -55,6 +58,12 @@ impl<P> Inbound<P> {
         self.peer_context = Some(ctx);
         self
     }
+
+    /// Advertise the protocol under `prefix` instead of `/swarm`.
+    pub fn with_prefix(mut self, prefix: ProtocolPrefix) -> Self {
+        self.prefix = prefix;
+        self
+    }
 }
 
 impl<P: HeaderedInbound> UpgradeInfo for Inbound<P> {
@@ -62,7 +71,7 @@ impl<P: HeaderedInbound> UpgradeInfo for Inbound<P> {
     type InfoIter = std::iter::Once<Self::Info>;
 
     fn protocol_info(&self) -> Self::InfoIter {
-        std::iter::once(self.inner.protocol_name())
+        std::iter::once(self.prefix.apply(self.inner.protocol_name()))
     }
 }
 
@@ -144,6 +153,7 @@ impl<P: HeaderedInbound> InboundUpgrade<Stream> for Inbound<P> {
 pub struct Outbound<P> {
     inner: P,
     peer_context: Option<PeerContext>,
+    prefix: ProtocolPrefix,
 }
 
 impl<P> Outbound<P> {
@@ -152,6 +162,7 @@ impl<P> Outbound<P> {
         Self {
             inner,
             peer_context: None,
+            prefix: ProtocolPrefix::DEFAULT,
         }
     }
 
@@ -160,6 +171,12 @@ impl<P> Outbound<P> {
         self.peer_context = Some(ctx);
         self
     }
+
+    /// Advertise the protocol under `prefix` instead of `/swarm`.
+    pub fn with_prefix(mut self, prefix: ProtocolPrefix) -> Self {
+        self.prefix = prefix;
+        self
+    }
 }
 
 impl<P: HeaderedOutbound> UpgradeInfo for Outbound<P> {
@@ -167,7 +184,7 @@ impl<P: HeaderedOutbound> UpgradeInfo for Outbound<P> {
     type InfoIter = std::iter::Once<Self::Info>;
 
     fn protocol_info(&self) -> Self::InfoIter {
-        std::iter::once(self.inner.protocol_name())
+        std::iter::once(self.prefix.apply(self.inner.protocol_name()))
     }
 }
 
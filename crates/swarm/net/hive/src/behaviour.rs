@@ -6,10 +6,10 @@ use std::{
     task::{Context, Poll},
 };
 
-use crate::HIVE_INBOUND_QUOTA;
 use crate::cache::PeerCache;
 use crate::handler::{HiveCommand, HiveHandler, HiveHandlerEvent};
 use crate::peer_handler::{HivePeerHandler, LearnAndDial};
+use crate::{HIVE_INBOUND_QUOTA, HiveLimits};
 use libp2p::{
     Multiaddr, PeerId,
     swarm::{
@@ -34,6 +34,13 @@ pub enum HiveEvent {
         connection_id: ConnectionId,
         peers: Vec<SwarmPeer>,
     },
+    /// An inbound response broke a [`HiveLimits`] cap and was rejected
+    /// unprocessed. The sender is at fault.
+    LimitExceeded {
+        peer_id: PeerId,
+        connection_id: ConnectionId,
+        error: ProtocolStreamError,
+    },
     /// Error occurred.
     Error {
         peer_id: PeerId,
@@ -53,6 +60,8 @@ pub struct HiveBehaviour<I> {
     /// Shared with each handler so per-peer buckets survive reconnects but
     /// are freed by [`Self::on_swarm_event`] on the final `ConnectionClosed`.
     inbound_limit: Arc<KeyedRateLimiter<PeerId>>,
+    /// Caps on each inbound response, copied into every handler.
+    limits: HiveLimits,
 }
 
 impl<I> HiveBehaviour<I>
@@ -75,9 +84,16 @@ where
             events: VecDeque::new(),
             peer_handler,
             inbound_limit: Arc::new(KeyedRateLimiter::new(HIVE_INBOUND_QUOTA)),
+            limits: HiveLimits::default(),
         }
     }
 
+    /// Override the inbound response caps.
+    pub fn with_limits(mut self, limits: HiveLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     /// Broadcast a batch of peers on an existing connection. The topology
     /// already throttles broadcast cadence, so no outbound rate-limit is
     /// applied here.
@@ -118,6 +134,7 @@ where
             self.cache.clone(),
             self.inbound_limit.clone(),
             self.peer_handler.clone(),
+            self.limits,
         ))
     }
 
@@ -135,6 +152,7 @@ where
             self.cache.clone(),
             self.inbound_limit.clone(),
             self.peer_handler.clone(),
+            self.limits,
         ))
     }
 
@@ -171,6 +189,14 @@ where
                         peers,
                     }));
             }
+            HiveHandlerEvent::LimitExceeded(error) => {
+                self.events
+                    .push_back(ToSwarm::GenerateEvent(HiveEvent::LimitExceeded {
+                        peer_id,
+                        connection_id,
+                        error,
+                    }));
+            }
             HiveHandlerEvent::Error(error) => {
                 debug!(%peer_id, %error, "hive error");
                 self.events
@@ -1,7 +1,36 @@
 //! Proto codec helpers for hive protocol (2.0.0).
 
+use quick_protobuf::MessageWrite;
 use vertex_swarm_peer::SwarmPeer;
 
+use crate::HiveLimits;
+use crate::error::HiveError;
+
+/// Reject a received `Peers` message that breaks `limits`.
+///
+/// Runs on the decoded wire message before any record is validated, so an
+/// oversized response costs no signature recovery.
+pub(crate) fn check_limits(
+    proto: &vertex_swarm_net_proto::hive::Peers,
+    limits: &HiveLimits,
+) -> Result<(), HiveError> {
+    let count = proto.peers.len();
+    if count > limits.max_peers_per_response {
+        return Err(HiveError::TooManyPeers {
+            count,
+            max: limits.max_peers_per_response,
+        });
+    }
+    let size = proto.get_size();
+    if size > limits.max_response_bytes {
+        return Err(HiveError::ResponseTooLarge {
+            size,
+            max: limits.max_response_bytes,
+        });
+    }
+    Ok(())
+}
+
 /// Encode `SwarmPeer` records into a proto `Peers` message for sending.
 pub(crate) fn encode_peers(peers: &[SwarmPeer]) -> vertex_swarm_net_proto::hive::Peers {
     let proto_peers = peers
@@ -20,3 +49,60 @@ pub(crate) fn encode_peers(peers: &[SwarmPeer]) -> vertex_swarm_net_proto::hive:
         .collect();
     vertex_swarm_net_proto::hive::Peers { peers: proto_peers }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw_peer(index: u8) -> vertex_swarm_net_proto::hive::SwarmPeer {
+        vertex_swarm_net_proto::hive::SwarmPeer {
+            multiaddrs: vec![index; 40],
+            signature: vec![index; 65],
+            overlay: vec![index; 32],
+            nonce: vec![index; 32],
+            timestamp: 1,
+            chequebook_address: Vec::new(),
+        }
+    }
+
+    fn peers(count: usize) -> vertex_swarm_net_proto::hive::Peers {
+        vertex_swarm_net_proto::hive::Peers {
+            peers: (0..count).map(|i| raw_peer(i as u8)).collect(),
+        }
+    }
+
+    #[test]
+    fn within_cap_response_is_accepted_whole() {
+        let limits = HiveLimits::default();
+        let proto = peers(limits.max_peers_per_response);
+        assert!(check_limits(&proto, &limits).is_ok());
+        assert_eq!(proto.peers.len(), limits.max_peers_per_response);
+    }
+
+    #[test]
+    fn over_peer_cap_response_is_rejected() {
+        let limits = HiveLimits {
+            max_peers_per_response: 4,
+            ..HiveLimits::default()
+        };
+        let result = check_limits(&peers(5), &limits);
+        assert!(matches!(
+            result,
+            Err(HiveError::TooManyPeers { count: 5, max: 4 })
+        ));
+        assert!(result.is_err_and(|e| e.is_limit_violation()));
+    }
+
+    #[test]
+    fn over_byte_cap_response_is_rejected() {
+        let proto = peers(3);
+        let limits = HiveLimits {
+            max_response_bytes: proto.get_size() - 1,
+            ..HiveLimits::default()
+        };
+        assert!(matches!(
+            check_limits(&proto, &limits),
+            Err(HiveError::ResponseTooLarge { .. })
+        ));
+    }
+}
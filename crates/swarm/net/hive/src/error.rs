@@ -1,4 +1,4 @@
-//! Hive protocol validation errors.
+//! Hive protocol errors.
 
 use metrics::counter;
 use strum::IntoStaticStr;
@@ -29,3 +29,34 @@ impl ValidationFailure {
         counter!("hive_peer_validation_failures_total", "reason" => reason).increment(1);
     }
 }
+
+vertex_net_codec::protocol_error! {
+    /// Hive inbound stream errors.
+    pub enum HiveError {
+        /// Response listed more peer records than the configured cap.
+        #[error("hive response carries {count} peers, max {max}")]
+        TooManyPeers { count: usize, max: usize },
+
+        /// Encoded response exceeded the configured byte cap.
+        #[error("hive response is {size} bytes, max {max}")]
+        ResponseTooLarge { size: usize, max: usize },
+    }
+}
+
+impl HiveError {
+    /// True when the sender broke a response cap, which warrants an adverse
+    /// score; false for transport or decode failures.
+    #[must_use]
+    pub fn is_limit_violation(&self) -> bool {
+        matches!(
+            self,
+            Self::TooManyPeers { .. } | Self::ResponseTooLarge { .. }
+        )
+    }
+}
+
+impl From<vertex_net_codec::StreamClosed> for HiveError {
+    fn from(_: vertex_net_codec::StreamClosed) -> Self {
+        Self::ConnectionClosed
+    }
+}
@@ -27,12 +27,16 @@ use metrics::counter;
 use tracing::{debug, trace, warn};
 use vertex_metrics::labels::direction;
 use vertex_net_ratelimiter::{KeyedRateLimiter, Quota};
-use vertex_swarm_api::SwarmIdentity;
+use vertex_swarm_api::{SwarmIdentity, SwarmSpec};
 use vertex_swarm_net_handler_core::HandlerCore;
-use vertex_swarm_net_headers::{Inbound, Outbound, ProtocolStreamError, UpgradeError};
+use vertex_swarm_net_headers::{
+    Inbound, Outbound, ProtocolError, ProtocolPrefix, ProtocolStreamError, UpgradeError,
+};
 use vertex_swarm_peer::SwarmPeer;
 
+use crate::HiveLimits;
 use crate::cache::PeerCache;
+use crate::error::HiveError;
 use crate::peer_handler::HivePeerHandler;
 use crate::protocol::{HiveInner, HiveOutboundInner, HiveOutboundProtocol};
 
@@ -62,6 +66,8 @@ pub enum HiveCommand {
 pub enum HiveHandlerEvent {
     /// Received peers from this connection.
     PeersReceived(Vec<SwarmPeer>),
+    /// Inbound response broke a [`HiveLimits`] cap.
+    LimitExceeded(ProtocolStreamError),
     /// Error occurred.
     Error(ProtocolStreamError),
 }
@@ -82,6 +88,10 @@ pub struct HiveHandler<I: SwarmIdentity> {
     inbound_limit: Arc<KeyedRateLimiter<PeerId>>,
     /// Consulted by the protocol reader to pick the inbound dispatch policy.
     peer_handler: Arc<dyn HivePeerHandler>,
+    /// Caps applied to every inbound response.
+    limits: HiveLimits,
+    /// Namespace from the identity's spec, applied to both directions.
+    prefix: ProtocolPrefix,
 }
 
 impl<I> HiveHandler<I>
@@ -94,15 +104,18 @@ where
         cache: Arc<PeerCache>,
         inbound_limit: Arc<KeyedRateLimiter<PeerId>>,
         peer_handler: Arc<dyn HivePeerHandler>,
+        limits: HiveLimits,
     ) -> Self {
         Self {
             core: HandlerCore::new(INBOUND_SUBSTREAM_QUOTA),
             remote_peer_id,
+            prefix: ProtocolPrefix::new(identity.spec().protocol_prefix()),
             identity,
             cache,
             pending_broadcasts: VecDeque::new(),
             inbound_limit,
             peer_handler,
+            limits,
         }
     }
 }
@@ -125,8 +138,9 @@ where
             self.remote_peer_id,
             self.inbound_limit.clone(),
             self.peer_handler.clone(),
+            self.limits,
         );
-        let upgrade = Inbound::new(inner);
+        let upgrade = Inbound::new(inner).with_prefix(self.prefix);
         SubstreamProtocol::new(upgrade, ()).with_timeout(STREAM_TIMEOUT)
     }
 
@@ -151,7 +165,7 @@ where
         {
             self.core.set_outbound_pending(true);
             debug!(peer_count = peers.len(), "Sending hive broadcast");
-            let protocol = Outbound::new(HiveOutboundInner::new(&peers));
+            let protocol = Outbound::new(HiveOutboundInner::new(&peers)).with_prefix(self.prefix);
             return Poll::Ready(ConnectionHandlerEvent::OutboundSubstreamRequest {
                 protocol: SubstreamProtocol::new(protocol, ()).with_timeout(STREAM_TIMEOUT),
             });
@@ -208,10 +222,24 @@ where
             }
 
             ConnectionEvent::ListenUpgradeError(ListenUpgradeError { error, .. }) => {
+                // Classify while the error is still typed: a limit violation
+                // is the sender's fault and is scored, a transport error is not.
+                let limit_exceeded = matches!(
+                    &error,
+                    ProtocolError::Protocol(inner)
+                        if inner
+                            .downcast_ref::<HiveError>()
+                            .is_some_and(HiveError::is_limit_violation)
+                );
                 let hive_error =
                     UpgradeError::record_and_convert(error, "hive", direction::INBOUND);
                 warn!(error = %hive_error, "Hive inbound stream error");
-                self.core.push_event(HiveHandlerEvent::Error(hive_error));
+                let event = if limit_exceeded {
+                    HiveHandlerEvent::LimitExceeded(hive_error)
+                } else {
+                    HiveHandlerEvent::Error(hive_error)
+                };
+                self.core.push_event(event);
             }
 
             ConnectionEvent::DialUpgradeError(error) => {
@@ -27,9 +27,10 @@
 //!    `hive_peers_discarded_total{reason="bootnode_mode"}` on the raw wire
 //!    count.
 //!
-//! There is no outbound rate limit. The local topology already throttles its
-//! own broadcast cadence via dial / save intervals, and the payload is bounded
-//! by [`MAX_BATCH_SIZE`]; an outbound per-peer bucket measurably starved
+//! There is no outbound rate limit here. The local topology paces its own
+//! broadcasts (refresh and announce intervals, and a global budget on
+//! exchanges with newly activated peers), and the payload is bounded by
+//! [`MAX_BATCH_SIZE`]; an outbound per-peer bucket measurably starved
 //! legitimate gossip cycles after a few neighborhood refreshes.
 //!
 //! # Protocol assumptions not in the Book of Swarm
@@ -39,6 +40,12 @@
 //! - [`MAX_BATCH_SIZE`] = 30 peers per wire message, sized so the encoded
 //!   protobuf fits comfortably within the framed read buffer alongside the
 //!   header exchange.
+//! - Inbound responses are capped by [`HiveLimits`]: by default at most
+//!   [`MAX_BATCH_SIZE`] records and [`MAX_RESPONSE_BYTES`] encoded bytes. The
+//!   caps are checked in the codec on the raw message, ahead of the rate
+//!   limiter and signature recovery, so an oversized response is rejected with
+//!   [`HiveError`] without validating any of its entries, and the topology
+//!   scores the sender down.
 //! - Wire protocol id [`PROTOCOL_NAME`] = `/swarm/hive/2.0.0/peers`. The
 //!   `2.0.0` bump tracks the [`SwarmPeer`] record extension to include
 //!   timestamp + chequebook; see the `vertex-swarm-peer` crate for the
@@ -59,7 +66,7 @@ mod peer_handler;
 mod protocol;
 
 pub use behaviour::{HiveBehaviour, HiveEvent};
-pub use error::ValidationFailure;
+pub use error::{HiveError, ValidationFailure};
 pub use peer_handler::{DiscardSilently, HivePeerHandler, InboundPolicy, LearnAndDial};
 
 /// Protocol name for hive.
@@ -68,6 +75,29 @@ pub const PROTOCOL_NAME: &str = "/swarm/hive/2.0.0/peers";
 /// Maximum number of peers per broadcast message.
 pub const MAX_BATCH_SIZE: usize = 30;
 
+/// Frame ceiling for a hive response; the configurable byte cap in
+/// [`HiveLimits`] can only tighten it.
+pub const MAX_RESPONSE_BYTES: usize = 32 * 1024;
+
+/// Caps on a single inbound hive response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HiveLimits {
+    /// Maximum peer records accepted in one response.
+    pub max_peers_per_response: usize,
+    /// Maximum encoded response size in bytes. Values above
+    /// [`MAX_RESPONSE_BYTES`] have no further effect.
+    pub max_response_bytes: usize,
+}
+
+impl Default for HiveLimits {
+    fn default() -> Self {
+        Self {
+            max_peers_per_response: MAX_BATCH_SIZE,
+            max_response_bytes: MAX_RESPONSE_BYTES,
+        }
+    }
+}
+
 /// Per-peer inbound quota for hive batches: 128 token burst, fully replenished
 /// every 60 seconds (i.e. one token every ~0.47 s). 128 = 4 * `MaxBins`.
 pub const HIVE_INBOUND_QUOTA: Quota =
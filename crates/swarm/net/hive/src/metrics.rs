@@ -14,6 +14,8 @@ use vertex_metrics::{DURATION_FINE, HistogramBucketConfig};
 ///   on the raw wire peer count.
 /// - `reason="verifier_rejected"` - a peer record failed signature or
 ///   overlay validation.
+/// - `reason="limit_exceeded"` - the response broke a `HiveLimits` cap;
+///   counted on the raw wire peer count.
 pub const HIVE_PEERS_DISCARDED_TOTAL: &str = "hive_peers_discarded_total";
 
 /// Label value for [`HIVE_PEERS_DISCARDED_TOTAL`]: bootnode-mode discard.
@@ -25,6 +27,9 @@ pub const DISCARD_REASON_RATE_LIMITED: &str = "rate_limited";
 /// Label value for [`HIVE_PEERS_DISCARDED_TOTAL`]: verifier-rejected discard.
 pub const DISCARD_REASON_VERIFIER_REJECTED: &str = "verifier_rejected";
 
+/// Label value for [`HIVE_PEERS_DISCARDED_TOTAL`]: response over a cap.
+pub const DISCARD_REASON_LIMIT_EXCEEDED: &str = "limit_exceeded";
+
 /// Histogram bucket configurations for hive-specific metrics.
 pub const HISTOGRAM_BUCKETS: &[HistogramBucketConfig] = &[
     HistogramBucketConfig {
@@ -19,21 +19,19 @@ use vertex_swarm_primitives::{NetworkId, Nonce};
 use vertex_tasks::TaskExecutor;
 use vertex_util_runtime::time::Instant;
 
-use crate::PROTOCOL_NAME;
 use crate::cache::PeerCache;
-use crate::codec::encode_peers;
-use crate::error::ValidationFailure;
+use crate::codec::{check_limits, encode_peers};
+use crate::error::{HiveError, ValidationFailure};
 use crate::metrics::{
-    DISCARD_REASON_BOOTNODE_MODE, DISCARD_REASON_RATE_LIMITED, DISCARD_REASON_VERIFIER_REJECTED,
-    HIVE_PEERS_DISCARDED_TOTAL,
+    DISCARD_REASON_BOOTNODE_MODE, DISCARD_REASON_LIMIT_EXCEEDED, DISCARD_REASON_RATE_LIMITED,
+    DISCARD_REASON_VERIFIER_REJECTED, HIVE_PEERS_DISCARDED_TOTAL,
 };
 use crate::peer_handler::{HivePeerHandler, InboundPolicy};
+use crate::{HiveLimits, MAX_RESPONSE_BYTES, PROTOCOL_NAME};
 use vertex_net_ratelimiter::KeyedRateLimiter;
 
 /// 32 KiB frame limit (fits ~100 peers at typical size).
-const MAX_MESSAGE_SIZE: usize = 32 * 1024;
-
-type Framed = FramedProto<MAX_MESSAGE_SIZE>;
+type Framed = FramedProto<MAX_RESPONSE_BYTES>;
 
 /// Result of inbound peer validation.
 #[derive(Debug)]
@@ -48,6 +46,7 @@ pub struct HiveInner<I: SwarmIdentity> {
     remote_peer_id: PeerId,
     inbound_limit: Arc<KeyedRateLimiter<PeerId>>,
     peer_handler: Arc<dyn HivePeerHandler>,
+    limits: HiveLimits,
 }
 
 impl<I: SwarmIdentity> std::fmt::Debug for HiveInner<I> {
@@ -63,6 +62,7 @@ impl<I: SwarmIdentity> HiveInner<I> {
         remote_peer_id: PeerId,
         inbound_limit: Arc<KeyedRateLimiter<PeerId>>,
         peer_handler: Arc<dyn HivePeerHandler>,
+        limits: HiveLimits,
     ) -> Self {
         Self {
             identity,
@@ -70,13 +70,14 @@ impl<I: SwarmIdentity> HiveInner<I> {
             remote_peer_id,
             inbound_limit,
             peer_handler,
+            limits,
         }
     }
 }
 
 impl<I: SwarmIdentity> HeaderedInbound for HiveInner<I> {
     type Output = ValidatedPeers;
-    type Error = ProtocolStreamError;
+    type Error = HiveError;
 
     fn protocol_name(&self) -> &'static str {
         PROTOCOL_NAME
@@ -89,12 +90,21 @@ impl<I: SwarmIdentity> HeaderedInbound for HiveInner<I> {
         let remote_peer_id = self.remote_peer_id;
         let inbound_limit = self.inbound_limit;
         let peer_handler = self.peer_handler;
+        let limits = self.limits;
         Box::pin(async move {
             use vertex_swarm_net_proto::hive::Peers;
 
             debug!(%network_id, "Hive: reading peers");
-            let (proto, _) =
-                Framed::recv::<Peers, ProtocolStreamError, _>(stream.into_inner()).await?;
+            let (proto, _) = Framed::recv::<Peers, HiveError, _>(stream.into_inner()).await?;
+
+            // Reject an oversized response outright, before it is charged
+            // against the rate limiter or any record is validated.
+            if let Err(error) = check_limits(&proto, &limits) {
+                counter!(HIVE_PEERS_DISCARDED_TOTAL, "reason" => DISCARD_REASON_LIMIT_EXCEEDED)
+                    .increment(proto.peers.len() as u64);
+                warn!(%remote_peer_id, %error, "hive: response exceeds limits");
+                return Err(error);
+            }
 
             let raw_peers = proto.peers;
             let raw_count = raw_peers.len();
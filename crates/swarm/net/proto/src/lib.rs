@@ -5,6 +5,7 @@ mod generated {
     include!(concat!(env!("OUT_DIR"), "/proto/mod.rs"));
 }
 
+pub use generated::goodbye;
 pub use generated::handshake;
 pub use generated::headers;
 pub use generated::hive;
@@ -69,15 +69,40 @@ impl ProtoMessage for Ack {
 
 /// Request the chunks in `bin` from bin id `start` (inclusive) upward. The wire
 /// `bin` field is `int32`; a value outside `0..=MAX_PO` is a decode error.
+///
+/// `limit` caps the offered page; `None` (zero on the wire) leaves the page
+/// size to the responder. The field is only spoken once Accord is active, so
+/// the handler sends and honours it behind that hardfork.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Get {
     pub bin: Bin,
     pub start: u64,
+    pub limit: Option<u64>,
 }
 
 impl Get {
     pub fn new(bin: Bin, start: u64) -> Self {
-        Self { bin, start }
+        Self {
+            bin,
+            start,
+            limit: None,
+        }
+    }
+
+    /// Ask the responder to offer at most `limit` descriptors. Zero clears the
+    /// cap, since it is indistinguishable from an absent field on the wire.
+    #[must_use]
+    pub fn with_limit(mut self, limit: u64) -> Self {
+        self.limit = (limit > 0).then_some(limit);
+        self
+    }
+
+    /// Descriptors to offer for this request: the requested cap, never above
+    /// [`DEFAULT_MAX_PAGE`](crate::DEFAULT_MAX_PAGE).
+    pub fn page_size(&self) -> u64 {
+        self.limit.map_or(crate::DEFAULT_MAX_PAGE, |limit| {
+            limit.min(crate::DEFAULT_MAX_PAGE)
+        })
     }
 }
 
@@ -90,6 +115,7 @@ impl ProtoMessage for Get {
         Ok(vertex_swarm_net_proto::pullsync::Get {
             bin: i32::from(self.bin.get()),
             start: self.start,
+            limit: self.limit.unwrap_or_default(),
         })
     }
 
@@ -99,6 +125,7 @@ impl ProtoMessage for Get {
         Ok(Self {
             bin,
             start: proto.start,
+            limit: (proto.limit > 0).then_some(proto.limit),
         })
     }
 }
@@ -323,16 +350,38 @@ mod tests {
         assert_proto_roundtrip!(Get::new(Bin::new(11).expect("valid bin"), 42));
     }
 
+    #[test]
+    fn get_limit_roundtrip_and_page_size() {
+        let bin = Bin::new(3).expect("valid bin");
+        assert_proto_roundtrip!(Get::new(bin, 7).with_limit(64));
+
+        assert_eq!(Get::new(bin, 0).page_size(), crate::DEFAULT_MAX_PAGE);
+        assert_eq!(Get::new(bin, 0).with_limit(64).page_size(), 64);
+        assert_eq!(
+            Get::new(bin, 0).with_limit(10_000).page_size(),
+            crate::DEFAULT_MAX_PAGE
+        );
+        assert_eq!(Get::new(bin, 0).with_limit(0).limit, None);
+    }
+
     #[test]
     fn get_rejects_out_of_range_bin() {
-        let proto = vertex_swarm_net_proto::pullsync::Get { bin: 99, start: 0 };
+        let proto = vertex_swarm_net_proto::pullsync::Get {
+            bin: 99,
+            start: 0,
+            limit: 0,
+        };
         let err = Get::from_proto(proto).expect_err("bin 99 exceeds MAX_PO");
         assert!(matches!(err, PullsyncError::InvalidBin(99)));
     }
 
     #[test]
     fn get_rejects_negative_bin() {
-        let proto = vertex_swarm_net_proto::pullsync::Get { bin: -1, start: 0 };
+        let proto = vertex_swarm_net_proto::pullsync::Get {
+            bin: -1,
+            start: 0,
+            limit: 0,
+        };
         let err = Get::from_proto(proto).expect_err("a negative bin is invalid");
         assert!(matches!(err, PullsyncError::InvalidBin(-1)));
     }
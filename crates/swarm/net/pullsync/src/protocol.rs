@@ -42,8 +42,8 @@ const MAX_CHUNK_DATA_SIZE: usize = SPAN_SIZE + HASH_SIZE + SOC_SIGNATURE_SIZE +
 /// space and framing.
 const MAX_HANDSHAKE_SIZE: usize = 8 * (nectar_primitives::Bin::COUNT + 1) + PROTOBUF_FRAMING;
 
-/// Accept-limit for a `Get`: a bin and a start cursor.
-const MAX_GET_SIZE: usize = 8 + 8 + PROTOBUF_FRAMING;
+/// Accept-limit for a `Get`: a bin, a start cursor and a page limit.
+const MAX_GET_SIZE: usize = 8 + 8 + 8 + PROTOBUF_FRAMING;
 
 /// Accept-limit for an `Offer` page: a full page of descriptors plus the
 /// `topmost` cursor and framing. A local DoS guard, not wire-visible.
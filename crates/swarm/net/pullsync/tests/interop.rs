@@ -16,7 +16,7 @@
 use alloy_primitives::{B256, Signature};
 use nectar_postage::Stamp;
 use nectar_primitives::{AnyChunk, ChunkAddress, ContentChunk};
-use quick_protobuf::Writer;
+use quick_protobuf::{BytesReader, MessageRead, Writer};
 use vertex_net_codec::ProtoMessage;
 use vertex_swarm_primitives::{Bin, StampedChunk};
 
@@ -35,6 +35,17 @@ where
     out
 }
 
+/// Decode raw protobuf bytes (no length framing) into a domain message.
+fn from_proto_bytes<M>(bytes: &[u8]) -> M
+where
+    M: ProtoMessage,
+    M::DecodeError: std::fmt::Debug,
+{
+    let mut reader = BytesReader::from_bytes(bytes);
+    let proto = M::Proto::from_reader(&mut reader, bytes).expect("read");
+    M::from_proto(proto).expect("decode")
+}
+
 fn descriptor(addr: u8, batch: u8, hash: u8) -> ChunkDescriptor {
     ChunkDescriptor::new(
         ChunkAddress::new([addr; 32]),
@@ -72,6 +83,32 @@ fn get_high_bin_roundtrips() {
     assert_eq!(decoded.start, 1_000_000);
 }
 
+/// Before Accord a `Get` carries only `bin` and `start`, and no `limit` bytes.
+#[test]
+fn get_without_limit_fixed_bytes() {
+    let bin = Bin::new(3).unwrap();
+    // field 1 (bin) = 3, field 2 (start) = 7.
+    let expected = [0x08, 0x03, 0x10, 0x07];
+    assert_eq!(proto_bytes(Get::new(bin, 7)), expected);
+    assert_eq!(proto_bytes(Get::new(bin, 7).with_limit(0)), expected);
+
+    let decoded: Get = from_proto_bytes(&expected);
+    assert_eq!(decoded, Get::new(bin, 7));
+    assert_eq!(decoded.limit, None);
+}
+
+/// The Accord `limit` is field 100, varint: tag `(100 << 3) | 0` = 800, which
+/// encodes as `0xa0 0x06`, after the two pre-Accord fields.
+#[test]
+fn get_with_limit_fixed_bytes() {
+    let bin = Bin::new(3).unwrap();
+    let expected = [0x08, 0x03, 0x10, 0x07, 0xa0, 0x06, 0x40];
+    let get = Get::new(bin, 7).with_limit(64);
+    assert_eq!(proto_bytes(get), expected);
+    assert_eq!(from_proto_bytes::<Get>(&expected), get);
+    assert_eq!(get.page_size(), 64);
+}
+
 /// An offer of two descriptors pins the repeated-`Chunk` layout: each chunk is a
 /// length-delimited submessage carrying three 32-byte fields in field order.
 #[test]
@@ -2,9 +2,9 @@
 
 use alloy_primitives::Signature;
 use bytes::Bytes;
-use nectar_primitives::{ChunkAddress, Nonce};
+use nectar_primitives::{AnyChunk, ChunkAddress, Nonce};
 use vertex_net_codec::{Codec, ProtoMessage};
-use vertex_swarm_primitives::{Bin, StampedChunk, StorageRadius};
+use vertex_swarm_primitives::{Bin, Stamp, StampedChunk, StorageRadius};
 
 use crate::error::PushsyncError;
 
@@ -16,25 +16,45 @@ pub(crate) type ReceiptCodec = Codec<ReceiptResponse, PushsyncError>;
 
 /// Delivery of a chunk to be stored.
 ///
-/// Carries the chunk and its postage stamp as one [`StampedChunk`]. The wire
-/// `address` field is the chunk's own address; on decode it disambiguates and
-/// validates the reconstructed chunk.
+/// The wire `address` field is the chunk's own address; on decode it
+/// disambiguates and validates the reconstructed chunk.
 ///
-/// The pairing is boxed: a [`StampedChunk`] is large, and boxing it here keeps
-/// the message enums that carry a delivery small.
+/// An empty `stamp` field decodes to `None` rather than failing: the codec has
+/// no view of the network's stamp policy, so the receiving handler refuses an
+/// unstamped delivery unless it is a storer on a network where storage is free.
+///
+/// The chunk is boxed: it is large, and boxing it here keeps the message enums
+/// that carry a delivery small.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Delivery {
-    /// The chunk paired with its postage stamp.
-    pub chunk: Box<StampedChunk>,
+    /// The delivered chunk.
+    pub chunk: Box<AnyChunk>,
+    /// The postage stamp, if the pusher attached one.
+    pub stamp: Option<Stamp>,
 }
 
 impl Delivery {
     /// Create a new delivery.
     pub fn new(chunk: StampedChunk) -> Self {
+        let (chunk, stamp) = chunk.into_parts();
         Self {
             chunk: Box::new(chunk),
+            stamp: Some(stamp),
         }
     }
+
+    /// Create a delivery with no stamp.
+    pub fn unstamped(chunk: AnyChunk) -> Self {
+        Self {
+            chunk: Box::new(chunk),
+            stamp: None,
+        }
+    }
+
+    /// Split into the chunk and its stamp, if any.
+    pub fn into_parts(self) -> (AnyChunk, Option<Stamp>) {
+        (*self.chunk, self.stamp)
+    }
 }
 
 impl ProtoMessage for Delivery {
@@ -44,11 +64,13 @@ impl ProtoMessage for Delivery {
 
     fn into_proto(self) -> Result<Self::Proto, Self::EncodeError> {
         let address = *self.chunk.address();
-        let (chunk, stamp) = (*self.chunk).into_parts();
         Ok(vertex_swarm_net_proto::pushsync::Delivery {
             address: address.to_vec(),
-            data: chunk.into_bytes().to_vec(),
-            stamp: stamp.to_bytes().to_vec(),
+            data: (*self.chunk).into_bytes().to_vec(),
+            stamp: self
+                .stamp
+                .map(|stamp| stamp.to_bytes().to_vec())
+                .unwrap_or_default(),
         })
     }
 
@@ -57,10 +79,17 @@ impl ProtoMessage for Delivery {
             return Err(PushsyncError::InvalidAddressLength(proto.address.len()));
         }
         let address = ChunkAddress::from_slice(&proto.address)?;
-        let stamp = nectar_postage::Stamp::try_from_slice(&proto.stamp)?;
-        let chunk = StampedChunk::reconstruct(address, Bytes::from(proto.data), stamp)
+        let stamp = if proto.stamp.is_empty() {
+            None
+        } else {
+            Some(Stamp::try_from_slice(&proto.stamp)?)
+        };
+        let chunk = AnyChunk::from_wire_bytes(&address, Bytes::from(proto.data))
             .map_err(|e| PushsyncError::InvalidChunk(e.to_string()))?;
-        Ok(Self::new(chunk))
+        Ok(Self {
+            chunk: Box::new(chunk),
+            stamp,
+        })
     }
 }
 
@@ -189,8 +218,7 @@ impl ProtoMessage for ReceiptResponse {
 mod tests {
     use super::*;
     use alloy_primitives::B256;
-    use nectar_postage::Stamp;
-    use nectar_primitives::{AnyChunk, ContentChunk};
+    use nectar_primitives::ContentChunk;
     use vertex_net_codec::assert_proto_roundtrip;
 
     /// A stamp with a deterministic, well-formed signature for roundtrip tests.
@@ -231,7 +259,16 @@ mod tests {
         assert_eq!(Bytes::from(proto.data.clone()), wire_data);
         let decoded = Delivery::from_proto(proto).unwrap();
         assert_eq!(*decoded.chunk.address(), address);
-        assert_eq!(decoded.chunk.chunk().clone().into_bytes(), wire_data);
+        assert_eq!((*decoded.chunk).into_bytes(), wire_data);
+    }
+
+    #[test]
+    fn test_unstamped_delivery_roundtrip() {
+        let (chunk, _) = test_stamped_chunk().into_parts();
+        let proto = Delivery::unstamped(chunk.clone()).into_proto().unwrap();
+        assert!(proto.stamp.is_empty());
+        let decoded = Delivery::from_proto(proto).unwrap();
+        assert_eq!(decoded.into_parts(), (chunk, None));
     }
 
     #[test]
@@ -38,6 +38,10 @@ vertex_net_codec::protocol_error! {
         /// all-zero structural-failure signature). Rejected at the decode boundary.
         #[error("custody receipt signature did not recover to a signer overlay")]
         MalformedReceiptSignature,
+
+        /// A compressed frame could not be written or read.
+        #[error("compression error: {0}")]
+        Compression(#[from] vertex_net_codec::CompressionError),
     }
 }
 
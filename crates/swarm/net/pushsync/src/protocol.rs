@@ -13,6 +13,7 @@ use futures::{SinkExt, TryStreamExt, future::BoxFuture};
 use nectar_postage::STAMP_SIZE;
 use nectar_primitives::bmt::{DEFAULT_BODY_SIZE, HASH_SIZE, SPAN_SIZE};
 use tracing::debug;
+use vertex_net_codec::{Compressed, Compression};
 use vertex_swarm_net_headers::{
     HeaderedInbound, HeaderedOutbound, HeaderedStream, Inbound, Outbound,
 };
@@ -47,7 +48,9 @@ const MAX_MESSAGE_SIZE: usize = HASH_SIZE + MAX_CHUNK_DATA_SIZE + STAMP_SIZE + P
 
 /// Pushsync inbound: receives a chunk delivery from remote.
 #[derive(Debug, Clone)]
-pub struct PushsyncInboundInner;
+pub struct PushsyncInboundInner {
+    compression: Compression,
+}
 
 impl HeaderedInbound for PushsyncInboundInner {
     type Output = (Delivery, PushsyncResponder);
@@ -59,7 +62,11 @@ impl HeaderedInbound for PushsyncInboundInner {
 
     fn read(self, stream: HeaderedStream) -> BoxFuture<'static, Result<Self::Output, Self::Error>> {
         Box::pin(async move {
-            let codec = DeliveryCodec::new(MAX_MESSAGE_SIZE);
+            let codec = Compressed::new(
+                DeliveryCodec::new(MAX_MESSAGE_SIZE),
+                self.compression,
+                MAX_MESSAGE_SIZE,
+            );
             let mut framed = Framed::new(stream.into_inner(), codec);
 
             debug!("Pushsync: Reading chunk delivery");
@@ -74,7 +81,14 @@ impl HeaderedInbound for PushsyncInboundInner {
             // Use into_parts() to preserve any buffered data across the codec switch.
             let parts = framed.into_parts();
             let responder = PushsyncResponder {
-                framed: Framed::new(parts.io, ReceiptCodec::new(MAX_MESSAGE_SIZE)),
+                framed: Framed::new(
+                    parts.io,
+                    Compressed::new(
+                        ReceiptCodec::new(MAX_MESSAGE_SIZE),
+                        self.compression,
+                        MAX_MESSAGE_SIZE,
+                    ),
+                ),
             };
 
             Ok((delivery, responder))
@@ -84,7 +98,7 @@ impl HeaderedInbound for PushsyncInboundInner {
 
 /// Handle for sending a receipt response.
 pub struct PushsyncResponder {
-    framed: Framed<libp2p::Stream, ReceiptCodec>,
+    framed: Framed<libp2p::Stream, Compressed<ReceiptCodec>>,
 }
 
 impl PushsyncResponder {
@@ -108,12 +122,17 @@ impl PushsyncResponder {
 #[derive(Debug, Clone)]
 pub struct PushsyncOutboundInner {
     delivery: Delivery,
+    compression: Compression,
 }
 
 impl PushsyncOutboundInner {
-    /// Create a new outbound pushsync with the given delivery.
-    pub fn new(delivery: Delivery) -> Self {
-        Self { delivery }
+    /// Create a new outbound pushsync with the given delivery, framed with
+    /// `compression`.
+    pub fn new(delivery: Delivery, compression: Compression) -> Self {
+        Self {
+            delivery,
+            compression,
+        }
     }
 }
 
@@ -131,7 +150,11 @@ impl HeaderedOutbound for PushsyncOutboundInner {
     ) -> BoxFuture<'static, Result<Self::Output, Self::Error>> {
         Box::pin(async move {
             // Send the delivery
-            let delivery_codec = DeliveryCodec::new(MAX_MESSAGE_SIZE);
+            let delivery_codec = Compressed::new(
+                DeliveryCodec::new(MAX_MESSAGE_SIZE),
+                self.compression,
+                MAX_MESSAGE_SIZE,
+            );
             let mut framed = Framed::new(stream.into_inner(), delivery_codec);
 
             debug!(chunk_address = %self.delivery.chunk.address(), "Pushsync: Sending chunk delivery");
@@ -140,7 +163,11 @@ impl HeaderedOutbound for PushsyncOutboundInner {
             // Switch to receipt codec and read response.
             // Use into_parts() to preserve any buffered data across the codec switch.
             let parts = framed.into_parts();
-            let receipt_codec = ReceiptCodec::new(MAX_MESSAGE_SIZE);
+            let receipt_codec = Compressed::new(
+                ReceiptCodec::new(MAX_MESSAGE_SIZE),
+                self.compression,
+                MAX_MESSAGE_SIZE,
+            );
             let mut framed = Framed::new(parts.io, receipt_codec);
 
             debug!("Pushsync: Reading receipt");
@@ -158,12 +185,14 @@ pub type PushsyncInboundProtocol = Inbound<PushsyncInboundInner>;
 /// Outbound protocol type for handler.
 pub type PushsyncOutboundProtocol = Outbound<PushsyncOutboundInner>;
 
-/// Create an inbound protocol handler.
-pub fn inbound() -> PushsyncInboundProtocol {
-    Inbound::new(PushsyncInboundInner)
+/// Create an inbound protocol handler framing with the connection's
+/// negotiated `compression`.
+pub fn inbound(compression: Compression) -> PushsyncInboundProtocol {
+    Inbound::new(PushsyncInboundInner { compression })
 }
 
-/// Create an outbound protocol handler for the given delivery.
-pub fn outbound(delivery: Delivery) -> PushsyncOutboundProtocol {
-    Outbound::new(PushsyncOutboundInner::new(delivery))
+/// Create an outbound protocol handler for the given delivery, framing with the
+/// connection's negotiated `compression`.
+pub fn outbound(delivery: Delivery, compression: Compression) -> PushsyncOutboundProtocol {
+    Outbound::new(PushsyncOutboundInner::new(delivery, compression))
 }
@@ -8,28 +8,68 @@
 //! delivery is reconstructed and validated against the requested address, so a
 //! mismatch is a decode error rather than a silently-wrong chunk.
 
+use std::time::Duration;
+
 use asynchronous_codec::{Decoder, Encoder};
 use bytes::{Bytes, BytesMut};
 use nectar_primitives::{AnyChunk, ChunkAddress};
 use vertex_net_codec::{Codec, ProtoMessage};
-use vertex_swarm_primitives::{Stamp, StampedChunk};
+use vertex_swarm_primitives::{Cacheability, Stamp, StampedChunk};
 
 use crate::error::RetrievalError;
 
 /// Codec for retrieval request messages.
-pub(crate) type RequestCodec = Codec<Request, RetrievalError>;
+pub type RequestCodec = Codec<Request, RetrievalError>;
+
+/// Hop budget an originated request starts with when the config sets none.
+pub const DEFAULT_RETRIEVAL_TTL: u8 = 16;
 
 /// A request for a chunk by its address.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Request {
     /// The address of the chunk to retrieve.
     pub address: ChunkAddress,
+    /// Forwarding hops left; `None` when the sender set no budget.
+    pub ttl: Option<u8>,
+    /// How long the sender will still wait for the delivery; `None` when it
+    /// set no deadline. Carried at millisecond precision.
+    pub deadline: Option<Duration>,
 }
 
 impl Request {
-    /// Create a new retrieval request.
+    /// Create a new retrieval request with no hop or time budget.
     pub fn new(address: ChunkAddress) -> Self {
-        Self { address }
+        Self {
+            address,
+            ttl: None,
+            deadline: None,
+        }
+    }
+
+    /// Set the hop budget carried on the wire.
+    #[must_use]
+    pub fn with_ttl(mut self, ttl: u8) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Set the time budget carried on the wire.
+    #[must_use]
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// The budget to put on the request if this node forwards it: one less
+    /// than received, or than `default` when the sender set none.
+    ///
+    /// A request that arrives with nothing left fails with
+    /// [`RetrievalError::TtlExceeded`]; it may still be served locally.
+    pub fn forward_ttl(&self, default: u8) -> Result<u8, RetrievalError> {
+        self.ttl
+            .unwrap_or(default)
+            .checked_sub(1)
+            .ok_or(RetrievalError::TtlExceeded)
     }
 }
 
@@ -41,6 +81,14 @@ impl ProtoMessage for Request {
     fn into_proto(self) -> Result<Self::Proto, Self::EncodeError> {
         Ok(vertex_swarm_net_proto::retrieval::Request {
             addr: self.address.to_vec(),
+            ttl: self.ttl.map(|ttl| vec![ttl]).unwrap_or_default(),
+            // Zero is "no deadline" on the wire, so a sub-millisecond budget
+            // rounds up rather than vanishing.
+            deadline_ms: self.deadline.map_or(0, |deadline| {
+                u32::try_from(deadline.as_millis())
+                    .unwrap_or(u32::MAX)
+                    .max(1)
+            }),
         })
     }
 
@@ -49,7 +97,18 @@ impl ProtoMessage for Request {
             return Err(RetrievalError::InvalidAddressLength(proto.addr.len()));
         }
         let address = ChunkAddress::from_slice(&proto.addr)?;
-        Ok(Self { address })
+        let ttl = match proto.ttl.as_slice() {
+            [] => None,
+            [ttl] => Some(*ttl),
+            other => return Err(RetrievalError::InvalidTtlLength(other.len())),
+        };
+        let deadline =
+            (proto.deadline_ms > 0).then(|| Duration::from_millis(u64::from(proto.deadline_ms)));
+        Ok(Self {
+            address,
+            ttl,
+            deadline,
+        })
     }
 }
 
@@ -108,6 +167,18 @@ impl Delivery {
         matches!(self, Self::Error)
     }
 
+    /// How long the delivered chunk may be cached, `None` for a failure.
+    ///
+    /// Read from the validated chunk, not the wire: a retrieval delivery ships
+    /// no stamp, so a mutable chunk arrives with no version signal and is not
+    /// cached.
+    pub fn cacheability(&self) -> Option<Cacheability> {
+        match self {
+            Self::Chunk { chunk, .. } => Some(Cacheability::of(chunk)),
+            Self::Error => None,
+        }
+    }
+
     /// Encode this delivery to its protobuf wire form. The stamp field is always
     /// left empty: a retrieval delivery ships the chunk `data` only, and any stamp
     /// the chunk arrived with is dropped at the first forwarder hop. The requester
@@ -244,6 +315,69 @@ mod tests {
     #[test]
     fn test_request_roundtrip() {
         assert_proto_roundtrip!(Request::new(ChunkAddress::new([0x42; 32])));
+        assert_proto_roundtrip!(Request::new(ChunkAddress::new([0x42; 32])).with_ttl(3));
+        assert_proto_roundtrip!(
+            Request::new(ChunkAddress::new([0x42; 32])).with_deadline(Duration::from_millis(1500))
+        );
+    }
+
+    /// Encode a request and decode it as the next hop would.
+    fn relay(request: Request) -> Request {
+        let mut codec = RequestCodec::new(1024);
+        let mut buf = BytesMut::new();
+        codec.encode(request, &mut buf).unwrap();
+        codec.decode(&mut buf).unwrap().expect("frame decoded")
+    }
+
+    /// A request sent with TTL 1 is forwarded once with TTL 0, and the second
+    /// hop refuses to forward it further.
+    #[test]
+    fn ttl_is_spent_after_two_hops() {
+        let address = ChunkAddress::new([0x42; 32]);
+
+        let first = relay(Request::new(address).with_ttl(1));
+        assert_eq!(first.ttl, Some(1));
+        let next = first.forward_ttl(DEFAULT_RETRIEVAL_TTL).unwrap();
+        assert_eq!(next, 0);
+
+        let second = relay(Request::new(address).with_ttl(next));
+        assert_eq!(second.ttl, Some(0));
+        assert!(matches!(
+            second.forward_ttl(DEFAULT_RETRIEVAL_TTL),
+            Err(RetrievalError::TtlExceeded)
+        ));
+    }
+
+    /// A request without a budget (the pre-Accord shape) takes the local
+    /// default, and a multi-byte TTL field is rejected.
+    #[test]
+    fn absent_ttl_uses_default_and_malformed_ttl_fails() {
+        let address = ChunkAddress::new([0x42; 32]);
+        let request = relay(Request::new(address));
+        assert_eq!(request.ttl, None);
+        assert_eq!(request.forward_ttl(4).unwrap(), 3);
+
+        let proto = vertex_swarm_net_proto::retrieval::Request {
+            addr: address.to_vec(),
+            ttl: vec![1, 2],
+            deadline_ms: 0,
+        };
+        assert!(matches!(
+            Request::from_proto(proto),
+            Err(RetrievalError::InvalidTtlLength(2))
+        ));
+    }
+
+    /// A request without a deadline (the pre-Accord shape) decodes with none,
+    /// and a sub-millisecond budget is not mistaken for "no deadline".
+    #[test]
+    fn absent_deadline_decodes_as_none() {
+        let address = ChunkAddress::new([0x42; 32]);
+        assert_eq!(relay(Request::new(address)).deadline, None);
+        assert_eq!(
+            relay(Request::new(address).with_deadline(Duration::from_micros(10))).deadline,
+            Some(Duration::from_millis(1))
+        );
     }
 
     /// Encode a delivery and decode it back through the address-aware codec.
@@ -21,6 +21,22 @@ vertex_net_codec::protocol_error! {
         /// is already claimed by `InvalidAddress` via `#[from]`.
         #[error("invalid chunk: {0}")]
         InvalidChunk(String),
+
+        /// The request's hop budget is spent, so it may not be forwarded.
+        #[error("retrieval TTL exceeded")]
+        TtlExceeded,
+
+        /// The request's time budget is spent, so it may not be forwarded.
+        #[error("retrieval deadline exceeded")]
+        DeadlineExceeded,
+
+        /// The TTL field was not a single byte.
+        #[error("invalid TTL length: expected 1, got {0}")]
+        InvalidTtlLength(usize),
+
+        /// A compressed frame could not be written or read.
+        #[error("compression error: {0}")]
+        Compression(#[from] vertex_net_codec::CompressionError),
     }
 }
 
@@ -1,7 +1,7 @@
 //! Retrieval protocol for Swarm chunk request and delivery.
 
 mod codec;
-pub use codec::{Delivery, Request};
+pub use codec::{DEFAULT_RETRIEVAL_TTL, Delivery, Request, RequestCodec};
 
 mod error;
 pub use error::RetrievalError;
@@ -11,11 +11,9 @@
 use asynchronous_codec::Framed;
 use futures::{SinkExt, TryStreamExt, future::BoxFuture};
 use nectar_postage::STAMP_SIZE;
-use nectar_primitives::{
-    ChunkAddress,
-    bmt::{DEFAULT_BODY_SIZE, HASH_SIZE, SPAN_SIZE},
-};
+use nectar_primitives::bmt::{DEFAULT_BODY_SIZE, HASH_SIZE, SPAN_SIZE};
 use tracing::debug;
+use vertex_net_codec::{Compressed, Compression};
 use vertex_swarm_net_headers::{
     HeaderedInbound, HeaderedOutbound, HeaderedStream, Inbound, Outbound,
 };
@@ -61,7 +59,9 @@ const MAX_DELIVERY_SIZE: usize = MAX_CHUNK_DATA_SIZE + STAMP_SIZE + PROTOBUF_FRA
 
 /// Retrieval inbound: receives a chunk request from remote.
 #[derive(Debug, Clone)]
-pub struct RetrievalInboundInner;
+pub struct RetrievalInboundInner {
+    compression: Compression,
+}
 
 impl HeaderedInbound for RetrievalInboundInner {
     type Output = (Request, RetrievalResponder);
@@ -73,7 +73,11 @@ impl HeaderedInbound for RetrievalInboundInner {
 
     fn read(self, stream: HeaderedStream) -> BoxFuture<'static, Result<Self::Output, Self::Error>> {
         Box::pin(async move {
-            let codec = RequestCodec::new(MAX_DELIVERY_SIZE);
+            let codec = Compressed::new(
+                RequestCodec::new(MAX_DELIVERY_SIZE),
+                self.compression,
+                MAX_DELIVERY_SIZE,
+            );
             let mut framed = Framed::new(stream.into_inner(), codec);
 
             debug!("Retrieval: Reading chunk request");
@@ -92,7 +96,11 @@ impl HeaderedInbound for RetrievalInboundInner {
             let responder = RetrievalResponder {
                 framed: Framed::new(
                     parts.io,
-                    DeliveryCodec::new(MAX_DELIVERY_SIZE, request.address),
+                    Compressed::new(
+                        DeliveryCodec::new(MAX_DELIVERY_SIZE, request.address),
+                        self.compression,
+                        MAX_DELIVERY_SIZE,
+                    ),
                 ),
             };
 
@@ -103,7 +111,7 @@ impl HeaderedInbound for RetrievalInboundInner {
 
 /// Handle for sending a delivery response.
 pub struct RetrievalResponder {
-    framed: Framed<libp2p::Stream, DeliveryCodec>,
+    framed: Framed<libp2p::Stream, Compressed<DeliveryCodec>>,
 }
 
 impl RetrievalResponder {
@@ -135,13 +143,17 @@ impl RetrievalResponder {
 /// Retrieval outbound: requests a chunk from remote.
 #[derive(Debug, Clone)]
 pub struct RetrievalOutboundInner {
-    address: ChunkAddress,
+    request: Request,
+    compression: Compression,
 }
 
 impl RetrievalOutboundInner {
-    /// Create a new outbound request for the given chunk address.
-    pub fn new(address: ChunkAddress) -> Self {
-        Self { address }
+    /// Create a new outbound request framed with `compression`.
+    pub fn new(request: Request, compression: Compression) -> Self {
+        Self {
+            request,
+            compression,
+        }
     }
 }
 
@@ -159,18 +171,32 @@ impl HeaderedOutbound for RetrievalOutboundInner {
     ) -> BoxFuture<'static, Result<Self::Output, Self::Error>> {
         Box::pin(async move {
             // Send the request
-            let request_codec = RequestCodec::new(MAX_DELIVERY_SIZE);
+            let request_codec = Compressed::new(
+                RequestCodec::new(MAX_DELIVERY_SIZE),
+                self.compression,
+                MAX_DELIVERY_SIZE,
+            );
             let mut framed = Framed::new(stream.into_inner(), request_codec);
 
-            debug!(chunk_address = %self.address, "Retrieval: Sending chunk request");
-            framed.send(Request::new(self.address)).await?;
+            let address = self.request.address;
+            debug!(
+                chunk_address = %address,
+                ttl = ?self.request.ttl,
+                deadline = ?self.request.deadline,
+                "Retrieval: Sending chunk request"
+            );
+            framed.send(self.request).await?;
 
             // Switch to delivery codec and read response. The codec is given the
             // requested address so it can reconstruct and validate the chunk;
             // the retrieval wire frame carries no address of its own.
             // Use into_parts() to preserve any buffered data across the codec switch.
             let parts = framed.into_parts();
-            let delivery_codec = DeliveryCodec::new(MAX_DELIVERY_SIZE, self.address);
+            let delivery_codec = Compressed::new(
+                DeliveryCodec::new(MAX_DELIVERY_SIZE, address),
+                self.compression,
+                MAX_DELIVERY_SIZE,
+            );
             let mut framed = Framed::new(parts.io, delivery_codec);
 
             debug!("Retrieval: Reading delivery response");
@@ -188,14 +214,16 @@ pub type RetrievalInboundProtocol = Inbound<RetrievalInboundInner>;
 /// Outbound protocol type for handler.
 pub type RetrievalOutboundProtocol = Outbound<RetrievalOutboundInner>;
 
-/// Create an inbound protocol handler.
-pub fn inbound() -> RetrievalInboundProtocol {
-    Inbound::new(RetrievalInboundInner)
+/// Create an inbound protocol handler framing with the connection's
+/// negotiated `compression`.
+pub fn inbound(compression: Compression) -> RetrievalInboundProtocol {
+    Inbound::new(RetrievalInboundInner { compression })
 }
 
-/// Create an outbound protocol handler for the given chunk address.
-pub fn outbound(address: ChunkAddress) -> RetrievalOutboundProtocol {
-    Outbound::new(RetrievalOutboundInner::new(address))
+/// Create an outbound protocol handler for the given request, framing with the
+/// connection's negotiated `compression`.
+pub fn outbound(request: Request, compression: Compression) -> RetrievalOutboundProtocol {
+    Outbound::new(RetrievalOutboundInner::new(request, compression))
 }
 
 #[cfg(test)]
@@ -205,7 +233,7 @@ mod tests {
     use asynchronous_codec::{Decoder, Encoder};
     use bytes::BytesMut;
     use nectar_postage::Stamp;
-    use nectar_primitives::{AnyChunk, SingleOwnerChunk};
+    use nectar_primitives::{AnyChunk, ChunkAddress, SingleOwnerChunk};
     use vertex_swarm_primitives::StampedChunk;
 
     use super::*;
@@ -319,4 +347,70 @@ mod tests {
             "no body present yet, decoder must await more bytes rather than yield a frame"
         );
     }
+
+    /// Encode a maximal delivery under `compression`, as a responder would.
+    fn delivery_wire(compression: Compression) -> (ChunkAddress, BytesMut) {
+        let stamped = maximal_delivery();
+        let address = *stamped.address();
+        let mut enc = Compressed::new(
+            DeliveryCodec::new(MAX_DELIVERY_SIZE, address),
+            compression,
+            MAX_DELIVERY_SIZE,
+        );
+        let mut buf = BytesMut::new();
+        enc.encode(Delivery::success(stamped), &mut buf)
+            .expect("maximal delivery must encode");
+        (address, buf)
+    }
+
+    /// Two compression-capable peers put fewer bytes on the wire for a large
+    /// delivery, and the receiver gets the same chunk back.
+    #[test]
+    fn capable_pair_compresses_large_deliveries() {
+        let (address, plain) = delivery_wire(Compression::None);
+        let negotiated = Compression::negotiate(true, true);
+        let (_, mut compressed) = delivery_wire(negotiated);
+        assert!(
+            compressed.len() < plain.len(),
+            "compressed delivery ({} bytes) must be smaller than plain ({} bytes)",
+            compressed.len(),
+            plain.len()
+        );
+
+        let mut dec = Compressed::new(
+            DeliveryCodec::new(MAX_DELIVERY_SIZE, address),
+            negotiated,
+            MAX_DELIVERY_SIZE,
+        );
+        let decoded = dec
+            .decode(&mut compressed)
+            .expect("decode must not error")
+            .expect("frame must decode");
+        match decoded {
+            Delivery::Chunk { chunk, .. } => assert_eq!(
+                (*chunk).into_bytes(),
+                maximal_delivery().chunk().clone().into_bytes()
+            ),
+            Delivery::Error => panic!("expected a chunk, got a failure"),
+        }
+    }
+
+    /// When only one side supports compression the pair falls back to plain
+    /// frames, byte-identical to what an older peer sends and expects.
+    #[test]
+    fn mixed_pair_falls_back_to_plain_frames() {
+        let negotiated = Compression::negotiate(true, false);
+        assert_eq!(negotiated, Compression::None);
+
+        let (address, plain) = delivery_wire(Compression::None);
+        let (_, mut mixed) = delivery_wire(negotiated);
+        assert_eq!(mixed, plain);
+
+        let mut legacy = DeliveryCodec::new(MAX_DELIVERY_SIZE, address);
+        let decoded = legacy
+            .decode(&mut mixed)
+            .expect("decode must not error")
+            .expect("frame must decode");
+        assert!(matches!(decoded, Delivery::Chunk { .. }));
+    }
 }
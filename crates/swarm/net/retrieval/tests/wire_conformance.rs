@@ -0,0 +1,121 @@
+//! Wire-conformance vectors for the retrieval request frame.
+//!
+//! `/swarm/retrieval/1.4.0/retrieval` opens with one length-delimited protobuf
+//! `Request { bytes addr = 1; bytes ttl = 100; uint32 deadline_ms = 101; }`.
+//! `ttl` is the Accord hop budget, a single byte on field 100, and
+//! `deadline_ms` the Accord time budget in milliseconds on field 101. No
+//! pre-Accord version assigns either field, so a request without them is
+//! byte-for-byte the pre-Accord frame. The framing is
+//! an unsigned-varint length prefix followed by the protobuf message. The
+//! expected bytes are written out by hand, so a change to a field number, its
+//! wire type, or the framing fails the assertion rather than quietly moving
+//! the vector.
+#![allow(clippy::unwrap_used)]
+
+use asynchronous_codec::{Decoder, Encoder};
+use bytes::BytesMut;
+use std::time::Duration;
+
+use nectar_primitives::ChunkAddress;
+use vertex_swarm_net_retrieval::{Request, RequestCodec, RetrievalError};
+
+const ADDRESS: [u8; 32] = [0x11; 32];
+
+fn encode(request: Request) -> Vec<u8> {
+    let mut codec = RequestCodec::new(1024);
+    let mut buf = BytesMut::new();
+    codec.encode(request, &mut buf).unwrap();
+    buf.to_vec()
+}
+
+fn decode(wire: &[u8]) -> Result<Request, RetrievalError> {
+    let mut codec = RequestCodec::new(1024);
+    let mut buf = BytesMut::from(wire);
+    codec.decode(&mut buf).map(Option::unwrap)
+}
+
+/// `addr` as field 1, wire type 2: tag 0x0a, length 32, the address.
+fn addr_field() -> Vec<u8> {
+    let mut out = vec![0x0a, 0x20];
+    out.extend_from_slice(&ADDRESS);
+    out
+}
+
+#[test]
+fn request_without_ttl_is_the_pre_accord_frame() {
+    let mut expected = vec![0x22];
+    expected.extend(addr_field());
+
+    assert_eq!(encode(Request::new(ChunkAddress::new(ADDRESS))), expected);
+    let request = decode(&expected).unwrap();
+    assert_eq!(request.address, ChunkAddress::new(ADDRESS));
+    assert_eq!(request.ttl, None);
+}
+
+#[test]
+fn ttl_rides_on_field_100() {
+    // Field 100, wire type 2: key 802 as the varint 0xa2 0x06, length 1, 7.
+    let mut expected = vec![0x26];
+    expected.extend(addr_field());
+    expected.extend([0xa2, 0x06, 0x01, 0x07]);
+
+    assert_eq!(
+        encode(Request::new(ChunkAddress::new(ADDRESS)).with_ttl(7)),
+        expected
+    );
+    assert_eq!(decode(&expected).unwrap().ttl, Some(7));
+}
+
+#[test]
+fn multi_byte_ttl_is_rejected() {
+    let mut wire = vec![0x27];
+    wire.extend(addr_field());
+    wire.extend([0xa2, 0x06, 0x02, 0x07, 0x07]);
+
+    assert!(matches!(
+        decode(&wire),
+        Err(RetrievalError::InvalidTtlLength(2))
+    ));
+}
+
+#[test]
+fn deadline_rides_on_field_101() {
+    // Field 101, wire type 0: key 808 as the varint 0xa8 0x06, then 1500 as
+    // the varint 0xdc 0x0b.
+    let mut expected = vec![0x26];
+    expected.extend(addr_field());
+    expected.extend([0xa8, 0x06, 0xdc, 0x0b]);
+
+    assert_eq!(
+        encode(Request::new(ChunkAddress::new(ADDRESS)).with_deadline(Duration::from_millis(1500))),
+        expected
+    );
+    assert_eq!(
+        decode(&expected).unwrap().deadline,
+        Some(Duration::from_millis(1500))
+    );
+}
+
+#[test]
+fn ttl_and_deadline_follow_the_address_in_field_order() {
+    let mut expected = vec![0x2a];
+    expected.extend(addr_field());
+    expected.extend([0xa2, 0x06, 0x01, 0x07]);
+    expected.extend([0xa8, 0x06, 0xdc, 0x0b]);
+
+    let request = Request::new(ChunkAddress::new(ADDRESS))
+        .with_ttl(7)
+        .with_deadline(Duration::from_millis(1500));
+    assert_eq!(encode(request.clone()), expected);
+    assert_eq!(decode(&expected).unwrap(), request);
+}
+
+#[test]
+fn zero_deadline_decodes_as_none() {
+    // proto3 omits a zero scalar, but an explicit zero still means no budget.
+    let mut wire = vec![0x25];
+    wire.extend(addr_field());
+    wire.extend([0xa8, 0x06, 0x00]);
+
+    assert_eq!(decode(&wire).unwrap().deadline, None);
+}
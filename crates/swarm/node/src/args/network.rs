@@ -1,15 +1,20 @@
 //! P2P network CLI arguments and validated configuration.
 
+use std::net::IpAddr;
 use std::time::Duration;
 
 use clap::Args;
 use serde::{Deserialize, Serialize};
 use vertex_swarm_api::{
-    ConfigAddressKind, ConfigError, ConnectionProfile, Multiaddr, SwarmNetworkConfig,
-    SwarmPeerConfig, SwarmRoutingConfig,
+    ConfigAddressKind, ConfigError, ConnectionProfile, DEFAULT_COMMAND_CHANNEL_CAPACITY,
+    DEFAULT_DNS_CACHE_TTL, Multiaddr, OverlayAddress, SwarmNetworkConfig, SwarmPeerConfig,
+    SwarmRoutingConfig,
 };
+use vertex_swarm_primitives::SwarmAddressExt;
 use vertex_swarm_topology::{KademliaConfig, RoutingArgs};
 
+use crate::{ReadYourWrites, RetrievalSelection};
+
 use super::peer::{PeerArgs, PeerConfig};
 
 /// Default P2P listen port.
@@ -18,18 +23,6 @@ const DEFAULT_P2P_PORT: u16 = 1634;
 /// Default listen address.
 const DEFAULT_LISTEN_ADDR: &str = "0.0.0.0";
 
-/// Default maximum established connections, enforced at the transport layer
-/// by the swarm's connection-limits behaviour.
-///
-/// This is a resource backstop, not a topology-shaping knob: the kademlia
-/// topology already budgets its own dials (a 160-peer taper across balanced
-/// bins by default) and additionally connects to every available
-/// neighborhood peer and accepts a few inbound per bin above target. A
-/// healthy saturated table therefore sits in the 200-300 connection range,
-/// so the transport cap defaults comfortably above that. Setting it near or
-/// below topology's own totals starves the routing table and stalls depth.
-const DEFAULT_MAX_PEERS: usize = 400;
-
 /// Default idle timeout in seconds.
 const DEFAULT_IDLE_TIMEOUT_SECS: u64 = 60;
 
@@ -74,6 +67,30 @@ pub struct NetworkArgs {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub trusted_peers_raw: Vec<String>,
 
+    /// Comma-separated list of static peers as `<overlay>@<multiaddr>`. The
+    /// multiaddr must end in `/p2p/<peer id>`. Static peers are kept
+    /// connected, redialed under the dial backoff when they drop, and exempt
+    /// from bin limits.
+    #[arg(long = "network.static-peers", value_delimiter = ',')]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub static_peers_raw: Vec<String>,
+
+    /// Comma-separated DNS servers for resolving `/dnsaddr/` bootnodes.
+    /// Defaults to the system resolver.
+    #[arg(long = "network.dns-servers", value_delimiter = ',')]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dns_servers: Vec<IpAddr>,
+
+    /// Seconds a resolved `/dnsaddr/` bootnode is reused before it is looked
+    /// up again. When a later lookup fails the last resolution is used.
+    #[arg(long = "network.dns-cache-ttl", default_value_t = DEFAULT_DNS_CACHE_TTL.as_secs())]
+    pub dns_cache_ttl_secs: u64,
+
+    /// Capacity of the client command channel. Requests that find it full
+    /// wait for room, then fail as overloaded.
+    #[arg(long = "network.command-capacity", default_value_t = DEFAULT_COMMAND_CHANNEL_CAPACITY)]
+    pub command_capacity: usize,
+
     /// P2P listen port.
     #[arg(long = "network.port", default_value_t = DEFAULT_P2P_PORT)]
     pub port: u16,
@@ -143,6 +160,21 @@ pub struct NetworkArgs {
     #[serde(default = "default_mdns")]
     pub mdns: bool,
 
+    /// Offer zstd compression of large protocol payloads (disabled by default).
+    ///
+    /// Used only with peers that offer it too; others see plain frames.
+    /// Accepts a bare `--network.compression` (enables) or an explicit
+    /// `--network.compression=true`/`--network.compression=false`.
+    #[arg(
+        long = "network.compression",
+        num_args = 0..=1,
+        default_value_t = false,
+        default_missing_value = "true",
+        action = clap::ArgAction::Set,
+    )]
+    #[serde(default)]
+    pub compression: bool,
+
     /// Connection pacing profile: aggressive, balanced, or conservative.
     /// Defaults by node mode: client = aggressive, bootnode/storer = balanced.
     #[arg(long = "network.connection-profile", value_name = "PROFILE")]
@@ -151,16 +183,70 @@ pub struct NetworkArgs {
 
     /// Maximum number of established connections (transport-level hard cap).
     ///
-    /// Enforced by the swarm independently of kademlia bin logic. Lowering
-    /// this below the topology's own connection totals (about 200-300 for a
+    /// Enforced by the swarm independently of kademlia bin logic. Defaults by
+    /// node mode: bootnode = 1000, storer = 500, client = 400. Lowering this
+    /// below the topology's own connection totals (about 200-300 for a
     /// saturated table) limits routing table health.
-    #[arg(long = "network.max-peers", default_value_t = DEFAULT_MAX_PEERS)]
-    pub max_peers: usize,
+    #[arg(long = "network.max-peers", value_name = "COUNT")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_peers: Option<usize>,
+
+    /// Maximum outbound dials in flight at once; further dials queue until one
+    /// resolves. Defaults to the connection profile's dial concurrency.
+    #[arg(long = "network.max-concurrent-dials", value_name = "COUNT")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_dials: Option<usize>,
+
+    /// Connects plus disconnects per minute above which peer churn is
+    /// reported as a warning. Unset disables the alert.
+    #[arg(long = "network.churn-threshold", value_name = "PER_MINUTE")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub churn_threshold: Option<f64>,
+
+    /// Seconds between re-announcements of the node's own record to connected
+    /// neighbors. Unset or 0 announces it only in the handshake.
+    #[arg(long = "network.self-announce-interval", value_name = "SECS")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub self_announce_interval_secs: Option<u64>,
+
+    /// Activated peers required before an inbound retrieval that misses the
+    /// local store is forwarded. Below it the miss is refused at once as not
+    /// ready; local hits are always served. 0 disables the gate.
+    #[arg(
+        long = "network.min-peers-to-serve",
+        value_name = "COUNT",
+        default_value_t = 0
+    )]
+    pub min_peers_to_serve: usize,
+
+    /// Gossip exchanges with newly activated peers sent back to back before
+    /// pacing starts. Defaults to the gossip tuning.
+    #[arg(long = "network.exchange-burst", value_name = "COUNT")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exchange_burst: Option<usize>,
+
+    /// Milliseconds to earn one more gossip exchange once the burst is spent.
+    /// Values below the gossip minimum are raised to it. Defaults to the
+    /// gossip tuning.
+    #[arg(long = "network.exchange-interval", value_name = "MILLIS")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exchange_interval_ms: Option<u64>,
 
     /// Connection idle timeout in seconds.
     #[arg(long = "network.idle-timeout", default_value_t = DEFAULT_IDLE_TIMEOUT_SECS)]
     pub idle_timeout_secs: u64,
 
+    /// Which peers retrievals are requested from: closest (proximity),
+    /// latency (fastest observed distance first), or score (admission band and
+    /// peer score).
+    #[arg(long = "network.retrieval-selector", value_name = "SELECTOR", default_value_t = RetrievalSelection::Closest)]
+    pub retrieval_selector: RetrievalSelection,
+
+    /// Serve chunks this node uploaded back from a short-lived local cache,
+    /// so a publisher reads its own recent uploads before they propagate.
+    #[arg(long = "network.read-your-writes")]
+    pub read_your_writes: bool,
+
     /// Peer management configuration.
     #[command(flatten)]
     #[serde(default)]
@@ -179,6 +265,10 @@ impl Default for NetworkArgs {
             no_trust_local_peers: false,
             bootnodes_raw: Vec::new(),
             trusted_peers_raw: Vec::new(),
+            static_peers_raw: Vec::new(),
+            dns_servers: Vec::new(),
+            dns_cache_ttl_secs: DEFAULT_DNS_CACHE_TTL.as_secs(),
+            command_capacity: DEFAULT_COMMAND_CHANNEL_CAPACITY,
             port: DEFAULT_P2P_PORT,
             addr: DEFAULT_LISTEN_ADDR.to_string(),
             nat_addrs_raw: Vec::new(),
@@ -186,9 +276,18 @@ impl Default for NetworkArgs {
             autonat: true,
             upnp: false,
             mdns: true,
+            compression: false,
             connection_profile: None,
-            max_peers: DEFAULT_MAX_PEERS,
+            max_peers: None,
+            max_concurrent_dials: None,
+            churn_threshold: None,
+            self_announce_interval_secs: None,
+            min_peers_to_serve: 0,
+            exchange_burst: None,
+            exchange_interval_ms: None,
             idle_timeout_secs: DEFAULT_IDLE_TIMEOUT_SECS,
+            retrieval_selector: RetrievalSelection::default(),
+            read_your_writes: false,
             peer: PeerArgs::default(),
             routing: RoutingArgs::default(),
         }
@@ -236,16 +335,29 @@ pub struct NetworkConfig<R = KademliaConfig> {
     listen_addrs: Vec<Multiaddr>,
     bootnodes: Vec<Multiaddr>,
     trusted_peers: Vec<Multiaddr>,
+    static_peers: Vec<(OverlayAddress, Multiaddr)>,
+    dns_servers: Vec<IpAddr>,
+    dns_cache_ttl: Duration,
+    command_capacity: usize,
     nat_addrs: Vec<Multiaddr>,
     nat_auto: bool,
     autonat: bool,
     upnp: bool,
     mdns: bool,
+    compression: bool,
     discovery_enabled: bool,
     trust_local_peers: bool,
     connection_profile: Option<ConnectionProfile>,
-    max_peers: usize,
+    max_peers: Option<usize>,
+    max_concurrent_dials: Option<usize>,
+    churn_threshold: Option<f64>,
+    self_announce_interval: Option<Duration>,
+    min_peers_to_serve: usize,
+    exchange_burst: Option<usize>,
+    exchange_interval: Option<Duration>,
     idle_timeout: Duration,
+    retrieval_selector: RetrievalSelection,
+    read_your_writes: bool,
     peer: PeerConfig,
     routing: R,
     /// libp2p identify agent string, set at node assembly. `None` defers to the
@@ -265,6 +377,17 @@ impl<R> NetworkConfig<R> {
         self
     }
 
+    /// Get the built-in retrieval selector the node draws candidates with.
+    pub fn retrieval_selector(&self) -> RetrievalSelection {
+        self.retrieval_selector
+    }
+
+    /// Get the read-your-writes settings for uploaded chunks; `None` when
+    /// disabled.
+    pub fn read_your_writes(&self) -> Option<ReadYourWrites> {
+        self.read_your_writes.then(ReadYourWrites::default)
+    }
+
     /// Get the routing configuration.
     pub fn routing(&self) -> &R {
         &self.routing
@@ -276,16 +399,29 @@ impl<R> NetworkConfig<R> {
             listen_addrs: self.listen_addrs,
             bootnodes: self.bootnodes,
             trusted_peers: self.trusted_peers,
+            static_peers: self.static_peers,
+            dns_servers: self.dns_servers,
+            dns_cache_ttl: self.dns_cache_ttl,
+            command_capacity: self.command_capacity,
             nat_addrs: self.nat_addrs,
             nat_auto: self.nat_auto,
             autonat: self.autonat,
             upnp: self.upnp,
             mdns: self.mdns,
+            compression: self.compression,
             discovery_enabled: self.discovery_enabled,
             trust_local_peers: self.trust_local_peers,
             connection_profile: self.connection_profile,
             max_peers: self.max_peers,
+            max_concurrent_dials: self.max_concurrent_dials,
+            churn_threshold: self.churn_threshold,
+            self_announce_interval: self.self_announce_interval,
+            min_peers_to_serve: self.min_peers_to_serve,
+            exchange_burst: self.exchange_burst,
+            exchange_interval: self.exchange_interval,
             idle_timeout: self.idle_timeout,
+            retrieval_selector: self.retrieval_selector,
+            read_your_writes: self.read_your_writes,
             peer: self.peer,
             routing,
             agent_version: self.agent_version,
@@ -311,16 +447,29 @@ impl Default for NetworkConfig<KademliaConfig> {
             listen_addrs: vec![listen_addr],
             bootnodes: Vec::new(),
             trusted_peers: Vec::new(),
+            static_peers: Vec::new(),
+            dns_servers: Vec::new(),
+            dns_cache_ttl: DEFAULT_DNS_CACHE_TTL,
+            command_capacity: DEFAULT_COMMAND_CHANNEL_CAPACITY,
             nat_addrs: Vec::new(),
             nat_auto: true,
             autonat: true,
             upnp: false,
             mdns: true,
+            compression: false,
             discovery_enabled: true,
             trust_local_peers: true,
             connection_profile: None,
-            max_peers: DEFAULT_MAX_PEERS,
+            max_peers: None,
+            max_concurrent_dials: None,
+            churn_threshold: None,
+            self_announce_interval: None,
+            min_peers_to_serve: 0,
+            exchange_burst: None,
+            exchange_interval: None,
             idle_timeout: Duration::from_secs(DEFAULT_IDLE_TIMEOUT_SECS),
+            retrieval_selector: RetrievalSelection::default(),
+            read_your_writes: false,
             peer: PeerConfig::default(),
             routing: KademliaConfig::default(),
             agent_version: None,
@@ -334,9 +483,14 @@ impl TryFrom<&NetworkArgs> for NetworkConfig<KademliaConfig> {
     fn try_from(args: &NetworkArgs) -> Result<Self, Self::Error> {
         // The cap is enforced at the transport layer, so zero would deny
         // every connection and isolate the node. Fail fast instead.
-        if args.max_peers == 0 {
+        if args.max_peers == Some(0) {
             return Err(ConfigError::ZeroMaxPeers);
         }
+        if let Some(threshold) = args.churn_threshold
+            && !(threshold.is_finite() && threshold > 0.0)
+        {
+            return Err(ConfigError::InvalidChurnThreshold(threshold));
+        }
 
         let listen_addr_str = format!("/ip4/{}/tcp/{}", args.addr, args.port);
         let listen_addrs =
@@ -374,6 +528,12 @@ impl TryFrom<&NetworkArgs> for NetworkConfig<KademliaConfig> {
             })
             .collect::<Result<Vec<_>, _>>()?;
 
+        let static_peers = args
+            .static_peers_raw
+            .iter()
+            .map(|s| parse_static_peer(s))
+            .collect::<Result<Vec<_>, _>>()?;
+
         let nat_addrs = args
             .nat_addrs_raw
             .iter()
@@ -390,16 +550,32 @@ impl TryFrom<&NetworkArgs> for NetworkConfig<KademliaConfig> {
             listen_addrs,
             bootnodes,
             trusted_peers,
+            static_peers,
+            dns_servers: args.dns_servers.clone(),
+            dns_cache_ttl: Duration::from_secs(args.dns_cache_ttl_secs),
+            command_capacity: args.command_capacity.max(1),
             nat_addrs,
             nat_auto: args.nat_auto,
             autonat: args.autonat,
             upnp: args.upnp,
             mdns: args.mdns,
+            compression: args.compression,
             discovery_enabled: !args.disable_discovery,
             trust_local_peers: !args.no_trust_local_peers,
             connection_profile: args.connection_profile,
             max_peers: args.max_peers,
+            max_concurrent_dials: args.max_concurrent_dials,
+            churn_threshold: args.churn_threshold,
+            self_announce_interval: args
+                .self_announce_interval_secs
+                .filter(|secs| *secs > 0)
+                .map(Duration::from_secs),
+            min_peers_to_serve: args.min_peers_to_serve,
+            exchange_burst: args.exchange_burst,
+            exchange_interval: args.exchange_interval_ms.map(Duration::from_millis),
             idle_timeout: Duration::from_secs(args.idle_timeout_secs),
+            retrieval_selector: args.retrieval_selector,
+            read_your_writes: args.read_your_writes,
             peer: PeerConfig::from(&args.peer),
             routing: args.routing.routing_config(),
             agent_version: None,
@@ -407,6 +583,24 @@ impl TryFrom<&NetworkArgs> for NetworkConfig<KademliaConfig> {
     }
 }
 
+/// Parse one `<overlay>@<multiaddr>` static peer entry.
+fn parse_static_peer(entry: &str) -> Result<(OverlayAddress, Multiaddr), ConfigError> {
+    let (overlay, addr) = entry
+        .split_once('@')
+        .ok_or_else(|| ConfigError::MalformedStaticPeer(entry.to_owned()))?;
+    let overlay =
+        OverlayAddress::from_hex(overlay).map_err(|e| ConfigError::InvalidStaticPeerOverlay {
+            overlay: overlay.to_owned(),
+            source: e,
+        })?;
+    let addr = addr.parse().map_err(|e| ConfigError::InvalidAddress {
+        kind: ConfigAddressKind::StaticPeer,
+        addr: addr.to_owned(),
+        source: e,
+    })?;
+    Ok((overlay, addr))
+}
+
 impl<R> SwarmNetworkConfig for NetworkConfig<R> {
     fn listen_addrs(&self) -> &[Multiaddr] {
         &self.listen_addrs
@@ -424,11 +618,23 @@ impl<R> SwarmNetworkConfig for NetworkConfig<R> {
         &self.trusted_peers
     }
 
+    fn static_peers(&self) -> &[(OverlayAddress, Multiaddr)] {
+        &self.static_peers
+    }
+
+    fn dns_servers(&self) -> &[IpAddr] {
+        &self.dns_servers
+    }
+
+    fn dns_cache_ttl(&self) -> Duration {
+        self.dns_cache_ttl
+    }
+
     fn discovery_enabled(&self) -> bool {
         self.discovery_enabled
     }
 
-    fn max_peers(&self) -> usize {
+    fn max_peers(&self) -> Option<usize> {
         self.max_peers
     }
 
@@ -456,6 +662,14 @@ impl<R> SwarmNetworkConfig for NetworkConfig<R> {
         self.mdns
     }
 
+    fn compression_enabled(&self) -> bool {
+        self.compression
+    }
+
+    fn command_channel_capacity(&self) -> usize {
+        self.command_capacity
+    }
+
     fn trust_local_peers(&self) -> bool {
         self.trust_local_peers
     }
@@ -463,6 +677,30 @@ impl<R> SwarmNetworkConfig for NetworkConfig<R> {
     fn connection_profile(&self) -> Option<ConnectionProfile> {
         self.connection_profile
     }
+
+    fn max_concurrent_dials(&self) -> Option<usize> {
+        self.max_concurrent_dials
+    }
+
+    fn churn_threshold(&self) -> Option<f64> {
+        self.churn_threshold
+    }
+
+    fn self_announce_interval(&self) -> Option<Duration> {
+        self.self_announce_interval
+    }
+
+    fn min_peers_to_serve(&self) -> usize {
+        self.min_peers_to_serve
+    }
+
+    fn exchange_burst(&self) -> Option<usize> {
+        self.exchange_burst
+    }
+
+    fn exchange_interval(&self) -> Option<Duration> {
+        self.exchange_interval
+    }
 }
 
 impl<R> SwarmPeerConfig for NetworkConfig<R> {
@@ -484,7 +722,7 @@ impl<R: Default> SwarmRoutingConfig for NetworkConfig<R> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use vertex_swarm_api::{DEFAULT_PEER_MAX_PER_BIN, PeerConfigValues};
+    use vertex_swarm_api::{DEFAULT_PEER_MAX_PER_BIN, PeerConfigValues, SwarmNodeType};
 
     #[test]
     fn network_config_from_default_args() {
@@ -493,7 +731,7 @@ mod tests {
 
         // Default listen address is constructed from addr:port
         assert!(!config.listen_addrs().is_empty());
-        assert_eq!(config.max_peers(), DEFAULT_MAX_PEERS);
+        assert_eq!(config.max_peers(), None);
         assert_eq!(
             config.idle_timeout(),
             Duration::from_secs(DEFAULT_IDLE_TIMEOUT_SECS)
@@ -501,6 +739,24 @@ mod tests {
         assert!(config.discovery_enabled());
     }
 
+    #[test]
+    fn dns_resolver_flags_propagate() {
+        let defaults =
+            NetworkConfig::try_from(&NetworkArgs::default()).expect("default args should be valid");
+        assert!(defaults.dns_servers().is_empty());
+        assert_eq!(defaults.dns_cache_ttl(), DEFAULT_DNS_CACHE_TTL);
+
+        let server: IpAddr = "9.9.9.9".parse().expect("valid ip");
+        let args = NetworkArgs {
+            dns_servers: vec![server],
+            dns_cache_ttl_secs: 300,
+            ..Default::default()
+        };
+        let config = NetworkConfig::try_from(&args).expect("valid args");
+        assert_eq!(config.dns_servers(), &[server]);
+        assert_eq!(config.dns_cache_ttl(), Duration::from_secs(300));
+    }
+
     #[test]
     fn nat_traversal_defaults() {
         // AutoNAT v2 is on by default for every node type; UPnP is opt-in.
@@ -622,6 +878,12 @@ mod tests {
                 bare: true,
                 get: |a| a.upnp,
             },
+            Case {
+                flag: "network.compression",
+                default: false,
+                bare: true,
+                get: |a| a.compression,
+            },
         ];
 
         for case in cases {
@@ -699,7 +961,7 @@ mod tests {
     #[test]
     fn network_config_fails_on_zero_max_peers() {
         let args = NetworkArgs {
-            max_peers: 0,
+            max_peers: Some(0),
             ..Default::default()
         };
 
@@ -707,6 +969,26 @@ mod tests {
         assert!(matches!(result, Err(ConfigError::ZeroMaxPeers)));
     }
 
+    #[test]
+    fn max_peers_defaults_by_node_type() {
+        let config = NetworkConfig::try_from(&NetworkArgs::default()).expect("default args");
+        let bootnode = config.effective_max_peers(SwarmNodeType::Bootnode);
+        let client = config.effective_max_peers(SwarmNodeType::Client);
+        assert!(
+            bootnode > client,
+            "bootnode cap {bootnode} should exceed client cap {client}"
+        );
+
+        // An explicit cap overrides the node-type default for every mode.
+        let args = NetworkArgs {
+            max_peers: Some(50),
+            ..Default::default()
+        };
+        let config = NetworkConfig::try_from(&args).expect("explicit cap");
+        assert_eq!(config.effective_max_peers(SwarmNodeType::Bootnode), 50);
+        assert_eq!(config.effective_max_peers(SwarmNodeType::Client), 50);
+    }
+
     #[test]
     fn network_config_fails_on_invalid_bootnode() {
         let args = NetworkArgs {
@@ -780,4 +1062,176 @@ mod tests {
             Some(ConnectionProfile::Conservative)
         );
     }
+
+    #[test]
+    fn retrieval_selector_flag_parses_and_propagates() {
+        use clap::Parser;
+
+        let parsed = TestCli::try_parse_from(["test"]).expect("default should parse");
+        let config = NetworkConfig::try_from(&parsed.network).expect("valid args");
+        assert_eq!(config.retrieval_selector(), RetrievalSelection::Closest);
+
+        for (value, expected) in [
+            ("closest", RetrievalSelection::Closest),
+            ("latency", RetrievalSelection::Latency),
+            ("score", RetrievalSelection::Score),
+        ] {
+            let parsed = TestCli::try_parse_from(["test", "--network.retrieval-selector", value])
+                .expect("selector value should parse");
+            let config = NetworkConfig::try_from(&parsed.network).expect("valid args");
+            assert_eq!(config.retrieval_selector(), expected);
+        }
+
+        assert!(
+            TestCli::try_parse_from(["test", "--network.retrieval-selector", "random"]).is_err()
+        );
+    }
+
+    #[test]
+    fn max_concurrent_dials_flag_propagates() {
+        use clap::Parser;
+
+        let parsed = TestCli::try_parse_from(["test"]).expect("default should parse");
+        let config = NetworkConfig::try_from(&parsed.network).expect("valid args");
+        assert_eq!(config.max_concurrent_dials(), None);
+
+        let parsed = TestCli::try_parse_from(["test", "--network.max-concurrent-dials", "8"])
+            .expect("dial limit should parse");
+        let config = NetworkConfig::try_from(&parsed.network).expect("valid args");
+        let swapped = config.with_routing(KademliaConfig::default());
+        assert_eq!(swapped.max_concurrent_dials(), Some(8));
+    }
+
+    #[test]
+    fn self_announce_interval_flag_propagates() {
+        use clap::Parser;
+
+        let parsed = TestCli::try_parse_from(["test"]).expect("default should parse");
+        let config = NetworkConfig::try_from(&parsed.network).expect("valid args");
+        assert_eq!(config.self_announce_interval(), None);
+
+        let parsed = TestCli::try_parse_from(["test", "--network.self-announce-interval", "600"])
+            .expect("interval should parse");
+        let config = NetworkConfig::try_from(&parsed.network).expect("valid args");
+        assert_eq!(
+            config.self_announce_interval(),
+            Some(Duration::from_secs(600))
+        );
+
+        let parsed = TestCli::try_parse_from(["test", "--network.self-announce-interval", "0"])
+            .expect("zero should parse");
+        let config = NetworkConfig::try_from(&parsed.network).expect("valid args");
+        assert_eq!(config.self_announce_interval(), None);
+    }
+
+    #[test]
+    fn min_peers_to_serve_flag_propagates() {
+        use clap::Parser;
+
+        let parsed = TestCli::try_parse_from(["test"]).expect("default should parse");
+        let config = NetworkConfig::try_from(&parsed.network).expect("valid args");
+        assert_eq!(config.min_peers_to_serve(), 0);
+
+        let parsed = TestCli::try_parse_from(["test", "--network.min-peers-to-serve", "4"])
+            .expect("count should parse");
+        let config = NetworkConfig::try_from(&parsed.network).expect("valid args");
+        assert_eq!(config.min_peers_to_serve(), 4);
+        assert_eq!(
+            config
+                .with_routing(KademliaConfig::default())
+                .min_peers_to_serve(),
+            4
+        );
+    }
+
+    #[test]
+    fn exchange_pacing_flags_propagate() {
+        use clap::Parser;
+
+        let parsed = TestCli::try_parse_from(["test"]).expect("default should parse");
+        let config = NetworkConfig::try_from(&parsed.network).expect("valid args");
+        assert_eq!(config.exchange_burst(), None);
+        assert_eq!(config.exchange_interval(), None);
+
+        let parsed = TestCli::try_parse_from([
+            "test",
+            "--network.exchange-burst",
+            "2",
+            "--network.exchange-interval",
+            "1000",
+        ])
+        .expect("pacing should parse");
+        let config = NetworkConfig::try_from(&parsed.network)
+            .expect("valid args")
+            .with_routing(KademliaConfig::default());
+        assert_eq!(config.exchange_burst(), Some(2));
+        assert_eq!(config.exchange_interval(), Some(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn read_your_writes_flag_propagates() {
+        use clap::Parser;
+
+        let parsed = TestCli::try_parse_from(["test"]).expect("default should parse");
+        let config = NetworkConfig::try_from(&parsed.network).expect("valid args");
+        assert_eq!(config.read_your_writes(), None);
+
+        let parsed = TestCli::try_parse_from(["test", "--network.read-your-writes"])
+            .expect("flag should parse");
+        let config = NetworkConfig::try_from(&parsed.network).expect("valid args");
+        assert_eq!(config.read_your_writes(), Some(ReadYourWrites::default()));
+        assert_eq!(
+            config
+                .with_routing(KademliaConfig::default())
+                .read_your_writes(),
+            Some(ReadYourWrites::default())
+        );
+    }
+
+    #[test]
+    fn churn_threshold_flag_propagates() {
+        use clap::Parser;
+
+        let parsed = TestCli::try_parse_from(["test"]).expect("default should parse");
+        let config = NetworkConfig::try_from(&parsed.network).expect("valid args");
+        assert_eq!(config.churn_threshold(), None);
+
+        let parsed = TestCli::try_parse_from(["test", "--network.churn-threshold", "30"])
+            .expect("churn threshold should parse");
+        let config = NetworkConfig::try_from(&parsed.network).expect("valid args");
+        assert_eq!(config.churn_threshold(), Some(30.0));
+
+        let parsed = TestCli::try_parse_from(["test", "--network.churn-threshold", "0"])
+            .expect("zero parses as a float");
+        assert!(matches!(
+            NetworkConfig::try_from(&parsed.network),
+            Err(ConfigError::InvalidChurnThreshold(_))
+        ));
+    }
+
+    #[test]
+    fn static_peers_flag_propagates() {
+        use clap::Parser;
+
+        let overlay = OverlayAddress::from([0x11; 32]);
+        let addr = format!(
+            "/ip4/203.0.113.10/tcp/1634/p2p/{}",
+            libp2p::PeerId::random()
+        );
+        let entry = format!("{}@{addr}", overlay.to_hex());
+        let parsed = TestCli::try_parse_from(["test", "--network.static-peers", &entry])
+            .expect("static peers should parse");
+        let config = NetworkConfig::try_from(&parsed.network).expect("valid args");
+        assert_eq!(
+            config.static_peers(),
+            &[(overlay, addr.parse().expect("valid multiaddr"))]
+        );
+
+        let parsed = TestCli::try_parse_from(["test", "--network.static-peers", &addr])
+            .expect("a bare multiaddr parses as a string");
+        assert!(matches!(
+            NetworkConfig::try_from(&parsed.network),
+            Err(ConfigError::MalformedStaticPeer(_))
+        ));
+    }
 }
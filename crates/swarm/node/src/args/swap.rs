@@ -18,7 +18,8 @@ use serde::{Deserialize, Serialize};
 #[serde(default)]
 pub struct SwapArgs {
     /// Enable or disable SWAP settlement. Unset defaults on for storers and off
-    /// for clients. No effect unless built with the `swap` feature.
+    /// for clients and on dev networks. No effect unless built with the `swap`
+    /// feature.
     #[arg(long = "swap", num_args = 0..=1, require_equals = true, default_missing_value = "true")]
     #[serde(default)]
     pub enable: Option<bool>,
@@ -45,6 +46,13 @@ pub struct SwapArgs {
     #[arg(long = "swap.bounce-limit", default_value_t = DEFAULT_BOUNCE_LIMIT)]
     #[serde(default = "default_bounce_limit", with = "u128_string")]
     pub bounce_limit: u128,
+
+    /// Settle in the background through a queue of at most this many
+    /// unresolved settlements, crediting the debt when it is queued. `0` settles
+    /// inline on the accounting path.
+    #[arg(long = "swap.settlement-queue", default_value_t = 0)]
+    #[serde(default)]
+    pub settlement_queue: usize,
 }
 
 /// Serialize a `u128` as a decimal string so figment's i64/u64 value model can
@@ -81,6 +89,7 @@ impl Default for SwapArgs {
             beneficiary: None,
             deploy: false,
             bounce_limit: DEFAULT_BOUNCE_LIMIT,
+            settlement_queue: 0,
         }
     }
 }
@@ -94,6 +103,7 @@ impl SwapArgs {
             beneficiary: self.beneficiary,
             deploy: self.deploy,
             bounce_limit: self.bounce_limit,
+            settlement_queue: self.settlement_queue,
         }
     }
 }
@@ -106,7 +116,8 @@ impl SwapArgs {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SwapConfig {
     /// SWAP request: `Some(true)`/`Some(false)` force it on or off, `None` takes
-    /// the node-type default (on for storers, off for clients).
+    /// the network's default for the node type (see
+    /// `SwarmSpec::default_swap_enabled`).
     pub enable: Option<bool>,
 
     /// This node's chequebook contract address, if configured.
@@ -121,6 +132,9 @@ pub struct SwapConfig {
 
     /// Per-peer cap on uncashed cheque exposure, in cumulative-payout units.
     pub bounce_limit: u128,
+
+    /// Capacity of the background settlement queue; `0` settles inline.
+    pub settlement_queue: usize,
 }
 
 impl Default for SwapConfig {
@@ -141,6 +155,7 @@ mod tests {
         assert_eq!(cfg.beneficiary, None);
         assert!(!cfg.deploy);
         assert_eq!(cfg.bounce_limit, DEFAULT_BOUNCE_LIMIT);
+        assert_eq!(cfg.settlement_queue, 0);
     }
 
     #[test]
@@ -153,6 +168,7 @@ mod tests {
             beneficiary: Some(beneficiary),
             deploy: true,
             bounce_limit: 42,
+            settlement_queue: 16,
         };
         let cfg = args.swap_config();
         assert_eq!(cfg.enable, Some(true));
@@ -160,5 +176,6 @@ mod tests {
         assert_eq!(cfg.beneficiary, Some(beneficiary));
         assert!(cfg.deploy);
         assert_eq!(cfg.bounce_limit, 42);
+        assert_eq!(cfg.settlement_queue, 16);
     }
 }
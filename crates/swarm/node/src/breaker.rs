@@ -0,0 +1,335 @@
+//! Per-peer, per-protocol circuit breaker on malformed responses.
+//!
+//! A peer that keeps answering one protocol with malformed data (an invalid
+//! chunk on retrieval, an invalid receipt on pushsync) stays connected and may
+//! serve the other protocol perfectly well, so scoring alone is too coarse: it
+//! would either keep asking the peer or drop it for everything. The breaker
+//! takes the peer out of candidate selection for the one protocol instead.
+//!
+//! - **Closed**: the peer is selectable. Each malformed response is stamped;
+//!   [`ProtocolBreaker::threshold`] of them within the error window opens the
+//!   breaker.
+//! - **Open**: the peer is skipped for that protocol until the cooldown elapses.
+//! - **Half-open**: the peer is selectable again on probation. A good response
+//!   closes the breaker and clears its history; one more malformed response
+//!   reopens it for another cooldown.
+//!
+//! Blameless failures (misses, timeouts, transport errors) never reach the
+//! breaker, matching the scoring policy in the client service. State is kept per
+//! `(peer, protocol)` and dropped once a breaker is closed with no recent
+//! errors or the peer disconnects, so memory tracks the connected peers
+//! currently misbehaving.
+
+use std::collections::{HashMap, VecDeque};
+use std::num::NonZeroUsize;
+
+use parking_lot::Mutex;
+use tracing::debug;
+use vertex_swarm_primitives::{OverlayAddress, SwarmAddressExt};
+use vertex_tasks::time::{Duration, Instant};
+
+/// Default count of malformed responses within the window that opens a breaker.
+/// Kept below [`InvalidDataBan::DEFAULT_THRESHOLD`], so the breaker opens
+/// before the same invalid-data reports ban the peer outright.
+///
+/// [`InvalidDataBan::DEFAULT_THRESHOLD`]: vertex_swarm_peer_manager::InvalidDataBan::DEFAULT_THRESHOLD
+pub const DEFAULT_BREAKER_THRESHOLD: NonZeroUsize = match NonZeroUsize::new(5) {
+    Some(threshold) => threshold,
+    None => unreachable!(),
+};
+
+/// Default window over which malformed responses are counted.
+pub const DEFAULT_BREAKER_WINDOW: Duration = Duration::from_secs(60);
+
+/// Default time an open breaker keeps a peer out before it half-opens.
+pub const DEFAULT_BREAKER_COOLDOWN: Duration = Duration::from_secs(120);
+
+/// Protocol a breaker guards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum BreakerProtocol {
+    /// Retrieval requests; tripped by invalid chunks.
+    Retrieval,
+    /// Pushsync pushes; tripped by invalid receipts.
+    Pushsync,
+}
+
+/// One `(peer, protocol)` breaker.
+#[derive(Debug)]
+enum BreakerState {
+    /// Selectable; recent malformed responses, oldest first.
+    Closed(VecDeque<Instant>),
+    /// Skipped until the cooldown from `since` elapses.
+    Open { since: Instant },
+    /// Selectable on probation after a cooldown.
+    HalfOpen,
+}
+
+/// Per-peer, per-protocol breakers, shared by the client service (which records
+/// outcomes) and the dispatch engine (which skips open peers).
+pub struct ProtocolBreaker {
+    threshold: NonZeroUsize,
+    window: Duration,
+    cooldown: Duration,
+    breakers: Mutex<HashMap<(OverlayAddress, BreakerProtocol), BreakerState>>,
+}
+
+impl Default for ProtocolBreaker {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_BREAKER_THRESHOLD,
+            DEFAULT_BREAKER_WINDOW,
+            DEFAULT_BREAKER_COOLDOWN,
+        )
+    }
+}
+
+impl ProtocolBreaker {
+    /// Open after `threshold` malformed responses within `window`, and half-open
+    /// `cooldown` after opening.
+    pub fn new(threshold: NonZeroUsize, window: Duration, cooldown: Duration) -> Self {
+        Self {
+            threshold,
+            window,
+            cooldown,
+            breakers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Malformed responses within the window that open a breaker.
+    pub fn threshold(&self) -> NonZeroUsize {
+        self.threshold
+    }
+
+    /// Record a malformed `protocol` response from `peer`.
+    pub fn record_error(&self, peer: OverlayAddress, protocol: BreakerProtocol) {
+        self.record_error_at(peer, protocol, Instant::now());
+    }
+
+    /// Record a well-formed `protocol` response from `peer`, closing a
+    /// half-open breaker.
+    pub fn record_success(&self, peer: OverlayAddress, protocol: BreakerProtocol) {
+        self.record_success_at(peer, protocol, Instant::now());
+    }
+
+    /// Whether `peer` may be selected for `protocol`.
+    pub fn allows(&self, peer: &OverlayAddress, protocol: BreakerProtocol) -> bool {
+        self.allows_at(peer, protocol, Instant::now())
+    }
+
+    /// Forget every breaker for `peer` on disconnect.
+    ///
+    /// A reconnecting peer starts closed; it reopens after another
+    /// [`threshold`](Self::threshold) malformed responses.
+    pub fn forget(&self, peer: &OverlayAddress) {
+        self.breakers.lock().retain(|(p, _), _| p != peer);
+    }
+
+    /// Drop the `candidates` whose `protocol` breaker is open, keeping order.
+    pub fn retain_allowed(
+        &self,
+        mut candidates: Vec<OverlayAddress>,
+        protocol: BreakerProtocol,
+    ) -> Vec<OverlayAddress> {
+        let now = Instant::now();
+        candidates.retain(|peer| self.allows_at(peer, protocol, now));
+        candidates
+    }
+
+    fn record_error_at(&self, peer: OverlayAddress, protocol: BreakerProtocol, now: Instant) {
+        let mut breakers = self.breakers.lock();
+        let state = breakers
+            .entry((peer, protocol))
+            .or_insert_with(|| BreakerState::Closed(VecDeque::new()));
+        let open = match state {
+            BreakerState::Closed(errors) => {
+                while errors
+                    .front()
+                    .is_some_and(|at| now.duration_since(*at) >= self.window)
+                {
+                    errors.pop_front();
+                }
+                errors.push_back(now);
+                errors.len() >= self.threshold.get()
+            }
+            // The probation response was malformed too.
+            BreakerState::HalfOpen => true,
+            // Already open: the cooldown runs from the opening error.
+            BreakerState::Open { .. } => false,
+        };
+        if open {
+            *state = BreakerState::Open { since: now };
+            let protocol: &'static str = protocol.into();
+            debug!(peer = %peer.to_short_hex(), protocol, "protocol breaker opened");
+            metrics::counter!("swarm.client.breaker_opened", "protocol" => protocol).increment(1);
+        }
+    }
+
+    fn record_success_at(&self, peer: OverlayAddress, protocol: BreakerProtocol, now: Instant) {
+        let mut breakers = self.breakers.lock();
+        let key = (peer, protocol);
+        let forget = match breakers.get_mut(&key) {
+            Some(BreakerState::HalfOpen) => true,
+            Some(BreakerState::Closed(errors)) => {
+                errors.retain(|at| now.duration_since(*at) < self.window);
+                errors.is_empty()
+            }
+            Some(BreakerState::Open { .. }) | None => false,
+        };
+        if forget {
+            breakers.remove(&key);
+        }
+    }
+
+    fn allows_at(&self, peer: &OverlayAddress, protocol: BreakerProtocol, now: Instant) -> bool {
+        let mut breakers = self.breakers.lock();
+        let Some(state) = breakers.get_mut(&(*peer, protocol)) else {
+            return true;
+        };
+        match state {
+            BreakerState::Open { since } if now.duration_since(*since) < self.cooldown => false,
+            BreakerState::Open { .. } => {
+                *state = BreakerState::HalfOpen;
+                true
+            }
+            BreakerState::Closed(_) | BreakerState::HalfOpen => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLD: NonZeroUsize = match NonZeroUsize::new(3) {
+        Some(threshold) => threshold,
+        None => unreachable!(),
+    };
+    const WINDOW: Duration = Duration::from_secs(60);
+    const COOLDOWN: Duration = Duration::from_secs(30);
+
+    fn peer(n: u8) -> OverlayAddress {
+        OverlayAddress::from([n; 32])
+    }
+
+    fn breaker() -> ProtocolBreaker {
+        ProtocolBreaker::new(THRESHOLD, WINDOW, COOLDOWN)
+    }
+
+    /// The breaker must open before the invalid-data ban fires on the same
+    /// reports, or it never gets a say.
+    #[test]
+    fn default_breaker_opens_before_the_invalid_data_ban() {
+        use vertex_swarm_peer_manager::InvalidDataBan;
+
+        let ban = usize::try_from(InvalidDataBan::DEFAULT_THRESHOLD).unwrap();
+        assert!(DEFAULT_BREAKER_THRESHOLD.get() < ban);
+        assert!(DEFAULT_BREAKER_WINDOW <= InvalidDataBan::DEFAULT_WINDOW);
+    }
+
+    #[test]
+    fn retrieval_errors_exclude_a_peer_from_retrieval_only() {
+        let breaker = breaker();
+        let bad = peer(1);
+        let good = peer(2);
+        let candidates = vec![bad, good];
+
+        for _ in 0..THRESHOLD.get() - 1 {
+            breaker.record_error(bad, BreakerProtocol::Retrieval);
+        }
+        assert_eq!(
+            breaker.retain_allowed(candidates.clone(), BreakerProtocol::Retrieval),
+            candidates,
+            "below the threshold the peer stays selectable"
+        );
+
+        breaker.record_error(bad, BreakerProtocol::Retrieval);
+        assert_eq!(
+            breaker.retain_allowed(candidates.clone(), BreakerProtocol::Retrieval),
+            vec![good],
+            "an open breaker drops the peer from retrieval selection"
+        );
+        assert_eq!(
+            breaker.retain_allowed(candidates.clone(), BreakerProtocol::Pushsync),
+            candidates,
+            "the peer is still eligible for pushsync"
+        );
+    }
+
+    #[test]
+    fn errors_outside_the_window_do_not_accumulate() {
+        let breaker = breaker();
+        let p = peer(3);
+        let start = Instant::now();
+
+        for i in 0..THRESHOLD.get() {
+            // Each error lands just after the previous one aged out.
+            let at = start + WINDOW * u32::try_from(i).unwrap();
+            breaker.record_error_at(p, BreakerProtocol::Retrieval, at);
+        }
+        assert!(breaker.allows_at(&p, BreakerProtocol::Retrieval, start + WINDOW * 3));
+    }
+
+    #[test]
+    fn half_open_after_cooldown_then_closes_on_success() {
+        let breaker = breaker();
+        let p = peer(4);
+        let start = Instant::now();
+
+        for _ in 0..THRESHOLD.get() {
+            breaker.record_error_at(p, BreakerProtocol::Retrieval, start);
+        }
+        assert!(!breaker.allows_at(&p, BreakerProtocol::Retrieval, start));
+        assert!(!breaker.allows_at(&p, BreakerProtocol::Retrieval, start + COOLDOWN / 2));
+
+        let probe = start + COOLDOWN;
+        assert!(
+            breaker.allows_at(&p, BreakerProtocol::Retrieval, probe),
+            "half-open after the cooldown"
+        );
+        breaker.record_success_at(p, BreakerProtocol::Retrieval, probe);
+        assert!(breaker.breakers.lock().is_empty(), "closed and forgotten");
+
+        // A fresh history: one error no longer opens it.
+        breaker.record_error_at(p, BreakerProtocol::Retrieval, probe);
+        assert!(breaker.allows_at(&p, BreakerProtocol::Retrieval, probe));
+    }
+
+    #[test]
+    fn forget_drops_every_protocol_for_the_peer() {
+        let breaker = breaker();
+        let (gone, kept) = (peer(6), peer(7));
+
+        for _ in 0..THRESHOLD.get() {
+            breaker.record_error(gone, BreakerProtocol::Retrieval);
+            breaker.record_error(gone, BreakerProtocol::Pushsync);
+            breaker.record_error(kept, BreakerProtocol::Retrieval);
+        }
+        breaker.forget(&gone);
+
+        assert!(breaker.allows(&gone, BreakerProtocol::Retrieval));
+        assert!(breaker.allows(&gone, BreakerProtocol::Pushsync));
+        assert!(!breaker.allows(&kept, BreakerProtocol::Retrieval));
+        assert_eq!(breaker.breakers.lock().len(), 1);
+    }
+
+    #[test]
+    fn a_probation_error_reopens_at_once() {
+        let breaker = breaker();
+        let p = peer(5);
+        let start = Instant::now();
+
+        for _ in 0..THRESHOLD.get() {
+            breaker.record_error_at(p, BreakerProtocol::Pushsync, start);
+        }
+        let probe = start + COOLDOWN;
+        assert!(breaker.allows_at(&p, BreakerProtocol::Pushsync, probe));
+
+        breaker.record_error_at(p, BreakerProtocol::Pushsync, probe);
+        assert!(
+            !breaker.allows_at(&p, BreakerProtocol::Pushsync, probe + COOLDOWN / 2),
+            "one malformed probation response reopens the breaker"
+        );
+        assert!(breaker.allows_at(&p, BreakerProtocol::Pushsync, probe + COOLDOWN));
+    }
+}
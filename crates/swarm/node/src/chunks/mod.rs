@@ -2,5 +2,7 @@
 //! client entry points expose as their RPC chunk surface.
 
 mod providers;
+mod recent;
 
 pub use providers::NetworkChunkProvider;
+pub use recent::{DEFAULT_RECENT_PUSH_CAPACITY, DEFAULT_RECENT_PUSH_TTL, ReadYourWrites};
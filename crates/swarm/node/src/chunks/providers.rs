@@ -8,12 +8,16 @@ use vertex_swarm_api::{
     SwarmChunkSender, SwarmError, SwarmLocalStore, SwarmResult,
 };
 use vertex_swarm_net_pushsync::Receipt;
+use vertex_tasks::time::Duration;
 
+use super::recent::{ReadYourWrites, RecentPushes};
 use crate::ClientHandle;
+use crate::breaker::ProtocolBreaker;
 use crate::dispatch::{
-    CandidateOrdering, DispatchEngine, InflightLimit, LatencyHint, RetrievalTopology,
+    CandidateOrdering, DispatchEngine, InflightLimit, LatencyHint, RetrievalOptions,
+    RetrievalTopology,
 };
-use crate::selection::SettlementTrigger;
+use crate::selection::{PeerSelector, SettlementTrigger};
 
 /// Chunk provider driving the shared retrieval engine, generic over the three
 /// retrieval capabilities: a native client wires the score- and affordability-
@@ -27,6 +31,10 @@ use crate::selection::SettlementTrigger;
 /// receipt still verifies; only an unverifiable early-session view (before the
 /// neighbourhood is credible) yields [`SwarmError::UnconfirmedCustody`].
 ///
+/// With [`with_read_your_writes`](Self::with_read_your_writes), chunks this
+/// provider pushed are served back from a short-lived local cache, so a
+/// publisher reads its own recent uploads before they have propagated.
+///
 /// Every retrieval terminal surfaces as [`SwarmError::RetrievalExhausted`];
 /// forwarding retrieval has no authoritative negative, so absence is never
 /// claimed.
@@ -42,6 +50,9 @@ where
     /// duplicate origin retrieval of a cached content chunk serves locally.
     /// `None` for an embedder that wires a cacheless provider.
     store: Option<Arc<dyn SwarmLocalStore>>,
+    /// Chunks this provider recently pushed, served back to the publisher.
+    /// `None` unless read-your-writes is enabled.
+    recent: Option<Arc<RecentPushes>>,
 }
 
 impl<O, G, L> NetworkChunkProvider<O, G, L>
@@ -78,8 +89,95 @@ where
                 settlement,
             ),
             store,
+            recent: None,
         }
     }
+
+    /// Draw retrieval candidates through `selector` instead of plain proximity.
+    pub fn with_selector(mut self, selector: Arc<dyn PeerSelector>) -> Self {
+        self.engine = self.engine.with_selector(selector);
+        self
+    }
+
+    /// Skip peers whose [`ProtocolBreaker`] is open for retrieval or pushsync.
+    pub fn with_breaker(mut self, breaker: Arc<ProtocolBreaker>) -> Self {
+        self.engine = self.engine.with_breaker(breaker);
+        self
+    }
+
+    /// Serve retrievals of chunks this provider pushed within `config.ttl` from
+    /// a local cache, so a publisher can read back its own recent uploads
+    /// without racing propagation.
+    pub fn with_read_your_writes(mut self, config: ReadYourWrites) -> Self {
+        self.recent = Some(Arc::new(RecentPushes::new(config)));
+        self
+    }
+
+    /// Retrieve `address` under caller-set [`RetrievalOptions`], serving the
+    /// local cache first as [`SwarmChunkProvider::retrieve_chunk`] does. A
+    /// latency-sensitive reader passes [`RetrievalOptions::fast`] to fail with
+    /// [`SwarmError::RetrievalTimeout`] rather than wait out the full retry
+    /// policy.
+    pub async fn retrieve_chunk_with(
+        &self,
+        address: &ChunkAddress,
+        options: RetrievalOptions,
+    ) -> SwarmResult<ChunkRetrievalResult> {
+        if let Some(cached) = self.local(address) {
+            return Ok(cached);
+        }
+        self.engine.retrieve_with(address, options).await
+    }
+
+    /// Serve a retrieval locally when the node already holds the chunk: from
+    /// the store, or from the chunks it recently pushed.
+    fn local(&self, address: &ChunkAddress) -> Option<ChunkRetrievalResult> {
+        self.cached(address)
+            .or_else(|| self.recently_pushed(address))
+    }
+
+    /// Serve the publisher's own recent upload from the read-your-writes
+    /// cache. The pushed chunk was validated before it left, so it is served
+    /// as is.
+    fn recently_pushed(&self, address: &ChunkAddress) -> Option<ChunkRetrievalResult> {
+        let (chunk, stamp) = self.recent.as_ref()?.get(address)?.into_parts();
+        metrics::counter!("swarm.client.read_your_writes_hits").increment(1);
+        Some(ChunkRetrievalResult {
+            chunk,
+            stamp: Some(stamp),
+            served_by: self.engine.topology().overlay_address(),
+            retries: 0,
+            elapsed: Duration::ZERO,
+        })
+    }
+
+    /// Serve our own duplicate retrieval from the local store before racing
+    /// the swarm: no request leaves the node and nothing is booked. On a
+    /// storer the store reads the reserve too. `get` applies the single-owner
+    /// TTL; the node's own overlay stands in as the serving peer to mark a
+    /// local serve.
+    ///
+    /// The address is re-derived from the stored bytes, so a copy that no
+    /// longer answers `address` is evicted and the retrieval falls through
+    /// to the network.
+    fn cached(&self, address: &ChunkAddress) -> Option<ChunkRetrievalResult> {
+        let store = self.store.as_ref()?;
+        let cached = store.get(address).ok()??;
+        if *cached.address() != *address {
+            tracing::warn!(%address, "local chunk does not match its address, evicting");
+            metrics::counter!("swarm.client.local_chunk_mismatch").increment(1);
+            let _ = store.remove(address);
+            return None;
+        }
+        let (chunk, stamp) = cached.into_parts();
+        Some(ChunkRetrievalResult {
+            chunk,
+            stamp,
+            served_by: self.engine.topology().overlay_address(),
+            retries: 0,
+            elapsed: Duration::ZERO,
+        })
+    }
 }
 
 #[async_trait]
@@ -95,27 +193,25 @@ where
     L: LatencyHint + 'static,
 {
     async fn retrieve_chunk(&self, address: &ChunkAddress) -> SwarmResult<ChunkRetrievalResult> {
-        // Serve our own duplicate retrieval from the local cache before racing
-        // the swarm. `get` applies the single-owner TTL and only content chunks
-        // are cached, so a hit is an immutable, byte-safe content chunk; the
-        // node's own overlay stands in as the serving peer to mark a local serve.
-        if let Some(store) = &self.store
-            && let Ok(Some(cached)) = store.get(address)
-            && *cached.address() == *address
-        {
-            let (chunk, stamp) = cached.into_parts();
-            return Ok(ChunkRetrievalResult {
-                chunk,
-                stamp,
-                served_by: self.engine.topology().overlay_address(),
-            });
+        if let Some(cached) = self.local(address) {
+            return Ok(cached);
         }
         self.engine.retrieve(address).await
     }
 
-    fn has_chunk(&self, _address: &ChunkAddress) -> bool {
-        // Client nodes don't have local storage
-        false
+    async fn retrieve_chunk_fast(
+        &self,
+        address: &ChunkAddress,
+        max_latency: Duration,
+    ) -> SwarmResult<ChunkRetrievalResult> {
+        self.retrieve_chunk_with(address, RetrievalOptions::fast(max_latency))
+            .await
+    }
+
+    fn has_chunk(&self, address: &ChunkAddress) -> bool {
+        self.store
+            .as_ref()
+            .is_some_and(|store| store.contains(address))
     }
 }
 
@@ -126,9 +222,15 @@ where
     L: LatencyHint + 'static,
 {
     /// Push `chunk` through the engine's sequential origin push profile,
-    /// projecting the verified receipt onto the public boundary.
+    /// projecting the verified receipt onto the public boundary. Once custody
+    /// is confirmed the chunk enters the read-your-writes cache, if enabled.
     async fn push_to_closest(&self, chunk: StampedChunk) -> SwarmResult<PushReceipt> {
-        self.engine.push(chunk).await.map(push_receipt_of)
+        let Some(recent) = &self.recent else {
+            return self.engine.push(chunk).await.map(push_receipt_of);
+        };
+        let receipt = self.engine.push(chunk.clone()).await?;
+        recent.record(chunk);
+        Ok(push_receipt_of(receipt))
     }
 }
 
@@ -180,6 +282,134 @@ mod tests {
         ChunkAddress::new(bytes)
     }
 
+    mod local_store {
+        use nectar_primitives::{AnyChunk, ContentChunk};
+        use tokio::sync::mpsc;
+        use vertex_swarm_api::SwarmTopologyState;
+        use vertex_swarm_primitives::CachedChunk;
+        use vertex_swarm_test_utils::MockTopology;
+
+        use super::*;
+        use crate::ClientCommand;
+        use crate::dispatch::{NoLatencyHint, ProximityOnly};
+        use crate::test_support::{NoSettle, inflight};
+
+        #[tokio::test]
+        async fn a_locally_stored_chunk_is_served_without_a_network_request() {
+            let chunk: AnyChunk = ContentChunk::new(&b"already-stored"[..])
+                .expect("valid content chunk")
+                .into();
+            let address = *chunk.address();
+            let store: Arc<dyn SwarmLocalStore> =
+                Arc::new(vertex_swarm_localstore::ChunkStore::with_budget(1 << 20, 0));
+            store
+                .put(CachedChunk::new(chunk.clone(), None))
+                .expect("store the chunk");
+
+            // A connected peer is available, so a miss would dispatch to it.
+            let topology = Arc::new(
+                MockTopology::new(1, 1, 0).with_closest(vec![SwarmAddress::from([1u8; 32])]),
+            );
+            let (tx, mut rx) = mpsc::channel::<ClientCommand>(16);
+            let provider = NetworkChunkProvider::new(
+                ClientHandle::new(tx),
+                Arc::clone(&topology) as Arc<dyn RetrievalTopology>,
+                Bin::MAX,
+                ProximityOnly,
+                inflight(),
+                NoLatencyHint,
+                Arc::new(NoSettle),
+                Some(Arc::clone(&store)),
+            );
+
+            assert!(provider.has_chunk(&address));
+            let result = provider
+                .retrieve_chunk(&address)
+                .await
+                .expect("served locally");
+            assert_eq!(result.chunk, chunk);
+            assert_eq!(result.served_by, topology.overlay_address());
+            assert_eq!(result.retries, 0);
+            assert!(
+                rx.try_recv().is_err(),
+                "no client command reaches the network"
+            );
+        }
+    }
+
+    mod read_your_writes {
+        use alloy_primitives::{B256, Signature};
+        use nectar_primitives::ContentChunk;
+        use tokio::sync::mpsc;
+        use vertex_swarm_api::{Stamp, StorageRadius, SwarmTopologyState};
+        use vertex_swarm_test_utils::{MockTopology, test_identity};
+
+        use super::*;
+        use crate::dispatch::{NoLatencyHint, ProximityOnly};
+        use crate::test_support::{NoSettle, inflight};
+        use crate::{ClientCommand, ReadYourWrites};
+
+        fn test_stamp() -> Stamp {
+            let mut raw = [0u8; 65];
+            raw[..64].fill(1);
+            raw[64] = 27;
+            let sig = Signature::try_from(&raw[..]).expect("valid signature bytes");
+            Stamp::new(B256::repeat_byte(0xaa), 3, 7, 42, sig)
+        }
+
+        #[tokio::test]
+        async fn a_pushed_chunk_reads_back_without_a_network_request() {
+            let chunk = ContentChunk::new(&b"just-pushed"[..]).expect("valid content chunk");
+            let stamped = StampedChunk::new(chunk.into(), test_stamp());
+            let address = *stamped.address();
+
+            // Depth zero on a credible view: any signed receipt verifies.
+            let topology = Arc::new(
+                MockTopology::new(1, 1, 0).with_closest(vec![SwarmAddress::from([1u8; 32])]),
+            );
+            let (tx, mut rx) = mpsc::channel::<ClientCommand>(16);
+            let provider = NetworkChunkProvider::new(
+                ClientHandle::new(tx),
+                Arc::clone(&topology) as Arc<dyn RetrievalTopology>,
+                Bin::MAX,
+                ProximityOnly,
+                inflight(),
+                NoLatencyHint,
+                Arc::new(NoSettle),
+                None,
+            )
+            .with_read_your_writes(ReadYourWrites::default());
+
+            let storer = async {
+                match rx.recv().await.expect("push dispatched") {
+                    ClientCommand::PushChunk {
+                        address, response, ..
+                    } => {
+                        let receipt = Receipt::sign(&test_identity(), address, StorageRadius::ZERO)
+                            .expect("sign receipt");
+                        response.send(Ok(receipt)).ok();
+                    }
+                    other => panic!("unexpected command: {other:?}"),
+                }
+            };
+            let (pushed, ()) = tokio::join!(provider.send_chunk_unchecked(stamped.clone()), storer);
+            pushed.expect("custody confirmed");
+
+            let result = provider
+                .retrieve_chunk(&address)
+                .await
+                .expect("served from the recent-push cache");
+            assert_eq!(result.chunk, *stamped.chunk());
+            assert_eq!(result.stamp.as_ref(), Some(stamped.stamp()));
+            assert_eq!(result.served_by, topology.overlay_address());
+            assert_eq!(result.retries, 0);
+            assert!(
+                rx.try_recv().is_err(),
+                "the read-back never reaches the network"
+            );
+        }
+    }
+
     mod staggered_race {
         use std::time::{Duration, Instant};
 
@@ -592,7 +822,7 @@ mod tests {
     }
 
     mod gated_fallback {
-        use vertex_swarm_api::SwarmError;
+        use vertex_swarm_api::{RetrievalFailure, SwarmError};
 
         use super::address;
 
@@ -605,6 +835,7 @@ mod tests {
             // the consumer.
             let retrieval = SwarmError::RetrievalExhausted {
                 address: address(0xaa),
+                reason: RetrievalFailure::NoPeers,
             };
             assert!(matches!(retrieval, SwarmError::RetrievalExhausted { .. }));
 
@@ -0,0 +1,148 @@
+//! Read-your-writes cache of recently pushed chunks.
+//!
+//! A publisher that just pushed a chunk expects to read it back at once, but
+//! the chunk may not yet have propagated to the storers a retrieval reaches.
+//! [`RecentPushes`] keeps each chunk the provider pushed successfully for a
+//! short window, and retrieval consults it before racing the swarm, so the
+//! publisher can always read back its own recent uploads.
+
+use std::collections::{HashMap, VecDeque};
+
+use parking_lot::Mutex;
+use vertex_swarm_api::{ChunkAddress, StampedChunk};
+use vertex_tasks::time::{Duration, Instant};
+
+/// How long a pushed chunk is served locally. Ample for the chunk to reach
+/// its neighbourhood.
+pub const DEFAULT_RECENT_PUSH_TTL: Duration = Duration::from_secs(60);
+
+/// Pushed chunks kept before the oldest are dropped: about 16 MiB of 4 KiB
+/// chunks.
+pub const DEFAULT_RECENT_PUSH_CAPACITY: usize = 4096;
+
+/// Read-your-writes settings for a [`NetworkChunkProvider`].
+///
+/// [`NetworkChunkProvider`]: super::NetworkChunkProvider
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadYourWrites {
+    /// How long a pushed chunk is served from the cache.
+    pub ttl: Duration,
+    /// Chunks kept before the oldest are evicted.
+    pub capacity: usize,
+}
+
+impl Default for ReadYourWrites {
+    fn default() -> Self {
+        Self {
+            ttl: DEFAULT_RECENT_PUSH_TTL,
+            capacity: DEFAULT_RECENT_PUSH_CAPACITY,
+        }
+    }
+}
+
+/// Bounded, short-lived cache of the chunks this node pushed.
+pub(crate) struct RecentPushes {
+    ttl: Duration,
+    capacity: usize,
+    inner: Mutex<Entries>,
+}
+
+#[derive(Default)]
+struct Entries {
+    chunks: HashMap<ChunkAddress, (Instant, StampedChunk)>,
+    /// Insertion order for expiry and eviction. A re-pushed chunk leaves a
+    /// stale record behind, skipped because its time no longer matches.
+    order: VecDeque<(Instant, ChunkAddress)>,
+}
+
+impl Entries {
+    fn pop_oldest(&mut self) -> bool {
+        let Some((at, address)) = self.order.pop_front() else {
+            return false;
+        };
+        if self.chunks.get(&address).is_some_and(|(t, _)| *t == at) {
+            self.chunks.remove(&address);
+        }
+        true
+    }
+}
+
+impl RecentPushes {
+    pub(crate) fn new(config: ReadYourWrites) -> Self {
+        Self {
+            ttl: config.ttl,
+            capacity: config.capacity.max(1),
+            inner: Mutex::new(Entries::default()),
+        }
+    }
+
+    /// Keep `chunk` for the read-your-writes window, expiring stale entries
+    /// and evicting the oldest once at capacity.
+    pub(crate) fn record(&self, chunk: StampedChunk) {
+        let now = Instant::now();
+        let mut entries = self.inner.lock();
+        while entries
+            .order
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) >= self.ttl)
+        {
+            entries.pop_oldest();
+        }
+        let address = *chunk.address();
+        entries.chunks.insert(address, (now, chunk));
+        entries.order.push_back((now, address));
+        while entries.chunks.len() > self.capacity && entries.pop_oldest() {}
+    }
+
+    /// The chunk pushed at `address`, while still inside the window.
+    pub(crate) fn get(&self, address: &ChunkAddress) -> Option<StampedChunk> {
+        let entries = self.inner.lock();
+        let (at, chunk) = entries.chunks.get(address)?;
+        (at.elapsed() < self.ttl).then(|| chunk.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{B256, Signature};
+    use nectar_primitives::ContentChunk;
+    use vertex_swarm_api::Stamp;
+
+    use super::*;
+
+    fn stamped(data: &'static [u8]) -> StampedChunk {
+        let mut raw = [0u8; 65];
+        raw[..64].fill(1);
+        raw[64] = 27;
+        let sig = Signature::try_from(&raw[..]).expect("valid signature bytes");
+        let chunk = ContentChunk::new(data).expect("valid content chunk");
+        StampedChunk::new(chunk.into(), Stamp::new(B256::ZERO, 0, 0, 0, sig))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn pushes_expire_after_the_window_and_evict_at_capacity() {
+        let recent = RecentPushes::new(ReadYourWrites {
+            ttl: Duration::from_secs(10),
+            capacity: 2,
+        });
+        let (a, b, c) = (stamped(b"a"), stamped(b"b"), stamped(b"c"));
+
+        recent.record(a.clone());
+        tokio::time::advance(Duration::from_secs(6)).await;
+        recent.record(b.clone());
+        assert!(recent.get(a.address()).is_some());
+
+        tokio::time::advance(Duration::from_secs(5)).await;
+        assert!(recent.get(a.address()).is_none(), "past the window");
+        assert!(recent.get(b.address()).is_some());
+
+        // Re-pushing refreshes the window; the third chunk then evicts the
+        // oldest live entry at capacity.
+        recent.record(b.clone());
+        recent.record(c.clone());
+        recent.record(a.clone());
+        assert!(recent.get(b.address()).is_none(), "evicted at capacity");
+        assert!(recent.get(c.address()).is_some());
+        assert!(recent.get(a.address()).is_some());
+    }
+}
@@ -3,20 +3,25 @@
 //! Owns channels to `ClientBehaviour` and processes incoming events.
 
 use std::sync::Arc;
+use std::time::Duration;
 
-use nectar_primitives::ChunkAddress;
+use alloy_primitives::{Address, B256, U256, keccak256};
+use futures::future::{self, Either};
+use futures_timer::Delay;
+use nectar_primitives::{AnyChunk, ChunkAddress, PrimitivesError, SingleOwnerChunk};
 use tokio::sync::{mpsc, oneshot};
 use tracing::{debug, warn};
 use vertex_swarm_api::{
-    Admission, AdmissionControl, Au, BandwidthDebit, PeerReporter, ReportSource, SwarmLocalStore,
-    SwarmPricing, SwarmScoringEvent,
+    Admission, AdmissionControl, Au, BandwidthDebit, DEFAULT_MIN_PAYMENT_THRESHOLD, NetworkIngest,
+    PeerReporter, ReportSource, SwarmLocalStore, SwarmPricing, SwarmScoringEvent,
 };
 use vertex_swarm_client_protocol::PseudosettleAck;
 pub use vertex_swarm_client_protocol::{ChunkTransferError, RetrievalResult};
 use vertex_swarm_net_pushsync::Receipt;
-use vertex_swarm_primitives::{CachedChunk, OverlayAddress, StampedChunk};
+use vertex_swarm_primitives::{Cacheability, OverlayAddress, StampedChunk, SwarmAddressExt};
 use vertex_tasks::{GracefulShutdown, MaybeSend, SpawnableTask};
 
+use crate::breaker::{BreakerProtocol, ProtocolBreaker};
 use crate::inflight::PeerInflightLimiter;
 use crate::protocol::{ClientCommand, ClientEvent, FailureKind};
 use crate::retrieval_latency::RetrievalLatency;
@@ -24,9 +29,44 @@ use crate::selection::SettlementTrigger;
 
 const RETRIEVAL_SOURCE: ReportSource = ReportSource::Protocol("retrieval");
 const PUSHSYNC_SOURCE: ReportSource = ReportSource::Protocol("pushsync");
+const PRICING_SOURCE: ReportSource = ReportSource::Protocol("pricing");
 
 pub(crate) const DEFAULT_CHANNEL_CAPACITY: usize = 256;
 
+/// Default time a request waits for room in a full command channel before it
+/// fails as [`ChunkTransferError::Overloaded`].
+pub const DEFAULT_COMMAND_SEND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Why a single-owner chunk retrieval by owner and identifier failed.
+#[derive(Debug, thiserror::Error, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum SocRetrievalError {
+    /// The underlying retrieval failed.
+    #[error(transparent)]
+    Transfer(#[from] ChunkTransferError),
+    /// The peer answered the derived address with a content chunk.
+    #[error("expected a single-owner chunk at {0}")]
+    NotSingleOwner(ChunkAddress),
+    /// The signature does not recover a signer.
+    #[error("invalid single-owner signature")]
+    InvalidSignature(#[source] PrimitivesError),
+    /// The signature recovers a signer other than the requested owner.
+    #[error("single-owner chunk signed by {actual}, expected {expected}")]
+    OwnerMismatch { expected: Address, actual: Address },
+}
+
+/// Address of the single-owner chunk `owner` publishes under `id`:
+/// `keccak256(id || owner)`.
+///
+/// Crate-private until nectar exposes the derivation next to
+/// `SingleOwnerChunk`.
+pub(crate) fn soc_address(owner: &Address, id: &B256) -> ChunkAddress {
+    let mut preimage = [0u8; 52];
+    preimage[..32].copy_from_slice(id.as_slice());
+    preimage[32..].copy_from_slice(owner.as_slice());
+    ChunkAddress::new(keccak256(preimage).0)
+}
+
 /// Handle for sending commands to the network layer.
 ///
 /// Request methods ([`Self::retrieve_chunk`], [`Self::push_chunk`]) thread a
@@ -42,6 +82,8 @@ pub struct ClientHandle {
     /// reached no charge. Absent on the lightweight launcher, where origin
     /// dispatch neither books nor gates.
     origin: Option<OriginGate>,
+    /// How long a request waits for room in a full command channel.
+    send_timeout: Duration,
 }
 
 /// Book-at-send and the admission band for origin requests.
@@ -65,9 +107,19 @@ impl ClientHandle {
         Self {
             command_tx,
             origin: None,
+            send_timeout: DEFAULT_COMMAND_SEND_TIMEOUT,
         }
     }
 
+    /// Replace how long a request waits for room in a full command channel
+    /// before failing as [`ChunkTransferError::Overloaded`] (default
+    /// [`DEFAULT_COMMAND_SEND_TIMEOUT`]).
+    #[must_use]
+    pub fn with_send_timeout(mut self, timeout: Duration) -> Self {
+        self.send_timeout = timeout;
+        self
+    }
+
     /// Attach the origin credit gate so an own-request dispatch bands the
     /// request and books its price at the moment it dispatches.
     ///
@@ -164,7 +216,7 @@ impl ClientHandle {
             mpsc::error::TrySendError::Full(_) => {
                 warn!("Client command channel full");
                 metrics::counter!("swarm.client.commands_dropped").increment(1);
-                ChunkTransferError::ChannelClosed
+                ChunkTransferError::Overloaded
             }
             mpsc::error::TrySendError::Closed(_) => ChunkTransferError::ChannelClosed,
         })
@@ -188,6 +240,28 @@ impl ClientHandle {
             .map_err(|_| ChunkTransferError::ChannelClosed)
     }
 
+    /// Send a request command, waiting up to the send timeout for room.
+    ///
+    /// The backpressure path for request callers: a full channel holds the
+    /// caller instead of growing a queue, and a channel that stays full for the
+    /// whole timeout fails the request as [`ChunkTransferError::Overloaded`]
+    /// without dispatching it.
+    async fn send_request(&self, command: ClientCommand) -> Result<(), ChunkTransferError> {
+        let reserve = std::pin::pin!(self.command_tx.reserve());
+        match future::select(reserve, Delay::new(self.send_timeout)).await {
+            Either::Left((Ok(permit), _)) => {
+                permit.send(command);
+                Ok(())
+            }
+            Either::Left((Err(_), _)) => Err(ChunkTransferError::ChannelClosed),
+            Either::Right(_) => {
+                warn!(timeout = ?self.send_timeout, "Client command channel overloaded");
+                metrics::counter!("swarm.client.commands_overloaded").increment(1);
+                Err(ChunkTransferError::Overloaded)
+            }
+        }
+    }
+
     /// Retrieve a chunk from a specific peer.
     ///
     /// Any failure on the path resolves or drops the response channel, so this
@@ -199,18 +273,38 @@ impl ClientHandle {
         peer: OverlayAddress,
         address: ChunkAddress,
         originated: bool,
+    ) -> Result<RetrievalResult, ChunkTransferError> {
+        self.retrieve_chunk_with_budget(peer, address, originated, None, None)
+            .await
+    }
+
+    /// [`retrieve_chunk`](Self::retrieve_chunk) with an explicit hop and time
+    /// budget on the request; `None` sends the handler's configured TTL or
+    /// retrieval timeout.
+    pub async fn retrieve_chunk_with_budget(
+        &self,
+        peer: OverlayAddress,
+        address: ChunkAddress,
+        originated: bool,
+        ttl: Option<u8>,
+        deadline: Option<Duration>,
     ) -> Result<RetrievalResult, ChunkTransferError> {
         // Gate on the band and book the price at dispatch.
         let committed = self.reserve_origin(peer, &address, originated)?;
 
         let (tx, rx) = oneshot::channel();
 
-        if let Err(e) = self.send_command(ClientCommand::RetrieveChunk {
-            peer,
-            address,
-            response: tx,
-            originated,
-        }) {
+        if let Err(e) = self
+            .send_request(ClientCommand::RetrieveChunk {
+                peer,
+                address,
+                response: tx,
+                originated,
+                ttl,
+                deadline,
+            })
+            .await
+        {
             // Never reached the wire, so nothing was charged: refund.
             self.refund_origin(peer, committed);
             return Err(e);
@@ -227,6 +321,34 @@ impl ClientHandle {
         result
     }
 
+    /// Retrieve the single-owner chunk `owner` published under `id` from a
+    /// specific peer.
+    ///
+    /// Derives the address from `owner` and `id`, retrieves it as an origin
+    /// request, and checks that the delivery is a single-owner chunk whose
+    /// signature recovers `owner`. A content chunk or a foreign signer at the
+    /// derived address is refused rather than returned.
+    pub async fn retrieve_soc(
+        &self,
+        peer: OverlayAddress,
+        owner: Address,
+        id: B256,
+    ) -> Result<SingleOwnerChunk, SocRetrievalError> {
+        let address = soc_address(&owner, &id);
+        let result = self.retrieve_chunk(peer, address, true).await?;
+        let AnyChunk::SingleOwner(soc) = result.chunk else {
+            return Err(SocRetrievalError::NotSingleOwner(address));
+        };
+        let actual = soc.owner().map_err(SocRetrievalError::InvalidSignature)?;
+        if actual != owner {
+            return Err(SocRetrievalError::OwnerMismatch {
+                expected: owner,
+                actual,
+            });
+        }
+        Ok(soc)
+    }
+
     /// Push a stamped chunk to a specific peer.
     ///
     /// Same failure semantics as [`Self::retrieve_chunk`]. The returned
@@ -246,13 +368,16 @@ impl ClientHandle {
 
         let (tx, rx) = oneshot::channel();
 
-        if let Err(e) = self.send_command(ClientCommand::PushChunk {
-            peer,
-            address,
-            chunk,
-            response: tx,
-            originated,
-        }) {
+        if let Err(e) = self
+            .send_request(ClientCommand::PushChunk {
+                peer,
+                address,
+                chunk,
+                response: tx,
+                originated,
+            })
+            .await
+        {
             self.refund_origin(peer, committed);
             return Err(e);
         }
@@ -277,12 +402,22 @@ pub struct ClientService {
     /// Client cache for the node's own retrieval deliveries. Content chunks are
     /// cached; single-owner chunks are not (no version signal).
     store: Option<Arc<dyn SwarmLocalStore>>,
+    /// The network's chunk set; a delivery is validated against it before it
+    /// is cached.
+    ingest: NetworkIngest,
     /// Per-peer retrieval in-flight limiter shared with the chunk provider;
     /// the peer entry is forgotten on disconnect.
     inflight: Option<Arc<PeerInflightLimiter>>,
+    /// Per-peer, per-protocol breaker shared with the chunk provider; fed
+    /// malformed and well-formed responses, consulted at selection. The peer's
+    /// breakers are forgotten on disconnect.
+    breaker: Option<Arc<ProtocolBreaker>>,
     /// Per-PO retrieval-latency estimate shared with the chunk provider; a
     /// completed originated retrieval is recorded here keyed by its proximity.
     retrieval_latency: Option<Arc<RetrievalLatency>>,
+    /// Lowest payment threshold a peer may announce; a lower announcement is
+    /// reported as an accounting violation.
+    min_payment_threshold: U256,
 }
 
 impl ClientService {
@@ -299,8 +434,11 @@ impl ClientService {
             event_rx,
             reporter: None,
             store: None,
+            ingest: NetworkIngest::default(),
             inflight: None,
+            breaker: None,
             retrieval_latency: None,
+            min_payment_threshold: U256::from(DEFAULT_MIN_PAYMENT_THRESHOLD),
         };
 
         (service, event_tx, handle)
@@ -319,8 +457,11 @@ impl ClientService {
             event_rx,
             reporter: None,
             store: None,
+            ingest: NetworkIngest::default(),
             inflight: None,
+            breaker: None,
             retrieval_latency: None,
+            min_payment_threshold: U256::from(DEFAULT_MIN_PAYMENT_THRESHOLD),
         };
 
         (service, handle)
@@ -345,6 +486,14 @@ impl ClientService {
         self
     }
 
+    /// Validate cached deliveries against the spec's chunk set instead of the
+    /// standard one.
+    #[must_use]
+    pub fn with_ingest(mut self, ingest: NetworkIngest) -> Self {
+        self.ingest = ingest;
+        self
+    }
+
     /// Attach the per-peer retrieval in-flight limiter so the service forgets a
     /// peer's slot accounting on disconnect.
     ///
@@ -356,6 +505,16 @@ impl ClientService {
         self
     }
 
+    /// Attach the per-protocol breaker so malformed responses take a peer out
+    /// of selection for the protocol that produced them.
+    ///
+    /// Must be the same [`ProtocolBreaker`] the chunk provider selects against.
+    #[must_use]
+    pub fn with_breaker(mut self, breaker: Arc<ProtocolBreaker>) -> Self {
+        self.breaker = Some(breaker);
+        self
+    }
+
     /// Attach the per-PO retrieval-latency estimate so a completed originated
     /// retrieval feeds the hedge the chunk provider paces its race with.
     ///
@@ -367,6 +526,14 @@ impl ClientService {
         self
     }
 
+    /// Set the lowest payment threshold a peer may announce, normally the
+    /// spec's [`min_payment_threshold`](vertex_swarm_api::SwarmSpec::min_payment_threshold).
+    #[must_use]
+    pub fn with_min_payment_threshold(mut self, threshold: u64) -> Self {
+        self.min_payment_threshold = U256::from(threshold);
+        self
+    }
+
     /// Get a handle for sending commands.
     pub fn handle(&self) -> ClientHandle {
         self.handle.clone()
@@ -384,6 +551,18 @@ impl ClientService {
         }
     }
 
+    fn record_error(&self, peer: OverlayAddress, protocol: BreakerProtocol) {
+        if let Some(breaker) = &self.breaker {
+            breaker.record_error(peer, protocol);
+        }
+    }
+
+    fn record_success(&self, peer: OverlayAddress, protocol: BreakerProtocol) {
+        if let Some(breaker) = &self.breaker {
+            breaker.record_success(peer, protocol);
+        }
+    }
+
     /// Run the event processing loop with graceful shutdown support.
     pub async fn run(mut self, shutdown: GracefulShutdown) {
         let mut shutdown = std::pin::pin!(shutdown);
@@ -413,8 +592,8 @@ impl ClientService {
     fn process_event(&self, event: ClientEvent) {
         match event {
             ClientEvent::PeerActivated { peer_id, overlay } => {
-                debug!(%peer_id, %overlay, "Peer activated for client protocols");
-                // TODO: Trigger pricing announcement based on peer type
+                debug!(%peer_id, overlay = %overlay.to_short_hex(), "Peer activated for client protocols");
+                // Pricing is announced by the behaviour on activation, by peer node type.
             }
 
             ClientEvent::PricingReceived {
@@ -423,7 +602,23 @@ impl ClientService {
                 threshold,
             } => {
                 debug!(%peer_id, %peer, %threshold, "Received pricing threshold");
-                // TODO: Validate threshold against minimum
+                // A threshold under the network floor would have us settle
+                // faster than the accounting is tuned to pay. Severe, so the
+                // peer manager bans and disconnects the peer.
+                if threshold < self.min_payment_threshold {
+                    warn!(
+                        %peer,
+                        %threshold,
+                        floor = %self.min_payment_threshold,
+                        "Peer announced a payment threshold below the network floor"
+                    );
+                    metrics::counter!("swarm.client.pricing_rejected").increment(1);
+                    self.report(
+                        &peer,
+                        SwarmScoringEvent::AccountingViolation,
+                        PRICING_SOURCE,
+                    );
+                }
                 // TODO: Store peer's threshold for bandwidth accounting
             }
 
@@ -454,10 +649,11 @@ impl ClientService {
                     latency_estimate.record(address.proximity(&peer).get(), latency);
                 }
                 if let Some(store) = &self.store
-                    && chunk.is_content()
+                    && Cacheability::of(&chunk).is_immutable()
                 {
-                    let _ = store.put(CachedChunk::new(chunk, stamp));
+                    let _ = self.ingest.put(&**store, chunk, stamp);
                 }
+                self.record_success(peer, BreakerProtocol::Retrieval);
                 self.report(
                     &peer,
                     SwarmScoringEvent::RetrievalSuccess { latency },
@@ -480,6 +676,11 @@ impl ClientService {
                 metrics::counter!("swarm.client.inbound_missed").increment(1);
             }
 
+            ClientEvent::InboundNotReady { peer, address } => {
+                debug!(%peer, %address, "Inbound retrieval refused: not enough peers to serve");
+                metrics::counter!("swarm.client.inbound_not_ready").increment(1);
+            }
+
             ClientEvent::InboundRelayed { peer } => {
                 debug!(%peer, "Relayed pushsync receipt to pusher");
                 metrics::counter!("swarm.client.inbound_relayed").increment(1);
@@ -495,6 +696,22 @@ impl ClientService {
                 metrics::counter!("swarm.client.inbound_push_failed").increment(1);
             }
 
+            ClientEvent::InboundStalled { peer, protocol } => {
+                // The peer held an inbound substream without progress past the
+                // protocol's deadline; the handler dropped it. Score the stall.
+                debug!(%peer, %protocol, "Inbound substream stalled (substream dropped)");
+                metrics::counter!(
+                    "swarm.client.inbound_stalled",
+                    "protocol" => protocol,
+                )
+                .increment(1);
+                self.report(
+                    &peer,
+                    SwarmScoringEvent::ProtocolError,
+                    ReportSource::Protocol(protocol),
+                );
+            }
+
             ClientEvent::ReceiptReceived {
                 peer,
                 address,
@@ -506,6 +723,7 @@ impl ClientService {
                 // reservation, not here; a relay leg is accounted by the
                 // forwarder.
                 debug!(%peer, %address, ?latency, "Receipt received");
+                self.record_success(peer, BreakerProtocol::Pushsync);
                 self.report(
                     &peer,
                     SwarmScoringEvent::PushSuccess { latency },
@@ -514,10 +732,13 @@ impl ClientService {
             }
 
             ClientEvent::PeerDisconnected { peer_id, overlay } => {
-                debug!(%peer_id, %overlay, "Peer disconnected");
+                debug!(%peer_id, overlay = %overlay.to_short_hex(), "Peer disconnected");
                 if let Some(inflight) = &self.inflight {
                     inflight.forget(&overlay);
                 }
+                if let Some(breaker) = &self.breaker {
+                    breaker.forget(&overlay);
+                }
             }
 
             ClientEvent::ProtocolError {
@@ -543,11 +764,14 @@ impl ClientService {
                 kind,
             } => {
                 // Scoring policy: a malformed chunk is misbehaviour and scored
-                // adversely. A plain `Protocol` failure (miss, timeout, or
-                // transport error) is blameless and not scored, so a bulk
-                // download's flood of misses cannot decay the peer set past the
-                // disconnect threshold; the staggered race steers around an
-                // unhelpful candidate within a request instead.
+                // adversely. A timeout is a slow or withholding peer and may be
+                // transient, so it costs a mild, decaying `RetrievalFailure`
+                // without touching the breaker. A lost connection is not scored
+                // here: the peer is gone and topology scores the disconnect. A
+                // plain `Protocol` failure (a miss) is blameless and not scored,
+                // so a bulk download's flood of misses cannot decay the peer set
+                // past the disconnect threshold; the staggered race steers
+                // around an unhelpful candidate within a request instead.
                 warn!(%peer, %address, %error, ?kind, "Retrieval failed");
                 match kind {
                     FailureKind::InvalidChunk => {
@@ -556,8 +780,19 @@ impl ClientService {
                             "protocol" => "retrieval",
                         )
                         .increment(1);
+                        self.record_error(peer, BreakerProtocol::Retrieval);
                         self.report(&peer, SwarmScoringEvent::InvalidData, RETRIEVAL_SOURCE);
                     }
+                    FailureKind::TimedOut => {
+                        self.report(&peer, SwarmScoringEvent::RetrievalFailure, RETRIEVAL_SOURCE);
+                    }
+                    FailureKind::ConnectionLost => {
+                        metrics::counter!(
+                            "swarm.client.connection_lost",
+                            "protocol" => "retrieval",
+                        )
+                        .increment(1);
+                    }
                     FailureKind::Protocol => {
                         // Blameless miss: counted but not scored.
                         metrics::counter!(
@@ -576,7 +811,8 @@ impl ClientService {
                 kind,
             } => {
                 // Same scoring policy as retrieval: a malformed receipt is
-                // scored, a plain `Protocol` failure is blameless.
+                // scored, a timeout costs a decaying `PushFailure`, and a lost
+                // connection or a plain `Protocol` failure is not scored.
                 warn!(%peer, %address, %error, ?kind, "Push failed");
                 match kind {
                     FailureKind::InvalidChunk => {
@@ -585,8 +821,19 @@ impl ClientService {
                             "protocol" => "pushsync",
                         )
                         .increment(1);
+                        self.record_error(peer, BreakerProtocol::Pushsync);
                         self.report(&peer, SwarmScoringEvent::InvalidData, PUSHSYNC_SOURCE);
                     }
+                    FailureKind::TimedOut => {
+                        self.report(&peer, SwarmScoringEvent::PushFailure, PUSHSYNC_SOURCE);
+                    }
+                    FailureKind::ConnectionLost => {
+                        metrics::counter!(
+                            "swarm.client.connection_lost",
+                            "protocol" => "pushsync",
+                        )
+                        .increment(1);
+                    }
                     FailureKind::Protocol => {
                         metrics::counter!(
                             "swarm.client.retrieval_miss",
@@ -796,6 +1043,108 @@ mod tests {
         reporter.assert_none();
     }
 
+    #[test]
+    fn timeout_is_scored_but_a_lost_connection_is_not() {
+        let (service, reporter) = service_with_reporter();
+        service.process_event(ClientEvent::RetrievalFailed {
+            peer: peer(11),
+            address: ChunkAddress::zero(),
+            error: "connection reset".into(),
+            kind: FailureKind::ConnectionLost,
+        });
+        service.process_event(ClientEvent::PushFailed {
+            peer: peer(11),
+            address: ChunkAddress::zero(),
+            error: "connection reset".into(),
+            kind: FailureKind::ConnectionLost,
+        });
+        reporter.assert_none();
+
+        service.process_event(ClientEvent::RetrievalFailed {
+            peer: peer(12),
+            address: ChunkAddress::zero(),
+            error: "timeout".into(),
+            kind: FailureKind::TimedOut,
+        });
+        let (reported_peer, event, source) = reporter.single();
+        assert_eq!(reported_peer, peer(12));
+        assert_eq!(event, SwarmScoringEvent::RetrievalFailure);
+        assert_eq!(source, ReportSource::Protocol("retrieval"));
+        assert!(
+            event.default_weight() > SwarmScoringEvent::InvalidData.default_weight(),
+            "a timeout costs less than malformed data"
+        );
+    }
+
+    #[test]
+    fn timed_out_retrievals_do_not_trip_the_breaker() {
+        let breaker = Arc::new(ProtocolBreaker::default());
+        let (service, _event_tx, _handle) = ClientService::new();
+        let service = service.with_breaker(Arc::clone(&breaker));
+        for _ in 0..breaker.threshold().get() {
+            service.process_event(ClientEvent::RetrievalFailed {
+                peer: peer(13),
+                address: ChunkAddress::zero(),
+                error: "timeout".into(),
+                kind: FailureKind::TimedOut,
+            });
+        }
+        assert!(breaker.allows(&peer(13), BreakerProtocol::Retrieval));
+    }
+
+    #[test]
+    fn pricing_below_the_spec_floor_is_rejected() {
+        use vertex_swarm_api::SwarmSpec;
+
+        let spec = vertex_swarm_spec::SpecBuilder::dev()
+            .min_payment_threshold(5_000_000)
+            .build();
+        let (service, reporter) = service_with_reporter();
+        let service = service.with_min_payment_threshold(spec.min_payment_threshold());
+        let pricing = |n, threshold: u64| ClientEvent::PricingReceived {
+            peer: peer(n),
+            peer_id: libp2p::identity::Keypair::generate_ed25519()
+                .public()
+                .to_peer_id(),
+            threshold: U256::from(threshold),
+        };
+
+        service.process_event(pricing(8, 5_000_000));
+        service.process_event(pricing(9, 6_000_000));
+        reporter.assert_none();
+
+        service.process_event(pricing(10, 4_999_999));
+        let (reported_peer, event, source) = reporter.single();
+        assert_eq!(reported_peer, peer(10));
+        assert_eq!(event, SwarmScoringEvent::AccountingViolation);
+        assert_eq!(source, ReportSource::Protocol("pricing"));
+    }
+
+    #[test]
+    fn malformed_retrievals_trip_the_retrieval_breaker_only() {
+        let breaker = Arc::new(ProtocolBreaker::default());
+        let (service, _event_tx, _handle) = ClientService::new();
+        let service = service.with_breaker(Arc::clone(&breaker));
+        let failure = |kind| ClientEvent::RetrievalFailed {
+            peer: peer(6),
+            address: ChunkAddress::zero(),
+            error: "invalid chunk".into(),
+            kind,
+        };
+
+        // Blameless misses never count toward the breaker.
+        for _ in 0..breaker.threshold().get() {
+            service.process_event(failure(FailureKind::Protocol));
+        }
+        assert!(breaker.allows(&peer(6), BreakerProtocol::Retrieval));
+
+        for _ in 0..breaker.threshold().get() {
+            service.process_event(failure(FailureKind::InvalidChunk));
+        }
+        assert!(!breaker.allows(&peer(6), BreakerProtocol::Retrieval));
+        assert!(breaker.allows(&peer(6), BreakerProtocol::Pushsync));
+    }
+
     #[test]
     fn inbound_malformed_delivery_reports_invalid_data_against_sender() {
         let (service, reporter) = service_with_reporter();
@@ -809,6 +1158,19 @@ mod tests {
         assert_eq!(source, ReportSource::Protocol("pushsync"));
     }
 
+    #[test]
+    fn inbound_stall_reports_protocol_error_against_peer() {
+        let (service, reporter) = service_with_reporter();
+        service.process_event(ClientEvent::InboundStalled {
+            peer: peer(7),
+            protocol: "retrieval",
+        });
+        let (reported_peer, event, source) = reporter.single();
+        assert_eq!(reported_peer, peer(7));
+        assert_eq!(event, SwarmScoringEvent::ProtocolError);
+        assert_eq!(source, ReportSource::Protocol("retrieval"));
+    }
+
     #[test]
     fn receipt_received_reports_push_success_with_latency() {
         let (service, reporter) = service_with_reporter();
@@ -1391,6 +1753,76 @@ mod tests {
         Stamp::new(B256::repeat_byte(0xaa), 3, 7, 42, sig)
     }
 
+    fn signed_soc(id: B256) -> (Address, SingleOwnerChunk) {
+        use alloy_signer_local::PrivateKeySigner;
+        let signer = PrivateKeySigner::from_bytes(&B256::repeat_byte(0x11)).expect("signer");
+        let soc = SingleOwnerChunk::new(id, &b"soc-by-owner"[..], &signer).expect("valid soc");
+        (signer.address(), soc)
+    }
+
+    #[tokio::test]
+    async fn retrieve_soc_derives_the_address_and_verifies_the_owner() {
+        let id = B256::repeat_byte(0x22);
+        let (owner, soc) = signed_soc(id);
+        let (tx, mut rx) = mpsc::channel::<ClientCommand>(16);
+        let handle = ClientHandle::new(tx);
+        let task = tokio::spawn(async move { handle.retrieve_soc(peer(4), owner, id).await });
+
+        // The mock peer answers the derived address with the owner's chunk.
+        match rx.recv().await.expect("dispatched") {
+            ClientCommand::RetrieveChunk {
+                peer: asked,
+                address,
+                response,
+                originated,
+                ..
+            } => {
+                assert_eq!(asked, peer(4));
+                assert_eq!(address, soc_address(&owner, &id));
+                assert_eq!(address, *soc.address());
+                assert!(originated);
+                response
+                    .send(Ok(RetrievalResult {
+                        chunk: soc.clone().into(),
+                        stamp: None,
+                        peer: asked,
+                    }))
+                    .expect("receiver alive");
+            }
+            other => panic!("unexpected command: {other:?}"),
+        }
+
+        let got = task.await.unwrap().expect("soc retrieved");
+        assert_eq!(got.id(), id);
+        assert_eq!(got.owner().expect("signature verifies"), owner);
+    }
+
+    #[tokio::test]
+    async fn retrieve_soc_refuses_a_content_chunk() {
+        let (owner, _) = signed_soc(B256::ZERO);
+        let (tx, mut rx) = mpsc::channel::<ClientCommand>(16);
+        let handle = ClientHandle::new(tx);
+        let task =
+            tokio::spawn(async move { handle.retrieve_soc(peer(5), owner, B256::ZERO).await });
+
+        match rx.recv().await.expect("dispatched") {
+            ClientCommand::RetrieveChunk { response, .. } => {
+                response
+                    .send(Ok(RetrievalResult {
+                        chunk: content_chunk(),
+                        stamp: None,
+                        peer: peer(5),
+                    }))
+                    .expect("receiver alive");
+            }
+            other => panic!("unexpected command: {other:?}"),
+        }
+        assert!(matches!(
+            task.await.unwrap(),
+            Err(SocRetrievalError::NotSingleOwner(_))
+        ));
+    }
+
     #[test]
     fn no_reporter_is_a_noop() {
         let (service, _event_tx, _handle) = ClientService::new();
@@ -1458,4 +1890,67 @@ mod tests {
             Some(ClientCommand::SendPseudosettle { .. })
         ));
     }
+
+    #[tokio::test]
+    async fn flooded_command_channel_overloads_instead_of_growing() {
+        use alloy_primitives::U256;
+
+        let (tx, mut rx) = mpsc::channel(2);
+        let handle = ClientHandle::new(tx).with_send_timeout(Duration::from_millis(50));
+
+        // Flood the channel to capacity.
+        for n in 0..2 {
+            handle
+                .send_command(ClientCommand::SendPseudosettle {
+                    peer: peer(n),
+                    amount: U256::from(1u64),
+                })
+                .expect("fits within capacity");
+        }
+
+        // The non-blocking path reports the overload instead of a closed channel.
+        assert!(matches!(
+            handle.send_command(ClientCommand::SendPseudosettle {
+                peer: peer(9),
+                amount: U256::from(1u64),
+            }),
+            Err(ChunkTransferError::Overloaded)
+        ));
+
+        // A request waits for room, then fails without being queued.
+        let result = handle
+            .retrieve_chunk(peer(3), ChunkAddress::new([7; 32]), false)
+            .await;
+        assert!(matches!(result, Err(ChunkTransferError::Overloaded)));
+        assert_eq!(rx.len(), 2, "the channel never grows past its capacity");
+
+        // Room freed within the timeout lets a waiting request through.
+        let waiting = {
+            let handle = handle.clone().with_send_timeout(Duration::from_secs(5));
+            tokio::spawn(async move {
+                handle
+                    .retrieve_chunk(peer(4), ChunkAddress::new([8; 32]), false)
+                    .await
+            })
+        };
+        tokio::task::yield_now().await;
+        assert!(
+            !waiting.is_finished(),
+            "the request is held by backpressure"
+        );
+        rx.recv().await.expect("drain one slot");
+        rx.recv().await.expect("drain another slot");
+        match rx.recv().await {
+            Some(ClientCommand::RetrieveChunk { response, .. }) => {
+                let _ = response.send(Err(ChunkTransferError::NotFound(ChunkAddress::new(
+                    [8; 32],
+                ))));
+            }
+            other => panic!("expected the queued retrieval, got {other:?}"),
+        }
+        assert!(matches!(
+            waiting.await.expect("task joins"),
+            Err(ChunkTransferError::NotFound(_))
+        ));
+    }
 }
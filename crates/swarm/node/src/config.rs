@@ -6,7 +6,7 @@ use std::sync::Arc;
 use eyre::Result;
 use serde::{Deserialize, Serialize};
 use vertex_node_api::NodeProtocolConfig;
-use vertex_swarm_accounting::{BandwidthArgs, DefaultBandwidthConfig};
+use vertex_swarm_accounting::{BandwidthArgs, DefaultBandwidthConfig, MAX_REFRESH_RATE};
 use vertex_swarm_identity::{Identity, IdentityArgs};
 use vertex_swarm_localstore::{LocalStoreArgs, LocalStoreConfig};
 use vertex_swarm_primitives::SwarmNodeType;
@@ -14,7 +14,7 @@ use vertex_swarm_primitives::SwarmNodeType;
 use vertex_swarm_redistribution::{RedistributionArgs, StorageConfig};
 use vertex_swarm_spec::Spec;
 
-use vertex_swarm_api::ConfigError;
+use vertex_swarm_api::{ConfigError, SwarmSpec};
 
 use crate::args::{
     ChainArgs, ChainConfig, NetworkArgs, NetworkConfig, ProtocolArgs, SwapArgs, SwapConfig,
@@ -83,6 +83,124 @@ impl ProtocolConfig {
     pub fn override_node_type(&mut self, node_type: SwarmNodeType) {
         self.node_type = node_type;
     }
+
+    /// Check the settings against each other and report every inconsistency.
+    ///
+    /// Runs before anything is built, so a misconfiguration surfaces as a list
+    /// of named fields rather than a failure part-way through launch. Any
+    /// [`IssueSeverity::Error`] means the node cannot start as configured.
+    /// Settings left unset are judged by the defaults `spec` resolves them to.
+    pub fn validate(&self, spec: &Spec) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+        let node_type = self.node_type;
+
+        if self.identity.ephemeral && node_type.requires_persistent_identity() {
+            issues.push(ConfigIssue::error(
+                "identity.ephemeral",
+                format!(
+                    "a {node_type} needs a stable overlay address; drop --ephemeral and configure a keystore"
+                ),
+            ));
+        }
+
+        if self.network.max_peers == Some(0) {
+            issues.push(ConfigIssue::error(
+                "network.max_peers",
+                "max peers is 0, which denies every connection; set it to at least 1",
+            ));
+        }
+
+        if self.bandwidth.refresh_rate > MAX_REFRESH_RATE {
+            issues.push(ConfigIssue::warning(
+                "bandwidth.refresh_rate",
+                format!(
+                    "refresh rate {} is above the ceiling and is lowered to {MAX_REFRESH_RATE}",
+                    self.bandwidth.refresh_rate
+                ),
+            ));
+        }
+
+        let swap_enabled = self
+            .swap
+            .enable
+            .unwrap_or_else(|| spec.default_swap_enabled(node_type));
+        // Only a build with SWAP acts on the setting, so only there does it
+        // pull in the chain.
+        let swap_needs_chain = cfg!(feature = "swap") && swap_enabled;
+        if node_type.needs_chain(swap_needs_chain) && self.chain.rpc_url.is_none() {
+            issues.push(ConfigIssue::error(
+                "chain.rpc_url",
+                format!("a {node_type} needs an Ethereum chain connection; set --chain.rpc-url"),
+            ));
+        }
+
+        if node_type == SwarmNodeType::Bootnode {
+            if self.swap.enable == Some(true) {
+                issues.push(ConfigIssue::warning(
+                    "swap.enable",
+                    "bootnodes do no accounting, so --swap has no effect",
+                ));
+            }
+        } else if swap_enabled && self.swap.chequebook.is_none() && !self.swap.deploy {
+            issues.push(ConfigIssue::warning(
+                "swap.chequebook",
+                "SWAP is enabled without a chequebook, so this node cannot issue cheques; \
+                 set --swap.chequebook or --swap.deploy",
+            ));
+        }
+
+        issues
+    }
+}
+
+/// How serious a [`ConfigIssue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum IssueSeverity {
+    /// The node starts, but a setting is ignored or a feature is degraded.
+    Warning,
+    /// The node cannot start as configured.
+    Error,
+}
+
+/// One problem found by [`ProtocolConfig::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    /// Config path of the offending setting, e.g. `network.max_peers`.
+    pub field: &'static str,
+    /// Whether the issue blocks startup.
+    pub severity: IssueSeverity,
+    /// What is wrong and how to fix it.
+    pub message: String,
+}
+
+impl ConfigIssue {
+    fn error(field: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            field,
+            severity: IssueSeverity::Error,
+            message: message.into(),
+        }
+    }
+
+    fn warning(field: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            field,
+            severity: IssueSeverity::Warning,
+            message: message.into(),
+        }
+    }
+
+    /// Whether this issue blocks startup.
+    pub fn is_error(&self) -> bool {
+        self.severity == IssueSeverity::Error
+    }
+}
+
+impl std::fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} in {}: {}", self.severity, self.field, self.message)
+    }
 }
 
 impl NodeProtocolConfig for ProtocolConfig {
@@ -101,3 +219,83 @@ impl NodeProtocolConfig for ProtocolConfig {
         self.swap = args.swap.clone();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use vertex_swarm_spec::{SpecBuilder, init_mainnet};
+
+    use super::*;
+
+    fn issue<'a>(issues: &'a [ConfigIssue], field: &str) -> Option<&'a ConfigIssue> {
+        issues.iter().find(|issue| issue.field == field)
+    }
+
+    #[test]
+    fn default_client_is_clean() {
+        assert_eq!(
+            ProtocolConfig::default().validate(&init_mainnet()),
+            Vec::new()
+        );
+    }
+
+    #[test]
+    fn storer_with_ephemeral_identity_is_an_error() {
+        let mut config = ProtocolConfig::default();
+        config.override_node_type(SwarmNodeType::Storer);
+        config.identity.ephemeral = true;
+
+        let issues = config.validate(&init_mainnet());
+        let ephemeral = issue(&issues, "identity.ephemeral").expect("ephemeral reported");
+        assert_eq!(ephemeral.severity, IssueSeverity::Error);
+
+        // A client may run ephemeral.
+        config.override_node_type(SwarmNodeType::Client);
+        assert!(issue(&config.validate(&init_mainnet()), "identity.ephemeral").is_none());
+    }
+
+    #[test]
+    fn swap_without_chequebook_is_a_warning() {
+        let mut config = ProtocolConfig::default();
+        config.swap.enable = Some(true);
+
+        let issues = config.validate(&init_mainnet());
+        let chequebook = issue(&issues, "swap.chequebook").expect("chequebook reported");
+        assert_eq!(chequebook.severity, IssueSeverity::Warning);
+        assert!(!chequebook.is_error());
+
+        // Deploying one on startup settles it.
+        config.swap.deploy = true;
+        assert!(issue(&config.validate(&init_mainnet()), "swap.chequebook").is_none());
+
+        // A storer defaults SWAP on, except on a dev network.
+        let mut config = ProtocolConfig::default();
+        config.override_node_type(SwarmNodeType::Storer);
+        config.chain.rpc_url = Some("http://localhost:8545".into());
+        assert!(issue(&config.validate(&init_mainnet()), "swap.chequebook").is_some());
+        let dev = SpecBuilder::dev().build();
+        assert!(issue(&config.validate(&dev), "swap.chequebook").is_none());
+    }
+
+    #[test]
+    fn zero_max_peers_is_an_error() {
+        let mut config = ProtocolConfig::default();
+        config.network.max_peers = Some(0);
+
+        let issues = config.validate(&init_mainnet());
+        let max_peers = issue(&issues, "network.max_peers").expect("max peers reported");
+        assert_eq!(max_peers.severity, IssueSeverity::Error);
+        assert!(issues.iter().any(ConfigIssue::is_error));
+    }
+
+    #[test]
+    fn refresh_rate_above_the_ceiling_is_a_warning() {
+        let mut config = ProtocolConfig::default();
+        config.bandwidth.refresh_rate = MAX_REFRESH_RATE;
+        assert!(issue(&config.validate(&init_mainnet()), "bandwidth.refresh_rate").is_none());
+
+        config.bandwidth.refresh_rate = MAX_REFRESH_RATE + 1;
+        let issues = config.validate(&init_mainnet());
+        let rate = issue(&issues, "bandwidth.refresh_rate").expect("refresh rate reported");
+        assert_eq!(rate.severity, IssueSeverity::Warning);
+    }
+}
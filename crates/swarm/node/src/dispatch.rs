@@ -5,7 +5,7 @@
 //! bin-route primary, staggered refilling race, sequential push walk, in-flight
 //! cap, adaptive stagger, headroom spill, and over-fetch metrics live in one
 //! place. Each capability is a small trait the engine is generic over: the
-//! native client supplies [`PeerSelector`], [`PeerInflightLimiter`], and
+//! native client supplies [`ScoreSelector`], [`PeerInflightLimiter`], and
 //! [`RetrievalLatency`]; the browser supplies the zero-sized null objects
 //! [`ProximityOnly`] and [`NoLatencyHint`] but the same real
 //! [`PeerInflightLimiter`].
@@ -15,27 +15,31 @@
 //! commit-at-dispatch, only sequential dispatch may pair with an on-verify
 //! commit.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::pin::pin;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
+use futures::future::{self, Either};
 use metrics::{counter, histogram};
 use nectar_primitives::SwarmAddress;
-use tokio::sync::OwnedSemaphorePermit;
+use parking_lot::Mutex;
+use tokio::sync::{OwnedSemaphorePermit, oneshot};
 use tracing::warn;
 use vertex_swarm_api::{
     Bin, ChunkAddress, ChunkRetrievalResult, NeighborhoodDepth, OverlayAddress, PeerReporter,
-    ReportSource, StampedChunk, SwarmError, SwarmResult, SwarmScoringEvent, SwarmTopologyPeers,
-    SwarmTopologyReporting, SwarmTopologyRouting, SwarmTopologyState,
+    ReportSource, RetrievalFailure, StampedChunk, SwarmError, SwarmResult, SwarmScoringEvent,
+    SwarmTopologyPeers, SwarmTopologyReporting, SwarmTopologyRouting, SwarmTopologyState,
 };
 use vertex_swarm_net_pushsync::{DepthVerdict, Receipt};
-use vertex_tasks::time::Duration;
+use vertex_tasks::time::{Duration, Instant};
 
+use crate::breaker::{BreakerProtocol, ProtocolBreaker};
 use crate::retrieval_latency::{RetrievalLatency, adaptive_stagger};
-use crate::selection::SettlementTrigger;
+use crate::selection::{ClosestSelector, PeerSelector, SettlementTrigger};
 use crate::{
-    ChunkTransferError, ClientHandle, PeerInflightLimiter, PeerSelector, RaceFailure,
-    RetrievalResult, race_with_refill,
+    ChunkTransferError, ClientHandle, PeerInflightLimiter, RaceFailure, RetrievalResult,
+    ScoreSelector, race_with_refill,
 };
 
 /// Proximity-ordered pool of closest connected peers the refilling race draws
@@ -162,7 +166,7 @@ impl<T> RetrievalTopology for T where
 
 /// Economic ordering of retrieval and pushsync candidates.
 ///
-/// The native client supplies [`PeerSelector`] (score- and affordability-aware);
+/// The native client supplies [`ScoreSelector`] (score- and affordability-aware);
 /// the browser supplies [`ProximityOnly`], which leaves the proximity order
 /// untouched.
 #[auto_impl::auto_impl(&, Arc)]
@@ -179,9 +183,9 @@ pub trait CandidateOrdering: Send + Sync {
     ) -> Vec<OverlayAddress>;
 }
 
-impl CandidateOrdering for PeerSelector {
+impl CandidateOrdering for ScoreSelector {
     fn order(&self, candidates: Vec<OverlayAddress>, chunk: &ChunkAddress) -> Vec<OverlayAddress> {
-        PeerSelector::order(self, candidates, chunk)
+        ScoreSelector::order(self, candidates, chunk)
     }
 
     fn order_closest_admissible(
@@ -189,7 +193,7 @@ impl CandidateOrdering for PeerSelector {
         candidates: Vec<OverlayAddress>,
         chunk: &ChunkAddress,
     ) -> Vec<OverlayAddress> {
-        PeerSelector::order_closest_admissible(self, candidates, chunk)
+        ScoreSelector::order_closest_admissible(self, candidates, chunk)
     }
 }
 
@@ -326,6 +330,109 @@ impl RaceBounds {
     }
 }
 
+/// How the attempts of one retrieval failed, folded into the most specific
+/// [`RetrievalFailure`] when the retrieval exhausts, and which peers failed
+/// it on the wire, so later selections of the same retrieval skip them. A
+/// local refusal or an unsent request says nothing about the peer, so it
+/// stays selectable (and settleable).
+#[derive(Debug, Default)]
+struct FailureTally {
+    invalid: AtomicUsize,
+    timed_out: AtomicUsize,
+    missed: AtomicUsize,
+    refused: AtomicUsize,
+    unavailable: AtomicUsize,
+    failed: Mutex<HashSet<OverlayAddress>>,
+}
+
+impl FailureTally {
+    fn record<T>(&self, peer: OverlayAddress, result: &Result<T, ChunkTransferError>) {
+        let (counter, on_wire) = match result {
+            Ok(_) => return,
+            Err(ChunkTransferError::InvalidChunk(_)) => (&self.invalid, true),
+            Err(ChunkTransferError::TimedOut) => (&self.timed_out, true),
+            Err(ChunkTransferError::Refused) => (&self.refused, false),
+            Err(
+                ChunkTransferError::Overloaded
+                | ChunkTransferError::Cancelled
+                | ChunkTransferError::ChannelClosed,
+            ) => (&self.unavailable, false),
+            Err(ChunkTransferError::NotConnected) => (&self.missed, false),
+            Err(_) => (&self.missed, true),
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        if on_wire {
+            self.failed.lock().insert(peer);
+        }
+    }
+
+    /// The peers that failed this retrieval on the wire so far.
+    fn failed_peers(&self) -> HashSet<OverlayAddress> {
+        self.failed.lock().clone()
+    }
+
+    /// An invalid delivery outranks a miss, a miss outranks a timeout, and a
+    /// timeout outranks a local refusal, which outranks a request the node
+    /// could not send. With no failed attempt the race outcome decides:
+    /// nothing to ask is
+    /// [`RetrievalFailure::NoPeers`], a deadline [`RetrievalFailure::Timeout`].
+    fn reason<E>(&self, outcome: &RaceFailure<E>) -> RetrievalFailure {
+        if self.invalid.load(Ordering::Relaxed) > 0 {
+            RetrievalFailure::InvalidChunk
+        } else if self.missed.load(Ordering::Relaxed) > 0 {
+            RetrievalFailure::NotFound
+        } else if self.timed_out.load(Ordering::Relaxed) > 0 {
+            RetrievalFailure::Timeout
+        } else if self.refused.load(Ordering::Relaxed) > 0 {
+            RetrievalFailure::Refused
+        } else if self.unavailable.load(Ordering::Relaxed) > 0 {
+            RetrievalFailure::Unavailable
+        } else {
+            match outcome {
+                RaceFailure::NoCandidates => RetrievalFailure::NoPeers,
+                RaceFailure::TimedOut => RetrievalFailure::Timeout,
+                RaceFailure::AllFailed(_) => RetrievalFailure::NotFound,
+            }
+        }
+    }
+}
+
+/// Per-call retrieval policy, for callers that trade coverage for latency.
+///
+/// The default is the full dispatch policy: the bin route, then the staggered
+/// close and spill races, each bounded by its own deadline. An interactive
+/// reader that would rather fail fast than wait out every retry sets
+/// `single_attempt` and a tight `max_latency`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetrievalOptions {
+    /// Wall-clock bound on the whole retrieval. Running out of it surfaces
+    /// [`SwarmError::RetrievalTimeout`]; `Duration::MAX` leaves each phase to
+    /// its own deadline.
+    pub max_latency: Duration,
+    /// Dispatch one attempt to the best candidate and give up on its failure
+    /// instead of refilling from the next peer.
+    pub single_attempt: bool,
+}
+
+impl RetrievalOptions {
+    /// A single best-peer attempt bounded by `max_latency`.
+    pub const fn fast(max_latency: Duration) -> Self {
+        Self {
+            max_latency,
+            single_attempt: true,
+        }
+    }
+}
+
+impl Default for RetrievalOptions {
+    fn default() -> Self {
+        Self {
+            max_latency: Duration::MAX,
+            single_attempt: false,
+        }
+    }
+}
+
 /// Shared dispatch engine for origin chunk retrieval.
 ///
 /// Generic over its three capabilities so a native client wires the concrete
@@ -333,7 +440,16 @@ impl RaceBounds {
 /// delegate `retrieve_chunk` to [`Self::retrieve`]. Every retrieval terminal
 /// (no candidates, all attempts failed, deadline) maps to
 /// [`SwarmError::RetrievalExhausted`]: forwarding retrieval has no authoritative
-/// negative, so the engine never adjudicates absence.
+/// negative, so the engine never adjudicates absence. Its
+/// [`RetrievalFailure`] reason says how the attempts failed. Only a caller-set
+/// [`RetrievalOptions::max_latency`] running out surfaces as
+/// [`SwarmError::RetrievalTimeout`] instead.
+///
+/// Concurrent full retrievals of one address are coalesced across clones of
+/// the engine: the first caller runs the race and the rest wait on its
+/// result, so the network sees one race and the origin price is booked once.
+/// The race's own legs go through the client handle per peer and are never
+/// coalesced with each other.
 #[derive(Clone)]
 pub struct DispatchEngine<O: CandidateOrdering, G: InflightLimit, L: LatencyHint> {
     client_handle: ClientHandle,
@@ -350,6 +466,64 @@ pub struct DispatchEngine<O: CandidateOrdering, G: InflightLimit, L: LatencyHint
     /// in-flight dedup, so concurrent gated retrievals collapse to one settle per
     /// peer.
     settlement: Arc<dyn SettlementTrigger>,
+    /// Draws the fallback's close and spill slices from the topology;
+    /// [`ClosestSelector`] unless configured otherwise.
+    selector: Arc<dyn PeerSelector>,
+    /// Skips peers whose breaker for the protocol is open; `None` selects
+    /// every candidate.
+    breaker: Option<Arc<ProtocolBreaker>>,
+    /// Full retrievals in flight, shared across clones.
+    coalesced: Arc<CoalescedRetrievals>,
+}
+
+/// What a waiter receives from the leader's race: the chunk, or the reason it
+/// was exhausted.
+type CoalescedOutcome = Result<ChunkRetrievalResult, RetrievalFailure>;
+
+/// Full retrievals in flight by address, each with the callers waiting on the
+/// leader's race.
+type CoalescedRetrievals = Mutex<HashMap<ChunkAddress, Vec<oneshot::Sender<CoalescedOutcome>>>>;
+
+/// The leading caller's claim on an in-flight retrieval.
+///
+/// Dropping the claim unfinished (the leader was cancelled) withdraws the
+/// entry and drops the waiters' senders; each waiter then retries, and the
+/// first to get there leads a fresh race the others join.
+struct RetrievalLead<'a> {
+    coalesced: &'a CoalescedRetrievals,
+    address: ChunkAddress,
+    finished: bool,
+}
+
+impl RetrievalLead<'_> {
+    /// Withdraw the entry and hand every waiter the leader's outcome.
+    ///
+    /// An error other than exhaustion carries nothing a waiter can share, so
+    /// those waiters are released to retry as above.
+    fn finish(mut self, result: &SwarmResult<ChunkRetrievalResult>) {
+        self.finished = true;
+        let waiters = self
+            .coalesced
+            .lock()
+            .remove(&self.address)
+            .unwrap_or_default();
+        let outcome = match result {
+            Ok(served) => Ok(served.clone()),
+            Err(SwarmError::RetrievalExhausted { reason, .. }) => Err(*reason),
+            Err(_) => return,
+        };
+        for waiter in waiters {
+            let _ = waiter.send(outcome.clone());
+        }
+    }
+}
+
+impl Drop for RetrievalLead<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.coalesced.lock().remove(&self.address);
+        }
+    }
 }
 
 impl<O, G, L> DispatchEngine<O, G, L>
@@ -376,6 +550,34 @@ where
             inflight,
             latency,
             settlement,
+            selector: Arc::new(ClosestSelector),
+            breaker: None,
+            coalesced: Arc::default(),
+        }
+    }
+
+    /// Replace the default proximity [`PeerSelector`] for retrieval.
+    pub fn with_selector(mut self, selector: Arc<dyn PeerSelector>) -> Self {
+        self.selector = selector;
+        self
+    }
+
+    /// Skip peers whose [`ProtocolBreaker`] is open for the protocol being
+    /// dispatched.
+    pub fn with_breaker(mut self, breaker: Arc<ProtocolBreaker>) -> Self {
+        self.breaker = Some(breaker);
+        self
+    }
+
+    /// Drop the `candidates` whose `protocol` breaker is open.
+    fn admitted(
+        &self,
+        candidates: Vec<OverlayAddress>,
+        protocol: BreakerProtocol,
+    ) -> Vec<OverlayAddress> {
+        match &self.breaker {
+            Some(breaker) => breaker.retain_allowed(candidates, protocol),
+            None => candidates,
         }
     }
 
@@ -395,7 +597,10 @@ where
     /// booking at dispatch through the origin credit gate.
     pub async fn push(&self, chunk: StampedChunk) -> SwarmResult<Receipt> {
         let address = *chunk.address();
-        let closest = self.topology.closest_to(&address, PUSH_CANDIDATE_COUNT);
+        let closest = self.admitted(
+            self.topology.closest_to(&address, PUSH_CANDIDATE_COUNT),
+            BreakerProtocol::Pushsync,
+        );
         // Rank by band and score, hard-skipping a refused peer; an all-gated set
         // yields an empty result and the generic no-storer outcome below.
         let closest = self.ordering.order(closest, &address);
@@ -498,17 +703,23 @@ where
     /// admissible one there forwards it just the same, without parking the
     /// pipeline slot. If even the wider slice is fully gated the result is empty
     /// and the caller falls through to its terminal failure. With
-    /// [`ProximityOnly`] the proximity order is returned unchanged.
+    /// [`ProximityOnly`] the proximity order is returned unchanged. The wider
+    /// slice skips `exclude`, the peers that already failed this retrieval.
     fn order_with_spill(
         &self,
         candidates: Vec<OverlayAddress>,
         chunk: &ChunkAddress,
+        exclude: &HashSet<OverlayAddress>,
     ) -> Vec<OverlayAddress> {
         let ordered = self.ordering.order(candidates, chunk);
         if !ordered.is_empty() {
             return ordered;
         }
-        let wide = self.topology.closest_to(chunk, RETRIEVE_SPILL_WIDTH);
+        let wide = self.admitted(
+            self.selector
+                .select_excluding(chunk, &*self.topology, RETRIEVE_SPILL_WIDTH, exclude),
+            BreakerProtocol::Retrieval,
+        );
         self.ordering.order_closest_admissible(wide, chunk)
     }
 
@@ -527,6 +738,7 @@ where
         bounds: RaceBounds,
         enforce_cap: bool,
         attempts: &AtomicUsize,
+        failures: &FailureTally,
     ) -> Result<RetrievalResult, RaceFailure<ChunkTransferError>> {
         race_with_refill(
             candidates,
@@ -550,22 +762,212 @@ where
                     .retrieve_chunk(peer_overlay, chunk_address, true);
                 Some(async move {
                     let _permit = permit;
-                    request.await
+                    let result = request.await;
+                    failures.record(peer_overlay, &result);
+                    result
                 })
             },
         )
         .await
     }
 
+    /// The bin-route candidates for `chunk_address`: the forwarding bin's
+    /// admitted peers, spilling to the adjacent bins, ranked by the accounting
+    /// band and trimmed to those with in-flight headroom. The flag is whether
+    /// the in-flight cap should be enforced at dispatch.
+    fn bin_route(
+        &self,
+        chunk_address: &SwarmAddress,
+        address: &ChunkAddress,
+    ) -> (Vec<OverlayAddress>, bool) {
+        let local = self.topology.overlay_address();
+        let max_bin = self.max_bin.get();
+        let bin_candidates =
+            bin_routed_order(chunk_address, &local, max_bin, RETRIEVE_WIDTH, |bin| {
+                self.topology.connected_peers_in_bin(bin)
+            });
+        let bin_candidates = self.admitted(bin_candidates, BreakerProtocol::Retrieval);
+        let bin_candidates = self.ordering.order(bin_candidates, address);
+        // Availability: drop peers at their in-flight cap so the route leads with
+        // an in-headroom peer. The cap is the non-economic muxer guard, composed
+        // after the accounting band, never merged with it.
+        self.inflight.available(bin_candidates)
+    }
+
     /// Retrieve a chunk by the full dispatch policy.
     ///
     /// Runs the bin-route primary (single-flight, in-bin peers first), then the
     /// staggered bounded-refill fallback. Every retrieval terminal maps to
-    /// [`SwarmError::RetrievalExhausted`]; the attempt count and last error stay
-    /// in the metrics and debug log, never the error variant.
+    /// [`SwarmError::RetrievalExhausted`] with the most specific
+    /// [`RetrievalFailure`] across the attempts; the attempt count stays in the
+    /// metrics.
     pub async fn retrieve(&self, address: &ChunkAddress) -> SwarmResult<ChunkRetrievalResult> {
+        self.retrieve_with(address, RetrievalOptions::default())
+            .await
+    }
+
+    /// Retrieve a chunk under caller-set [`RetrievalOptions`].
+    ///
+    /// With `single_attempt` the engine dispatches one attempt to the best
+    /// candidate (the bin route's lead, else the closest admitted peer) and
+    /// returns on its outcome without retrying; otherwise the full race is
+    /// shared with any concurrent retrieval of the same address. Either way,
+    /// running out of `max_latency` drops this caller's attempts (or its wait
+    /// on a shared race) and surfaces [`SwarmError::RetrievalTimeout`].
+    pub async fn retrieve_with(
+        &self,
+        address: &ChunkAddress,
+        options: RetrievalOptions,
+    ) -> SwarmResult<ChunkRetrievalResult> {
+        if options.single_attempt {
+            // One attempt never outlives the full race's own deadline.
+            return self
+                .retrieve_once(address, options.max_latency.min(RETRIEVE_DEADLINE))
+                .await;
+        }
+        let full = self.retrieve_coalesced(address);
+        if options.max_latency == Duration::MAX {
+            return full.await;
+        }
+        // `futures_timer::Delay` for the same wasm `Send` reason as the settle
+        // drive backoff. Losing the select drops the race and, with it, every
+        // in-flight attempt and the permit it holds.
+        match future::select(pin!(full), futures_timer::Delay::new(options.max_latency)).await {
+            Either::Left((outcome, _)) => outcome,
+            Either::Right(_) => {
+                counter!("swarm.client.retrieval_total", "outcome" => "timed_out", "path" => "deadline")
+                    .increment(1);
+                Err(SwarmError::RetrievalTimeout {
+                    address: *address,
+                    deadline: options.max_latency,
+                })
+            }
+        }
+    }
+
+    /// One attempt at the best candidate, bounded by `deadline`.
+    ///
+    /// A peer that filled its in-flight slot is skipped without spending the
+    /// attempt, so the one attempt lands on the best peer with headroom.
+    async fn retrieve_once(
+        &self,
+        address: &ChunkAddress,
+        deadline: Duration,
+    ) -> SwarmResult<ChunkRetrievalResult> {
         let chunk_address = SwarmAddress::new(address.0.into());
         let attempts = AtomicUsize::new(0);
+        let failures = FailureTally::default();
+        let started = Instant::now();
+
+        let (mut candidates, mut enforce_cap) = self.bin_route(&chunk_address, address);
+        if candidates.is_empty() {
+            let closest = self.admitted(
+                self.selector
+                    .select(&chunk_address, &*self.topology, RETRIEVE_WIDTH),
+                BreakerProtocol::Retrieval,
+            );
+            (candidates, enforce_cap) =
+                self.inflight
+                    .available(self.order_with_spill(closest, address, &HashSet::new()));
+        }
+
+        let outcome = self
+            .race_attempts(
+                candidates,
+                chunk_address,
+                RaceBounds::sequential(1, deadline),
+                enforce_cap,
+                &attempts,
+                &failures,
+            )
+            .await;
+
+        let dispatched = attempts.load(Ordering::Relaxed);
+        histogram!("swarm.client.retrieval_attempts").record(dispatched as f64);
+        let outcome_label = match &outcome {
+            Ok(_) => "hit",
+            Err(RaceFailure::NoCandidates) => "no_peers",
+            Err(RaceFailure::AllFailed(_)) => "exhausted",
+            Err(RaceFailure::TimedOut) => "timed_out",
+        };
+        counter!("swarm.client.retrieval_total", "outcome" => outcome_label, "path" => "single_attempt")
+            .increment(1);
+
+        match outcome {
+            Ok(result) => Ok(ChunkRetrievalResult {
+                chunk: result.chunk,
+                stamp: result.stamp,
+                served_by: result.peer,
+                retries: 0,
+                elapsed: started.elapsed(),
+            }),
+            Err(RaceFailure::TimedOut) => Err(SwarmError::RetrievalTimeout {
+                address: *address,
+                deadline,
+            }),
+            Err(failure) => Err(SwarmError::RetrievalExhausted {
+                address: *address,
+                reason: failures.reason(&failure),
+            }),
+        }
+    }
+
+    /// Join the full retrieval of `address` already in flight, or lead one.
+    ///
+    /// A joined caller books nothing. If the leader is dropped before it
+    /// finishes, a waiter takes over and runs the race itself.
+    async fn retrieve_coalesced(
+        &self,
+        address: &ChunkAddress,
+    ) -> SwarmResult<ChunkRetrievalResult> {
+        loop {
+            let waiter = {
+                let mut coalesced = self.coalesced.lock();
+                match coalesced.get_mut(address) {
+                    Some(waiters) => {
+                        let (tx, rx) = oneshot::channel();
+                        waiters.push(tx);
+                        Some(rx)
+                    }
+                    None => {
+                        coalesced.insert(*address, Vec::new());
+                        None
+                    }
+                }
+            };
+            let Some(rx) = waiter else {
+                break;
+            };
+            counter!("swarm.client.retrievals_coalesced").increment(1);
+            match rx.await {
+                Ok(Ok(served)) => return Ok(served),
+                Ok(Err(reason)) => {
+                    return Err(SwarmError::RetrievalExhausted {
+                        address: *address,
+                        reason,
+                    });
+                }
+                // The leader went away without an outcome to share.
+                Err(_) => continue,
+            }
+        }
+
+        let lead = RetrievalLead {
+            coalesced: &self.coalesced,
+            address: *address,
+            finished: false,
+        };
+        let result = self.retrieve_full(address).await;
+        lead.finish(&result);
+        result
+    }
+
+    /// The full dispatch policy behind [`Self::retrieve`].
+    async fn retrieve_full(&self, address: &ChunkAddress) -> SwarmResult<ChunkRetrievalResult> {
+        let chunk_address = SwarmAddress::new(address.0.into());
+        let attempts = AtomicUsize::new(0);
+        let failures = FailureTally::default();
+        let started = Instant::now();
 
         // PRIMARY: bin-bucket proximity route. Route the chunk to its Kademlia
         // forwarding bin b = PO(local, chunk) and dispatch the best in-headroom
@@ -577,17 +979,7 @@ where
         // one metered attempt and over-fetches nothing. The accounting band ranks
         // the route here; a gated route falls through to the staggered fallback,
         // which spills to a wider headroom slice.
-        let local = self.topology.overlay_address();
-        let max_bin = self.max_bin.get();
-        let bin_candidates =
-            bin_routed_order(&chunk_address, &local, max_bin, RETRIEVE_WIDTH, |bin| {
-                self.topology.connected_peers_in_bin(bin)
-            });
-        let bin_candidates = self.ordering.order(bin_candidates, address);
-        // Availability: drop peers at their in-flight cap so the route leads with
-        // an in-headroom peer. The cap is the non-economic muxer guard, composed
-        // after the accounting band, never merged with it.
-        let (bin_candidates, enforce_cap) = self.inflight.available(bin_candidates);
+        let (bin_candidates, enforce_cap) = self.bin_route(&chunk_address, address);
 
         if !bin_candidates.is_empty() {
             let primary = self
@@ -599,6 +991,7 @@ where
                     RaceBounds::sequential(PRIMARY_ROUTE_BUDGET, PRIMARY_ROUTE_DEADLINE),
                     enforce_cap,
                     &attempts,
+                    &failures,
                 )
                 .await;
             if let Ok(result) = primary {
@@ -611,6 +1004,8 @@ where
                     chunk: result.chunk,
                     stamp: result.stamp,
                     served_by: result.peer,
+                    retries: dispatched.saturating_sub(1),
+                    elapsed: started.elapsed(),
                 });
             }
         }
@@ -634,13 +1029,27 @@ where
         // collapses the concurrent gated retrievals of a bulk download to one
         // settle per peer, so this drives the download at the peers' forgiveness
         // rate rather than spamming settles.
+        //
+        // Every selection skips the peers that already failed this retrieval on
+        // the wire (on the bin route or an earlier race), so each retry reaches
+        // a genuinely different peer. A peer the local gate refused is not
+        // excluded: settling exists to make it usable again.
         let mut settle_drives = 0usize;
         let outcome = loop {
-            let closest_peers = self.topology.closest_to(&chunk_address, RETRIEVE_WIDTH);
+            let failed = failures.failed_peers();
+            let closest_peers = self.admitted(
+                self.selector.select_excluding(
+                    &chunk_address,
+                    &*self.topology,
+                    RETRIEVE_WIDTH,
+                    &failed,
+                ),
+                BreakerProtocol::Retrieval,
+            );
             // Spill to a wider in-headroom slice when every close peer is gated, so
             // a fully gated close set routes around its spent peers rather than
             // blocking.
-            let closest_peers = self.order_with_spill(closest_peers, address);
+            let closest_peers = self.order_with_spill(closest_peers, address, &failed);
             let (close_candidates, _enforce_cap) = self.inflight.available(closest_peers);
 
             // Farther-ring spill: when the whole close set fails on the wire (not
@@ -650,9 +1059,15 @@ where
             // gated close set already spilled to this slice above, so its
             // already-raced set covers the slice and the difference is empty.
             let raced: HashSet<OverlayAddress> = close_candidates.iter().copied().collect();
-            let wide = self
-                .topology
-                .closest_to(&chunk_address, RETRIEVE_SPILL_WIDTH);
+            let wide = self.admitted(
+                self.selector.select_excluding(
+                    &chunk_address,
+                    &*self.topology,
+                    RETRIEVE_SPILL_WIDTH,
+                    &failed,
+                ),
+                BreakerProtocol::Retrieval,
+            );
             let wide = self.ordering.order_closest_admissible(wide, address);
             let spill_ring: Vec<OverlayAddress> = wide
                 .into_iter()
@@ -664,11 +1079,15 @@ where
                 // Either fully gated or genuinely peerless. Settle the closest raw
                 // peers so their debt drains below the disconnect line, back off for
                 // the settles to land, and re-select. A peerless node (no closest
-                // peers) or an exhausted drive budget falls through to the terminal
+                // peers, or only ones that failed this retrieval on the wire) or an
+                // exhausted drive budget falls through to the terminal
                 // no-candidates failure the consumer re-streams on.
-                let gated = self
+                let gated: Vec<OverlayAddress> = self
                     .topology
-                    .closest_to(&chunk_address, RETRIEVE_SETTLE_DRIVE_WIDTH);
+                    .closest_to(&chunk_address, RETRIEVE_SETTLE_DRIVE_WIDTH)
+                    .into_iter()
+                    .filter(|peer| !failed.contains(peer))
+                    .collect();
                 if gated.is_empty() || settle_drives >= RETRIEVE_SETTLE_DRIVES {
                     break Err(RaceFailure::NoCandidates);
                 }
@@ -716,7 +1135,9 @@ where
                     .retrieve_chunk(peer_overlay, chunk_address, true);
                 Some(async move {
                     let _permit = permit;
-                    request.await
+                    let result = request.await;
+                    failures.record(peer_overlay, &result);
+                    result
                 })
             };
 
@@ -761,12 +1182,17 @@ where
                 chunk: result.chunk,
                 stamp: result.stamp,
                 served_by: result.peer,
+                retries: dispatched.saturating_sub(1),
+                elapsed: started.elapsed(),
             }),
             // Forwarding retrieval has no authoritative negative, so every
             // terminal is the same honest outcome: the reachable peers were
-            // exhausted without serving the chunk. The which-attempt and
-            // last-error detail lives in the metrics and debug log above.
-            Err(_) => Err(SwarmError::RetrievalExhausted { address: *address }),
+            // exhausted without serving the chunk. The reason says how the
+            // attempts failed; the attempt count lives in the metrics above.
+            Err(failure) => Err(SwarmError::RetrievalExhausted {
+                address: *address,
+                reason: failures.reason(&failure),
+            }),
         }
     }
 }
@@ -1427,7 +1853,6 @@ mod tests {
     /// because the engine is generic over [`RetrievalTopology`], so a mock stands
     /// in for the real handle.
     mod settle_drive {
-        use std::num::NonZeroUsize;
         use std::sync::{Arc, Mutex};
 
         use vertex_swarm_api::{Bin, ChunkAddress, OverlayAddress, SwarmError};
@@ -1438,12 +1863,8 @@ mod tests {
             RetrievalTopology,
         };
         use crate::ClientHandle;
-        use crate::inflight::PeerInflightLimiter;
         use crate::selection::SettlementTrigger;
-
-        fn overlay(byte: u8) -> OverlayAddress {
-            OverlayAddress::from([byte; 32])
-        }
+        use crate::test_support::{inflight, overlay};
 
         /// Ordering that gates every candidate: the fully-refused band.
         #[derive(Clone)]
@@ -1491,7 +1912,7 @@ mod tests {
                 topology,
                 Bin::MAX,
                 GateAll,
-                PeerInflightLimiter::new(NonZeroUsize::new(4).unwrap()),
+                inflight(),
                 NoLatencyHint,
                 Arc::new(settle.clone()),
             );
@@ -1510,4 +1931,454 @@ mod tests {
             );
         }
     }
+
+    /// A configured [`PeerSelector`](crate::PeerSelector) decides which peers
+    /// the fallback race contacts and in what order: the client service sees
+    /// retrieval commands in exactly the selector's order, and the result
+    /// reports which of them served.
+    mod peer_selector {
+        use std::sync::Arc;
+
+        use vertex_swarm_api::{ChunkAddress, OverlayAddress, SwarmTopologyRouting};
+        use vertex_swarm_test_utils::MockTopology;
+
+        use super::super::RetrievalTopology;
+        use nectar_primitives::ContentChunk;
+
+        use crate::selection::PeerSelector;
+        use crate::test_support::{engine, overlay};
+        use crate::{ClientCommand, RetrievalResult};
+
+        /// Selector that asks the farthest of the closest peers first.
+        struct FarthestFirst;
+        impl PeerSelector for FarthestFirst {
+            fn select(
+                &self,
+                address: &ChunkAddress,
+                topology: &dyn SwarmTopologyRouting,
+                count: usize,
+            ) -> Vec<OverlayAddress> {
+                let mut peers = topology.closest_to(address, count);
+                peers.reverse();
+                peers
+            }
+        }
+
+        #[tokio::test]
+        async fn retrieval_follows_the_configured_selector_order() {
+            // The mock's bin route is empty, so every attempt comes from the
+            // selector-driven fallback.
+            let topology: Arc<dyn RetrievalTopology> =
+                Arc::new(MockTopology::new(3, 3, 0).with_closest(vec![
+                    overlay(1),
+                    overlay(2),
+                    overlay(3),
+                ]));
+            let (engine, mut rx) = engine(topology);
+            let engine = engine.with_selector(Arc::new(FarthestFirst));
+
+            let retrieval =
+                tokio::spawn(async move { engine.retrieve(&ChunkAddress::from([0x42; 32])).await });
+
+            // Dropping each response fails that attempt, so the race refills
+            // from the next peer in the selector's order.
+            let mut contacted = Vec::new();
+            for _ in 0..3 {
+                match rx.recv().await.expect("dispatched") {
+                    ClientCommand::RetrieveChunk { peer, .. } => contacted.push(peer),
+                    other => panic!("unexpected command: {other:?}"),
+                }
+            }
+            assert_eq!(contacted, vec![overlay(3), overlay(2), overlay(1)]);
+            retrieval.abort();
+        }
+
+        #[tokio::test]
+        async fn a_retried_retrieval_reports_who_served_it_and_after_how_many_retries() {
+            let topology: Arc<dyn RetrievalTopology> =
+                Arc::new(MockTopology::new(2, 2, 0).with_closest(vec![overlay(1), overlay(2)]));
+            let (engine, mut rx) = engine(topology);
+
+            let retrieval =
+                tokio::spawn(async move { engine.retrieve(&ChunkAddress::from([0x42; 32])).await });
+
+            // The first peer fails by dropping its response; the second serves.
+            match rx.recv().await.expect("first attempt") {
+                ClientCommand::RetrieveChunk { peer, .. } => assert_eq!(peer, overlay(1)),
+                other => panic!("unexpected command: {other:?}"),
+            }
+            match rx.recv().await.expect("retry") {
+                ClientCommand::RetrieveChunk { peer, response, .. } => {
+                    assert_eq!(peer, overlay(2));
+                    let chunk = ContentChunk::new(&b"provenance"[..]).expect("valid content chunk");
+                    response
+                        .send(Ok(RetrievalResult {
+                            chunk: chunk.into(),
+                            stamp: None,
+                            peer,
+                        }))
+                        .expect("receiver alive");
+                }
+                other => panic!("unexpected command: {other:?}"),
+            }
+
+            let result = retrieval.await.unwrap().expect("served on the retry");
+            assert_eq!(result.served_by, overlay(2));
+            assert_eq!(result.retries, 1);
+        }
+    }
+
+    /// A single-attempt retrieval dispatches to the best peer once and, when
+    /// that peer never answers, fails on its own tight deadline instead of
+    /// refilling from the next candidate.
+    mod retrieval_options {
+        use std::sync::Arc;
+        use std::time::{Duration, Instant};
+
+        use vertex_swarm_api::{ChunkAddress, SwarmError};
+        use vertex_swarm_test_utils::MockTopology;
+
+        use super::super::{RetrievalOptions, RetrievalTopology};
+        use crate::ClientCommand;
+        use crate::test_support::{engine, overlay};
+
+        #[tokio::test]
+        async fn single_attempt_gives_up_on_an_unresponsive_peer_without_retrying() {
+            let topology: Arc<dyn RetrievalTopology> =
+                Arc::new(MockTopology::new(3, 3, 0).with_closest(vec![
+                    overlay(1),
+                    overlay(2),
+                    overlay(3),
+                ]));
+            let (engine, mut rx) = engine(topology);
+
+            let deadline = Duration::from_millis(100);
+            let started = Instant::now();
+            let retrieval = tokio::spawn(async move {
+                engine
+                    .retrieve_with(
+                        &ChunkAddress::from([0x42; 32]),
+                        RetrievalOptions::fast(deadline),
+                    )
+                    .await
+            });
+
+            // The best peer takes the request and never answers; holding the
+            // response sender keeps the attempt pending rather than failed.
+            let _unanswered = match rx.recv().await.expect("dispatched") {
+                ClientCommand::RetrieveChunk { peer, response, .. } => {
+                    assert_eq!(peer, overlay(1));
+                    response
+                }
+                other => panic!("unexpected command: {other:?}"),
+            };
+
+            let outcome = tokio::time::timeout(Duration::from_secs(5), retrieval)
+                .await
+                .expect("single attempt finishes on its own deadline")
+                .unwrap();
+            assert!(
+                matches!(outcome, Err(SwarmError::RetrievalTimeout { deadline: d, .. }) if d == deadline),
+                "unexpected outcome: {outcome:?}"
+            );
+            assert!(
+                started.elapsed() < Duration::from_secs(2),
+                "failed fast, well inside the full race deadline"
+            );
+            assert!(rx.try_recv().is_err(), "no retry was dispatched");
+        }
+    }
+
+    /// An exhausted retrieval names how its attempts failed.
+    mod failure_reason {
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        use vertex_swarm_api::{
+            ChunkAddress, ChunkRetrievalResult, OverlayAddress, RetrievalFailure, SwarmError,
+            SwarmResult,
+        };
+        use vertex_swarm_test_utils::MockTopology;
+
+        use super::super::RetrievalTopology;
+        use crate::test_support::{engine, overlay};
+        use crate::{ChunkTransferError, ClientCommand};
+
+        /// Retrieve from `peers`, answering every attempt with `answer`.
+        async fn exhaust(
+            peers: Vec<OverlayAddress>,
+            answer: fn(ChunkAddress) -> ChunkTransferError,
+        ) -> SwarmResult<ChunkRetrievalResult> {
+            let topology: Arc<dyn RetrievalTopology> =
+                Arc::new(MockTopology::new(peers.len(), peers.len(), 0).with_closest(peers));
+            let (engine, mut rx) = engine(topology);
+            tokio::spawn(async move {
+                while let Some(command) = rx.recv().await {
+                    if let ClientCommand::RetrieveChunk {
+                        address, response, ..
+                    } = command
+                    {
+                        let _ = response.send(Err(answer(address)));
+                    }
+                }
+            });
+            tokio::time::timeout(
+                Duration::from_secs(30),
+                engine.retrieve(&ChunkAddress::from([0x42; 32])),
+            )
+            .await
+            .expect("retrieval terminates")
+        }
+
+        fn reason(outcome: SwarmResult<ChunkRetrievalResult>) -> RetrievalFailure {
+            match outcome {
+                Err(SwarmError::RetrievalExhausted { reason, .. }) => reason,
+                other => panic!("unexpected outcome: {other:?}"),
+            }
+        }
+
+        #[tokio::test]
+        async fn an_empty_topology_has_no_peers() {
+            let outcome = exhaust(Vec::new(), ChunkTransferError::NotFound).await;
+            assert_eq!(reason(outcome), RetrievalFailure::NoPeers);
+        }
+
+        #[tokio::test]
+        async fn peers_that_miss_report_not_found() {
+            let outcome = exhaust(vec![overlay(1), overlay(2)], ChunkTransferError::NotFound).await;
+            assert_eq!(reason(outcome), RetrievalFailure::NotFound);
+        }
+
+        #[tokio::test]
+        async fn peers_that_time_out_report_timeout() {
+            let outcome = exhaust(vec![overlay(1), overlay(2)], |_| {
+                ChunkTransferError::TimedOut
+            })
+            .await;
+            assert_eq!(reason(outcome), RetrievalFailure::Timeout);
+        }
+
+        #[tokio::test]
+        async fn a_tampered_delivery_reports_invalid_chunk() {
+            let outcome = exhaust(vec![overlay(1), overlay(2)], |_| {
+                ChunkTransferError::InvalidChunk("address mismatch".to_string())
+            })
+            .await;
+            assert_eq!(reason(outcome), RetrievalFailure::InvalidChunk);
+        }
+
+        #[tokio::test]
+        async fn a_local_refusal_is_not_reported_as_a_miss() {
+            let outcome = exhaust(vec![overlay(1), overlay(2)], |_| {
+                ChunkTransferError::Refused
+            })
+            .await;
+            assert_eq!(reason(outcome), RetrievalFailure::Refused);
+        }
+
+        #[tokio::test]
+        async fn an_overloaded_node_reports_unavailable() {
+            let outcome = exhaust(vec![overlay(1), overlay(2)], |_| {
+                ChunkTransferError::Overloaded
+            })
+            .await;
+            assert_eq!(reason(outcome), RetrievalFailure::Unavailable);
+        }
+    }
+
+    /// A peer that failed a retrieval is not asked again by the same
+    /// retrieval: the fallback race skips the peers the bin route already
+    /// tried.
+    mod exclusion {
+        use std::collections::HashSet;
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        use vertex_swarm_api::{ChunkAddress, RetrievalFailure, SwarmError};
+        use vertex_swarm_test_utils::MockTopology;
+
+        use super::super::RetrievalTopology;
+        use crate::test_support::{engine, overlay};
+        use crate::{ChunkTransferError, ClientCommand};
+
+        #[tokio::test]
+        async fn the_fallback_skips_peers_that_already_failed() {
+            // The two closest peers are also the bin route, so both fail there
+            // first; the fallback must move on to the third.
+            let topology: Arc<dyn RetrievalTopology> = Arc::new(
+                MockTopology::new(3, 3, 0)
+                    .with_connected_in_bins(vec![overlay(1), overlay(2)])
+                    .with_closest(vec![overlay(1), overlay(2), overlay(3)]),
+            );
+            let (engine, mut rx) = engine(topology);
+            let retrieval =
+                tokio::spawn(async move { engine.retrieve(&ChunkAddress::from([0x42; 32])).await });
+
+            let mut contacted = Vec::new();
+            for _ in 0..3 {
+                match rx.recv().await.expect("dispatched") {
+                    ClientCommand::RetrieveChunk {
+                        peer,
+                        address,
+                        response,
+                        ..
+                    } => {
+                        contacted.push(peer);
+                        let _ = response.send(Err(ChunkTransferError::NotFound(address)));
+                    }
+                    other => panic!("unexpected command: {other:?}"),
+                }
+            }
+            let bin_route: HashSet<_> = contacted[..2].iter().copied().collect();
+            assert_eq!(bin_route, HashSet::from([overlay(1), overlay(2)]));
+            assert_eq!(contacted[2], overlay(3), "a distinct third peer is tried");
+
+            let outcome = tokio::time::timeout(Duration::from_secs(30), retrieval)
+                .await
+                .expect("retrieval terminates")
+                .expect("retrieval task");
+            assert!(
+                matches!(
+                    outcome,
+                    Err(SwarmError::RetrievalExhausted {
+                        reason: RetrievalFailure::NotFound,
+                        ..
+                    })
+                ),
+                "every distinct peer failed: {outcome:?}"
+            );
+            assert!(rx.recv().await.is_none(), "no failed peer was asked again");
+        }
+
+        #[tokio::test]
+        async fn a_locally_refused_peer_stays_selectable() {
+            // The bin route is refused at the local gate for both peers. That
+            // says nothing about the peers, so the fallback asks them again.
+            let topology: Arc<dyn RetrievalTopology> = Arc::new(
+                MockTopology::new(2, 2, 0)
+                    .with_connected_in_bins(vec![overlay(1), overlay(2)])
+                    .with_closest(vec![overlay(1), overlay(2)]),
+            );
+            let (engine, mut rx) = engine(topology);
+            let retrieval =
+                tokio::spawn(async move { engine.retrieve(&ChunkAddress::from([0x42; 32])).await });
+
+            let mut contacted = Vec::new();
+            while let Some(command) = rx.recv().await {
+                if let ClientCommand::RetrieveChunk {
+                    peer,
+                    address,
+                    response,
+                    ..
+                } = command
+                {
+                    let answer = if contacted.len() < 2 {
+                        ChunkTransferError::Refused
+                    } else {
+                        ChunkTransferError::NotFound(address)
+                    };
+                    contacted.push(peer);
+                    let _ = response.send(Err(answer));
+                }
+            }
+
+            let bin_route: HashSet<_> = contacted[..2].iter().copied().collect();
+            assert_eq!(bin_route, HashSet::from([overlay(1), overlay(2)]));
+            assert!(
+                contacted[2..].iter().any(|peer| bin_route.contains(peer)),
+                "a refused peer is asked again: {contacted:?}"
+            );
+            let outcome = tokio::time::timeout(Duration::from_secs(30), retrieval)
+                .await
+                .expect("retrieval terminates")
+                .expect("retrieval task");
+            assert!(outcome.is_err(), "every attempt failed: {outcome:?}");
+        }
+    }
+
+    /// Concurrent full retrievals of one address run one race between them.
+    mod coalescing {
+        use std::sync::Arc;
+
+        use nectar_primitives::ContentChunk;
+        use tokio::sync::mpsc;
+        use tokio::task::JoinHandle;
+        use vertex_swarm_api::{ChunkAddress, ChunkRetrievalResult, OverlayAddress, SwarmResult};
+        use vertex_swarm_test_utils::MockTopology;
+
+        use super::super::RetrievalTopology;
+        use crate::test_support::{TestEngine as Engine, overlay};
+        use crate::{ClientCommand, RetrievalResult};
+
+        fn engine() -> (Arc<Engine>, mpsc::Receiver<ClientCommand>) {
+            let topology: Arc<dyn RetrievalTopology> =
+                Arc::new(MockTopology::new(1, 1, 0).with_closest(vec![overlay(1)]));
+            let (engine, rx) = crate::test_support::engine(topology);
+            (Arc::new(engine), rx)
+        }
+
+        fn spawn_retrieve(engine: &Arc<Engine>) -> JoinHandle<SwarmResult<ChunkRetrievalResult>> {
+            let engine = Arc::clone(engine);
+            tokio::spawn(async move { engine.retrieve(&ChunkAddress::from([0x42; 32])).await })
+        }
+
+        async fn until_joined(engine: &Engine) {
+            while engine.coalesced.lock().values().all(Vec::is_empty) {
+                tokio::task::yield_now().await;
+            }
+        }
+
+        fn served(peer: OverlayAddress) -> RetrievalResult {
+            let chunk = ContentChunk::new(&b"coalesced"[..]).expect("valid content chunk");
+            RetrievalResult {
+                chunk: chunk.into(),
+                stamp: None,
+                peer,
+            }
+        }
+
+        #[tokio::test]
+        async fn concurrent_retrievals_share_one_race() {
+            let (engine, mut rx) = engine();
+            let first = spawn_retrieve(&engine);
+            let response = match rx.recv().await.expect("leader dispatched") {
+                ClientCommand::RetrieveChunk { response, .. } => response,
+                other => panic!("unexpected command: {other:?}"),
+            };
+            let second = spawn_retrieve(&engine);
+            until_joined(&engine).await;
+
+            response.send(Ok(served(overlay(1)))).expect("leader alive");
+            for task in [first, second] {
+                let got = task.await.unwrap().expect("both callers get the chunk");
+                assert_eq!(got.served_by, overlay(1));
+            }
+            assert!(rx.try_recv().is_err(), "one race reached the network");
+            assert!(engine.coalesced.lock().is_empty());
+        }
+
+        #[tokio::test]
+        async fn a_waiter_takes_over_when_the_leader_is_dropped() {
+            let (engine, mut rx) = engine();
+            let first = spawn_retrieve(&engine);
+            let _abandoned = match rx.recv().await.expect("leader dispatched") {
+                ClientCommand::RetrieveChunk { response, .. } => response,
+                other => panic!("unexpected command: {other:?}"),
+            };
+            let second = spawn_retrieve(&engine);
+            until_joined(&engine).await;
+
+            first.abort();
+            let response = match rx.recv().await.expect("the waiter leads a new race") {
+                ClientCommand::RetrieveChunk { response, .. } => response,
+                other => panic!("unexpected command: {other:?}"),
+            };
+            response
+                .send(Ok(served(overlay(1))))
+                .expect("new leader alive");
+
+            let got = second.await.unwrap().expect("the waiter is served");
+            assert_eq!(got.served_by, overlay(1));
+        }
+    }
 }
@@ -0,0 +1,78 @@
+//! The node's BZZ balance, read from the spec's token contract.
+
+use alloy_primitives::{Address, U256};
+use async_trait::async_trait;
+use vertex_swarm_api::{NodeFinance, SwarmResult, SwarmSpec, TokenBalanceClient};
+
+/// [`NodeFinance`] over a chain client, reading the token contract the spec
+/// names for the node's wallet.
+#[derive(Debug, Clone)]
+pub struct SpecFinance<C> {
+    client: C,
+    token: Address,
+    owner: Address,
+}
+
+impl<C> SpecFinance<C> {
+    /// Read `owner`'s balance of `spec`'s token through `client`.
+    pub fn new(client: C, spec: &impl SwarmSpec, owner: Address) -> Self {
+        Self {
+            client,
+            token: spec.token_address(),
+            owner,
+        }
+    }
+
+    /// The token contract the balance is read from.
+    pub fn token(&self) -> Address {
+        self.token
+    }
+}
+
+#[async_trait]
+impl<C: TokenBalanceClient> NodeFinance for SpecFinance<C> {
+    async fn balance(&self) -> SwarmResult<U256> {
+        self.client.token_balance(self.token, self.owner).await
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use vertex_swarm_api::SwarmToken;
+
+    /// Records each query and answers with a fixed balance.
+    struct MockChain {
+        balance: U256,
+        queries: Mutex<Vec<(Address, Address)>>,
+    }
+
+    #[async_trait]
+    impl TokenBalanceClient for MockChain {
+        async fn token_balance(&self, token: Address, owner: Address) -> SwarmResult<U256> {
+            self.queries.lock().unwrap().push((token, owner));
+            Ok(self.balance)
+        }
+    }
+
+    #[tokio::test]
+    async fn balance_reads_the_spec_token_for_the_node_wallet() {
+        let spec = vertex_swarm_spec::init_mainnet();
+        let owner = Address::repeat_byte(0x42);
+        let chain = Arc::new(MockChain {
+            balance: U256::from(10_000_000_000_000_000u64),
+            queries: Mutex::new(Vec::new()),
+        });
+        let finance = SpecFinance::new(Arc::clone(&chain), &*spec, owner);
+
+        assert_eq!(finance.balance().await.unwrap(), chain.balance);
+        assert_eq!(finance.token(), spec.token().address());
+        assert_eq!(
+            *chain.queries.lock().unwrap(),
+            vec![(spec.token().address(), owner)]
+        );
+    }
+}
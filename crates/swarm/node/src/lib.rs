@@ -11,25 +11,29 @@ pub mod args;
 mod config;
 
 #[cfg(feature = "cli")]
-pub use config::ProtocolConfig;
+pub use config::{ConfigIssue, IssueSeverity, ProtocolConfig};
 
 mod bootnodes;
+mod breaker;
 mod chunks;
 mod client_service;
 mod dispatch;
+mod finance;
 mod inflight;
 mod node;
 mod protocol;
 mod retrieval_latency;
 mod selection;
 mod staggered_race;
+#[cfg(test)]
+mod test_support;
 
 pub use node::{
     BaseNode, BuiltInfrastructure, ClientCore, ClientCoreCtx, ClientLauncher, ClientNode,
     ClientNodeBuilder, ClientNodeParts, ClientTailParams, LaunchedClient, NativeChunkProvider,
-    NodeBuildError, NodeRunParts, NodeRunTaskFn, PseudosettleWiring, RunTaskFn,
-    SettlementEventSenders, SharedAccounting, assemble_client_core, build_client_core_tail,
-    single_task, spawn_client_command_bridge,
+    NodeBuildError, NodeDiagnostics, NodeRunParts, NodeRunTaskFn, PseudosettleWiring, RunTaskFn,
+    SettlementEventSenders, SharedAccounting, StorageProbe, assemble_client_core,
+    build_client_core_tail, single_task, spawn_client_command_bridge,
 };
 #[cfg(not(target_arch = "wasm32"))]
 pub use node::{BootNode, BootNodeBuilder};
@@ -47,23 +51,39 @@ pub use vertex_chain::SharedChainProvider;
 
 pub use vertex_swarm_api::SwarmNodeType;
 
-pub use client_service::{ChunkTransferError, ClientHandle, ClientService, RetrievalResult};
+pub use client_service::{
+    ChunkTransferError, ClientHandle, ClientService, DEFAULT_COMMAND_SEND_TIMEOUT, RetrievalResult,
+    SocRetrievalError,
+};
 #[cfg(feature = "swap")]
 pub use protocol::SwapEvent;
 pub use protocol::{
-    ClientCommand, ClientEvent, FailureKind, PseudosettleEvent, PushResponseTx, RetrievalResponseTx,
+    ClientCommand, ClientEvent, FailureKind, PricingAnnouncement, PseudosettleEvent,
+    PushResponseTx, RetrievalResponseTx,
 };
+/// Client protocol latency histograms and their Prometheus bucket presets.
+pub use vertex_swarm_client_behaviour::metrics as client_metrics;
 
+pub use breaker::{
+    BreakerProtocol, DEFAULT_BREAKER_COOLDOWN, DEFAULT_BREAKER_THRESHOLD, DEFAULT_BREAKER_WINDOW,
+    ProtocolBreaker,
+};
 pub use inflight::{DEFAULT_PEER_INFLIGHT_CAP, PeerInflightLimiter};
 pub use retrieval_latency::RetrievalLatency;
-pub use selection::{AccountingSettlement, PeerScores, PeerSelector, SettlementTrigger};
+pub use selection::{
+    AccountingSettlement, ClosestSelector, LatencySelector, PeerScores, PeerSelector,
+    RetrievalSelection, ScoreSelector, SettlementTrigger,
+};
 pub use staggered_race::{RETRIEVAL_STAGGER, RaceFailure, race_candidates, race_with_refill};
 
 pub use bootnodes::BootnodeProvider;
-pub use chunks::NetworkChunkProvider;
+pub use chunks::{
+    DEFAULT_RECENT_PUSH_CAPACITY, DEFAULT_RECENT_PUSH_TTL, NetworkChunkProvider, ReadYourWrites,
+};
 pub use dispatch::{
     CandidateOrdering, DispatchEngine, InflightLimit, LatencyHint, NoLatencyHint, ProximityOnly,
-    RetrievalTopology,
+    RetrievalOptions, RetrievalTopology,
 };
+pub use finance::SpecFinance;
 pub use node::stats::StatsConfig;
 pub use node::task::spawn_stats_task;
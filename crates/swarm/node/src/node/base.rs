@@ -5,7 +5,7 @@ use libp2p::connection_limits::{self, ConnectionLimits};
 use libp2p::{Multiaddr, PeerId, Swarm, swarm::NetworkBehaviour, swarm::SwarmEvent};
 use nectar_primitives::SwarmAddress;
 use tracing::{debug, info, trace, warn};
-use vertex_swarm_api::{SwarmIdentity, SwarmNetworkConfig};
+use vertex_swarm_api::{SwarmIdentity, SwarmNetworkConfig, SwarmNodeType};
 use vertex_swarm_net_identify as identify;
 use vertex_swarm_topology::TopologyHandle;
 
@@ -29,17 +29,19 @@ const MAX_ESTABLISHED_PER_PEER: u32 = 2;
 /// Build the transport-level connection-limits behaviour from the network
 /// configuration.
 ///
-/// The total established cap comes from `--network.max-peers` and is a
-/// resource backstop (file descriptors, memory, bandwidth), enforced by the
-/// swarm independently of kademlia bin logic. Topology keeps full ownership
-/// of connection composition: per-bin targets, saturation, inbound ceilings,
-/// and trimming. The transport cap only bounds how many connections can
-/// exist at all, so it must sit above topology's own steady-state total
-/// (see the `--network.max-peers` default rationale in the args module).
+/// The total established cap comes from `--network.max-peers`, falling back
+/// to the `node_type` default when unset. It is a resource backstop (file
+/// descriptors, memory, bandwidth), enforced by the swarm independently of
+/// kademlia bin logic. Topology keeps full ownership of connection
+/// composition: per-bin targets, saturation, inbound ceilings, and trimming.
+/// The transport cap only bounds how many connections can exist at all, so it
+/// must sit above topology's own steady-state total (see
+/// [`SwarmNodeType::default_max_peers`]).
 pub(crate) fn build_connection_limits(
     config: &impl SwarmNetworkConfig,
+    node_type: SwarmNodeType,
 ) -> connection_limits::Behaviour {
-    let max_established = u32::try_from(config.max_peers()).unwrap_or(u32::MAX);
+    let max_established = u32::try_from(config.effective_max_peers(node_type)).unwrap_or(u32::MAX);
     connection_limits::Behaviour::new(
         ConnectionLimits::default()
             .with_max_established(Some(max_established))
@@ -226,8 +228,8 @@ mod tests {
         fn discovery_enabled(&self) -> bool {
             false
         }
-        fn max_peers(&self) -> usize {
-            self.max_peers
+        fn max_peers(&self) -> Option<usize> {
+            Some(self.max_peers)
         }
         fn idle_timeout(&self) -> Duration {
             Duration::from_secs(30)
@@ -243,7 +245,7 @@ mod tests {
 
     fn limits_swarm(max_peers: usize) -> Swarm<LimitsOnly> {
         Swarm::new_ephemeral_tokio(|_| LimitsOnly {
-            limits: build_connection_limits(&CapConfig { max_peers }),
+            limits: build_connection_limits(&CapConfig { max_peers }, SwarmNodeType::Client),
         })
     }
 
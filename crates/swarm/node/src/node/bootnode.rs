@@ -66,6 +66,8 @@ impl<I: SwarmIdentity + Clone> BootnodeBehaviour<I> {
         agent_version: Option<&str>,
     ) -> Self {
         let agent_versions = topology.agent_versions();
+        let client_config = ClientBehaviourConfig::for_role(SwarmNodeType::Bootnode)
+            .with_protocol_prefix(topology.protocol_prefix());
         Self {
             connection_limits,
             // Identify advertises addresses scoped to each peer (see
@@ -81,7 +83,7 @@ impl<I: SwarmIdentity + Clone> BootnodeBehaviour<I> {
             // pushsync, so its cache is never consulted; a zero-budget cache and
             // the stub forwarder keep the behaviour inert.
             client: ClientBehaviour::new(
-                ClientBehaviourConfig::for_role(SwarmNodeType::Bootnode),
+                client_config,
                 Arc::new(vertex_swarm_localstore::ChunkStore::with_budget(0, 0)),
                 Arc::new(StubForwarder),
             ),
@@ -322,7 +324,8 @@ impl<I: SwarmIdentity + Clone> BootNodeBuilder<I> {
             }
         };
 
-        let connection_limits = super::base::build_connection_limits(network_config);
+        let connection_limits =
+            super::base::build_connection_limits(network_config, SwarmNodeType::Bootnode);
         let base = super::builder::build_base_node(
             infra,
             network_config,
@@ -393,8 +396,8 @@ mod tests {
         fn discovery_enabled(&self) -> bool {
             true
         }
-        fn max_peers(&self) -> usize {
-            32
+        fn max_peers(&self) -> Option<usize> {
+            Some(32)
         }
         fn idle_timeout(&self) -> Duration {
             Duration::from_secs(60)
@@ -2,15 +2,18 @@
 
 use std::time::Duration;
 
+use alloy_primitives::U256;
 use eyre::{Result, WrapErr};
 use libp2p::{Multiaddr, Swarm, identity::PublicKey, swarm::NetworkBehaviour};
 use tracing::{info, warn};
 use vertex_net_peer_store::PeerSnapshotStore;
+use vertex_swarm_accounting::{DEFAULT_LIGHT_PAYMENT_THRESHOLD, DEFAULT_PAYMENT_THRESHOLD};
 use vertex_swarm_api::{
-    SwarmIdentity, SwarmNetworkConfig, SwarmPeerConfig, SwarmRoutingConfig, SwarmTopologyCommands,
+    NetworkIngest, OverlayAddress, SwarmIdentity, SwarmNetworkConfig, SwarmPeerConfig,
+    SwarmRoutingConfig, SwarmTopologyCommands,
 };
 use vertex_swarm_peer_manager::PeerSnapshot;
-use vertex_swarm_spec::HasSpec;
+use vertex_swarm_spec::{HasSpec, SwarmHardfork};
 use vertex_swarm_topology::{
     KademliaConfig, TopologyBehaviour, TopologyBehaviourBuilder, TopologyConfig, TopologyHandle,
 };
@@ -21,6 +24,7 @@ use super::base::BaseNode;
 use super::error::NodeBuildError;
 
 use crate::BootnodeProvider;
+use crate::protocol::{BehaviourConfig as ClientBehaviourConfig, PricingAnnouncement};
 
 /// Build the identify config, announcing `agent_version` when the assembly layer
 /// supplied one and otherwise keeping the protocol library default.
@@ -35,6 +39,32 @@ pub(crate) fn identify_config(
     }
 }
 
+/// The announcement a node builder falls back to when none was configured: the
+/// default light threshold to client peers, the default full one to storers.
+pub(crate) fn default_pricing() -> PricingAnnouncement {
+    PricingAnnouncement::new(
+        U256::from(DEFAULT_PAYMENT_THRESHOLD),
+        U256::from(DEFAULT_LIGHT_PAYMENT_THRESHOLD),
+    )
+}
+
+/// Client-behaviour config that announces `pricing` on activation, under
+/// the protocol prefix, Accord activation and chunk set of `topology`'s spec,
+/// forwarding inbound retrieval misses only once `min_peers_to_serve` peers
+/// are active.
+pub(crate) fn client_behaviour_config<I: SwarmIdentity + Clone>(
+    topology: &TopologyBehaviour<I>,
+    pricing: PricingAnnouncement,
+    min_peers_to_serve: usize,
+) -> ClientBehaviourConfig {
+    ClientBehaviourConfig::default()
+        .with_pricing(pricing)
+        .with_min_peers_to_serve(min_peers_to_serve)
+        .with_protocol_prefix(topology.protocol_prefix())
+        .with_accord(topology.fork_condition(SwarmHardfork::Accord))
+        .with_ingest(NetworkIngest::for_spec::<I::Spec>())
+}
+
 pub(crate) type PeerStore = std::sync::Arc<dyn PeerSnapshotStore<PeerSnapshot>>;
 
 /// Pre-built infrastructure components ready for swarm assembly.
@@ -121,11 +151,15 @@ impl<C: SwarmNetworkConfig> SwarmNetworkConfig for ConfigWithBootnodes<'_, C> {
         self.inner.trusted_peers()
     }
 
+    fn static_peers(&self) -> &[(OverlayAddress, Multiaddr)] {
+        self.inner.static_peers()
+    }
+
     fn discovery_enabled(&self) -> bool {
         self.inner.discovery_enabled()
     }
 
-    fn max_peers(&self) -> usize {
+    fn max_peers(&self) -> Option<usize> {
         self.inner.max_peers()
     }
 
@@ -153,6 +187,10 @@ impl<C: SwarmNetworkConfig> SwarmNetworkConfig for ConfigWithBootnodes<'_, C> {
         self.inner.mdns_enabled()
     }
 
+    fn compression_enabled(&self) -> bool {
+        self.inner.compression_enabled()
+    }
+
     fn trust_local_peers(&self) -> bool {
         self.inner.trust_local_peers()
     }
@@ -160,6 +198,34 @@ impl<C: SwarmNetworkConfig> SwarmNetworkConfig for ConfigWithBootnodes<'_, C> {
     fn connection_profile(&self) -> Option<vertex_swarm_api::ConnectionProfile> {
         self.inner.connection_profile()
     }
+
+    fn max_concurrent_dials(&self) -> Option<usize> {
+        self.inner.max_concurrent_dials()
+    }
+
+    fn churn_threshold(&self) -> Option<f64> {
+        self.inner.churn_threshold()
+    }
+
+    fn self_announce_interval(&self) -> Option<Duration> {
+        self.inner.self_announce_interval()
+    }
+
+    fn min_peers_to_serve(&self) -> usize {
+        self.inner.min_peers_to_serve()
+    }
+
+    fn exchange_burst(&self) -> Option<usize> {
+        self.inner.exchange_burst()
+    }
+
+    fn exchange_interval(&self) -> Option<Duration> {
+        self.inner.exchange_interval()
+    }
+
+    fn command_channel_capacity(&self) -> usize {
+        self.inner.command_channel_capacity()
+    }
 }
 
 impl<C: SwarmPeerConfig> SwarmPeerConfig for ConfigWithBootnodes<'_, C> {
@@ -11,7 +11,10 @@ use libp2p::{Multiaddr, PeerId, identity::PublicKey, swarm::NetworkBehaviour, sw
 use nectar_primitives::SwarmAddress;
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
-use vertex_swarm_api::{SwarmIdentity, SwarmNetworkConfig, SwarmPeerConfig, SwarmRoutingConfig};
+use vertex_swarm_api::{
+    SwarmIdentity, SwarmNetworkConfig, SwarmNodeType, SwarmPeerConfig, SwarmRoutingConfig,
+    SwarmSpec,
+};
 use vertex_swarm_net_identify as identify;
 use vertex_swarm_topology::{
     KademliaConfig, TopologyBehaviour, TopologyCommand, TopologyConfig, TopologyEvent,
@@ -20,14 +23,14 @@ use vertex_swarm_topology::{
 use vertex_tasks::GracefulShutdown;
 use vertex_tasks::TaskExecutor;
 
-use vertex_swarm_api::SwarmLocalStore;
+use vertex_swarm_api::{NetworkIngest, SwarmLocalStore};
 
 use super::base::BaseNode;
 use super::builder::BuiltInfrastructure;
 use super::nat::{NatBehaviour, NatEvent};
 use crate::protocol::{
-    BehaviourConfig as ClientBehaviourConfig, ClientBehaviour, ClientCommand, ClientEvent,
-    PseudosettleEvent, StubForwarder,
+    ClientBehaviour, ClientCommand, ClientEvent, PricingAnnouncement, PseudosettleEvent,
+    StubForwarder,
 };
 use crate::{ClientHandle, ClientService};
 
@@ -47,15 +50,22 @@ pub(crate) struct ClientNodeBehaviour<I: SwarmIdentity + Clone> {
 }
 
 impl<I: SwarmIdentity + Clone> ClientNodeBehaviour<I> {
+    // Assembles already-built parts plus the network settings they read; a
+    // params struct would only rename the same fields.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn from_parts(
         local_public_key: PublicKey,
         topology: TopologyBehaviour<I>,
         nat: NatBehaviour,
         connection_limits: connection_limits::Behaviour,
         store: Arc<dyn SwarmLocalStore>,
+        pricing: PricingAnnouncement,
+        min_peers_to_serve: usize,
         agent_version: Option<&str>,
     ) -> Self {
         let agent_versions = topology.agent_versions();
+        let client_config =
+            super::builder::client_behaviour_config(&topology, pricing, min_peers_to_serve);
         Self {
             connection_limits,
             // Identify advertises addresses scoped per peer (see
@@ -70,11 +80,7 @@ impl<I: SwarmIdentity + Clone> ClientNodeBehaviour<I> {
             // Cache-only client never relays: the stub forwarder resets the
             // substream on cache miss and every inbound pushsync. The real relay
             // is installed by `enable_forwarding`.
-            client: ClientBehaviour::new(
-                ClientBehaviourConfig::default(),
-                store,
-                Arc::new(StubForwarder),
-            ),
+            client: ClientBehaviour::new(client_config, store, Arc::new(StubForwarder)),
         }
     }
 }
@@ -85,12 +91,14 @@ async fn build_client_base<I, C>(
     infra: BuiltInfrastructure<I>,
     network_config: &C,
     store: Arc<dyn SwarmLocalStore>,
+    pricing: PricingAnnouncement,
 ) -> Result<BaseNode<I, ClientNodeBehaviour<I>>>
 where
     I: SwarmIdentity + Clone,
     C: SwarmNetworkConfig,
 {
-    let connection_limits = super::base::build_connection_limits(network_config);
+    let connection_limits =
+        super::base::build_connection_limits(network_config, SwarmNodeType::Client);
     super::builder::build_base_node(infra, network_config, "Client node", move |pk, topology| {
         let nat = NatBehaviour::from_config(network_config, pk.to_peer_id());
         ClientNodeBehaviour::from_parts(
@@ -99,6 +107,8 @@ where
             nat,
             connection_limits,
             store,
+            pricing,
+            network_config.min_peers_to_serve(),
             network_config.agent_version(),
         )
     })
@@ -215,14 +225,36 @@ impl<I: SwarmIdentity + Clone> ClientNode<I> {
     /// for: a responsible delivery is put into `reserve` and acknowledged with a
     /// receipt signed by the identity key, bound to its nonce. Non-responsible
     /// deliveries still forward (see
-    /// [`enable_forwarding`](Self::enable_forwarding)).
+    /// [`enable_forwarding`](Self::enable_forwarding)). At most
+    /// `validation_concurrency` stamp checks run at once; excess deliveries
+    /// queue. Each stamp is then checked against the batch set by `verifier`.
+    /// Stamps are neither required nor checked when the spec waives storage
+    /// payment. With `refuse_out_of_range`, a delivery outside the storage
+    /// radius is refused instead of forwarded.
     ///
     /// Must be called during node assembly, before the event loop accepts
     /// connections: a handler created earlier does not capture the capability.
-    pub fn enable_storage(&mut self, reserve: Arc<dyn vertex_swarm_api::ReserveStore>) {
+    pub fn enable_storage(
+        &mut self,
+        reserve: Arc<dyn vertex_swarm_api::ReserveStore>,
+        verifier: Option<Arc<dyn vertex_swarm_api::StampVerifier>>,
+        validation_concurrency: usize,
+        refuse_out_of_range: bool,
+    ) {
         let signer: Arc<dyn vertex_swarm_primitives::OverlaySigner + Send + Sync> =
             Arc::new(self.base.identity().clone());
-        let capability = crate::protocol::StorerCapability::new(reserve, signer);
+        let policy = vertex_swarm_api::StampPolicy::for_spec(self.base.identity().spec());
+        let mut capability = crate::protocol::StorerCapability::new(reserve, signer)
+            .with_validation_concurrency(validation_concurrency)
+            .with_stamp_policy(policy)
+            .with_out_of_range_policy(if refuse_out_of_range {
+                crate::protocol::OutOfRangePolicy::Refuse
+            } else {
+                crate::protocol::OutOfRangePolicy::Forward
+            });
+        if let Some(verifier) = verifier {
+            capability = capability.with_stamp_verifier(verifier);
+        }
         self.base
             .swarm
             .behaviour_mut()
@@ -385,6 +417,7 @@ impl<I: SwarmIdentity + Clone> ClientNode<I> {
                 overlay,
                 peer_id,
                 node_type,
+                capabilities,
                 ..
             } => {
                 self.base
@@ -395,6 +428,7 @@ impl<I: SwarmIdentity + Clone> ClientNode<I> {
                         peer_id,
                         overlay,
                         node_type,
+                        capabilities,
                     });
             }
             TopologyEvent::PeerDisconnected { .. } => {}
@@ -403,6 +437,9 @@ impl<I: SwarmIdentity + Clone> ClientNode<I> {
             TopologyEvent::PhaseChanged { .. } => {}
             TopologyEvent::DialFailed { .. } => {}
             TopologyEvent::PingCompleted { .. } => {}
+            TopologyEvent::PingFailed { .. } => {}
+            TopologyEvent::ChurnExceeded { .. } => {}
+            TopologyEvent::NetworkPartitionSuspected { .. } => {}
         }
     }
 
@@ -432,6 +469,7 @@ pub struct ClientNodeBuilder<I: SwarmIdentity + Clone> {
     infra: Option<BuiltInfrastructure<I>>,
     kademlia_config: Option<KademliaConfig>,
     store: Option<Arc<dyn SwarmLocalStore>>,
+    pricing: Option<PricingAnnouncement>,
     pseudosettle_event_tx: Option<mpsc::UnboundedSender<PseudosettleEvent>>,
     #[cfg(feature = "swap")]
     swap_event_tx: Option<mpsc::UnboundedSender<crate::protocol::SwapEvent>>,
@@ -444,6 +482,7 @@ impl<I: SwarmIdentity + Clone> ClientNodeBuilder<I> {
             infra: None,
             kademlia_config: None,
             store: None,
+            pricing: None,
             pseudosettle_event_tx: None,
             #[cfg(feature = "swap")]
             swap_event_tx: None,
@@ -467,6 +506,13 @@ impl<I: SwarmIdentity + Clone> ClientNodeBuilder<I> {
         self
     }
 
+    /// Payment thresholds announced to peers on activation. Defaults to the
+    /// default thresholds when unset.
+    pub fn with_pricing(mut self, pricing: PricingAnnouncement) -> Self {
+        self.pricing = Some(pricing);
+        self
+    }
+
     pub fn with_pseudosettle_events(
         mut self,
         tx: mpsc::UnboundedSender<PseudosettleEvent>,
@@ -520,7 +566,9 @@ impl<I: SwarmIdentity + Clone> ClientNodeBuilder<I> {
             ))
         });
 
-        let mut base = build_client_base(infra, network_config, Arc::clone(&store)).await?;
+        let pricing = self.pricing.unwrap_or_else(super::builder::default_pricing);
+        let mut base =
+            build_client_base(infra, network_config, Arc::clone(&store), pricing).await?;
 
         base.swarm
             .behaviour()
@@ -543,16 +591,19 @@ impl<I: SwarmIdentity + Clone> ClientNodeBuilder<I> {
         super::task::spawn_stats_task(
             Arc::new(base.topology_handle.clone()),
             Arc::clone(base.topology_handle.peer_manager().score_distribution()),
+            base.swarm.behaviour().client.latency(),
             super::stats::StatsConfig::default(),
             &executor,
         );
 
-        let (command_tx, command_rx) =
-            mpsc::channel(crate::client_service::DEFAULT_CHANNEL_CAPACITY);
+        let (command_tx, command_rx) = mpsc::channel(network_config.command_channel_capacity());
         let (event_tx, event_rx) = mpsc::channel(crate::client_service::DEFAULT_CHANNEL_CAPACITY);
 
         let (client_service, client_handle) = ClientService::with_channels(command_tx, event_rx);
-        let client_service = client_service.with_store(store);
+        let client_service = client_service
+            .with_store(store)
+            .with_ingest(NetworkIngest::for_spec::<I::Spec>())
+            .with_min_payment_threshold(base.identity().spec().min_payment_threshold());
 
         let node = ClientNode {
             base,
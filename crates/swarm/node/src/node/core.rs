@@ -8,9 +8,11 @@
 //! settlement service onto the node command channel. Spawning takes a bare
 //! [`TaskExecutor`] so both the native context and the browser launcher drive it.
 
+use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Duration;
 
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 use tracing::warn;
 use vertex_swarm_accounting::{
     Accounting, AccountingBuilder, ClientAccounting, DefaultBandwidthConfig, FixedPricer,
@@ -21,15 +23,16 @@ use vertex_swarm_accounting_pseudosettle::{
 };
 use vertex_swarm_api::{
     Au, PeerReporter, SwarmAccountingConfig, SwarmBandwidthAccounting, SwarmClientAccounting,
-    SwarmNodeType, SwarmSettlementProvider,
+    SwarmNodeType, SwarmSettlementProvider, SwarmTopologyRouting, SwarmTopologyState,
 };
 use vertex_swarm_identity::Identity;
 use vertex_swarm_peer_manager::{DEFAULT_TICK_INTERVAL, spawn_peer_manager_task};
+use vertex_swarm_primitives::OverlayAddress;
 use vertex_swarm_spec::Spec;
-use vertex_swarm_topology::TopologyHandle;
+use vertex_swarm_topology::{TopologyEvent, TopologyHandle};
 use vertex_tasks::TaskExecutor;
 
-use crate::chunks::NetworkChunkProvider;
+use crate::chunks::{NetworkChunkProvider, ReadYourWrites};
 
 #[cfg(feature = "swap")]
 use alloy_chains::NamedChain;
@@ -44,14 +47,17 @@ use vertex_chain::SharedChainProvider;
 #[cfg(feature = "swap")]
 use vertex_swarm_accounting_swap::service::SwapCommand;
 #[cfg(feature = "swap")]
-use vertex_swarm_accounting_swap::{SwapEvent, SwapHandle, SwapProvider, SwapService};
+use vertex_swarm_accounting_swap::{
+    SettlementQueueReceiver, SettlementWorker, SwapEvent, SwapHandle, SwapProvider, SwapService,
+};
 #[cfg(feature = "swap")]
 use vertex_swarm_api::{SwarmIdentity, SwarmSpec};
 
 use crate::retrieval_latency::RetrievalLatency;
 use crate::{
     AccountingSettlement, ClientCommand, ClientHandle, ClientService, DEFAULT_PEER_INFLIGHT_CAP,
-    PeerInflightLimiter, PeerSelector, RetrievalTopology, SettlementTrigger,
+    PeerInflightLimiter, PeerSelector, ProtocolBreaker, RetrievalSelection, RetrievalTopology,
+    ScoreSelector, SettlementTrigger,
 };
 
 /// The concrete shared accounting both client-backed node types build: the
@@ -77,10 +83,13 @@ pub struct ClientCore {
     /// and settlement.
     pub accounting: SharedAccounting,
     /// Retrieval and pushsync candidate selection over the shared accounting.
-    pub selector: Arc<PeerSelector>,
+    pub selector: Arc<ScoreSelector>,
     /// Per-peer retrieval in-flight cap shared by the chunk provider (reserves
     /// slots) and the service (forgets a peer on disconnect).
     pub inflight: Arc<PeerInflightLimiter>,
+    /// Per-peer, per-protocol breaker shared by the service (records malformed
+    /// responses) and the chunk provider (skips open peers).
+    pub breaker: Arc<ProtocolBreaker>,
     /// Per-PO retrieval-latency estimate shared by the service (records) and the
     /// chunk provider (reads to pace its staggered race). Internal mechanism, so
     /// crate-visible rather than part of the re-exported surface.
@@ -148,7 +157,11 @@ pub fn assemble_client_core(ctx: ClientCoreCtx) -> ClientCore {
 
     // Pseudosettle is registered first so soft accounting forgives total debt
     // before swap settles originated debt; the order matches `settle_all`.
+    let rate_limits = bandwidth.rate_limits();
+    let reputation = bandwidth.reputation_scaling();
     let accounting = AccountingBuilder::new(bandwidth)
+        .with_rate_limits(rate_limits)
+        .with_reputation_scaling(reputation)
         .with_pricer_from_config(spec)
         .with_settlement(pseudosettle_provider)
         .with_settlements(extra_settlement)
@@ -168,7 +181,7 @@ pub fn assemble_client_core(ctx: ClientCoreCtx) -> ClientCore {
     // Ranking only: the selector triggers no settlement. The origin credit gate
     // settles the peer a request actually dispatches to (`settlement_trigger`),
     // so the settle fan-out is the legs contacted, not the candidate window.
-    let selector = Arc::new(PeerSelector::new(
+    let selector = Arc::new(ScoreSelector::new(
         Arc::new(topology.clone()),
         admission.clone(),
         Arc::new(accounting.pricing().clone()),
@@ -199,6 +212,11 @@ pub fn assemble_client_core(ctx: ClientCoreCtx) -> ClientCore {
     // service has observed.
     let retrieval_latency = Arc::new(RetrievalLatency::new());
 
+    // Per-peer, per-protocol breaker: the service opens it on repeated malformed
+    // responses and the chunk provider skips an open peer for that protocol
+    // only, so a peer with bad retrieval data can still take our pushes.
+    let breaker = Arc::new(ProtocolBreaker::default());
+
     // The service reports through the same peer-manager authority accounting uses
     // and forgets a peer's in-flight slots on disconnect. The origin debit is
     // reserved and committed by the dispatch gate on the origin-gated handle, not
@@ -206,12 +224,14 @@ pub fn assemble_client_core(ctx: ClientCoreCtx) -> ClientCore {
     let client_service = client_service
         .with_reporter(reporter)
         .with_inflight_limiter(Arc::clone(&inflight))
+        .with_breaker(Arc::clone(&breaker))
         .with_retrieval_latency(Arc::clone(&retrieval_latency));
 
     ClientCore {
         accounting,
         selector,
         inflight,
+        breaker,
         retrieval_latency,
         origin_handle,
         settlement_trigger,
@@ -225,12 +245,14 @@ pub fn assemble_client_core(ctx: ClientCoreCtx) -> ClientCore {
 ///
 /// Produced by [`PseudosettleWiring::prepare`] before the accounting is built;
 /// consumed by [`PseudosettleWiring::spawn`] after the node command channel
-/// exists. Wasm-clean: tokio sync channels and an `Au` refresh rate only.
+/// exists. Wasm-clean: tokio sync channels, an `Au` refresh rate, and the ack
+/// clock-skew tolerance only.
 pub struct PseudosettleWiring {
     command_rx: mpsc::UnboundedReceiver<PseudosettleCommand>,
     event_tx: mpsc::UnboundedSender<PseudosettleEvent>,
     event_rx: mpsc::UnboundedReceiver<PseudosettleEvent>,
     refresh_rate: Au,
+    clock_skew_tolerance: Duration,
 }
 
 impl PseudosettleWiring {
@@ -255,6 +277,7 @@ impl PseudosettleWiring {
                 event_tx,
                 event_rx,
                 refresh_rate: config.refresh_rate(),
+                clock_skew_tolerance: config.clock_skew_tolerance(),
             },
         )
     }
@@ -297,6 +320,7 @@ impl PseudosettleWiring {
             accounting,
             self.refresh_rate,
         )
+        .with_clock_skew_tolerance(self.clock_skew_tolerance)
         .with_reporter(reporter);
 
         executor.spawn_service("swarm.pseudosettle_service", service);
@@ -318,6 +342,8 @@ pub struct SwapWiring {
     beneficiary: Address,
     chain: NamedChain,
     bounce_limit: u128,
+    /// Backend handle and queue receiver when settling in the background.
+    settlement_worker: Option<(SwapHandle, SettlementQueueReceiver)>,
 }
 
 #[cfg(feature = "swap")]
@@ -328,8 +354,10 @@ impl SwapWiring {
     /// false, or when SWAP is requested but the required chequebook address and
     /// settlement chain cannot be resolved. `beneficiary` defaults to the node
     /// Ethereum address when `None`: the only payout address a cheque sent to us
-    /// may name. The returned provider is registered with the accounting builder;
-    /// the returned wiring is later handed to [`SwapWiring::spawn`].
+    /// may name. A non-zero `settlement_queue` settles through a background
+    /// queue of that capacity instead of inline. The returned provider is
+    /// registered with the accounting builder; the returned wiring is later
+    /// handed to [`SwapWiring::spawn`].
     #[allow(clippy::too_many_arguments)]
     pub fn prepare<C>(
         spec: &Arc<Spec>,
@@ -339,6 +367,7 @@ impl SwapWiring {
         beneficiary: Option<Address>,
         deploy: bool,
         bounce_limit: u128,
+        settlement_queue: usize,
         swap_enabled: bool,
     ) -> Option<(SwapProvider<C>, Self)>
     where
@@ -377,7 +406,16 @@ impl SwapWiring {
         // can be embedded in the accounting before the accounting is built.
         let (command_tx, command_rx) = mpsc::unbounded_channel();
         let handle = SwapHandle::new(command_tx);
-        let provider = SwapProvider::with_handle(config.clone(), handle);
+        let (provider, settlement_worker) = if settlement_queue == 0 {
+            (SwapProvider::with_handle(config.clone(), handle), None)
+        } else {
+            let (queue, receiver) =
+                vertex_swarm_accounting_swap::settlement_queue(settlement_queue);
+            (
+                SwapProvider::with_queue(config.clone(), queue),
+                Some((handle, receiver)),
+            )
+        };
 
         info!(%chequebook, %beneficiary, %chain, "SWAP settlement enabled");
 
@@ -390,6 +428,7 @@ impl SwapWiring {
             beneficiary,
             chain,
             bounce_limit,
+            settlement_worker,
         };
 
         Some((provider, wiring))
@@ -404,7 +443,8 @@ impl SwapWiring {
     ///
     /// The service records cheque-driven balance changes against `accounting`
     /// (the same instance the provider settles through), drains the provider's
-    /// command channel, and consumes routed swap wire events. Cheque violations
+    /// command channel, and consumes routed swap wire events. With a settlement
+    /// queue, a worker over the same accounting drives it. Cheque violations
     /// are reported through `reporter` so they feed peer scoring. Its
     /// `SendCheque` commands are forwarded to the node through `client_handle`.
     /// With the `swap-chequebook` feature and a connected chain provider, received
@@ -431,6 +471,13 @@ impl SwapWiring {
             client_handle,
         );
 
+        if let Some((handle, receiver)) = self.settlement_worker {
+            executor.spawn_service(
+                "swarm.swap_settlement_worker",
+                SettlementWorker::new(receiver, accounting.clone(), Arc::new(handle)),
+            );
+        }
+
         let service = SwapService::new(
             self.command_rx,
             self.swap_event_rx,
@@ -521,6 +568,56 @@ pub fn spawn_client_command_bridge(
     });
 }
 
+/// Keep the accounting's neighbour serve-rate bucket in step with the
+/// neighbourhood.
+///
+/// Re-reads the neighbourhood on every activation, disconnect and depth change
+/// and moves the peers that entered or left it. Spawned only when a distinct
+/// neighbour rate is configured; otherwise every peer shares one bucket.
+pub fn spawn_neighbour_rate_tracker(
+    executor: &TaskExecutor,
+    topology: TopologyHandle<Arc<Identity>>,
+    accounting: Arc<Accounting<DefaultBandwidthConfig, Arc<Identity>>>,
+) {
+    executor.spawn_with_graceful_shutdown_signal(
+        "swarm.neighbour_rate",
+        move |shutdown| async move {
+            let mut events = topology.subscribe();
+            let mut neighbours: HashSet<OverlayAddress> = HashSet::new();
+            let mut shutdown = std::pin::pin!(shutdown);
+            loop {
+                tokio::select! {
+                    guard = &mut shutdown => {
+                        drop(guard);
+                        break;
+                    }
+                    event = events.recv() => {
+                        match event {
+                            Ok(
+                                TopologyEvent::PeerReady { .. }
+                                | TopologyEvent::PeerDisconnected { .. }
+                                | TopologyEvent::DepthChanged { .. },
+                            )
+                            | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                            Ok(_) => continue,
+                            Err(broadcast::error::RecvError::Closed) => break,
+                        }
+                        let current: HashSet<OverlayAddress> =
+                            topology.neighbors(topology.depth()).into_iter().collect();
+                        for peer in current.difference(&neighbours) {
+                            accounting.set_neighbour(*peer, true);
+                        }
+                        for peer in neighbours.difference(&current) {
+                            accounting.set_neighbour(*peer, false);
+                        }
+                        neighbours = current;
+                    }
+                }
+            }
+        },
+    );
+}
+
 /// The node run-loop task the launch tail hands back for the entry point to
 /// spawn.
 ///
@@ -580,7 +677,7 @@ pub struct NodeRunParts {
 /// the RPC chunk surface both client entry points expose; content integrity is
 /// enforced during retrieval decode, so no download-side wrapper sits over it.
 pub type NativeChunkProvider =
-    NetworkChunkProvider<Arc<PeerSelector>, Arc<PeerInflightLimiter>, Arc<RetrievalLatency>>;
+    NetworkChunkProvider<Arc<ScoreSelector>, Arc<PeerInflightLimiter>, Arc<RetrievalLatency>>;
 
 /// Outputs of [`build_client_core_tail`]: the run-loop task, the topology handle,
 /// the chunk provider, the shared accounting and throttled client handle
@@ -630,6 +727,8 @@ pub struct ClientSwapParams {
     pub deploy: bool,
     /// Per-peer cap on uncashed cheque exposure.
     pub bounce_limit: u128,
+    /// Capacity of the background settlement queue; `0` settles inline.
+    pub settlement_queue: usize,
 }
 
 /// Borrowed, wasm-clean inputs to [`build_client_core_tail`].
@@ -642,6 +741,12 @@ pub struct ClientTailParams<'a> {
     pub identity: &'a Arc<Identity>,
     /// Bandwidth config driving accounting, pricing, and the self-throttle.
     pub bandwidth: &'a DefaultBandwidthConfig,
+    /// Built-in retrieval source selection, used when `selector` is `None`.
+    pub selection: RetrievalSelection,
+    /// Retrieval source selection supplied in code; overrides `selection`.
+    pub selector: Option<Arc<dyn PeerSelector>>,
+    /// Serve the node's own recent uploads back locally; `None` disables it.
+    pub read_your_writes: Option<ReadYourWrites>,
     /// SWAP settlement parameters.
     #[cfg(feature = "swap")]
     pub swap: ClientSwapParams,
@@ -655,7 +760,9 @@ pub struct ClientTailParams<'a> {
 /// the concrete node over the settlement event sinks and returns its run parts
 /// plus the node-type provider store; the tail is agnostic to whether that node
 /// is a bare client or a storer. SWAP defaults on for storers and off for
-/// clients, overridable through `params.swap.enable`.
+/// clients and on development networks (see
+/// [`SwarmSpec::default_swap_enabled`]), overridable through
+/// `params.swap.enable`.
 ///
 /// The returned run task is left for the caller to spawn: the native builder
 /// hands it to the binary, the embedded launcher spawns it on its executor.
@@ -684,7 +791,7 @@ where
         let swap_enabled = params
             .swap
             .enable
-            .unwrap_or(params.node_type.swap_default());
+            .unwrap_or_else(|| params.spec.default_swap_enabled(params.node_type));
         SwapWiring::prepare(
             params.spec,
             params.identity,
@@ -693,6 +800,7 @@ where
             params.swap.beneficiary,
             params.swap.deploy,
             params.swap.bounce_limit,
+            params.swap.settlement_queue,
             swap_enabled,
         )
         .unzip()
@@ -758,6 +866,19 @@ where
         reporter: Arc::clone(&reporter),
     });
 
+    if params
+        .bandwidth
+        .rate_limits()
+        .neighbour_bytes_per_sec
+        .is_some()
+    {
+        spawn_neighbour_rate_tracker(
+            executor,
+            topology.clone(),
+            core.accounting.bandwidth().clone(),
+        );
+    }
+
     // Multi-hop forwarding plus storer ingest must precede the event loop. The
     // run closure applies both to its concrete node over the shared accounting,
     // then returns the run task. Forwarder relay legs run over the plain handle:
@@ -780,7 +901,18 @@ where
         Arc::clone(&core.retrieval_latency),
         Arc::clone(&core.settlement_trigger),
         provider_cache,
-    );
+    )
+    .with_breaker(Arc::clone(&core.breaker));
+    let selector = params.selector.unwrap_or_else(|| {
+        params
+            .selection
+            .selector(&core.retrieval_latency, &core.selector)
+    });
+    let chunks = chunks.with_selector(selector);
+    let chunks = match params.read_your_writes {
+        Some(config) => chunks.with_read_your_writes(config),
+        None => chunks,
+    };
 
     executor.spawn_service("swarm.client_service", core.client_service);
 
@@ -954,6 +1086,7 @@ mod tests {
             None,
             false,
             0,
+            0,
             true,
         )
         .expect("swap wiring is prepared for a chequebook on a named chain");
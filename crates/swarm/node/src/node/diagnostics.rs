@@ -0,0 +1,93 @@
+//! Diagnostic dump assembly over the live node subsystems.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use vertex_swarm_api::{
+    ConfigDiagnostics, DiagnosticDump, Ledger, LedgerDiagnostics, PeerDiagnostics,
+    StorageDiagnostics, SwarmDiagnostics, SwarmIdentity, SwarmTopologyPeers, TopologyDiagnostics,
+};
+use vertex_swarm_primitives::{Bin, SwarmAddressExt, all_bins};
+use vertex_swarm_topology::TopologyHandle;
+
+/// Reserve occupancy reader, erased so the storer cone stays out of this type.
+pub type StorageProbe = Arc<dyn Fn() -> StorageDiagnostics + Send + Sync>;
+
+/// [`SwarmDiagnostics`] source over a node's topology handle.
+///
+/// Peer scores come from the peer manager behind the topology. The ledger and
+/// storage sections are filled only when the node type wires them: bootnodes
+/// run no accounting and only storers hold a reserve.
+pub struct NodeDiagnostics<I: SwarmIdentity> {
+    topology: TopologyHandle<I>,
+    config: ConfigDiagnostics,
+    ledger: Option<Arc<dyn Ledger>>,
+    storage: Option<StorageProbe>,
+}
+
+impl<I: SwarmIdentity> NodeDiagnostics<I> {
+    /// Diagnostics over `topology` reporting `config`, with no ledger or
+    /// storage section until one is attached.
+    pub fn new(topology: TopologyHandle<I>, config: ConfigDiagnostics) -> Self {
+        Self {
+            topology,
+            config,
+            ledger: None,
+            storage: None,
+        }
+    }
+
+    /// Report per-peer balances from `ledger`.
+    pub fn with_ledger(mut self, ledger: Arc<dyn Ledger>) -> Self {
+        self.ledger = Some(ledger);
+        self
+    }
+
+    /// Report reserve occupancy from `probe`.
+    pub fn with_storage(mut self, probe: StorageProbe) -> Self {
+        self.storage = Some(probe);
+        self
+    }
+}
+
+impl<I: SwarmIdentity> SwarmDiagnostics for NodeDiagnostics<I> {
+    fn diagnostic_dump(&self) -> DiagnosticDump {
+        // One point-in-time copy of the peer set, so every row reads the
+        // same instant.
+        let states: HashMap<_, _> = self
+            .topology
+            .peer_manager()
+            .peer_states()
+            .into_iter()
+            .map(|state| (state.overlay, state))
+            .collect();
+        let mut peers = Vec::new();
+        let mut accounting = Vec::new();
+
+        for bin in all_bins(Bin::MAX) {
+            for (overlay, multiaddrs) in self.topology.connected_peer_details_in_bin(bin) {
+                if let Some(ledger) = &self.ledger {
+                    accounting.push(LedgerDiagnostics::new(&overlay, ledger.snapshot(&overlay)));
+                }
+                let state = states.get(&overlay);
+                peers.push(PeerDiagnostics {
+                    overlay: overlay.to_hex(),
+                    proximity_order: bin.get(),
+                    multiaddrs: multiaddrs.iter().map(ToString::to_string).collect(),
+                    score: state.map(|s| s.score),
+                    discovery_source: state
+                        .and_then(|s| s.discovery_source)
+                        .map(|source| source.to_string()),
+                });
+            }
+        }
+
+        DiagnosticDump {
+            topology: TopologyDiagnostics::collect(&self.topology),
+            peers,
+            accounting,
+            storage: self.storage.as_ref().map(|probe| probe()),
+            config: self.config.clone(),
+        }
+    }
+}
@@ -39,18 +39,13 @@ use super::core::{
 };
 #[cfg(feature = "swap")]
 use super::core::{ClientSwapParams, node_chain_provider};
-use crate::ClientHandle;
 use crate::inflight::PeerInflightLimiter;
+use crate::protocol::PricingAnnouncement;
+use crate::{ClientHandle, PeerSelector, ReadYourWrites, RetrievalSelection};
 
 /// Default connection idle timeout for a launched client.
 const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
 
-/// Default transport-layer cap on established connections.
-///
-/// A saturated routing table sits comfortably below this; it is a resource
-/// backstop, not a topology knob.
-const DEFAULT_MAX_PEERS: usize = 400;
-
 /// Network configuration assembled from the launcher's fields.
 ///
 /// A launched client is dial-only: it carries no listen addresses and leaves
@@ -60,7 +55,7 @@ struct LaunchNetworkConfig {
     bootnodes: Vec<Multiaddr>,
     peer: DefaultPeerConfig,
     routing: KademliaConfig,
-    max_peers: usize,
+    max_peers: Option<usize>,
     idle_timeout: Duration,
 }
 
@@ -77,7 +72,7 @@ impl SwarmNetworkConfig for LaunchNetworkConfig {
         true
     }
 
-    fn max_peers(&self) -> usize {
+    fn max_peers(&self) -> Option<usize> {
         self.max_peers
     }
 
@@ -186,7 +181,8 @@ pub struct ClientLauncher {
     bootnodes: Vec<Multiaddr>,
     kademlia: KademliaConfig,
     bandwidth: DefaultBandwidthConfig,
-    max_peers: usize,
+    /// Transport-layer connection cap. `None` keeps the client default.
+    max_peers: Option<usize>,
     idle_timeout: Duration,
     /// Byte budget for the default in-memory cache (ignored when a store is set).
     cache_budget_bytes: u64,
@@ -194,6 +190,12 @@ pub struct ClientLauncher {
     soc_cache_ttl_ns: u64,
     /// Caller-supplied client cache. `None` builds the default in-memory cache.
     store: Option<Arc<dyn SwarmLocalStore>>,
+    /// Built-in retrieval source selection, used when `selector` is unset.
+    selection: RetrievalSelection,
+    /// Retrieval source selection supplied in code. Overrides `selection`.
+    selector: Option<Arc<dyn PeerSelector>>,
+    /// Read-your-writes cache for uploaded chunks. `None` disables it.
+    read_your_writes: Option<ReadYourWrites>,
     /// SWAP settlement parameters. `None` keeps settlement pseudosettle-only.
     #[cfg(feature = "swap")]
     swap: Option<LauncherSwapConfig>,
@@ -208,11 +210,14 @@ impl ClientLauncher {
             bootnodes: Vec::new(),
             kademlia: KademliaConfig::default(),
             bandwidth: DefaultBandwidthConfig::default(),
-            max_peers: DEFAULT_MAX_PEERS,
+            max_peers: None,
             idle_timeout: DEFAULT_IDLE_TIMEOUT,
             cache_budget_bytes: DEFAULT_CACHE_BUDGET_BYTES,
             soc_cache_ttl_ns: DEFAULT_SOC_CACHE_TTL_NS,
             store: None,
+            selection: RetrievalSelection::default(),
+            selector: None,
+            read_your_writes: None,
             #[cfg(feature = "swap")]
             swap: None,
         }
@@ -254,10 +259,11 @@ impl ClientLauncher {
         self
     }
 
-    /// Set the transport-layer cap on established connections.
+    /// Set the transport-layer cap on established connections, overriding
+    /// the client default ([`SwarmNodeType::default_max_peers`]).
     #[must_use]
     pub fn with_max_peers(mut self, max: usize) -> Self {
-        self.max_peers = max;
+        self.max_peers = Some(max);
         self
     }
 
@@ -278,6 +284,31 @@ impl ClientLauncher {
         self
     }
 
+    /// Choose one of the built-in retrieval selectors. Defaults to
+    /// [`RetrievalSelection::Closest`].
+    #[must_use]
+    pub fn with_retrieval_selection(mut self, selection: RetrievalSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Serve chunks this client uploaded back from a short-lived local cache,
+    /// so it reads its own recent uploads before they propagate. Off by
+    /// default.
+    #[must_use]
+    pub fn with_read_your_writes(mut self, config: ReadYourWrites) -> Self {
+        self.read_your_writes = Some(config);
+        self
+    }
+
+    /// Choose which peers retrievals are requested from. Defaults to the
+    /// closest connected peers; see [`PeerSelector`] for the alternatives.
+    #[must_use]
+    pub fn with_peer_selector(mut self, selector: Arc<dyn PeerSelector>) -> Self {
+        self.selector = Some(selector);
+        self
+    }
+
     /// Enable SWAP cheque settlement on top of pseudosettle.
     ///
     /// Without this the launched client settles by pseudosettle only. With the
@@ -358,6 +389,8 @@ impl ClientLauncher {
         };
 
         // The launcher always builds a client, which paces against the scaled line.
+        // Peers are still told the unscaled thresholds.
+        let pricing = PricingAnnouncement::from_config(&self.bandwidth);
         let bandwidth = self.bandwidth.for_client();
 
         let tail_params = ClientTailParams {
@@ -365,6 +398,9 @@ impl ClientLauncher {
             spec: &spec,
             identity: &self.identity,
             bandwidth: &bandwidth,
+            selection: self.selection,
+            selector: self.selector,
+            read_your_writes: self.read_your_writes,
             #[cfg(feature = "swap")]
             swap: ClientSwapParams {
                 // An embedded client defaults SWAP off; `with_swap` turns it on.
@@ -374,6 +410,7 @@ impl ClientLauncher {
                 // The browser cannot deploy a chequebook.
                 deploy: false,
                 bounce_limit: self.swap.as_ref().map_or(0, |cfg| cfg.bounce_limit),
+                settlement_queue: 0,
             },
         };
 
@@ -395,6 +432,7 @@ impl ClientLauncher {
                 let node_builder = ClientNode::builder(identity)
                     .with_kademlia_config(kademlia)
                     .with_store(store_for_node)
+                    .with_pricing(pricing)
                     .with_pseudosettle_events(events.pseudosettle);
                 #[cfg(feature = "swap")]
                 let node_builder = match events.swap {
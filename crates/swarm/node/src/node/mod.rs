@@ -17,6 +17,7 @@ mod builder;
 #[allow(unreachable_pub)]
 mod client;
 mod core;
+mod diagnostics;
 mod error;
 mod launch;
 // NAT traversal and LAN discovery only exist natively. The browser client
@@ -43,6 +44,7 @@ pub use core::{
 };
 #[cfg(feature = "swap")]
 pub use core::{ClientSwapParams, NodeChainError, SwapWiring, node_chain_provider};
+pub use diagnostics::{NodeDiagnostics, StorageProbe};
 pub use error::NodeBuildError;
 #[cfg(feature = "swap")]
 pub use launch::LauncherSwapConfig;
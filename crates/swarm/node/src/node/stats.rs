@@ -1,12 +1,14 @@
 //! Node statistics configuration and reporting.
 //!
-//! Provides configuration and logging for node health and topology statistics.
+//! Provides configuration and logging for node health, topology and protocol
+//! latency statistics.
 //! The background task that drives periodic reporting lives in [`super::task`].
 
 use std::time::Duration;
 
-use tracing::info;
+use tracing::{debug, info};
 use vertex_swarm_api::{SwarmTopologyState, SwarmTopologyStats};
+use vertex_swarm_client_behaviour::LatencySnapshot;
 
 const DEFAULT_STATS_INTERVAL: Duration = Duration::from_secs(20);
 
@@ -36,6 +38,7 @@ pub(crate) fn log_stats<T: SwarmTopologyState + SwarmTopologyStats>(topology: &T
     let stored = topology.stored_peers_count();
     let depth = topology.depth();
     let pending = topology.pending_connections_count();
+    let churn = topology.churn_rate();
 
     let bin_sizes = topology.bin_sizes();
     let mut bin_summary = String::new();
@@ -62,11 +65,38 @@ pub(crate) fn log_stats<T: SwarmTopologyState + SwarmTopologyStats>(topology: &T
         stored,
         depth = depth.get(),
         pending,
+        churn_per_min = churn,
         bins = %bin_summary,
         "swarm status"
     );
 }
 
+/// Per-protocol latency percentiles, for protocols with samples.
+pub(crate) fn log_latency(snapshot: &LatencySnapshot) {
+    let protocols = [
+        ("retrieval", &snapshot.retrieval),
+        ("pushsync", &snapshot.pushsync),
+        ("pseudosettle", &snapshot.pseudosettle),
+    ];
+    for (protocol, histogram) in protocols {
+        if histogram.count > 0 {
+            debug!(
+                protocol,
+                count = histogram.count,
+                p50 = %percentile(histogram.p50),
+                p90 = %percentile(histogram.p90),
+                p99 = %percentile(histogram.p99),
+                "message latency"
+            );
+        }
+    }
+}
+
+/// A percentile past the last bucket has no upper bound to report.
+fn percentile(value: Option<Duration>) -> String {
+    value.map_or_else(|| "slow".to_string(), |d| format!("{d:?}"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,6 +108,17 @@ mod tests {
         assert_eq!(config.interval, Duration::from_secs(20));
     }
 
+    #[test]
+    fn test_log_latency_empty() {
+        log_latency(&LatencySnapshot::default());
+    }
+
+    #[test]
+    fn test_percentile_past_last_bucket() {
+        assert_eq!(percentile(None), "slow");
+        assert_eq!(percentile(Some(Duration::from_millis(25))), "25ms");
+    }
+
     #[test]
     fn test_log_stats_empty() {
         let topology = MockTopology::new(0, 0, 0).with_stored(0);
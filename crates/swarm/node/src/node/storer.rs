@@ -18,11 +18,11 @@ use nectar_primitives::SwarmAddress;
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 use vertex_swarm_api::{
-    PullStorage, SwarmIdentity, SwarmLocalStore, SwarmNetworkConfig, SwarmPeerConfig,
-    SwarmRoutingConfig,
+    NetworkIngest, PullStorage, SwarmIdentity, SwarmLocalStore, SwarmNetworkConfig,
+    SwarmPeerConfig, SwarmRoutingConfig, SwarmSpec,
 };
 use vertex_swarm_net_identify as identify;
-use vertex_swarm_primitives::Bin;
+use vertex_swarm_primitives::{Bin, NeighborhoodDepth, SwarmNodeType};
 use vertex_swarm_puller::{PullerHandle, PullsyncControl};
 use vertex_swarm_storer_behaviour::{
     PullsyncBehaviour, PullsyncEvent, StorerBehaviour, StorerBehaviourEvent,
@@ -38,8 +38,8 @@ use super::base::BaseNode;
 use super::builder::BuiltInfrastructure;
 use super::nat::{NatBehaviour, NatEvent};
 use crate::protocol::{
-    BehaviourConfig as ClientBehaviourConfig, ClientBehaviour, ClientCommand, ClientEvent,
-    PseudosettleEvent, StubForwarder,
+    ClientBehaviour, ClientCommand, ClientEvent, PricingAnnouncement, PseudosettleEvent,
+    StubForwarder,
 };
 use crate::{ClientHandle, ClientService};
 
@@ -56,6 +56,7 @@ enum PullsyncCommand {
         request_id: u64,
         bin: Bin,
         start: u64,
+        limit: u64,
     },
 }
 
@@ -77,7 +78,7 @@ impl PullsyncControl for StorerPullsyncControl {
         }
     }
 
-    fn sync_range(&self, peer: PeerId, request_id: u64, bin: Bin, start: u64) {
+    fn sync_range(&self, peer: PeerId, request_id: u64, bin: Bin, start: u64, limit: u64) {
         if self
             .command_tx
             .try_send(PullsyncCommand::SyncRange {
@@ -85,6 +86,7 @@ impl PullsyncControl for StorerPullsyncControl {
                 request_id,
                 bin,
                 start,
+                limit,
             })
             .is_err()
         {
@@ -110,6 +112,9 @@ pub(crate) struct StorerNodeBehaviour<I: SwarmIdentity + Clone> {
 }
 
 impl<I: SwarmIdentity + Clone> StorerNodeBehaviour<I> {
+    // Assembles already-built parts plus the network settings they read; a
+    // params struct would only rename the same fields.
+    #[allow(clippy::too_many_arguments)]
     fn from_parts(
         local_public_key: PublicKey,
         topology: TopologyBehaviour<I>,
@@ -117,11 +122,15 @@ impl<I: SwarmIdentity + Clone> StorerNodeBehaviour<I> {
         connection_limits: connection_limits::Behaviour,
         store: Arc<dyn SwarmLocalStore>,
         pullsync_storage: Arc<dyn PullStorage>,
+        pricing: PricingAnnouncement,
+        min_peers_to_serve: usize,
         agent_version: Option<&str>,
     ) -> Self {
         let agent_versions = topology.agent_versions();
+        let protocol_prefix = topology.protocol_prefix().to_owned();
+        let accord = topology.fork_condition(vertex_swarm_spec::SwarmHardfork::Accord);
         let client = ClientBehaviour::new(
-            ClientBehaviourConfig::default(),
+            super::builder::client_behaviour_config(&topology, pricing, min_peers_to_serve),
             store,
             Arc::new(StubForwarder),
         );
@@ -135,7 +144,9 @@ impl<I: SwarmIdentity + Clone> StorerNodeBehaviour<I> {
             topology,
             storer: StorerBehaviour {
                 client,
-                pullsync: PullsyncBehaviour::new(pullsync_storage),
+                pullsync: PullsyncBehaviour::new(pullsync_storage)
+                    .with_protocol_prefix(&protocol_prefix)
+                    .with_accord(accord),
             },
         }
     }
@@ -148,12 +159,14 @@ async fn build_storer_base<I, C>(
     network_config: &C,
     store: Arc<dyn SwarmLocalStore>,
     pullsync_storage: Arc<dyn PullStorage>,
+    pricing: PricingAnnouncement,
 ) -> Result<BaseNode<I, StorerNodeBehaviour<I>>>
 where
     I: SwarmIdentity + Clone,
     C: SwarmNetworkConfig,
 {
-    let connection_limits = super::base::build_connection_limits(network_config);
+    let connection_limits =
+        super::base::build_connection_limits(network_config, SwarmNodeType::Storer);
     super::builder::build_base_node(infra, network_config, "Storer node", move |pk, topology| {
         let nat = NatBehaviour::from_config(network_config, pk.to_peer_id());
         StorerNodeBehaviour::from_parts(
@@ -163,6 +176,8 @@ where
             connection_limits,
             store,
             pullsync_storage,
+            pricing,
+            network_config.min_peers_to_serve(),
             network_config.agent_version(),
         )
     })
@@ -288,10 +303,27 @@ impl<I: SwarmIdentity + Clone> StorerNode<I> {
 
     /// Install the storer ingest capability on the client sub-behaviour. See
     /// [`ClientNode::enable_storage`](super::ClientNode::enable_storage).
-    pub fn enable_storage(&mut self, reserve: Arc<dyn vertex_swarm_api::ReserveStore>) {
+    pub fn enable_storage(
+        &mut self,
+        reserve: Arc<dyn vertex_swarm_api::ReserveStore>,
+        verifier: Option<Arc<dyn vertex_swarm_api::StampVerifier>>,
+        validation_concurrency: usize,
+        refuse_out_of_range: bool,
+    ) {
         let signer: Arc<dyn vertex_swarm_primitives::OverlaySigner + Send + Sync> =
             Arc::new(self.base.identity().clone());
-        let capability = crate::protocol::StorerCapability::new(reserve, signer);
+        let policy = vertex_swarm_api::StampPolicy::for_spec(self.base.identity().spec());
+        let mut capability = crate::protocol::StorerCapability::new(reserve, signer)
+            .with_validation_concurrency(validation_concurrency)
+            .with_stamp_policy(policy)
+            .with_out_of_range_policy(if refuse_out_of_range {
+                crate::protocol::OutOfRangePolicy::Refuse
+            } else {
+                crate::protocol::OutOfRangePolicy::Forward
+            });
+        if let Some(verifier) = verifier {
+            capability = capability.with_stamp_verifier(verifier);
+        }
         self.base
             .swarm
             .behaviour_mut()
@@ -471,6 +503,7 @@ impl<I: SwarmIdentity + Clone> StorerNode<I> {
                 overlay,
                 peer_id,
                 node_type,
+                capabilities,
                 ..
             } => {
                 self.base.swarm.behaviour_mut().storer.client.on_command(
@@ -478,15 +511,32 @@ impl<I: SwarmIdentity + Clone> StorerNode<I> {
                         peer_id,
                         overlay,
                         node_type,
+                        capabilities,
                     },
                 );
             }
             TopologyEvent::PeerDisconnected { .. } => {}
             TopologyEvent::PeerRejected { .. } => {}
-            TopologyEvent::DepthChanged { .. } => {}
+            TopologyEvent::DepthChanged {
+                old_depth,
+                new_depth,
+            } => {
+                // A shallower depth widens the neighbourhood: pull the bins
+                // that just came into range.
+                if let (Some(puller), Some(old), Some(new)) = (
+                    &self.puller,
+                    Bin::new(old_depth).ok(),
+                    Bin::new(new_depth).ok(),
+                ) {
+                    puller.depth_changed(NeighborhoodDepth::new(old), NeighborhoodDepth::new(new));
+                }
+            }
             TopologyEvent::PhaseChanged { .. } => {}
             TopologyEvent::DialFailed { .. } => {}
             TopologyEvent::PingCompleted { .. } => {}
+            TopologyEvent::PingFailed { .. } => {}
+            TopologyEvent::ChurnExceeded { .. } => {}
+            TopologyEvent::NetworkPartitionSuspected { .. } => {}
         }
     }
 
@@ -547,7 +597,8 @@ impl<I: SwarmIdentity + Clone> StorerNode<I> {
                 request_id,
                 bin,
                 start,
-            } => pullsync.sync_range(peer, request_id, bin, start),
+                limit,
+            } => pullsync.sync_range(peer, request_id, bin, start, limit),
         }
     }
 
@@ -569,6 +620,7 @@ pub struct StorerNodeBuilder<I: SwarmIdentity + Clone> {
     kademlia_config: Option<KademliaConfig>,
     store: Option<Arc<dyn SwarmLocalStore>>,
     pullsync_storage: Option<Arc<dyn PullStorage>>,
+    pricing: Option<PricingAnnouncement>,
     pseudosettle_event_tx: Option<mpsc::UnboundedSender<PseudosettleEvent>>,
     #[cfg(feature = "swap")]
     swap_event_tx: Option<mpsc::UnboundedSender<crate::protocol::SwapEvent>>,
@@ -581,6 +633,7 @@ impl<I: SwarmIdentity + Clone> StorerNodeBuilder<I> {
             kademlia_config: None,
             store: None,
             pullsync_storage: None,
+            pricing: None,
             pseudosettle_event_tx: None,
             #[cfg(feature = "swap")]
             swap_event_tx: None,
@@ -604,6 +657,13 @@ impl<I: SwarmIdentity + Clone> StorerNodeBuilder<I> {
         self
     }
 
+    /// Payment thresholds announced to peers on activation. Defaults to the
+    /// default thresholds when unset.
+    pub fn with_pricing(mut self, pricing: PricingAnnouncement) -> Self {
+        self.pricing = Some(pricing);
+        self
+    }
+
     pub fn with_pseudosettle_events(
         mut self,
         tx: mpsc::UnboundedSender<PseudosettleEvent>,
@@ -661,8 +721,15 @@ impl<I: SwarmIdentity + Clone> StorerNodeBuilder<I> {
             ))
         });
 
-        let mut base =
-            build_storer_base(infra, network_config, Arc::clone(&store), pullsync_storage).await?;
+        let pricing = self.pricing.unwrap_or_else(super::builder::default_pricing);
+        let mut base = build_storer_base(
+            infra,
+            network_config,
+            Arc::clone(&store),
+            pullsync_storage,
+            pricing,
+        )
+        .await?;
 
         base.swarm
             .behaviour()
@@ -690,17 +757,20 @@ impl<I: SwarmIdentity + Clone> StorerNodeBuilder<I> {
         super::task::spawn_stats_task(
             Arc::new(base.topology_handle.clone()),
             Arc::clone(base.topology_handle.peer_manager().score_distribution()),
+            base.swarm.behaviour().storer.client.latency(),
             super::stats::StatsConfig::default(),
             &executor,
         );
 
-        let (command_tx, command_rx) =
-            mpsc::channel(crate::client_service::DEFAULT_CHANNEL_CAPACITY);
+        let (command_tx, command_rx) = mpsc::channel(network_config.command_channel_capacity());
         let (event_tx, event_rx) = mpsc::channel(crate::client_service::DEFAULT_CHANNEL_CAPACITY);
         let (pullsync_command_tx, pullsync_command_rx) = mpsc::channel(PULLSYNC_COMMAND_CAPACITY);
 
         let (client_service, client_handle) = ClientService::with_channels(command_tx, event_rx);
-        let client_service = client_service.with_store(store);
+        let client_service = client_service
+            .with_store(store)
+            .with_ingest(NetworkIngest::for_spec::<I::Spec>())
+            .with_min_payment_threshold(base.identity().spec().min_payment_threshold());
         let pullsync_control = StorerPullsyncControl {
             command_tx: pullsync_command_tx,
         };
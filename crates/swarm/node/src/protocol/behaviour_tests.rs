@@ -12,16 +12,17 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use alloy_primitives::{B256, Signature};
+use alloy_signer::SignerSync;
 use alloy_signer_local::PrivateKeySigner;
 use futures::StreamExt;
 use libp2p::Swarm;
 use libp2p_swarm_test::SwarmExt;
-use nectar_postage::Stamp;
+use nectar_postage::{Stamp, StampDigest, StampIndex};
 use nectar_primitives::{AnyChunk, ContentChunk, SingleOwnerChunk};
 use tokio::sync::oneshot;
 use vertex_swarm_api::SwarmLocalStore;
 use vertex_swarm_localstore::{ChunkStore, Clock};
-use vertex_swarm_primitives::{OverlayAddress, StampedChunk, SwarmNodeType};
+use vertex_swarm_primitives::{OverlayAddress, PeerCapabilities, StampedChunk, SwarmNodeType};
 
 use crate::ChunkTransferError;
 use crate::client_service::RetrievalResult;
@@ -36,13 +37,20 @@ impl Clock for FixedClock {
     }
 }
 
+/// Content chunk under a genuinely signed stamp, so it passes storer ingest
+/// validation.
 fn content_chunk(payload: &'static [u8]) -> StampedChunk {
-    let sig = Signature::from_raw(&[1u8; 65]).expect("valid signature");
-    let stamp = Stamp::new(B256::repeat_byte(0xaa), 3, 7, 42, sig);
     let chunk: AnyChunk = ContentChunk::new(payload)
         .expect("valid content chunk")
         .into();
-    StampedChunk::new(chunk, stamp)
+    let batch = B256::repeat_byte(0xaa);
+    let index = StampIndex::new(3, 7);
+    let prehash = StampDigest::new(*chunk.address(), batch, index, 42).to_prehash();
+    let sig = PrivateKeySigner::from_bytes(&B256::repeat_byte(0x33))
+        .expect("signer")
+        .sign_message_sync(prehash.as_slice())
+        .expect("stamp signature");
+    StampedChunk::new(chunk, Stamp::with_index(batch, index, 42, sig))
 }
 
 fn soc_chunk(payload: &'static [u8], stamp_ns: u64) -> StampedChunk {
@@ -76,6 +84,25 @@ async fn connect_and_activate(
     server: &mut Swarm<ClientBehaviour>,
     client_overlay: OverlayAddress,
     server_overlay: OverlayAddress,
+) {
+    connect_and_activate_with(
+        client,
+        server,
+        client_overlay,
+        server_overlay,
+        PeerCapabilities::NONE,
+    )
+    .await;
+}
+
+/// [`connect_and_activate`] with `capabilities` as the handshake outcome on
+/// both ends.
+async fn connect_and_activate_with(
+    client: &mut Swarm<ClientBehaviour>,
+    server: &mut Swarm<ClientBehaviour>,
+    client_overlay: OverlayAddress,
+    server_overlay: OverlayAddress,
+    capabilities: PeerCapabilities,
 ) {
     let client_peer = *client.local_peer_id();
     let server_peer = *server.local_peer_id();
@@ -89,6 +116,7 @@ async fn connect_and_activate(
             peer_id: server_peer,
             overlay: server_overlay,
             node_type: SwarmNodeType::Client,
+            capabilities,
         });
     server
         .behaviour_mut()
@@ -96,6 +124,7 @@ async fn connect_and_activate(
             peer_id: client_peer,
             overlay: client_overlay,
             node_type: SwarmNodeType::Client,
+            capabilities,
         });
 }
 
@@ -141,6 +170,8 @@ async fn serves_a_content_chunk_from_the_cache() {
             address,
             response: tx,
             originated: true,
+            ttl: None,
+            deadline: None,
         });
 
     let result = drive_until_retrieved(&mut client, &mut server, rx).await;
@@ -149,6 +180,46 @@ async fn serves_a_content_chunk_from_the_cache() {
     assert_eq!(delivered.chunk, *chunk.chunk());
 }
 
+#[tokio::test]
+async fn compression_capable_peers_retrieve_a_large_chunk() {
+    let chunk = content_chunk(&[0x5a; 4096]);
+    let address = *chunk.address();
+
+    let server_store: Arc<dyn SwarmLocalStore> =
+        Arc::new(ChunkStore::with_budget(1 << 20, 1_000_000_000));
+    server_store.put(chunk.clone().into()).unwrap();
+
+    let mut client = swarm_with_store(Arc::new(ChunkStore::with_budget(1 << 20, 1_000)));
+    let mut server = swarm_with_store(server_store);
+
+    let server_overlay = overlay(2);
+    connect_and_activate_with(
+        &mut client,
+        &mut server,
+        overlay(1),
+        server_overlay,
+        PeerCapabilities::COMPRESSION,
+    )
+    .await;
+
+    let (tx, rx) = oneshot::channel();
+    client
+        .behaviour_mut()
+        .on_command(ClientCommand::RetrieveChunk {
+            peer: server_overlay,
+            address,
+            response: tx,
+            originated: true,
+            ttl: None,
+            deadline: None,
+        });
+
+    let delivered = drive_until_retrieved(&mut client, &mut server, rx)
+        .await
+        .expect("served compressed from cache");
+    assert_eq!(delivered.chunk, *chunk.chunk());
+}
+
 #[tokio::test]
 async fn serves_a_fresh_soc_from_the_cache() {
     // SOC stamped at 900ns, served at 1000ns under a 500ns TTL: still fresh.
@@ -176,6 +247,8 @@ async fn serves_a_fresh_soc_from_the_cache() {
             address,
             response: tx,
             originated: true,
+            ttl: None,
+            deadline: None,
         });
 
     let delivered = drive_until_retrieved(&mut client, &mut server, rx)
@@ -213,6 +286,8 @@ async fn expired_soc_is_not_served_and_resets() {
             address,
             response: tx,
             originated: true,
+            ttl: None,
+            deadline: None,
         });
 
     let result = drive_until_retrieved(&mut client, &mut server, rx).await;
@@ -242,6 +317,8 @@ async fn cache_miss_resets_with_stub_forwarder() {
             address,
             response: tx,
             originated: true,
+            ttl: None,
+            deadline: None,
         });
 
     let result = drive_until_retrieved(&mut client, &mut server, rx).await;
@@ -251,6 +328,56 @@ async fn cache_miss_resets_with_stub_forwarder() {
     );
 }
 
+#[tokio::test]
+async fn under_connected_server_serves_hits_but_refuses_to_forward_misses() {
+    let chunk = content_chunk(b"gated serve");
+    let address = *chunk.address();
+    let missing = *content_chunk(b"never cached").address();
+
+    let server_store: Arc<dyn SwarmLocalStore> =
+        Arc::new(ChunkStore::with_budget(1 << 20, 1_000_000_000));
+    server_store.put(chunk.clone().into()).unwrap();
+
+    let mut client = swarm_with_store(Arc::new(ChunkStore::with_budget(1 << 20, 1_000)));
+    let mut server = Swarm::new_ephemeral_tokio(move |_| {
+        ClientBehaviour::new(
+            Config::for_role(SwarmNodeType::Client).with_min_peers_to_serve(2),
+            server_store,
+            Arc::new(StubForwarder),
+        )
+    });
+
+    let server_overlay = overlay(2);
+    connect_and_activate(&mut client, &mut server, overlay(1), server_overlay).await;
+
+    let retrieve = |client: &mut Swarm<ClientBehaviour>, address| {
+        let (tx, rx) = oneshot::channel();
+        client
+            .behaviour_mut()
+            .on_command(ClientCommand::RetrieveChunk {
+                peer: server_overlay,
+                address,
+                response: tx,
+                originated: true,
+                ttl: None,
+                deadline: None,
+            });
+        rx
+    };
+
+    // One activated peer, two required: a cached chunk is still served.
+    let rx = retrieve(&mut client, address);
+    let delivered = drive_until_retrieved(&mut client, &mut server, rx)
+        .await
+        .expect("a local hit is served regardless of the gate");
+    assert_eq!(delivered.chunk, *chunk.chunk());
+
+    // A miss would need forwarding, which the gate refuses.
+    let rx = retrieve(&mut client, missing);
+    let result = drive_until_retrieved(&mut client, &mut server, rx).await;
+    assert!(result.is_err(), "an under-connected node must not forward");
+}
+
 #[tokio::test]
 async fn inbound_pushsync_resets_with_stub_forwarder() {
     // A cache-only client never takes custody: inbound pushsync forwards, the
@@ -678,6 +805,8 @@ async fn three_node_retrieval_relays_verifies_and_accounts() {
         address,
         response: tx,
         originated: true,
+        ttl: None,
+        deadline: None,
     });
 
     // B's forwarder commands are pumped back into B.
@@ -771,6 +900,8 @@ async fn relay_does_not_cache_a_forwarded_soc() {
         address,
         response: tx,
         originated: true,
+        ttl: None,
+        deadline: None,
     });
 
     let result = {
@@ -835,6 +966,8 @@ async fn relay_without_strictly_closer_peer_resets_rather_than_looping() {
         address,
         response: tx,
         originated: true,
+        ttl: None,
+        deadline: None,
     });
 
     let result = {
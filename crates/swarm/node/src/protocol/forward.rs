@@ -38,6 +38,8 @@ use vertex_swarm_client_behaviour::{
 use vertex_swarm_net_pushsync::{DepthVerdict, Receipt};
 use vertex_swarm_primitives::{NeighborhoodDepth, OverlayAddress, StampedChunk};
 
+use vertex_tasks::time::{Duration, Instant};
+
 use crate::{ClientHandle, RetrievalResult};
 
 /// Report source for shallow/malformed receipts caught on the relay path.
@@ -132,6 +134,10 @@ trait RelayOp: Send + Sync {
 struct RetrieveRelay {
     handle: ClientHandle,
     address: ChunkAddress,
+    /// Hops left on the relayed request.
+    ttl: u8,
+    /// When the requester stops waiting, if it said.
+    expires_at: Option<Instant>,
 }
 
 impl RelayOp for RetrieveRelay {
@@ -139,9 +145,25 @@ impl RelayOp for RetrieveRelay {
 
     async fn attempt(&self, closer: OverlayAddress) -> Result<RetrievalResult, ForwardError> {
         let address = self.address;
+        // Each leg carries only what is left of the requester's budget; with
+        // nothing left there is no one to deliver to.
+        let deadline = match self.expires_at {
+            Some(at) => {
+                let left = at.saturating_duration_since(Instant::now());
+                if left.is_zero() {
+                    return Err(ForwardError::DeadlineExceeded);
+                }
+                Some(left)
+            }
+            None => None,
+        };
         // `originated = false`: a relay leg, debited by the walk, so the
         // service must not debit the completion event.
-        match self.handle.retrieve_chunk(closer, address, false).await {
+        match self
+            .handle
+            .retrieve_chunk_with_budget(closer, address, false, Some(self.ttl), deadline)
+            .await
+        {
             // Edge verification: the relayed chunk must answer the requested
             // address before we account, cache, or relay it. The chunk is
             // address-derived (BMT hash or owner plus signature), so equality
@@ -293,6 +315,8 @@ where
                 receive.apply();
                 return Ok((output, provide));
             }
+            // The requester gave up; the next candidate would be wasted.
+            Err(err @ ForwardError::DeadlineExceeded) => return Err(err),
             Err(err) => {
                 // Release the downstream reservation; the upstream `provide`
                 // stays held for the next candidate.
@@ -316,12 +340,16 @@ where
         &self,
         address: ChunkAddress,
         exclude: OverlayAddress,
+        ttl: u8,
+        deadline: Option<Duration>,
     ) -> BoxFuture<'static, Result<ForwardedChunk, ForwardError>> {
         let candidates = closer_candidates(&*self.topology, &address, exclude, self.local);
         let accounting = Arc::clone(&self.accounting);
         let op = RetrieveRelay {
             handle: self.handle.clone(),
             address,
+            ttl,
+            expires_at: deadline.map(|deadline| Instant::now() + deadline),
         };
 
         Box::pin(async move {
@@ -564,15 +592,22 @@ mod tests {
         let (chunk_for_answer, stamp_for_answer) = chunk.clone().into_parts();
         let got = drive_one_command(
             rx,
-            forwarder.retrieve(address, requester),
+            forwarder.retrieve(address, requester, 3, Some(Duration::from_secs(5))),
             move |cmd| match cmd {
                 ClientCommand::RetrieveChunk {
                     peer,
                     address: requested,
                     response,
                     originated,
+                    ttl,
+                    deadline,
                 } => {
                     assert!(!originated, "a relay leg is never an origin request");
+                    assert_eq!(ttl, Some(3), "the relayed request carries the spent TTL");
+                    assert!(
+                        deadline.is_some_and(|left| left <= Duration::from_secs(5)),
+                        "the relayed request carries what is left of the budget"
+                    );
                     assert_eq!(peer, closer, "the upstream leg targets the closer peer");
                     assert_eq!(requested, address);
                     response
@@ -648,25 +683,24 @@ mod tests {
         );
 
         let (chunk_for_answer, stamp_for_answer) = chunk.clone().into_parts();
-        let forwarded =
-            drive_one_command(
-                rx,
-                forwarder.retrieve(address, requester),
-                move |cmd| match cmd {
-                    ClientCommand::RetrieveChunk { response, .. } => {
-                        response
-                            .send(Ok(RetrievalResult {
-                                chunk: chunk_for_answer,
-                                stamp: Some(stamp_for_answer),
-                                peer: closer,
-                            }))
-                            .expect("receiver alive");
-                    }
-                    other => panic!("unexpected command: {other:?}"),
-                },
-            )
-            .await
-            .expect("relay succeeds");
+        let forwarded = drive_one_command(
+            rx,
+            forwarder.retrieve(address, requester, 3, None),
+            move |cmd| match cmd {
+                ClientCommand::RetrieveChunk { response, .. } => {
+                    response
+                        .send(Ok(RetrievalResult {
+                            chunk: chunk_for_answer,
+                            stamp: Some(stamp_for_answer),
+                            peer: closer,
+                        }))
+                        .expect("receiver alive");
+                }
+                other => panic!("unexpected command: {other:?}"),
+            },
+        )
+        .await
+        .expect("relay succeeds");
 
         // Simulate the handler's wire-write failure: drop the provide action.
         drop(forwarded.provide);
@@ -1035,7 +1069,7 @@ mod tests {
             Arc::new(RecordingReporter::default()) as Arc<dyn PeerReporter>,
         );
         let err = forwarder
-            .retrieve(address, requester)
+            .retrieve(address, requester, 3, None)
             .await
             .expect_err("no strictly-closer peer");
         assert!(matches!(err, ForwardError::NoCloserPeer));
@@ -1067,20 +1101,21 @@ mod tests {
         );
 
         // The upstream peer reports a failure: no chunk comes back.
-        let err = drive_one_command(
-            rx,
-            forwarder.retrieve(address, requester),
-            |cmd| match cmd {
-                ClientCommand::RetrieveChunk { response, .. } => {
-                    response
-                        .send(Err(crate::ChunkTransferError::Remote))
-                        .expect("receiver alive");
-                }
-                other => panic!("unexpected command: {other:?}"),
-            },
-        )
-        .await
-        .expect_err("relay fails when the upstream leg fails");
+        let err =
+            drive_one_command(
+                rx,
+                forwarder.retrieve(address, requester, 3, None),
+                |cmd| match cmd {
+                    ClientCommand::RetrieveChunk { response, .. } => {
+                        response
+                            .send(Err(crate::ChunkTransferError::Remote))
+                            .expect("receiver alive");
+                    }
+                    other => panic!("unexpected command: {other:?}"),
+                },
+            )
+            .await
+            .expect_err("relay fails when the upstream leg fails");
         assert!(matches!(err, ForwardError::AllPeersFailed));
 
         // Both reservations were released on drop: balances are untouched.
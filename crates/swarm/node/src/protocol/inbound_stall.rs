@@ -0,0 +1,291 @@
+//! Repro for the inbound substream liveness invariant.
+//!
+//! A peer can open a retrieval substream, negotiate the protocol id, write a
+//! fragment of its request and then go silent. Without a per-protocol inbound
+//! deadline the serving handler would hold that substream and its read buffer
+//! until the connection closed. This module drives that scenario through
+//! `libp2p-swarm-test`: a real [`ClientBehaviour`] server against a dialer that
+//! writes a partial length-prefixed frame and stalls.
+//!
+//! The gate: the server drops the substream within `inbound_retrieval_timeout`
+//! (the stalling dialer observes its stream close) and reports the peer with
+//! [`ClientEvent::InboundStalled`], which the client service scores.
+
+use std::io;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use futures::future::BoxFuture;
+use futures::{AsyncReadExt, AsyncWriteExt, StreamExt};
+use libp2p::core::transport::PortUse;
+use libp2p::core::{Endpoint, Multiaddr, UpgradeInfo};
+use libp2p::swarm::{
+    ConnectionDenied, ConnectionHandler, ConnectionHandlerEvent, ConnectionId, FromSwarm,
+    NetworkBehaviour, NotifyHandler, SubstreamProtocol, SwarmEvent, THandler, THandlerInEvent,
+    THandlerOutEvent, ToSwarm,
+    handler::{ConnectionEvent, FullyNegotiatedOutbound},
+};
+use libp2p::{OutboundUpgrade, PeerId, Stream, Swarm};
+use libp2p_swarm_test::SwarmExt;
+use vertex_swarm_api::SwarmLocalStore;
+use vertex_swarm_localstore::ChunkStore;
+use vertex_swarm_net_retrieval::PROTOCOL_NAME as RETRIEVAL_PROTOCOL;
+use vertex_swarm_primitives::{OverlayAddress, PeerCapabilities, SwarmNodeType};
+
+use crate::protocol::{
+    BehaviourConfig, ClientBehaviour, ClientCommand, ClientEvent, StubForwarder,
+};
+
+/// Negotiates the retrieval protocol id, writes a length prefix announcing a
+/// 64-byte frame followed by only two bytes of it, then waits for the remote to
+/// drop the substream.
+struct StallingUpgrade;
+
+impl UpgradeInfo for StallingUpgrade {
+    type Info = &'static str;
+    type InfoIter = std::iter::Once<Self::Info>;
+
+    fn protocol_info(&self) -> Self::InfoIter {
+        std::iter::once(RETRIEVAL_PROTOCOL)
+    }
+}
+
+impl OutboundUpgrade<Stream> for StallingUpgrade {
+    type Output = ();
+    type Error = io::Error;
+    type Future = BoxFuture<'static, Result<(), io::Error>>;
+
+    fn upgrade_outbound(self, mut socket: Stream, _info: Self::Info) -> Self::Future {
+        Box::pin(async move {
+            socket.write_all(&[0x40, 0x0a, 0x0b]).await?;
+            socket.flush().await?;
+            // Never write the rest. Resolve once the server aborts the
+            // substream: a clean close reads zero, a reset reads an error.
+            let mut buf = [0u8; 64];
+            loop {
+                match socket.read(&mut buf).await {
+                    Ok(0) | Err(_) => return Ok(()),
+                    Ok(_) => {}
+                }
+            }
+        })
+    }
+}
+
+/// A connection handler that opens one stalling retrieval substream on request
+/// and reports when the server has aborted it.
+#[derive(Default)]
+struct StallingHandler {
+    open: bool,
+    aborted: bool,
+}
+
+impl ConnectionHandler for StallingHandler {
+    type FromBehaviour = ();
+    type ToBehaviour = ();
+    type InboundProtocol = libp2p::core::upgrade::DeniedUpgrade;
+    type OutboundProtocol = StallingUpgrade;
+    type InboundOpenInfo = ();
+    type OutboundOpenInfo = ();
+
+    fn listen_protocol(&self) -> SubstreamProtocol<Self::InboundProtocol, Self::InboundOpenInfo> {
+        SubstreamProtocol::new(libp2p::core::upgrade::DeniedUpgrade, ())
+    }
+
+    fn poll(
+        &mut self,
+        _cx: &mut Context<'_>,
+    ) -> Poll<
+        ConnectionHandlerEvent<Self::OutboundProtocol, Self::OutboundOpenInfo, Self::ToBehaviour>,
+    > {
+        if std::mem::take(&mut self.open) {
+            // Far above the server's deadline: the dialer must not be the one
+            // to give up on the substream.
+            return Poll::Ready(ConnectionHandlerEvent::OutboundSubstreamRequest {
+                protocol: SubstreamProtocol::new(StallingUpgrade, ())
+                    .with_timeout(Duration::from_secs(60)),
+            });
+        }
+        if std::mem::take(&mut self.aborted) {
+            return Poll::Ready(ConnectionHandlerEvent::NotifyBehaviour(()));
+        }
+        Poll::Pending
+    }
+
+    fn on_behaviour_event(&mut self, _event: Self::FromBehaviour) {
+        self.open = true;
+    }
+
+    fn on_connection_event(
+        &mut self,
+        event: ConnectionEvent<
+            Self::InboundProtocol,
+            Self::OutboundProtocol,
+            Self::InboundOpenInfo,
+            Self::OutboundOpenInfo,
+        >,
+    ) {
+        if let ConnectionEvent::FullyNegotiatedOutbound(FullyNegotiatedOutbound { .. }) = event {
+            self.aborted = true;
+        }
+    }
+}
+
+/// A behaviour that installs a [`StallingHandler`] on every connection.
+#[derive(Default)]
+struct StallingBehaviour {
+    pending: Option<PeerId>,
+    aborted: bool,
+}
+
+impl StallingBehaviour {
+    fn open_stalled_retrieval(&mut self, peer: PeerId) {
+        self.pending = Some(peer);
+    }
+}
+
+impl NetworkBehaviour for StallingBehaviour {
+    type ConnectionHandler = StallingHandler;
+    type ToSwarm = ();
+
+    fn handle_established_inbound_connection(
+        &mut self,
+        _connection_id: ConnectionId,
+        _peer: PeerId,
+        _local_addr: &Multiaddr,
+        _remote_addr: &Multiaddr,
+    ) -> Result<THandler<Self>, ConnectionDenied> {
+        Ok(StallingHandler::default())
+    }
+
+    fn handle_established_outbound_connection(
+        &mut self,
+        _connection_id: ConnectionId,
+        _peer: PeerId,
+        _addr: &Multiaddr,
+        _role_override: Endpoint,
+        _port_use: PortUse,
+    ) -> Result<THandler<Self>, ConnectionDenied> {
+        Ok(StallingHandler::default())
+    }
+
+    fn on_swarm_event(&mut self, _event: FromSwarm<'_>) {}
+
+    fn on_connection_handler_event(
+        &mut self,
+        _peer_id: PeerId,
+        _connection_id: ConnectionId,
+        _event: THandlerOutEvent<Self>,
+    ) {
+        self.aborted = true;
+    }
+
+    fn poll(
+        &mut self,
+        _cx: &mut Context<'_>,
+    ) -> Poll<ToSwarm<Self::ToSwarm, THandlerInEvent<Self>>> {
+        if let Some(peer_id) = self.pending.take() {
+            return Poll::Ready(ToSwarm::NotifyHandler {
+                peer_id,
+                handler: NotifyHandler::Any,
+                event: (),
+            });
+        }
+        if std::mem::take(&mut self.aborted) {
+            return Poll::Ready(ToSwarm::GenerateEvent(()));
+        }
+        Poll::Pending
+    }
+}
+
+/// Build a serving `ClientBehaviour` with a short inbound retrieval deadline so
+/// the stall is bounded in milliseconds, not the 30s default.
+fn server_with_inbound_retrieval_timeout(timeout: Duration) -> Swarm<ClientBehaviour> {
+    Swarm::new_ephemeral_tokio(move |_| {
+        let mut config = BehaviourConfig::for_role(SwarmNodeType::Client);
+        config.handler.inbound_retrieval_timeout = timeout;
+        let store: Arc<dyn SwarmLocalStore> = Arc::new(ChunkStore::with_budget(1 << 20, 1_000));
+        ClientBehaviour::new(config, store, Arc::new(StubForwarder))
+    })
+}
+
+#[tokio::test]
+async fn stalled_inbound_retrieval_is_aborted_and_reported() {
+    let deadline = Duration::from_millis(200);
+
+    let mut server = server_with_inbound_retrieval_timeout(deadline);
+    let mut dialer = Swarm::new_ephemeral_tokio(|_| StallingBehaviour::default());
+
+    let server_peer = *server.local_peer_id();
+    let dialer_peer = *dialer.local_peer_id();
+    let dialer_overlay = OverlayAddress::from([0x3c; 32]);
+
+    server.listen().with_memory_addr_external().await;
+    dialer.listen().with_memory_addr_external().await;
+    dialer.connect(&mut server).await;
+
+    // The server only advertises retrieval once the peer is activated, so wait
+    // for activation before the dialer opens its substream.
+    server
+        .behaviour_mut()
+        .on_command(ClientCommand::ActivatePeer {
+            peer_id: dialer_peer,
+            overlay: dialer_overlay,
+            node_type: SwarmNodeType::Client,
+            capabilities: PeerCapabilities::NONE,
+        });
+    let activate = async {
+        loop {
+            tokio::select! {
+                event = server.select_next_some() => {
+                    if let SwarmEvent::Behaviour(ClientEvent::PeerActivated { .. }) = event {
+                        return;
+                    }
+                }
+                _ = dialer.select_next_some() => {}
+            }
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(5), activate)
+        .await
+        .expect("server activates the dialer");
+
+    dialer.behaviour_mut().open_stalled_retrieval(server_peer);
+
+    let start = Instant::now();
+    let (mut stalled, mut aborted) = (None, false);
+    let drive = async {
+        while stalled.is_none() || !aborted {
+            tokio::select! {
+                event = server.select_next_some() => {
+                    if let SwarmEvent::Behaviour(ClientEvent::InboundStalled { peer, protocol }) = event {
+                        stalled = Some((peer, protocol));
+                    }
+                }
+                event = dialer.select_next_some() => {
+                    if let SwarmEvent::Behaviour(()) = event {
+                        aborted = true;
+                    }
+                }
+            }
+        }
+    };
+
+    // A wall-clock guard far below the 30s default but generous over the 200ms
+    // deadline: an unbounded read would trip this guard instead.
+    tokio::time::timeout(Duration::from_secs(5), drive)
+        .await
+        .expect("the stalled substream must be aborted via its inbound deadline");
+    let elapsed = start.elapsed();
+
+    assert_eq!(
+        stalled,
+        Some((dialer_overlay, RETRIEVAL_PROTOCOL)),
+        "the stalling peer must be reported against the retrieval protocol"
+    );
+    assert!(
+        elapsed >= deadline,
+        "the substream was aborted before its deadline ({elapsed:?})"
+    );
+}
@@ -13,11 +13,14 @@ mod forward;
 #[cfg(test)]
 mod behaviour_tests;
 #[cfg(test)]
+mod inbound_stall;
+#[cfg(test)]
 mod timeout_repro;
 
 pub(crate) use forward::NetworkForwarder;
 pub(crate) use vertex_swarm_client_behaviour::{
-    BehaviourConfig, ClientBehaviour, StorerCapability, StubForwarder,
+    BehaviourConfig, ClientBehaviour, OutOfRangePolicy, PricingAnnouncement, StorerCapability,
+    StubForwarder,
 };
 
 #[cfg(feature = "swap")]
@@ -31,8 +31,9 @@ use libp2p::swarm::{
 use libp2p_swarm_test::SwarmExt;
 use nectar_primitives::ChunkAddress;
 use tokio::sync::oneshot;
+use vertex_net_codec::Compression;
 use vertex_swarm_localstore::ChunkStore;
-use vertex_swarm_primitives::{OverlayAddress, SwarmNodeType};
+use vertex_swarm_primitives::{OverlayAddress, PeerCapabilities, SwarmNodeType};
 
 use crate::ChunkTransferError;
 use crate::client_service::RetrievalResult;
@@ -62,7 +63,7 @@ impl ConnectionHandler for WithholdingHandler {
         // No timeout on the inbound side: the server intends to stall, so it must
         // not be the one to drop the substream. The requester's outbound deadline
         // is what bounds the exchange.
-        SubstreamProtocol::new(inbound(), ())
+        SubstreamProtocol::new(inbound(Compression::None), ())
     }
 
     fn poll(
@@ -179,6 +180,7 @@ async fn withholding_peer_resolves_as_timed_out_within_the_deadline() {
             peer_id: server_peer,
             overlay: server_overlay,
             node_type: SwarmNodeType::Client,
+            capabilities: PeerCapabilities::NONE,
         });
 
     let address = ChunkAddress::new([0x11; 32]);
@@ -190,6 +192,8 @@ async fn withholding_peer_resolves_as_timed_out_within_the_deadline() {
             address,
             response: tx,
             originated: true,
+            ttl: None,
+            deadline: None,
         });
 
     let start = Instant::now();
@@ -1,6 +1,6 @@
 //! Score- and credit-aware peer selection for retrieval and pushsync.
 //!
-//! Topology returns candidate storers in proximity order. [`PeerSelector`]
+//! Topology returns candidate storers in proximity order. [`ScoreSelector`]
 //! reorders them with three additional signals before a request goes out. The
 //! reorder is a pure function: the selector never triggers settlement. A request
 //! settles the one peer it actually dispatches to, at the origin credit gate, so
@@ -30,6 +30,12 @@
 //! The price consulted per candidate is [`SwarmPricing::peer_price`], the same
 //! per-peer chunk price the accounting layer debits when the request is
 //! served.
+//!
+//! Which peers enter the retrieval window at all is a separate, pluggable
+//! policy: [`PeerSelector`] draws the candidate slice from the topology. The
+//! default [`ClosestSelector`] is plain proximity; [`LatencySelector`] and
+//! [`ScoreSelector`] are the alternatives the node can be configured with,
+//! named by [`RetrievalSelection`].
 
 use std::collections::HashSet;
 use std::sync::Arc;
@@ -40,12 +46,14 @@ use rustc_hash::FxBuildHasher;
 use tracing::debug;
 use vertex_swarm_api::{
     Admission, AdmissionControl, DEFAULT_PEER_WARN_THRESHOLD, SwarmBandwidthAccounting,
-    SwarmIdentity, SwarmPeerBandwidth, SwarmPricing,
+    SwarmIdentity, SwarmPeerBandwidth, SwarmPricing, SwarmTopologyRouting,
 };
 use vertex_swarm_primitives::OverlayAddress;
 use vertex_swarm_topology::TopologyHandle;
 use vertex_tasks::TaskExecutor;
 
+use crate::RetrievalLatency;
+
 /// Per-peer in-flight settle set: a peer is present while a settle to it is
 /// running, so a second trigger for it is deduped (the per-peer rate limit, the
 /// next settle cannot start until the prior one clears). Overlay keys are
@@ -149,19 +157,110 @@ where
     }
 }
 
+/// Retrieval source-selection policy: which peers a chunk is requested from,
+/// and in what order.
+///
+/// The dispatch engine asks it for each candidate slice it races (the close set
+/// and the wider spill ring); the accounting band and in-flight cap are applied
+/// on top of whatever it returns. Within one retrieval the engine asks through
+/// [`select_excluding`](Self::select_excluding), so a peer that already failed
+/// the request is not picked again.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait PeerSelector: Send + Sync {
+    /// Up to `count` peers to request `address` from, best first.
+    fn select(
+        &self,
+        address: &ChunkAddress,
+        topology: &dyn SwarmTopologyRouting,
+        count: usize,
+    ) -> Vec<OverlayAddress>;
+
+    /// Up to `count` peers as [`select`](Self::select) picks them, skipping
+    /// `exclude`: the peers that already failed the current request.
+    ///
+    /// The default widens the selection by the size of `exclude` and filters
+    /// it, so the result still fills `count` when enough other peers exist.
+    fn select_excluding(
+        &self,
+        address: &ChunkAddress,
+        topology: &dyn SwarmTopologyRouting,
+        count: usize,
+        exclude: &HashSet<OverlayAddress>,
+    ) -> Vec<OverlayAddress> {
+        if exclude.is_empty() {
+            return self.select(address, topology, count);
+        }
+        self.select(address, topology, count.saturating_add(exclude.len()))
+            .into_iter()
+            .filter(|peer| !exclude.contains(peer))
+            .take(count)
+            .collect()
+    }
+}
+
+/// The default [`PeerSelector`]: the `count` connected peers closest to the
+/// chunk, in proximity order.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ClosestSelector;
+
+impl PeerSelector for ClosestSelector {
+    fn select(
+        &self,
+        address: &ChunkAddress,
+        topology: &dyn SwarmTopologyRouting,
+        count: usize,
+    ) -> Vec<OverlayAddress> {
+        topology.closest_to(address, count)
+    }
+}
+
+/// [`PeerSelector`] that favours the forwarding distance with the lowest
+/// observed retrieval latency.
+///
+/// Takes the closest `count` peers and stably sorts them by the per-PO
+/// estimate at `PO(peer, chunk)`. Unsampled distances sort last, so a cold
+/// estimate leaves the proximity order unchanged.
+#[derive(Clone, Debug)]
+pub struct LatencySelector {
+    latency: Arc<RetrievalLatency>,
+}
+
+impl LatencySelector {
+    /// Select against the estimate the client service records into.
+    pub fn new(latency: Arc<RetrievalLatency>) -> Self {
+        Self { latency }
+    }
+}
+
+impl PeerSelector for LatencySelector {
+    fn select(
+        &self,
+        address: &ChunkAddress,
+        topology: &dyn SwarmTopologyRouting,
+        count: usize,
+    ) -> Vec<OverlayAddress> {
+        let mut candidates = topology.closest_to(address, count);
+        candidates.sort_by_key(|peer| {
+            let estimate = self.latency.estimate(address.proximity(peer).get());
+            (estimate.is_none(), estimate)
+        });
+        candidates
+    }
+}
+
 /// Reorders proximity-ordered candidates by the admission band and score.
 ///
 /// Built by the node assembly from the topology handle (scores), bandwidth
 /// accounting (the admission band and settlement), and the pricer (per-peer
 /// chunk price). Consumed by the retrieval and pushsync candidate-selection
 /// paths.
-pub struct PeerSelector {
+pub struct ScoreSelector {
     scores: Arc<dyn PeerScores>,
     admission: Arc<dyn AdmissionControl>,
     pricing: Arc<dyn SwarmPricing>,
 }
 
-impl PeerSelector {
+impl ScoreSelector {
     /// Compose a selector from its query surfaces.
     ///
     /// Ranking only: the selector never triggers settlement. A request settles
@@ -238,6 +337,54 @@ impl PeerSelector {
     }
 }
 
+impl PeerSelector for ScoreSelector {
+    /// The closest `count` peers, reordered by the admission band and score.
+    fn select(
+        &self,
+        address: &ChunkAddress,
+        topology: &dyn SwarmTopologyRouting,
+        count: usize,
+    ) -> Vec<OverlayAddress> {
+        self.order(topology.closest_to(address, count), address)
+    }
+}
+
+/// Which built-in [`PeerSelector`] draws the retrieval window.
+///
+/// The node resolves the variant against its own latency estimate and score
+/// selector when it assembles the chunk provider (CLI:
+/// `--network.retrieval-selector`). A selector supplied in code takes
+/// precedence over this choice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, strum::Display, strum::EnumString)]
+#[cfg_attr(feature = "cli", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "cli", serde(rename_all = "lowercase"))]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum RetrievalSelection {
+    /// [`ClosestSelector`]: plain proximity.
+    #[default]
+    Closest,
+    /// [`LatencySelector`]: the fastest observed forwarding distance first.
+    Latency,
+    /// [`ScoreSelector`]: reordered by the admission band and peer score.
+    Score,
+}
+
+impl RetrievalSelection {
+    /// The selector this choice names, over the node's latency estimate and
+    /// score selector.
+    pub fn selector(
+        self,
+        latency: &Arc<RetrievalLatency>,
+        scores: &Arc<ScoreSelector>,
+    ) -> Arc<dyn PeerSelector> {
+        match self {
+            Self::Closest => Arc::new(ClosestSelector),
+            Self::Latency => Arc::new(LatencySelector::new(Arc::clone(latency))),
+            Self::Score => Arc::clone(scores) as Arc<dyn PeerSelector>,
+        }
+    }
+}
+
 /// How [`rank_candidates`] orders the admissible peers.
 #[derive(Clone, Copy)]
 enum Tiering {
@@ -549,8 +696,8 @@ mod tests {
     fn selector(
         scores: HashMap<OverlayAddress, f64>,
         unaffordable: Vec<OverlayAddress>,
-    ) -> PeerSelector {
-        PeerSelector::new(
+    ) -> ScoreSelector {
+        ScoreSelector::new(
             Arc::new(FixedScores(scores)),
             Arc::new(FixedLedger::new(unaffordable)),
             Arc::new(UnitPricer),
@@ -560,8 +707,8 @@ mod tests {
     fn selector_with_settle_due(
         unaffordable: Vec<OverlayAddress>,
         settle_due: Vec<OverlayAddress>,
-    ) -> PeerSelector {
-        PeerSelector::new(
+    ) -> ScoreSelector {
+        ScoreSelector::new(
             Arc::new(FixedScores(HashMap::new())),
             Arc::new(FixedLedger {
                 unaffordable,
@@ -626,6 +773,28 @@ mod tests {
         assert_eq!(ordered, vec![peer(2)]);
     }
 
+    #[test]
+    fn latency_selector_prefers_the_fastest_sampled_distance() {
+        // Against the zero chunk, 0x80.. sits at PO 0, 0x40.. at PO 1, 0x20.. at
+        // PO 2. PO 2 is fastest, PO 1 slower, PO 0 unsampled and so last.
+        let (po0, po1, po2) = (peer(0x80), peer(0x40), peer(0x20));
+        let topology =
+            vertex_swarm_test_utils::MockTopology::new(3, 3, 0).with_closest(vec![po0, po1, po2]);
+        let latency = Arc::new(RetrievalLatency::new());
+        latency.record(1, std::time::Duration::from_millis(50));
+        latency.record(2, std::time::Duration::from_millis(10));
+
+        let selected = LatencySelector::new(latency).select(&ChunkAddress::zero(), &topology, 3);
+        assert_eq!(selected, vec![po2, po1, po0]);
+
+        let cold = LatencySelector::new(Arc::new(RetrievalLatency::new()));
+        assert_eq!(
+            cold.select(&ChunkAddress::zero(), &topology, 3),
+            vec![po0, po1, po2],
+            "a cold estimate keeps the proximity order"
+        );
+    }
+
     // Dedup of `AccountingSettlement` over a mock bandwidth accounting whose
     // settle parks until released, so two triggers for one peer can overlap.
     use std::sync::atomic::{AtomicUsize, Ordering};
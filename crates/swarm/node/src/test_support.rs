@@ -0,0 +1,48 @@
+//! Shared test infrastructure for the retrieval dispatch tests.
+
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use vertex_swarm_api::{Bin, OverlayAddress};
+
+use crate::dispatch::{DispatchEngine, NoLatencyHint, ProximityOnly, RetrievalTopology};
+use crate::selection::SettlementTrigger;
+use crate::{ClientCommand, ClientHandle, PeerInflightLimiter};
+
+/// The engine the dispatch tests drive: proximity ordering, a per-peer cap of
+/// four, and no latency hint.
+pub(crate) type TestEngine = DispatchEngine<ProximityOnly, PeerInflightLimiter, NoLatencyHint>;
+
+/// Settlement trigger that does nothing.
+pub(crate) struct NoSettle;
+
+impl SettlementTrigger for NoSettle {
+    fn trigger_settlement(&self, _peer: OverlayAddress) {}
+}
+
+pub(crate) fn overlay(byte: u8) -> OverlayAddress {
+    OverlayAddress::from([byte; 32])
+}
+
+pub(crate) fn inflight() -> PeerInflightLimiter {
+    PeerInflightLimiter::new(NonZeroUsize::new(4).expect("non-zero"))
+}
+
+/// A dispatch engine over `topology`, and the receiving end of its client
+/// command channel.
+pub(crate) fn engine(
+    topology: Arc<dyn RetrievalTopology>,
+) -> (TestEngine, mpsc::Receiver<ClientCommand>) {
+    let (tx, rx) = mpsc::channel(16);
+    let engine = DispatchEngine::new(
+        ClientHandle::new(tx),
+        topology,
+        Bin::MAX,
+        ProximityOnly,
+        inflight(),
+        NoLatencyHint,
+        Arc::new(NoSettle),
+    );
+    (engine, rx)
+}
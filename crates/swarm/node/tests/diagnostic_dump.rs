@@ -0,0 +1,96 @@
+//! Integration test: [`NodeDiagnostics`] over a live cluster client reports the
+//! connected bootnode in both the topology and peer sections of the dump.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::time::Duration;
+
+use eyre::Result;
+use libp2p::Multiaddr;
+use tokio::time::{sleep, timeout};
+use vertex_swarm_api::{
+    ConfigDiagnostics, SwarmDiagnostics as _, SwarmNetworkConfig, SwarmNodeType,
+    SwarmTopologyStats as _,
+};
+use vertex_swarm_node::NodeDiagnostics;
+use vertex_swarm_primitives::SwarmAddressExt as _;
+use vertex_swarm_test_utils::cluster::ClusterBuilder;
+
+/// Cap on wall-clock time the test will wait for the client to connect.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+struct DumpNetworkConfig;
+
+impl SwarmNetworkConfig for DumpNetworkConfig {
+    fn listen_addrs(&self) -> &[Multiaddr] {
+        &[]
+    }
+    fn bootnodes(&self) -> &[Multiaddr] {
+        &[]
+    }
+    fn discovery_enabled(&self) -> bool {
+        true
+    }
+    fn max_peers(&self) -> Option<usize> {
+        Some(16)
+    }
+    fn idle_timeout(&self) -> Duration {
+        Duration::from_secs(30)
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn dump_reports_connected_bootnode() -> Result<()> {
+    let cluster = ClusterBuilder::new()
+        .with_bootnode()
+        .with_clients(1)
+        .build()
+        .await?;
+
+    let bootnode_overlay = cluster.bootnode().overlay.to_hex();
+    let client = cluster
+        .clients()
+        .first()
+        .ok_or_else(|| eyre::eyre!("cluster built without a client"))?;
+    let topology = client.topology.clone();
+
+    timeout(CONNECT_TIMEOUT, async {
+        while topology.connected_peers_count() == 0 {
+            sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .map_err(|_| {
+        eyre::eyre!("client did not connect to the bootnode within {CONNECT_TIMEOUT:?}")
+    })?;
+
+    let diagnostics = NodeDiagnostics::new(
+        topology,
+        ConfigDiagnostics::from_network(SwarmNodeType::Client, &DumpNetworkConfig),
+    );
+    let dump = diagnostics.diagnostic_dump();
+
+    assert_eq!(dump.topology.overlay, client.overlay.to_hex());
+    assert!(dump.topology.connected_peers >= 1);
+    assert!(!dump.topology.bins.is_empty());
+    assert!(
+        dump.peers
+            .iter()
+            .any(|peer| peer.overlay == bootnode_overlay),
+        "peer section should list the bootnode; got {:?}",
+        dump.peers
+    );
+    assert!(dump.accounting.is_empty(), "no ledger was wired");
+    assert!(dump.storage.is_none(), "no reserve was wired");
+    assert_eq!(dump.config.node_type, SwarmNodeType::Client);
+
+    for (idx, result) in cluster.shutdown().await.iter().enumerate() {
+        if let Err(err) = result {
+            return Err(eyre::eyre!(
+                "cluster node #{idx} did not shut down cleanly: {err}"
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -142,6 +142,7 @@ fn client_core_accounting_wires_pseudosettle_and_swap_on_wasm() {
         None,
         false,
         0,
+        0,
         true,
     )
     .expect("swap wiring is prepared for a chequebook on a named chain");
@@ -0,0 +1,273 @@
+//! Per-connection lifecycle state machine.
+//!
+//! Connection setup is driven by independent event sources (the pending
+//! outbound dial, swarm `ConnectionEstablished`, handshake completion, peer
+//! activation, swarm `ConnectionClosed`) whose relative order is not enforced
+//! by libp2p. Each connection walks
+//! `Dialing -> Connected -> Handshaking -> Authenticated -> Active -> Closing`,
+//! and [`ConnectionStateMachine::apply`] rejects any event that arrives out of
+//! that order. A rejected event is logged, counted, and leaves the recorded
+//! phase untouched, so a late or duplicate event cannot corrupt state.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use metrics::counter;
+use strum::IntoStaticStr;
+use tracing::warn;
+
+/// Lifecycle phase of a single connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum ConnectionPhase {
+    /// Outbound dial in flight, no transport yet.
+    Dialing,
+    /// Transport established, handshake not yet started.
+    Connected,
+    /// Handshake exchange in progress.
+    Handshaking,
+    /// Handshake completed and the peer's identity verified.
+    Authenticated,
+    /// Admitted to the topology and announced to subscribers.
+    Active,
+    /// Teardown started; no further transitions are accepted.
+    Closing,
+}
+
+/// Event that moves a connection between phases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum ConnectionTransition {
+    /// Outbound dial started.
+    Dial,
+    /// Transport connection established (inbound or completed dial).
+    Establish,
+    /// Handshake protocol started on the connection.
+    StartHandshake,
+    /// Handshake completed successfully.
+    Authenticate,
+    /// Peer admitted and activated.
+    Activate,
+    /// Connection is being closed.
+    Close,
+}
+
+impl ConnectionTransition {
+    /// Phase reached by applying this transition from `from`, or `None` if
+    /// the transition is not valid there.
+    fn target(self, from: Option<ConnectionPhase>) -> Option<ConnectionPhase> {
+        use ConnectionPhase::*;
+        match (from, self) {
+            (None, Self::Dial) => Some(Dialing),
+            (None | Some(Dialing), Self::Establish) => Some(Connected),
+            (Some(Connected), Self::StartHandshake) => Some(Handshaking),
+            (Some(Handshaking), Self::Authenticate) => Some(Authenticated),
+            (Some(Authenticated), Self::Activate) => Some(Active),
+            (Some(phase), Self::Close) if phase != Closing => Some(Closing),
+            _ => None,
+        }
+    }
+}
+
+/// A transition that is not valid from the connection's current phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("invalid connection transition {transition:?} from {from:?}")]
+pub struct InvalidTransition {
+    /// Phase the connection was in, `None` if it was not tracked.
+    pub from: Option<ConnectionPhase>,
+    /// Rejected transition.
+    pub transition: ConnectionTransition,
+}
+
+/// Validated lifecycle phases keyed by connection.
+#[derive(Debug)]
+pub struct ConnectionStateMachine<K> {
+    phases: HashMap<K, ConnectionPhase>,
+}
+
+impl<K> Default for ConnectionStateMachine<K> {
+    fn default() -> Self {
+        Self {
+            phases: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Copy + Debug> ConnectionStateMachine<K> {
+    /// Create a machine tracking no connections.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply `transition` to the connection `key`.
+    ///
+    /// On success returns the new phase. An invalid transition is logged,
+    /// counted, and returned as an error; the recorded phase is unchanged.
+    pub fn apply(
+        &mut self,
+        key: K,
+        transition: ConnectionTransition,
+    ) -> Result<ConnectionPhase, InvalidTransition> {
+        let from = self.phases.get(&key).copied();
+        let Some(to) = transition.target(from) else {
+            let from_label: &'static str = from.map_or("untracked", Into::into);
+            let transition_label: &'static str = transition.into();
+            warn!(
+                connection = ?key,
+                ?from,
+                ?transition,
+                "dropping out-of-order connection transition"
+            );
+            counter!(
+                "peer_manager_invalid_connection_transitions_total",
+                "from" => from_label,
+                "transition" => transition_label,
+            )
+            .increment(1);
+            return Err(InvalidTransition { from, transition });
+        };
+        self.phases.insert(key, to);
+        Ok(to)
+    }
+
+    /// Current phase of `key`, `None` if not tracked.
+    pub fn phase(&self, key: &K) -> Option<ConnectionPhase> {
+        self.phases.get(key).copied()
+    }
+
+    /// Forget `key` once the connection is fully closed.
+    pub fn closed(&mut self, key: &K) -> Option<ConnectionPhase> {
+        self.phases.remove(key)
+    }
+
+    /// Number of tracked connections.
+    pub fn len(&self) -> usize {
+        self.phases.len()
+    }
+
+    /// Whether no connection is tracked.
+    pub fn is_empty(&self) -> bool {
+        self.phases.is_empty()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use ConnectionPhase::*;
+    use ConnectionTransition::*;
+
+    fn walk(
+        machine: &mut ConnectionStateMachine<u32>,
+        key: u32,
+        transitions: &[ConnectionTransition],
+    ) {
+        for &t in transitions {
+            machine.apply(key, t).unwrap();
+        }
+    }
+
+    #[test]
+    fn valid_outbound_sequence() {
+        let mut machine = ConnectionStateMachine::new();
+        let steps = [
+            (Dial, Dialing),
+            (Establish, Connected),
+            (StartHandshake, Handshaking),
+            (Authenticate, Authenticated),
+            (Activate, Active),
+            (Close, Closing),
+        ];
+        for (transition, phase) in steps {
+            assert_eq!(machine.apply(1, transition), Ok(phase));
+            assert_eq!(machine.phase(&1), Some(phase));
+        }
+        assert_eq!(machine.closed(&1), Some(Closing));
+        assert!(machine.is_empty());
+    }
+
+    #[test]
+    fn inbound_connection_skips_dialing() {
+        let mut machine = ConnectionStateMachine::new();
+        walk(
+            &mut machine,
+            1,
+            &[Establish, StartHandshake, Authenticate, Activate],
+        );
+        assert_eq!(machine.phase(&1), Some(Active));
+    }
+
+    #[test]
+    fn activate_before_handshake_is_rejected() {
+        let mut machine = ConnectionStateMachine::new();
+        walk(&mut machine, 1, &[Establish, StartHandshake]);
+
+        assert_eq!(
+            machine.apply(1, Activate),
+            Err(InvalidTransition {
+                from: Some(Handshaking),
+                transition: Activate,
+            })
+        );
+        assert_eq!(machine.phase(&1), Some(Handshaking));
+
+        // The proper sequence still goes through afterwards.
+        walk(&mut machine, 1, &[Authenticate, Activate]);
+        assert_eq!(machine.phase(&1), Some(Active));
+    }
+
+    #[test]
+    fn events_for_untracked_connection_are_rejected() {
+        let mut machine = ConnectionStateMachine::<u32>::new();
+        for transition in [StartHandshake, Authenticate, Activate, Close] {
+            assert_eq!(
+                machine.apply(7, transition),
+                Err(InvalidTransition {
+                    from: None,
+                    transition,
+                })
+            );
+        }
+        assert!(machine.is_empty());
+    }
+
+    #[test]
+    fn duplicate_and_backward_transitions_are_rejected() {
+        let mut machine = ConnectionStateMachine::new();
+        walk(
+            &mut machine,
+            1,
+            &[Establish, StartHandshake, Authenticate, Activate],
+        );
+
+        for transition in [Dial, Establish, StartHandshake, Authenticate, Activate] {
+            assert!(machine.apply(1, transition).is_err());
+            assert_eq!(machine.phase(&1), Some(Active));
+        }
+    }
+
+    #[test]
+    fn nothing_is_accepted_after_close() {
+        let mut machine = ConnectionStateMachine::new();
+        walk(&mut machine, 1, &[Establish, StartHandshake, Close]);
+
+        for transition in [StartHandshake, Authenticate, Activate, Close] {
+            assert!(machine.apply(1, transition).is_err());
+            assert_eq!(machine.phase(&1), Some(Closing));
+        }
+    }
+
+    #[test]
+    fn connections_are_independent() {
+        let mut machine = ConnectionStateMachine::new();
+        walk(&mut machine, 1, &[Establish, StartHandshake, Authenticate]);
+        walk(&mut machine, 2, &[Establish]);
+
+        assert!(machine.apply(2, Activate).is_err());
+        assert_eq!(machine.apply(1, Activate), Ok(Active));
+        assert_eq!(machine.phase(&2), Some(Connected));
+        assert_eq!(machine.len(), 2);
+    }
+}
@@ -5,7 +5,7 @@ use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU64, Ordering};
 use std::time::Duration;
 
 use metrics::gauge;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
 use tracing::debug;
 use vertex_net_local::IpCapability;
@@ -14,7 +14,7 @@ use vertex_net_peer_registry::ConnectionDirection;
 use vertex_swarm_api::SwarmScoringEvent;
 use vertex_swarm_peer::SwarmPeer;
 use vertex_swarm_peer_score::{PeerScore, ScoreChange, SwarmPeerScore, SwarmScoringConfig};
-use vertex_swarm_primitives::{OverlayAddress, SwarmNodeType};
+use vertex_swarm_primitives::{OverlayAddress, PeerCapabilities, SwarmNodeType};
 
 /// Exclusive health state for a peer.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -66,6 +66,16 @@ const STALE_FAILURE_THRESHOLD: u32 = 48;
 /// re-admit the peer later; the gossip intake cooldown bounds how often.
 const UNVERIFIED_STALE_FAILURE_THRESHOLD: u32 = 3;
 
+/// Backoff base for a peer that failed protocol negotiation (10 minutes).
+///
+/// The transport connected but the peer does not speak our handshake, so
+/// it is incompatible rather than transiently unreachable; retrying on the
+/// ordinary 30-second base only repeats a connection that cannot succeed.
+const INCOMPATIBLE_BACKOFF_BASE_SECS: u64 = 10 * 60;
+
+/// Backoff ceiling for a peer that failed protocol negotiation (6 hours).
+const INCOMPATIBLE_BACKOFF_MAX_SECS: u64 = 6 * 3600;
+
 /// Score half-life for disconnected peers (10 minutes).
 pub(crate) const DISCONNECTED_SCORE_HALF_LIFE_SECS: u64 = 600;
 
@@ -100,6 +110,12 @@ pub struct PeerSnapshot {
     pub last_seen: u64,
 }
 
+impl vertex_net_peer_store::LastSeen for PeerSnapshot {
+    fn last_seen(&self) -> u64 {
+        self.last_seen
+    }
+}
+
 /// Current wall-clock time in unix seconds.
 ///
 /// Delegates to [`vertex_util_runtime::time::now_unix_secs`], the single
@@ -151,6 +167,37 @@ pub enum TrustLevel {
     Trusted = 2,
 }
 
+/// How the local node first learned of a peer.
+///
+/// Recorded once, when the peer first enters the store; a later connection
+/// through another path does not overwrite it. Process-local, like
+/// [`TrustLevel`]: entries restored from a snapshot report
+/// [`Self::Snapshot`] rather than the source of the previous session.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::IntoStaticStr, strum::FromRepr,
+)]
+#[strum(serialize_all = "snake_case")]
+#[repr(u8)]
+pub enum DiscoverySource {
+    /// Gossiped by a connected peer over hive.
+    Hive = 1,
+    /// One of the configured bootnodes.
+    Bootnode = 2,
+    /// One of the configured trusted peers.
+    Trusted = 3,
+    /// One of the configured static peers.
+    Static = 4,
+    /// Dialed on an operator command.
+    Dial = 5,
+    /// Connected to us without being known first.
+    Inbound = 6,
+    /// Restored from the peer snapshot at startup.
+    Snapshot = 7,
+}
+
+/// Encoding of an unrecorded [`DiscoverySource`]; no variant uses it.
+const SOURCE_UNSET: u8 = 0;
+
 /// A [`SwarmNodeType`] plus a confirmed bit, packed into one atomic byte.
 ///
 /// A peer's node type flows in from two sources with different trust levels:
@@ -250,8 +297,15 @@ pub(crate) struct PeerEntry {
     /// Direction of the current connection ([`DIRECTION_NONE`] while
     /// disconnected). Process-local, never persisted.
     direction: AtomicU8,
+    /// Whether the most recent connection was inbound. Kept across the
+    /// disconnect, unlike `direction`, so [`Self::is_inbound_only`] still
+    /// answers once the peer is gone. Process-local, never persisted.
+    last_inbound: AtomicBool,
     /// [`TrustLevel`] discriminant, written at handshake completion only.
     trust: AtomicU8,
+    /// [`DiscoverySource`] discriminant, written once ([`SOURCE_UNSET`]
+    /// until then). Process-local, never persisted.
+    source: AtomicU8,
     /// Whether a completed handshake has ever confirmed this peer's identity
     /// in this process (see [`Self::is_verified`]).
     ///
@@ -261,6 +315,25 @@ pub(crate) struct PeerEntry {
     /// record: a gossiped address refresh on a verified peer does not clear
     /// it.
     verified: AtomicBool,
+    /// Invalid-data reports in the current strike window (see
+    /// [`Self::record_invalid_data`]). Process-local, never persisted.
+    invalid_data: Mutex<StrikeWindow>,
+    /// Capabilities negotiated on the most recent handshake; `None` until the
+    /// first one. Kept across the disconnect so a reconnect that negotiates a
+    /// different set is noticed. Process-local, never persisted.
+    capabilities: RwLock<Option<PeerCapabilities>>,
+    /// Whether the last failed connection reached the peer but failed
+    /// protocol negotiation, which stretches the backoff (see
+    /// [`INCOMPATIBLE_BACKOFF_BASE_SECS`]). Cleared by a completed handshake.
+    /// Process-local, never persisted.
+    incompatible: AtomicBool,
+}
+
+/// Fixed-window strike counter: `count` reports since `started` (unix secs).
+#[derive(Default)]
+struct StrikeWindow {
+    started: u64,
+    count: u32,
 }
 
 impl PeerEntry {
@@ -283,8 +356,13 @@ impl PeerEntry {
             connected_since: AtomicU64::new(0),
             last_productive: AtomicU64::new(0),
             direction: AtomicU8::new(DIRECTION_NONE),
+            last_inbound: AtomicBool::new(false),
             trust: AtomicU8::new(TrustLevel::Normal as u8),
+            source: AtomicU8::new(SOURCE_UNSET),
             verified: AtomicBool::new(false),
+            invalid_data: Mutex::new(StrikeWindow::default()),
+            capabilities: RwLock::new(None),
+            incompatible: AtomicBool::new(false),
         }
     }
 
@@ -309,8 +387,13 @@ impl PeerEntry {
             connected_since: AtomicU64::new(0),
             last_productive: AtomicU64::new(0),
             direction: AtomicU8::new(DIRECTION_NONE),
+            last_inbound: AtomicBool::new(false),
             trust: AtomicU8::new(TrustLevel::Normal as u8),
+            source: AtomicU8::new(DiscoverySource::Snapshot as u8),
             verified: AtomicBool::new(false),
+            invalid_data: Mutex::new(StrikeWindow::default()),
+            capabilities: RwLock::new(None),
+            incompatible: AtomicBool::new(false),
         }
     }
 
@@ -339,6 +422,11 @@ impl PeerEntry {
         self.last_seen.load(Ordering::Relaxed)
     }
 
+    #[cfg(test)]
+    pub(crate) fn set_last_seen(&self, last_seen: u64) {
+        self.last_seen.store(last_seen, Ordering::Relaxed);
+    }
+
     pub(crate) fn consecutive_failures(&self) -> u32 {
         self.backoff.consecutive_failures()
     }
@@ -456,6 +544,8 @@ impl PeerEntry {
             .store(unix_timestamp_secs(), Ordering::Release);
         self.direction
             .store(direction_to_repr(direction), Ordering::Release);
+        self.last_inbound
+            .store(direction == ConnectionDirection::Inbound, Ordering::Release);
         self.trust.store(trust as u8, Ordering::Release);
         self.reset_failures();
         self.touch();
@@ -506,10 +596,39 @@ impl PeerEntry {
         TrustLevel::from_repr(self.trust.load(Ordering::Acquire)).unwrap_or_default()
     }
 
+    /// Record how the peer was learned unless a source is already recorded.
+    pub(crate) fn set_discovery_source(&self, source: DiscoverySource) {
+        let _ = self.source.compare_exchange(
+            SOURCE_UNSET,
+            source as u8,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        );
+    }
+
+    pub(crate) fn discovery_source(&self) -> Option<DiscoverySource> {
+        DiscoverySource::from_repr(self.source.load(Ordering::Acquire))
+    }
+
     pub(crate) fn record_latency(&self, rtt: Duration) {
         self.scoring.record_latency(rtt);
     }
 
+    /// Count an invalid-data report at `now`, returning the reports in the
+    /// current window including this one. A report more than `window_secs`
+    /// after the window opened starts a new window.
+    pub(crate) fn record_invalid_data(&self, now: u64, window_secs: u64) -> u32 {
+        let mut strikes = self.invalid_data.lock();
+        if strikes.count == 0 || now.saturating_sub(strikes.started) > window_secs {
+            *strikes = StrikeWindow {
+                started: now,
+                count: 0,
+            };
+        }
+        strikes.count = strikes.count.saturating_add(1);
+        strikes.count
+    }
+
     pub(crate) fn ban(&self, reason: Option<String>) {
         *self.ban_info.write() = Some((unix_timestamp_secs(), reason.unwrap_or_default()));
     }
@@ -524,16 +643,63 @@ impl PeerEntry {
         self.backoff.record_failure(unix_timestamp_secs());
     }
 
+    /// Record a connection that reached the peer but failed protocol
+    /// negotiation: a dial failure whose backoff runs on the longer
+    /// incompatible-peer schedule until the next completed handshake.
+    pub(crate) fn record_negotiation_failure(&self) {
+        self.incompatible.store(true, Ordering::Relaxed);
+        self.record_dial_failure();
+    }
+
+    pub(crate) fn is_incompatible(&self) -> bool {
+        self.incompatible.load(Ordering::Relaxed)
+    }
+
     pub(crate) fn is_banned(&self) -> bool {
         self.ban_info.read().is_some()
     }
 
+    /// Not banned, not in backoff, and not inbound-only.
     pub(crate) fn is_dialable(&self) -> bool {
-        !self.is_banned() && !self.is_in_backoff()
+        !self.is_banned() && !self.is_in_backoff() && !self.is_inbound_only()
+    }
+
+    /// Whether the peer reached us inbound on its last connection and
+    /// advertises no address we could dial back. Such a peer cannot be
+    /// redialed, so it is never a dial candidate. A later outbound connection
+    /// or a record refresh that adds an IP address clears it.
+    pub(crate) fn is_inbound_only(&self) -> bool {
+        self.last_inbound.load(Ordering::Acquire) && !self.peer.read().is_dialable()
+    }
+
+    /// Replace the cached capabilities with those negotiated on a new
+    /// handshake, returning the previous value when it differed.
+    pub(crate) fn set_capabilities(
+        &self,
+        capabilities: PeerCapabilities,
+    ) -> Option<PeerCapabilities> {
+        let previous = self.capabilities.write().replace(capabilities);
+        previous.filter(|previous| *previous != capabilities)
+    }
+
+    /// Whether the current connection did useful work within the last
+    /// `window`.
+    pub(crate) fn was_productive_within(&self, window: Duration) -> bool {
+        self.was_productive_since_connect()
+            && self.last_productive.load(Ordering::Acquire)
+                >= unix_timestamp_secs().saturating_sub(window.as_secs())
     }
 
     /// Backoff with per-peer jitter (+/-25%) to prevent synchronized retry storms.
     pub(crate) fn backoff_remaining(&self) -> Option<Duration> {
+        if self.is_incompatible() {
+            return self.backoff.remaining_jittered_with(
+                unix_timestamp_secs(),
+                INCOMPATIBLE_BACKOFF_BASE_SECS,
+                INCOMPATIBLE_BACKOFF_MAX_SECS,
+                self.jitter_seed,
+            );
+        }
         self.backoff
             .remaining_jittered(unix_timestamp_secs(), self.jitter_seed)
     }
@@ -585,6 +751,7 @@ impl PeerEntry {
 
     fn reset_failures(&self) {
         self.backoff.reset();
+        self.incompatible.store(false, Ordering::Relaxed);
     }
 }
 
@@ -598,6 +765,49 @@ impl From<&PeerEntry> for PeerSnapshot {
     }
 }
 
+/// Point-in-time copy of one peer's state, as listed by
+/// [`PeerManager::peer_states`](crate::PeerManager::peer_states).
+///
+/// A plain value: later changes to the peer do not show through.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeerInfo {
+    /// The peer's overlay address.
+    pub overlay: OverlayAddress,
+    /// Node type; provisional until a handshake confirms it.
+    pub node_type: SwarmNodeType,
+    /// Current score.
+    pub score: f64,
+    /// Unix seconds the peer was last seen healthy.
+    pub last_seen: u64,
+    /// Unix seconds the current connection completed its handshake, or
+    /// `None` while disconnected.
+    pub connected_since: Option<u64>,
+    /// Direction of the current connection, or `None` while disconnected.
+    pub direction: Option<ConnectionDirection>,
+    /// Whether a handshake has confirmed the peer in this process.
+    pub verified: bool,
+    /// Whether the peer is banned.
+    pub banned: bool,
+    /// How the peer was first learned, when recorded.
+    pub discovery_source: Option<DiscoverySource>,
+}
+
+impl PeerInfo {
+    pub(crate) fn capture(overlay: OverlayAddress, entry: &PeerEntry) -> Self {
+        Self {
+            overlay,
+            node_type: entry.node_type(),
+            score: entry.score(),
+            last_seen: entry.last_seen(),
+            connected_since: entry.connected_since(),
+            direction: entry.direction(),
+            verified: entry.is_verified(),
+            banned: entry.is_banned(),
+            discovery_source: entry.discovery_source(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -685,6 +895,27 @@ mod tests {
         assert!(entry.backoff_remaining().unwrap().as_secs() <= 76);
     }
 
+    #[test]
+    fn test_negotiation_failure_backs_off_longer() {
+        let unreachable = test_entry(1, SwarmNodeType::Client);
+        let incompatible = test_entry(2, SwarmNodeType::Client);
+
+        unreachable.record_dial_failure();
+        incompatible.record_negotiation_failure();
+
+        assert!(incompatible.is_incompatible());
+        assert_eq!(incompatible.consecutive_failures(), 1);
+        let ordinary = unreachable.backoff_remaining().unwrap();
+        let stretched = incompatible.backoff_remaining().unwrap();
+        // 30s vs 10min bases, each within +/-25% jitter.
+        assert!(ordinary.as_secs() <= 38);
+        assert!(stretched.as_secs() >= 400, "got {stretched:?}");
+
+        record_success(&incompatible, Duration::from_millis(50));
+        assert!(!incompatible.is_incompatible());
+        assert!(!incompatible.is_in_backoff());
+    }
+
     #[test]
     fn test_success_resets_failures() {
         let entry = test_entry(1, SwarmNodeType::Client);
@@ -6,6 +6,7 @@
 
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+mod connection_state;
 mod entry;
 mod maintenance;
 mod manager;
@@ -15,8 +16,13 @@ mod scoring;
 mod snapshot_store;
 mod tasks;
 
-pub use entry::{PeerSnapshot, TrustLevel};
-pub use manager::{LIFECYCLE_CHANNEL_CAPACITY, PeerManager, PeerManagerConfig, PeerManagerHandle};
+pub use connection_state::{
+    ConnectionPhase, ConnectionStateMachine, ConnectionTransition, InvalidTransition,
+};
+pub use entry::{DiscoverySource, PeerInfo, PeerSnapshot, TrustLevel};
+pub use manager::{
+    InvalidDataBan, LIFECYCLE_CHANNEL_CAPACITY, PeerManager, PeerManagerConfig, PeerManagerHandle,
+};
 pub use proximity_index::{AddError, ProximityIndex};
 pub use score_distribution::ScoreDistribution;
 pub use snapshot_store::DbPeerSnapshotStore;
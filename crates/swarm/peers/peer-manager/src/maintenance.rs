@@ -4,10 +4,11 @@
 use metrics::gauge;
 use std::sync::atomic::Ordering;
 use tracing::{debug, info, warn};
+use vertex_net_peer_store::PeerSnapshotStoreExt;
 use vertex_swarm_api::SwarmIdentity;
 use vertex_swarm_primitives::OverlayAddress;
 
-use crate::entry::{PeerEntry, PeerSnapshot, on_health_added};
+use crate::entry::{PeerEntry, PeerSnapshot, on_health_added, unix_timestamp_secs};
 use crate::manager::PeerManager;
 
 impl<I: SwarmIdentity> PeerManager<I> {
@@ -50,14 +51,26 @@ impl<I: SwarmIdentity> PeerManager<I> {
     /// shutdown so the final state is not lost to the snapshot interval.
     /// Unverified entries are skipped: they carry only a relayed gossip
     /// claim, and persisting them would let junk records survive restarts.
-    /// Gossip re-delivers any that are real.
+    /// Gossip re-delivers any that are real. Entries past the snapshot TTL
+    /// are skipped too, so each periodic write also expires the store.
+    /// A connected peer counts as seen now: `last_seen` only moves on
+    /// connect, so a long-held connection would otherwise age out of the
+    /// store while it is still up.
     pub fn snapshot(&self) {
         let Some(ref store) = self.store else { return };
+        let now = unix_timestamp_secs();
+        let cutoff = now.saturating_sub(self.snapshot_ttl.as_secs());
         let records: Vec<PeerSnapshot> = self
             .peers
             .iter()
             .filter(|r| r.value().is_verified())
-            .map(|r| PeerSnapshot::from(r.value().as_ref()))
+            .filter_map(|r| {
+                let mut record = PeerSnapshot::from(r.value().as_ref());
+                if r.value().is_connected() {
+                    record.last_seen = now;
+                }
+                (record.last_seen >= cutoff).then_some(record)
+            })
             .collect();
         match store.store(&records) {
             Ok(()) => debug!(peers = records.len(), "wrote peer snapshot"),
@@ -130,12 +143,19 @@ impl<I: SwarmIdentity> PeerManager<I> {
 
     /// Seed the peer set from the snapshot store.
     ///
-    /// Called once during construction. Entries that would exceed the
-    /// per-bin cap are dropped; rediscovery via gossip refills them if they
-    /// are still alive.
+    /// Called once during construction. Entries past the snapshot TTL are
+    /// expired from the store first; entries that would exceed the per-bin
+    /// cap are dropped. Rediscovery via gossip refills either if they are
+    /// still alive.
     pub(crate) fn load_from_store(&self) {
         let Some(ref store) = self.store else { return };
 
+        match store.expire_stale(self.snapshot_ttl, unix_timestamp_secs()) {
+            Ok(0) => {}
+            Ok(expired) => info!(expired, "expired stale peers from snapshot"),
+            Err(e) => warn!(error = %e, "failed to expire stale peer snapshot entries"),
+        }
+
         let records = match store.load() {
             Ok(records) => records,
             Err(e) => {
@@ -157,6 +177,7 @@ impl<I: SwarmIdentity> PeerManager<I> {
             ));
             self.score_distribution.on_peer_added(entry.score());
             on_health_added(entry.health_state());
+            let _membership = self.membership.read();
             self.peers.insert(overlay, entry);
             loaded += 1;
         }
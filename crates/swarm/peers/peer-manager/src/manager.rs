@@ -14,6 +14,7 @@ use std::time::Duration;
 
 use dashmap::DashMap;
 use metrics::{counter, gauge};
+use parking_lot::RwLock;
 use tokio::sync::broadcast;
 use tracing::{debug, warn};
 use vertex_net_local::IpCapability;
@@ -25,11 +26,13 @@ use vertex_swarm_api::{
 };
 use vertex_swarm_peer::{SwarmPeer, Timestamp, check_timestamp};
 use vertex_swarm_peer_score::SwarmScoringConfig;
-use vertex_swarm_primitives::{Bin, OverlayAddress, SwarmNodeType};
+use vertex_swarm_primitives::{
+    Bin, DistanceMetric, OverlayAddress, PeerCapabilities, SwarmNodeType, XorDistance,
+};
 
 use crate::entry::{
-    HealthState, PeerEntry, PeerSnapshot, TrustLevel, on_health_added, on_health_changed,
-    on_health_removed, unix_timestamp_secs,
+    DiscoverySource, HealthState, PeerEntry, PeerInfo, PeerSnapshot, TrustLevel, on_health_added,
+    on_health_changed, on_health_removed, unix_timestamp_secs,
 };
 use crate::proximity_index::{AddError, ProximityIndex};
 use crate::score_distribution::ScoreDistribution;
@@ -74,8 +77,60 @@ pub struct PeerManagerConfig {
     /// the peer's score is reset to the disconnect threshold, so it must
     /// behave to climb back; it is not forgiven to neutral.
     pub ban_duration: Duration,
+    /// Age past which a persisted peer is dropped instead of restored.
+    ///
+    /// Applied to the store at startup and to every snapshot written, so the
+    /// persisted set stays bounded and a long-offline node does not redial
+    /// peers that have since gone.
+    pub snapshot_ttl: Duration,
     /// Snapshot persistence; `None` keeps the peer set memory-only.
     pub store: Option<Arc<dyn PeerSnapshotStore<PeerSnapshot>>>,
+    /// Ban a peer that keeps sending invalid data, whatever its score;
+    /// `None` leaves invalid data to scoring alone.
+    pub invalid_data_ban: Option<InvalidDataBan>,
+    /// Metric assigning known peers to proximity bins. Must match the one
+    /// the routing table uses, or dial candidates are drawn from the wrong
+    /// bins.
+    pub distance_metric: Arc<dyn DistanceMetric>,
+}
+
+/// Ban a peer after `threshold` [`SwarmScoringEvent::InvalidData`] reports
+/// within `window`.
+///
+/// Each report is still scored, so a stray malformed chunk only costs score.
+/// A peer that repeatedly serves chunks failing validation is banned with
+/// [`BanCause::InvalidData`] even while its score, propped up by earlier
+/// good service, stays above the ban threshold.
+///
+/// The node's per-protocol circuit breaker acts on the same signals first: it
+/// opens after five malformed responses within a minute and takes the peer
+/// out of that protocol only. The default strike count sits above it, so the
+/// ban is reserved for a peer that keeps failing the breaker's half-open
+/// probes, not one that had a single bad burst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidDataBan {
+    /// Reports within one window that trigger the ban.
+    pub threshold: u32,
+    /// Length of the strike window.
+    pub window: Duration,
+}
+
+impl InvalidDataBan {
+    /// Default strikes before a ban: the breaker's five plus three failed
+    /// half-open probes, which its two-minute cooldown fits inside the window.
+    pub const DEFAULT_THRESHOLD: u32 = 8;
+
+    /// Default strike window (10 minutes).
+    pub const DEFAULT_WINDOW: Duration = Duration::from_secs(600);
+}
+
+impl Default for InvalidDataBan {
+    fn default() -> Self {
+        Self {
+            threshold: Self::DEFAULT_THRESHOLD,
+            window: Self::DEFAULT_WINDOW,
+        }
+    }
 }
 
 impl PeerManagerConfig {
@@ -97,6 +152,12 @@ impl PeerManagerConfig {
     /// cheaply, short enough that a transiently broken peer is not lost for
     /// good; bans never survive a restart either way.
     pub const DEFAULT_BAN_DURATION: Duration = Duration::from_secs(12 * 3600);
+
+    /// Default persisted-peer TTL (7 days).
+    ///
+    /// A peer unseen for a week has most likely left the network; bootnodes
+    /// and gossip repopulate the set faster than dialing the dead would.
+    pub const DEFAULT_SNAPSHOT_TTL: Duration = Duration::from_secs(7 * 24 * 3600);
 }
 
 impl Default for PeerManagerConfig {
@@ -106,7 +167,10 @@ impl Default for PeerManagerConfig {
             max_per_bin: Self::DEFAULT_MAX_PER_BIN,
             snapshot_interval: Self::DEFAULT_SNAPSHOT_INTERVAL,
             ban_duration: Self::DEFAULT_BAN_DURATION,
+            snapshot_ttl: Self::DEFAULT_SNAPSHOT_TTL,
             store: None,
+            invalid_data_ban: Some(InvalidDataBan::default()),
+            distance_metric: Arc::new(XorDistance),
         }
     }
 }
@@ -123,6 +187,11 @@ pub struct PeerManager<I: SwarmIdentity> {
     pub(crate) index: ProximityIndex,
     /// The entire known peer set.
     pub(crate) peers: DashMap<OverlayAddress, Arc<PeerEntry>>,
+    /// Held shared around every insert into or removal from `peers`, and
+    /// exclusively by [`Self::peer_states`], so a listing sees the set as it
+    /// stood at one instant rather than shard by shard. Always taken before
+    /// any `peers` shard lock.
+    pub(crate) membership: RwLock<()>,
     /// Snapshot persistence (None for ephemeral/test mode).
     pub(crate) store: Option<Arc<dyn PeerSnapshotStore<PeerSnapshot>>>,
     /// O(1) ban checks, mapping each banned overlay to its ban expiry in
@@ -135,6 +204,10 @@ pub struct PeerManager<I: SwarmIdentity> {
     pub(crate) snapshot_interval: Duration,
     /// Duration of a timed ban.
     pub(crate) ban_duration: Duration,
+    /// Age past which a persisted peer expires.
+    pub(crate) snapshot_ttl: Duration,
+    /// Strike policy for repeated invalid data.
+    pub(crate) invalid_data_ban: Option<InvalidDataBan>,
     /// Unix seconds of the last periodic snapshot.
     pub(crate) last_snapshot: AtomicU64,
     /// Per-bucket gauge tracking of score distribution.
@@ -147,7 +220,8 @@ impl<I: SwarmIdentity> PeerManager<I> {
     /// Create a peer manager for `identity` from `config`.
     ///
     /// With `config.store` set, the peer set is loaded from the snapshot on
-    /// construction; entries that would exceed the per-bin cap are dropped.
+    /// construction; entries past the snapshot TTL or that would exceed the
+    /// per-bin cap are dropped.
     /// The banned set always starts empty: bans are timed, runtime-only
     /// state that is re-earned in seconds.
     pub fn new(identity: &I, config: PeerManagerConfig) -> Arc<Self> {
@@ -156,20 +230,27 @@ impl<I: SwarmIdentity> PeerManager<I> {
             max_per_bin,
             snapshot_interval,
             ban_duration,
+            snapshot_ttl,
             store,
+            invalid_data_ban,
+            distance_metric,
         } = config;
         let local_overlay = identity.overlay_address();
         let max_po = identity.spec().max_po();
         let (lifecycle_tx, _) = broadcast::channel(LIFECYCLE_CHANNEL_CAPACITY);
         let pm = Arc::new(Self {
             _identity: PhantomData,
-            index: ProximityIndex::new(local_overlay, max_po, max_per_bin),
+            index: ProximityIndex::new(local_overlay, max_po, max_per_bin)
+                .with_metric(distance_metric),
             peers: DashMap::new(),
+            membership: RwLock::new(()),
             store,
             banned_set: DashMap::new(),
             scoring_config: Arc::new(scoring),
             snapshot_interval,
             ban_duration,
+            snapshot_ttl,
+            invalid_data_ban,
             last_snapshot: AtomicU64::new(unix_timestamp_secs()),
             score_distribution: Arc::new(ScoreDistribution::new()),
             lifecycle_tx,
@@ -305,6 +386,14 @@ impl<I: SwarmIdentity> PeerManager<I> {
         self.peers.get(overlay).map(|r| r.score())
     }
 
+    /// Whether the peer's score has fallen below the warn threshold. Unknown
+    /// peers are not poorly reputed.
+    #[must_use]
+    pub fn has_poor_reputation(&self, overlay: &OverlayAddress) -> bool {
+        self.get_peer_score(overlay)
+            .is_some_and(|score| score < self.scoring_config.warn_threshold())
+    }
+
     /// Get SwarmPeer for a single overlay.
     #[must_use]
     pub fn get_swarm_peer(&self, overlay: &OverlayAddress) -> Option<SwarmPeer> {
@@ -483,6 +572,7 @@ impl<I: SwarmIdentity> PeerManager<I> {
             // The provisional refresh is dropped if a concurrent handshake
             // has already confirmed the node type.
             entry.set_provisional_node_type(SwarmNodeType::Client);
+            entry.set_discovery_source(DiscoverySource::Hive);
         }
         overlay
     }
@@ -615,6 +705,62 @@ impl<I: SwarmIdentity> PeerManager<I> {
             .is_some_and(|e| e.was_productive_since_connect())
     }
 
+    /// Whether the peer reached us inbound and advertises no address we could
+    /// dial back. Such a peer is never offered as a dial candidate, so it is
+    /// not redialed once it drops.
+    #[must_use]
+    pub fn is_inbound_only(&self, overlay: &OverlayAddress) -> bool {
+        self.peers.get(overlay).is_some_and(|e| e.is_inbound_only())
+    }
+
+    /// Cache the capabilities negotiated on a completed handshake.
+    ///
+    /// The cache is per overlay, so it outlives the connection. A reconnect
+    /// that negotiates a different set (the peer upgraded, or crossed a fork
+    /// that enables the capabilities field) replaces the stale value, and the
+    /// previous value is returned.
+    pub fn record_capabilities(
+        &self,
+        overlay: &OverlayAddress,
+        capabilities: PeerCapabilities,
+    ) -> Option<PeerCapabilities> {
+        let previous = self.peers.get(overlay)?.set_capabilities(capabilities)?;
+        counter!("peer_manager_capabilities_changed_total").increment(1);
+        debug!(
+            ?overlay,
+            ?previous,
+            ?capabilities,
+            "peer capabilities changed"
+        );
+        Some(previous)
+    }
+
+    /// Record how a connected peer was learned, for a peer that was not
+    /// already known. Topology calls this at handshake completion, where the
+    /// dial reason is known; a peer first learned from gossip or the snapshot
+    /// keeps that source.
+    pub fn record_discovery_source(&self, overlay: &OverlayAddress, source: DiscoverySource) {
+        if let Some(entry) = self.peers.get(overlay) {
+            entry.set_discovery_source(source);
+        }
+    }
+
+    /// How the peer was first learned in this process; `None` for an unknown
+    /// peer.
+    #[must_use]
+    pub fn discovery_source(&self, overlay: &OverlayAddress) -> Option<DiscoverySource> {
+        self.peers.get(overlay).and_then(|e| e.discovery_source())
+    }
+
+    /// Whether the peer's current connection did useful work (a chunk served
+    /// or pushed) within the last `window`.
+    #[must_use]
+    pub fn was_productive_within(&self, overlay: &OverlayAddress, window: Duration) -> bool {
+        self.peers
+            .get(overlay)
+            .is_some_and(|e| e.was_productive_within(window))
+    }
+
     /// Stored [`TrustLevel`] for a peer (one atomic load on the entry).
     ///
     /// Defaults to [`TrustLevel::Normal`] for unknown peers; the level is
@@ -697,6 +843,20 @@ impl<I: SwarmIdentity> PeerManager<I> {
         self.peers.len()
     }
 
+    /// Copy every known peer's state at one point in time.
+    ///
+    /// Peers cannot join or leave the set while the copy is taken, so a
+    /// listing built from it never mixes entries from before and after a
+    /// change to the set. The result is detached from the manager.
+    #[must_use]
+    pub fn peer_states(&self) -> Vec<PeerInfo> {
+        let _membership = self.membership.write();
+        self.peers
+            .iter()
+            .map(|r| PeerInfo::capture(*r.key(), r.value()))
+            .collect()
+    }
+
     /// Insert or update a peer, returning its entry.
     ///
     /// `node_type` only seeds new entries (as a provisional value); existing
@@ -726,6 +886,7 @@ impl<I: SwarmIdentity> PeerManager<I> {
             return None;
         }
 
+        let _membership = self.membership.read();
         match self.peers.entry(overlay) {
             Entry::Occupied(e) => {
                 // A concurrent insert won the race; refresh addresses.
@@ -813,7 +974,11 @@ impl<I: SwarmIdentity> PeerManager<I> {
     /// Fully remove a peer from all data structures (index, peer set,
     /// banned set).
     pub(crate) fn remove_peer(&self, overlay: &OverlayAddress) {
-        if let Some((_, entry)) = self.peers.remove(overlay) {
+        let removed = {
+            let _membership = self.membership.read();
+            self.peers.remove(overlay)
+        };
+        if let Some((_, entry)) = removed {
             self.score_distribution.on_peer_removed(entry.score());
             on_health_removed(entry.health_state());
             if !entry.is_verified() {
@@ -983,6 +1148,64 @@ mod tests {
         assert!(pm.index().exists(&overlay));
     }
 
+    #[test]
+    fn discovery_source_records_the_first_path_a_peer_was_learned_by() {
+        let pm = manager();
+        let gossiped = test_overlay(1);
+        let bootnode = test_overlay(2);
+
+        pm.store_discovered_peer(test_swarm_peer(1));
+        connect(&pm, 2, SwarmNodeType::Storer);
+        pm.record_discovery_source(&bootnode, DiscoverySource::Bootnode);
+
+        assert_eq!(pm.discovery_source(&gossiped), Some(DiscoverySource::Hive));
+        assert_eq!(
+            pm.discovery_source(&bootnode),
+            Some(DiscoverySource::Bootnode)
+        );
+
+        // Connecting to the gossiped peer later keeps its original source.
+        connect(&pm, 1, SwarmNodeType::Storer);
+        pm.record_discovery_source(&gossiped, DiscoverySource::Dial);
+        assert_eq!(pm.discovery_source(&gossiped), Some(DiscoverySource::Hive));
+        assert_eq!(pm.discovery_source(&test_overlay(3)), None);
+    }
+
+    #[test]
+    fn peer_states_is_unaffected_by_later_changes() {
+        let pm = manager();
+        connect(&pm, 1, SwarmNodeType::Storer);
+        pm.store_discovered_peer(test_swarm_peer(2));
+
+        let states = pm.peer_states();
+        assert_eq!(states.len(), 2);
+        let connected = |states: &[PeerInfo], n: u8| {
+            states
+                .iter()
+                .find(|p| p.overlay == test_overlay(n))
+                .map(|p| p.connected_since.is_some())
+        };
+        assert_eq!(connected(&states, 1), Some(true));
+        assert_eq!(connected(&states, 2), Some(false));
+
+        pm.on_peer_disconnected(&test_overlay(1), DisconnectReason::RemoteClose);
+        connect(&pm, 2, SwarmNodeType::Storer);
+        pm.store_discovered_peer(test_swarm_peer(3));
+        pm.remove_peer(&test_overlay(2));
+
+        // The earlier copy still shows the set as it was.
+        assert_eq!(states.len(), 2);
+        assert_eq!(connected(&states, 1), Some(true));
+        assert_eq!(connected(&states, 2), Some(false));
+        assert_eq!(connected(&states, 3), None);
+
+        let now = pm.peer_states();
+        assert_eq!(now.len(), 2);
+        assert_eq!(connected(&now, 1), Some(false));
+        assert_eq!(connected(&now, 2), None);
+        assert!(connected(&now, 3).is_some());
+    }
+
     #[test]
     fn test_peer_lifecycle() {
         let pm = manager();
@@ -1117,6 +1340,10 @@ mod tests {
             !pm2.is_verified(&test_overlay(1)),
             "restored peers re-earn verification on the next handshake"
         );
+        assert_eq!(
+            pm2.discovery_source(&test_overlay(1)),
+            Some(DiscoverySource::Snapshot)
+        );
     }
 
     #[test]
@@ -1353,7 +1580,7 @@ mod tests {
             .map(|byte| PeerSnapshot {
                 peer: make_swarm_peer_minimal(byte),
                 node_type: SwarmNodeType::Client,
-                last_seen: 1000,
+                last_seen: unix_timestamp_secs(),
             })
             .collect();
         store.store(&records).unwrap();
@@ -1372,6 +1599,64 @@ mod tests {
         assert_eq!(pm.index().bin_size(Bin::new(0).unwrap()), 2);
     }
 
+    #[test]
+    fn test_startup_expires_snapshot_entries_past_ttl() {
+        let store = memory_store();
+        let now = unix_timestamp_secs();
+        let ttl = PeerManagerConfig::DEFAULT_SNAPSHOT_TTL.as_secs();
+        let records = vec![
+            PeerSnapshot {
+                peer: test_swarm_peer(1),
+                node_type: SwarmNodeType::Storer,
+                last_seen: now - 2 * ttl,
+            },
+            PeerSnapshot {
+                peer: test_swarm_peer(2),
+                node_type: SwarmNodeType::Storer,
+                last_seen: now,
+            },
+        ];
+        store.store(&records).unwrap();
+
+        let pm = manager_with_store(Arc::clone(&store));
+
+        assert!(pm.get_swarm_peer(&test_overlay(1)).is_none());
+        assert!(pm.get_swarm_peer(&test_overlay(2)).is_some());
+        assert_eq!(
+            store.load().unwrap().len(),
+            1,
+            "expired entry removed from disk"
+        );
+    }
+
+    #[test]
+    fn test_snapshot_keeps_long_connected_peer() {
+        let store = memory_store();
+        let pm = manager_with_store(Arc::clone(&store));
+        let ttl = PeerManagerConfig::DEFAULT_SNAPSHOT_TTL.as_secs();
+        let stale = unix_timestamp_secs() - 2 * ttl;
+
+        connect(&pm, 1, SwarmNodeType::Storer);
+        connect(&pm, 2, SwarmNodeType::Storer);
+        pm.on_peer_disconnected(&test_overlay(2), DisconnectReason::RemoteClose);
+        // Both last connected more than a TTL ago; only peer 1 is still up.
+        for n in 1..=2 {
+            pm.peers.get(&test_overlay(n)).unwrap().set_last_seen(stale);
+        }
+        pm.snapshot();
+
+        let records = store.load().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(
+            OverlayAddress::from(*records[0].peer.overlay()),
+            test_overlay(1)
+        );
+        assert!(
+            records[0].last_seen > stale,
+            "connected peer written as seen now"
+        );
+    }
+
     #[test]
     fn test_ban_does_not_persist_across_restart() {
         let store = memory_store();
@@ -1851,6 +2136,54 @@ mod tests {
         assert_eq!(bans, 1, "repeated Ban outcomes must not re-emit Banned");
     }
 
+    #[test]
+    fn test_single_invalid_data_report_costs_score_without_banning() {
+        let pm = manager();
+        let overlay = test_overlay(1);
+        connect(&pm, 1, SwarmNodeType::Storer);
+        let before = pm.get_peer_score(&overlay).expect("tracked");
+
+        pm.report_peer(
+            &overlay,
+            SwarmScoringEvent::InvalidData,
+            ReportSource::Protocol("retrieval"),
+        );
+
+        assert!(pm.get_peer_score(&overlay).expect("tracked") < before);
+        assert!(!pm.is_banned(&overlay));
+    }
+
+    #[test]
+    fn test_repeated_invalid_data_bans_above_the_score_threshold() {
+        let pm = manager();
+        let overlay = test_overlay(1);
+        connect(&pm, 1, SwarmNodeType::Storer);
+        let mut rx = pm.subscribe();
+
+        for _ in 0..InvalidDataBan::DEFAULT_THRESHOLD {
+            pm.report_peer(
+                &overlay,
+                SwarmScoringEvent::InvalidData,
+                ReportSource::Protocol("retrieval"),
+            );
+        }
+
+        assert!(pm.is_banned(&overlay));
+        assert!(
+            !pm.scoring_config
+                .should_ban(pm.get_peer_score(&overlay).expect("tracked")),
+            "the strike count bans, not the score"
+        );
+        assert!(drain_events(&mut rx).iter().any(|e| matches!(
+            e,
+            PeerLifecycleEvent::Banned {
+                overlay: o,
+                reason: BanCause::InvalidData,
+                ..
+            } if *o == overlay
+        )));
+    }
+
     #[test]
     fn test_report_peer_unknown_overlay_is_dropped() {
         let pm = manager();
@@ -1903,6 +2236,77 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_inbound_only_peer_is_not_redialed() {
+        let pm = manager();
+        // No advertised IP address: reachable only inbound.
+        let inbound_only = OverlayAddress::from(*make_swarm_peer_minimal(0x80).overlay());
+        let dialable = test_overlay(2);
+
+        for peer in [make_swarm_peer_minimal(0x80), test_swarm_peer(2)] {
+            pm.on_peer_connected(
+                peer,
+                SwarmNodeType::Client,
+                ConnectionDirection::Inbound,
+                TrustLevel::Normal,
+            );
+        }
+        pm.on_peer_disconnected(&inbound_only, DisconnectReason::RemoteClose);
+        pm.on_peer_disconnected(&dialable, DisconnectReason::RemoteClose);
+
+        assert!(pm.is_inbound_only(&inbound_only));
+        assert!(!pm.is_inbound_only(&dialable));
+        let eligible = pm.eligible_peers();
+        assert!(!eligible.contains(&inbound_only));
+        assert!(eligible.contains(&dialable));
+        assert!(pm.get_dialable_peers(&[inbound_only]).is_empty());
+
+        // An outbound connection proves a dial path and clears the flag.
+        pm.on_peer_connected(
+            make_swarm_peer_minimal(0x80),
+            SwarmNodeType::Client,
+            ConnectionDirection::Outbound,
+            TrustLevel::Normal,
+        );
+        assert!(!pm.is_inbound_only(&inbound_only));
+    }
+
+    #[test]
+    fn test_reconnect_with_upgraded_capabilities_refreshes_cache() {
+        let pm = manager();
+        let overlay = test_overlay(1);
+        let connect = || {
+            pm.on_peer_connected(
+                test_swarm_peer(1),
+                SwarmNodeType::Storer,
+                ConnectionDirection::Outbound,
+                TrustLevel::Normal,
+            )
+        };
+
+        // First seen on an older version that negotiates nothing.
+        connect();
+        assert_eq!(
+            pm.record_capabilities(&overlay, PeerCapabilities::NONE),
+            None
+        );
+        pm.on_peer_disconnected(&overlay, DisconnectReason::RemoteClose);
+
+        // The upgraded peer reconnects: the cache outlived the connection, so
+        // the stale set is reported as replaced.
+        connect();
+        assert_eq!(
+            pm.record_capabilities(&overlay, PeerCapabilities::COMPRESSION),
+            Some(PeerCapabilities::NONE)
+        );
+
+        // An unchanged reconnect is not a change.
+        assert_eq!(
+            pm.record_capabilities(&overlay, PeerCapabilities::COMPRESSION),
+            None
+        );
+    }
+
     #[test]
     fn test_gossip_cannot_mutate_trust_or_node_type() {
         let pm = manager();
@@ -2066,6 +2470,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tick_sweeps_every_expired_ban() {
+        let pm = manager();
+        for n in 1..=4 {
+            connect(&pm, n, SwarmNodeType::Client);
+        }
+        let now = unix_timestamp_secs();
+        let expired = |n: u8, ago: u64| {
+            pm.ban_with_expiry(&test_overlay(n), BanCause::Requested, None, Some(now - ago));
+        };
+        expired(1, 3600);
+        expired(2, 1);
+        // Never re-encountered: banned by overlay alone, with no peer entry.
+        expired(5, 60);
+        pm.ban_with_expiry(
+            &test_overlay(3),
+            BanCause::Requested,
+            None,
+            Some(now + 3600),
+        );
+        pm.ban_permanent(&test_overlay(4), BanCause::Requested, None);
+        assert_eq!(pm.banned_count(), 5);
+
+        pm.tick(now);
+        assert_eq!(
+            pm.banned_set(),
+            [test_overlay(3), test_overlay(4)].into_iter().collect(),
+            "only the active timed ban and the permanent ban remain"
+        );
+    }
+
     #[test]
     fn test_permanent_ban_never_expires() {
         let pm = manager();
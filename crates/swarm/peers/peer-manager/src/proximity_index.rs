@@ -6,7 +6,7 @@ use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use hashlink::LinkedHashSet;
 use metrics::gauge;
 use parking_lot::RwLock;
-use vertex_swarm_primitives::{Bin, OverlayAddress};
+use vertex_swarm_primitives::{Bin, DistanceMetric, OverlayAddress, XorDistance};
 
 /// Error returned when adding a peer to the index fails.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error, strum::IntoStaticStr)]
@@ -28,6 +28,8 @@ pub enum AddError {
 pub struct ProximityIndex {
     local_overlay: OverlayAddress,
     max_po: u8,
+    /// Metric that assigns addresses to bins; [`XorDistance`] by default.
+    metric: Arc<dyn DistanceMetric>,
     /// 0 = unbounded.
     max_per_bin: usize,
     bins: Vec<RwLock<LinkedHashSet<OverlayAddress>>>,
@@ -59,6 +61,7 @@ impl ProximityIndex {
         Self {
             local_overlay,
             max_po,
+            metric: Arc::new(XorDistance),
             max_per_bin,
             bins: (0..num_bins)
                 .map(|_| RwLock::new(LinkedHashSet::new()))
@@ -70,6 +73,23 @@ impl ProximityIndex {
         }
     }
 
+    /// Assign bins with `metric` instead of XOR distance.
+    ///
+    /// Bins are computed on insertion, so this is only meaningful on an empty
+    /// index.
+    #[must_use]
+    pub fn with_metric(mut self, metric: Arc<dyn DistanceMetric>) -> Self {
+        debug_assert!(self.is_empty(), "metric changed on a populated index");
+        self.metric = metric;
+        self
+    }
+
+    /// The metric bins are assigned with.
+    #[must_use]
+    pub fn metric(&self) -> &dyn DistanceMetric {
+        self.metric.as_ref()
+    }
+
     #[must_use]
     pub fn local_overlay(&self) -> &OverlayAddress {
         &self.local_overlay
@@ -196,7 +216,11 @@ impl ProximityIndex {
     pub fn bin_for(&self, addr: &OverlayAddress) -> Bin {
         // proximity is range-validated (<= MAX_PO) and we cap at max_po, so the
         // value is always a valid Bin.
-        let po = self.local_overlay.proximity(addr).get().min(self.max_po);
+        let po = self
+            .metric
+            .proximity(&self.local_overlay, addr)
+            .get()
+            .min(self.max_po);
         Bin::new(po).unwrap_or(Bin::MAX)
     }
 
@@ -16,7 +16,7 @@ use vertex_swarm_api::{
 use vertex_swarm_peer_score::ScoreOutcome;
 use vertex_swarm_primitives::OverlayAddress;
 
-use crate::entry::on_health_changed;
+use crate::entry::{on_health_changed, unix_timestamp_secs};
 use crate::manager::PeerManager;
 
 impl<I: SwarmIdentity> PeerManager<I> {
@@ -32,6 +32,10 @@ impl<I: SwarmIdentity> PeerManager<I> {
     /// - `Ban`: ban the peer ([`Self::ban`]), which emits
     ///   [`PeerLifecycleEvent::Banned`].
     ///
+    /// An [`SwarmScoringEvent::InvalidData`] report also counts a strike
+    /// against the configured [`InvalidDataBan`](crate::InvalidDataBan); the
+    /// strike that reaches its threshold bans the peer whatever its score.
+    ///
     /// Reports for unknown peers are dropped: scoring only applies to peers
     /// the manager tracks.
     ///
@@ -137,6 +141,22 @@ impl<I: SwarmIdentity> PeerManager<I> {
                 self.ban(overlay, BanCause::LowScore, Some(reason));
             }
         }
+
+        if let SwarmScoringEvent::InvalidData = event
+            && let Some(policy) = self.invalid_data_ban
+        {
+            let strikes = entry.record_invalid_data(unix_timestamp_secs(), policy.window.as_secs());
+            if strikes >= policy.threshold {
+                warn!(
+                    ?overlay,
+                    strikes,
+                    source = source_label,
+                    "banning peer for repeated invalid data"
+                );
+                let reason = format!("{strikes} invalid-data reports within {:?}", policy.window);
+                self.ban(overlay, BanCause::InvalidData, Some(reason));
+            }
+        }
     }
 
     /// Record round-trip latency for a peer without affecting its score.
@@ -168,6 +188,27 @@ impl<I: SwarmIdentity> PeerManager<I> {
         }
     }
 
+    /// Record a connection that reached the peer but failed protocol
+    /// negotiation: applies the longer incompatible-peer backoff, no score
+    /// change.
+    ///
+    /// The peer is reachable but does not speak our handshake, so redialing
+    /// on the ordinary schedule would only repeat the failure. A later
+    /// completed handshake restores the ordinary schedule.
+    pub fn record_negotiation_failure(&self, overlay: &OverlayAddress) {
+        if let Some(entry) = self.peers.get(overlay) {
+            let old_state = entry.health_state();
+            entry.record_negotiation_failure();
+            on_health_changed(old_state, entry.health_state());
+            debug!(
+                ?overlay,
+                failures = entry.consecutive_failures(),
+                backoff_secs = entry.backoff_remaining().map(|d| d.as_secs()),
+                "recorded protocol negotiation failure with backoff"
+            );
+        }
+    }
+
     /// Record an early disconnect (post-handshake connection that failed quickly).
     ///
     /// Re-arms the dial backoff (the handshake's success reset it) and
@@ -2,17 +2,72 @@
 
 use std::sync::Arc;
 
+use serde::{Deserialize, Deserializer};
 use vertex_net_peer_store::PeerSnapshotStore;
 use vertex_net_peer_store::error::StoreError;
-use vertex_storage::{Database, DatabaseError, DbTx, DbTxMut, Table, table};
-use vertex_swarm_primitives::OverlayAddress;
+use vertex_storage::{
+    Database, DatabaseError, DbTx, DbTxMut, FormatError, Table, Unversioned, Versioned,
+    VersionedFormat, table,
+};
+use vertex_swarm_peer::SwarmPeer;
+use vertex_swarm_primitives::{OverlayAddress, SwarmNodeType};
 
 use crate::entry::PeerSnapshot;
 
 // Single snapshot table: OverlayAddress -> PeerSnapshot. The table name is
 // new with the identity-only record; tables from earlier schemas are simply
 // ignored.
-table!(pub(crate) PeerSnapshotTable, "peer_snapshots", OverlayAddress, PeerSnapshot);
+table!(pub(crate) PeerSnapshotTable, "peer_snapshots", OverlayAddress, Versioned<PeerSnapshot>);
+
+// The same table as written before the versioned envelope: bare records,
+// read back through `PeerSnapshotV1` and rewritten enveloped by the next store.
+table!(pub(crate) LegacyPeerSnapshotTable, "peer_snapshots", OverlayAddress, Unversioned<PeerSnapshot>);
+
+/// Snapshot layout from before `SwarmPeer` carried a serde version: the body
+/// of a version 1 row, and the whole of a row from before the envelope.
+#[derive(Deserialize)]
+struct PeerSnapshotV1 {
+    #[serde(deserialize_with = "SwarmPeer::deserialize_unversioned")]
+    peer: SwarmPeer,
+    node_type: SwarmNodeType,
+    last_seen: u64,
+}
+
+impl From<PeerSnapshotV1> for PeerSnapshot {
+    fn from(old: PeerSnapshotV1) -> Self {
+        Self {
+            peer: old.peer,
+            node_type: old.node_type,
+            last_seen: old.last_seen,
+        }
+    }
+}
+
+// Version 2: the embedded `SwarmPeer` carries its own serde format version.
+// Version 1 rows are read through `PeerSnapshotV1` and rewritten at version 2
+// by the next store.
+impl VersionedFormat for PeerSnapshot {
+    const MAGIC: u8 = 0x50;
+    const VERSION: u8 = 2;
+
+    fn migrate(version: u8, body: &[u8]) -> Result<Self, FormatError> {
+        match version {
+            1 => postcard::from_bytes::<PeerSnapshotV1>(body)
+                .map(Self::from)
+                .map_err(|e| FormatError::Body(e.to_string())),
+            _ => Err(FormatError::UnsupportedVersion {
+                found: version,
+                current: Self::VERSION,
+            }),
+        }
+    }
+
+    fn deserialize_unversioned<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        PeerSnapshotV1::deserialize(deserializer).map(Self::from)
+    }
+}
 
 fn db_err(e: DatabaseError) -> StoreError {
     StoreError::Storage(e.to_string())
@@ -43,9 +98,14 @@ impl<DB: Database> DbPeerSnapshotStore<DB> {
 impl<DB: Database> PeerSnapshotStore<PeerSnapshot> for DbPeerSnapshotStore<DB> {
     fn load(&self) -> Result<Vec<PeerSnapshot>, StoreError> {
         self.db
-            .view(|tx| {
-                let entries = tx.entries::<PeerSnapshotTable>()?;
-                Ok(entries.into_iter().map(|(_, v)| v).collect())
+            .view(|tx| match tx.entries::<PeerSnapshotTable>() {
+                Ok(entries) => Ok(entries.into_iter().map(|(_, v)| v.into_inner()).collect()),
+                // `store` replaces the whole table, so a row that fails the
+                // envelope means the table predates it.
+                Err(_) => {
+                    let entries = tx.entries::<LegacyPeerSnapshotTable>()?;
+                    Ok(entries.into_iter().map(|(_, v)| v.into_inner()).collect())
+                }
             })
             .map_err(db_err)
     }
@@ -55,7 +115,7 @@ impl<DB: Database> PeerSnapshotStore<PeerSnapshot> for DbPeerSnapshotStore<DB> {
             .update(|tx| {
                 tx.clear::<PeerSnapshotTable>()?;
                 for record in records {
-                    tx.put::<PeerSnapshotTable>(*record.peer.overlay(), record.clone())?;
+                    tx.put::<PeerSnapshotTable>(*record.peer.overlay(), Versioned(record.clone()))?;
                 }
                 Ok(())
             })
@@ -130,6 +190,83 @@ mod tests {
         assert!(store.load().unwrap().is_empty());
     }
 
+    /// A pre-encoded postcard row written verbatim: a postcard tuple of `u8`s
+    /// carries no length prefix.
+    #[derive(Debug)]
+    struct RawRow(Vec<u8>);
+
+    impl serde::Serialize for RawRow {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            use serde::ser::SerializeTuple;
+            let mut tuple = serializer.serialize_tuple(self.0.len())?;
+            for byte in &self.0 {
+                tuple.serialize_element(byte)?;
+            }
+            tuple.end()
+        }
+    }
+
+    impl<'de> Deserialize<'de> for RawRow {
+        fn deserialize<D: Deserializer<'de>>(_deserializer: D) -> Result<Self, D::Error> {
+            Err(serde::de::Error::custom("write-only test row"))
+        }
+    }
+
+    table!(RawRows, "peer_snapshots", OverlayAddress, RawRow);
+
+    /// The snapshot in the layout an older build wrote. `peer` leads the
+    /// snapshot and its versioned form is a one-byte version ahead of the
+    /// bare payload, so dropping that byte yields the old layout.
+    fn v1_body(snapshot: &PeerSnapshot) -> Vec<u8> {
+        let current = postcard::to_allocvec(snapshot).unwrap();
+        assert_eq!(current.first(), Some(&1));
+        current.get(1..).unwrap().to_vec()
+    }
+
+    #[test]
+    fn test_version_1_rows_migrate() {
+        let store = setup_store();
+        let snapshot = make_snapshot(4);
+        let mut row = vec![PeerSnapshot::MAGIC, 1];
+        row.extend(v1_body(&snapshot));
+        store
+            .db
+            .update(|tx| tx.put::<RawRows>(*snapshot.peer.overlay(), RawRow(row)))
+            .unwrap();
+
+        let loaded = store.load().unwrap();
+        assert_eq!(loaded.len(), 1);
+        let restored = loaded.first().unwrap();
+        assert_eq!(restored.peer, snapshot.peer);
+        assert_eq!(restored.node_type, snapshot.node_type);
+        assert_eq!(restored.last_seen, snapshot.last_seen);
+    }
+
+    #[test]
+    fn test_rows_from_before_the_envelope_still_load() {
+        let store = setup_store();
+        let snapshot = make_snapshot(3);
+        store
+            .db
+            .update(|tx| tx.put::<RawRows>(*snapshot.peer.overlay(), RawRow(v1_body(&snapshot))))
+            .unwrap();
+
+        let loaded = store.load().unwrap();
+        assert_eq!(loaded.len(), 1);
+        let restored = loaded.first().unwrap();
+        assert_eq!(restored.peer, snapshot.peer);
+        assert_eq!(restored.node_type, snapshot.node_type);
+        assert_eq!(restored.last_seen, snapshot.last_seen);
+
+        // The next store rewrites the table enveloped.
+        store.store(&loaded).unwrap();
+        let enveloped = store
+            .db
+            .view(|tx| tx.entries::<PeerSnapshotTable>())
+            .unwrap();
+        assert_eq!(enveloped.len(), 1);
+    }
+
     #[test]
     fn test_load_on_fresh_store_is_empty() {
         let store = setup_store();
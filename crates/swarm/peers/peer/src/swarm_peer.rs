@@ -57,9 +57,11 @@ pub struct SwarmPeerWire<'a> {
 /// The `ethereum_address` is the EIP-191 signer recovered from the
 /// signature at construction time (sign or parse) and cached. Callers
 /// never need to redo recovery.
+///
+/// With the `serde` feature the record serializes in a versioned form (see
+/// `SERDE_VERSION`), so persisted peers survive later layout changes.
 #[non_exhaustive]
 #[derive(Clone, Debug, PartialEq, Eq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SwarmPeer {
     multiaddrs: Vec<Multiaddr>,
     signature: Signature,
@@ -291,6 +293,14 @@ impl SwarmPeer {
         IpCapability::from_addrs(&self.multiaddrs)
     }
 
+    /// Whether the record advertises any IP address we could dial back.
+    ///
+    /// False for peers that only ever reach us inbound (browsers, hosts
+    /// behind a restrictive NAT): once dropped they cannot be redialed.
+    pub fn is_dialable(&self) -> bool {
+        self.ip_capability() != IpCapability::None
+    }
+
     /// Filter multiaddrs multiaddrs by address scope.
     pub fn addrs_by_scope(&self, scope: AddressScope) -> Vec<Multiaddr> {
         self.multiaddrs
@@ -342,6 +352,170 @@ fn parse_chequebook(bytes: &[u8]) -> Result<Option<Address>, SwarmPeerError> {
     }
 }
 
+#[cfg(feature = "serde")]
+pub use versioned::SERDE_VERSION;
+
+/// Versioned serde form of [`SwarmPeer`].
+///
+/// A peer serializes as a two-field struct: the format `version`, then the
+/// `payload` laid out as that version defines. Deserialization dispatches on
+/// the version, so a later layout can add fields and still read older data,
+/// while a version this build does not know is refused with an error naming
+/// it instead of being misparsed as the current layout.
+#[cfg(feature = "serde")]
+mod versioned {
+    use std::fmt;
+
+    use serde::de::{self, MapAccess, SeqAccess, Visitor};
+    use serde::ser::SerializeStruct;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    /// `SwarmPeer` serde format version this build writes.
+    pub const SERDE_VERSION: u8 = 1;
+
+    const FIELDS: &[&str] = &["version", "payload"];
+
+    /// Version 1 payload, borrowed for serialization.
+    #[derive(Serialize)]
+    struct PayloadV1Ref<'a> {
+        multiaddrs: &'a [Multiaddr],
+        signature: &'a Signature,
+        overlay: &'a SwarmAddress,
+        nonce: &'a Nonce,
+        timestamp: &'a Timestamp,
+        chequebook: &'a Option<Address>,
+        ethereum_address: &'a Address,
+    }
+
+    /// Version 1 payload.
+    #[derive(Deserialize)]
+    struct PayloadV1 {
+        multiaddrs: Vec<Multiaddr>,
+        signature: Signature,
+        overlay: SwarmAddress,
+        nonce: Nonce,
+        timestamp: Timestamp,
+        chequebook: Option<Address>,
+        ethereum_address: Address,
+    }
+
+    impl From<PayloadV1> for SwarmPeer {
+        fn from(payload: PayloadV1) -> Self {
+            Self {
+                multiaddrs: payload.multiaddrs,
+                signature: payload.signature,
+                overlay: payload.overlay,
+                nonce: payload.nonce,
+                timestamp: payload.timestamp,
+                chequebook: payload.chequebook,
+                ethereum_address: payload.ethereum_address,
+            }
+        }
+    }
+
+    impl SwarmPeer {
+        /// Decode the form written before the serde version existed: the
+        /// version 1 payload on its own, with no version field ahead of it.
+        ///
+        /// For stores reading rows persisted by older builds; fit for
+        /// `#[serde(deserialize_with = ...)]`.
+        pub fn deserialize_unversioned<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            PayloadV1::deserialize(deserializer).map(Self::from)
+        }
+    }
+
+    fn unsupported<E: de::Error>(found: u8) -> E {
+        E::custom(format_args!(
+            "unsupported SwarmPeer format version {found} (this build reads up to {SERDE_VERSION})"
+        ))
+    }
+
+    impl Serialize for SwarmPeer {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("SwarmPeer", FIELDS.len())?;
+            state.serialize_field("version", &SERDE_VERSION)?;
+            state.serialize_field(
+                "payload",
+                &PayloadV1Ref {
+                    multiaddrs: &self.multiaddrs,
+                    signature: &self.signature,
+                    overlay: &self.overlay,
+                    nonce: &self.nonce,
+                    timestamp: &self.timestamp,
+                    chequebook: &self.chequebook,
+                    ethereum_address: &self.ethereum_address,
+                },
+            )?;
+            state.end()
+        }
+    }
+
+    impl<'de> Deserialize<'de> for SwarmPeer {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_struct("SwarmPeer", FIELDS, SwarmPeerVisitor)
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[serde(field_identifier, rename_all = "lowercase")]
+    enum Field {
+        Version,
+        Payload,
+    }
+
+    struct SwarmPeerVisitor;
+
+    impl<'de> Visitor<'de> for SwarmPeerVisitor {
+        type Value = SwarmPeer;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a versioned SwarmPeer")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<SwarmPeer, A::Error> {
+            let version: u8 = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            match version {
+                1 => seq
+                    .next_element::<PayloadV1>()?
+                    .map(SwarmPeer::from)
+                    .ok_or_else(|| de::Error::invalid_length(1, &self)),
+                other => Err(unsupported(other)),
+            }
+        }
+
+        /// Self-describing formats must carry `version` ahead of `payload`,
+        /// as [`Serialize`] writes it: the payload cannot be decoded before
+        /// its layout is known.
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<SwarmPeer, A::Error> {
+            match map.next_key()? {
+                Some(Field::Version) => {}
+                Some(Field::Payload) => {
+                    return Err(de::Error::custom(
+                        "SwarmPeer `version` must precede `payload`",
+                    ));
+                }
+                None => return Err(de::Error::missing_field("version")),
+            }
+            let version: u8 = map.next_value()?;
+            match map.next_key()? {
+                Some(Field::Payload) => {}
+                Some(Field::Version) => return Err(de::Error::duplicate_field("version")),
+                None => return Err(de::Error::missing_field("payload")),
+            }
+            match version {
+                1 => map.next_value::<PayloadV1>().map(SwarmPeer::from),
+                other => Err(unsupported(other)),
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 #[allow(
     clippy::unwrap_used,
@@ -674,4 +848,44 @@ mod tests {
         );
         assert!(matches!(res, Err(SwarmPeerError::InvalidOverlay)));
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_form_is_versioned() {
+        let identity = test_identity(NetworkId::new(1), Nonce::from([0x22u8; 32]));
+        let multiaddrs: Vec<Multiaddr> = vec!["/ip4/127.0.0.1/tcp/1634".parse().unwrap()];
+        let peer = SwarmPeer::sign(
+            &identity,
+            multiaddrs,
+            Timestamp::from_seconds(now_secs()),
+            Some(Address::from([0xAB; 20])),
+        )
+        .unwrap();
+
+        // The current version round-trips in both compact and
+        // self-describing formats.
+        let mut bytes = postcard::to_allocvec(&peer).unwrap();
+        assert_eq!(bytes.first(), Some(&SERDE_VERSION));
+        assert_eq!(postcard::from_bytes::<SwarmPeer>(&bytes).unwrap(), peer);
+        let json = serde_json::to_value(&peer).unwrap();
+        assert_eq!(json["version"], SERDE_VERSION);
+        assert_eq!(
+            serde_json::from_value::<SwarmPeer>(json.clone()).unwrap(),
+            peer
+        );
+
+        // A version this build does not know is refused by name, not
+        // misparsed as the current layout.
+        *bytes.first_mut().unwrap() = SERDE_VERSION + 1;
+        let err = postcard::from_bytes::<SwarmPeer>(&bytes).unwrap_err();
+        assert!(matches!(err, postcard::Error::SerdeDeCustom));
+        let mut json = json;
+        json["version"] = (SERDE_VERSION + 1).into();
+        let err = serde_json::from_value::<SwarmPeer>(json).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("unsupported SwarmPeer format version 2"),
+            "{err}"
+        );
+    }
 }
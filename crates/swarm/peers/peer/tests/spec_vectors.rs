@@ -0,0 +1,178 @@
+//! Fixture-driven spec vectors for the three derivations every conforming Swarm
+//! node must reproduce byte for byte:
+//!
+//! 1. Overlay: `keccak256(eth_address || network_id_le(8) || nonce(32))`.
+//! 2. Content-chunk address: the BMT root over `span(8) || payload`.
+//! 3. Handshake sign-data: `"bee-handshake-" || multiaddrs || overlay ||
+//!    network_id_be(8) || nonce(32) || timestamp_be(8) || chequebook(20)`.
+//!
+//! The vectors live in `fixtures/spec_vectors.json` so new cases are data, not
+//! code; its `_provenance` header names the source of each category. Sign-data
+//! vectors pin the raw pre-image, so an endianness slip in any field (the
+//! network id is little-endian in the overlay and big-endian in sign-data)
+//! surfaces here before it reaches a signature. Those carrying a reference
+//! signature must also recover its signer over the pinned pre-image.
+
+#![allow(
+    clippy::expect_used,
+    clippy::unwrap_used,
+    reason = "conformance fixtures: panicking on malformed test inputs is intended"
+)]
+
+use alloy_primitives::{Address, Signature, hex};
+use libp2p::Multiaddr;
+use nectar_primitives::signing::sign_data;
+use nectar_primitives::{Chunk, DefaultContentChunk, SwarmAddress};
+use serde::Deserialize;
+use vertex_swarm_peer::serialize_multiaddrs;
+use vertex_swarm_primitives::{NetworkId, Nonce, Timestamp, compute_overlay};
+
+const FIXTURE: &str = include_str!("fixtures/spec_vectors.json");
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpecVectors {
+    overlay: Vec<OverlayVector>,
+    content_chunk: Vec<ChunkVector>,
+    handshake_sign_data: Vec<SignDataVector>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OverlayVector {
+    ethereum_address: String,
+    network_id: u64,
+    nonce: String,
+    overlay: String,
+}
+
+#[derive(Deserialize)]
+struct ChunkVector {
+    /// `span || payload`, hex.
+    data: String,
+    address: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignDataVector {
+    multiaddrs: Vec<String>,
+    overlay: String,
+    network_id: u64,
+    nonce: String,
+    timestamp: i64,
+    chequebook: Option<String>,
+    sign_data: String,
+    /// Address the reference signature recovers to, when there is one.
+    signer: Option<String>,
+    /// Reference EIP-191 signature over `sign_data` (`r || s || v`).
+    signature: Option<String>,
+}
+
+fn load() -> SpecVectors {
+    let vectors: SpecVectors = serde_json::from_str(FIXTURE).expect("fixture parses");
+    // The suite is only meaningful with real coverage in every category.
+    assert!(
+        vectors.overlay.len() >= 3,
+        "need at least three overlay vectors"
+    );
+    assert!(
+        vectors.content_chunk.len() >= 3,
+        "need at least three chunk vectors"
+    );
+    assert!(
+        vectors.handshake_sign_data.len() >= 3,
+        "need at least three sign-data vectors"
+    );
+    assert!(
+        vectors
+            .handshake_sign_data
+            .iter()
+            .filter(|v| v.signature.is_some())
+            .count()
+            >= 2,
+        "need at least two reference-signed sign-data vectors"
+    );
+    vectors
+}
+
+fn bytes<const N: usize>(value: &str) -> [u8; N] {
+    hex::decode(value)
+        .expect("valid hex")
+        .try_into()
+        .unwrap_or_else(|v: Vec<u8>| panic!("expected {N} bytes, got {}", v.len()))
+}
+
+#[test]
+fn overlay_vectors() {
+    for (idx, v) in load().overlay.iter().enumerate() {
+        let got = compute_overlay(
+            &Address::from(bytes::<20>(&v.ethereum_address)),
+            NetworkId::new(v.network_id),
+            &Nonce::from(bytes::<32>(&v.nonce)),
+        );
+        assert_eq!(
+            got,
+            SwarmAddress::from(bytes::<32>(&v.overlay)),
+            "overlay vector {idx}"
+        );
+    }
+}
+
+#[test]
+fn content_chunk_address_vectors() {
+    for (idx, v) in load().content_chunk.iter().enumerate() {
+        let data = hex::decode(&v.data).expect("valid hex");
+        let chunk = DefaultContentChunk::try_from(data.as_slice())
+            .unwrap_or_else(|err| panic!("chunk vector {idx}: decode failed: {err}"));
+        assert_eq!(
+            hex::encode(chunk.address().as_slice()),
+            v.address,
+            "chunk vector {idx}"
+        );
+    }
+}
+
+#[test]
+fn handshake_sign_data_vectors() {
+    for (idx, v) in load().handshake_sign_data.iter().enumerate() {
+        let multiaddrs: Vec<Multiaddr> = v
+            .multiaddrs
+            .iter()
+            .map(|addr| addr.parse().expect("valid multiaddr"))
+            .collect();
+        let chequebook = v
+            .chequebook
+            .as_deref()
+            .map(|addr| Address::from(bytes::<20>(addr)));
+
+        let got = sign_data(
+            &serialize_multiaddrs(&multiaddrs),
+            &SwarmAddress::from(bytes::<32>(&v.overlay)),
+            NetworkId::new(v.network_id),
+            &Nonce::from(bytes::<32>(&v.nonce)),
+            Timestamp::from_seconds(v.timestamp),
+            chequebook.as_ref(),
+        );
+        assert_eq!(hex::encode(&got[..]), v.sign_data, "sign-data vector {idx}");
+    }
+}
+
+#[test]
+fn reference_signatures_recover_over_the_sign_data() {
+    for (idx, v) in load().handshake_sign_data.iter().enumerate() {
+        let (Some(signature), Some(signer)) = (&v.signature, &v.signer) else {
+            continue;
+        };
+        let signature =
+            Signature::from_raw(&bytes::<65>(signature)).expect("valid signature literal");
+        let recovered = signature
+            .recover_address_from_msg(hex::decode(&v.sign_data).expect("valid hex"))
+            .unwrap_or_else(|err| panic!("sign-data vector {idx}: recovery failed: {err}"));
+        assert_eq!(
+            recovered,
+            Address::from(bytes::<20>(signer)),
+            "sign-data vector {idx}"
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Text form of Swarm addresses for logs and RPC.
+//!
+//! Every operator surface prints an address the same way: lowercase hex with
+//! no prefix, or its first four bytes when a log line only needs to tell peers
+//! apart. Parsing accepts an optional `0x` prefix and refuses anything that is
+//! not exactly 32 bytes.
+
+use alloc::string::String;
+
+use alloy_primitives::hex;
+use nectar_primitives::SwarmAddress;
+
+/// Bytes of an address.
+const ADDRESS_LEN: usize = 32;
+
+/// Bytes kept by [`SwarmAddressExt::to_short_hex`].
+const SHORT_LEN: usize = 4;
+
+/// Why a string is not a Swarm address.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum AddressParseError {
+    /// The input is not hex.
+    #[error("invalid address hex: {0}")]
+    InvalidHex(#[from] hex::FromHexError),
+    /// The input decodes to the wrong number of bytes.
+    #[error("invalid address: expected {ADDRESS_LEN} bytes, got {0}")]
+    WrongLength(usize),
+}
+
+/// Hex formatting and parsing for [`SwarmAddress`] (overlay and chunk
+/// addresses alike).
+pub trait SwarmAddressExt: Sized {
+    /// Full lowercase hex, no prefix.
+    fn to_hex(&self) -> String;
+
+    /// The first four bytes as hex, for log lines.
+    fn to_short_hex(&self) -> String;
+
+    /// Parse 32 bytes of hex, with or without a `0x` prefix.
+    fn from_hex(s: &str) -> Result<Self, AddressParseError>;
+}
+
+impl SwarmAddressExt for SwarmAddress {
+    fn to_hex(&self) -> String {
+        hex::encode(self.as_bytes())
+    }
+
+    fn to_short_hex(&self) -> String {
+        hex::encode(self.as_bytes().get(..SHORT_LEN).unwrap_or_default())
+    }
+
+    fn from_hex(s: &str) -> Result<Self, AddressParseError> {
+        let bytes = hex::decode(s.strip_prefix("0x").unwrap_or(s))?;
+        if bytes.len() != ADDRESS_LEN {
+            return Err(AddressParseError::WrongLength(bytes.len()));
+        }
+        Self::from_slice(&bytes).map_err(|_| AddressParseError::WrongLength(bytes.len()))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn address() -> SwarmAddress {
+        let mut bytes = [0u8; ADDRESS_LEN];
+        for (byte, value) in bytes.iter_mut().zip([0xde, 0xad, 0xbe, 0xef]) {
+            *byte = value;
+        }
+        if let Some(last) = bytes.last_mut() {
+            *last = 0x01;
+        }
+        SwarmAddress::from(bytes)
+    }
+
+    #[test]
+    fn hex_roundtrips_with_or_without_prefix() {
+        let address = address();
+        let hex = address.to_hex();
+        assert_eq!(hex.len(), 2 * ADDRESS_LEN);
+        assert!(hex.starts_with("deadbeef"));
+        assert!(hex.ends_with("01"));
+
+        assert_eq!(SwarmAddress::from_hex(&hex).unwrap(), address);
+        assert_eq!(
+            SwarmAddress::from_hex(&format!("0x{hex}")).unwrap(),
+            address
+        );
+    }
+
+    #[test]
+    fn wrong_length_or_non_hex_is_refused() {
+        assert_eq!(
+            SwarmAddress::from_hex("abcd"),
+            Err(AddressParseError::WrongLength(2))
+        );
+        let long = "00".repeat(ADDRESS_LEN + 1);
+        assert_eq!(
+            SwarmAddress::from_hex(&long),
+            Err(AddressParseError::WrongLength(ADDRESS_LEN + 1))
+        );
+        assert!(matches!(
+            SwarmAddress::from_hex(&"zz".repeat(ADDRESS_LEN)),
+            Err(AddressParseError::InvalidHex(_))
+        ));
+    }
+
+    #[test]
+    fn short_form_is_the_first_four_bytes() {
+        assert_eq!(address().to_short_hex(), "deadbeef");
+    }
+}
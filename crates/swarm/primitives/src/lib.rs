@@ -30,18 +30,29 @@
 //!   not cross-comparable. It carries no `contains` membership method, since
 //!   that connotation belongs only to the local connectivity boundary.
 //!
+//! Routing tables compute `ProximityOrder` through a [`DistanceMetric`]
+//! ([`XorDistance`] unless overridden for research).
+//!
 //! Enumerate the bin space only through [`all_bins`], [`balanced_bins`], and
 //! [`neighborhood_bins`] (the sole places a `Bin` is built from a raw index).
 //! Extract the raw `u8` with `.get()` only at edges (logs, metrics, the wire).
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+mod address;
+mod content;
+mod distance;
 mod signer;
 mod stamped;
 mod validated;
 
+pub use address::{AddressParseError, SwarmAddressExt};
+pub use content::content_address;
+pub use distance::{DistanceMetric, XorDistance};
 pub use signer::{OverlaySigner, Signer, SignerSync};
-pub use stamped::{CachedChunk, StampedChunk, StampedChunkExt, VerifiedStampedChunk};
+pub use stamped::{Cacheability, CachedChunk, StampedChunk, StampedChunkExt, VerifiedStampedChunk};
 pub use validated::{ValidatedChunk, ValidationError};
 
 // Re-export canonical Swarm primitives from nectar. See the crate-level docs
@@ -201,6 +212,7 @@ pub type OverlayAddress = SwarmAddress;
     Default,
     Hash,
     strum::Display,
+    strum::EnumString,
     strum::FromRepr,
     strum::IntoStaticStr,
 )]
@@ -281,6 +293,74 @@ impl SwarmNodeType {
             SwarmNodeType::Client => swap_enabled,
         }
     }
+
+    /// Default transport-level cap on established connections, used when the
+    /// configuration sets none.
+    ///
+    /// A saturated routing table sits in the 200-300 range, so every cap stays
+    /// above that. Storers also connect to every neighborhood peer and take
+    /// more headroom. Bootnodes are dialled by every joining node and answer
+    /// with hive gossip, so they take the highest cap. Clients only route their
+    /// own requests and keep the moderate cap every node used before.
+    pub const fn default_max_peers(&self) -> usize {
+        match self {
+            SwarmNodeType::Bootnode => 1000,
+            SwarmNodeType::Client => 400,
+            SwarmNodeType::Storer => 500,
+        }
+    }
+}
+
+/// Optional protocol features a peer advertises in the handshake.
+///
+/// A bit set carried on the wire as a `u64`. A feature is used on a connection
+/// only when both ends advertise it, so the set stored for a connected peer is
+/// the [`intersection`](Self::intersection) of ours and theirs. Unknown bits
+/// from a newer peer are kept on decode and drop out of the intersection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct PeerCapabilities(u64);
+
+impl PeerCapabilities {
+    /// No optional features.
+    pub const NONE: Self = Self(0);
+
+    /// zstd compression of large protocol payloads.
+    pub const COMPRESSION: Self = Self(1);
+
+    /// Build from the wire bits.
+    #[inline]
+    #[must_use]
+    pub const fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    /// The wire bits.
+    #[inline]
+    #[must_use]
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Whether every feature in `other` is in `self`.
+    #[inline]
+    #[must_use]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Features in both sets: what a connection between the two may use.
+    #[inline]
+    #[must_use]
+    pub const fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// `self` without the features in `other`.
+    #[inline]
+    #[must_use]
+    pub const fn without(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
 }
 
 /// Named bundle of topology pacing parameters.
@@ -458,6 +538,27 @@ mod tests {
         assert!(SwarmNodeType::Client.needs_chain(true));
     }
 
+    #[test]
+    fn capabilities_are_used_only_when_both_ends_advertise() {
+        let both = PeerCapabilities::COMPRESSION.intersection(PeerCapabilities::COMPRESSION);
+        assert!(both.contains(PeerCapabilities::COMPRESSION));
+
+        let mixed = PeerCapabilities::COMPRESSION.intersection(PeerCapabilities::NONE);
+        assert_eq!(mixed, PeerCapabilities::NONE);
+
+        // An unknown bit from a newer peer survives decode but not negotiation.
+        let newer = PeerCapabilities::from_bits(0b110);
+        assert_eq!(newer.bits(), 0b110);
+        assert_eq!(
+            PeerCapabilities::COMPRESSION.intersection(newer),
+            PeerCapabilities::NONE
+        );
+        assert_eq!(
+            PeerCapabilities::COMPRESSION.without(PeerCapabilities::COMPRESSION),
+            PeerCapabilities::NONE
+        );
+    }
+
     #[test]
     fn connection_profile_default_by_node_type() {
         assert_eq!(
@@ -105,6 +105,55 @@ impl CachedChunk {
     pub fn into_parts(self) -> (AnyChunk, Option<Stamp>) {
         (self.chunk, self.stamp)
     }
+
+    #[inline]
+    #[must_use]
+    pub fn cacheability(&self) -> Cacheability {
+        Cacheability::of(&self.chunk)
+    }
+
+    /// Whether a cache may hold this chunk: an immutable chunk always, a
+    /// mutable one only with a stamp whose timestamp bounds its freshness.
+    #[inline]
+    #[must_use]
+    pub fn is_cacheable(&self) -> bool {
+        self.cacheability().is_immutable() || self.stamp.is_some()
+    }
+}
+
+/// Whether a cached copy of a chunk can go stale, read from the chunk itself.
+///
+/// Derived from the verified chunk type rather than from anything a peer
+/// claims: a remote hint that a single-owner chunk is immutable would let the
+/// peer pin a stale revision in our cache indefinitely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cacheability {
+    /// The address commits to the bytes (a content chunk), so a cached copy is
+    /// valid forever and needs no expiry.
+    Immutable,
+    /// The owner may re-sign new content at the same address (a single-owner
+    /// chunk), so a cached copy is only as good as its stamp timestamp.
+    Mutable,
+}
+
+impl Cacheability {
+    /// The cacheability of `chunk`. Anything not content-addressed is treated
+    /// as mutable.
+    #[inline]
+    #[must_use]
+    pub fn of(chunk: &AnyChunk) -> Self {
+        if chunk.is_content() {
+            Self::Immutable
+        } else {
+            Self::Mutable
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn is_immutable(self) -> bool {
+        matches!(self, Self::Immutable)
+    }
 }
 
 impl From<StampedChunk> for CachedChunk {
@@ -210,4 +259,17 @@ mod tests {
             .expect_err("wrong address must fail");
         assert!(matches!(err, StampError::Chunk(_)));
     }
+
+    #[test]
+    fn mutable_chunks_need_a_stamp_to_be_cacheable() {
+        let content = CachedChunk::new(content_chunk().into(), None);
+        assert_eq!(content.cacheability(), Cacheability::Immutable);
+        assert!(content.is_cacheable());
+
+        let soc: AnyChunk = single_owner_chunk().into();
+        let stampless = CachedChunk::new(soc.clone(), None);
+        assert_eq!(stampless.cacheability(), Cacheability::Mutable);
+        assert!(!stampless.is_cacheable());
+        assert!(CachedChunk::new(soc, Some(test_stamp())).is_cacheable());
+    }
 }
@@ -2,6 +2,12 @@
 //!
 //! [`ValidatedChunk<C>`] can only be created through validation, providing
 //! compile-time guarantees that chunks have been checked against a [`ChunkTypeSet`].
+//!
+//! Validation checks the chunk type only. An [`AnyChunk`] cannot hold an
+//! address its bytes do not produce: nectar hashes a content chunk and
+//! recovers a single-owner chunk's owner when building it, and the wire
+//! codecs reject a chunk whose address differs from the one requested or
+//! pushed. Re-hashing here would repeat that work on every ingest.
 
 use core::marker::PhantomData;
 
@@ -42,18 +48,24 @@ impl<C: ChunkTypeSet> ValidatedChunk<C> {
     ///
     /// Returns [`ValidationError`] if the chunk's type is not supported by `C`.
     pub fn new(chunk: AnyChunk) -> Result<Self, ValidationError> {
-        if !C::supports(chunk.type_id()) {
-            return Err(ValidationError {
-                chunk_type: chunk.type_id(),
-                reason: "chunk type not supported by this chunk set",
-            });
-        }
+        Self::check_type(&chunk)?;
         Ok(Self {
             inner: chunk,
             _marker: PhantomData,
         })
     }
 
+    fn check_type(chunk: &AnyChunk) -> Result<(), ValidationError> {
+        if C::supports(chunk.type_id()) {
+            Ok(())
+        } else {
+            Err(ValidationError {
+                chunk_type: chunk.type_id(),
+                reason: "chunk type not supported by this chunk set",
+            })
+        }
+    }
+
     /// Create without validation.
     ///
     /// # Safety
@@ -18,7 +18,8 @@ pub use seams::{
     NeighbourSource, PullsyncControl, PullsyncEvent, ReadinessGate, ReserveAdmit, SyncTarget,
 };
 pub use service::{
-    BuiltPuller, DEFAULT_EVENT_CAPACITY, DEFAULT_PEER_RESPONSE_TIMEOUT, DEFAULT_TAIL_BACKOFF,
-    Puller, PullerConfig, PullerHandle, PullerSeams, build_puller, spawn_puller,
+    BuiltPuller, DEFAULT_BATCH_SIZE, DEFAULT_EVENT_CAPACITY, DEFAULT_MAX_IN_FLIGHT,
+    DEFAULT_PEER_RESPONSE_TIMEOUT, DEFAULT_TAIL_BACKOFF, Puller, PullerConfig, PullerHandle,
+    PullerSeams, PullerStats, build_puller, newly_in_range, spawn_puller,
 };
 pub use verifier::{FundingVerifier, SignatureVerifier};
@@ -8,7 +8,7 @@
 use std::future::Future;
 
 use libp2p::PeerId;
-use vertex_swarm_api::SwarmResult;
+use vertex_swarm_api::{NetworkStore, SwarmResult};
 use vertex_swarm_primitives::{Bin, OverlayAddress, StampedChunk};
 
 pub use vertex_swarm_storer_behaviour::PullsyncEvent;
@@ -23,8 +23,9 @@ pub trait PullsyncControl: Send + Sync {
     /// Open the cursor handshake against `peer`.
     fn fetch_cursors(&self, peer: PeerId, request_id: u64);
 
-    /// Open a range exchange against `peer` for `bin` from `start`.
-    fn sync_range(&self, peer: PeerId, request_id: u64, bin: Bin, start: u64);
+    /// Open a range exchange against `peer` for `bin` from `start`, asking for
+    /// at most `limit` chunks.
+    fn sync_range(&self, peer: PeerId, request_id: u64, bin: Bin, start: u64, limit: u64);
 }
 
 /// Readiness gate the puller awaits before each sync pass.
@@ -59,22 +60,17 @@ pub trait NeighbourSource: Send + Sync {
 
 /// Reserve admission put seam.
 ///
-/// A blanket impl bridges any [`SwarmLocalStore`] (the storer reserve), so the
-/// puller crate depends on no storer backend.
-///
-/// [`SwarmLocalStore`]: vertex_swarm_api::SwarmLocalStore
+/// Implemented for [`NetworkStore`], the reserve as network ingest sees it,
+/// so the puller crate depends on no storer backend and every pulled chunk is
+/// validated against the network's chunk set.
 pub trait ReserveAdmit: Send + Sync {
     /// Admit a verified, stamped chunk to the reserve.
     fn admit(&self, chunk: StampedChunk) -> SwarmResult<()>;
 }
 
-/// Bridge any [`SwarmLocalStore`] to the put seam, converting the chunk to the
-/// always-stamped [`CachedChunk`] the reserve expects.
-///
-/// [`SwarmLocalStore`]: vertex_swarm_api::SwarmLocalStore
-/// [`CachedChunk`]: vertex_swarm_primitives::CachedChunk
-impl<S: vertex_swarm_api::SwarmLocalStore> ReserveAdmit for S {
+impl ReserveAdmit for NetworkStore {
     fn admit(&self, chunk: StampedChunk) -> SwarmResult<()> {
-        self.put(vertex_swarm_primitives::CachedChunk::from(chunk))
+        let (chunk, stamp) = chunk.into_parts();
+        self.put(chunk, Some(stamp))
     }
 }
@@ -6,8 +6,20 @@
 //! in-scope bin drives `sync_range` from the persisted interval upward,
 //! verifying and admitting each delivered chunk before advancing the interval.
 //! When caught up it backs off and re-passes (live tail).
-
-use std::collections::HashSet;
+//!
+//! Range requests are bounded by [`PullerConfig::batch_size`] and at most
+//! [`PullerConfig::max_in_flight`] are outstanding per peer; a bin's next batch
+//! is requested only once the current one is applied. Progress is reported
+//! through [`PullerHandle::stats`].
+//!
+//! A depth decrease widens the neighbourhood, so the bins between the new and
+//! old depth become the node's responsibility. [`PullerHandle::depth_changed`]
+//! queues those bins and the loop reindexes them at once instead of waiting out
+//! the tail backoff.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
 use libp2p::PeerId;
@@ -16,7 +28,9 @@ use tracing::{debug, warn};
 use vertex_swarm_api::{
     IntervalStore, PeerReporter, PullChunkVerifier, ReportSource, SwarmScoringEvent,
 };
-use vertex_swarm_primitives::{Bin, OverlayAddress};
+use vertex_swarm_primitives::{
+    Bin, NeighborhoodDepth, OverlayAddress, SwarmAddressExt, balanced_bins,
+};
 use vertex_tasks::{GracefulShutdown, MaybeSend, SpawnableTask, time};
 
 use crate::seams::{
@@ -33,6 +47,12 @@ pub const DEFAULT_TAIL_BACKOFF: Duration = Duration::from_secs(5);
 /// handler's own outbound and read bounds so a slow exchange is not cut off.
 pub const DEFAULT_PEER_RESPONSE_TIMEOUT: Duration = Duration::from_secs(45);
 
+/// Chunks asked for per range request; the responder's own page cap.
+pub const DEFAULT_BATCH_SIZE: u64 = 250;
+
+/// Range requests outstanding per peer at once.
+pub const DEFAULT_MAX_IN_FLIGHT: usize = 1;
+
 /// Tuning for the [`Puller`] loop.
 #[derive(Debug, Clone, Copy)]
 pub struct PullerConfig {
@@ -42,6 +62,13 @@ pub struct PullerConfig {
     /// never-connected or silent peer yields no `Failed` event, so without this
     /// the per-peer await blocks the whole pass forever.
     pub peer_response_timeout: Duration,
+    /// Most chunks asked for in one range request. The responder caps it at
+    /// its own page size. The limit is gated on `SwarmHardfork::Accord`: it is
+    /// only sent and honoured once Accord is active.
+    pub batch_size: u64,
+    /// Most range requests outstanding against one peer, across its bins. A
+    /// bin never has more than one, since its next start is the last topmost.
+    pub max_in_flight: usize,
 }
 
 impl Default for PullerConfig {
@@ -49,6 +76,55 @@ impl Default for PullerConfig {
         Self {
             tail_backoff: DEFAULT_TAIL_BACKOFF,
             peer_response_timeout: DEFAULT_PEER_RESPONSE_TIMEOUT,
+            batch_size: DEFAULT_BATCH_SIZE,
+            max_in_flight: DEFAULT_MAX_IN_FLIGHT,
+        }
+    }
+}
+
+/// Point-in-time pull-sync progress.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PullerStats {
+    /// Chunks verified and admitted to the reserve since the puller started.
+    pub chunks_synced: u64,
+    /// Range pages delivered and checked since the puller started.
+    pub pages_synced: u64,
+    /// Mean admission rate since the puller started.
+    pub chunks_per_second: f64,
+}
+
+/// Progress counters shared between the loop and its handle.
+struct Progress {
+    started: time::Instant,
+    chunks: AtomicU64,
+    pages: AtomicU64,
+}
+
+impl Progress {
+    fn new() -> Self {
+        Self {
+            started: time::Instant::now(),
+            chunks: AtomicU64::new(0),
+            pages: AtomicU64::new(0),
+        }
+    }
+
+    fn record(&self, chunks: u64) {
+        self.chunks.fetch_add(chunks, Ordering::Relaxed);
+        self.pages.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn stats(&self) -> PullerStats {
+        let chunks_synced = self.chunks.load(Ordering::Relaxed);
+        let elapsed = self.started.elapsed().as_secs_f64();
+        PullerStats {
+            chunks_synced,
+            pages_synced: self.pages.load(Ordering::Relaxed),
+            chunks_per_second: if elapsed > 0.0 {
+                chunks_synced as f64 / elapsed
+            } else {
+                0.0
+            },
         }
     }
 }
@@ -92,6 +168,9 @@ pub struct Puller<C, E, S, V, A, G, N, R> {
     /// timed-out command cannot be matched to the next command for the same
     /// peer and bin. Local to the in-process surface; never on the wire.
     next_request_id: u64,
+    /// Bins a depth decrease brought into range, fed by [`PullerHandle`].
+    reindex: Option<mpsc::Receiver<Vec<Bin>>>,
+    progress: Arc<Progress>,
 }
 
 impl<C, S, V, A, G, N, R> Puller<C, PullsyncEvent, S, V, A, G, N, R>
@@ -130,9 +209,22 @@ where
             reporter,
             config,
             next_request_id: 0,
+            reindex: None,
+            progress: Arc::new(Progress::new()),
         }
     }
 
+    /// Reindex the bins received on `reindex` as they arrive.
+    pub fn with_reindex(mut self, reindex: mpsc::Receiver<Vec<Bin>>) -> Self {
+        self.reindex = Some(reindex);
+        self
+    }
+
+    /// Sync progress so far.
+    pub fn stats(&self) -> PullerStats {
+        self.progress.stats()
+    }
+
     /// Next outbound command id; wraps after `u64::MAX` commands, which the
     /// await never confuses for a stale in-flight reply.
     fn next_request_id(&mut self) -> u64 {
@@ -163,12 +255,22 @@ where
                 () = self.sync_pass() => {}
             }
 
-            tokio::select! {
+            let reindex = tokio::select! {
                 guard = &mut shutdown => {
                     drop(guard);
                     break;
                 }
-                () = time::sleep(self.config.tail_backoff) => {}
+                () = time::sleep(self.config.tail_backoff) => None,
+                Some(bins) = next_reindex(&mut self.reindex) => Some(bins),
+            };
+            if let Some(bins) = reindex {
+                tokio::select! {
+                    guard = &mut shutdown => {
+                        drop(guard);
+                        return;
+                    }
+                    () = self.reindex(&bins) => {}
+                }
             }
         }
         debug!("puller shutdown complete");
@@ -195,6 +297,25 @@ where
         }
     }
 
+    /// Sync only `bins` from every current neighbour that serves them.
+    ///
+    /// Driven by a depth decrease: the newly in-range bins are pulled without
+    /// waiting for the next full pass. Skips the readiness gate, since losing
+    /// neighbours is what shrank the depth.
+    pub async fn reindex(&mut self, bins: &[Bin]) {
+        debug!(?bins, "puller reindexing newly in-range bins");
+        let mut rejected = HashSet::new();
+        for mut target in self.neighbours.targets() {
+            target.bins.retain(|bin| bins.contains(bin));
+            if target.bins.is_empty() || rejected.contains(&target.peer) {
+                continue;
+            }
+            if self.sync_peer(&target).await {
+                rejected.insert(target.peer);
+            }
+        }
+    }
+
     /// Fetch a peer's cursors, reconcile its epoch, then sync each in-scope bin.
     ///
     /// Returns `true` if a delivered chunk failed verification, so the caller
@@ -209,16 +330,11 @@ where
         };
 
         if let Err(e) = self.reconcile_epoch(&target.overlay, epoch) {
-            warn!(overlay = %target.overlay, error = %e, "puller epoch reconcile failed");
+            warn!(overlay = %target.overlay.to_short_hex(), error = %e, "puller epoch reconcile failed");
             return false;
         }
 
-        for bin in &target.bins {
-            if self.sync_bin(target, *bin).await {
-                return true;
-            }
-        }
-        false
+        self.sync_bins(target).await
     }
 
     /// Drain the event stream until this command's cursor handshake answers,
@@ -277,107 +393,167 @@ where
         self.intervals.reset_peer(overlay, epoch)
     }
 
-    /// Drive one bin from its persisted interval upward until caught up.
+    /// Drive the target's bins from their persisted intervals upward until each
+    /// is caught up.
+    ///
+    /// Each range request asks for at most `batch_size` chunks, and at most
+    /// `max_in_flight` requests are outstanding at once. A bin's next batch is
+    /// issued only once its current one has been verified and admitted, so a
+    /// large bin is pulled page by page instead of all at once.
     ///
     /// Returns `true` only on a peer-blameworthy rejection: that peer is reported
     /// for invalid data and skipped for the rest of the pass. A transient
     /// rejection (an unknown batch during catch-up) also leaves the interval
     /// unadvanced so the page is retried on a later pass, but neither reports nor
     /// skips the peer.
-    async fn sync_bin(&mut self, target: &SyncTarget, bin: Bin) -> bool {
+    async fn sync_bins(&mut self, target: &SyncTarget) -> bool {
+        let window = self.config.max_in_flight.max(1);
+        let mut queued: VecDeque<Bin> = target.bins.iter().copied().collect();
+        let mut in_flight: HashMap<u64, (Bin, u64)> = HashMap::new();
+
         loop {
-            let start = match self.intervals.interval(&target.overlay, bin) {
-                Ok(start) => start,
-                Err(e) => {
-                    warn!(overlay = %target.overlay, error = %e, "puller interval read failed");
-                    return false;
+            while in_flight.len() < window
+                && let Some(bin) = queued.pop_front()
+            {
+                if let Some((request_id, start)) = self.request_page(target, bin) {
+                    in_flight.insert(request_id, (bin, start));
+                }
+            }
+            if in_flight.is_empty() {
+                return false;
+            }
+
+            let (request_id, page) = match self.await_range(target.peer, &in_flight).await {
+                Some(reply) => reply,
+                None => {
+                    // Late replies to the abandoned requests are dropped as stale.
+                    in_flight.clear();
+                    continue;
                 }
             };
+            let Some((bin, start)) = in_flight.remove(&request_id) else {
+                continue;
+            };
+            let Some((topmost, chunks)) = page else {
+                continue;
+            };
 
-            let request_id = self.next_request_id();
-            self.control.sync_range(target.peer, request_id, bin, start);
+            match self.apply_page(target, bin, start, topmost, chunks) {
+                PageOutcome::Advanced => queued.push_front(bin),
+                PageOutcome::Done => {}
+                PageOutcome::Blameworthy => return true,
+            }
+        }
+    }
 
-            let (topmost, chunks) = match self.await_range(target.peer, request_id, bin).await {
-                Some(page) => page,
-                None => return false,
-            };
+    /// Issue the next range request for `bin` from its persisted interval,
+    /// returning its request id and start. `None` drops the bin for this pass.
+    fn request_page(&mut self, target: &SyncTarget, bin: Bin) -> Option<(u64, u64)> {
+        let start = match self.intervals.interval(&target.overlay, bin) {
+            Ok(start) => start,
+            Err(e) => {
+                warn!(overlay = %target.overlay.to_short_hex(), error = %e, "puller interval read failed");
+                return None;
+            }
+        };
 
-            let mut rejected = false;
-            let mut blameworthy = false;
-            for chunk in chunks {
-                match self.verifier.verify(&chunk) {
-                    Ok(()) => {
-                        if let Err(e) = self.admit.admit(chunk) {
-                            warn!(overlay = %target.overlay, error = %e, "puller reserve admit failed");
-                        }
-                    }
+        let request_id = self.next_request_id();
+        self.control
+            .sync_range(target.peer, request_id, bin, start, self.config.batch_size);
+        Some((request_id, start))
+    }
+
+    /// Verify and admit one delivered page, then advance the bin's interval.
+    fn apply_page(
+        &self,
+        target: &SyncTarget,
+        bin: Bin,
+        start: u64,
+        topmost: u64,
+        chunks: Vec<vertex_swarm_primitives::StampedChunk>,
+    ) -> PageOutcome {
+        let mut admitted = 0u64;
+        let mut rejected = false;
+        let mut blameworthy = false;
+        for chunk in chunks {
+            match self.verifier.verify(&chunk) {
+                Ok(()) => match self.admit.admit(chunk) {
+                    Ok(()) => admitted += 1,
                     Err(e) => {
-                        // Any rejection taints the whole offer: do not advance
-                        // past it, or the unverified span is skipped forever.
-                        rejected = true;
-                        blameworthy |= e.is_peer_blameworthy();
-                        debug!(overlay = %target.overlay, reason = <&'static str>::from(&e), "puller rejected chunk");
+                        warn!(overlay = %target.overlay.to_short_hex(), error = %e, "puller reserve admit failed");
                     }
+                },
+                Err(e) => {
+                    // Any rejection taints the whole offer: do not advance
+                    // past it, or the unverified span is skipped forever.
+                    rejected = true;
+                    blameworthy |= e.is_peer_blameworthy();
+                    debug!(overlay = %target.overlay.to_short_hex(), reason = <&'static str>::from(&e), "puller rejected chunk");
                 }
             }
-
-            // A tainted page never advances the interval. A blameworthy rejection
-            // also reports the source for invalid data and skips it for the rest
-            // of the pass; a transient one (an unknown batch the indexer has not
-            // caught up to) only stops this bin so the page is retried later.
-            if rejected {
-                if blameworthy {
-                    self.reporter.report_peer(
-                        &target.overlay,
-                        SwarmScoringEvent::InvalidData,
-                        PULLSYNC_SOURCE,
-                    );
-                }
-                return blameworthy;
+        }
+        self.progress.record(admitted);
+
+        // A tainted page never advances the interval. A blameworthy rejection
+        // also reports the source for invalid data and skips it for the rest
+        // of the pass; a transient one (an unknown batch the indexer has not
+        // caught up to) only stops this bin so the page is retried later.
+        if rejected {
+            if blameworthy {
+                self.reporter.report_peer(
+                    &target.overlay,
+                    SwarmScoringEvent::InvalidData,
+                    PULLSYNC_SOURCE,
+                );
+                return PageOutcome::Blameworthy;
             }
+            return PageOutcome::Done;
+        }
 
-            // Caught up: the offer covered nothing past the resume point.
-            if topmost <= start {
-                return false;
-            }
+        // Caught up: the offer covered nothing past the resume point.
+        if topmost <= start {
+            return PageOutcome::Done;
+        }
 
-            if let Err(e) = self.intervals.set_interval(&target.overlay, bin, topmost) {
-                warn!(overlay = %target.overlay, error = %e, "puller interval write failed");
-                return false;
-            }
+        if let Err(e) = self.intervals.set_interval(&target.overlay, bin, topmost) {
+            warn!(overlay = %target.overlay.to_short_hex(), error = %e, "puller interval write failed");
+            return PageOutcome::Done;
         }
+        PageOutcome::Advanced
     }
 
-    /// Drain the event stream until this command's range page answers,
-    /// returning `(topmost, chunks)`. Matching is keyed on `request_id` so a
-    /// stale reply buffered from a prior timed-out command for the same peer and
-    /// bin is discarded rather than advancing the interval past undelivered
-    /// data. `None` abandons this peer: it failed, the deadline elapsed, or the
-    /// stream closed.
+    /// Drain the event stream until one of the `in_flight` range requests
+    /// answers, returning its id and `(topmost, chunks)`, or `None` for the page
+    /// if it failed. Matching is keyed on request id so a stale reply buffered
+    /// from a prior timed-out command for the same peer and bin is discarded
+    /// rather than advancing the interval past undelivered data. `None` overall
+    /// abandons every outstanding request: the deadline elapsed or the stream
+    /// closed.
     async fn await_range(
         &mut self,
         peer: PeerId,
-        request_id: u64,
-        bin: Bin,
-    ) -> Option<(u64, Vec<vertex_swarm_primitives::StampedChunk>)> {
+        in_flight: &HashMap<u64, (Bin, u64)>,
+    ) -> Option<(u64, Option<RangePage>)> {
         let ceiling = self.config.peer_response_timeout;
         let events = &mut self.events;
         let drained = async {
             loop {
                 match events.recv().await? {
                     PullsyncEvent::RangeDelivered {
-                        request_id: id,
+                        request_id,
                         topmost,
                         chunks,
                         ..
-                    } if id == request_id => return Some((topmost, chunks)),
+                    } if in_flight.contains_key(&request_id) => {
+                        return Some((request_id, Some((topmost, chunks))));
+                    }
                     PullsyncEvent::Failed {
-                        request_id: id,
+                        request_id,
                         failure,
                         ..
-                    } if id == request_id => {
+                    } if in_flight.contains_key(&request_id) => {
                         debug!(%peer, %failure, "puller range exchange failed");
-                        return None;
+                        return Some((request_id, None));
                     }
                     _ => continue,
                 }
@@ -386,18 +562,50 @@ where
         match time::timeout(ceiling, drained).await {
             Ok(result) => result,
             Err(_elapsed) => {
-                warn!(%peer, %bin, "puller range exchange timed out, abandoning peer");
+                let bins: Vec<Bin> = in_flight.values().map(|(bin, _)| *bin).collect();
+                warn!(%peer, ?bins, "puller range exchange timed out, abandoning peer");
                 None
             }
         }
     }
 }
 
+/// A delivered range page: the topmost id the offer covered and its chunks.
+type RangePage = (u64, Vec<vertex_swarm_primitives::StampedChunk>);
+
+/// What a delivered page did to its bin.
+enum PageOutcome {
+    /// The interval advanced; request the bin's next batch.
+    Advanced,
+    /// Caught up, transiently rejected, or unwritable; stop this bin.
+    Done,
+    /// A chunk failed verification through the peer's fault.
+    Blameworthy,
+}
+
+/// Next batch of bins to reindex; pending forever without a reindex channel.
+async fn next_reindex(reindex: &mut Option<mpsc::Receiver<Vec<Bin>>>) -> Option<Vec<Bin>> {
+    match reindex {
+        Some(rx) => rx.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Bins a move from depth `old` to `new` brings into the neighbourhood,
+/// shallowest first: `new..old` on a decrease, none otherwise.
+pub fn newly_in_range(old: NeighborhoodDepth, new: NeighborhoodDepth) -> Vec<Bin> {
+    balanced_bins(old)
+        .filter(|bin| new.contains(*bin))
+        .collect()
+}
+
 /// Cloneable handle to the puller's event sender, for the node bridge to feed
 /// [`PullsyncEvent`]s in.
 #[derive(Clone)]
 pub struct PullerHandle {
     events: mpsc::Sender<PullsyncEvent>,
+    reindex: mpsc::Sender<Vec<Bin>>,
+    progress: Arc<Progress>,
 }
 
 impl PullerHandle {
@@ -411,11 +619,32 @@ impl PullerHandle {
     ) -> Result<(), Box<mpsc::error::TrySendError<PullsyncEvent>>> {
         self.events.try_send(event).map_err(Box::new)
     }
+
+    /// Sync progress of the running puller.
+    pub fn stats(&self) -> PullerStats {
+        self.progress.stats()
+    }
+
+    /// Report a depth change; a decrease queues the newly in-range bins for
+    /// an immediate reindex. A full queue drops the request, since the next
+    /// regular pass covers the same bins.
+    pub fn depth_changed(&self, old: NeighborhoodDepth, new: NeighborhoodDepth) {
+        let bins = newly_in_range(old, new);
+        if bins.is_empty() {
+            return;
+        }
+        if self.reindex.try_send(bins).is_err() {
+            debug!(%old, %new, "puller reindex queue full, leaving bins to the next pass");
+        }
+    }
 }
 
 /// Default event-channel capacity.
 pub const DEFAULT_EVENT_CAPACITY: usize = 256;
 
+/// Pending reindex requests; depth changes are rare, so a few suffice.
+const REINDEX_CAPACITY: usize = 8;
+
 impl<C, S, V, A, G, N, R> SpawnableTask for Puller<C, PullsyncEvent, S, V, A, G, N, R>
 where
     C: PullsyncControl + 'static,
@@ -456,8 +685,17 @@ where
     R: PeerReporter,
 {
     let (events_tx, events_rx) = mpsc::channel(event_capacity);
-    let puller = Puller::new(seams, events_rx, config);
-    (puller, PullerHandle { events: events_tx })
+    let (reindex_tx, reindex_rx) = mpsc::channel(REINDEX_CAPACITY);
+    let puller = Puller::new(seams, events_rx, config).with_reindex(reindex_rx);
+    let progress = Arc::clone(&puller.progress);
+    (
+        puller,
+        PullerHandle {
+            events: events_tx,
+            reindex: reindex_tx,
+            progress,
+        },
+    )
 }
 
 /// Spawn the puller as a graceful-shutdown service, returning its event handle.
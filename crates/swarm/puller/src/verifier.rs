@@ -6,9 +6,12 @@
 //! batch from a [`BatchStore`] and runs the same usability, expiry, capacity and
 //! owner checks the reserve enforces on admission, so an underfunded or expired
 //! batch taints the syncing page rather than being silently dropped at the
-//! reserve put.
+//! reserve put. [`FundingVerifier`] is also the pushsync
+//! [`StampVerifier`](vertex_swarm_api::StampVerifier), so pushed and pulled
+//! chunks pass the same batch check before custody.
 
-use nectar_postage::{BatchStore, StampError};
+use nectar_postage::{BatchStore, Stamp, StampError};
+use nectar_primitives::{AnyChunk, ChunkAddress};
 use vertex_swarm_api::{PullChunkVerifier, VerifyError};
 use vertex_swarm_postage::{AdmissionError, AdmissionValidator};
 use vertex_swarm_primitives::StampedChunk;
@@ -46,15 +49,8 @@ impl<BS> FundingVerifier<BS> {
     }
 }
 
-impl<BS> PullChunkVerifier for FundingVerifier<BS>
-where
-    BS: BatchStore + Send + Sync,
-    BS::Error: Send + Sync,
-{
-    fn verify(&self, chunk: &StampedChunk) -> Result<(), VerifyError> {
-        let stamp = chunk.stamp();
-        let address = chunk.address();
-
+impl<BS: BatchStore> FundingVerifier<BS> {
+    fn check(&self, stamp: &Stamp, address: &ChunkAddress) -> Result<(), VerifyError> {
         let batch = self
             .batches
             .get(&stamp.batch())
@@ -68,6 +64,26 @@ where
     }
 }
 
+impl<BS> PullChunkVerifier for FundingVerifier<BS>
+where
+    BS: BatchStore + Send + Sync,
+    BS::Error: Send + Sync,
+{
+    fn verify(&self, chunk: &StampedChunk) -> Result<(), VerifyError> {
+        self.check(chunk.stamp(), chunk.address())
+    }
+}
+
+impl<BS> vertex_swarm_api::StampVerifier for FundingVerifier<BS>
+where
+    BS: BatchStore + Send + Sync,
+    BS::Error: Send + Sync,
+{
+    fn verify(&self, chunk: &AnyChunk, stamp: &Stamp) -> Result<(), VerifyError> {
+        self.check(stamp, chunk.address())
+    }
+}
+
 /// Map the reserve's admission taxonomy onto the puller's verify taxonomy.
 ///
 /// Unusable and expired batches collapse onto `UnknownBatch` (the verifier's
@@ -254,6 +270,28 @@ mod tests {
         assert!(matches!(err, VerifyError::UnknownBatch));
     }
 
+    #[test]
+    fn pushsync_verifier_runs_the_funding_check() {
+        use vertex_swarm_api::StampVerifier;
+
+        let s = signer();
+        let batch = batch_for(s.address(), 1_000_000);
+        let chunk = content_chunk();
+        let stamped = signed_chunk(&s, &batch, &chunk, 0);
+
+        let unknown = verifier(MockBatchStore::default().with_context(usable_context()));
+        let err = StampVerifier::verify(&unknown, stamped.chunk(), stamped.stamp()).unwrap_err();
+        assert!(matches!(err, VerifyError::UnknownBatch));
+
+        let funded = verifier(
+            MockBatchStore::default()
+                .with_batch(batch)
+                .with_context(usable_context()),
+        );
+        StampVerifier::verify(&funded, stamped.chunk(), stamped.stamp())
+            .expect("funded chunk admits");
+    }
+
     #[test]
     fn expired_batch_is_rejected() {
         let s = signer();
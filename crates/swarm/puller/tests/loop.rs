@@ -17,10 +17,10 @@ use vertex_swarm_api::{
     IntervalStore, PeerReporter, PullChunkVerifier, ReportSource, SwarmResult, SwarmScoringEvent,
     VerifyError,
 };
-use vertex_swarm_primitives::{Bin, OverlayAddress, StampedChunk};
+use vertex_swarm_primitives::{Bin, NeighborhoodDepth, OverlayAddress, StampedChunk};
 use vertex_swarm_puller::{
-    NeighbourSource, Puller, PullerConfig, PullerSeams, PullsyncControl, PullsyncEvent,
-    ReserveAdmit, SyncTarget,
+    DEFAULT_BATCH_SIZE, NeighbourSource, Puller, PullerConfig, PullerSeams, PullsyncControl,
+    PullsyncEvent, ReserveAdmit, SyncTarget, newly_in_range,
 };
 
 // The readiness gate is exercised by `run`, not `sync_pass`; these tests drive
@@ -44,6 +44,8 @@ struct MockControl {
     /// Request ids stamped on each `sync_range`, in issue order, so a test can
     /// reply with the live id rather than guessing it.
     range_ids: Arc<Mutex<Vec<u64>>>,
+    /// Chunk limit asked for on each `sync_range`, in issue order.
+    limits: Arc<Mutex<Vec<u64>>>,
 }
 
 impl PullsyncControl for MockControl {
@@ -51,9 +53,10 @@ impl PullsyncControl for MockControl {
         self.fetched.lock().unwrap().push(peer);
     }
 
-    fn sync_range(&self, peer: PeerId, request_id: u64, bin: Bin, start: u64) {
+    fn sync_range(&self, peer: PeerId, request_id: u64, bin: Bin, start: u64, limit: u64) {
         self.ranges.lock().unwrap().push((peer, bin, start));
         self.range_ids.lock().unwrap().push(request_id);
+        self.limits.lock().unwrap().push(limit);
     }
 }
 
@@ -772,3 +775,173 @@ async fn poison_peer_is_reported_and_skipped_other_peer_syncs() {
         vec![(poison, bin(2), 0), (good, bin(2), 0), (good, bin(2), 10)]
     );
 }
+
+// Depth 4 -> 2 brings bins 2 and 3 into the neighbourhood. The reindex pulls
+// exactly those from the neighbour, leaving bin 4 (already synced) to the
+// regular pass.
+#[tokio::test]
+async fn depth_decrease_reindexes_newly_in_range_bins() {
+    let control = MockControl::default();
+    let peer = PeerId::random();
+    let (events_tx, events_rx) = mpsc::channel(32);
+    let mut puller = Puller::new(
+        PullerSeams {
+            control: control.clone(),
+            intervals: MockIntervals::default(),
+            verifier: FixedVerifier { accept: true },
+            admit: MockAdmit::default(),
+            readiness: NoGate,
+            neighbours: OneTarget(SyncTarget {
+                peer,
+                overlay: overlay(1),
+                bins: vec![bin(2), bin(3), bin(4)],
+            }),
+            reporter: MockReporter::default(),
+        },
+        events_rx,
+        PullerConfig::default(),
+    );
+
+    let depth = |n| NeighborhoodDepth::new(bin(n));
+    assert!(newly_in_range(depth(2), depth(4)).is_empty());
+    let bins = newly_in_range(depth(4), depth(2));
+    assert_eq!(bins, vec![bin(2), bin(3)]);
+
+    let events = [
+        PullsyncEvent::CursorsReceived {
+            peer,
+            request_id: 0,
+            cursors: vec![],
+            epoch: 1,
+        },
+        PullsyncEvent::RangeDelivered {
+            peer,
+            request_id: 1,
+            bin: bin(2),
+            topmost: 0,
+            chunks: vec![],
+        },
+        PullsyncEvent::RangeDelivered {
+            peer,
+            request_id: 2,
+            bin: bin(3),
+            topmost: 0,
+            chunks: vec![],
+        },
+    ];
+    for event in events {
+        events_tx.send(event).await.unwrap();
+    }
+    puller.reindex(&bins).await;
+
+    assert_eq!(*control.fetched.lock().unwrap(), vec![peer]);
+    assert_eq!(
+        *control.ranges.lock().unwrap(),
+        vec![(peer, bin(2), 0), (peer, bin(3), 0)]
+    );
+}
+
+// A 10-chunk bin at batch size 4 is pulled as pages of 4, 4 and 2, then a
+// caught-up page. Each request carries the batch size, and the next one is
+// issued only after the previous page is applied.
+#[tokio::test]
+async fn large_bin_is_requested_in_bounded_batches() {
+    const BATCH: u64 = 4;
+    const TOTAL: u64 = 10;
+
+    let control = MockControl::default();
+    let intervals = MockIntervals::default();
+    let admit = MockAdmit::default();
+    let peer = PeerId::random();
+    let ov = overlay(1);
+    let (events_tx, events_rx) = mpsc::channel(32);
+    let mut puller = Puller::new(
+        PullerSeams {
+            control: control.clone(),
+            intervals: intervals.clone(),
+            verifier: FixedVerifier { accept: true },
+            admit: admit.clone(),
+            readiness: NoGate,
+            neighbours: OneTarget(SyncTarget {
+                peer,
+                overlay: ov,
+                bins: vec![bin(2)],
+            }),
+            reporter: MockReporter::default(),
+        },
+        events_rx,
+        PullerConfig {
+            batch_size: BATCH,
+            ..PullerConfig::default()
+        },
+    );
+    assert_ne!(BATCH, DEFAULT_BATCH_SIZE);
+
+    events_tx
+        .send(PullsyncEvent::CursorsReceived {
+            peer,
+            request_id: 0,
+            cursors: vec![],
+            epoch: 1,
+        })
+        .await
+        .unwrap();
+    let pass = tokio::spawn(async move {
+        puller.sync_pass().await;
+        puller
+    });
+
+    // Answer each request only once it is issued, checking that no further
+    // request went out ahead of its reply.
+    let mut served = 0u64;
+    for page in 0u8.. {
+        let issued = loop {
+            let ids = control.range_ids.lock().unwrap().clone();
+            if ids.len() > usize::from(page) {
+                break ids;
+            }
+            tokio::task::yield_now().await;
+        };
+        assert_eq!(issued.len(), usize::from(page) + 1, "one request in flight");
+
+        let count = BATCH.min(TOTAL - served);
+        let chunks = (0..count)
+            .map(|i| stamped(u8::try_from(served + i).unwrap()))
+            .collect();
+        served += count;
+        events_tx
+            .send(PullsyncEvent::RangeDelivered {
+                peer,
+                request_id: *issued.last().unwrap(),
+                bin: bin(2),
+                topmost: served,
+                chunks,
+            })
+            .await
+            .unwrap();
+        if count == 0 {
+            break;
+        }
+    }
+    let puller = pass.await.unwrap();
+
+    assert_eq!(
+        *control.ranges.lock().unwrap(),
+        vec![
+            (peer, bin(2), 0),
+            (peer, bin(2), 4),
+            (peer, bin(2), 8),
+            (peer, bin(2), 10),
+        ]
+    );
+    assert!(
+        control.limits.lock().unwrap().iter().all(|l| *l == BATCH),
+        "every request asks for the configured batch"
+    );
+    assert_eq!(admit.admitted.lock().unwrap().len(), 10);
+    assert_eq!(intervals.interval(&ov, bin(2)).unwrap(), TOTAL);
+
+    let stats = puller.stats();
+    assert_eq!(stats.chunks_synced, TOTAL);
+    assert_eq!(stats.pages_synced, 4);
+}
@@ -3,12 +3,13 @@
 use crate::StorageConfig;
 use clap::Args;
 use serde::{Deserialize, Serialize};
+use vertex_swarm_api::DEFAULT_VALIDATION_CONCURRENCY;
 
 /// Redistribution configuration for storage incentives.
 ///
 /// Controls participation in the Swarm redistribution game, which rewards
 /// nodes for storing and serving chunks within their neighborhood.
-#[derive(Debug, Args, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Args, Clone, Serialize, Deserialize)]
 #[command(next_help_heading = "Redistribution")]
 #[serde(default)]
 pub struct RedistributionArgs {
@@ -18,11 +19,34 @@ pub struct RedistributionArgs {
     /// committing storage proofs and potentially earning BZZ rewards.
     #[arg(long)]
     pub redistribution: bool,
+
+    /// Inbound chunk validations run at once off the event loop; further
+    /// deliveries queue. Values below one are raised to one.
+    #[arg(long = "storage.validation-concurrency", default_value_t = DEFAULT_VALIDATION_CONCURRENCY)]
+    pub validation_concurrency: usize,
+
+    /// Refuse pushed chunks outside the storage radius instead of relaying
+    /// them towards their neighbourhood. The pusher sees a failed push and
+    /// moves on to a closer peer.
+    #[arg(long = "storage.refuse-out-of-range")]
+    pub refuse_out_of_range: bool,
+}
+
+impl Default for RedistributionArgs {
+    fn default() -> Self {
+        Self {
+            redistribution: false,
+            validation_concurrency: DEFAULT_VALIDATION_CONCURRENCY,
+            refuse_out_of_range: false,
+        }
+    }
 }
 
 impl RedistributionArgs {
     /// Create validated StorageConfig from these CLI arguments.
     pub fn storage_config(&self) -> StorageConfig {
         StorageConfig::new(self.redistribution)
+            .with_validation_concurrency(self.validation_concurrency)
+            .with_refuse_out_of_range(self.refuse_out_of_range)
     }
 }
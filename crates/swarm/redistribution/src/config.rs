@@ -1,11 +1,13 @@
 //! Validated storage incentives configuration.
 
-use vertex_swarm_api::SwarmStorageConfig;
+use vertex_swarm_api::{DEFAULT_VALIDATION_CONCURRENCY, SwarmStorageConfig};
 
 /// Validated storage incentives configuration.
 #[derive(Debug, Clone)]
 pub struct StorageConfig {
     redistribution_enabled: bool,
+    validation_concurrency: usize,
+    refuse_out_of_range: bool,
 }
 
 impl StorageConfig {
@@ -13,17 +15,52 @@ impl StorageConfig {
     pub fn new(redistribution_enabled: bool) -> Self {
         Self {
             redistribution_enabled,
+            validation_concurrency: DEFAULT_VALIDATION_CONCURRENCY,
+            refuse_out_of_range: false,
         }
     }
 
+    /// Cap concurrent inbound chunk validations at `limit` (at least one).
+    #[must_use]
+    pub fn with_validation_concurrency(mut self, limit: usize) -> Self {
+        self.validation_concurrency = limit.max(1);
+        self
+    }
+
+    /// Refuse pushes for chunks outside the storage radius instead of
+    /// relaying them.
+    #[must_use]
+    pub fn with_refuse_out_of_range(mut self, refuse: bool) -> Self {
+        self.refuse_out_of_range = refuse;
+        self
+    }
+
     /// Whether redistribution is enabled.
     pub fn redistribution_enabled(&self) -> bool {
         self.redistribution_enabled
     }
+
+    /// Concurrent inbound chunk validations.
+    pub fn validation_concurrency(&self) -> usize {
+        self.validation_concurrency
+    }
+
+    /// Whether out-of-range pushes are refused.
+    pub fn refuse_out_of_range(&self) -> bool {
+        self.refuse_out_of_range
+    }
 }
 
 impl SwarmStorageConfig for StorageConfig {
     fn redistribution_enabled(&self) -> bool {
         self.redistribution_enabled
     }
+
+    fn validation_concurrency(&self) -> usize {
+        self.validation_concurrency
+    }
+
+    fn refuse_out_of_range(&self) -> bool {
+        self.refuse_out_of_range
+    }
 }
@@ -6,7 +6,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     tonic_build::configure()
         .build_server(true)
-        .build_client(false)
+        .build_client(true)
         .file_descriptor_set_path(out_dir.join("swarm_descriptor.bin"))
         .compile_protos(
             &[
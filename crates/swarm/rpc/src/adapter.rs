@@ -1,15 +1,20 @@
 //! gRPC adapter over an api component container.
 //!
 //! [`GrpcAdapter<C>`] registers exactly the services `C`'s capabilities expose:
-//! the node status service is gated on [`HasTopology`], the chunk service on
-//! [`HasChunkClient`]. Registration uses per-shape [`RegistersGrpcServices`]
+//! the node status service is gated on [`HasTopology`] and [`HasDiagnostics`]
+//! (answering `GetBalance` when [`HasFinance`] yields a source), the chunk
+//! service on [`HasChunkClient`]. Registration uses per-shape [`RegistersGrpcServices`]
 //! impls (one per concrete container) to avoid overlapping blanket impls for the
 //! optional chunk capability.
 
+use std::sync::Arc;
+
 use vertex_rpc_server::{GrpcRegistry, RegistersGrpcServices};
 use vertex_swarm_api::{
-    BinCursorStore, BootnodeComponents, ClientComponents, HasChunkClient, HasReserve, HasStore,
-    HasTopology, StorerComponents, SwarmTopologyPeers, SwarmTopologyState, SwarmTopologyStats,
+    BinCursorStore, BootnodeComponents, ClientComponents, DiagnosticsHandle, FinanceHandle,
+    HasChunkClient, HasDiagnostics, HasFinance, HasReserve, HasStore, HasTopology, PeerPinger,
+    StorerComponents, SwarmTopologyPeers, SwarmTopologyRouting, SwarmTopologyState,
+    SwarmTopologyStats,
 };
 use vertex_swarm_stream::ChunkClient;
 
@@ -44,6 +49,18 @@ impl<C: HasTopology> HasTopology for GrpcAdapter<C> {
     }
 }
 
+impl<C: HasDiagnostics> HasDiagnostics for GrpcAdapter<C> {
+    fn diagnostics(&self) -> &DiagnosticsHandle {
+        self.components.diagnostics()
+    }
+}
+
+impl<C: HasFinance> HasFinance for GrpcAdapter<C> {
+    fn finance(&self) -> Option<&FinanceHandle> {
+        self.components.finance()
+    }
+}
+
 impl<C: HasChunkClient> HasChunkClient for GrpcAdapter<C> {
     type ChunkClient = C::ChunkClient;
 
@@ -72,16 +89,24 @@ impl<C> GrpcAdapter<C> {
     /// Register the node status service and the shared reflection descriptor.
     pub fn register_node(&self, registry: &mut GrpcRegistry)
     where
-        C: HasTopology,
+        C: HasTopology + HasDiagnostics + HasFinance,
         C::Topology: SwarmTopologyState
             + SwarmTopologyStats
             + SwarmTopologyPeers
+            + SwarmTopologyRouting
+            + PeerPinger
             + Clone
             + Send
             + Sync
             + 'static,
     {
-        let node_service = NodeService::new(self.components.topology().clone());
+        let topology = self.components.topology().clone();
+        let mut node_service =
+            NodeService::new(topology.clone(), self.components.diagnostics().clone())
+                .with_pinger(Arc::new(topology));
+        if let Some(finance) = self.components.finance() {
+            node_service = node_service.with_finance(Arc::new(finance.clone()));
+        }
         let node_server = proto::node::node_server::NodeServer::new(node_service);
         registry.add_service(node_server);
         registry.add_descriptor(proto::FILE_DESCRIPTOR_SET);
@@ -119,7 +144,15 @@ impl<C> GrpcAdapter<C> {
 /// Bootnodes register the node status service only.
 impl<T> RegistersGrpcServices for GrpcAdapter<BootnodeComponents<T>>
 where
-    T: SwarmTopologyState + SwarmTopologyStats + SwarmTopologyPeers + Clone + Send + Sync + 'static,
+    T: SwarmTopologyState
+        + SwarmTopologyStats
+        + SwarmTopologyPeers
+        + SwarmTopologyRouting
+        + PeerPinger
+        + Clone
+        + Send
+        + Sync
+        + 'static,
 {
     fn register_grpc_services(&self, registry: &mut GrpcRegistry) {
         self.register_node(registry);
@@ -129,7 +162,15 @@ where
 /// Client nodes register the node status service and the chunk service.
 impl<T, C> RegistersGrpcServices for GrpcAdapter<ClientComponents<T, C>>
 where
-    T: SwarmTopologyState + SwarmTopologyStats + SwarmTopologyPeers + Clone + Send + Sync + 'static,
+    T: SwarmTopologyState
+        + SwarmTopologyStats
+        + SwarmTopologyPeers
+        + SwarmTopologyRouting
+        + PeerPinger
+        + Clone
+        + Send
+        + Sync
+        + 'static,
     C: ChunkClient + Send + Sync,
 {
     fn register_grpc_services(&self, registry: &mut GrpcRegistry) {
@@ -142,7 +183,15 @@ where
 /// reserve service over the `R` reserve axis.
 impl<T, C, S, R> RegistersGrpcServices for GrpcAdapter<StorerComponents<T, C, S, R>>
 where
-    T: SwarmTopologyState + SwarmTopologyStats + SwarmTopologyPeers + Clone + Send + Sync + 'static,
+    T: SwarmTopologyState
+        + SwarmTopologyStats
+        + SwarmTopologyPeers
+        + SwarmTopologyRouting
+        + PeerPinger
+        + Clone
+        + Send
+        + Sync
+        + 'static,
     C: ChunkClient + Send + Sync,
     S: Send + Sync,
     R: BinCursorStore + Clone + 'static,
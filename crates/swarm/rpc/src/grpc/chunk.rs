@@ -12,6 +12,7 @@ use tonic::{Request, Response, Status, Streaming};
 use vertex_swarm_api::{ChunkAddress, PushReceipt, Stamp, StampedChunk, SwarmError};
 use vertex_swarm_stream::{
     ChunkClient, ChunkClientExt, StreamConfig, VerifiedChunk, get_stream_from, parse_address,
+    retrieve_verified_fast,
 };
 
 /// Server-side policy for the caller-controlled per-request `validate` flag. A
@@ -93,13 +94,17 @@ fn parse_stamped_chunk(req: &UploadChunkRequest) -> Result<StampedChunk, Status>
 }
 
 /// Exhausted retrieval becomes `unavailable` (absence is not provable, so the
-/// caller may retry), no-storer `not_found`, all else `internal`.
+/// caller may retry), a timed-out one `deadline_exceeded`, no-storer
+/// `not_found`, all else `internal`.
 #[allow(clippy::result_large_err)]
 fn retrieval_status(error: &SwarmError) -> Status {
     match error {
         SwarmError::RetrievalExhausted { .. } => {
             Status::unavailable(format!("retrieval exhausted: {error}"))
         }
+        SwarmError::RetrievalTimeout { .. } => {
+            Status::deadline_exceeded(format!("retrieval timed out: {error}"))
+        }
         SwarmError::NoStorer { .. } => Status::not_found(format!("chunk not found: {error}")),
         other => Status::internal(format!("chunk retrieval failed: {other}")),
     }
@@ -185,8 +190,15 @@ impl<P: ChunkClient> Chunk for ChunkService<P> {
         let req = request.into_inner();
         let address = parse_chunk_address(&req.address)?;
 
-        // `get` verifies the bytes answer the address, so wrong bytes error.
-        match self.provider.get(address).await {
+        // Both paths verify the bytes answer the address, so wrong bytes error.
+        let retrieved = match req.max_latency_ms {
+            0 => self.provider.get(address).await,
+            ms => {
+                let max_latency = std::time::Duration::from_millis(u64::from(ms));
+                retrieve_verified_fast(self.provider.clone(), address, max_latency).await
+            }
+        };
+        match retrieved {
             Ok(verified) => Ok(Response::new(verified_response(address, verified))),
             Err(e) => Err(retrieval_status(&e)),
         }
@@ -430,6 +442,75 @@ mod tests {
         assert_eq!(StampValidation::default(), StampValidation::Enforce);
     }
 
+    /// A non-zero `max_latency_ms` routes through the provider's fast path with
+    /// that budget; zero keeps the full retrieval.
+    #[tokio::test]
+    async fn max_latency_selects_the_fast_path() {
+        use std::time::Duration;
+        use vertex_swarm_api::{
+            ChunkRetrievalResult, PushReceipt, SwarmChunkProvider, SwarmChunkSender, SwarmResult,
+        };
+
+        /// Fails the full path as absent and the fast path as timed out, so
+        /// the status code names the path taken.
+        #[derive(Clone)]
+        struct PathProvider;
+
+        #[tonic::async_trait]
+        impl SwarmChunkProvider for PathProvider {
+            async fn retrieve_chunk(
+                &self,
+                address: &ChunkAddress,
+            ) -> SwarmResult<ChunkRetrievalResult> {
+                Err(SwarmError::NoStorer {
+                    chunk_address: *address,
+                })
+            }
+
+            async fn retrieve_chunk_fast(
+                &self,
+                address: &ChunkAddress,
+                max_latency: Duration,
+            ) -> SwarmResult<ChunkRetrievalResult> {
+                assert_eq!(max_latency, Duration::from_millis(250));
+                Err(SwarmError::RetrievalTimeout {
+                    address: *address,
+                    deadline: max_latency,
+                })
+            }
+
+            fn has_chunk(&self, _address: &ChunkAddress) -> bool {
+                false
+            }
+        }
+
+        #[tonic::async_trait]
+        impl SwarmChunkSender for PathProvider {
+            async fn send_chunk_unchecked(&self, chunk: StampedChunk) -> SwarmResult<PushReceipt> {
+                Err(SwarmError::NoStorer {
+                    chunk_address: *chunk.address(),
+                })
+            }
+
+            async fn send_chunk(&self, chunk: StampedChunk) -> SwarmResult<PushReceipt> {
+                self.send_chunk_unchecked(chunk).await
+            }
+        }
+
+        let service = ChunkService::new(PathProvider);
+        let request = |max_latency_ms| {
+            Request::new(RetrieveChunkRequest {
+                address: vec![0x42; 32],
+                max_latency_ms,
+            })
+        };
+
+        let full = service.retrieve_chunk(request(0)).await.unwrap_err();
+        assert_eq!(full.code(), tonic::Code::NotFound);
+        let fast = service.retrieve_chunk(request(250)).await.unwrap_err();
+        assert_eq!(fast.code(), tonic::Code::DeadlineExceeded);
+    }
+
     /// A verified item maps onto the wire response with a populated `served_by`,
     /// driving the same `get_stream_from` core the RPC routes through.
     #[tokio::test]
@@ -454,6 +535,8 @@ mod tests {
                     chunk: self.chunk.clone(),
                     stamp: None,
                     served_by: OverlayAddress::from(SERVED_BY),
+                    retries: 0,
+                    elapsed: std::time::Duration::ZERO,
                 })
             }
 
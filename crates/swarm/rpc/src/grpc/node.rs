@@ -1,37 +1,76 @@
 //! Node service implementation for Swarm topology and status information.
 
+use std::sync::Arc;
+
 use tonic::{Request, Response, Status};
-use vertex_swarm_api::{SwarmTopologyPeers, SwarmTopologyState, SwarmTopologyStats};
-use vertex_swarm_primitives::Bin;
+use vertex_swarm_api::{
+    ChunkAddress, ConfigDiagnostics, ConnectionAuditRecord, DepthExplanation, DepthLimit,
+    DiagnosticDump, DiagnosticsHandle, LedgerDiagnostics, NodeFinance, PeerDiagnostics, PeerPinger,
+    StorageDiagnostics, SwarmDiagnostics, SwarmError, SwarmTopologyPeers, SwarmTopologyRouting,
+    SwarmTopologyState, SwarmTopologyStats, TopologyDiagnostics,
+};
+use vertex_swarm_primitives::{Bin, OverlayAddress, SwarmAddressExt};
 
 use crate::proto::node::{
-    BinInfo, GetStatusRequest, GetStatusResponse, GetTopologyRequest, GetTopologyResponse,
-    PeerInfo, node_server::Node,
+    BinInfo, ClosestPeer, ClosestPeersRequest, ClosestPeersResponse, ConnectionAuditEntry,
+    DepthBin, DumpConfig, DumpLedger, DumpPeer, DumpRequest, DumpResponse, DumpStorage,
+    DumpTopology, GetBalanceRequest, GetBalanceResponse, GetConnectionAuditRequest,
+    GetConnectionAuditResponse, GetDepthExplanationRequest, GetDepthExplanationResponse,
+    GetStatusRequest, GetStatusResponse, GetTopologyRequest, GetTopologyResponse, PeerInfo,
+    PingPeerRequest, PingPeerResponse, node_server::Node,
 };
 
 /// Node service implementation.
 ///
-/// Provides gRPC endpoints for querying Swarm node status and topology.
+/// Provides gRPC endpoints for querying Swarm node status, topology, routing,
+/// and the full diagnostic dump.
 pub struct NodeService<T> {
     topology: T,
+    diagnostics: DiagnosticsHandle,
+    finance: Option<Arc<dyn NodeFinance>>,
+    pinger: Option<Arc<dyn PeerPinger>>,
 }
 
 impl<T> NodeService<T> {
-    pub fn new(topology: T) -> Self {
-        Self { topology }
+    pub fn new(topology: T, diagnostics: DiagnosticsHandle) -> Self {
+        Self {
+            topology,
+            diagnostics,
+            finance: None,
+            pinger: None,
+        }
+    }
+
+    /// Answer `GetBalance` from `finance`; without it the call is unavailable.
+    pub fn with_finance(mut self, finance: Arc<dyn NodeFinance>) -> Self {
+        self.finance = Some(finance);
+        self
+    }
+
+    /// Answer `PingPeer` through `pinger`; without it the call is unavailable.
+    pub fn with_pinger(mut self, pinger: Arc<dyn PeerPinger>) -> Self {
+        self.pinger = Some(pinger);
+        self
     }
 }
 
 #[tonic::async_trait]
-impl<T: SwarmTopologyState + SwarmTopologyStats + SwarmTopologyPeers + Send + Sync + 'static> Node
-    for NodeService<T>
+impl<T> Node for NodeService<T>
+where
+    T: SwarmTopologyState
+        + SwarmTopologyStats
+        + SwarmTopologyPeers
+        + SwarmTopologyRouting
+        + Send
+        + Sync
+        + 'static,
 {
     async fn get_status(
         &self,
         _request: Request<GetStatusRequest>,
     ) -> Result<Response<GetStatusResponse>, Status> {
         Ok(Response::new(GetStatusResponse {
-            overlay_address: self.topology.overlay_address().to_string(),
+            overlay_address: self.topology.overlay_address().to_hex(),
             depth: u32::from(self.topology.depth().get()),
             connected_peers: self.topology.connected_peers_count() as u32,
             known_peers: self.topology.routing_peers_count() as u32,
@@ -53,11 +92,11 @@ impl<T: SwarmTopologyState + SwarmTopologyStats + SwarmTopologyPeers + Send + Sy
                     let details = self
                         .topology
                         .connected_peer_details_in_bin(Bin::new(idx as u8).unwrap_or(Bin::MAX));
-                    let addrs = details.iter().map(|(o, _)| o.to_string()).collect();
+                    let addrs = details.iter().map(|(o, _)| o.to_hex()).collect();
                     let info = details
                         .into_iter()
                         .map(|(overlay, multiaddrs)| PeerInfo {
-                            overlay: overlay.to_string(),
+                            overlay: overlay.to_hex(),
                             multiaddrs: multiaddrs.iter().map(|m| m.to_string()).collect(),
                         })
                         .collect();
@@ -77,9 +116,521 @@ impl<T: SwarmTopologyState + SwarmTopologyStats + SwarmTopologyPeers + Send + Sy
             .collect();
 
         Ok(Response::new(GetTopologyResponse {
-            overlay_address: self.topology.overlay_address().to_string(),
+            overlay_address: self.topology.overlay_address().to_hex(),
             depth: u32::from(self.topology.depth().get()),
             bins,
         }))
     }
+
+    async fn dump(&self, _request: Request<DumpRequest>) -> Result<Response<DumpResponse>, Status> {
+        Ok(Response::new(self.diagnostics.diagnostic_dump().into()))
+    }
+
+    async fn closest_peers(
+        &self,
+        request: Request<ClosestPeersRequest>,
+    ) -> Result<Response<ClosestPeersResponse>, Status> {
+        let request = request.into_inner();
+        let address = parse_address(&request.address)?;
+        let peers = self
+            .topology
+            .closest_to(&address, request.count as usize)
+            .into_iter()
+            .map(|overlay| ClosestPeer {
+                overlay: overlay.to_hex(),
+                proximity_order: u32::from(address.proximity(&overlay).get()),
+            })
+            .collect();
+
+        Ok(Response::new(ClosestPeersResponse { peers }))
+    }
+
+    async fn get_balance(
+        &self,
+        _request: Request<GetBalanceRequest>,
+    ) -> Result<Response<GetBalanceResponse>, Status> {
+        let finance = self
+            .finance
+            .as_ref()
+            .ok_or_else(|| Status::unavailable("no chain connection configured"))?;
+        let balance = finance
+            .balance()
+            .await
+            .map_err(|e| Status::internal(format!("balance query failed: {e}")))?;
+
+        Ok(Response::new(GetBalanceResponse {
+            balance: balance.to_string(),
+        }))
+    }
+
+    async fn ping_peer(
+        &self,
+        request: Request<PingPeerRequest>,
+    ) -> Result<Response<PingPeerResponse>, Status> {
+        let pinger = self
+            .pinger
+            .as_ref()
+            .ok_or_else(|| Status::unavailable("peer ping not supported by this node"))?;
+        let overlay: OverlayAddress = parse_address(&request.into_inner().overlay)?;
+        let rtt = pinger.ping(&overlay).await.map_err(|e| match e {
+            SwarmError::PeerUnavailable { .. } => {
+                Status::failed_precondition(format!("peer {overlay} is not connected"))
+            }
+            e => Status::unavailable(format!("ping to {overlay} failed: {e}")),
+        })?;
+
+        Ok(Response::new(PingPeerResponse {
+            rtt_micros: u64::try_from(rtt.as_micros()).unwrap_or(u64::MAX),
+        }))
+    }
+
+    async fn get_depth_explanation(
+        &self,
+        _request: Request<GetDepthExplanationRequest>,
+    ) -> Result<Response<GetDepthExplanationResponse>, Status> {
+        Ok(Response::new(self.topology.depth_explanation().into()))
+    }
+
+    async fn get_connection_audit(
+        &self,
+        request: Request<GetConnectionAuditRequest>,
+    ) -> Result<Response<GetConnectionAuditResponse>, Status> {
+        let limit = request.into_inner().limit as usize;
+        let entries = self
+            .topology
+            .connection_audit(limit)
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(Response::new(GetConnectionAuditResponse { entries }))
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_address(address: &str) -> Result<ChunkAddress, Status> {
+    ChunkAddress::from_hex(address).map_err(|e| Status::invalid_argument(e.to_string()))
+}
+
+impl From<DepthExplanation> for GetDepthExplanationResponse {
+    fn from(explanation: DepthExplanation) -> Self {
+        let (limiting_bin, missing) = match explanation.limit {
+            DepthLimit::Unsaturated { bin, missing } => (Some(u32::from(bin.get())), missing),
+            DepthLimit::LowWatermark { missing } => (None, missing),
+            DepthLimit::MaxPo => (None, 0),
+        };
+        let limit: &'static str = explanation.limit.into();
+        Self {
+            depth: u32::from(explanation.depth.get()),
+            saturation: u32::from(explanation.saturation),
+            low_watermark: u32::from(explanation.low_watermark),
+            bins: explanation
+                .bins
+                .into_iter()
+                .map(|bin| DepthBin {
+                    proximity_order: u32::from(bin.bin.get()),
+                    connected_peers: bin.connected as u32,
+                    cumulative_peers: bin.cumulative as u32,
+                    saturated: bin.saturated,
+                })
+                .collect(),
+            limit: limit.to_string(),
+            limiting_bin,
+            missing_peers: missing as u32,
+        }
+    }
+}
+
+impl From<ConnectionAuditRecord> for ConnectionAuditEntry {
+    fn from(record: ConnectionAuditRecord) -> Self {
+        Self {
+            at_unix_ms: record.at_unix_ms,
+            peer_id: record.peer_id.to_string(),
+            overlay: record.overlay.map(|overlay| overlay.to_hex()),
+            stage: record.stage.to_string(),
+            detail: record.detail.map(str::to_string),
+        }
+    }
+}
+
+impl From<DiagnosticDump> for DumpResponse {
+    fn from(dump: DiagnosticDump) -> Self {
+        Self {
+            topology: Some(dump.topology.into()),
+            peers: dump.peers.into_iter().map(Into::into).collect(),
+            accounting: dump.accounting.into_iter().map(Into::into).collect(),
+            storage: dump.storage.map(Into::into),
+            config: Some(dump.config.into()),
+        }
+    }
+}
+
+impl From<TopologyDiagnostics> for DumpTopology {
+    fn from(topology: TopologyDiagnostics) -> Self {
+        Self {
+            overlay_address: topology.overlay,
+            network_id: topology.network_id,
+            depth: u32::from(topology.depth),
+            neighbourhood_credible: topology.neighbourhood_credible,
+            connected_peers: topology.connected_peers as u32,
+            known_peers: topology.routing_peers as u32,
+            pending_connections: topology.pending_connections as u32,
+            stored_peers: topology.stored_peers as u32,
+            bins: topology
+                .bins
+                .into_iter()
+                .map(|bin| BinInfo {
+                    proximity_order: u32::from(bin.proximity_order),
+                    connected_peers: bin.connected as u32,
+                    known_peers: bin.known as u32,
+                    connected_peer_addresses: Vec::new(),
+                    connected_peer_info: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<PeerDiagnostics> for DumpPeer {
+    fn from(peer: PeerDiagnostics) -> Self {
+        Self {
+            overlay: peer.overlay,
+            proximity_order: u32::from(peer.proximity_order),
+            multiaddrs: peer.multiaddrs,
+            score: peer.score,
+            discovery_source: peer.discovery_source,
+        }
+    }
+}
+
+impl From<LedgerDiagnostics> for DumpLedger {
+    fn from(ledger: LedgerDiagnostics) -> Self {
+        Self {
+            overlay: ledger.overlay,
+            balance: ledger.balance.get(),
+            reserved: ledger.reserved.get(),
+            disconnect_line: ledger.disconnect_line.get(),
+            settle_trigger: ledger.settle_trigger.get(),
+        }
+    }
+}
+
+impl From<StorageDiagnostics> for DumpStorage {
+    fn from(storage: StorageDiagnostics) -> Self {
+        Self {
+            storage_radius: u32::from(storage.storage_radius),
+            reserve_count: storage.reserve_count,
+            reserve_capacity: storage.reserve_capacity,
+        }
+    }
+}
+
+impl From<ConfigDiagnostics> for DumpConfig {
+    fn from(config: ConfigDiagnostics) -> Self {
+        Self {
+            node_type: config.node_type.to_string(),
+            listen_addrs: config.listen_addrs,
+            bootnodes: config.bootnodes,
+            trusted_peers: config.trusted_peers,
+            nat_addrs: config.nat_addrs,
+            discovery_enabled: config.discovery_enabled,
+            max_peers: config.max_peers as u32,
+            idle_timeout_secs: config.idle_timeout_secs,
+            nat_auto_enabled: config.nat_auto_enabled,
+            autonat_enabled: config.autonat_enabled,
+            upnp_enabled: config.upnp_enabled,
+            mdns_enabled: config.mdns_enabled,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vertex_swarm_api::{ConnectionAuditStage, SwarmResult, SwarmTopologyBins};
+    use vertex_swarm_primitives::{NeighborhoodDepth, NetworkId};
+
+    use super::*;
+
+    /// Connected set of fixed overlays; routing ranks them by proximity to the
+    /// target the way the Kademlia table does.
+    struct FixedTopology {
+        peers: Vec<OverlayAddress>,
+    }
+
+    impl SwarmTopologyBins for FixedTopology {
+        fn bin_sizes(&self) -> Vec<(usize, usize)> {
+            Vec::new()
+        }
+    }
+
+    impl SwarmTopologyState for FixedTopology {
+        fn overlay_address(&self) -> OverlayAddress {
+            overlay(0)
+        }
+        fn network_id(&self) -> NetworkId {
+            NetworkId::MAINNET
+        }
+        fn depth(&self) -> NeighborhoodDepth {
+            NeighborhoodDepth::ZERO
+        }
+        fn neighbourhood_credible(&self) -> bool {
+            false
+        }
+    }
+
+    impl SwarmTopologyStats for FixedTopology {
+        fn connected_peers_count(&self) -> usize {
+            self.peers.len()
+        }
+        fn routing_peers_count(&self) -> usize {
+            self.peers.len()
+        }
+        fn pending_connections_count(&self) -> usize {
+            0
+        }
+        fn stored_peers_count(&self) -> usize {
+            0
+        }
+        fn churn_rate(&self) -> f64 {
+            0.0
+        }
+        fn connection_audit(&self, limit: usize) -> Vec<ConnectionAuditRecord> {
+            let records: Vec<_> = self
+                .peers
+                .iter()
+                .map(|overlay| ConnectionAuditRecord {
+                    at_unix_ms: 1_000,
+                    peer_id: libp2p::PeerId::random(),
+                    overlay: Some(*overlay),
+                    stage: ConnectionAuditStage::Activated,
+                    detail: Some("outbound"),
+                })
+                .collect();
+            let skip = records.len().saturating_sub(limit);
+            records.into_iter().skip(skip).collect()
+        }
+    }
+
+    impl SwarmTopologyPeers for FixedTopology {
+        fn connected_peers_in_bin(&self, _bin: Bin) -> Vec<OverlayAddress> {
+            Vec::new()
+        }
+        fn connected_peer_details_in_bin(
+            &self,
+            _bin: Bin,
+        ) -> Vec<(OverlayAddress, Vec<libp2p::Multiaddr>)> {
+            Vec::new()
+        }
+    }
+
+    impl SwarmTopologyRouting for FixedTopology {
+        fn closest_to(&self, address: &ChunkAddress, count: usize) -> Vec<OverlayAddress> {
+            let mut peers = self.peers.clone();
+            peers.sort_by_key(|peer| std::cmp::Reverse(address.proximity(peer)));
+            peers.truncate(count);
+            peers
+        }
+        fn neighbors(&self, _depth: NeighborhoodDepth) -> Vec<OverlayAddress> {
+            Vec::new()
+        }
+    }
+
+    struct NoDiagnostics;
+
+    impl SwarmDiagnostics for NoDiagnostics {
+        fn diagnostic_dump(&self) -> DiagnosticDump {
+            unreachable!("closest_peers never reads diagnostics")
+        }
+    }
+
+    struct FixedFinance(u64);
+
+    #[async_trait::async_trait]
+    impl NodeFinance for FixedFinance {
+        async fn balance(&self) -> SwarmResult<alloy_primitives::U256> {
+            Ok(alloy_primitives::U256::from(self.0))
+        }
+    }
+
+    /// Answers pings from the one connected overlay with a fixed RTT.
+    struct ConnectedPinger {
+        connected: OverlayAddress,
+        rtt: std::time::Duration,
+    }
+
+    #[async_trait::async_trait]
+    impl PeerPinger for ConnectedPinger {
+        async fn ping(&self, peer: &OverlayAddress) -> SwarmResult<std::time::Duration> {
+            if *peer == self.connected {
+                Ok(self.rtt)
+            } else {
+                Err(SwarmError::PeerUnavailable {
+                    peer: Some(*peer),
+                    reason: "not connected".to_string(),
+                })
+            }
+        }
+    }
+
+    fn overlay(first: u8) -> OverlayAddress {
+        let mut bytes = [0u8; 32];
+        bytes[0] = first;
+        OverlayAddress::from(bytes)
+    }
+
+    #[tokio::test]
+    async fn closest_peers_match_routing_order_and_proximity() {
+        // Against a zero target: 0x01 shares 7 leading bits, 0x10 shares 3,
+        // 0x80 shares none.
+        let topology = FixedTopology {
+            peers: vec![overlay(0x80), overlay(0x01), overlay(0x10)],
+        };
+        let target = ChunkAddress::from([0u8; 32]);
+        let expected = topology.closest_to(&target, 2);
+        let service = NodeService::new(topology, DiagnosticsHandle::new(NoDiagnostics));
+
+        let response = service
+            .closest_peers(Request::new(ClosestPeersRequest {
+                address: format!("0x{}", target.to_hex()),
+                count: 2,
+            }))
+            .await
+            .expect("valid request")
+            .into_inner();
+
+        let overlays: Vec<_> = response.peers.iter().map(|p| p.overlay.clone()).collect();
+        let expected: Vec<_> = expected.iter().map(SwarmAddressExt::to_hex).collect();
+        assert_eq!(overlays, expected);
+        assert_eq!(
+            overlays,
+            vec![overlay(0x01).to_hex(), overlay(0x10).to_hex()]
+        );
+        let proximities: Vec<_> = response.peers.iter().map(|p| p.proximity_order).collect();
+        assert_eq!(proximities, vec![7, 3]);
+    }
+
+    #[tokio::test]
+    async fn closest_peers_rejects_malformed_address() {
+        let topology = FixedTopology { peers: Vec::new() };
+        let service = NodeService::new(topology, DiagnosticsHandle::new(NoDiagnostics));
+
+        let status = service
+            .closest_peers(Request::new(ClosestPeersRequest {
+                address: "abcd".to_string(),
+                count: 1,
+            }))
+            .await
+            .expect_err("short address");
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn get_balance_reports_plur_or_unavailable() {
+        let service = NodeService::new(
+            FixedTopology { peers: Vec::new() },
+            DiagnosticsHandle::new(NoDiagnostics),
+        );
+        let status = service
+            .get_balance(Request::new(GetBalanceRequest {}))
+            .await
+            .expect_err("no finance configured");
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+
+        let service = service.with_finance(Arc::new(FixedFinance(10_000_000_000_000_000)));
+        let response = service
+            .get_balance(Request::new(GetBalanceRequest {}))
+            .await
+            .expect("balance")
+            .into_inner();
+        assert_eq!(response.balance, "10000000000000000");
+    }
+
+    #[tokio::test]
+    async fn ping_peer_returns_rtt_or_not_connected() {
+        let service = NodeService::new(
+            FixedTopology { peers: Vec::new() },
+            DiagnosticsHandle::new(NoDiagnostics),
+        );
+        let request = |peer: OverlayAddress| {
+            Request::new(PingPeerRequest {
+                overlay: peer.to_hex(),
+            })
+        };
+        let status = service
+            .ping_peer(request(overlay(0x01)))
+            .await
+            .expect_err("no pinger configured");
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+
+        let service = service.with_pinger(Arc::new(ConnectedPinger {
+            connected: overlay(0x01),
+            rtt: std::time::Duration::from_micros(1_500),
+        }));
+        let response = service
+            .ping_peer(request(overlay(0x01)))
+            .await
+            .expect("responsive peer")
+            .into_inner();
+        assert_eq!(response.rtt_micros, 1_500);
+
+        let status = service
+            .ping_peer(request(overlay(0x02)))
+            .await
+            .expect_err("peer not connected");
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+    }
+
+    #[tokio::test]
+    async fn depth_explanation_reports_the_limiting_bin() {
+        let service = NodeService::new(
+            FixedTopology { peers: Vec::new() },
+            DiagnosticsHandle::new(NoDiagnostics),
+        );
+        let response = service
+            .get_depth_explanation(Request::new(GetDepthExplanationRequest {}))
+            .await
+            .expect("explanation")
+            .into_inner();
+
+        // An empty table is held at depth 0 by its unsaturated first bin.
+        assert_eq!(response.depth, 0);
+        assert_eq!(response.limit, "unsaturated");
+        assert_eq!(response.limiting_bin, Some(0));
+        assert_eq!(response.missing_peers, response.saturation);
+        // Deepest bin first, down to bin 0.
+        let orders: Vec<u32> = response
+            .bins
+            .iter()
+            .map(|bin| bin.proximity_order)
+            .collect();
+        assert_eq!(orders.last(), Some(&0));
+        assert!(
+            orders
+                .iter()
+                .zip(orders.iter().skip(1))
+                .all(|(deeper, next)| *deeper == next + 1)
+        );
+    }
+
+    #[tokio::test]
+    async fn connection_audit_returns_the_newest_records() {
+        let service = NodeService::new(
+            FixedTopology {
+                peers: vec![overlay(0x10), overlay(0x20)],
+            },
+            DiagnosticsHandle::new(NoDiagnostics),
+        );
+        let response = service
+            .get_connection_audit(Request::new(GetConnectionAuditRequest { limit: 1 }))
+            .await
+            .expect("audit")
+            .into_inner();
+
+        assert_eq!(response.entries.len(), 1);
+        let entry = response.entries.first().expect("one entry");
+        assert_eq!(entry.overlay, Some(overlay(0x20).to_hex()));
+        assert_eq!(entry.stage, "activated");
+        assert_eq!(entry.detail.as_deref(), Some("outbound"));
+        assert_eq!(entry.at_unix_ms, 1_000);
+    }
 }
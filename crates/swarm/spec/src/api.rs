@@ -6,6 +6,7 @@ use crate::{
 };
 use alloc::{string::String, vec::Vec};
 use alloy_chains::Chain;
+use core::num::NonZeroUsize;
 use nectar_primitives::{NetworkId, StandardChunkSet};
 use nectar_swarms::Swarm;
 use vertex_swarm_api::{SwarmSpec, SwarmSpecProvider};
@@ -55,6 +56,22 @@ impl SwarmSpec for Spec {
         self.reserve_capacity
     }
 
+    fn protocol_prefix(&self) -> &str {
+        &self.protocol_prefix
+    }
+
+    fn min_payment_threshold(&self) -> u64 {
+        self.min_payment_threshold
+    }
+
+    fn address_size(&self) -> NonZeroUsize {
+        self.address_size
+    }
+
+    fn requires_storage_payment(&self) -> bool {
+        self.storage_payment.unwrap_or_else(|| !self.is_dev())
+    }
+
     fn is_fork_active_at_timestamp(&self, fork: SwarmHardfork, timestamp: u64) -> bool {
         match self.hardforks.get(fork) {
             Some(ForkCondition::Timestamp(activation_time)) => timestamp >= activation_time,
@@ -125,6 +142,41 @@ mod tests {
         assert!(dev.is_dev());
     }
 
+    #[test]
+    fn test_storage_payment() {
+        assert!(init_mainnet().requires_storage_payment());
+        assert!(init_testnet().requires_storage_payment());
+        assert!(!SpecBuilder::dev().build().requires_storage_payment());
+
+        // The flag overrides the network default either way.
+        assert!(
+            SpecBuilder::dev()
+                .storage_payment(true)
+                .build()
+                .requires_storage_payment()
+        );
+        assert!(
+            !SpecBuilder::testnet()
+                .storage_payment(false)
+                .build()
+                .requires_storage_payment()
+        );
+    }
+
+    #[test]
+    fn test_default_swap_enabled() {
+        use vertex_swarm_api::SwarmNodeType;
+
+        let mainnet = init_mainnet();
+        assert!(mainnet.default_swap_enabled(SwarmNodeType::Storer));
+        assert!(!mainnet.default_swap_enabled(SwarmNodeType::Client));
+        assert!(init_testnet().default_swap_enabled(SwarmNodeType::Storer));
+
+        let dev = SpecBuilder::dev().build();
+        assert!(!dev.default_swap_enabled(SwarmNodeType::Storer));
+        assert!(!dev.default_swap_enabled(SwarmNodeType::Client));
+    }
+
     #[test]
     fn test_spec_provider() {
         let spec = init_mainnet();
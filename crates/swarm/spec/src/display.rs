@@ -80,6 +80,8 @@ impl<S: SwarmSpec> Loggable for S {
             self.token().symbol(),
             self.token().address()
         );
+        info!("  Protocol prefix: {}", self.protocol_prefix());
+        info!("  Min payment threshold: {}", self.min_payment_threshold());
         info!("  Chunk size: {} bytes", self.chunk_size());
         info!("  Chunks: {}", S::ChunkSet::format_supported_types());
         info!(
@@ -88,7 +90,11 @@ impl<S: SwarmSpec> Loggable for S {
             format_reserve_size(self.reserve_capacity(), self.chunk_size())
         );
 
-        tracing::debug!("  Max proximity order: {}", self.max_po());
+        tracing::debug!(
+            "  Address size: {} bytes (max proximity order {})",
+            self.address_size(),
+            self.max_po()
+        );
 
         info!("  Hardforks:");
         for (fork, condition) in self.hardforks().forks_iter() {
@@ -156,6 +156,7 @@ pub mod prelude {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use core::num::NonZeroUsize;
     use nectar_primitives::NetworkId;
 
     #[test]
@@ -209,4 +210,63 @@ mod tests {
         assert_eq!(custom.chunk_size(), nectar_primitives::DEFAULT_BODY_SIZE);
         assert_eq!(custom.reserve_capacity(), 1 << 20);
     }
+
+    #[test]
+    fn test_protocol_prefix() {
+        assert_eq!(init_mainnet().protocol_prefix(), "/swarm");
+        assert_eq!(SpecBuilder::new().build().protocol_prefix(), "/swarm");
+
+        let custom = SpecBuilder::testnet().protocol_prefix("/myswarm").build();
+        assert_eq!(custom.protocol_prefix(), "/myswarm");
+        assert_eq!(
+            SpecBuilder::from(&custom).build().protocol_prefix(),
+            "/myswarm"
+        );
+    }
+
+    #[test]
+    fn test_min_payment_threshold() {
+        assert_eq!(
+            init_mainnet().min_payment_threshold(),
+            vertex_swarm_api::DEFAULT_MIN_PAYMENT_THRESHOLD
+        );
+
+        let custom = SpecBuilder::testnet()
+            .min_payment_threshold(5_000_000)
+            .build();
+        assert_eq!(custom.min_payment_threshold(), 5_000_000);
+        assert_eq!(
+            SpecBuilder::from(&custom).build().min_payment_threshold(),
+            5_000_000
+        );
+    }
+
+    #[test]
+    fn test_address_size_sets_max_po() {
+        assert_eq!(
+            init_mainnet().address_size(),
+            vertex_swarm_api::DEFAULT_ADDRESS_SIZE
+        );
+        assert_eq!(init_mainnet().max_po(), nectar_primitives::MAX_PO);
+
+        let two = NonZeroUsize::new(2).expect("non-zero");
+        let custom = SpecBuilder::testnet().address_size(two).build();
+        assert_eq!(custom.max_po(), 15);
+        assert_eq!(SpecBuilder::from(&custom).build().address_size(), two);
+        assert_eq!(
+            SpecBuilder::testnet()
+                .address_size(NonZeroUsize::new(64).expect("non-zero"))
+                .build()
+                .max_po(),
+            nectar_primitives::MAX_PO,
+            "capped at the widest range the bin types hold"
+        );
+    }
+
+    #[test]
+    fn test_zero_address_size_is_rejected() {
+        let spec = Spec::try_from("network_id = 7\naddress_size = 2").expect("valid spec");
+        assert_eq!(spec.address_size().get(), 2);
+        assert!(Spec::try_from("network_id = 7\naddress_size = 0").is_err());
+    }
 }
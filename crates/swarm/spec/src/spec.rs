@@ -24,8 +24,12 @@ use alloc::{
     vec::Vec,
 };
 use alloy_chains::{Chain, NamedChain};
+use core::num::NonZeroUsize;
 #[cfg(feature = "std")]
 use std::sync::OnceLock;
+use vertex_swarm_api::{
+    DEFAULT_ADDRESS_SIZE, DEFAULT_MIN_PAYMENT_THRESHOLD, DEFAULT_PROTOCOL_PREFIX,
+};
 use vertex_swarm_forks::{ForkCondition, SwarmHardfork, SwarmHardforks, SwarmHardforksTrait};
 
 /// A concrete Swarm network specification.
@@ -76,6 +80,25 @@ pub struct Spec {
     /// Reserve capacity in number of chunks for Storers (typically 2^22)
     #[serde(default = "default_reserve_capacity")]
     pub reserve_capacity: u64,
+
+    /// Namespace for every wire protocol name (e.g. `/swarm`, `/myswarm`).
+    #[serde(default = "default_protocol_prefix")]
+    pub protocol_prefix: String,
+
+    /// Lowest payment threshold a peer may announce, in accounting units.
+    #[serde(default = "default_min_payment_threshold")]
+    pub min_payment_threshold: u64,
+
+    /// Whether storing a chunk requires a postage stamp. `None` derives it
+    /// from the network: free on development networks, paid elsewhere.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_payment: Option<bool>,
+
+    /// Overlay and chunk address length in bytes; the maximum proximity order
+    /// (and so the bin count) derives from it. Only research networks change
+    /// it from the default 32. Zero is rejected on load.
+    #[serde(default = "default_address_size")]
+    pub address_size: NonZeroUsize,
 }
 
 fn default_chain() -> Chain {
@@ -94,6 +117,18 @@ fn default_reserve_capacity() -> u64 {
     DEFAULT_RESERVE_CAPACITY
 }
 
+fn default_protocol_prefix() -> String {
+    DEFAULT_PROTOCOL_PREFIX.to_string()
+}
+
+fn default_min_payment_threshold() -> u64 {
+    DEFAULT_MIN_PAYMENT_THRESHOLD
+}
+
+fn default_address_size() -> NonZeroUsize {
+    DEFAULT_ADDRESS_SIZE
+}
+
 impl Default for Spec {
     fn default() -> Self {
         Self {
@@ -105,6 +140,10 @@ impl Default for Spec {
             token: dev::TOKEN,
             genesis_timestamp: 0,
             reserve_capacity: DEFAULT_RESERVE_CAPACITY,
+            protocol_prefix: default_protocol_prefix(),
+            min_payment_threshold: DEFAULT_MIN_PAYMENT_THRESHOLD,
+            storage_payment: None,
+            address_size: DEFAULT_ADDRESS_SIZE,
         }
     }
 }
@@ -127,6 +166,10 @@ pub(crate) fn init_mainnet() -> Arc<Spec> {
                 token: mainnet::TOKEN,
                 genesis_timestamp: SwarmHardfork::MAINNET_GENESIS_TIMESTAMP,
                 reserve_capacity: DEFAULT_RESERVE_CAPACITY,
+                protocol_prefix: default_protocol_prefix(),
+                min_payment_threshold: DEFAULT_MIN_PAYMENT_THRESHOLD,
+                storage_payment: None,
+                address_size: DEFAULT_ADDRESS_SIZE,
             };
 
             Arc::new(spec)
@@ -152,6 +195,10 @@ pub(crate) fn init_testnet() -> Arc<Spec> {
                 token: testnet::TOKEN,
                 genesis_timestamp: SwarmHardfork::TESTNET_GENESIS_TIMESTAMP,
                 reserve_capacity: DEFAULT_RESERVE_CAPACITY,
+                protocol_prefix: default_protocol_prefix(),
+                min_payment_threshold: DEFAULT_MIN_PAYMENT_THRESHOLD,
+                storage_payment: None,
+                address_size: DEFAULT_ADDRESS_SIZE,
             };
 
             Arc::new(spec)
@@ -184,6 +231,10 @@ pub struct SpecBuilder {
     token: Option<Token>,
     genesis_timestamp: Option<u64>,
     reserve_capacity: Option<u64>,
+    protocol_prefix: Option<String>,
+    min_payment_threshold: Option<u64>,
+    storage_payment: Option<bool>,
+    address_size: Option<NonZeroUsize>,
 }
 
 impl SpecBuilder {
@@ -260,6 +311,34 @@ impl SpecBuilder {
         self
     }
 
+    /// Set the protocol-name prefix (e.g. `/myswarm`).
+    ///
+    /// Nodes only negotiate protocols with peers using the same prefix.
+    pub fn protocol_prefix(mut self, prefix: impl ToString) -> Self {
+        self.protocol_prefix = Some(prefix.to_string());
+        self
+    }
+
+    /// Set the lowest payment threshold a peer may announce.
+    pub fn min_payment_threshold(mut self, threshold: u64) -> Self {
+        self.min_payment_threshold = Some(threshold);
+        self
+    }
+
+    /// Require or waive postage stamps for storage, overriding the network
+    /// default (free on development networks, paid elsewhere).
+    pub fn storage_payment(mut self, required: bool) -> Self {
+        self.storage_payment = Some(required);
+        self
+    }
+
+    /// Set the overlay and chunk address length in bytes, from which the
+    /// maximum proximity order derives.
+    pub fn address_size(mut self, bytes: NonZeroUsize) -> Self {
+        self.address_size = Some(bytes);
+        self
+    }
+
     /// Build the specification
     pub fn build(self) -> Spec {
         let chain = self.chain.unwrap_or(Chain::from(NamedChain::Dev));
@@ -301,6 +380,12 @@ impl SpecBuilder {
             token,
             genesis_timestamp,
             reserve_capacity: self.reserve_capacity.unwrap_or(DEFAULT_RESERVE_CAPACITY),
+            protocol_prefix: self.protocol_prefix.unwrap_or_else(default_protocol_prefix),
+            min_payment_threshold: self
+                .min_payment_threshold
+                .unwrap_or(DEFAULT_MIN_PAYMENT_THRESHOLD),
+            storage_payment: self.storage_payment,
+            address_size: self.address_size.unwrap_or(DEFAULT_ADDRESS_SIZE),
         }
     }
 
@@ -334,6 +419,10 @@ impl From<&Spec> for SpecBuilder {
             token: Some(spec.token.clone()),
             genesis_timestamp: Some(spec.genesis_timestamp),
             reserve_capacity: Some(spec.reserve_capacity),
+            protocol_prefix: Some(spec.protocol_prefix.clone()),
+            min_payment_threshold: Some(spec.min_payment_threshold),
+            storage_payment: spec.storage_payment,
+            address_size: Some(spec.address_size),
         }
     }
 }
@@ -19,6 +19,8 @@ use libp2p::{
 use strum::IntoStaticStr;
 use vertex_net_ratelimiter::{KeyedRateLimiter, Quota};
 use vertex_swarm_api::{Bin, PullStorage, StampedChunk};
+use vertex_swarm_forks::ForkCondition;
+use vertex_swarm_net_headers::ProtocolPrefix;
 
 use crate::error::PullsyncFailure;
 use crate::handler::{PullsyncCommand, PullsyncHandler, PullsyncHandlerEvent};
@@ -72,6 +74,12 @@ pub struct PullsyncBehaviour {
     /// Shared into each handler so the per-peer chunks-per-second bucket
     /// survives reconnects; freed on the final `ConnectionClosed`.
     chunk_limit: Arc<KeyedRateLimiter<PeerId>>,
+    /// Namespace the pullsync ids are advertised under; see
+    /// [`Self::with_protocol_prefix`].
+    prefix: ProtocolPrefix,
+    /// Activation of the hardfork carrying the range page `limit`; see
+    /// [`Self::with_accord`].
+    accord: ForkCondition,
     events: VecDeque<ToSwarm<PullsyncEvent, PullsyncCommand>>,
 }
 
@@ -81,10 +89,29 @@ impl PullsyncBehaviour {
         Self {
             storage,
             chunk_limit: Arc::new(KeyedRateLimiter::new(CHUNK_QUOTA)),
+            prefix: ProtocolPrefix::DEFAULT,
+            accord: ForkCondition::Never,
             events: VecDeque::new(),
         }
     }
 
+    /// Advertise the pullsync ids under the spec's protocol prefix instead of
+    /// `/swarm`. Must be set before any peer connects.
+    #[must_use]
+    pub fn with_protocol_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = ProtocolPrefix::new(prefix);
+        self
+    }
+
+    /// Send and honour the range page `limit` once `accord`, the spec's
+    /// activation of that hardfork, is reached. Before it requests carry no
+    /// limit and inbound limits are ignored.
+    #[must_use]
+    pub fn with_accord(mut self, accord: ForkCondition) -> Self {
+        self.accord = accord;
+        self
+    }
+
     /// Open the cursor handshake against `peer`. The peer's cursors arrive as a
     /// [`PullsyncEvent::CursorsReceived`] carrying `request_id`.
     pub fn fetch_cursors(&mut self, peer: PeerId, request_id: u64) {
@@ -95,9 +122,12 @@ impl PullsyncBehaviour {
         });
     }
 
-    /// Open a range exchange against `peer` for `bin` from `start`. The selected
-    /// chunks arrive as a [`PullsyncEvent::RangeDelivered`] carrying `request_id`.
-    pub fn sync_range(&mut self, peer: PeerId, request_id: u64, bin: Bin, start: u64) {
+    /// Open a range exchange against `peer` for `bin` from `start`, asking for
+    /// at most `limit` chunks (zero, or a pre-Accord spec, leaves the page size
+    /// to the peer). The
+    /// selected chunks arrive as a [`PullsyncEvent::RangeDelivered`] carrying
+    /// `request_id`.
+    pub fn sync_range(&mut self, peer: PeerId, request_id: u64, bin: Bin, start: u64, limit: u64) {
         self.events.push_back(ToSwarm::NotifyHandler {
             peer_id: peer,
             handler: NotifyHandler::Any,
@@ -105,6 +135,7 @@ impl PullsyncBehaviour {
                 request_id,
                 bin,
                 start,
+                limit,
             },
         });
     }
@@ -114,6 +145,8 @@ impl PullsyncBehaviour {
             peer,
             Arc::clone(&self.storage),
             Arc::clone(&self.chunk_limit),
+            self.prefix,
+            self.accord,
         )
     }
 }
@@ -32,17 +32,19 @@ use libp2p::{
 use tracing::{debug, warn};
 use vertex_net_ratelimiter::{KeyedRateLimiter, Quota};
 use vertex_swarm_api::{Bin, ChunkAddress, PullStorage, StampedChunk, SwarmResult};
+use vertex_swarm_forks::ForkCondition;
 use vertex_swarm_net_handler_core::HandlerCore;
+use vertex_swarm_net_headers::ProtocolPrefix;
 use vertex_swarm_net_pullsync::{
-    Ack, BitVector, ChunkDescriptor, DEFAULT_MAX_PAGE, Delivery, Get, Offer, SyncRequester,
-    SyncResponder, Want,
+    Ack, BitVector, ChunkDescriptor, Delivery, Get, Offer, SyncRequester, SyncResponder, Want,
 };
 use vertex_swarm_primitives::all_bins;
 use vertex_tasks::time::timeout;
+use vertex_util_runtime::time::now_unix_secs;
 
 use crate::error::PullsyncFailure;
 use crate::upgrade::{
-    InboundOutput, OutboundOutput, PullsyncInboundUpgrade, PullsyncOutboundUpgrade,
+    InboundOutput, OutboundOutput, OutboundRequest, PullsyncInboundUpgrade, PullsyncOutboundUpgrade,
 };
 
 /// Per-connection inbound substream-open quota: headroom for a burst of cursor
@@ -85,10 +87,12 @@ pub enum PullsyncCommand {
     /// Open the cursor handshake and report the peer's per-bin cursors.
     FetchCursors { request_id: u64 },
     /// Open a range exchange for `bin` from `start` and collect the deliveries.
+    /// `limit` caps the offered page; zero leaves it to the responder.
     SyncRange {
         request_id: u64,
         bin: Bin,
         start: u64,
+        limit: u64,
     },
 }
 
@@ -149,6 +153,10 @@ pub struct PullsyncHandler {
     /// Shared with the behaviour so the per-peer chunks-per-second bucket
     /// survives reconnects; freed on the final `ConnectionClosed`.
     chunk_limit: Arc<KeyedRateLimiter<PeerId>>,
+    /// Namespace both substream ids are advertised and requested under.
+    prefix: ProtocolPrefix,
+    /// Activation of the hardfork carrying the range page `limit`.
+    accord: ForkCondition,
     pending_commands: VecDeque<PullsyncCommand>,
     inbound: FuturesUnordered<BoxFuture<'static, InboundOutcome>>,
     outbound: FuturesUnordered<BoxFuture<'static, RangeOutcome>>,
@@ -159,12 +167,16 @@ impl PullsyncHandler {
         remote_peer_id: PeerId,
         storage: Arc<dyn PullStorage>,
         chunk_limit: Arc<KeyedRateLimiter<PeerId>>,
+        prefix: ProtocolPrefix,
+        accord: ForkCondition,
     ) -> Self {
         Self {
             remote_peer_id,
             storage,
             core: HandlerCore::new(INBOUND_SUBSTREAM_QUOTA),
             chunk_limit,
+            prefix,
+            accord,
             pending_commands: VecDeque::new(),
             inbound: FuturesUnordered::new(),
             outbound: FuturesUnordered::new(),
@@ -201,9 +213,19 @@ impl PullsyncHandler {
         }));
     }
 
+    /// Whether the Accord range page `limit` is spoken right now.
+    fn accord_active(&self) -> bool {
+        self.accord.active_at_timestamp(now_unix_secs())
+    }
+
     /// Serve a range request: page the bin, offer descriptors, deliver the wanted
-    /// chunks under the per-second cap.
+    /// chunks under the per-second cap. A `limit` sent before Accord is ignored.
     fn serve_range(&mut self, get: Get, responder: SyncResponder) {
+        let get = if self.accord_active() {
+            get
+        } else {
+            Get::new(get.bin, get.start)
+        };
         let storage = Arc::clone(&self.storage);
         let chunk_limit = Arc::clone(&self.chunk_limit);
         let peer = self.remote_peer_id;
@@ -244,7 +266,7 @@ async fn serve_range_inner(
     get: Get,
     responder: SyncResponder,
 ) -> InboundOutcome {
-    let (descriptors, topmost) = match page_bin(&storage, get.bin, get.start) {
+    let (descriptors, topmost) = match page_bin(&storage, get.bin, get.start, get.page_size()) {
         Ok(page) => page,
         Err(e) => {
             debug!(error = %e, "Pullsync range scan failed");
@@ -332,13 +354,15 @@ async fn serve_range_inner(
     }
 }
 
-/// Collect up to [`DEFAULT_MAX_PAGE`] descriptors for `bin` from `start`, paired
-/// with their address, plus the topmost sequence covered.
+/// Collect up to `limit` descriptors for `bin` from `start`, paired with their
+/// address, plus the topmost sequence covered. The caller caps `limit` at
+/// [`DEFAULT_MAX_PAGE`](vertex_swarm_net_pullsync::DEFAULT_MAX_PAGE).
 #[allow(clippy::type_complexity)]
 fn page_bin(
     storage: &Arc<dyn PullStorage>,
     bin: Bin,
     start: u64,
+    limit: u64,
 ) -> SwarmResult<(Vec<(ChunkAddress, ChunkDescriptor)>, u64)> {
     let mut descriptors = Vec::new();
     // Raised only from scanned ids; an empty range yields topmost 0, never
@@ -352,7 +376,7 @@ fn page_bin(
             item.address,
             ChunkDescriptor::new(item.address, item.batch_id, item.stamp_hash),
         ));
-        if descriptors.len() as u64 >= DEFAULT_MAX_PAGE {
+        if descriptors.len() as u64 >= limit {
             break;
         }
     }
@@ -440,7 +464,8 @@ impl ConnectionHandler for PullsyncHandler {
     type OutboundOpenInfo = OutboundInfo;
 
     fn listen_protocol(&self) -> SubstreamProtocol<Self::InboundProtocol, Self::InboundOpenInfo> {
-        SubstreamProtocol::new(PullsyncInboundUpgrade, ()).with_timeout(STREAM_TIMEOUT)
+        SubstreamProtocol::new(PullsyncInboundUpgrade::new(self.prefix), ())
+            .with_timeout(STREAM_TIMEOUT)
     }
 
     fn connection_keep_alive(&self) -> bool {
@@ -506,17 +531,24 @@ impl ConnectionHandler for PullsyncHandler {
         {
             let (protocol, info) = match cmd {
                 PullsyncCommand::FetchCursors { request_id } => (
-                    PullsyncOutboundUpgrade::Cursors,
+                    PullsyncOutboundUpgrade::new(OutboundRequest::Cursors, self.prefix),
                     OutboundInfo::Cursors { request_id },
                 ),
                 PullsyncCommand::SyncRange {
                     request_id,
                     bin,
                     start,
-                } => (
-                    PullsyncOutboundUpgrade::Sync(Get::new(bin, start)),
-                    OutboundInfo::Sync { request_id, bin },
-                ),
+                    limit,
+                } => {
+                    let mut get = Get::new(bin, start);
+                    if self.accord_active() {
+                        get = get.with_limit(limit);
+                    }
+                    (
+                        PullsyncOutboundUpgrade::new(OutboundRequest::Sync(get), self.prefix),
+                        OutboundInfo::Sync { request_id, bin },
+                    )
+                }
             };
             return Poll::Ready(ConnectionHandlerEvent::OutboundSubstreamRequest {
                 protocol: SubstreamProtocol::new(protocol, info).with_timeout(OUTBOUND_TIMEOUT),
@@ -2,10 +2,11 @@
 //!
 //! Inbound advertises both [`PROTOCOL_CURSORS`] and [`PROTOCOL_SYNC`] and
 //! dispatches on the negotiated id. Outbound knows its id from the command.
+//! Both advertise the ids under the spec's protocol prefix.
 
 use futures::future::BoxFuture;
 use libp2p::{InboundUpgrade, OutboundUpgrade, Stream, core::UpgradeInfo};
-use vertex_swarm_net_headers::ProtocolError;
+use vertex_swarm_net_headers::{ProtocolError, ProtocolPrefix};
 use vertex_swarm_net_pullsync::{
     Ack, CursorsResponder, Get, Offer, PROTOCOL_CURSORS, PROTOCOL_SYNC, SyncRequester,
     SyncResponder, cursors_inbound, cursors_outbound, sync_inbound, sync_outbound,
@@ -21,14 +22,24 @@ pub enum InboundOutput {
 
 /// Inbound upgrade for both pullsync substreams.
 #[derive(Clone, Debug, Default)]
-pub struct PullsyncInboundUpgrade;
+pub struct PullsyncInboundUpgrade {
+    prefix: ProtocolPrefix,
+}
+
+impl PullsyncInboundUpgrade {
+    pub fn new(prefix: ProtocolPrefix) -> Self {
+        Self { prefix }
+    }
+}
 
 impl UpgradeInfo for PullsyncInboundUpgrade {
     type Info = &'static str;
     type InfoIter = std::array::IntoIter<Self::Info, 2>;
 
     fn protocol_info(&self) -> Self::InfoIter {
-        [PROTOCOL_CURSORS, PROTOCOL_SYNC].into_iter()
+        [PROTOCOL_CURSORS, PROTOCOL_SYNC]
+            .map(|name| self.prefix.apply(name))
+            .into_iter()
     }
 }
 
@@ -38,8 +49,9 @@ impl InboundUpgrade<Stream> for PullsyncInboundUpgrade {
     type Future = BoxFuture<'static, Result<Self::Output, Self::Error>>;
 
     fn upgrade_inbound(self, socket: Stream, info: Self::Info) -> Self::Future {
+        let canonical = self.prefix.canonical(info);
         Box::pin(async move {
-            match info {
+            match canonical.as_ref() {
                 PROTOCOL_CURSORS => {
                     let responder = cursors_inbound().upgrade_inbound(socket, info).await?;
                     Ok(InboundOutput::Cursors(responder))
@@ -63,24 +75,36 @@ pub enum OutboundOutput {
     Sync(Offer, SyncRequester),
 }
 
-/// Outbound upgrade selecting one pullsync substream per command.
-pub enum PullsyncOutboundUpgrade {
+/// Pullsync substream an outbound command opens.
+pub enum OutboundRequest {
     /// Open the cursor handshake.
     Cursors,
     /// Open a range exchange for the given `Get`.
     Sync(Get),
 }
 
+/// Outbound upgrade selecting one pullsync substream per command.
+pub struct PullsyncOutboundUpgrade {
+    request: OutboundRequest,
+    prefix: ProtocolPrefix,
+}
+
+impl PullsyncOutboundUpgrade {
+    pub fn new(request: OutboundRequest, prefix: ProtocolPrefix) -> Self {
+        Self { request, prefix }
+    }
+}
+
 impl UpgradeInfo for PullsyncOutboundUpgrade {
     type Info = &'static str;
     type InfoIter = std::iter::Once<Self::Info>;
 
     fn protocol_info(&self) -> Self::InfoIter {
-        let name = match self {
-            Self::Cursors => PROTOCOL_CURSORS,
-            Self::Sync(_) => PROTOCOL_SYNC,
+        let name = match self.request {
+            OutboundRequest::Cursors => PROTOCOL_CURSORS,
+            OutboundRequest::Sync(_) => PROTOCOL_SYNC,
         };
-        std::iter::once(name)
+        std::iter::once(self.prefix.apply(name))
     }
 }
 
@@ -91,12 +115,12 @@ impl OutboundUpgrade<Stream> for PullsyncOutboundUpgrade {
 
     fn upgrade_outbound(self, socket: Stream, info: Self::Info) -> Self::Future {
         Box::pin(async move {
-            match self {
-                Self::Cursors => {
+            match self.request {
+                OutboundRequest::Cursors => {
                     let ack = cursors_outbound().upgrade_outbound(socket, info).await?;
                     Ok(OutboundOutput::Cursors(ack))
                 }
-                Self::Sync(get) => {
+                OutboundRequest::Sync(get) => {
                     let (offer, requester) =
                         sync_outbound(get).upgrade_outbound(socket, info).await?;
                     Ok(OutboundOutput::Sync(offer, requester))
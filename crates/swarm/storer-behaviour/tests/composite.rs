@@ -198,7 +198,7 @@ async fn composite_routes_pullsync_range() {
     puller
         .behaviour_mut()
         .pullsync
-        .sync_range(server_peer, 1, bin, 0);
+        .sync_range(server_peer, 1, bin, 0, 0);
 
     let event = tokio::time::timeout(Duration::from_secs(10), async {
         loop {
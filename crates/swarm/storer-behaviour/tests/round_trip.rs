@@ -15,6 +15,7 @@ use nectar_primitives::{AnyChunk, Bin, ChunkAddress, ContentChunk, ProximityOrde
 use vertex_swarm_api::{
     BatchId, BinScanItem, PullStorage, StampedChunk, StorageRadius, SwarmResult,
 };
+use vertex_swarm_forks::ForkCondition;
 use vertex_swarm_primitives::CachedChunk;
 use vertex_swarm_storer_behaviour::{PullsyncBehaviour, PullsyncEvent};
 
@@ -146,8 +147,44 @@ fn content(payload: &'static [u8]) -> StampedChunk {
 }
 
 fn syncer(storage: MockPullStorage) -> Swarm<PullsyncBehaviour> {
+    syncer_at(storage, ForkCondition::Never)
+}
+
+/// A syncer speaking the Accord range `limit` once `accord` is reached.
+fn syncer_at(storage: MockPullStorage, accord: ForkCondition) -> Swarm<PullsyncBehaviour> {
     let storage: Arc<dyn PullStorage> = Arc::new(storage);
-    Swarm::new_ephemeral_tokio(move |_| PullsyncBehaviour::new(Arc::clone(&storage)))
+    Swarm::new_ephemeral_tokio(move |_| {
+        PullsyncBehaviour::new(Arc::clone(&storage)).with_accord(accord)
+    })
+}
+
+/// Request a range page and wait for the puller's first behaviour event.
+async fn range_event(
+    puller: &mut Swarm<PullsyncBehaviour>,
+    server: &mut Swarm<PullsyncBehaviour>,
+    bin: Bin,
+    limit: u64,
+) -> PullsyncEvent {
+    let server_peer = *server.local_peer_id();
+    connect(puller, server).await;
+    puller
+        .behaviour_mut()
+        .sync_range(server_peer, 4, bin, 0, limit);
+
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            tokio::select! {
+                _ = server.select_next_some() => {}
+                ev = puller.select_next_some() => {
+                    if let libp2p::swarm::SwarmEvent::Behaviour(e) = ev {
+                        return e;
+                    }
+                }
+            }
+        }
+    })
+    .await
+    .expect("range resolved within timeout")
 }
 
 /// Connect a puller and a syncer over an in-memory transport.
@@ -211,7 +248,7 @@ async fn range_exchange_delivers_the_page() {
     let server_peer = *server.local_peer_id();
 
     connect(&mut puller, &mut server).await;
-    puller.behaviour_mut().sync_range(server_peer, 2, bin, 0);
+    puller.behaviour_mut().sync_range(server_peer, 2, bin, 0, 0);
 
     let event = tokio::time::timeout(Duration::from_secs(10), async {
         loop {
@@ -248,6 +285,59 @@ async fn range_exchange_delivers_the_page() {
     }
 }
 
+fn limited_chunks() -> Vec<StampedChunk> {
+    vec![
+        content(b"limited chunk one"),
+        content(b"limited chunk two"),
+        content(b"limited chunk three"),
+    ]
+}
+
+/// Past Accord a limited request is offered at most `limit` chunks, and
+/// `topmost` stops at the last one offered so the next page resumes after it.
+#[tokio::test]
+async fn range_exchange_honours_the_limit() {
+    let bin = Bin::new(3).expect("valid bin");
+    let chunks = limited_chunks();
+    let first = *chunks[0].address();
+    let accord = ForkCondition::Timestamp(0);
+    let mut puller = syncer_at(MockPullStorage::default(), accord);
+    let mut server = syncer_at(MockPullStorage::with_chunks(bin, 1, chunks), accord);
+
+    match range_event(&mut puller, &mut server, bin, 1).await {
+        PullsyncEvent::RangeDelivered {
+            topmost, chunks, ..
+        } => {
+            assert_eq!(topmost, 1, "topmost stops at the one offered entry");
+            assert_eq!(chunks.len(), 1);
+            assert_eq!(*chunks[0].address(), first);
+        }
+        other => panic!("expected a range delivery, got {other:?}"),
+    }
+}
+
+/// Before Accord the limit is neither sent nor honoured: the whole bin is
+/// offered even to a responder that would otherwise read it.
+#[tokio::test]
+async fn range_limit_is_ignored_before_accord() {
+    let bin = Bin::new(3).expect("valid bin");
+    let mut puller = syncer(MockPullStorage::default());
+    let mut server = syncer_at(
+        MockPullStorage::with_chunks(bin, 1, limited_chunks()),
+        ForkCondition::Timestamp(0),
+    );
+
+    match range_event(&mut puller, &mut server, bin, 1).await {
+        PullsyncEvent::RangeDelivered {
+            topmost, chunks, ..
+        } => {
+            assert_eq!(topmost, 3, "the unlimited page covers the whole bin");
+            assert_eq!(chunks.len(), 3);
+        }
+        other => panic!("expected a range delivery, got {other:?}"),
+    }
+}
+
 /// An empty range completes promptly with topmost 0 and no want round.
 #[tokio::test]
 async fn empty_range_completes_with_no_want() {
@@ -260,7 +350,7 @@ async fn empty_range_completes_with_no_want() {
     let server_peer = *server.local_peer_id();
 
     connect(&mut puller, &mut server).await;
-    puller.behaviour_mut().sync_range(server_peer, 3, bin, 0);
+    puller.behaviour_mut().sync_range(server_peer, 3, bin, 0, 0);
 
     let event = tokio::time::timeout(Duration::from_secs(5), async {
         loop {
@@ -852,3 +852,50 @@ fn evict_to_capacity_frees_the_body_only_on_the_last_reference() {
         "body freed only when the last referencing entry is evicted"
     );
 }
+
+#[test]
+fn verify_flags_corrupt_and_orphaned_entries_and_repair_removes_them() {
+    use vertex_storage::DbTxMut;
+
+    let fx = Fixture::new();
+    let (good, good_addr) = content_chunk_in_bucket0(1);
+    let (bad, bad_addr) = content_chunk_in_bucket0(2);
+    let (lost, lost_addr) = content_chunk_in_bucket0(3);
+    fx.put(&good, &good_addr, fx.batch_id(), 0, 100).unwrap();
+    fx.put(&bad, &bad_addr, fx.batch_id(), 1, 100).unwrap();
+    fx.put(&lost, &lost_addr, fx.batch_id(), 2, 100).unwrap();
+    assert!(fx.reserve.verify().unwrap().is_consistent());
+
+    // Flip the last payload byte of one body so it no longer hashes to its
+    // address, and drop another body outright so its entry is orphaned.
+    fx.db
+        .update(|tx| {
+            let mut payload = tx.get::<Payload>(bad_addr)?.expect("body stored");
+            if let Some(last) = payload.typed_bytes.last_mut() {
+                *last ^= 0xff;
+            }
+            tx.put::<Payload>(bad_addr, payload)?;
+            tx.delete::<Payload>(lost_addr)?;
+            Ok(())
+        })
+        .unwrap();
+
+    let report = fx.reserve.verify().unwrap();
+    assert_eq!(report.scanned, 2);
+    assert_eq!(report.corrupt, vec![bad_addr]);
+    assert_eq!(report.orphaned, vec![lost_addr]);
+    assert_eq!(report.removed, 0, "verify changes nothing");
+    assert_eq!(fx.row_count::<Entry>(), 3);
+
+    let repaired = fx.reserve.repair().unwrap();
+    assert_eq!(repaired.corrupt, report.corrupt);
+    assert_eq!(repaired.orphaned, report.orphaned);
+    assert_eq!(repaired.removed, 2, "both flagged entries removed");
+
+    assert!(!fx.reserve.contains(&bad_addr), "corrupt body removed");
+    assert!(fx.reserve.contains(&good_addr), "intact chunk untouched");
+    assert_eq!(fx.row_count::<Entry>(), 1);
+    assert_eq!(fx.row_count::<Payload>(), 1);
+    assert_eq!(fx.reserve.count().unwrap(), 1, "size counter follows");
+    assert!(fx.reserve.verify().unwrap().is_consistent());
+}
@@ -49,14 +49,23 @@
 //! decrement the shared payload, drop the body only when the last referencing
 //! entry goes.
 //!
+//! # Consistency
+//!
+//! [`DbReserve::verify`] scans for bodies that no longer hash to their address
+//! and entries whose body is missing; [`DbReserve::repair`] re-checks and
+//! removes them through the eviction delete path. Both report through the
+//! same [`VerifyReport`](crate::VerifyReport) as [`ChunkStore::verify`].
+//!
 //! [`AnyChunk`]: nectar_primitives::AnyChunk
 //! [`AdmissionValidator`]: vertex_swarm_postage::AdmissionValidator
 //! [`StampIndexTable`]: vertex_swarm_postage::StampIndexTable
 //! [`postage::decide`]: vertex_swarm_postage::decide
+//! [`ChunkStore::verify`]: crate::ChunkStore::verify
 
 mod schema;
 mod store;
 mod tx;
+mod verify;
 
 #[cfg(test)]
 mod consensus_spec;
@@ -38,15 +38,15 @@ use super::tx::{
 /// [`BinCursorStore`]. The payload and all index rows commit in one transaction
 /// per operation via the shared database handle.
 pub struct DbReserve<DB: Database, BS: BatchStore> {
-    db: Arc<DB>,
+    pub(super) db: Arc<DB>,
     /// Validate-on-ingest admission for stamped chunks.
     admission: AdmissionValidator,
     /// Loads the batch a stamp references, and the live [`PostageContext`].
     batches: BS,
     /// In-memory size counter, kept in step with the authoritative [`Entry`]
     /// table count for cheap reads.
-    reserve: Reserve,
-    overlay: OverlayAddress,
+    pub(super) reserve: Reserve,
+    pub(super) overlay: OverlayAddress,
     /// Current storage-responsibility radius (a single `0..=MAX_PO` byte).
     ///
     /// `Relaxed` suffices: the radius carries no happens-before relationship
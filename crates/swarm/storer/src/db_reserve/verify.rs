@@ -0,0 +1,126 @@
+//! Consistency check and repair for the reserve tables.
+//!
+//! [`DbReserve::verify`] walks every stored body and every stamped entry in one
+//! read transaction, reporting bodies that no longer hash to their address and
+//! entries whose body is gone. [`DbReserve::repair`] runs that read-only scan,
+//! then opens a short write transaction touching only the flagged addresses:
+//! each is re-read and skipped if a write since the scan put it right, so a
+//! concurrent put is never deleted as corrupt and ingest stalls only for the
+//! deletes. Deletes go through the same path eviction uses, so the repaired
+//! tables keep the no-tombstone invariant.
+
+use nectar_primitives::ChunkAddress;
+use tracing::warn;
+use vertex_storage::{Database, DatabaseError, DbTx, DbTxMut};
+use vertex_swarm_api::SwarmResult;
+use vertex_swarm_postage::BatchStore;
+
+use super::EvictTarget;
+use super::schema::{Entry, Payload};
+use super::store::DbReserve;
+use super::tx::{delete_entry_in_tx, storage_err};
+use crate::VerifyReport;
+use crate::verify::body_matches;
+
+impl<DB: Database, BS: BatchStore> DbReserve<DB, BS> {
+    /// Scan the reserve for corrupt bodies and orphaned entries without
+    /// changing anything.
+    ///
+    /// A full scan of the payload and entry tables inside one read
+    /// transaction, so it sees a consistent snapshot while writes go on.
+    pub fn verify(&self) -> SwarmResult<VerifyReport> {
+        self.db
+            .view(|tx| scan(tx).map(|(report, _)| report))
+            .map_err(storage_err)
+    }
+
+    /// Scan as [`Self::verify`], then remove every stamped entry of an address
+    /// that is still corrupt or orphaned when re-checked, along with any
+    /// corrupt body.
+    ///
+    /// The returned report lists what the scan found and how many entries
+    /// were removed. Removed chunks are simply absent afterwards; pull-sync
+    /// re-fetches any the node is still responsible for.
+    pub fn repair(&self) -> SwarmResult<VerifyReport> {
+        let (mut report, targets) = self.db.view(scan).map_err(storage_err)?;
+        if report.is_consistent() {
+            return Ok(report);
+        }
+
+        let overlay = self.overlay;
+        report.removed = self
+            .db
+            .update(|tx| {
+                let mut removed = 0;
+                for address in report.corrupt.iter().chain(&report.orphaned) {
+                    // A body re-stored intact since the scan clears the flag.
+                    let still_bad = tx
+                        .get::<Payload>(*address)?
+                        .is_none_or(|payload| !body_matches(address, &payload.typed_bytes));
+                    if !still_bad {
+                        continue;
+                    }
+                    for target in targets.iter().filter(|t| t.addr == *address) {
+                        if delete_entry_in_tx(tx, &overlay, target)? {
+                            removed += 1;
+                        }
+                    }
+                    // A corrupt body whose refcount overstated its entries
+                    // survives the decrements above; drop it outright.
+                    tx.delete::<Payload>(*address)?;
+                }
+                Ok(removed)
+            })
+            .map_err(storage_err)?;
+        self.reserve.on_removed_n(report.removed);
+
+        warn!(
+            corrupt = report.corrupt.len(),
+            orphaned = report.orphaned.len(),
+            removed = report.removed,
+            "repaired inconsistent reserve entries"
+        );
+        Ok(report)
+    }
+}
+
+/// Build the report and collect the stamped entries of every flagged address.
+///
+/// Keys are listed without their values and each body is read on its own, so
+/// only addresses are held in memory.
+fn scan<T: DbTx>(tx: &T) -> Result<(VerifyReport, Vec<EvictTarget>), DatabaseError> {
+    let mut report = VerifyReport::default();
+
+    // Payload keys list in address order, so `corrupt` comes out sorted and
+    // the entry pass can binary-search it.
+    for address in tx.keys::<Payload>()? {
+        report.scanned += 1;
+        let intact = tx
+            .get::<Payload>(address)?
+            .is_some_and(|payload| body_matches(&address, &payload.typed_bytes));
+        if !intact {
+            report.corrupt.push(address);
+        }
+    }
+
+    let mut targets = Vec::new();
+    for key in tx.keys::<Entry>()? {
+        let orphaned = !tx.exists::<Payload>(key.addr)?;
+        if orphaned {
+            report.orphaned.push(key.addr);
+        }
+        if orphaned || report.corrupt.binary_search(&key.addr).is_ok() {
+            targets.push(EvictTarget {
+                batch: key.batch,
+                stamp_hash: key.stamp_hash,
+                addr: key.addr,
+            });
+        }
+    }
+    // Entries are keyed proximity-major, so one address's entries need not be
+    // adjacent.
+    report.orphaned.sort_unstable();
+    report.orphaned.dedup();
+
+    Ok((report, targets))
+}
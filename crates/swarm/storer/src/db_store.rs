@@ -9,7 +9,8 @@ use std::sync::Arc;
 use nectar_primitives::ChunkAddress;
 use vertex_storage::{Database, DbTx, DbTxMut, Table, table};
 
-use crate::{ChunkStore, StorerResult};
+use crate::verify::body_matches;
+use crate::{ChunkStore, StorerResult, VerifyReport};
 
 // Chunk table: ChunkAddress -> raw chunk bytes.
 //
@@ -91,6 +92,49 @@ impl<DB: Database> ChunkStore for DbChunkStore<DB> {
         }
         Ok(())
     }
+
+    fn verify(&self) -> StorerResult<VerifyReport> {
+        // One read transaction over the whole table: a consistent snapshot that
+        // never blocks writers. The cursor walks in key order, so `corrupt`
+        // comes out sorted.
+        let report = self.db.view(|tx| {
+            let mut report = VerifyReport::default();
+            let mut cursor = tx.cursor::<ChunkTable>()?;
+            let mut entry = cursor.first()?;
+            while let Some((address, data)) = entry {
+                report.scanned += 1;
+                if !body_matches(&address, &data) {
+                    report.corrupt.push(address);
+                }
+                entry = cursor.next()?;
+            }
+            Ok(report)
+        })?;
+        Ok(report)
+    }
+
+    fn repair(&self) -> StorerResult<VerifyReport> {
+        let mut report = self.verify()?;
+        if report.is_consistent() {
+            return Ok(report);
+        }
+        // Only the flagged keys are touched, each re-read first so a chunk
+        // deleted or re-stored since the scan is left alone.
+        report.removed = self.db.update(|tx| {
+            let mut removed = 0;
+            for address in &report.corrupt {
+                let still_corrupt = tx
+                    .get::<ChunkTable>(*address)?
+                    .is_some_and(|data| !body_matches(address, &data));
+                if still_corrupt {
+                    tx.delete::<ChunkTable>(*address)?;
+                    removed += 1;
+                }
+            }
+            Ok(removed)
+        })?;
+        Ok(report)
+    }
 }
 
 #[cfg(test)]
@@ -249,4 +293,27 @@ mod tests {
         assert_eq!(store.get(&addr).unwrap(), Some(b"persisted".to_vec()));
         assert_eq!(store.count().unwrap(), 1);
     }
+
+    #[test]
+    fn test_verify_flags_corrupt_chunk_and_repair_removes_it() {
+        with_backends(|store| {
+            let (good, good_bytes) = crate::traits::tests::typed_chunk(b"intact");
+            let (bad, bad_bytes) = crate::traits::tests::corrupt_chunk(b"bit rot");
+            store.put(&good, &good_bytes).unwrap();
+            store.put(&bad, &bad_bytes).unwrap();
+
+            let report = store.verify().unwrap();
+            assert_eq!(report.scanned, 2);
+            assert_eq!(report.corrupt, vec![bad]);
+            assert_eq!(report.removed, 0, "verify changes nothing");
+            assert!(store.contains(&bad).unwrap());
+
+            let repaired = store.repair().unwrap();
+            assert_eq!(repaired.corrupt, vec![bad]);
+            assert_eq!(repaired.removed, 1);
+            assert!(!store.contains(&bad).unwrap(), "corrupt chunk removed");
+            assert!(store.contains(&good).unwrap(), "intact chunk untouched");
+            assert!(store.verify().unwrap().is_consistent());
+        });
+    }
 }
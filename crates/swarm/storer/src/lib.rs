@@ -16,6 +16,7 @@ mod expiry;
 mod radius;
 mod reserve;
 mod traits;
+mod verify;
 
 pub use cache::ChunkCache;
 pub use db_intervals::DbIntervalStore;
@@ -29,6 +30,7 @@ pub use radius::{
 };
 pub use reserve::{EvictionStrategy, Reserve};
 pub use traits::ChunkStore;
+pub use verify::VerifyReport;
 
 /// Result type for storer operations.
 pub type StorerResult<T> = Result<T, StorerError>;
@@ -5,6 +5,7 @@
 //! the vertex-storage `Database` trait.
 
 use crate::StorerResult;
+use crate::verify::{VerifyReport, body_matches};
 use nectar_primitives::ChunkAddress;
 
 /// Chunk storage backend trait.
@@ -49,6 +50,52 @@ pub trait ChunkStore: Send + Sync {
     fn for_each<F>(&self, callback: F) -> StorerResult<()>
     where
         F: FnMut(&ChunkAddress) -> bool;
+
+    /// Scan every stored chunk and report those whose bytes no longer decode
+    /// or hash to their address, without changing anything.
+    ///
+    /// The default lists addresses with [`Self::for_each`] and reads each
+    /// body on its own; a chunk deleted mid-scan is skipped, not flagged.
+    fn verify(&self) -> StorerResult<VerifyReport> {
+        let mut addresses = Vec::new();
+        self.for_each(|address| {
+            addresses.push(*address);
+            true
+        })?;
+        addresses.sort_unstable();
+
+        let mut report = VerifyReport::default();
+        for address in addresses {
+            let Some(data) = self.get(&address)? else {
+                continue;
+            };
+            report.scanned += 1;
+            if !body_matches(&address, &data) {
+                report.corrupt.push(address);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Run [`Self::verify`], then delete every flagged chunk that is still
+    /// corrupt when re-read.
+    ///
+    /// The scan holds nothing; only the flagged keys are re-checked and
+    /// deleted afterwards. Backends with transactions should do that second
+    /// step in one short write transaction.
+    fn repair(&self) -> StorerResult<VerifyReport> {
+        let mut report = self.verify()?;
+        for address in &report.corrupt {
+            if self
+                .get(address)?
+                .is_some_and(|data| !body_matches(address, &data))
+            {
+                self.delete(address)?;
+                report.removed += 1;
+            }
+        }
+        Ok(report)
+    }
 }
 
 /// In-memory chunk store for testing.
@@ -113,3 +160,49 @@ pub(crate) mod memory {
         }
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use nectar_primitives::{AnyChunk, Chunk, DefaultContentChunk};
+
+    use super::memory::MemoryChunkStore;
+    use super::*;
+
+    /// A content chunk's address and type-tagged bytes.
+    pub(crate) fn typed_chunk(payload: &[u8]) -> (ChunkAddress, Vec<u8>) {
+        let chunk = DefaultContentChunk::new(payload.to_vec()).unwrap();
+        let address = *chunk.address();
+        (address, AnyChunk::from(chunk).to_typed_bytes().to_vec())
+    }
+
+    /// As [`typed_chunk`], with the last payload byte flipped so the bytes no
+    /// longer hash to the address.
+    pub(crate) fn corrupt_chunk(payload: &[u8]) -> (ChunkAddress, Vec<u8>) {
+        let (address, mut bytes) = typed_chunk(payload);
+        if let Some(last) = bytes.last_mut() {
+            *last ^= 0xff;
+        }
+        (address, bytes)
+    }
+
+    #[test]
+    fn memory_store_verify_and_repair() {
+        let store = MemoryChunkStore::new();
+        let (good, good_bytes) = typed_chunk(b"intact");
+        let (bad, bad_bytes) = corrupt_chunk(b"bit rot");
+        store.put(&good, &good_bytes).unwrap();
+        store.put(&bad, &bad_bytes).unwrap();
+
+        let report = store.verify().unwrap();
+        assert_eq!(report.scanned, 2);
+        assert_eq!(report.corrupt, vec![bad]);
+        assert!(report.orphaned.is_empty());
+        assert_eq!(report.removed, 0, "verify changes nothing");
+
+        let repaired = store.repair().unwrap();
+        assert_eq!(repaired.removed, 1);
+        assert!(!store.contains(&bad).unwrap(), "corrupt chunk removed");
+        assert!(store.contains(&good).unwrap(), "intact chunk untouched");
+        assert!(store.verify().unwrap().is_consistent());
+    }
+}
@@ -0,0 +1,39 @@
+//! Consistency-scan report shared by the chunk stores and the reserve.
+
+use nectar_primitives::{AnyChunk, ChunkAddress};
+use vertex_swarm_primitives::content_address;
+
+/// What a store consistency scan found.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// Stored chunk bodies scanned.
+    pub scanned: u64,
+    /// Addresses whose stored body fails to decode or no longer hashes to the
+    /// address it is stored under, ascending.
+    pub corrupt: Vec<ChunkAddress>,
+    /// Addresses with index entries but no stored body, ascending. Always
+    /// empty for a [`ChunkStore`], which keeps no index beside its bodies.
+    ///
+    /// [`ChunkStore`]: crate::ChunkStore
+    pub orphaned: Vec<ChunkAddress>,
+    /// Entries removed by a repair; zero after a plain verify.
+    pub removed: u64,
+}
+
+impl VerifyReport {
+    /// Whether the scan found nothing to repair.
+    pub fn is_consistent(&self) -> bool {
+        self.corrupt.is_empty() && self.orphaned.is_empty()
+    }
+}
+
+/// Whether a stored body decodes as type-tagged chunk bytes for `address`
+/// and, for a content chunk, still hashes to it. The same check
+/// [`ValidatedChunk`] applies on ingest.
+///
+/// [`ValidatedChunk`]: vertex_swarm_primitives::ValidatedChunk
+pub(crate) fn body_matches(address: &ChunkAddress, typed_bytes: &[u8]) -> bool {
+    AnyChunk::from_typed_bytes(address, typed_bytes).is_ok_and(|chunk| {
+        !chunk.is_content() || content_address(chunk.data(), chunk.span()) == *address
+    })
+}
@@ -24,13 +24,14 @@ pub mod wasm;
 use std::collections::VecDeque;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use futures::StreamExt;
 use futures::stream::{FuturesUnordered, Stream};
 use nectar_primitives::{AnyChunk, ChunkAddress};
 use vertex_swarm_api::{
-    OverlayAddress, PushReceipt, Stamp, StampedChunk, SwarmChunkProvider, SwarmChunkSender,
-    SwarmError, SwarmResult,
+    ChunkRetrievalResult, OverlayAddress, PushReceipt, Stamp, StampedChunk, SwarmChunkProvider,
+    SwarmChunkSender, SwarmError, SwarmResult,
 };
 // `Send` on native (so the streams stay `Send` for tonic), unbounded on wasm.
 use vertex_tasks::{MaybeSend, MaybeSendBoxFuture, MaybeSendIter, MaybeSendStream};
@@ -192,6 +193,24 @@ where
     P: SwarmChunkProvider,
 {
     let result = provider.retrieve_chunk(&address).await?;
+    verified(address, result)
+}
+
+/// [`retrieve_verified`] through the provider's fast path: one attempt at the
+/// best peer, bounded by `max_latency`.
+pub async fn retrieve_verified_fast<P>(
+    provider: P,
+    address: ChunkAddress,
+    max_latency: Duration,
+) -> SwarmResult<VerifiedChunk>
+where
+    P: SwarmChunkProvider,
+{
+    let result = provider.retrieve_chunk_fast(&address, max_latency).await?;
+    verified(address, result)
+}
+
+fn verified(address: ChunkAddress, result: ChunkRetrievalResult) -> SwarmResult<VerifiedChunk> {
     if *result.chunk.address() != address {
         return Err(SwarmError::InvalidChunk {
             address: Some(address),
@@ -590,7 +609,8 @@ mod tests {
     use async_trait::async_trait;
     use nectar_primitives::{AnyChunk, ContentChunk, Nonce};
     use vertex_swarm_api::{
-        ChunkRetrievalResult, Stamp, StorageRadius, SwarmChunkProvider, SwarmChunkSender,
+        ChunkRetrievalResult, RetrievalFailure, Stamp, StorageRadius, SwarmChunkProvider,
+        SwarmChunkSender,
     };
 
     use super::*;
@@ -668,9 +688,14 @@ mod tests {
                         chunk,
                         stamp: Some(stamp),
                         served_by: OverlayAddress::from([1u8; 32]),
+                        retries: 0,
+                        elapsed: std::time::Duration::ZERO,
                     })
                 }
-                None => Err(SwarmError::RetrievalExhausted { address: *address }),
+                None => Err(SwarmError::RetrievalExhausted {
+                    address: *address,
+                    reason: RetrievalFailure::NotFound,
+                }),
             }
         }
 
@@ -697,6 +722,8 @@ mod tests {
                 chunk,
                 stamp: Some(stamp),
                 served_by: OverlayAddress::from([2u8; 32]),
+                retries: 0,
+                elapsed: std::time::Duration::ZERO,
             })
         }
 
@@ -895,6 +922,8 @@ mod tests {
                 chunk: self.chunk.clone(),
                 stamp: None,
                 served_by: OverlayAddress::from([3u8; 32]),
+                retries: 0,
+                elapsed: std::time::Duration::ZERO,
             })
         }
 
@@ -584,8 +584,8 @@ impl vertex_swarm_api::SwarmNetworkConfig for TestNetworkConfig {
     fn discovery_enabled(&self) -> bool {
         true
     }
-    fn max_peers(&self) -> usize {
-        16
+    fn max_peers(&self) -> Option<usize> {
+        Some(16)
     }
     fn idle_timeout(&self) -> Duration {
         Duration::from_secs(30)
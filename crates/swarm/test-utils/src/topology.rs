@@ -26,6 +26,7 @@ pub struct MockTopology {
     depth: u8,
     credible: bool,
     closest: Vec<OverlayAddress>,
+    in_bins: Vec<OverlayAddress>,
 }
 
 impl Default for MockTopology {
@@ -39,6 +40,7 @@ impl Default for MockTopology {
             depth: 0,
             credible: true,
             closest: Vec::new(),
+            in_bins: Vec::new(),
         }
     }
 }
@@ -66,6 +68,7 @@ impl MockTopology {
             depth,
             credible: true,
             closest: Vec::new(),
+            in_bins: Vec::new(),
         }
     }
 
@@ -126,6 +129,15 @@ impl MockTopology {
         self
     }
 
+    /// Set the connected peers returned by
+    /// [`SwarmTopologyPeers::connected_peers_in_bin`], each in the bin of its
+    /// proximity to the local overlay.
+    #[must_use]
+    pub fn with_connected_in_bins(mut self, peers: Vec<OverlayAddress>) -> Self {
+        self.in_bins = peers;
+        self
+    }
+
     /// Get the overlay address as SwarmAddress.
     pub fn overlay(&self) -> SwarmAddress {
         self.identity.overlay_address()
@@ -172,8 +184,13 @@ impl SwarmTopologyRouting for MockTopology {
 }
 
 impl SwarmTopologyPeers for MockTopology {
-    fn connected_peers_in_bin(&self, _po: Bin) -> Vec<OverlayAddress> {
-        Vec::new()
+    fn connected_peers_in_bin(&self, po: Bin) -> Vec<OverlayAddress> {
+        let local = self.overlay();
+        self.in_bins
+            .iter()
+            .filter(|peer| local.proximity(peer).get() == po.get())
+            .copied()
+            .collect()
     }
 
     fn connected_peer_details_in_bin(
@@ -200,6 +217,10 @@ impl SwarmTopologyStats for MockTopology {
     fn stored_peers_count(&self) -> usize {
         self.stored
     }
+
+    fn churn_rate(&self) -> f64 {
+        0.0
+    }
 }
 
 impl SwarmTopologyReporting for MockTopology {
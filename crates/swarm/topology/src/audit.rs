@@ -0,0 +1,114 @@
+//! Connection-establishment audit log.
+//!
+//! General logs say what the node did; reconstructing what happened to one
+//! connection from them means grepping several modules at several levels.
+//! [`ConnectionAudit`] keeps a structured record of each lifecycle step (dial
+//! started, connected, handshake result, activated, closed with reason) in a
+//! bounded ring buffer the RPC can read back, and writes each record to the
+//! dedicated [`AUDIT_TARGET`] tracing target so it can be enabled on its own.
+
+use std::collections::VecDeque;
+
+use libp2p::PeerId;
+use parking_lot::Mutex;
+use tracing::debug;
+use vertex_swarm_api::{ConnectionAuditRecord, ConnectionAuditStage};
+use vertex_swarm_primitives::OverlayAddress;
+use vertex_tasks::time::now_unix_millis;
+
+/// Tracing target every audit record is logged under
+/// (`RUST_LOG=connection_audit=debug`).
+pub const AUDIT_TARGET: &str = "connection_audit";
+
+/// Records kept before the oldest are dropped. A few minutes of heavy churn.
+pub(crate) const AUDIT_CAPACITY: usize = 1024;
+
+/// Bounded audit log shared by the behaviour (records) and the handle (reads).
+pub(crate) struct ConnectionAudit {
+    capacity: usize,
+    records: Mutex<VecDeque<ConnectionAuditRecord>>,
+}
+
+impl ConnectionAudit {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            records: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Append a record, evicting the oldest once at capacity.
+    pub(crate) fn record(
+        &self,
+        peer_id: PeerId,
+        overlay: Option<OverlayAddress>,
+        stage: ConnectionAuditStage,
+        detail: Option<&'static str>,
+    ) {
+        debug!(
+            target: AUDIT_TARGET,
+            %peer_id,
+            ?overlay,
+            stage = <&'static str>::from(stage),
+            detail,
+            "connection audit"
+        );
+        let record = ConnectionAuditRecord {
+            at_unix_ms: now_unix_millis(),
+            peer_id,
+            overlay,
+            stage,
+            detail,
+        };
+        let mut records = self.records.lock();
+        if records.len() >= self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    /// The most recent `limit` records, oldest first.
+    pub(crate) fn recent(&self, limit: usize) -> Vec<ConnectionAuditRecord> {
+        let records = self.records.lock();
+        let skip = records.len().saturating_sub(limit);
+        records.iter().skip(skip).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vertex_swarm_test_utils::test_overlay;
+
+    use super::*;
+
+    #[test]
+    fn keeps_the_most_recent_records_in_order() {
+        let audit = ConnectionAudit::new(3);
+        let peer = PeerId::random();
+        for stage in [
+            ConnectionAuditStage::DialStarted,
+            ConnectionAuditStage::Connected,
+            ConnectionAuditStage::HandshakeCompleted,
+            ConnectionAuditStage::Activated,
+        ] {
+            audit.record(peer, Some(test_overlay(1)), stage, None);
+        }
+
+        let stages = |records: Vec<ConnectionAuditRecord>| -> Vec<_> {
+            records.into_iter().map(|r| r.stage).collect()
+        };
+        assert_eq!(
+            stages(audit.recent(10)),
+            vec![
+                ConnectionAuditStage::Connected,
+                ConnectionAuditStage::HandshakeCompleted,
+                ConnectionAuditStage::Activated,
+            ],
+            "the oldest record is evicted at capacity"
+        );
+        assert_eq!(
+            stages(audit.recent(1)),
+            vec![ConnectionAuditStage::Activated]
+        );
+    }
+}
@@ -1,7 +1,7 @@
 //! Network topology behaviour managing peer connections via handshake, hive, and ping.
 
 use std::{
-    collections::{HashMap, HashSet, VecDeque},
+    collections::{HashMap, HashSet, VecDeque, hash_map::Entry},
     sync::Arc,
     task::{Context, Poll},
     time::Duration,
@@ -22,12 +22,15 @@ use vertex_net_local::{AddressScope, classify_multiaddr, same_subnet};
 use vertex_net_peer_store::PeerSnapshotStore;
 use vertex_net_ratelimiter::{Quota, RateLimitedErr, RateLimiter};
 use vertex_swarm_api::{
-    BanCause, ConnectionProfile, DisconnectReason, PeerLifecycleEvent, SwarmIdentity,
+    BanCause, ConnectionProfile, DisconnectReason, PeerLifecycleEvent, SwarmIdentity, SwarmSpec,
 };
+use vertex_swarm_forks::{ForkCondition, SwarmHardfork};
 use vertex_swarm_net_hive::MAX_BATCH_SIZE;
 use vertex_swarm_net_identify as identify;
-use vertex_swarm_peer::SwarmPeer;
-use vertex_swarm_peer_manager::{PeerManager, PeerSnapshot, TrustLevel};
+use vertex_swarm_peer::{SwarmPeer, Timestamp};
+use vertex_swarm_peer_manager::{
+    ConnectionStateMachine, ConnectionTransition, PeerManager, PeerSnapshot, TrustLevel,
+};
 use vertex_swarm_primitives::{Bin, NeighborhoodDepth, OverlayAddress, all_bins};
 
 use crate::DialReason;
@@ -46,14 +49,21 @@ pub(crate) type ConnectionRegistry = PeerRegistry<OverlayAddress, Option<DialRea
 pub(crate) type BootnodeResolutionFuture =
     vertex_tasks::MaybeSendBoxFuture<(Vec<Multiaddr>, Vec<Multiaddr>)>;
 use crate::TopologyCommand;
+use crate::audit::ConnectionAudit;
+use crate::bootnode::{BootnodeConnector, DEFAULT_BOOTNODE_RETRY_BUDGET};
 use crate::builder::PendingTopologyTasks;
+use crate::churn::ChurnTracker;
 use crate::composed::ProtocolBehaviours;
 use crate::events::TopologyEvent;
 use crate::extract_peer_id;
 use crate::gossip::{GossipConfig, GossipHandle, GossipInput};
-use crate::kademlia::{KademliaConfig, KademliaRouting, RoutingEvaluatorHandle, SwarmRouting};
+use crate::kademlia::{
+    KademliaConfig, KademliaRouting, RoutingEvaluatorHandle, SwarmRouting, peer_selection,
+};
 use crate::metrics::{TopologyMetrics, po_label};
 use crate::nat_discovery::LocalAddressManager;
+use crate::partition::PartitionDetector;
+use crate::static_peers::StaticPeers;
 
 /// Type-erased peer snapshot store.
 pub(crate) type PeerStore = Arc<dyn PeerSnapshotStore<PeerSnapshot>>;
@@ -63,19 +73,27 @@ pub(crate) type PeerStore = Arc<dyn PeerSnapshotStore<PeerSnapshot>>;
 /// scored down.
 const DEFAULT_EARLY_DISCONNECT_THRESHOLD: Duration = Duration::from_secs(30);
 
+/// How recently an inbound-only peer must have done useful work to keep its
+/// eviction grace (see [`TopologyConfig::with_inbound_only_grace`]).
+const DEFAULT_INBOUND_ONLY_GRACE: Duration = Duration::from_secs(10 * 60);
+
 /// Event broadcast buffer (256 allows burst without blocking poll loop).
 pub(crate) const EVENT_CHANNEL_CAPACITY: usize = 256;
 
 /// Command buffer (64 is sufficient for typical dial/disconnect rate).
 pub(crate) const COMMAND_CHANNEL_CAPACITY: usize = 64;
 
+/// Dials held back while the outbound-dial limit is reached. Beyond this a
+/// reconnect storm sheds dials instead of growing the queue.
+pub(crate) const MAX_QUEUED_DIALS: usize = 1024;
+
 /// Target for dialing a peer (internal).
 ///
-/// `DialTarget` is only ever passed by value to `Self::dial(...)` and dropped
-/// at the end of that call; it never lives in a collection. The size
-/// asymmetry between `Known` and `Unknown` is a one-shot stack cost per
-/// dial, so boxing the `SwarmPeer` would just add a heap allocation for no
-/// real benefit.
+/// `DialTarget` is passed by value to `Self::dial(...)` and only outlives that
+/// call while parked in the bounded dial queue at the outbound-dial limit.
+/// The size asymmetry between `Known` and `Unknown` is small next to the
+/// queue bound, so boxing the `SwarmPeer` would just add a heap allocation
+/// for no real benefit.
 #[derive(Debug)]
 #[allow(clippy::large_enum_variant)]
 pub(crate) enum DialTarget {
@@ -120,8 +138,8 @@ impl DialTarget {
 /// time: the explicit [`Self::with_connection_profile`] selection wins,
 /// otherwise the network configuration's choice, otherwise the node-type
 /// default. The `Option` overrides here ([`Self::with_dial_interval`],
-/// [`Self::with_dial_quota`]) pin single knobs over whatever the profile
-/// resolves to, for tests and embedders.
+/// [`Self::with_dial_quota`], [`Self::with_max_concurrent_dials`]) pin single
+/// knobs over whatever the profile resolves to, for tests and embedders.
 #[derive(Debug, Clone)]
 pub struct TopologyConfig {
     pub kademlia: KademliaConfig,
@@ -135,7 +153,28 @@ pub struct TopologyConfig {
     pub dial_interval: Option<Duration>,
     /// Explicit discovery dial-rate quota; `None` uses the profile's quota.
     pub dial_quota: Option<Quota>,
+    /// Explicit cap on simultaneous outbound dials of every kind (discovery,
+    /// bootnode, command); `None` uses the profile's dial concurrency.
+    pub max_concurrent_dials: Option<usize>,
+    /// Explicit cap on handshakes in progress, inbound and outbound; `None`
+    /// uses the handshake crate's default.
+    pub max_concurrent_handshakes: Option<usize>,
     pub early_disconnect_threshold: Duration,
+    /// How recently an inbound-only peer must have served us to be ranked
+    /// with reachable peers for eviction; zero disables the grace.
+    pub inbound_only_grace: Duration,
+    /// Connects plus disconnects per minute above which
+    /// [`TopologyEvent::ChurnExceeded`] is raised; `None` disables the alert.
+    pub churn_threshold: Option<f64>,
+    /// Redials a bootnode that dropped before serving us gets before it is
+    /// demoted to an ordinary known peer.
+    pub bootnode_retry_budget: u32,
+    /// Peers kept connected regardless of Kademlia's bin logic, as
+    /// `(overlay, multiaddr)`; the multiaddr needs a `/p2p/` component.
+    pub static_peers: Vec<(OverlayAddress, Multiaddr)>,
+    /// Cadence at which the node's own record is re-signed and sent to
+    /// connected neighbors; `None` announces it only in the handshake.
+    pub self_announce_interval: Option<Duration>,
 }
 
 impl Default for TopologyConfig {
@@ -146,7 +185,14 @@ impl Default for TopologyConfig {
             connection_profile: None,
             dial_interval: None,
             dial_quota: None,
+            max_concurrent_dials: None,
+            max_concurrent_handshakes: None,
             early_disconnect_threshold: DEFAULT_EARLY_DISCONNECT_THRESHOLD,
+            inbound_only_grace: DEFAULT_INBOUND_ONLY_GRACE,
+            churn_threshold: None,
+            bootnode_retry_budget: DEFAULT_BOOTNODE_RETRY_BUDGET,
+            static_peers: Vec::new(),
+            self_announce_interval: None,
         }
     }
 }
@@ -186,10 +232,67 @@ impl TopologyConfig {
         self
     }
 
+    /// Pin the outbound-dial limit over the profile's dial concurrency.
+    /// Dials past the limit queue until an in-flight dial resolves.
+    pub fn with_max_concurrent_dials(mut self, limit: usize) -> Self {
+        self.max_concurrent_dials = Some(limit);
+        self
+    }
+
+    /// Cap the handshakes in progress at once, separately from the
+    /// connection limits. Handshakes past the cap wait for a slot.
+    pub fn with_max_concurrent_handshakes(mut self, limit: usize) -> Self {
+        self.max_concurrent_handshakes = Some(limit);
+        self
+    }
+
     pub fn with_early_disconnect_threshold(mut self, threshold: Duration) -> Self {
         self.early_disconnect_threshold = threshold;
         self
     }
+
+    /// Keep an inbound-only peer (one we cannot dial back) off the front of
+    /// the eviction order while it has served a chunk within `grace`. It
+    /// cannot be confirmed reachable, so without the grace it would always
+    /// rank below reachable peers. `Duration::ZERO` disables the grace.
+    pub fn with_inbound_only_grace(mut self, grace: Duration) -> Self {
+        self.inbound_only_grace = grace;
+        self
+    }
+
+    /// Raise [`TopologyEvent::ChurnExceeded`] when connects plus disconnects
+    /// over the last [`CHURN_WINDOW`](crate::CHURN_WINDOW) exceed
+    /// `per_minute`.
+    pub fn with_churn_threshold(mut self, per_minute: f64) -> Self {
+        self.churn_threshold = Some(per_minute);
+        self
+    }
+
+    /// Redial a bootnode that drops before serving its gossip at most
+    /// `budget` times, then treat it as an ordinary known peer.
+    pub fn with_bootnode_retry_budget(mut self, budget: u32) -> Self {
+        self.bootnode_retry_budget = budget;
+        self
+    }
+
+    /// Always keep these peers connected: redial them whenever they drop
+    /// (under the dial backoff once a dial or handshake fails), admit them
+    /// past bin saturation and the connection limit, and never evict them.
+    /// Replaces the network configuration's `--network.static-peers`.
+    pub fn with_static_peers(mut self, peers: Vec<(OverlayAddress, Multiaddr)>) -> Self {
+        self.static_peers = peers;
+        self
+    }
+
+    /// Re-sign the node's own record every `interval` and send it to every
+    /// connected neighbor, so their copy carries a fresh timestamp and the
+    /// current address set without waiting for a reconnect. Neighbors drop
+    /// an unchanged record inside their record cooldown, so intervals
+    /// shorter than it only cost signatures.
+    pub fn with_self_announce_interval(mut self, interval: Duration) -> Self {
+        self.self_announce_interval = Some(interval);
+        self
+    }
 }
 
 /// Network topology behaviour managing peer connections.
@@ -213,7 +316,12 @@ pub struct TopologyBehaviour<I: SwarmIdentity + Clone> {
     pub(crate) connection_registry: Arc<ConnectionRegistry>,
     pub(crate) nat_discovery: Arc<LocalAddressManager>,
     pub(crate) bootnodes: Vec<Multiaddr>,
+    /// Redial budget of the bootnodes dialed so far.
+    pub(crate) bootnode_connector: BootnodeConnector,
     pub(crate) trusted_peers: Vec<Multiaddr>,
+    /// Peers kept connected regardless of bin logic; shared with the
+    /// handshake admission gate.
+    pub(crate) static_peers: Arc<StaticPeers>,
 
     // Channels
     pub(crate) command_rx: mpsc::Receiver<TopologyCommand>,
@@ -228,6 +336,11 @@ pub struct TopologyBehaviour<I: SwarmIdentity + Clone> {
     // Periodic dial interval
     pub(crate) dial_interval: vertex_tasks::time::Interval,
 
+    /// Re-announcement of the own record to neighbors; `None` when disabled.
+    /// The first tick waits a full period, the handshake having just sent
+    /// the record.
+    pub(crate) self_announce_interval: Option<vertex_tasks::time::Interval>,
+
     /// GCRA bucket shaping the discovery dial rate. Bursts after a candidate
     /// influx drain immediately up to the bucket size; beyond it, candidates
     /// stay queued in routing until tokens replenish.
@@ -241,6 +354,11 @@ pub struct TopologyBehaviour<I: SwarmIdentity + Clone> {
     // Pending dnsaddr resolution for bootnodes (resolved_bootnodes, resolved_trusted)
     pub(crate) pending_bootnode_resolution: Option<BootnodeResolutionFuture>,
 
+    /// `/dnsaddr/` resolver held for the node's lifetime, so a re-bootstrap
+    /// whose lookup fails dials the last good resolution.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) dnsaddr: Arc<vertex_net_dnsaddr::DnsaddrResolver>,
+
     /// Static NAT addresses to emit as external addresses on first poll.
     /// Cleared after emitting to avoid re-emission.
     pub(crate) pending_nat_external_addrs: Vec<Multiaddr>,
@@ -252,9 +370,17 @@ pub struct TopologyBehaviour<I: SwarmIdentity + Clone> {
     /// Overlay may be unknown at dial time (bootnodes, commands).
     pub(crate) dial_tracker: DialTracker<OverlayAddress, DialReason>,
 
+    /// Dials refused by the tracker's in-flight limit, in arrival order.
+    /// Drained on poll as in-flight dials resolve; at most
+    /// [`MAX_QUEUED_DIALS`] entries.
+    pub(crate) queued_dials: VecDeque<(DialTarget, DialReason)>,
+
     /// Threshold for detecting post-handshake early disconnects.
     pub(crate) early_disconnect_threshold: Duration,
 
+    /// Eviction grace for inbound-only peers that are serving us.
+    pub(crate) inbound_only_grace: Duration,
+
     /// Close intent recorded at each close site, consumed by
     /// `handle_connection_closed` so a deliberate close is attributed to its
     /// real reason rather than re-derived from the libp2p cause. Keyed by
@@ -268,6 +394,10 @@ pub struct TopologyBehaviour<I: SwarmIdentity + Clone> {
     /// completion, and cleared at `ConnectionClosed`.
     pub(crate) outbound_public_dials: HashSet<ConnectionId>,
 
+    /// Per-connection lifecycle phases. Handshake completion and activation
+    /// are only acted on when they arrive in order for the connection.
+    pub(crate) connection_phases: ConnectionStateMachine<ConnectionId>,
+
     /// Receiver for the peer lifecycle event stream from PeerManager.
     ///
     /// Topology is the action-executing subscriber: `DisconnectRequested`
@@ -287,6 +417,12 @@ pub struct TopologyBehaviour<I: SwarmIdentity + Clone> {
 
     // Metrics
     pub(crate) metrics: Arc<TopologyMetrics>,
+    /// Connect/disconnect rate, shared with the handle.
+    pub(crate) churn: Arc<ChurnTracker>,
+    /// Connection lifecycle audit log, shared with the handle.
+    pub(crate) audit: Arc<ConnectionAudit>,
+    /// Partition heuristic over the connected bins.
+    pub(crate) partition: PartitionDetector,
 
     /// Background-task inputs captured by [`crate::TopologyBehaviourBuilder`]
     /// and consumed by [`TopologyBehaviour::spawn_tasks`]. `None` once the
@@ -329,6 +465,18 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         self.nat_discovery.reachability()
     }
 
+    /// Protocol-name prefix of the local identity's spec, for the behaviours
+    /// composed alongside topology.
+    pub fn protocol_prefix(&self) -> &str {
+        self.identity.spec().protocol_prefix()
+    }
+
+    /// Activation of `fork` in the local identity's spec, for the behaviours
+    /// composed alongside topology that gate wire changes on it.
+    pub fn fork_condition(&self, fork: SwarmHardfork) -> ForkCondition {
+        self.identity.spec().hardforks().fork(fork)
+    }
+
     /// Shared agent version map, populated by identify and read by topology handle.
     pub fn agent_versions(&self) -> identify::AgentVersions {
         Arc::clone(&self.agent_versions)
@@ -347,12 +495,14 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
     /// step. The one exception is the duplicate-connection eviction, which
     /// closes a specific stale connection (`CloseConnection::One`) that the
     /// close handler short-circuits before attribution.
+    ///
+    /// The peer is first sent a goodbye carrying `reason`; the goodbye
+    /// behaviour emits the `CloseConnection` once it is written or fails.
     pub(crate) fn close_peer(&mut self, peer_id: PeerId, reason: DisconnectReason) {
+        let overlay = self.connection_registry.resolve_id(&peer_id);
+        info!(%peer_id, ?overlay, %reason, "Closing peer connection");
         self.pending_closes.insert(peer_id, reason);
-        self.pending_actions.push_back(ToSwarm::CloseConnection {
-            peer_id,
-            connection: libp2p::swarm::CloseConnection::All,
-        });
+        self.protocols.goodbye.close(peer_id, reason);
     }
 
     /// Handle a topology command (dial, close connection, etc.).
@@ -372,6 +522,13 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
                 debug!(%overlay, %peer_id, "Close connection command");
                 self.close_peer(peer_id, DisconnectReason::Requested);
             }
+            TopologyCommand::Disconnect { overlay, reason } => {
+                let Some(peer_id) = self.connection_registry.resolve_peer_id(&overlay) else {
+                    debug!(%overlay, %reason, "Cannot disconnect: peer not connected");
+                    return;
+                };
+                self.close_peer(peer_id, reason);
+            }
             TopologyCommand::BanPeer { overlay, reason } => {
                 self.peer_manager.ban(&overlay, BanCause::Requested, reason);
                 SwarmRouting::remove_peer(&*self.routing, &overlay);
@@ -384,6 +541,19 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
             TopologyCommand::SavePeers => {
                 self.save_peers();
             }
+            TopologyCommand::Ping { overlay, probe_id } => {
+                let Some(peer_id) = self.connection_registry.resolve_peer_id(&overlay) else {
+                    debug!(%overlay, "Cannot ping: peer not connected");
+                    self.emit_event(TopologyEvent::PingFailed {
+                        overlay,
+                        probe_id,
+                        error: "peer not connected".to_string(),
+                    });
+                    return;
+                };
+                debug!(%overlay, %peer_id, probe_id, "Ping command");
+                self.protocols.probe.probe(peer_id, probe_id);
+            }
         }
     }
 
@@ -413,6 +583,57 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         }
     }
 
+    /// Send a freshly signed own record to every connected neighbor.
+    ///
+    /// The advertised addresses are scoped per neighbor as in the handshake,
+    /// judged against the neighbor's own record; neighbors sharing a scope
+    /// share one signature.
+    pub(crate) fn announce_self(&mut self) {
+        let neighbors = peer_selection::connected_neighbors(
+            &self.identity.overlay_address(),
+            &self.peer_manager,
+            &self.connection_registry,
+            self.routing.depth(),
+        );
+        // `None` marks a scope whose signing failed, so it is skipped for the
+        // remaining neighbors rather than retried once per neighbor.
+        let mut signed: HashMap<Vec<Multiaddr>, Option<SwarmPeer>> = HashMap::new();
+        for neighbor in neighbors {
+            let Some(peer_addr) = self
+                .peer_manager
+                .get_swarm_peer(&neighbor)
+                .and_then(|peer| peer.multiaddrs().first().cloned())
+            else {
+                continue;
+            };
+            let addrs = self.nat_discovery.addresses_for_peer(&peer_addr);
+            if addrs.is_empty() {
+                continue;
+            }
+            let record = match signed.entry(addrs) {
+                Entry::Occupied(entry) => entry.get().clone(),
+                Entry::Vacant(entry) => {
+                    let record = SwarmPeer::sign(
+                        &*self.identity,
+                        entry.key().clone(),
+                        Timestamp::now(),
+                        None,
+                    )
+                    .inspect_err(|e| {
+                        warn!(error = %e, "Failed to sign own record for re-announcement");
+                    })
+                    .ok();
+                    entry.insert(record).clone()
+                }
+            };
+            let Some(record) = record else {
+                continue;
+            };
+            debug!(%neighbor, "Re-announcing own record");
+            self.broadcast_peers(neighbor, vec![record]);
+        }
+    }
+
     // Routing
 
     /// Drain candidates from the background evaluator's per-bin queues and
@@ -432,7 +653,11 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
             }
         }
 
-        while let Some(overlay) = self.routing.pop_candidate() {
+        // At the outbound-dial limit candidates stay queued in routing rather
+        // than spending rate tokens on dials that would only be held back.
+        while self.dial_tracker.has_capacity()
+            && let Some(overlay) = self.routing.pop_candidate()
+        {
             let Some(swarm_peer) = self
                 .peer_manager
                 .get_dialable_peers(std::slice::from_ref(&overlay))
@@ -507,36 +732,55 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         }
     }
 
+    /// Eviction rank of a connected peer, lowest evicted first.
+    ///
+    /// Ranks by reachability (the routing layer is overlay-keyed and has no
+    /// peer-id mapping; we own both the registry and the tracker). When
+    /// local-peer trust is on, a same-subnet / private-LAN peer ranks above a
+    /// remote peer of equal reachability: the tuple orders lexicographically
+    /// and the routing layer evicts the lowest rank first, so
+    /// `(reachability, is_local)` keeps locals last without ever overriding a
+    /// liveness demotion (a remote `Reachable` still outranks a local
+    /// `Unreachable`). With trust off, the locality bit is held at `false` so
+    /// ranking matches the reachability-only behaviour.
+    ///
+    /// Locality comes from the `TrustLevel` the peer manager stored at
+    /// handshake completion: one atomic load per candidate instead of
+    /// re-deriving address scope every trim round.
+    ///
+    /// An inbound-only peer that served us within the inbound-only grace
+    /// ranks as `Reachable`: it can never prove reachability, and dropping it
+    /// loses it for good since it cannot be redialed.
+    pub(crate) fn eviction_rank(
+        &self,
+    ) -> impl Fn(&OverlayAddress) -> (crate::PeerReachability, bool) + '_ {
+        let tracker = self.nat_discovery.reachability();
+        let trust_local = self.trust_local_peers;
+        let grace = self.inbound_only_grace;
+        move |overlay| {
+            let serving_inbound_only = !grace.is_zero()
+                && self.peer_manager.is_inbound_only(overlay)
+                && self.peer_manager.was_productive_within(overlay, grace);
+            let reachability = if serving_inbound_only {
+                crate::PeerReachability::Reachable
+            } else {
+                self.connection_registry
+                    .resolve_peer_id(overlay)
+                    .map(|peer_id| tracker.status(&peer_id))
+                    .unwrap_or(crate::PeerReachability::Unknown)
+            };
+            let is_local =
+                trust_local && self.peer_manager.trust_level(overlay) != TrustLevel::Normal;
+            (reachability, is_local)
+        }
+    }
+
     /// Evict surplus peers from overpopulated bins after depth change.
     ///
     /// Emits `CloseConnection` for each evicted peer. Existing event handlers
     /// (`handle_connection_closed`) handle cleanup of routing capacity and state.
     pub(crate) fn trim_overpopulated_bins(&mut self) {
-        // Rank each candidate by reachability (the routing layer is overlay-keyed
-        // and has no peer-id mapping; we own both the registry and the tracker).
-        // When local-peer trust is on, a same-subnet / private-LAN peer ranks
-        // above a remote peer of equal reachability: the tuple orders
-        // lexicographically and the routing layer evicts the lowest rank first,
-        // so `(reachability, is_local)` keeps locals last without ever
-        // overriding a liveness demotion (a remote `Reachable` still outranks a
-        // local `Unreachable`). With trust off, the locality bit is held at
-        // `false` so ranking matches the reachability-only behaviour.
-        //
-        // Locality comes from the `TrustLevel` the peer manager stored at
-        // handshake completion: one atomic load per candidate instead of
-        // re-deriving address scope every trim round.
-        let tracker = self.nat_discovery.reachability();
-        let trust_local = self.trust_local_peers;
-        let peer_manager = &self.peer_manager;
-        let registry = &self.connection_registry;
-        let candidates = self.routing.eviction_candidates(|overlay| {
-            let reachability = registry
-                .resolve_peer_id(overlay)
-                .map(|peer_id| tracker.status(&peer_id))
-                .unwrap_or(crate::PeerReachability::Unknown);
-            let is_local = trust_local && peer_manager.trust_level(overlay) != TrustLevel::Normal;
-            (reachability, is_local)
-        });
+        let candidates = self.routing.eviction_candidates(self.eviction_rank());
         if candidates.is_empty() {
             return;
         }
@@ -626,7 +870,36 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
 
     pub(crate) fn emit_event(&self, event: TopologyEvent) {
         self.metrics.record_event(&event);
+        let churned = matches!(
+            event,
+            TopologyEvent::PeerReady { .. } | TopologyEvent::PeerDisconnected { .. }
+        );
         let _ = self.event_tx.send(event);
+
+        if churned
+            && let Some(rate) = self.churn.record()
+            && let Some(threshold) = self.churn.threshold()
+        {
+            warn!(rate, threshold, "peer churn above threshold");
+            self.emit_event(TopologyEvent::ChurnExceeded { rate, threshold });
+        }
+    }
+
+    /// Run the partition heuristic after a connected-set change. A new
+    /// suspicion is broadcast and answered by re-bootstrapping: every
+    /// bootnode is dialed again, demoted ones with a fresh retry budget.
+    pub(crate) fn check_partition(&mut self) {
+        let connected = self.routing.connected_bin_sizes();
+        let Some(reason) = self.partition.evaluate(&connected, self.routing.depth()) else {
+            return;
+        };
+        warn!(
+            ?reason,
+            "network partition suspected, reconnecting to bootnodes"
+        );
+        self.emit_event(TopologyEvent::NetworkPartitionSuspected { reason });
+        self.connect_bootnodes();
+        self.evaluator_handle.trigger_evaluation();
     }
 
     /// Re-derive the topology phase after a connected-set or depth change
@@ -658,10 +931,12 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
             .set((dialing + handshaking + active) as f64);
     }
 
-    /// Push per-bin target/ceiling gauges and the global nominal gauge (called on depth change).
+    /// Push per-bin target/ceiling gauges and the global nominal, network-size
+    /// and connection-target gauges (called on depth change).
     pub(crate) fn push_bin_targets(&self) {
         let depth = self.routing.depth();
-        let limits = self.routing.limits();
+        let network_size = self.routing.estimated_network_size();
+        let limits = self.routing.limits().sized_for(network_size);
 
         for bin in all_bins(self.routing.max_bin()) {
             let label = po_label(bin.get());
@@ -683,12 +958,14 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         }
 
         metrics::gauge!("topology_bin_nominal_peers").set(limits.nominal() as f64);
+        metrics::gauge!("topology_estimated_network_size").set(network_size as f64);
+        metrics::gauge!("topology_target_connections").set(limits.total_target() as f64);
     }
 
     /// The [`Bin`] a peer occupies in this node's table (its proximity order to
-    /// the local overlay).
+    /// the local overlay under the routing metric).
     pub(crate) fn bin_for(&self, peer: &OverlayAddress) -> Bin {
-        Bin::from(self.identity.overlay_address().proximity(peer))
+        self.routing.bin_for(peer)
     }
 
     /// Check if we can advertise to a peer based on address scope.
@@ -761,6 +1038,24 @@ impl<I: SwarmIdentity + Clone + 'static> NetworkBehaviour for TopologyBehaviour<
         )
     }
 
+    fn handle_pending_outbound_connection(
+        &mut self,
+        connection_id: ConnectionId,
+        maybe_peer: Option<PeerId>,
+        addresses: &[Multiaddr],
+        effective_role: Endpoint,
+    ) -> Result<Vec<Multiaddr>, ConnectionDenied> {
+        let _ = self
+            .connection_phases
+            .apply(connection_id, ConnectionTransition::Dial);
+        self.protocols.handle_pending_outbound_connection(
+            connection_id,
+            maybe_peer,
+            addresses,
+            effective_role,
+        )
+    }
+
     fn handle_established_outbound_connection(
         &mut self,
         connection_id: ConnectionId,
@@ -887,12 +1182,20 @@ impl<I: SwarmIdentity + Clone + 'static> NetworkBehaviour for TopologyBehaviour<
         // Check for periodic dial candidate evaluation
         if self.dial_interval.poll_tick(cx).is_ready() {
             self.cleanup_stale_pending();
+            self.redial_bootnodes();
+            self.dial_static_peers();
             // Publish a pending depth lowering whose stability window has
             // expired; connection events are the other publication path.
             self.refresh_published_depth();
             self.evaluator_handle.trigger_evaluation();
         }
 
+        if let Some(interval) = self.self_announce_interval.as_mut()
+            && interval.poll_tick(cx).is_ready()
+        {
+            self.announce_self();
+        }
+
         // Poll composed protocols and process their events
         loop {
             match self.protocols.poll(cx) {
@@ -947,6 +1250,10 @@ impl<I: SwarmIdentity + Clone + 'static> NetworkBehaviour for TopologyBehaviour<
             }
         }
 
+        // Start dials held back by the outbound-dial limit before new
+        // candidates, so queued bootnode and command dials are not starved.
+        self.drain_queued_dials();
+
         // Drain candidates produced by the background evaluator task, shaped
         // by the dial-rate bucket (arms a wake-up timer when throttled).
         self.drain_candidate_queues(cx);
@@ -1007,7 +1314,6 @@ mod tests {
 
     use alloy_primitives::{Address, B256, Signature};
     use nectar_primitives::SwarmAddress;
-    use vertex_swarm_peer::Timestamp;
     use vertex_swarm_primitives::Nonce;
 
     fn peer_with_addr(addr: &str) -> SwarmPeer {
@@ -1087,6 +1393,9 @@ mod tests {
         assert_eq!(default.connection_profile, None);
         assert_eq!(default.dial_interval, None);
         assert!(default.dial_quota.is_none());
+        assert_eq!(default.max_concurrent_dials, None);
+        assert_eq!(default.max_concurrent_handshakes, None);
+        assert_eq!(default.self_announce_interval, None);
     }
 
     use vertex_swarm_api::{
@@ -1136,8 +1445,8 @@ mod tests {
         fn discovery_enabled(&self) -> bool {
             true
         }
-        fn max_peers(&self) -> usize {
-            32
+        fn max_peers(&self) -> Option<usize> {
+            Some(32)
         }
         fn idle_timeout(&self) -> Duration {
             Duration::from_secs(60)
@@ -1193,6 +1502,65 @@ mod tests {
         .expect("behaviour must emit an action")
     }
 
+    mod inbound_only {
+        use vertex_net_peer_registry::ConnectionDirection;
+        use vertex_swarm_api::{ReportSource, SwarmScoringEvent};
+        use vertex_swarm_test_utils::make_swarm_peer_minimal;
+
+        use super::*;
+
+        /// Connect a peer advertising no IP address, so it reached us inbound
+        /// and cannot be dialed back.
+        fn connect_inbound_only(behaviour: &TopologyBehaviour<Identity>) -> OverlayAddress {
+            let peer = make_swarm_peer_minimal(0x80);
+            let overlay = OverlayAddress::from(*peer.overlay());
+            behaviour.peer_manager.on_peer_connected(
+                peer,
+                SwarmNodeType::Client,
+                ConnectionDirection::Inbound,
+                TrustLevel::Normal,
+            );
+            overlay
+        }
+
+        fn serve_retrieval(behaviour: &TopologyBehaviour<Identity>, overlay: &OverlayAddress) {
+            behaviour.peer_manager.report_peer(
+                overlay,
+                SwarmScoringEvent::RetrievalSuccess {
+                    latency: Duration::from_millis(50),
+                },
+                ReportSource::Protocol("retrieval"),
+            );
+        }
+
+        /// While it serves retrievals an inbound-only peer ranks with the
+        /// reachable peers instead of at the front of the eviction order.
+        #[test]
+        fn serving_inbound_only_peer_is_not_preferentially_evicted() {
+            let behaviour = test_behaviour();
+            let overlay = connect_inbound_only(&behaviour);
+
+            let (idle, _) = behaviour.eviction_rank()(&overlay);
+            assert!(idle < crate::PeerReachability::Reachable);
+
+            serve_retrieval(&behaviour, &overlay);
+            let (serving, _) = behaviour.eviction_rank()(&overlay);
+            assert_eq!(serving, crate::PeerReachability::Reachable);
+        }
+
+        #[test]
+        fn zero_grace_disables_the_exemption() {
+            let behaviour = test_behaviour_with(
+                TopologyConfig::default().with_inbound_only_grace(Duration::ZERO),
+            );
+            let overlay = connect_inbound_only(&behaviour);
+
+            serve_retrieval(&behaviour, &overlay);
+            let (rank, _) = behaviour.eviction_rank()(&overlay);
+            assert!(rank < crate::PeerReachability::Reachable);
+        }
+    }
+
     mod lifecycle {
         use super::*;
 
@@ -1286,6 +1654,47 @@ mod tests {
                 _ => panic!("expected CloseConnection for the disconnect request"),
             }
         }
+
+        /// A debt-threshold disconnect sends the goodbye before closing and
+        /// records the reason for the close handler. Goodbye is spoken from
+        /// Accord on, so the node runs on the dev network where it is active.
+        #[tokio::test]
+        async fn allowance_disconnect_says_goodbye_first() {
+            let identity = Identity::random(vertex_swarm_spec::init_dev(), SwarmNodeType::Client);
+            let (mut behaviour, _handle) =
+                TopologyBehaviourBuilder::new(identity, &EventTestConfig::new())
+                    .try_build()
+                    .expect("build without runtime");
+            let overlay = test_overlay(1);
+            let peer_id = activate_connection(&behaviour, overlay);
+            behaviour
+                .protocols
+                .goodbye
+                .handle_established_outbound_connection(
+                    ConnectionId::new_unchecked(1),
+                    peer_id,
+                    &"/ip4/127.0.0.1/tcp/1634".parse().expect("valid"),
+                    Endpoint::Dialer,
+                    PortUse::Reuse,
+                )
+                .expect("goodbye accepts the connection");
+
+            behaviour.on_lifecycle_event(PeerLifecycleEvent::DisconnectRequested {
+                overlay,
+                reason: DisconnectReason::AllowanceExceeded,
+            });
+
+            assert_eq!(
+                behaviour.pending_closes.get(&peer_id),
+                Some(&DisconnectReason::AllowanceExceeded)
+            );
+            match next_action(&mut behaviour).await {
+                ToSwarm::NotifyHandler {
+                    peer_id: notified, ..
+                } => assert_eq!(notified, peer_id),
+                _ => panic!("expected the goodbye before the close"),
+            }
+        }
     }
 
     mod early_disconnect {
@@ -1305,7 +1714,10 @@ mod tests {
         }
 
         /// Register an active, peer-manager-known connection for overlay `n`.
-        fn connect(behaviour: &TopologyBehaviour<Identity>, n: u8) -> (OverlayAddress, PeerId) {
+        pub(super) fn connect(
+            behaviour: &TopologyBehaviour<Identity>,
+            n: u8,
+        ) -> (OverlayAddress, PeerId) {
             let overlay = test_overlay(n);
             let peer_id = PeerId::random();
             behaviour
@@ -1323,7 +1735,7 @@ mod tests {
             (overlay, peer_id)
         }
 
-        fn close(
+        pub(super) fn close(
             behaviour: &mut TopologyBehaviour<Identity>,
             peer_id: PeerId,
             cause: Option<&libp2p::swarm::ConnectionError>,
@@ -1511,7 +1923,7 @@ mod tests {
 
         /// Drain every dial action the behaviour currently has queued and
         /// return the peer ids dialed.
-        fn drain_dials(behaviour: &mut TopologyBehaviour<Identity>) -> Vec<PeerId> {
+        pub(super) fn drain_dials(behaviour: &mut TopologyBehaviour<Identity>) -> Vec<PeerId> {
             let waker = futures::task::noop_waker();
             let mut cx = Context::from_waker(&waker);
             let mut dialed = Vec::new();
@@ -1609,5 +2021,524 @@ mod tests {
                 "no redial storm: bootnode already tracked after first transition"
             );
         }
+
+        /// A bootnode whose dials keep failing is redialed on the evaluation
+        /// tick until its retry budget is spent, then demoted: it is no
+        /// longer tracked or redialed as a bootnode.
+        #[tokio::test]
+        async fn failing_bootnode_is_demoted_after_its_retry_budget() {
+            use libp2p::swarm::DialError;
+            use libp2p::swarm::behaviour::DialFailure;
+
+            const BUDGET: u32 = 2;
+            let mut behaviour =
+                test_behaviour_with(TopologyConfig::default().with_bootnode_retry_budget(BUDGET));
+            let bootnode_peer = PeerId::random();
+            let bootnode: Multiaddr = format!("/ip4/203.0.113.9/tcp/1634/p2p/{bootnode_peer}")
+                .parse()
+                .expect("valid bootnode multiaddr");
+            behaviour.bootnodes = vec![bootnode];
+
+            behaviour.on_command(TopologyCommand::ConnectBootnodes);
+            assert!(drain_dials(&mut behaviour).contains(&bootnode_peer));
+
+            let fail = |behaviour: &mut TopologyBehaviour<Identity>| {
+                behaviour.on_swarm_event(FromSwarm::DialFailure(DialFailure {
+                    peer_id: Some(bootnode_peer),
+                    error: &refused(),
+                    connection_id: ConnectionId::new_unchecked(0),
+                }));
+            };
+
+            for _ in 0..BUDGET {
+                fail(&mut behaviour);
+                assert!(behaviour.bootnode_connector.is_bootnode(&bootnode_peer));
+                behaviour.redial_bootnodes();
+                assert!(
+                    drain_dials(&mut behaviour).contains(&bootnode_peer),
+                    "a bootnode with retries left is redialed"
+                );
+            }
+
+            fail(&mut behaviour);
+            assert!(
+                !behaviour.bootnode_connector.is_bootnode(&bootnode_peer),
+                "the spent bootnode is demoted to an ordinary peer"
+            );
+            behaviour.redial_bootnodes();
+            assert!(drain_dials(&mut behaviour).is_empty());
+        }
+
+        /// A bootnode dial we aborted ourselves never reached the bootnode,
+        /// so it leaves the retry budget alone.
+        #[tokio::test]
+        async fn aborted_bootnode_dial_spends_no_retry() {
+            use libp2p::swarm::DialError;
+            use libp2p::swarm::behaviour::DialFailure;
+
+            let mut behaviour =
+                test_behaviour_with(TopologyConfig::default().with_bootnode_retry_budget(0));
+            let bootnode_peer = PeerId::random();
+            let bootnode: Multiaddr = format!("/ip4/203.0.113.9/tcp/1634/p2p/{bootnode_peer}")
+                .parse()
+                .expect("valid bootnode multiaddr");
+            behaviour.bootnodes = vec![bootnode];
+
+            behaviour.on_command(TopologyCommand::ConnectBootnodes);
+            assert!(drain_dials(&mut behaviour).contains(&bootnode_peer));
+
+            behaviour.on_swarm_event(FromSwarm::DialFailure(DialFailure {
+                peer_id: Some(bootnode_peer),
+                error: &DialError::Aborted,
+                connection_id: ConnectionId::new_unchecked(0),
+            }));
+            assert!(
+                behaviour.bootnode_connector.is_bootnode(&bootnode_peer),
+                "an aborted dial does not demote a bootnode with no retries left"
+            );
+        }
+
+        fn refused() -> libp2p::swarm::DialError {
+            use libp2p::core::transport::TransportError;
+            use std::io;
+
+            let addr: Multiaddr = "/ip4/203.0.113.9/tcp/1634".parse().expect("valid");
+            libp2p::swarm::DialError::Transport(vec![(
+                addr,
+                TransportError::Other(io::Error::from(io::ErrorKind::ConnectionRefused)),
+            )])
+        }
+    }
+
+    mod churn {
+        use super::early_disconnect::{close, connect};
+        use super::*;
+
+        /// Closes past the configured rate raise the alert once per excursion.
+        #[test]
+        fn disconnects_above_the_threshold_raise_churn_exceeded() {
+            let mut behaviour =
+                test_behaviour_with(TopologyConfig::default().with_churn_threshold(2.0));
+            let mut events = behaviour.event_tx.subscribe();
+
+            for n in 1..=4 {
+                let (_, peer_id) = connect(&behaviour, n);
+                close(&mut behaviour, peer_id, None);
+            }
+
+            let alerts: Vec<(f64, f64)> = std::iter::from_fn(|| events.try_recv().ok())
+                .filter_map(|event| match event {
+                    TopologyEvent::ChurnExceeded { rate, threshold } => Some((rate, threshold)),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(alerts, vec![(3.0, 2.0)]);
+        }
+    }
+
+    mod partition {
+        use super::bootnode_redial::drain_dials;
+        use super::*;
+
+        use crate::PartitionSuspicion;
+
+        /// Losing every peer below depth while the neighborhood stays
+        /// connected raises the suspicion once and redials the bootnodes.
+        #[tokio::test]
+        async fn losing_all_shallow_peers_suspects_a_partition_and_redials_bootnodes() {
+            let mut behaviour = test_behaviour();
+            let bootnode_peer = PeerId::random();
+            let bootnode: Multiaddr = format!("/ip4/203.0.113.11/tcp/1634/p2p/{bootnode_peer}")
+                .parse()
+                .expect("valid bootnode multiaddr");
+            behaviour.bootnodes = vec![bootnode];
+            let mut events = behaviour.event_tx.subscribe();
+
+            // Bins 0-2 saturated, the neighborhood at depth 3 holds six peers.
+            let seeded =
+                behaviour
+                    .routing
+                    .seed_test_topology(&[(0, 8), (1, 8), (2, 8), (3, 2), (6, 4)]);
+            behaviour.check_partition();
+            assert!(behaviour.routing.depth() > NeighborhoodDepth::ZERO);
+
+            for peer in seeded.iter().take(24) {
+                SwarmRouting::on_peer_disconnected(&*behaviour.routing, peer);
+                behaviour.check_partition();
+            }
+
+            let suspected: Vec<PartitionSuspicion> = std::iter::from_fn(|| events.try_recv().ok())
+                .filter_map(|event| match event {
+                    TopologyEvent::NetworkPartitionSuspected { reason } => Some(reason),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(
+                suspected,
+                vec![PartitionSuspicion::ShallowBinsLost {
+                    depth: 3,
+                    neighbors: 6,
+                }]
+            );
+            assert!(
+                drain_dials(&mut behaviour).contains(&bootnode_peer),
+                "a suspected partition redials the bootnodes"
+            );
+        }
+    }
+
+    mod static_peers {
+        use std::io;
+
+        use libp2p::core::ConnectedPoint;
+        use libp2p::swarm::behaviour::ConnectionClosed;
+        use vertex_net_peer_registry::ConnectionDirection;
+        use vertex_swarm_test_utils::{test_overlay, test_swarm_peer};
+
+        use super::bootnode_redial::drain_dials;
+        use super::*;
+
+        /// A connected static peer is left alone; once the remote side drops
+        /// it, the node dials it again without waiting for the next tick.
+        #[tokio::test]
+        async fn dropped_static_peer_is_redialed() {
+            let overlay = test_overlay(7);
+            let peer_id = PeerId::random();
+            let addr: Multiaddr = format!("/ip4/203.0.113.10/tcp/1634/p2p/{peer_id}")
+                .parse()
+                .expect("valid static peer multiaddr");
+            let mut behaviour = test_behaviour_with(
+                TopologyConfig::default().with_static_peers(vec![(overlay, addr)]),
+            );
+
+            let connection_id = ConnectionId::new_unchecked(1);
+            behaviour
+                .connection_registry
+                .connected_inbound(peer_id, connection_id);
+            behaviour
+                .connection_registry
+                .activate(peer_id, connection_id, overlay);
+            behaviour.dial_static_peers();
+            assert!(
+                drain_dials(&mut behaviour).is_empty(),
+                "a connected static peer is not redialed"
+            );
+
+            let endpoint = ConnectedPoint::Listener {
+                local_addr: "/ip4/127.0.0.1/tcp/1".parse().expect("valid"),
+                send_back_addr: "/ip4/127.0.0.2/tcp/2".parse().expect("valid"),
+            };
+            let reset =
+                libp2p::swarm::ConnectionError::IO(io::Error::from(io::ErrorKind::ConnectionReset));
+            behaviour.handle_connection_closed(ConnectionClosed {
+                peer_id,
+                connection_id,
+                endpoint: &endpoint,
+                cause: Some(&reset),
+                remaining_established: 0,
+            });
+            assert!(
+                drain_dials(&mut behaviour).contains(&peer_id),
+                "a dropped static peer is redialed"
+            );
+        }
+
+        /// A static peer that closes the connection before the handshake
+        /// completes backs off instead of being redialed in a loop.
+        #[tokio::test]
+        async fn static_peer_refusing_us_backs_off() {
+            let overlay = test_overlay(7);
+            let peer_id = PeerId::random();
+            let addr: Multiaddr = format!("/ip4/203.0.113.10/tcp/1634/p2p/{peer_id}")
+                .parse()
+                .expect("valid static peer multiaddr");
+            let mut behaviour = test_behaviour_with(
+                TopologyConfig::default().with_static_peers(vec![(overlay, addr)]),
+            );
+            // Known from an earlier session, so the backoff has an entry.
+            behaviour.peer_manager.on_peer_connected(
+                test_swarm_peer(7),
+                SwarmNodeType::Storer,
+                ConnectionDirection::Outbound,
+                TrustLevel::Trusted,
+            );
+
+            let connection_id = ConnectionId::new_unchecked(1);
+            behaviour
+                .connection_registry
+                .connected_inbound(peer_id, connection_id);
+            let endpoint = ConnectedPoint::Listener {
+                local_addr: "/ip4/127.0.0.1/tcp/1".parse().expect("valid"),
+                send_back_addr: "/ip4/127.0.0.2/tcp/2".parse().expect("valid"),
+            };
+            let reset =
+                libp2p::swarm::ConnectionError::IO(io::Error::from(io::ErrorKind::ConnectionReset));
+            behaviour.handle_connection_closed(ConnectionClosed {
+                peer_id,
+                connection_id,
+                endpoint: &endpoint,
+                cause: Some(&reset),
+                remaining_established: 0,
+            });
+
+            assert!(behaviour.peer_manager.peer_is_in_backoff(&overlay));
+            assert!(
+                drain_dials(&mut behaviour).is_empty(),
+                "a static peer that refused us is not redialed at once"
+            );
+            behaviour.dial_static_peers();
+            assert!(
+                drain_dials(&mut behaviour).is_empty(),
+                "the evaluation tick skips a static peer in backoff"
+            );
+        }
+    }
+
+    mod dial_limit {
+        use super::*;
+
+        /// Poll until idle, returning the peer ids dialed and the most dials
+        /// seen in flight at once.
+        fn drain_dials(behaviour: &mut TopologyBehaviour<Identity>) -> (Vec<PeerId>, usize) {
+            let waker = futures::task::noop_waker();
+            let mut cx = Context::from_waker(&waker);
+            let mut dialed = Vec::new();
+            let mut peak = behaviour.dial_tracker.in_flight_count();
+            loop {
+                match behaviour.poll(&mut cx) {
+                    Poll::Ready(ToSwarm::Dial { opts }) => {
+                        dialed.extend(opts.get_peer_id());
+                        peak = peak.max(behaviour.dial_tracker.in_flight_count());
+                    }
+                    Poll::Ready(_) => {}
+                    Poll::Pending => break,
+                }
+            }
+            (dialed, peak)
+        }
+
+        /// A burst of dials far above the limit keeps at most `limit` in
+        /// flight; the rest queue and start one by one as slots free.
+        #[tokio::test]
+        async fn burst_is_capped_and_excess_queues() {
+            const LIMIT: usize = 2;
+            const DIALS: usize = 6;
+
+            let mut behaviour =
+                test_behaviour_with(TopologyConfig::default().with_max_concurrent_dials(LIMIT));
+            for n in 0..DIALS {
+                let addr: Multiaddr =
+                    format!("/ip4/127.0.0.1/tcp/{}/p2p/{}", 1700 + n, PeerId::random())
+                        .parse()
+                        .expect("valid multiaddr");
+                behaviour.on_command(TopologyCommand::Dial(addr));
+            }
+
+            let (dialed, peak) = drain_dials(&mut behaviour);
+            assert_eq!(dialed.len(), LIMIT);
+            assert!(peak <= LIMIT, "in-flight dials exceeded the limit: {peak}");
+            assert_eq!(behaviour.queued_dials.len(), DIALS - LIMIT);
+
+            // Resolving one dial frees exactly one slot for the queue.
+            behaviour
+                .dial_tracker
+                .resolve(dialed.first().expect("a dial started"));
+            let (next, peak) = drain_dials(&mut behaviour);
+            assert_eq!(next.len(), 1);
+            assert!(peak <= LIMIT);
+            assert_eq!(behaviour.dial_tracker.in_flight_count(), LIMIT);
+            assert_eq!(behaviour.queued_dials.len(), DIALS - LIMIT - 1);
+        }
+    }
+
+    mod self_announce {
+        use libp2p::swarm::ConnectionId;
+        use vertex_net_peer_registry::ConnectionDirection;
+        use vertex_swarm_test_utils::{test_overlay, test_swarm_peer};
+
+        use super::*;
+
+        const INTERVAL: Duration = Duration::from_secs(60);
+
+        /// Register an active storer connection for overlay `n`; at depth
+        /// zero every storer is a neighbor.
+        fn connect_neighbor(behaviour: &TopologyBehaviour<Identity>, n: u8) -> PeerId {
+            let peer_id = PeerId::random();
+            let conn = ConnectionId::new_unchecked(usize::from(n));
+            behaviour
+                .connection_registry
+                .connected_inbound(peer_id, conn);
+            behaviour
+                .connection_registry
+                .activate(peer_id, conn, test_overlay(n));
+            behaviour.peer_manager.on_peer_connected(
+                test_swarm_peer(n),
+                SwarmNodeType::Storer,
+                ConnectionDirection::Inbound,
+                TrustLevel::Normal,
+            );
+            peer_id
+        }
+
+        /// Poll until idle, returning the peers a handler was notified for.
+        fn drain_notified(behaviour: &mut TopologyBehaviour<Identity>) -> Vec<PeerId> {
+            let waker = futures::task::noop_waker();
+            let mut cx = Context::from_waker(&waker);
+            let mut notified = Vec::new();
+            loop {
+                match behaviour.poll(&mut cx) {
+                    Poll::Ready(ToSwarm::NotifyHandler { peer_id, .. }) => notified.push(peer_id),
+                    Poll::Ready(_) => {}
+                    Poll::Pending => break,
+                }
+            }
+            notified
+        }
+
+        #[tokio::test(start_paused = true)]
+        async fn own_record_is_re_announced_to_neighbors_each_interval() {
+            let mut behaviour = test_behaviour_with(
+                TopologyConfig::default().with_self_announce_interval(INTERVAL),
+            );
+            behaviour
+                .nat_discovery
+                .on_new_listen_addr("/ip4/127.0.0.1/tcp/1634".parse().expect("valid multiaddr"));
+            let neighbor = connect_neighbor(&behaviour, 0x80);
+
+            assert!(
+                drain_notified(&mut behaviour).is_empty(),
+                "the handshake already carried the record"
+            );
+
+            tokio::time::advance(INTERVAL).await;
+            assert_eq!(drain_notified(&mut behaviour), vec![neighbor]);
+
+            tokio::time::advance(INTERVAL).await;
+            assert_eq!(drain_notified(&mut behaviour), vec![neighbor]);
+        }
+
+        #[tokio::test(start_paused = true)]
+        async fn disabled_by_default() {
+            let mut behaviour = test_behaviour();
+            behaviour
+                .nat_discovery
+                .on_new_listen_addr("/ip4/127.0.0.1/tcp/1634".parse().expect("valid multiaddr"));
+            connect_neighbor(&behaviour, 0x80);
+
+            drain_notified(&mut behaviour);
+            tokio::time::advance(INTERVAL * 10).await;
+            assert!(drain_notified(&mut behaviour).is_empty());
+        }
+    }
+
+    mod connection_audit {
+        use libp2p::core::ConnectedPoint;
+        use libp2p::swarm::ConnectionId;
+        use libp2p::swarm::behaviour::{ConnectionClosed, ConnectionEstablished};
+        use vertex_net_peer_registry::ConnectionDirection;
+        use vertex_swarm_api::ConnectionAuditStage;
+        use vertex_swarm_net_handshake::{HandshakeEvent, HandshakeInfo};
+        use vertex_swarm_test_utils::{test_overlay, test_swarm_peer};
+
+        use super::*;
+        use crate::composed::ProtocolEvent;
+
+        /// An inbound connection that completes the handshake and is closed
+        /// leaves one ordered record per lifecycle step.
+        #[tokio::test]
+        async fn inbound_lifecycle_is_recorded_in_order() {
+            let mut behaviour = test_behaviour();
+            let overlay = test_overlay(0x80);
+            let peer_id = PeerId::random();
+            let connection_id = ConnectionId::new_unchecked(1);
+            let endpoint = ConnectedPoint::Listener {
+                local_addr: "/ip4/127.0.0.1/tcp/1634".parse().expect("valid"),
+                send_back_addr: "/ip4/127.0.0.2/tcp/2".parse().expect("valid"),
+            };
+
+            behaviour.handle_connection_established(ConnectionEstablished {
+                peer_id,
+                connection_id,
+                endpoint: &endpoint,
+                failed_addresses: &[],
+                other_established: 0,
+            });
+            behaviour.process_protocol_event(
+                peer_id,
+                connection_id,
+                ProtocolEvent::Handshake(HandshakeEvent::Completed {
+                    peer_id,
+                    connection_id,
+                    direction: ConnectionDirection::Inbound,
+                    info: Box::new(HandshakeInfo {
+                        peer_id,
+                        swarm_peer: test_swarm_peer(0x80),
+                        node_type: SwarmNodeType::Storer,
+                        welcome_message: String::new(),
+                        observed_multiaddr: Multiaddr::empty(),
+                        capabilities: Default::default(),
+                    }),
+                }),
+            );
+            behaviour.handle_connection_closed(ConnectionClosed {
+                peer_id,
+                connection_id,
+                endpoint: &endpoint,
+                cause: None,
+                remaining_established: 0,
+            });
+
+            let records = behaviour.audit.recent(10);
+            let steps: Vec<_> = records.iter().map(|r| (r.stage, r.detail)).collect();
+            assert_eq!(
+                steps,
+                vec![
+                    (ConnectionAuditStage::Connected, Some("inbound")),
+                    (ConnectionAuditStage::HandshakeCompleted, Some("storer")),
+                    (ConnectionAuditStage::Activated, Some("inbound")),
+                    (ConnectionAuditStage::Closed, Some("local_close")),
+                ]
+            );
+            assert!(records.iter().all(|r| r.peer_id == peer_id));
+            assert_eq!(records.first().and_then(|r| r.overlay), None);
+            assert!(records.iter().skip(1).all(|r| r.overlay == Some(overlay)));
+            assert!(
+                records
+                    .iter()
+                    .zip(records.iter().skip(1))
+                    .all(|(a, b)| a.at_unix_ms <= b.at_unix_ms)
+            );
+        }
+
+        /// A handshake completion for a connection topology never saw
+        /// established is out of order and is dropped before activation.
+        #[tokio::test]
+        async fn completion_without_establishment_is_dropped() {
+            let mut behaviour = test_behaviour();
+            let peer_id = PeerId::random();
+            let connection_id = ConnectionId::new_unchecked(1);
+
+            behaviour.process_protocol_event(
+                peer_id,
+                connection_id,
+                ProtocolEvent::Handshake(HandshakeEvent::Completed {
+                    peer_id,
+                    connection_id,
+                    direction: ConnectionDirection::Inbound,
+                    info: Box::new(HandshakeInfo {
+                        peer_id,
+                        swarm_peer: test_swarm_peer(0x80),
+                        node_type: SwarmNodeType::Storer,
+                        welcome_message: String::new(),
+                        observed_multiaddr: Multiaddr::empty(),
+                        capabilities: Default::default(),
+                    }),
+                }),
+            );
+
+            assert!(behaviour.audit.recent(10).is_empty());
+            assert_eq!(behaviour.connection_phases.phase(&connection_id), None);
+            assert!(behaviour.connection_registry.resolve_id(&peer_id).is_none());
+        }
     }
 }
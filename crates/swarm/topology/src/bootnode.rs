@@ -0,0 +1,169 @@
+//! Reconnection budget for configured bootnodes.
+//!
+//! A bootnode is dialed for its initial hive gossip and then rotated away
+//! ([`DisconnectReason::BootnodeRotation`]). When one drops before it served
+//! us (a remote close or a failed dial), [`BootnodeConnector`] queues a redial
+//! for the next evaluation tick, up to a retry budget. Once the budget is
+//! spent the bootnode is demoted: it is no longer redialed as a bootnode, and
+//! if its handshake ever completed it stays in the peer manager as an ordinary
+//! known peer that Kademlia dials like any other.
+//!
+//! A rotation close means the bootnode did its job, so it refills the budget.
+//! Our own other closes (ban, trim, shutdown) are not drops and leave it alone.
+//!
+//! [`DisconnectReason::BootnodeRotation`]: crate::DisconnectReason::BootnodeRotation
+
+use std::collections::HashMap;
+
+use libp2p::{Multiaddr, PeerId};
+use tracing::{debug, info};
+
+use crate::extract_peer_id;
+
+/// Default number of redials a dropped bootnode gets before it is demoted.
+pub const DEFAULT_BOOTNODE_RETRY_BUDGET: u32 = 3;
+
+/// What a bootnode drop led to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BootnodeDrop {
+    /// A redial is queued for the next tick.
+    Redial,
+    /// The budget is spent; the peer is no longer treated as a bootnode.
+    Demoted,
+}
+
+struct BootnodeEntry {
+    addr: Multiaddr,
+    /// Redials used since the bootnode last served us.
+    retries: u32,
+}
+
+/// Tracks configured bootnodes by peer id and their remaining redials.
+pub(crate) struct BootnodeConnector {
+    budget: u32,
+    bootnodes: HashMap<PeerId, BootnodeEntry>,
+    /// Bootnodes waiting for their redial, in drop order.
+    due: Vec<PeerId>,
+}
+
+impl BootnodeConnector {
+    pub(crate) fn new(budget: u32) -> Self {
+        Self {
+            budget,
+            bootnodes: HashMap::new(),
+            due: Vec::new(),
+        }
+    }
+
+    /// Track a bootnode about to be dialed. Addresses without a `/p2p/`
+    /// component cannot be dialed and are ignored. A bootnode already tracked
+    /// keeps the retries it spent; a demoted one dialed again by an explicit
+    /// re-bootstrap starts over with a full budget.
+    pub(crate) fn register(&mut self, addr: Multiaddr) {
+        let Some(peer_id) = extract_peer_id(&addr) else {
+            return;
+        };
+        self.bootnodes
+            .entry(peer_id)
+            .and_modify(|entry| entry.addr = addr.clone())
+            .or_insert(BootnodeEntry { addr, retries: 0 });
+    }
+
+    /// Whether `peer_id` is currently treated as a bootnode.
+    pub(crate) fn is_bootnode(&self, peer_id: &PeerId) -> bool {
+        self.bootnodes.contains_key(peer_id)
+    }
+
+    /// The bootnode delivered its gossip: refill its budget.
+    pub(crate) fn on_served(&mut self, peer_id: &PeerId) {
+        if let Some(entry) = self.bootnodes.get_mut(peer_id) {
+            entry.retries = 0;
+        }
+    }
+
+    /// The bootnode dropped or failed us before serving. Callers report only
+    /// remote closes and dial failures on the bootnode's side, never our own
+    /// closes or aborted dials. Returns `None` for a peer that is not a
+    /// tracked bootnode.
+    pub(crate) fn on_dropped(&mut self, peer_id: &PeerId) -> Option<BootnodeDrop> {
+        let entry = self.bootnodes.get_mut(peer_id)?;
+        if entry.retries >= self.budget {
+            self.bootnodes.remove(peer_id);
+            self.due.retain(|due| due != peer_id);
+            info!(%peer_id, budget = self.budget, "bootnode retry budget spent, demoting to known peer");
+            metrics::counter!("topology_bootnodes_demoted_total").increment(1);
+            return Some(BootnodeDrop::Demoted);
+        }
+        entry.retries += 1;
+        debug!(%peer_id, retry = entry.retries, budget = self.budget, "bootnode dropped, redial queued");
+        if !self.due.contains(peer_id) {
+            self.due.push(*peer_id);
+        }
+        Some(BootnodeDrop::Redial)
+    }
+
+    /// Take the addresses of the bootnodes whose redial is due.
+    pub(crate) fn take_due(&mut self) -> Vec<Multiaddr> {
+        let due = std::mem::take(&mut self.due);
+        due.iter()
+            .filter_map(|peer_id| self.bootnodes.get(peer_id))
+            .map(|entry| entry.addr.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bootnode() -> (PeerId, Multiaddr) {
+        let peer_id = PeerId::random();
+        let addr = format!("/ip4/203.0.113.9/tcp/1634/p2p/{peer_id}")
+            .parse()
+            .expect("valid bootnode multiaddr");
+        (peer_id, addr)
+    }
+
+    #[test]
+    fn exhausted_budget_demotes_the_bootnode() {
+        let mut connector = BootnodeConnector::new(2);
+        let (peer_id, addr) = bootnode();
+        connector.register(addr.clone());
+
+        for _ in 0..2 {
+            assert_eq!(connector.on_dropped(&peer_id), Some(BootnodeDrop::Redial));
+            assert_eq!(connector.take_due(), vec![addr.clone()]);
+        }
+
+        assert_eq!(connector.on_dropped(&peer_id), Some(BootnodeDrop::Demoted));
+        assert!(!connector.is_bootnode(&peer_id));
+        assert!(
+            connector.take_due().is_empty(),
+            "a demoted bootnode is not redialed"
+        );
+        assert_eq!(
+            connector.on_dropped(&peer_id),
+            None,
+            "later drops are an ordinary peer's"
+        );
+    }
+
+    #[test]
+    fn serving_refills_the_budget() {
+        let mut connector = BootnodeConnector::new(1);
+        let (peer_id, addr) = bootnode();
+        connector.register(addr);
+
+        assert_eq!(connector.on_dropped(&peer_id), Some(BootnodeDrop::Redial));
+        connector.on_served(&peer_id);
+        assert_eq!(connector.on_dropped(&peer_id), Some(BootnodeDrop::Redial));
+        assert!(connector.is_bootnode(&peer_id));
+    }
+
+    #[test]
+    fn addresses_without_a_peer_id_are_not_tracked() {
+        let mut connector = BootnodeConnector::new(1);
+        connector.register("/ip4/203.0.113.9/tcp/1634".parse().expect("valid"));
+        assert!(connector.bootnodes.is_empty());
+    }
+}
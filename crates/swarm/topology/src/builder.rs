@@ -17,13 +17,17 @@ use vertex_swarm_api::{
 };
 use vertex_swarm_net_handshake::HANDSHAKE_TIMEOUT;
 use vertex_swarm_net_identify as identify;
-use vertex_swarm_peer_manager::{PeerManager, PeerManagerConfig};
+use vertex_swarm_peer_manager::{ConnectionStateMachine, PeerManager, PeerManagerConfig};
 use vertex_swarm_peer_score::SwarmScoringConfig;
+use vertex_swarm_primitives::{OverlayAddress, PeerCapabilities};
 
+use crate::audit::{AUDIT_CAPACITY, ConnectionAudit};
 use crate::behaviour::{
     COMMAND_CHANNEL_CAPACITY, ConnectionRegistry, EVENT_CHANNEL_CAPACITY, PeerStore,
     TopologyBehaviour, TopologyConfig,
 };
+use crate::bootnode::BootnodeConnector;
+use crate::churn::ChurnTracker;
 use crate::composed::ProtocolBehaviours;
 use crate::error::TopologyError;
 use crate::gossip::{GossipChannels, GossipConfig, gossip_channel, spawn_gossip_task};
@@ -33,7 +37,9 @@ use crate::kademlia::{
 };
 use crate::metrics::TopologyMetrics;
 use crate::nat_discovery::LocalAddressManager;
+use crate::partition::PartitionDetector;
 use crate::profile::PacingProfile;
+use crate::static_peers::StaticPeers;
 
 /// Inputs the background tasks need, captured at build time so that
 /// [`TopologyBehaviour::spawn_tasks`] can start them later without re-deriving
@@ -60,11 +66,16 @@ pub struct TopologyBehaviourBuilder<I: SwarmIdentity + Clone> {
     config: TopologyConfig,
     bootnodes: Vec<Multiaddr>,
     trusted_peers: Vec<Multiaddr>,
+    /// Static peers from the network configuration. Replaced by a non-empty
+    /// [`TopologyConfig::with_static_peers`].
+    network_static_peers: Vec<(OverlayAddress, Multiaddr)>,
     nat_addrs: Vec<Multiaddr>,
     /// No listen addresses configured: the node is dial-only, so its IP
     /// capability is pinned instead of listener-derived.
     dial_only: bool,
     trust_local_peers: bool,
+    /// Optional features advertised in the handshake.
+    capabilities: PeerCapabilities,
     scoring_config: SwarmScoringConfig,
     max_per_bin: usize,
     peer_store: Option<PeerStore>,
@@ -72,6 +83,26 @@ pub struct TopologyBehaviourBuilder<I: SwarmIdentity + Clone> {
     /// file). Overridden by an explicit [`TopologyConfig::with_connection_profile`];
     /// falls back to the node-type default when both are unset.
     network_profile: Option<ConnectionProfile>,
+    /// Outbound-dial limit from the network configuration. Overridden by an
+    /// explicit [`TopologyConfig::with_max_concurrent_dials`]; falls back to the
+    /// profile's dial concurrency when both are unset.
+    network_max_concurrent_dials: Option<usize>,
+    /// Churn alert threshold from the network configuration. Overridden by an
+    /// explicit [`TopologyConfig::with_churn_threshold`].
+    network_churn_threshold: Option<f64>,
+    /// Self-announce cadence from the network configuration. Overridden by an
+    /// explicit [`TopologyConfig::with_self_announce_interval`].
+    network_self_announce_interval: Option<Duration>,
+    /// Gossip exchange burst from the network configuration, applied over
+    /// [`TopologyConfig::gossip`] when set.
+    network_exchange_burst: Option<usize>,
+    /// Gossip exchange interval from the network configuration, applied over
+    /// [`TopologyConfig::gossip`] when set.
+    network_exchange_interval: Option<Duration>,
+    /// Resolver for `/dnsaddr/` bootnodes, from the network configuration's
+    /// DNS servers and cache TTL.
+    #[cfg(not(target_arch = "wasm32"))]
+    dnsaddr: Arc<vertex_net_dnsaddr::DnsaddrResolver>,
 }
 
 impl<I: SwarmIdentity + Clone> TopologyBehaviourBuilder<I> {
@@ -86,9 +117,15 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviourBuilder<I> {
             config: TopologyConfig::default(),
             bootnodes: network_config.bootnodes().to_vec(),
             trusted_peers: network_config.trusted_peers().to_vec(),
+            network_static_peers: network_config.static_peers().to_vec(),
             nat_addrs: network_config.nat_addrs().to_vec(),
             dial_only: network_config.listen_addrs().is_empty(),
             trust_local_peers: network_config.trust_local_peers(),
+            capabilities: if network_config.compression_enabled() {
+                PeerCapabilities::COMPRESSION
+            } else {
+                PeerCapabilities::NONE
+            },
             scoring_config: SwarmScoringConfig::builder()
                 .ban_threshold(peer_config.ban_threshold())
                 .warn_threshold(peer_config.warn_threshold())
@@ -96,6 +133,16 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviourBuilder<I> {
             max_per_bin: peer_config.max_per_bin(),
             peer_store: None,
             network_profile: network_config.connection_profile(),
+            network_max_concurrent_dials: network_config.max_concurrent_dials(),
+            network_churn_threshold: network_config.churn_threshold(),
+            network_self_announce_interval: network_config.self_announce_interval(),
+            network_exchange_burst: network_config.exchange_burst(),
+            network_exchange_interval: network_config.exchange_interval(),
+            #[cfg(not(target_arch = "wasm32"))]
+            dnsaddr: Arc::new(vertex_net_dnsaddr::DnsaddrResolver::from_config(
+                vertex_net_dnsaddr::DnsResolverConfig::from_servers(network_config.dns_servers()),
+                network_config.dns_cache_ttl(),
+            )),
         }
     }
 
@@ -131,6 +178,7 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviourBuilder<I> {
                 scoring: self.scoring_config,
                 max_per_bin: self.max_per_bin,
                 store: self.peer_store,
+                distance_metric: self.config.kademlia.distance_metric.clone(),
                 ..Default::default()
             },
         );
@@ -200,13 +248,29 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviourBuilder<I> {
         // Wire kademlia routing as the handshake admission gate so the
         // routing layer can veto a peer before the local side commits
         // to its final exchange message.
-        let admission_control = kademlia_admission_control(routing.clone());
+        let static_peers = if self.config.static_peers.is_empty() {
+            self.network_static_peers
+        } else {
+            self.config.static_peers.clone()
+        };
+        let static_peers = Arc::new(StaticPeers::new(static_peers));
+        let admission_control = kademlia_admission_control(routing.clone(), static_peers.clone());
 
         // Create composed protocol behaviours
-        let protocols =
-            ProtocolBehaviours::new(identity.clone(), nat_discovery.clone(), admission_control);
+        let protocols = ProtocolBehaviours::new(
+            identity.clone(),
+            nat_discovery.clone(),
+            admission_control,
+            self.config.gossip.hive_limits(),
+            self.config.max_concurrent_handshakes,
+            self.capabilities,
+        );
 
         let metrics = Arc::new(TopologyMetrics::new());
+        let churn = Arc::new(ChurnTracker::new(
+            self.config.churn_threshold.or(self.network_churn_threshold),
+        ));
+        let audit = Arc::new(ConnectionAudit::new(AUDIT_CAPACITY));
 
         let handle = TopologyHandle::new(
             identity.clone(),
@@ -216,6 +280,8 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviourBuilder<I> {
             command_tx,
             event_tx.clone(),
             agent_versions.clone(),
+            churn.clone(),
+            audit.clone(),
         );
 
         // Queue static NAT addresses to emit as external addresses on first poll
@@ -224,7 +290,13 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviourBuilder<I> {
         // Handles wired here; the tasks behind them start in `spawn_tasks`.
         let evaluator_handle = RoutingEvaluatorHandle::new();
         let (gossip, gossip_channels) = gossip_channel();
-        let gossip_config = self.config.gossip.clone();
+        let mut gossip_config = self.config.gossip.clone();
+        if let Some(burst) = self.network_exchange_burst {
+            gossip_config.exchange_burst = burst;
+        }
+        if let Some(interval) = self.network_exchange_interval {
+            gossip_config.exchange_interval = interval;
+        }
 
         let behaviour = TopologyBehaviour {
             identity,
@@ -234,35 +306,57 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviourBuilder<I> {
             connection_registry,
             nat_discovery,
             bootnodes: self.bootnodes,
+            bootnode_connector: BootnodeConnector::new(self.config.bootnode_retry_budget),
             trusted_peers: self.trusted_peers,
+            static_peers,
             command_rx,
             event_tx,
             pending_actions: VecDeque::new(),
             gossip,
             dial_interval: vertex_tasks::time::interval(evaluation_interval),
+            self_announce_interval: self
+                .config
+                .self_announce_interval
+                .or(self.network_self_announce_interval)
+                .map(|period| vertex_tasks::time::interval_after(period, period)),
             dial_rate: RateLimiter::new(dial_quota),
             dial_rate_timer: None,
             pending_bootnode_resolution: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            dnsaddr: self.dnsaddr,
             evaluator_handle,
             dial_tracker: DialTracker::new(DialTrackerConfig {
                 max_pending: 0, // not used as a queue, only for direct in-flight tracking
-                // Generous limit from the profile; routing capacity is the
-                // real gate on how many dials become connections.
-                max_in_flight: pacing.dial_concurrency,
+                // Global outbound-dial limit: an explicit cap (topology config,
+                // then network config), else the profile's generous default.
+                // Routing capacity is the real gate on how many dials become
+                // connections.
+                max_in_flight: self
+                    .config
+                    .max_concurrent_dials
+                    .or(self.network_max_concurrent_dials)
+                    .unwrap_or(pacing.dial_concurrency)
+                    .max(1),
                 pending_ttl: HANDSHAKE_TIMEOUT,
                 in_flight_timeout: HANDSHAKE_TIMEOUT,
                 cleanup_interval: Duration::from_secs(30),
                 metrics_label: Some("topology"),
                 ..Default::default()
             }),
+            queued_dials: VecDeque::new(),
             early_disconnect_threshold: self.config.early_disconnect_threshold,
+            inbound_only_grace: self.config.inbound_only_grace,
             pending_closes: HashMap::new(),
             outbound_public_dials: HashSet::new(),
+            connection_phases: ConnectionStateMachine::new(),
             lifecycle_rx,
             agent_versions,
             trust_local_peers: self.trust_local_peers,
             pending_nat_external_addrs,
             metrics,
+            churn,
+            audit,
+            partition: PartitionDetector::default(),
             pending_tasks: Some(PendingTopologyTasks {
                 gossip_config,
                 gossip_channels,
@@ -366,8 +460,8 @@ mod tests {
         fn discovery_enabled(&self) -> bool {
             true
         }
-        fn max_peers(&self) -> usize {
-            32
+        fn max_peers(&self) -> Option<usize> {
+            Some(32)
         }
         fn idle_timeout(&self) -> Duration {
             Duration::from_secs(60)
@@ -0,0 +1,147 @@
+//! Connection churn: connects plus disconnects per minute.
+//!
+//! A healthy node settles into a stable peer set; a high, sustained rate of
+//! connects and disconnects points at network trouble (flapping links, a peer
+//! set rejecting us, trim thrash). [`ChurnTracker`] keeps the event times of a
+//! sliding window and, when a threshold is configured, reports the crossing
+//! once so the behaviour can raise [`TopologyEvent::ChurnExceeded`]. The alert
+//! re-arms once the rate falls back to the threshold.
+//!
+//! [`TopologyEvent::ChurnExceeded`]: crate::TopologyEvent::ChurnExceeded
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use vertex_tasks::time::Instant;
+
+/// Sliding window the churn rate is measured over.
+pub const CHURN_WINDOW: Duration = Duration::from_secs(60);
+
+struct ChurnState {
+    /// Connect and disconnect times within the window, oldest first.
+    events: VecDeque<Instant>,
+    /// Whether the current excursion above the threshold was already reported.
+    alerting: bool,
+}
+
+/// Sliding-window connect/disconnect counter shared by the behaviour (records)
+/// and the handle (reads).
+pub(crate) struct ChurnTracker {
+    /// Events per minute above which a crossing is reported; `None` disables
+    /// alerting.
+    threshold: Option<f64>,
+    state: Mutex<ChurnState>,
+}
+
+impl ChurnTracker {
+    pub(crate) fn new(threshold: Option<f64>) -> Self {
+        Self {
+            threshold,
+            state: Mutex::new(ChurnState {
+                events: VecDeque::new(),
+                alerting: false,
+            }),
+        }
+    }
+
+    /// The configured alert threshold, in events per minute.
+    pub(crate) fn threshold(&self) -> Option<f64> {
+        self.threshold
+    }
+
+    /// Record a connect or disconnect. Returns the churn rate when this event
+    /// took it above the threshold.
+    pub(crate) fn record(&self) -> Option<f64> {
+        self.record_at(Instant::now())
+    }
+
+    /// Connects plus disconnects per minute over the last [`CHURN_WINDOW`].
+    pub(crate) fn churn_rate(&self) -> f64 {
+        self.churn_rate_at(Instant::now())
+    }
+
+    fn record_at(&self, now: Instant) -> Option<f64> {
+        let mut state = self.state.lock();
+        state.events.push_back(now);
+        let rate = prune_and_rate(&mut state.events, now);
+
+        let threshold = self.threshold?;
+        if rate <= threshold {
+            state.alerting = false;
+            return None;
+        }
+        if state.alerting {
+            return None;
+        }
+        state.alerting = true;
+        Some(rate)
+    }
+
+    fn churn_rate_at(&self, now: Instant) -> f64 {
+        prune_and_rate(&mut self.state.lock().events, now)
+    }
+}
+
+/// Drop events older than the window and return the per-minute rate.
+fn prune_and_rate(events: &mut VecDeque<Instant>, now: Instant) -> f64 {
+    while events
+        .front()
+        .is_some_and(|at| now.duration_since(*at) >= CHURN_WINDOW)
+    {
+        events.pop_front();
+    }
+    events.len() as f64 * 60.0 / CHURN_WINDOW.as_secs_f64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_counts_events_within_the_window() {
+        let tracker = ChurnTracker::new(None);
+        let start = Instant::now();
+
+        // Ten connect/disconnect pairs in the first ten seconds.
+        for i in 0..20 {
+            assert_eq!(
+                tracker.record_at(start + Duration::from_millis(500 * i)),
+                None
+            );
+        }
+        assert_eq!(tracker.churn_rate_at(start + Duration::from_secs(10)), 20.0);
+
+        // The burst ages out of the window.
+        assert_eq!(tracker.churn_rate_at(start + CHURN_WINDOW * 2), 0.0);
+    }
+
+    #[test]
+    fn warning_fires_once_above_the_threshold_and_rearms() {
+        let tracker = ChurnTracker::new(Some(5.0));
+        let start = Instant::now();
+
+        for i in 0..5 {
+            assert_eq!(tracker.record_at(start + Duration::from_secs(i)), None);
+        }
+        assert_eq!(
+            tracker.record_at(start + Duration::from_secs(5)),
+            Some(6.0),
+            "the sixth event in a minute crosses the threshold"
+        );
+        assert_eq!(
+            tracker.record_at(start + Duration::from_secs(6)),
+            None,
+            "one warning per excursion"
+        );
+
+        // After the window drains, a single event is back under the threshold
+        // and re-arms the alert.
+        let later = start + CHURN_WINDOW * 2;
+        assert_eq!(tracker.record_at(later), None);
+        for i in 1..5 {
+            assert_eq!(tracker.record_at(later + Duration::from_secs(i)), None);
+        }
+        assert_eq!(tracker.record_at(later + Duration::from_secs(5)), Some(6.0));
+    }
+}
@@ -1,33 +1,38 @@
 //! Composed protocol behaviours for topology.
 //!
-//! Uses libp2p's derive(NetworkBehaviour) to compose handshake, hive, and the
-//! stock libp2p ping protocol into a single behaviour with automatic handler
-//! composition.
+//! Uses libp2p's derive(NetworkBehaviour) to compose handshake, hive, goodbye,
+//! and the stock libp2p ping protocol into a single behaviour with automatic
+//! handler composition.
 //!
 //! Liveness and RTT come from `libp2p::ping` (`/ipfs/ping`), the same protocol
 //! the reference implementation uses for per-peer reachability (its reacher
 //! pings over `/ipfs/ping`). The bee `/swarm/pingpong` protocol was an
-//! operator-only diagnostic and is not used here.
+//! operator-only diagnostic and is not used here; operator spot checks go
+//! through [`PingProbe`], which sends a single `/ipfs/ping` on request.
 
 use std::sync::Arc;
 
 use libp2p::ping;
 use libp2p::swarm::NetworkBehaviour;
 use vertex_swarm_api::SwarmIdentity;
+use vertex_swarm_net_goodbye::{GoodbyeBehaviour, GoodbyeEvent};
 use vertex_swarm_net_handshake::{HandshakeBehaviour, HandshakeEvent, SharedAdmissionControl};
 use vertex_swarm_net_hive::{
-    DiscardSilently, HiveBehaviour, HiveEvent, HivePeerHandler, LearnAndDial,
+    DiscardSilently, HiveBehaviour, HiveEvent, HiveLimits, HivePeerHandler, LearnAndDial,
 };
-use vertex_swarm_primitives::SwarmNodeType;
+use vertex_swarm_primitives::{PeerCapabilities, SwarmNodeType};
 
 use crate::nat_discovery::LocalAddressManager;
+use crate::probe::{PingProbe, ProbeEvent};
 
 /// Combined event from all protocol behaviours.
 #[derive(Debug)]
 pub enum ProtocolEvent {
     Handshake(HandshakeEvent),
     Hive(HiveEvent),
+    Goodbye(GoodbyeEvent),
     Ping(ping::Event),
+    Probe(ProbeEvent),
 }
 
 impl ProtocolEvent {
@@ -49,14 +54,27 @@ impl ProtocolEvent {
                 connection_id,
                 ..
             }) => (*peer_id, *connection_id),
+            Self::Hive(HiveEvent::LimitExceeded {
+                peer_id,
+                connection_id,
+                ..
+            }) => (*peer_id, *connection_id),
             Self::Hive(HiveEvent::Error {
                 peer_id,
                 connection_id,
                 ..
             }) => (*peer_id, *connection_id),
+            Self::Goodbye(GoodbyeEvent::Received {
+                peer_id,
+                connection_id,
+                ..
+            }) => (*peer_id, *connection_id),
             Self::Ping(ping::Event {
                 peer, connection, ..
             }) => (*peer, *connection),
+            Self::Probe(ProbeEvent {
+                peer, connection, ..
+            }) => (*peer, *connection),
         }
     }
 }
@@ -73,16 +91,28 @@ impl From<HiveEvent> for ProtocolEvent {
     }
 }
 
+impl From<GoodbyeEvent> for ProtocolEvent {
+    fn from(event: GoodbyeEvent) -> Self {
+        ProtocolEvent::Goodbye(event)
+    }
+}
+
 impl From<ping::Event> for ProtocolEvent {
     fn from(event: ping::Event) -> Self {
         ProtocolEvent::Ping(event)
     }
 }
 
+impl From<ProbeEvent> for ProtocolEvent {
+    fn from(event: ProbeEvent) -> Self {
+        ProtocolEvent::Probe(event)
+    }
+}
+
 /// Composed protocol behaviours.
 ///
 /// This struct uses libp2p's derive macro to automatically compose
-/// the three protocol handlers into a single connection handler.
+/// the protocol handlers into a single connection handler.
 #[derive(NetworkBehaviour)]
 #[behaviour(to_swarm = "ProtocolEvent")]
 pub struct ProtocolBehaviours<I>
@@ -91,7 +121,9 @@ where
 {
     pub(crate) handshake: HandshakeBehaviour<I, LocalAddressManager>,
     pub(crate) hive: HiveBehaviour<I>,
+    pub(crate) goodbye: GoodbyeBehaviour<I>,
     pub(crate) ping: ping::Behaviour,
+    pub(crate) probe: PingProbe,
 }
 
 impl<I> ProtocolBehaviours<I>
@@ -108,23 +140,37 @@ where
     /// the routing layer can veto a peer before the local side commits
     /// to the final exchange message (see
     /// [`HandshakeBehaviour::with_admission_control`]).
+    /// `hive_limits` caps each inbound hive response; `handshake_limit`
+    /// overrides the handshake crate's concurrent-handshake cap;
+    /// `capabilities` are the optional features offered in the handshake.
     pub(crate) fn new(
         identity: Arc<I>,
         address_provider: Arc<LocalAddressManager>,
         admission_control: SharedAdmissionControl,
+        hive_limits: HiveLimits,
+        handshake_limit: Option<usize>,
+        capabilities: PeerCapabilities,
     ) -> Self {
         let peer_handler: Arc<dyn HivePeerHandler> = match identity.node_type() {
             SwarmNodeType::Bootnode => Arc::new(DiscardSilently),
             SwarmNodeType::Client | SwarmNodeType::Storer => Arc::new(LearnAndDial),
         };
 
+        let mut handshake = HandshakeBehaviour::new(identity.clone(), address_provider, "topology")
+            .with_admission_control(admission_control)
+            .with_capabilities(capabilities);
+        if let Some(limit) = handshake_limit {
+            handshake = handshake.with_max_concurrent_handshakes(limit);
+        }
         Self {
-            handshake: HandshakeBehaviour::new(identity.clone(), address_provider, "topology")
-                .with_admission_control(admission_control),
-            hive: HiveBehaviour::with_peer_handler(identity, peer_handler),
+            handshake,
+            hive: HiveBehaviour::with_peer_handler(identity.clone(), peer_handler)
+                .with_limits(hive_limits),
+            goodbye: GoodbyeBehaviour::new(identity),
             // Stock libp2p ping: periodic liveness + RTT over `/ipfs/ping`.
             // Defaults (15s interval, 20s timeout) match typical libp2p usage.
             ping: ping::Behaviour::new(ping::Config::new()),
+            probe: PingProbe::default(),
         }
     }
 }
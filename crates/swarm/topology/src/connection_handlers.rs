@@ -4,13 +4,14 @@ use libp2p::swarm::ConnectionError;
 use metrics::gauge;
 use tracing::{debug, trace, warn};
 use vertex_net_local::{AddressScope, classify_multiaddr};
-use vertex_net_peer_registry::ConnectionState;
-use vertex_swarm_api::{ReportSource, SwarmIdentity, SwarmScoringEvent};
+use vertex_net_peer_registry::{ConnectionDirection, ConnectionState};
+use vertex_swarm_api::{ConnectionAuditStage, ReportSource, SwarmIdentity, SwarmScoringEvent};
 use vertex_swarm_net_handshake::HANDSHAKE_TIMEOUT;
+use vertex_swarm_peer_manager::ConnectionTransition;
 use vertex_swarm_primitives::SwarmNodeType;
 
 use crate::error::{DialError, DisconnectReason};
-use crate::events::TopologyEvent;
+use crate::events::{DialReason, TopologyEvent};
 use crate::gossip::GossipInput;
 use crate::kademlia::{RoutingCapacity, SwarmRouting};
 
@@ -30,6 +31,20 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         &mut self,
         established: libp2p::swarm::behaviour::ConnectionEstablished,
     ) {
+        // A dialed connection arrives in Dialing, an inbound one untracked. The
+        // handshake handler is armed on every new connection, so the
+        // connection moves straight through Connected into Handshaking.
+        let _ = self
+            .connection_phases
+            .apply(established.connection_id, ConnectionTransition::Establish)
+            .and_then(|_| {
+                self.connection_phases.apply(
+                    established.connection_id,
+                    ConnectionTransition::StartHandshake,
+                )
+            });
+
+        let mut audited_overlay = None;
         if established.endpoint.is_dialer() {
             // Record outbound dials to a public-scope address. A successful
             // outbound connection proves the dialed address is reachable, so on
@@ -58,6 +73,7 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
                 if let Some(overlay) = &overlay {
                     self.routing.dial_connected(overlay);
                 }
+                audited_overlay = overlay;
             } else {
                 trace!(peer_id = %established.peer_id, "ConnectionEstablished for untracked outbound peer");
             }
@@ -66,6 +82,18 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
                 .connected_inbound(established.peer_id, established.connection_id);
             gauge!("peer_registry_pending_connections").increment(1.0);
         }
+
+        let direction = if established.endpoint.is_dialer() {
+            ConnectionDirection::Outbound
+        } else {
+            ConnectionDirection::Inbound
+        };
+        self.audit.record(
+            established.peer_id,
+            audited_overlay,
+            ConnectionAuditStage::Connected,
+            Some(direction.into()),
+        );
     }
 
     pub(crate) fn handle_connection_closed(
@@ -76,6 +104,10 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         // specific connection, regardless of whether other connections to
         // the peer remain.
         self.outbound_public_dials.remove(&closed.connection_id);
+        let _ = self
+            .connection_phases
+            .apply(closed.connection_id, ConnectionTransition::Close);
+        self.connection_phases.closed(&closed.connection_id);
 
         if closed.remaining_established > 0 {
             return;
@@ -108,6 +140,33 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         }
         let connected_at = removed_state.as_ref().and_then(|s| s.connected_at());
         let overlay = removed_state.as_ref().and_then(|s| s.id());
+        self.audit.record(
+            closed.peer_id,
+            overlay,
+            ConnectionAuditStage::Closed,
+            Some(reason.into()),
+        );
+
+        // A rotated bootnode served its gossip; one the remote side dropped
+        // spends a retry. Our other closes leave its budget alone.
+        if reason == DisconnectReason::BootnodeRotation {
+            self.bootnode_connector.on_served(&closed.peer_id);
+        } else if !reason.is_locally_initiated() {
+            self.bootnode_connector.on_dropped(&closed.peer_id);
+        }
+
+        // A static peer that drops after its handshake is reconnected
+        // straight away, whoever closed it. One that closes before the
+        // handshake refused us: it backs off like a failed dial and waits for
+        // the evaluation tick, so it cannot spin a dial loop. A ban still
+        // holds because the dial tracker refuses banned peers.
+        if self.static_peers.contains_peer_id(&closed.peer_id) {
+            if overlay.is_some() {
+                self.dial_static_peers();
+            } else {
+                self.record_static_dial_failure(&closed.peer_id);
+            }
+        }
 
         self.gossip.send(GossipInput::ConnectionClosed {
             peer_id: closed.peer_id,
@@ -192,9 +251,13 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         }
 
         self.refresh_topology_phase();
+        self.check_partition();
     }
 
     pub(crate) fn handle_dial_failure(&mut self, failure: libp2p::swarm::behaviour::DialFailure) {
+        // The dial never produced a connection, so it never closes.
+        self.connection_phases.closed(&failure.connection_id);
+
         let Some(peer_id) = failure.peer_id else {
             trace!("DialFailure without peer_id");
             return;
@@ -210,14 +273,34 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         let dial_duration = Some(request.queued_at().elapsed());
 
         let classified_error = classify_dial_error(failure.error);
+        if request.data == DialReason::Static && overlay.is_none() {
+            self.record_static_dial_failure(&peer_id);
+        }
+
+        // Only a failure on the bootnode's side spends a retry; a dial we
+        // aborted or denied ourselves never reached it.
+        if request.data == DialReason::Bootnode && !classified_error.is_local() {
+            self.bootnode_connector.on_dropped(&peer_id);
+        }
+        self.audit.record(
+            peer_id,
+            overlay,
+            ConnectionAuditStage::DialFailed,
+            Some((&classified_error).into()),
+        );
 
         // Release routing capacity for this failed dial
         if let Some(overlay) = &overlay {
             self.routing.release_dial(overlay);
             // Backoff applies even to locally-denied dials: redialing while
             // the transport cap is exhausted would be denied again, so pacing
-            // the retry is correct either way.
-            self.peer_manager.record_dial_failure(overlay);
+            // the retry is correct either way. A negotiation failure means the
+            // peer is incompatible and takes the longer schedule.
+            if classified_error == DialError::NegotiationFailed {
+                self.peer_manager.record_negotiation_failure(overlay);
+            } else {
+                self.peer_manager.record_dial_failure(overlay);
+            }
 
             // Score penalty based on error type, through the single report
             // path. Locally-denied dials carry no penalty: the peer was never
@@ -245,22 +328,35 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         });
     }
 
-    /// Clean up pending connections that have been waiting longer than HANDSHAKE_TIMEOUT.
+    /// Clean up dials past their in-flight timeout and pending connections
+    /// that have been waiting longer than HANDSHAKE_TIMEOUT.
     pub(crate) fn cleanup_stale_pending(&mut self) {
         // Clean up stale dials from the DialTracker (covers all outbound dials)
         let cleanup = self.dial_tracker.cleanup_expired();
         for request in cleanup.timed_out_in_flight {
+            if request.data == DialReason::Bootnode {
+                self.bootnode_connector.on_dropped(&request.peer_id);
+            }
+            if request.data == DialReason::Static && request.id.is_none() {
+                self.record_static_dial_failure(&request.peer_id);
+            }
             if let Some(overlay) = &request.id {
                 self.routing.release_dial(overlay);
                 self.peer_manager.record_dial_failure(overlay);
             }
+            let dial_duration = request.queued_at().elapsed();
             warn!(
                 peer_id = %request.peer_id,
                 overlay = ?request.id,
-                timeout = ?HANDSHAKE_TIMEOUT,
+                ?dial_duration,
                 "Cleaning up stale dial from tracker"
             );
-            let dial_duration = request.queued_at().elapsed();
+            self.audit.record(
+                request.peer_id,
+                request.id,
+                ConnectionAuditStage::DialFailed,
+                Some((&DialError::Stale).into()),
+            );
             self.emit_event(TopologyEvent::DialFailed {
                 overlay: request.id,
                 addrs: request.addrs,
@@ -280,6 +376,12 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
 
                 let reason = *state.reason();
                 let overlay = state.id();
+                if reason == Some(DialReason::Bootnode) {
+                    self.bootnode_connector.on_dropped(&peer_id);
+                }
+                if reason == Some(DialReason::Static) && overlay.is_none() {
+                    self.record_static_dial_failure(&peer_id);
+                }
 
                 if let Some(overlay) = &overlay {
                     self.routing.release_handshake(overlay);
@@ -292,6 +394,12 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
                     timeout = ?HANDSHAKE_TIMEOUT,
                     "Cleaning up stale handshake"
                 );
+                self.audit.record(
+                    peer_id,
+                    overlay,
+                    ConnectionAuditStage::HandshakeFailed,
+                    Some((&DialError::Stale).into()),
+                );
 
                 self.emit_event(TopologyEvent::DialFailed {
                     overlay,
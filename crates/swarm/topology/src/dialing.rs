@@ -1,12 +1,15 @@
 //! Dialing methods for topology behaviour.
 
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
+
 use libp2p::Multiaddr;
 use libp2p::PeerId;
 use libp2p::swarm::ToSwarm;
 use rand::seq::SliceRandom;
 use tracing::{debug, info, trace, warn};
 use vertex_net_dialer::error::PrepareError;
-use vertex_swarm_api::SwarmIdentity;
+use vertex_swarm_api::{ConnectionAuditStage, SwarmIdentity};
 use vertex_swarm_peer::SwarmPeer;
 use vertex_swarm_primitives::SwarmNodeType;
 use vertex_util_runtime::rand::non_crypto_rng;
@@ -16,7 +19,7 @@ use crate::behaviour::BootnodeResolutionFuture;
 use crate::gossip::GossipInput;
 use crate::kademlia::RoutingCapacity;
 
-use crate::behaviour::{DialTarget, TopologyBehaviour};
+use crate::behaviour::{DialTarget, MAX_QUEUED_DIALS, TopologyBehaviour};
 
 impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
     /// Dial a known SwarmPeer for discovery.
@@ -113,9 +116,28 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
                 trace!(%peer_id, "Skipping dial - peer in backoff or banned");
                 return;
             }
+            Err(PrepareError::AtCapacity) => {
+                if let Some(overlay) = target.overlay() {
+                    self.routing.release_dial(&overlay);
+                }
+                self.queue_dial(peer_id, target, reason);
+                return;
+            }
         };
 
+        // Neighborhood dials get more patience than shallow-bin ones.
+        if let Some(overlay) = target.overlay() {
+            self.dial_tracker
+                .set_in_flight_timeout(&peer_id, self.routing.dial_timeout(&overlay));
+        }
+
         debug!(%peer_id, ?reason, "Dialing peer");
+        self.audit.record(
+            peer_id,
+            target.overlay(),
+            ConnectionAuditStage::DialStarted,
+            Some(reason.into()),
+        );
 
         // Track discovery dials for delayed gossip exchange
         if reason == DialReason::Discovery {
@@ -125,6 +147,38 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         self.pending_actions.push_back(ToSwarm::Dial { opts });
     }
 
+    /// Hold a dial refused at the outbound-dial limit until a slot frees.
+    fn queue_dial(&mut self, peer_id: PeerId, target: DialTarget, reason: DialReason) {
+        if self
+            .queued_dials
+            .iter()
+            .any(|(queued, _)| queued.peer_id() == Some(peer_id))
+        {
+            return;
+        }
+        if self.queued_dials.len() >= MAX_QUEUED_DIALS {
+            metrics::counter!("topology_dials_shed_total").increment(1);
+            debug!(%peer_id, ?reason, "Dial queue full, dropping dial");
+            return;
+        }
+        trace!(%peer_id, ?reason, "Dial limit reached, queueing dial");
+        self.queued_dials.push_back((target, reason));
+        metrics::gauge!("topology_dials_queued").set(self.queued_dials.len() as f64);
+    }
+
+    /// Start queued dials while the outbound-dial limit has room.
+    pub(crate) fn drain_queued_dials(&mut self) {
+        if self.queued_dials.is_empty() {
+            return;
+        }
+        while self.dial_tracker.has_capacity()
+            && let Some((target, reason)) = self.queued_dials.pop_front()
+        {
+            self.dial(target, reason);
+        }
+        metrics::gauge!("topology_dials_queued").set(self.queued_dials.len() as f64);
+    }
+
     pub(crate) fn connect_bootnodes(&mut self) {
         let mut bootnodes = self.bootnodes.clone();
         bootnodes.shuffle(&mut non_crypto_rng());
@@ -135,10 +189,18 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         }
 
         // `/dnsaddr/` entries need resolution to dialable multiaddrs before they
-        // can be dialed. Native does this over the system resolver; the browser
-        // does it over DNS-over-HTTPS. When nothing needs resolving the helper
-        // returns `None` and we dial the literal addresses immediately.
-        match start_bootnode_resolution(bootnodes.clone(), trusted_peers.clone()) {
+        // can be dialed. Native does this over the node's caching resolver; the
+        // browser does it over DNS-over-HTTPS. When nothing needs resolving the
+        // helper returns `None` and we dial the literal addresses immediately.
+        #[cfg(not(target_arch = "wasm32"))]
+        let resolution = start_bootnode_resolution(
+            Arc::clone(&self.dnsaddr),
+            bootnodes.clone(),
+            trusted_peers.clone(),
+        );
+        #[cfg(target_arch = "wasm32")]
+        let resolution = start_bootnode_resolution(bootnodes.clone(), trusted_peers.clone());
+        match resolution {
             Some(future) => self.pending_bootnode_resolution = Some(future),
             None => self.dial_bootnodes(bootnodes, trusted_peers),
         }
@@ -155,6 +217,7 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         }
 
         for addr in bootnodes {
+            self.bootnode_connector.register(addr.clone());
             self.dial(DialTarget::Unknown(addr), DialReason::Bootnode);
         }
 
@@ -163,6 +226,36 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         }
     }
 
+    /// Redial the bootnodes that dropped before serving their gossip and
+    /// still have retries left (see [`crate::bootnode`]).
+    pub(crate) fn redial_bootnodes(&mut self) {
+        for addr in self.bootnode_connector.take_due() {
+            self.dial(DialTarget::Unknown(addr), DialReason::Bootnode);
+        }
+    }
+
+    /// Dial every static peer that is not connected, being dialed, or in
+    /// dial backoff (see [`crate::static_peers`]).
+    pub(crate) fn dial_static_peers(&mut self) {
+        let addrs: Vec<Multiaddr> = self
+            .static_peers
+            .iter()
+            .filter(|(overlay, _)| !self.peer_manager.peer_is_in_backoff(overlay))
+            .map(|(_, addr)| addr.clone())
+            .collect();
+        for addr in addrs {
+            self.dial(DialTarget::Unknown(addr), DialReason::Static);
+        }
+    }
+
+    /// Back off a static peer whose dial or handshake failed. Static peers
+    /// are dialed by address, so the failure carries no overlay of its own.
+    pub(crate) fn record_static_dial_failure(&self, peer_id: &PeerId) {
+        if let Some(overlay) = self.static_peers.overlay_of(peer_id) {
+            self.peer_manager.record_dial_failure(&overlay);
+        }
+    }
+
     /// Check if a PeerId is already being tracked (dialing, connected, or active).
     pub(crate) fn is_peer_tracked(&self, peer_id: &PeerId) -> bool {
         self.connection_registry.contains_peer(peer_id) || self.dial_tracker.contains_peer(peer_id)
@@ -170,17 +263,18 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
 }
 
 /// Start resolving `/dnsaddr/` bootnode and trusted-peer entries to dialable
-/// multiaddrs, using the system resolver.
+/// multiaddrs through the node's caching `resolver`.
 ///
 /// Returns `None` when no entry needs resolution so the caller dials the literal
 /// addresses directly. Bootnodes and trusted peers are resolved separately so
 /// the caller can preserve the per-list dial reason.
 #[cfg(not(target_arch = "wasm32"))]
 fn start_bootnode_resolution(
+    resolver: Arc<vertex_net_dnsaddr::DnsaddrResolver>,
     bootnodes: Vec<Multiaddr>,
     trusted_peers: Vec<Multiaddr>,
 ) -> Option<BootnodeResolutionFuture> {
-    use vertex_net_dnsaddr::{is_dnsaddr, resolve_all};
+    use vertex_net_dnsaddr::is_dnsaddr;
 
     let needs_resolution = bootnodes.iter().any(is_dnsaddr) || trusted_peers.iter().any(is_dnsaddr);
     if !needs_resolution {
@@ -194,8 +288,8 @@ fn start_bootnode_resolution(
     );
 
     Some(Box::pin(async move {
-        let resolved_bootnodes = resolve_all(bootnodes.iter()).await;
-        let resolved_trusted = resolve_all(trusted_peers.iter()).await;
+        let resolved_bootnodes = resolver.resolve_all(bootnodes.iter()).await;
+        let resolved_trusted = resolver.resolve_all(trusted_peers.iter()).await;
         (resolved_bootnodes, resolved_trusted)
     }))
 }
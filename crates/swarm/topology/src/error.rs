@@ -40,12 +40,21 @@ pub enum DialError {
     Other(String),
 }
 
+impl DialError {
+    /// Whether the dial ended on our side without the peer being contacted.
+    pub fn is_local(&self) -> bool {
+        matches!(self, Self::Denied | Self::Stale)
+    }
+}
+
 /// Reason for rejecting a peer connection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::IntoStaticStr)]
 #[strum(serialize_all = "snake_case")]
 pub enum RejectionReason {
     /// Kademlia bin is saturated.
     BinSaturated,
+    /// Connection limit reached and the peer fills no gap bin.
+    ConnectionLimit,
     /// Peer is banned.
     Banned,
     /// Duplicate connection from same peer.
@@ -74,6 +83,17 @@ pub enum TopologyError {
     #[error("peer not found: {overlay}")]
     PeerNotFound { overlay: OverlayAddress },
 
+    /// Peer has no established connection.
+    #[error("peer not connected: {overlay}")]
+    PeerNotConnected { overlay: OverlayAddress },
+
+    /// A requested ping completed without a round trip.
+    #[error("ping to {overlay} failed: {reason}")]
+    PingFailed {
+        overlay: OverlayAddress,
+        reason: String,
+    },
+
     /// Routing table is full for the given bin.
     #[error("bin {bin} is saturated, cannot add peer")]
     BinSaturated { bin: u8 },
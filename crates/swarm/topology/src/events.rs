@@ -3,9 +3,12 @@
 use std::time::Duration;
 
 use libp2p::{Multiaddr, PeerId};
-use vertex_swarm_primitives::{OverlayAddress, SwarmNodeType};
+use vertex_swarm_api::DisconnectReason;
+use vertex_swarm_peer_manager::DiscoverySource;
+use vertex_swarm_primitives::{OverlayAddress, PeerCapabilities, SwarmNodeType};
 
 use crate::kademlia::TopologyPhase;
+use crate::partition::PartitionSuspicion;
 
 pub use vertex_net_peer_registry::ConnectionDirection;
 
@@ -21,10 +24,27 @@ pub enum DialReason {
     Bootnode,
     /// Connecting to a trusted peer.
     Trusted,
+    /// Connecting to a static peer, kept connected regardless of bin logic.
+    Static,
     /// User-initiated dial command.
     Command,
 }
 
+impl DialReason {
+    /// How a peer first reached through a dial for this reason was learned.
+    /// A discovery dial targets a peer already recorded from gossip or the
+    /// snapshot, so its own mapping only applies to a record since evicted.
+    pub(crate) fn discovery_source(self) -> DiscoverySource {
+        match self {
+            Self::Discovery => DiscoverySource::Hive,
+            Self::Bootnode => DiscoverySource::Bootnode,
+            Self::Trusted => DiscoverySource::Trusted,
+            Self::Static => DiscoverySource::Static,
+            Self::Command => DiscoverySource::Dial,
+        }
+    }
+}
+
 /// Events emitted by TopologyService for external consumers.
 #[derive(Debug, Clone)]
 pub enum TopologyEvent {
@@ -36,6 +56,8 @@ pub enum TopologyEvent {
         node_type: SwarmNodeType,
         /// Whether we dialed or they dialed us.
         direction: ConnectionDirection,
+        /// Optional features both ends advertised in the handshake.
+        capabilities: PeerCapabilities,
     },
     /// Connection was rejected (bin saturated, duplicate, etc.).
     PeerRejected {
@@ -78,10 +100,34 @@ pub enum TopologyEvent {
         /// Dial reason.
         reason: Option<DialReason>,
     },
+    /// Connects plus disconnects per minute crossed the configured threshold.
+    /// Raised once per excursion; re-armed when the rate falls back.
+    ChurnExceeded {
+        /// Events per minute over the churn window.
+        rate: f64,
+        /// The configured threshold.
+        threshold: f64,
+    },
+    /// The connected bins look like the node was cut off from most of the
+    /// network; bootnodes are being redialed. Raised once per excursion;
+    /// re-armed when a shallow peer connects again.
+    NetworkPartitionSuspected {
+        /// Which signature of a partition was seen.
+        reason: PartitionSuspicion,
+    },
     /// Ping completed with RTT measurement.
     PingCompleted {
         overlay: OverlayAddress,
         rtt: Duration,
+        /// The requested probe this answers; `None` for the periodic ping.
+        probe_id: Option<u64>,
+    },
+    /// A requested ping got no round trip: the peer is not connected or the
+    /// probe failed.
+    PingFailed {
+        overlay: OverlayAddress,
+        probe_id: u64,
+        error: String,
     },
 }
 
@@ -94,6 +140,11 @@ pub enum TopologyCommand {
     Dial(Multiaddr),
     /// Close all connections to a peer.
     CloseConnection(OverlayAddress),
+    /// Close all connections to a peer, telling it why.
+    Disconnect {
+        overlay: OverlayAddress,
+        reason: DisconnectReason,
+    },
     /// Ban a peer and remove from routing.
     BanPeer {
         overlay: OverlayAddress,
@@ -101,4 +152,11 @@ pub enum TopologyCommand {
     },
     /// Flush known peers to persistent storage.
     SavePeers,
+    /// Send a single ping to a connected peer; answered by
+    /// [`TopologyEvent::PingCompleted`] or [`TopologyEvent::PingFailed`]
+    /// carrying the same `probe_id`.
+    Ping {
+        overlay: OverlayAddress,
+        probe_id: u64,
+    },
 }
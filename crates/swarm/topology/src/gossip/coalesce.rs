@@ -0,0 +1,118 @@
+//! Per-neighbor coalescing of new-peer announcements.
+//!
+//! Every storer that joins the neighborhood is announced to each connected
+//! neighbor. Sent one by one, a burst of joins opens a hive stream per peer
+//! per neighbor. [`AnnouncementCoalescer`] queues the announcements per
+//! recipient instead; each flush hands a recipient at most one batch of up
+//! to the per-response cap, and anything over the cap waits for the next
+//! flush.
+
+use std::collections::HashMap;
+
+use vertex_swarm_peer::SwarmPeer;
+use vertex_swarm_primitives::OverlayAddress;
+
+use super::events::GossipAction;
+
+/// Queues new-peer announcements per recipient until the next flush.
+pub(super) struct AnnouncementCoalescer {
+    /// Peers per message; the receiver rejects larger responses.
+    max_batch: usize,
+    /// Announcements waiting per recipient, oldest first.
+    pending: HashMap<OverlayAddress, Vec<SwarmPeer>>,
+}
+
+impl AnnouncementCoalescer {
+    pub(super) fn new(max_batch: usize) -> Self {
+        Self {
+            max_batch: max_batch.max(1),
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Queue `peer` for `to`. A record already waiting for the same
+    /// recipient is replaced in place, so a re-announced peer is sent once.
+    pub(super) fn push(&mut self, to: OverlayAddress, peer: SwarmPeer) {
+        let queue = self.pending.entry(to).or_default();
+        match queue.iter_mut().find(|p| p.overlay() == peer.overlay()) {
+            Some(queued) => *queued = peer,
+            None => queue.push(peer),
+        }
+    }
+
+    /// Drop everything waiting for a recipient that disconnected.
+    pub(super) fn forget(&mut self, to: &OverlayAddress) {
+        self.pending.remove(to);
+    }
+
+    /// Take one batch per recipient, leaving any overflow for the next flush.
+    pub(super) fn flush(&mut self) -> Vec<GossipAction> {
+        let max_batch = self.max_batch;
+        let actions = self
+            .pending
+            .iter_mut()
+            .map(|(to, queue)| GossipAction {
+                to: *to,
+                peers: queue.drain(..queue.len().min(max_batch)).collect(),
+            })
+            .collect();
+        self.pending.retain(|_, queue| !queue.is_empty());
+        actions
+    }
+
+    #[cfg(test)]
+    fn pending_for(&self, to: &OverlayAddress) -> usize {
+        self.pending.get(to).map_or(0, Vec::len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vertex_swarm_test_utils::{test_overlay, test_swarm_peer};
+
+    use super::*;
+
+    #[test]
+    fn a_burst_of_discoveries_reaches_a_neighbor_as_one_message() {
+        let neighbor = test_overlay(0xAA);
+        let other = test_overlay(0xBB);
+        let mut coalescer = AnnouncementCoalescer::new(30);
+
+        for n in 1..=20 {
+            coalescer.push(neighbor, test_swarm_peer(n));
+            coalescer.push(other, test_swarm_peer(n));
+        }
+        // A re-announced record is sent once.
+        coalescer.push(neighbor, test_swarm_peer(5));
+
+        let actions = coalescer.flush();
+        let to_neighbor: Vec<_> = actions.iter().filter(|a| a.to == neighbor).collect();
+        assert_eq!(to_neighbor.len(), 1, "one message for the whole burst");
+        assert_eq!(to_neighbor.first().map(|a| a.peers.len()), Some(20));
+        assert_eq!(actions.len(), 2, "one message per recipient");
+        assert!(coalescer.flush().is_empty());
+    }
+
+    #[test]
+    fn overflow_waits_for_the_next_flush() {
+        let neighbor = test_overlay(0xAA);
+        let mut coalescer = AnnouncementCoalescer::new(30);
+        for n in 1..=45 {
+            coalescer.push(neighbor, test_swarm_peer(n));
+        }
+
+        let sizes = |actions: Vec<GossipAction>| -> Vec<usize> {
+            actions.iter().map(|a| a.peers.len()).collect()
+        };
+        assert_eq!(sizes(coalescer.flush()), vec![30]);
+        assert_eq!(coalescer.pending_for(&neighbor), 15);
+        assert_eq!(sizes(coalescer.flush()), vec![15]);
+
+        coalescer.push(neighbor, test_swarm_peer(1));
+        coalescer.forget(&neighbor);
+        assert!(
+            coalescer.flush().is_empty(),
+            "disconnected recipient dropped"
+        );
+    }
+}
@@ -2,6 +2,12 @@
 
 use std::time::Duration;
 
+use vertex_swarm_net_hive::{HiveLimits, MAX_BATCH_SIZE, MAX_RESPONSE_BYTES};
+
+/// Floor on [`GossipConfig::exchange_interval`]. A zero interval would fire
+/// the exchange tick on every turn of the gossip loop.
+pub const MIN_EXCHANGE_INTERVAL: Duration = Duration::from_millis(10);
+
 /// Tuning knobs for gossip peer exchange and record intake.
 ///
 /// None of these values are fixed by the Swarm protocol; they trade
@@ -32,6 +38,31 @@ pub struct GossipConfig {
     /// if the connection closes first.
     pub health_check_delay: Duration,
 
+    /// Interval at which queued new-peer announcements are flushed to
+    /// connected neighbors.
+    ///
+    /// A storer joining the neighborhood is announced to every neighbor.
+    /// Announcements are queued per neighbor and sent as one hive message
+    /// of at most [`MAX_BATCH_SIZE`] peers per interval, so a burst of joins
+    /// costs one stream per neighbor instead of one per joined peer. Longer
+    /// intervals batch more but delay the news.
+    pub announce_interval: Duration,
+
+    /// Hive exchanges with newly activated peers that may go out back to
+    /// back.
+    ///
+    /// Filling gap bins activates many peers at once and each activated
+    /// storer gets an exchange. Up to this many are sent immediately; the
+    /// rest wait their turn at [`Self::exchange_interval`], so a burst of
+    /// activations does not turn into a burst of hive streams. Neighborhood
+    /// refreshes and announcements keep their own cadence.
+    pub exchange_burst: usize,
+
+    /// Time to earn one more exchange once [`Self::exchange_burst`] is spent:
+    /// the sustained rate of exchanges with newly activated peers. Values
+    /// below [`MIN_EXCHANGE_INTERVAL`] are raised to it.
+    pub exchange_interval: Duration,
+
     /// Minimum time between processing two records for the same overlay
     /// whose multiaddrs have not changed.
     ///
@@ -67,6 +98,35 @@ pub struct GossipConfig {
     /// timestamp, and a multiaddr fingerprint. Sized well above the known
     /// table so re-broadcast suppression holds across the whole supply.
     pub max_tracked_cooldowns: usize,
+
+    /// Maximum peer records accepted in a single inbound hive response.
+    ///
+    /// A response over the cap is rejected before any record is validated
+    /// and the sender is scored down. Senders batch at
+    /// [`MAX_BATCH_SIZE`], so the default admits every conforming response.
+    pub max_hive_peers_per_response: usize,
+
+    /// Maximum encoded size of a single inbound hive response, in bytes.
+    ///
+    /// Enforced like [`Self::max_hive_peers_per_response`]. Values above
+    /// [`MAX_RESPONSE_BYTES`] have no further effect: the frame reader
+    /// already stops there.
+    pub max_hive_response_bytes: usize,
+}
+
+impl GossipConfig {
+    /// Inbound response caps for the hive behaviour.
+    pub(crate) fn hive_limits(&self) -> HiveLimits {
+        HiveLimits {
+            max_peers_per_response: self.max_hive_peers_per_response,
+            max_response_bytes: self.max_hive_response_bytes,
+        }
+    }
+
+    /// [`Self::exchange_interval`] raised to [`MIN_EXCHANGE_INTERVAL`].
+    pub(crate) fn paced_exchange_interval(&self) -> Duration {
+        self.exchange_interval.max(MIN_EXCHANGE_INTERVAL)
+    }
 }
 
 impl Default for GossipConfig {
@@ -74,10 +134,15 @@ impl Default for GossipConfig {
         Self {
             refresh_interval: Duration::from_secs(600),
             health_check_delay: Duration::from_millis(500),
+            announce_interval: Duration::from_secs(1),
+            exchange_burst: 8,
+            exchange_interval: Duration::from_millis(250),
             record_cooldown: Duration::from_secs(300),
             max_records_per_gossiper: 64,
             max_tracked_gossipers: 1024,
             max_tracked_cooldowns: 8192,
+            max_hive_peers_per_response: MAX_BATCH_SIZE,
+            max_hive_response_bytes: MAX_RESPONSE_BYTES,
         }
     }
 }
@@ -93,9 +158,27 @@ mod tests {
         let config = GossipConfig::default();
         assert_eq!(config.refresh_interval, Duration::from_secs(600));
         assert_eq!(config.health_check_delay, Duration::from_millis(500));
+        assert_eq!(config.announce_interval, Duration::from_secs(1));
+        assert_eq!(config.exchange_burst, 8);
+        assert_eq!(config.exchange_interval, Duration::from_millis(250));
         assert_eq!(config.record_cooldown, Duration::from_secs(300));
         assert_eq!(config.max_records_per_gossiper, 64);
         assert_eq!(config.max_tracked_gossipers, 1024);
         assert_eq!(config.max_tracked_cooldowns, 8192);
+        assert_eq!(config.max_hive_peers_per_response, 30);
+        assert_eq!(config.max_hive_response_bytes, 32 * 1024);
+    }
+
+    #[test]
+    fn exchange_interval_is_floored() {
+        let config = GossipConfig {
+            exchange_interval: Duration::ZERO,
+            ..Default::default()
+        };
+        assert_eq!(config.paced_exchange_interval(), MIN_EXCHANGE_INTERVAL);
+        assert_eq!(
+            GossipConfig::default().paced_exchange_interval(),
+            Duration::from_millis(250)
+        );
     }
 }
@@ -23,20 +23,25 @@
 //!   dials use its per-peer backoff, and unverified entries expire on a
 //!   short failure budget (see the peer manager's stale policy) instead of
 //!   polluting candidate supply.
-//! - Exchange cadence: `refresh_interval` paces neighborhood broadcasts and
+//! - Exchange cadence: `refresh_interval` paces neighborhood broadcasts,
 //!   `health_check_delay` defers exchanges on fresh gossip dials until the
-//!   connection proves stable.
+//!   connection proves stable, and `announce_interval` coalesces new-peer
+//!   announcements so each neighbor gets at most one batch per interval.
+//!   Exchanges with newly activated peers are paced by `exchange_burst` and
+//!   `exchange_interval`, so filling gap bins spreads them over time.
 
+mod coalesce;
 mod config;
 mod error;
 mod events;
 mod filter;
 mod intake;
+mod pace;
 mod tasks;
 
 use tokio::sync::mpsc;
 
-pub use config::GossipConfig;
+pub use config::{GossipConfig, MIN_EXCHANGE_INTERVAL};
 pub(crate) use events::{GossipAction, GossipInput};
 pub(crate) use tasks::{GossipChannels, gossip_channel, spawn_gossip_task};
 
@@ -0,0 +1,149 @@
+//! Pacing of the hive exchanges we start with newly activated peers.
+//!
+//! Each activated storer gets a hive exchange. Filling gap bins activates many
+//! peers at once, and sending every exchange the moment its peer activates
+//! bursts hive streams onto the network. [`ExchangePacer`] is a token bucket
+//! over those exchanges: up to `burst` go out at once, then one more per
+//! `interval`. The rest wait in arrival order, one per peer, and a peer that
+//! disconnects while waiting is dropped.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use libp2p::PeerId;
+use vertex_util_runtime::time::Instant;
+
+/// Token bucket over outbound exchanges, queueing what it cannot send yet.
+pub(super) struct ExchangePacer<T> {
+    /// Exchanges that may go out back to back.
+    burst: usize,
+    /// Time to earn one more exchange.
+    interval: Duration,
+    tokens: usize,
+    last_refill: Instant,
+    /// Exchanges waiting for a token, oldest first.
+    queued: VecDeque<(PeerId, T)>,
+}
+
+impl<T> ExchangePacer<T> {
+    pub(super) fn new(burst: usize, interval: Duration, now: Instant) -> Self {
+        let burst = burst.max(1);
+        Self {
+            burst,
+            interval,
+            tokens: burst,
+            last_refill: now,
+            queued: VecDeque::new(),
+        }
+    }
+
+    /// Earn the tokens due since the last refill. The fraction of an
+    /// interval already elapsed is dropped, which only errs slow.
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.last_refill);
+        let earned = elapsed
+            .as_nanos()
+            .checked_div(self.interval.as_nanos())
+            .map_or(usize::MAX, |n| usize::try_from(n).unwrap_or(usize::MAX));
+        if earned > 0 {
+            self.tokens = self.tokens.saturating_add(earned).min(self.burst);
+            self.last_refill = now;
+        }
+    }
+
+    /// Send `exchange` for `peer` now if the budget allows and nothing is
+    /// waiting ahead of it; otherwise queue it. A peer already waiting keeps
+    /// its place with the newer exchange.
+    pub(super) fn admit(&mut self, now: Instant, peer: PeerId, exchange: T) -> Option<T> {
+        self.refill(now);
+        if let Some((_, queued)) = self.queued.iter_mut().find(|(p, _)| *p == peer) {
+            *queued = exchange;
+            return None;
+        }
+        if self.queued.is_empty() && self.tokens > 0 {
+            self.tokens -= 1;
+            return Some(exchange);
+        }
+        self.queued.push_back((peer, exchange));
+        None
+    }
+
+    /// Release the waiting exchanges the budget now allows, oldest first.
+    pub(super) fn release(&mut self, now: Instant) -> Vec<T> {
+        self.refill(now);
+        let ready = self.tokens.min(self.queued.len());
+        self.tokens -= ready;
+        self.queued
+            .drain(..ready)
+            .map(|(_, exchange)| exchange)
+            .collect()
+    }
+
+    /// Drop the exchange waiting for a peer that disconnected.
+    pub(super) fn forget(&mut self, peer: &PeerId) {
+        self.queued.retain(|(p, _)| p != peer);
+    }
+
+    #[cfg(test)]
+    fn queued(&self) -> usize {
+        self.queued.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_millis(250);
+
+    #[test]
+    fn a_gap_fill_burst_is_capped_and_spread_over_time() {
+        let start = Instant::now();
+        let mut pacer = ExchangePacer::new(4, INTERVAL, start);
+        let peers: Vec<PeerId> = (0..20).map(|_| PeerId::random()).collect();
+
+        let sent_at_once = peers
+            .iter()
+            .enumerate()
+            .filter_map(|(n, peer)| pacer.admit(start, *peer, n))
+            .collect::<Vec<_>>();
+        assert_eq!(sent_at_once, vec![0, 1, 2, 3], "only the burst goes out");
+        assert_eq!(pacer.queued(), 16);
+        assert!(pacer.release(start).is_empty());
+
+        assert_eq!(pacer.release(start + INTERVAL), vec![4]);
+        assert_eq!(
+            pacer.release(start + INTERVAL * 2 + INTERVAL / 2),
+            vec![5],
+            "one exchange per interval"
+        );
+        assert_eq!(
+            pacer.release(start + INTERVAL * 20).len(),
+            4,
+            "an idle stretch earns no more than the burst"
+        );
+        assert_eq!(pacer.queued(), 10);
+
+        // A newcomer queues behind the waiting exchanges.
+        let late = PeerId::random();
+        assert_eq!(pacer.admit(start + INTERVAL * 20, late, 99), None);
+        assert_eq!(pacer.queued(), 11);
+    }
+
+    #[test]
+    fn one_waiting_exchange_per_peer_and_disconnects_are_dropped() {
+        let start = Instant::now();
+        let mut pacer = ExchangePacer::new(1, INTERVAL, start);
+        let (first, waiting, gone) = (PeerId::random(), PeerId::random(), PeerId::random());
+
+        assert_eq!(pacer.admit(start, first, 1), Some(1));
+        assert_eq!(pacer.admit(start, waiting, 2), None);
+        assert_eq!(pacer.admit(start, gone, 3), None);
+        assert_eq!(pacer.admit(start, waiting, 4), None);
+        assert_eq!(pacer.queued(), 2, "a re-activated peer keeps one slot");
+
+        pacer.forget(&gone);
+        assert_eq!(pacer.release(start + INTERVAL), vec![4]);
+        assert!(pacer.release(start + INTERVAL * 5).is_empty());
+    }
+}
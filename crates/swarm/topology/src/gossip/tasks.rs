@@ -10,17 +10,20 @@ use libp2p::PeerId;
 use tokio::sync::mpsc;
 use tracing::{debug, trace};
 use vertex_swarm_api::{SwarmIdentity, SwarmNodeType};
+use vertex_swarm_net_hive::MAX_BATCH_SIZE;
 use vertex_swarm_peer::SwarmPeer;
 use vertex_swarm_peer_manager::PeerManager;
 use vertex_swarm_primitives::{Bin, NeighborhoodDepth, OverlayAddress};
 use vertex_tasks::time::sleep;
 use vertex_util_runtime::time::Instant;
 
+use super::coalesce::AnnouncementCoalescer;
 use super::events::{GossipAction, GossipCheckOk};
 use super::filter::{
     RecipientProfile, detect_depth_decrease, filter_peers_for_recipient, select_peers_for_distant,
 };
 use super::intake::GossipIntake;
+use super::pace::ExchangePacer;
 use super::{GossipConfig, GossipInput};
 use crate::kademlia::RoutingEvaluatorHandle;
 use crate::kademlia::peer_selection;
@@ -72,10 +75,19 @@ struct GossipTask<I: SwarmIdentity> {
     /// Periodic refresh tick; first fire one full period after spawn.
     gossip_tick: vertex_tasks::time::Interval,
 
+    // New-peer announcements, flushed to each neighbor once per announce tick
+    announcements: AnnouncementCoalescer,
+    announce_tick: vertex_tasks::time::Interval,
+
     // Delayed gossip exchange
     pending_exchanges: FuturesUnordered<PendingExchangeFuture>,
     cancelled_exchanges: HashSet<PeerId>,
 
+    // Exchanges with activated storers, paced so a burst of activations is
+    // spread over time
+    exchanges: ExchangePacer<PendingExchange>,
+    exchange_tick: vertex_tasks::time::Interval,
+
     // Triggers routing evaluation after admitting new dialable supply
     evaluator_handle: RoutingEvaluatorHandle,
 }
@@ -90,9 +102,18 @@ impl<I: SwarmIdentity> GossipTask<I> {
                 _ = self.gossip_tick.tick() => {
                     self.on_tick();
                 }
+                _ = self.announce_tick.tick() => {
+                    let actions = self.announcements.flush();
+                    self.emit_actions(actions);
+                }
                 Some(exchange) = self.pending_exchanges.next() => {
                     self.on_exchange_ready(exchange);
                 }
+                _ = self.exchange_tick.tick() => {
+                    for exchange in self.exchanges.release(Instant::now()) {
+                        self.exchange_gossip(&exchange.swarm_peer, exchange.node_type);
+                    }
+                }
                 else => break,
             }
         }
@@ -113,15 +134,21 @@ impl<I: SwarmIdentity> GossipTask<I> {
                     // Gossip dial: delay before exchanging (peer may drop us if bin saturated)
                     self.schedule_exchange(peer_id, swarm_peer, node_type);
                 } else {
-                    // Non-gossip: exchange immediately
-                    self.exchange_gossip(&swarm_peer, node_type);
+                    // Non-gossip: exchange as soon as the pace allows
+                    self.pace_exchange(PendingExchange {
+                        peer_id,
+                        swarm_peer,
+                        node_type,
+                    });
                 }
             }
             GossipInput::ConnectionClosed { peer_id, overlay } => {
                 self.gossip_dial_peers.remove(&peer_id);
                 self.cancelled_exchanges.insert(peer_id);
+                self.exchanges.forget(&peer_id);
                 if let Some(overlay) = &overlay {
                     self.last_broadcast.remove(overlay);
+                    self.announcements.forget(overlay);
                 }
                 let actions = self.check_depth_change();
                 self.emit_actions(actions);
@@ -216,7 +243,24 @@ impl<I: SwarmIdentity> GossipTask<I> {
         if self.cancelled_exchanges.remove(&exchange.peer_id) {
             return; // Peer disconnected while delay was pending
         }
-        self.exchange_gossip(&exchange.swarm_peer, exchange.node_type);
+        self.pace_exchange(exchange);
+    }
+
+    /// Exchange with a storer now if the pace allows, otherwise queue it for
+    /// the exchange tick. Non-storers get no hive exchange, so they are not
+    /// paced.
+    fn pace_exchange(&mut self, exchange: PendingExchange) {
+        if !exchange.node_type.requires_storage() {
+            self.exchange_gossip(&exchange.swarm_peer, exchange.node_type);
+            return;
+        }
+        match self
+            .exchanges
+            .admit(Instant::now(), exchange.peer_id, exchange)
+        {
+            Some(exchange) => self.exchange_gossip(&exchange.swarm_peer, exchange.node_type),
+            None => trace!("Gossip exchange queued behind the exchange pace"),
+        }
     }
 
     fn exchange_gossip(&mut self, swarm_peer: &SwarmPeer, node_type: SwarmNodeType) {
@@ -249,7 +293,7 @@ impl<I: SwarmIdentity> GossipTask<I> {
         let mut actions = Vec::new();
 
         for overlay in self.connection_registry.active_ids() {
-            let proximity = self.local_overlay.proximity(&overlay).get();
+            let proximity = self.peer_manager.index().bin_for(&overlay).get();
 
             if proximity >= new_depth
                 && proximity < old_depth
@@ -280,7 +324,7 @@ impl<I: SwarmIdentity> GossipTask<I> {
         }
 
         let new_peer_overlay = OverlayAddress::from(*peer.overlay());
-        let proximity = self.local_overlay.proximity(&new_peer_overlay).get();
+        let proximity = self.peer_manager.index().bin_for(&new_peer_overlay).get();
 
         if proximity >= depth {
             self.handle_new_neighbor(new_peer_overlay, peer.clone(), depth)
@@ -383,7 +427,8 @@ impl<I: SwarmIdentity> GossipTask<I> {
         let existing_neighbors = self.connected_neighbors();
 
         // Wrap in a single-element slice so filter_peers_for_recipient can borrow
-        // without cloning for each neighbor.
+        // without cloning for each neighbor. Notifications are queued and
+        // coalesced per neighbor until the next announce tick.
         let new_peer_slice = [new_peer_info];
         for neighbor in existing_neighbors {
             if neighbor != new_peer {
@@ -391,12 +436,9 @@ impl<I: SwarmIdentity> GossipTask<I> {
 
                 let filtered = self.filter_for_recipient(&new_peer_slice, &profile);
 
-                if !filtered.is_empty() {
-                    trace!(to = %neighbor, about = %new_peer, "Notifying neighbor about new peer");
-                    actions.push(GossipAction {
-                        to: neighbor,
-                        peers: filtered.into_iter().cloned().collect(),
-                    });
+                for peer in filtered {
+                    trace!(to = %neighbor, about = %new_peer, "Queueing new peer for neighbor");
+                    self.announcements.push(neighbor, peer.clone());
                 }
             }
         }
@@ -527,8 +569,22 @@ pub(crate) fn spawn_gossip_task<I: SwarmIdentity>(
             config.refresh_interval,
             config.refresh_interval,
         ),
+        announcements: AnnouncementCoalescer::new(MAX_BATCH_SIZE),
+        announce_tick: vertex_tasks::time::interval_after(
+            config.announce_interval,
+            config.announce_interval,
+        ),
         pending_exchanges: FuturesUnordered::new(),
         cancelled_exchanges: HashSet::new(),
+        exchanges: ExchangePacer::new(
+            config.exchange_burst,
+            config.paced_exchange_interval(),
+            Instant::now(),
+        ),
+        exchange_tick: vertex_tasks::time::interval_after(
+            config.paced_exchange_interval(),
+            config.paced_exchange_interval(),
+        ),
         evaluator_handle,
     };
 
@@ -1,12 +1,15 @@
 //! Handle for querying and controlling topology state.
 
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 
 use libp2p::{Multiaddr, PeerId};
 use nectar_primitives::{ChunkAddress, NetworkId};
 use tokio::sync::{broadcast, mpsc};
 use vertex_swarm_api::{
-    PeerReporter, SwarmIdentity, SwarmSpec, SwarmTopologyBins, SwarmTopologyCommands,
+    ConnectionAuditRecord, DepthExplanation, DisconnectReason, PeerPinger, PeerReporter,
+    SwarmError, SwarmIdentity, SwarmResult, SwarmSpec, SwarmTopologyBins, SwarmTopologyCommands,
     SwarmTopologyPeers, SwarmTopologyReporting, SwarmTopologyRouting, SwarmTopologyState,
     SwarmTopologyStats,
 };
@@ -14,11 +17,22 @@ use vertex_swarm_net_identify as identify;
 use vertex_swarm_peer_manager::PeerManager;
 use vertex_swarm_primitives::{Bin, NeighborhoodDepth, OverlayAddress, all_bins};
 
+use crate::audit::ConnectionAudit;
 use crate::behaviour::ConnectionRegistry;
+use crate::churn::ChurnTracker;
 use crate::events::TopologyEvent;
 use crate::kademlia::KademliaRouting;
 use crate::readiness::{BinReadiness, ReadinessSnapshot};
-use crate::{TopologyCommand, TopologyError};
+use crate::{PROBE_TIMEOUT, TopologyCommand, TopologyError};
+
+/// How long [`TopologyHandle::ping_peer`] waits for its probe's answer.
+///
+/// Stream negotiation and the echo are each bounded by [`PROBE_TIMEOUT`],
+/// so the behaviour always answers within twice that; the margin covers
+/// the trip through the event loop.
+const PING_WAIT: Duration = PROBE_TIMEOUT
+    .saturating_mul(2)
+    .saturating_add(Duration::from_secs(5));
 
 /// Handle for querying topology state. Cheap to clone.
 pub struct TopologyHandle<I: SwarmIdentity> {
@@ -29,6 +43,11 @@ pub struct TopologyHandle<I: SwarmIdentity> {
     command_tx: mpsc::Sender<TopologyCommand>,
     event_tx: broadcast::Sender<TopologyEvent>,
     agent_versions: identify::AgentVersions,
+    churn: Arc<ChurnTracker>,
+    audit: Arc<ConnectionAudit>,
+    /// Source of probe ids, shared across clones so concurrent pings from
+    /// different handles never collide.
+    next_probe_id: Arc<AtomicU64>,
 }
 
 impl<I: SwarmIdentity> Clone for TopologyHandle<I> {
@@ -41,6 +60,9 @@ impl<I: SwarmIdentity> Clone for TopologyHandle<I> {
             command_tx: self.command_tx.clone(),
             event_tx: self.event_tx.clone(),
             agent_versions: Arc::clone(&self.agent_versions),
+            churn: Arc::clone(&self.churn),
+            audit: Arc::clone(&self.audit),
+            next_probe_id: Arc::clone(&self.next_probe_id),
         }
     }
 }
@@ -54,6 +76,8 @@ impl<I: SwarmIdentity> TopologyHandle<I> {
         command_tx: mpsc::Sender<TopologyCommand>,
         event_tx: broadcast::Sender<TopologyEvent>,
         agent_versions: identify::AgentVersions,
+        churn: Arc<ChurnTracker>,
+        audit: Arc<ConnectionAudit>,
     ) -> Self {
         Self {
             identity,
@@ -63,6 +87,9 @@ impl<I: SwarmIdentity> TopologyHandle<I> {
             command_tx,
             event_tx,
             agent_versions,
+            churn,
+            audit,
+            next_probe_id: Arc::new(AtomicU64::new(0)),
         }
     }
 
@@ -120,6 +147,7 @@ impl<I: SwarmIdentity> TopologyHandle<I> {
             depth,
             neighborhood_connected,
             saturation_threshold,
+            min_storer_neighbors: self.routing.min_storer_neighbors(),
             bins,
             bins_at_target,
             neighborhood_stable_for: self.routing.neighborhood_stable_for(),
@@ -215,8 +243,8 @@ impl<I: SwarmIdentity> TopologyHandle<I> {
     }
 
     /// Resolve once the node is warm for its node type: routable for
-    /// clients and bootnodes, routable plus neighborhood-saturated for
-    /// storers.
+    /// clients and bootnodes, routable plus neighborhood-saturated, with the
+    /// configured neighbor floor, for storers.
     ///
     /// See [`ReadinessSnapshot::is_warm`] for the condition and
     /// [`Self::wait_until`] for the event semantics.
@@ -299,6 +327,13 @@ impl<I: SwarmIdentity> TopologyHandle<I> {
         self.connection_registry.resolve_peer_id(overlay)
     }
 
+    /// [`Self::resolve_peer_id`] for many overlays at once, under a single
+    /// registry lock. Results are positional: entry `i` belongs to
+    /// `overlays[i]`.
+    pub fn resolve_peer_ids(&self, overlays: &[OverlayAddress]) -> Vec<Option<PeerId>> {
+        self.connection_registry.resolve_peer_ids(overlays)
+    }
+
     /// The deepest bin the routing table tracks. The pullsync puller scopes its
     /// neighbourhood bins to this so it never drives ranges for bins the table
     /// cannot hold.
@@ -316,6 +351,98 @@ impl<I: SwarmIdentity> TopologyHandle<I> {
         let peer_id = self.connection_registry.resolve_peer_id(overlay)?;
         self.agent_versions.read().peek(&peer_id).cloned()
     }
+
+    /// Close all connections to `peer`, sending `reason` in a goodbye first.
+    pub async fn disconnect_with_reason(
+        &self,
+        peer: OverlayAddress,
+        reason: DisconnectReason,
+    ) -> Result<(), TopologyError> {
+        self.command_tx
+            .send(TopologyCommand::Disconnect {
+                overlay: peer,
+                reason,
+            })
+            .await
+            .map_err(|_| TopologyError::ServiceShutdown)
+    }
+
+    /// Send one ping to the connected `peer` and wait for its round-trip time.
+    ///
+    /// Resolves only on the answer to this call's own probe; round trips
+    /// from the periodic ping or from other callers are skipped. A disconnect
+    /// while the probe is in flight ends the wait as not connected, and an
+    /// answer lost to a lagging subscription ends it as a timeout after
+    /// [`PING_WAIT`].
+    pub async fn ping_peer(&self, peer: OverlayAddress) -> Result<Duration, TopologyError> {
+        if self.connection_registry.resolve_peer_id(&peer).is_none() {
+            return Err(TopologyError::PeerNotConnected { overlay: peer });
+        }
+
+        let probe_id = self.next_probe_id.fetch_add(1, Ordering::Relaxed);
+        // Subscribe before sending so the answer cannot be missed.
+        let mut events = self.event_tx.subscribe();
+        self.command_tx
+            .send(TopologyCommand::Ping {
+                overlay: peer,
+                probe_id,
+            })
+            .await
+            .map_err(|_| TopologyError::ServiceShutdown)?;
+
+        let answer = async {
+            loop {
+                match events.recv().await {
+                    Ok(TopologyEvent::PingCompleted {
+                        rtt,
+                        probe_id: Some(id),
+                        ..
+                    }) if id == probe_id => {
+                        return Ok(rtt);
+                    }
+                    Ok(TopologyEvent::PingFailed {
+                        overlay,
+                        probe_id: id,
+                        error,
+                    }) if id == probe_id => {
+                        return Err(TopologyError::PingFailed {
+                            overlay,
+                            reason: error,
+                        });
+                    }
+                    Ok(TopologyEvent::PeerDisconnected { overlay, .. }) if overlay == peer => {
+                        return Err(TopologyError::PeerNotConnected { overlay });
+                    }
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => {
+                        return Err(TopologyError::ServiceShutdown);
+                    }
+                }
+            }
+        };
+
+        vertex_tasks::time::timeout(PING_WAIT, answer)
+            .await
+            .unwrap_or_else(|_| {
+                Err(TopologyError::PingFailed {
+                    overlay: peer,
+                    reason: crate::probe::ProbeError::Timeout.to_string(),
+                })
+            })
+    }
+}
+
+#[async_trait::async_trait]
+impl<I: SwarmIdentity> PeerPinger for TopologyHandle<I> {
+    async fn ping(&self, peer: &OverlayAddress) -> SwarmResult<Duration> {
+        self.ping_peer(*peer).await.map_err(|e| match e {
+            TopologyError::PeerNotConnected { overlay } => SwarmError::PeerUnavailable {
+                peer: Some(overlay),
+                reason: "not connected".to_string(),
+            },
+            other => SwarmError::network(other),
+        })
+    }
 }
 
 impl<I: SwarmIdentity> SwarmTopologyBins for TopologyHandle<I> {
@@ -398,6 +525,18 @@ impl<I: SwarmIdentity> SwarmTopologyStats for TopologyHandle<I> {
     fn stored_peers_count(&self) -> usize {
         self.peer_manager.stored_count()
     }
+
+    fn churn_rate(&self) -> f64 {
+        self.churn.churn_rate()
+    }
+
+    fn depth_explanation(&self) -> DepthExplanation {
+        self.routing.depth_explanation()
+    }
+
+    fn connection_audit(&self, limit: usize) -> Vec<ConnectionAuditRecord> {
+        self.audit.recent(limit)
+    }
 }
 
 impl<I: SwarmIdentity> SwarmTopologyCommands for TopologyHandle<I> {
@@ -453,6 +592,10 @@ pub struct RoutingStats {
     pub depth: u8,
     pub known_peers_total: usize,
     pub connected_peers_total: usize,
+    /// Network size estimated from known-peer bin densities.
+    pub estimated_network_size: usize,
+    /// Total connection target sized for `estimated_network_size`.
+    pub target_connections: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -475,7 +618,8 @@ impl<I: SwarmIdentity> TopologyHandle<I> {
     pub fn routing_stats(&self) -> RoutingStats {
         let bin_sizes = self.routing.bin_sizes();
         let bin_phases = self.routing.all_bin_phases();
-        let limits = self.routing.limits();
+        let estimated_network_size = self.routing.estimated_network_size();
+        let limits = self.routing.limits().sized_for(estimated_network_size);
         let depth = self.routing.depth();
 
         let bins: Vec<BinStats> = bin_sizes
@@ -508,6 +652,8 @@ impl<I: SwarmIdentity> TopologyHandle<I> {
             depth: depth.get(),
             known_peers_total: self.routing.known_peers_total(),
             connected_peers_total: self.routing.connected_peers_total(),
+            estimated_network_size,
+            target_connections: limits.total_target(),
         }
     }
 }
@@ -522,7 +668,7 @@ mod tests {
     use crate::kademlia::{KademliaConfig, SwarmRouting};
     use vertex_net_peer_registry::ConnectionDirection;
     use vertex_swarm_peer_manager::{PeerManagerConfig, TrustLevel};
-    use vertex_swarm_primitives::SwarmNodeType;
+    use vertex_swarm_primitives::{PeerCapabilities, SwarmNodeType};
     use vertex_swarm_test_utils::{MockIdentity, test_overlay, test_peer_id, test_swarm_peer};
 
     struct ReadinessHarness {
@@ -530,8 +676,8 @@ mod tests {
         routing: Arc<KademliaRouting<MockIdentity>>,
         peer_manager: Arc<PeerManager<MockIdentity>>,
         event_tx: broadcast::Sender<TopologyEvent>,
-        // Held so handle commands stay sendable; unused by these tests.
-        _command_rx: mpsc::Receiver<TopologyCommand>,
+        // Held so handle commands stay sendable; read by the ping tests.
+        command_rx: mpsc::Receiver<TopologyCommand>,
     }
 
     fn harness(node_type: SwarmNodeType, event_capacity: usize) -> ReadinessHarness {
@@ -552,13 +698,15 @@ mod tests {
             command_tx,
             event_tx.clone(),
             identify::new_agent_versions(),
+            Arc::new(ChurnTracker::new(None)),
+            Arc::new(ConnectionAudit::new(16)),
         );
         ReadinessHarness {
             handle,
             routing,
             peer_manager,
             event_tx,
-            _command_rx: command_rx,
+            command_rx,
         }
     }
 
@@ -583,6 +731,7 @@ mod tests {
                 peer_id: test_peer_id(n),
                 node_type,
                 direction: ConnectionDirection::Outbound,
+                capabilities: PeerCapabilities::NONE,
             });
         }
 
@@ -590,6 +739,7 @@ mod tests {
             let _ = self.event_tx.send(TopologyEvent::PingCompleted {
                 overlay: test_overlay(n),
                 rtt: Duration::from_millis(1),
+                probe_id: None,
             });
         }
     }
@@ -925,4 +1075,86 @@ mod tests {
             .expect("predicate must re-evaluate on PeerDisconnected")
             .expect("wait_until must succeed");
     }
+
+    #[tokio::test]
+    async fn ping_peer_refuses_an_unconnected_overlay() {
+        let h = harness(SwarmNodeType::Client, 16);
+        let overlay = test_overlay(3);
+
+        let error = h
+            .handle
+            .ping_peer(overlay)
+            .await
+            .expect_err("not connected");
+        assert!(matches!(
+            error,
+            TopologyError::PeerNotConnected { overlay: o } if o == overlay
+        ));
+    }
+
+    /// Register `n` as connected so `ping_peer` sends its probe.
+    fn connect(h: &ReadinessHarness, n: u8) {
+        let peer_id = test_peer_id(n);
+        let connection = libp2p::swarm::ConnectionId::new_unchecked(n.into());
+        h.handle
+            .connection_registry
+            .connected_inbound(peer_id, connection);
+        h.handle
+            .connection_registry
+            .activate(peer_id, connection, test_overlay(n));
+    }
+
+    #[tokio::test]
+    async fn ping_peer_waits_for_its_own_probe() {
+        let mut h = harness(SwarmNodeType::Client, 16);
+        connect(&h, 3);
+        let overlay = test_overlay(3);
+
+        let handle = h.handle.clone();
+        let waiter = tokio::spawn(async move { handle.ping_peer(overlay).await });
+        let Some(TopologyCommand::Ping { probe_id, .. }) = h.command_rx.recv().await else {
+            panic!("expected a ping command");
+        };
+
+        // A periodic round trip and another caller's answer do not resolve it.
+        let _ = h.event_tx.send(TopologyEvent::PingCompleted {
+            overlay,
+            rtt: Duration::from_millis(1),
+            probe_id: None,
+        });
+        let _ = h.event_tx.send(TopologyEvent::PingCompleted {
+            overlay,
+            rtt: Duration::from_millis(2),
+            probe_id: Some(probe_id + 1),
+        });
+        let _ = h.event_tx.send(TopologyEvent::PingCompleted {
+            overlay,
+            rtt: Duration::from_millis(3),
+            probe_id: Some(probe_id),
+        });
+
+        let rtt = tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("own probe must resolve the wait")
+            .unwrap()
+            .unwrap();
+        assert_eq!(rtt, Duration::from_millis(3));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn ping_peer_gives_up_without_an_answer() {
+        let mut h = harness(SwarmNodeType::Client, 16);
+        connect(&h, 3);
+        let overlay = test_overlay(3);
+
+        let handle = h.handle.clone();
+        let waiter = tokio::spawn(async move { handle.ping_peer(overlay).await });
+        assert!(h.command_rx.recv().await.is_some());
+
+        let error = waiter.await.unwrap().expect_err("no answer");
+        assert!(matches!(
+            error,
+            TopologyError::PingFailed { overlay: o, .. } if o == overlay
+        ));
+    }
 }
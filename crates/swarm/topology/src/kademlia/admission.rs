@@ -2,7 +2,10 @@
 //!
 //! Delegates to [`KademliaRouting::admission_within_capacity`] with a
 //! direction-aware `extra` count so the in-flight peer is modelled
-//! correctly on both sides of the handshake. Plugs into the handshake
+//! correctly on both sides of the handshake, then consults
+//! [`KademliaRouting::limit_admission`] for the total connection limit.
+//! Static peers skip both checks.
+//! Plugs into the handshake
 //! behaviour through
 //! [`HandshakeBehaviour::with_admission_control`](vertex_swarm_net_handshake::HandshakeBehaviour::with_admission_control).
 
@@ -15,7 +18,8 @@ use vertex_swarm_net_handshake::{
 };
 use vertex_swarm_peer::{SwarmAddress, SwarmNodeType};
 
-use super::KademliaRouting;
+use super::{KademliaRouting, LimitAdmission};
+use crate::static_peers::StaticPeers;
 
 /// Admission gate backed by the kademlia routing table.
 ///
@@ -23,11 +27,21 @@ use super::KademliaRouting;
 /// same source of truth for capacity decisions as the dial planner.
 pub(crate) struct KademliaAdmissionControl<I: SwarmIdentity> {
     routing: Arc<KademliaRouting<I>>,
+    static_peers: Arc<StaticPeers>,
 }
 
 impl<I: SwarmIdentity> KademliaAdmissionControl<I> {
     pub(crate) fn new(routing: Arc<KademliaRouting<I>>) -> Self {
-        Self { routing }
+        Self {
+            routing,
+            static_peers: Arc::default(),
+        }
+    }
+
+    /// Always admit these peers, whatever the bin or connection counts.
+    pub(crate) fn with_static_peers(mut self, static_peers: Arc<StaticPeers>) -> Self {
+        self.static_peers = static_peers;
+        self
     }
 }
 
@@ -38,6 +52,9 @@ impl<I: SwarmIdentity> HandshakeAdmissionControl for KademliaAdmissionControl<I>
         _node_type: SwarmNodeType,
         direction: ConnectionDirection,
     ) -> AdmissionDecision {
+        if self.static_peers.contains(peer_overlay) {
+            return AdmissionDecision::Accept;
+        }
         // Inbound is not yet reserved at gate time; outbound was
         // reserved at dial planning. See
         // `KademliaRouting::admission_within_capacity` for the full
@@ -46,10 +63,14 @@ impl<I: SwarmIdentity> HandshakeAdmissionControl for KademliaAdmissionControl<I>
             ConnectionDirection::Inbound => 1,
             ConnectionDirection::Outbound => 0,
         };
-        if self.routing.admission_within_capacity(peer_overlay, extra) {
-            AdmissionDecision::Accept
-        } else {
-            AdmissionDecision::Reject(AdmissionRejection::Saturated)
+        if !self.routing.admission_within_capacity(peer_overlay, extra) {
+            return AdmissionDecision::Reject(AdmissionRejection::Saturated);
+        }
+        // A displacement is carried out by topology once the handshake
+        // completes; the gate only decides whether one is possible.
+        match self.routing.limit_admission(peer_overlay, extra, |_| ()) {
+            LimitAdmission::Within | LimitAdmission::Displace(_) => AdmissionDecision::Accept,
+            LimitAdmission::Full => AdmissionDecision::Reject(AdmissionRejection::ConnectionLimit),
         }
     }
 }
@@ -58,8 +79,9 @@ impl<I: SwarmIdentity> HandshakeAdmissionControl for KademliaAdmissionControl<I>
 /// [`HandshakeBehaviour::with_admission_control`](vertex_swarm_net_handshake::HandshakeBehaviour::with_admission_control).
 pub(crate) fn kademlia_admission_control<I: SwarmIdentity>(
     routing: Arc<KademliaRouting<I>>,
+    static_peers: Arc<StaticPeers>,
 ) -> SharedAdmissionControl {
-    Arc::new(KademliaAdmissionControl::new(routing))
+    Arc::new(KademliaAdmissionControl::new(routing).with_static_peers(static_peers))
 }
 
 #[cfg(test)]
@@ -138,6 +160,31 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn static_peer_is_admitted_into_a_full_bin() {
+        let base = SwarmAddress::with_first_byte(0x00);
+        let config = KademliaConfig::default()
+            .with_nominal(1)
+            .with_inbound_headroom(0)
+            .with_bootstrap_target(1)
+            .with_oversaturation_peers(1)
+            .with_saturation(1);
+        let routing = make_routing(base, config);
+        RoutingCapacity::reserve_inbound(&*routing, &SwarmAddress::with_first_byte(0xc0));
+
+        let peer = SwarmAddress::with_first_byte(0x80);
+        let addr = format!(
+            "/ip4/203.0.113.10/tcp/1634/p2p/{}",
+            libp2p::PeerId::random()
+        )
+        .parse()
+        .expect("valid");
+        let ac = KademliaAdmissionControl::new(routing)
+            .with_static_peers(Arc::new(StaticPeers::new([(peer, addr)])));
+        let decision = ac.evaluate(&peer, SwarmNodeType::Storer, ConnectionDirection::Inbound);
+        assert!(matches!(decision, AdmissionDecision::Accept));
+    }
+
     #[test]
     fn outbound_accepts_at_ceiling_after_dial_reserve() {
         // Capacity 1. The outbound peer reserved its slot via
@@ -223,11 +270,32 @@ mod tests {
         assert!(matches!(decision, AdmissionDecision::Accept));
     }
 
+    #[test]
+    fn inbound_rejects_at_connection_limit_without_donor_bin() {
+        // One connection allowed and already held. At depth 0 every bin is
+        // a neighborhood bin, so there is no redundant peer to displace.
+        let base = SwarmAddress::with_first_byte(0x00);
+        let config = KademliaConfig::default().with_max_connections(1);
+        let routing = make_routing(base, config);
+        RoutingCapacity::reserve_inbound(&*routing, &SwarmAddress::with_first_byte(0xc0));
+
+        let ac = KademliaAdmissionControl::new(routing);
+        let decision = ac.evaluate(
+            &SwarmAddress::with_first_byte(0x40),
+            SwarmNodeType::Storer,
+            ConnectionDirection::Inbound,
+        );
+        assert!(matches!(
+            decision,
+            AdmissionDecision::Reject(AdmissionRejection::ConnectionLimit)
+        ));
+    }
+
     #[test]
     fn shared_handle_dispatches() {
         let base = SwarmAddress::with_first_byte(0x00);
         let routing = make_routing(base, KademliaConfig::default());
-        let handle = kademlia_admission_control(routing);
+        let handle = kademlia_admission_control(routing, Arc::default());
         let decision = handle.evaluate(
             &SwarmAddress::with_first_byte(0x42),
             SwarmNodeType::Client,
@@ -1,9 +1,11 @@
 //! CLI arguments for Kademlia routing configuration.
 
+use std::time::Duration;
+
 use clap::Args;
 use serde::{Deserialize, Serialize};
 
-use super::{DepthAwareLimits, KademliaConfig};
+use super::{ConnectionStrategy, DepthAwareLimits, KademliaConfig};
 
 /// Kademlia routing CLI arguments.
 ///
@@ -27,6 +29,36 @@ pub struct RoutingArgs {
     #[arg(long = "network.routing.inbound-headroom")]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub inbound_headroom: Option<usize>,
+
+    /// Total connection limit; at the limit a gap-filling peer displaces a
+    /// peer from an oversaturated bin.
+    #[arg(long = "network.routing.max-connections")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_connections: Option<usize>,
+
+    /// Seconds a new connection must stay up before it counts toward depth
+    /// (0 counts it immediately).
+    #[arg(long = "network.routing.depth-warmup-secs")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth_warmup_secs: Option<u64>,
+
+    /// Count only peers scored above this toward depth (unset counts every
+    /// connected peer).
+    #[arg(long = "network.routing.depth-min-score")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth_min_score: Option<f64>,
+
+    /// Connected peers within depth a storer needs before it reports ready.
+    #[arg(long = "network.routing.min-storer-neighbors")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_storer_neighbors: Option<usize>,
+
+    /// How candidates are spread over the bins below depth: `eager` works
+    /// every unsaturated bin each round, `lazy` one bin at a time, `balanced`
+    /// closest bins first.
+    #[arg(long = "network.routing.connection-strategy", value_name = "STRATEGY")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection_strategy: Option<ConnectionStrategy>,
 }
 
 impl RoutingArgs {
@@ -42,6 +74,20 @@ impl RoutingArgs {
             limits = limits.with_inbound_headroom(headroom);
         }
 
-        KademliaConfig { limits, ..defaults }
+        KademliaConfig {
+            limits,
+            max_connections: self.max_connections,
+            depth_warmup: self
+                .depth_warmup_secs
+                .map_or(defaults.depth_warmup, Duration::from_secs),
+            depth_min_score: self.depth_min_score,
+            min_storer_neighbors: self
+                .min_storer_neighbors
+                .unwrap_or(defaults.min_storer_neighbors),
+            connection_strategy: self
+                .connection_strategy
+                .unwrap_or(defaults.connection_strategy),
+            ..defaults
+        }
     }
 }
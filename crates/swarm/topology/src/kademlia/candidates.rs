@@ -8,6 +8,7 @@ use vertex_swarm_primitives::{
     Bin, NeighborhoodDepth, OverlayAddress, balanced_bins, neighborhood_bins,
 };
 
+use super::config::ConnectionStrategy;
 use super::limits::LimitsSnapshot;
 
 /// Captured state for consistent candidate selection.
@@ -218,11 +219,13 @@ pub(crate) fn select_neighborhood_candidates<I: SwarmIdentity>(
     }
 }
 
-/// Select candidates for balanced bins (< depth) using linear tapering.
+/// Select candidates for balanced bins (< depth) using linear tapering,
+/// spread over the unsaturated bins according to `strategy`.
 pub(crate) fn select_balanced_candidates<I: SwarmIdentity>(
     selector: &mut CandidateSelector<'_>,
     peer_manager: &PeerManager<I>,
     connected_counts: impl Fn(Bin) -> usize,
+    strategy: ConnectionStrategy,
 ) {
     let depth = selector.snapshot().limits.depth;
     if depth == NeighborhoodDepth::ZERO {
@@ -260,6 +263,33 @@ pub(crate) fn select_balanced_candidates<I: SwarmIdentity>(
     // candidate supply or saturating bins starve their own refill.
     let connected = selector.connected_index();
 
+    if strategy == ConnectionStrategy::Eager {
+        // Deal one peer per bin per pass so every unsaturated bin gets a
+        // candidate before any bin gets its second.
+        let mut supplies: Vec<_> = bin_stats
+            .into_iter()
+            .map(|(bin, effective, deficit)| {
+                let supply = peer_manager
+                    .dialable_overlays_in_bin_excluding(bin, |overlay| connected.exists(overlay));
+                (bin, effective, deficit, supply)
+            })
+            .collect();
+        while !supplies.is_empty() && !selector.is_full() {
+            supplies.retain_mut(|(bin, effective, deficit, supply)| {
+                if selector.is_full()
+                    || !supply.any(|peer| {
+                        selector.try_add_with_bin_capacity(peer, *bin, *effective, peer_manager)
+                    })
+                {
+                    return false;
+                }
+                *deficit -= 1;
+                *deficit > 0
+            });
+        }
+        return;
+    }
+
     for (bin, effective, deficit) in bin_stats {
         if selector.is_full() {
             break;
@@ -279,6 +309,10 @@ pub(crate) fn select_balanced_candidates<I: SwarmIdentity>(
                 added += 1;
             }
         }
+
+        if strategy == ConnectionStrategy::Lazy && added > 0 {
+            break;
+        }
     }
 }
 
@@ -332,9 +366,12 @@ mod tests {
 
         // Bin 0 holds 5 connected against a saturation-floored target of 8:
         // deficit 3, and exactly two unconnected candidates are known.
-        select_balanced_candidates(&mut selector, &peer_manager, |bin| {
-            if bin == b(0) { 5 } else { 0 }
-        });
+        select_balanced_candidates(
+            &mut selector,
+            &peer_manager,
+            |bin| if bin == b(0) { 5 } else { 0 },
+            ConnectionStrategy::Balanced,
+        );
 
         let candidates = selector.finish();
         assert_eq!(
@@ -350,6 +387,63 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_eager_covers_every_bin_lazy_fills_one_at_a_time() {
+        use vertex_swarm_test_utils::{MockIdentity, make_swarm_peer_minimal};
+
+        let identity = MockIdentity::with_first_byte(0x00);
+        let peer_manager = PeerManager::new(&identity, PeerManagerConfig::default());
+        // Three known peers in each of bins 0..=3 (first bytes 0x80, 0x40,
+        // 0x20, 0x10), none connected.
+        for base in [0x80u8, 0x40, 0x20, 0x10] {
+            for i in 0..3u8 {
+                peer_manager.store_discovered_peer(make_swarm_peer_minimal(base + i));
+            }
+        }
+        let connected = test_proximity_index();
+        let limits = DepthAwareLimits::new(160, 3).with_saturation(8);
+
+        // One round against depth 4 with a budget of 4, every bin below
+        // depth short of its target; returns the bins the candidates hit.
+        let round = |strategy, queued: &HashSet<OverlayAddress>| {
+            let snapshot = CandidateSnapshot {
+                limits: LimitsSnapshot::capture(&limits, d(4)),
+                in_progress: HashSet::new(),
+                queued: queued.clone(),
+            };
+            let mut selector = CandidateSelector::new(&snapshot, &connected, 4);
+            select_balanced_candidates(&mut selector, &peer_manager, |_| 0, strategy);
+            selector.finish()
+        };
+        let bins = |candidates: &[OverlayAddress]| -> Vec<u8> {
+            let mut bins: Vec<u8> = candidates
+                .iter()
+                .map(|c| OverlayAddress::from([0u8; 32]).proximity(c).get())
+                .collect();
+            bins.sort_unstable();
+            bins.dedup();
+            bins
+        };
+
+        let eager = round(ConnectionStrategy::Eager, &HashSet::new());
+        assert_eq!(eager.len(), 4);
+        assert_eq!(bins(&eager), vec![0, 1, 2, 3], "one candidate per bin");
+
+        let balanced = round(ConnectionStrategy::Balanced, &HashSet::new());
+        assert_eq!(bins(&balanced), vec![2, 3], "closest bins first");
+
+        // Lazy works one bin per round; the next round moves on once the
+        // first bin's supply is queued.
+        let mut queued = HashSet::new();
+        for expected in [3, 2, 1, 0] {
+            let lazy = round(ConnectionStrategy::Lazy, &queued);
+            assert_eq!(lazy.len(), 3);
+            assert_eq!(bins(&lazy), vec![expected]);
+            queued.extend(lazy);
+        }
+        assert!(round(ConnectionStrategy::Lazy, &queued).is_empty());
+    }
+
     #[test]
     fn test_snapshot_eligibility() {
         use vertex_swarm_test_utils::{MockIdentity, make_swarm_peer_minimal};
@@ -13,8 +13,15 @@
 //! resolves them from the node's connection profile (see `crate::profile`)
 //! at behaviour construction, with the defaults here matching the Balanced
 //! profile.
+//!
+//! [`ConnectionStrategy`] is orthogonal to the budget: it decides how a round's
+//! balanced share is spread over the unsaturated bins below depth, not how
+//! large that share is.
+
+use std::{sync::Arc, time::Duration};
 
-use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use vertex_swarm_primitives::{DistanceMetric, XorDistance};
 
 use super::limits::DepthAwareLimits;
 
@@ -42,6 +49,66 @@ const DEFAULT_NEIGHBORHOOD_STABILITY_WINDOW: Duration = Duration::from_secs(30);
 /// (slow) capacity loss go unreported for long.
 const DEFAULT_DEPTH_LOWER_WINDOW: Duration = Duration::from_secs(30);
 
+/// Default in-flight timeout for dials into a neighborhood (depth) bin.
+///
+/// Neighbors are the connections a node cannot do without, so a slow dial
+/// is worth waiting out.
+const DEFAULT_NEIGHBOR_DIAL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default in-flight timeout for dials into a balanced (non-depth) bin.
+///
+/// Shallow bins have plenty of interchangeable candidates; a dial that has
+/// not connected by now frees its slot for the next one.
+const DEFAULT_BALANCED_DIAL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default time a new connection must survive before it counts toward depth.
+///
+/// Zero: every connected peer counts immediately. A node seeing depth flap on
+/// peers that connect and drop straight away sets this with
+/// [`KademliaConfig::with_depth_warmup`].
+const DEFAULT_DEPTH_WARMUP: Duration = Duration::ZERO;
+
+/// Default neighbor floor a storer needs on top of saturation before it
+/// reports warm.
+///
+/// Zero: saturation alone makes a storer warm. An operator whose replication
+/// needs a larger neighborhood raises it with
+/// [`KademliaConfig::with_min_storer_neighbors`].
+const DEFAULT_MIN_STORER_NEIGHBORS: usize = 0;
+
+/// How a connection-evaluation round spreads its balanced-bin candidates.
+///
+/// Only the bins below depth are affected; the neighborhood is always filled
+/// as fast as the budget allows.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    strum::Display,
+    strum::EnumString,
+    strum::IntoStaticStr,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum ConnectionStrategy {
+    /// Every unsaturated bin gets candidates in the same round: the budget is
+    /// dealt out one peer per bin at a time until it or the deficits run out.
+    /// Fastest route coverage, at the cost of more concurrent dials.
+    Eager,
+    /// Only the closest unsaturated bin that has dialable supply is worked
+    /// per round; shallower bins wait for later rounds.
+    Lazy,
+    /// Bins are filled closest first, each up to its full deficit, until the
+    /// budget runs out.
+    #[default]
+    Balanced,
+}
+
 /// Configuration for Kademlia routing.
 #[derive(Debug, Clone)]
 pub struct KademliaConfig {
@@ -58,12 +125,38 @@ pub struct KademliaConfig {
     /// the saturation deficit is a single peer (see
     /// [`Self::with_depth_lower_window`]).
     pub(crate) depth_lower_window: Duration,
+    /// How long a new connection must stay up before it counts toward depth
+    /// (see [`Self::with_depth_warmup`]).
+    pub(crate) depth_warmup: Duration,
+    /// Score a connected peer must exceed to count toward depth; `None`
+    /// counts every connected peer (see [`Self::with_quality_weighted_depth`]).
+    pub(crate) depth_min_score: Option<f64>,
+    /// Connected peers a storer needs within depth before it reports warm
+    /// (see [`Self::with_min_storer_neighbors`]).
+    pub(crate) min_storer_neighbors: usize,
     /// How long the neighborhood depth must hold still (with a saturated
     /// neighborhood) before the topology phase machine reports
     /// [`super::TopologyPhase::Stable`]. Any depth movement inside the
     /// window keeps the node in `Converging`, so churn cannot flap the
     /// phase. Default 60s.
     pub(crate) phase_stability_window: Duration,
+    /// In-flight timeout for dials into neighborhood bins
+    /// (see [`Self::with_dial_timeouts`]).
+    pub(crate) neighbor_dial_timeout: Duration,
+    /// In-flight timeout for dials into balanced bins
+    /// (see [`Self::with_dial_timeouts`]).
+    pub(crate) balanced_dial_timeout: Duration,
+    /// Total connection limit across all bins. At the limit a peer filling a
+    /// gap bin displaces a redundant peer from an oversaturated bin; any other
+    /// peer is refused. `None` leaves the total bounded only by the per-bin
+    /// ceilings.
+    pub(crate) max_connections: Option<usize>,
+    /// Metric behind bin assignment, depth and closest-peer ranking
+    /// (see [`Self::with_distance_metric`]).
+    pub(crate) distance_metric: Arc<dyn DistanceMetric>,
+    /// Spread of balanced-bin candidates per round
+    /// (see [`Self::with_connection_strategy`]).
+    pub(crate) connection_strategy: ConnectionStrategy,
 }
 
 impl Default for KademliaConfig {
@@ -74,7 +167,15 @@ impl Default for KademliaConfig {
             max_balanced_candidates: DEFAULT_MAX_BALANCED_CANDIDATES,
             neighborhood_stability_window: DEFAULT_NEIGHBORHOOD_STABILITY_WINDOW,
             depth_lower_window: DEFAULT_DEPTH_LOWER_WINDOW,
+            depth_warmup: DEFAULT_DEPTH_WARMUP,
+            depth_min_score: None,
+            min_storer_neighbors: DEFAULT_MIN_STORER_NEIGHBORS,
             phase_stability_window: DEFAULT_PHASE_STABILITY_WINDOW,
+            neighbor_dial_timeout: DEFAULT_NEIGHBOR_DIAL_TIMEOUT,
+            balanced_dial_timeout: DEFAULT_BALANCED_DIAL_TIMEOUT,
+            max_connections: None,
+            distance_metric: Arc::new(XorDistance),
+            connection_strategy: ConnectionStrategy::default(),
         }
     }
 }
@@ -125,6 +226,45 @@ impl KademliaConfig {
         self
     }
 
+    /// Set how long a newly connected peer must stay connected before it
+    /// counts toward neighborhood depth.
+    ///
+    /// A peer that connects and drops straight away would otherwise move
+    /// depth twice. During the warmup the peer is connected and usable but
+    /// left out of the depth calculation; a disconnect inside the warmup
+    /// never touches depth. The periodic tick folds warmed-up peers in.
+    pub fn with_depth_warmup(mut self, warmup: Duration) -> Self {
+        self.depth_warmup = warmup;
+        self
+    }
+
+    /// Count only connected peers scored above `min_score` toward
+    /// neighborhood depth.
+    ///
+    /// By default every connected peer counts, so a bin filled with flaky
+    /// peers saturates just like one filled with reliable ones and depth
+    /// can advance on peers that will not hold the neighborhood. In the
+    /// weighted mode a peer at or below `min_score`, or one the peer manager
+    /// has no score for, stays connected and usable but is left out of both
+    /// saturation and the low watermark. Scores move without connection
+    /// events, so the periodic tick folds a peer in once it earns its place.
+    pub fn with_quality_weighted_depth(mut self, min_score: f64) -> Self {
+        self.depth_min_score = Some(min_score);
+        self
+    }
+
+    /// Set how many connected peers a storer needs within depth before
+    /// `ReadinessSnapshot::is_warm` reports true.
+    ///
+    /// Saturation only asks for the spec's per-bin target; a storer that
+    /// replicates to its neighbors is not ready to take traffic until the
+    /// neighborhood is big enough to replicate into. Peers in shallower bins
+    /// never count. No effect on other node types.
+    pub fn with_min_storer_neighbors(mut self, neighbors: usize) -> Self {
+        self.min_storer_neighbors = neighbors;
+        self
+    }
+
     /// Set the phase-machine stability window: how long depth must hold
     /// still, with a saturated neighborhood, before the topology phase
     /// reports `Stable`.
@@ -133,6 +273,48 @@ impl KademliaConfig {
         self
     }
 
+    /// Set how long a dial may stay in flight before it is given up, for
+    /// neighborhood-bin and balanced-bin peers respectively.
+    ///
+    /// The bin is judged against the published depth when the dial starts.
+    /// A neighbor dial is worth more patience than a shallow-bin one: the
+    /// neighborhood has few candidates and every one of them matters, while
+    /// a stalled shallow dial only holds a slot another candidate could use.
+    pub fn with_dial_timeouts(mut self, neighbor: Duration, balanced: Duration) -> Self {
+        self.neighbor_dial_timeout = neighbor;
+        self.balanced_dial_timeout = balanced;
+        self
+    }
+
+    /// Set the total connection limit enforced on admission.
+    pub fn with_max_connections(mut self, limit: usize) -> Self {
+        self.max_connections = Some(limit);
+        self
+    }
+
+    /// Replace the XOR overlay distance with `metric` for research and
+    /// simulation.
+    ///
+    /// The routing table assigns bins, computes depth and ranks the peers
+    /// closest to an address through the metric, and the topology builder
+    /// hands it to the peer manager so known peers are binned the same way.
+    /// Remote peers still bin us by XOR, so a node on a custom metric is only
+    /// useful in a network of nodes running the same one.
+    pub fn with_distance_metric(mut self, metric: Arc<dyn DistanceMetric>) -> Self {
+        self.distance_metric = metric;
+        self
+    }
+
+    /// Set how each round spreads its candidates over the bins below depth.
+    ///
+    /// `Eager` suits operators who want routing to every bin quickly and can
+    /// afford the dials; `Lazy` conserves resources by filling one bin at a
+    /// time. The per-round budget and the per-bin targets still apply.
+    pub fn with_connection_strategy(mut self, strategy: ConnectionStrategy) -> Self {
+        self.connection_strategy = strategy;
+        self
+    }
+
     /// Set the per-bin bootstrap fill target used while `depth == 0`
     /// (production threads it from the connection profile).
     pub(crate) fn with_bootstrap_target(mut self, target: usize) -> Self {
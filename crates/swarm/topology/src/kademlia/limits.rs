@@ -284,6 +284,50 @@ impl DepthAwareLimits {
         NeighborhoodDepth::ZERO
     }
 
+    /// Estimate the network size from known-peer bin densities.
+    ///
+    /// Overlays are uniform, so bin `i` holds about `N / 2^(i + 1)` of the
+    /// network. Each bin with at least `nominal` known peers extrapolates to a
+    /// size; the largest wins, since shallow bins are sampled sparsely by
+    /// discovery and only ever under-count. Never below the known total.
+    pub(crate) fn estimate_network_size(&self, known_bin_sizes: &[usize]) -> usize {
+        let known: usize = known_bin_sizes.iter().sum();
+        known_bin_sizes
+            .iter()
+            .enumerate()
+            .filter(|&(_, &count)| count >= self.nominal.max(1))
+            .map(|(idx, &count)| count.saturating_mul(1usize << (idx + 1).min(32)))
+            .fold(known, usize::max)
+    }
+
+    /// Total connection target for a network of `network_size` peers.
+    ///
+    /// Keeps each balanced bin up to the expected depth at saturation plus
+    /// the whole neighbourhood, and never less than the configured total. A
+    /// network smaller than that is connected to in full. `0` (no estimate
+    /// yet) leaves the configured total unchanged.
+    pub(crate) fn total_target_for(&self, network_size: usize) -> usize {
+        if network_size == 0 {
+            return self.total_target;
+        }
+        let saturation = self.saturation.max(1);
+        let depth = (network_size / saturation)
+            .checked_ilog2()
+            .unwrap_or(0)
+            .min(u32::from(Bin::MAX.get()));
+        let neighborhood = network_size >> depth;
+        let needed = saturation
+            .saturating_mul(depth as usize)
+            .saturating_add(neighborhood);
+        needed.max(self.total_target).min(network_size)
+    }
+
+    /// These limits with the total target sized for `network_size` peers.
+    pub(crate) fn sized_for(&self, network_size: usize) -> Self {
+        self.clone()
+            .with_total_target(self.total_target_for(network_size))
+    }
+
     /// Effective depth: max(connected_depth, estimated_depth) for bootstrap.
     pub(crate) fn effective_depth(
         &self,
@@ -906,4 +950,33 @@ mod tests {
         // Neighborhood
         assert_eq!(snapshot.surplus(b(8), 1000), 0);
     }
+
+    /// Bin populations of an `n`-peer network with uniform overlays.
+    fn uniform_bins(n: usize) -> Vec<usize> {
+        (0..32).map(|i| n >> (i + 1)).collect()
+    }
+
+    #[test]
+    fn network_size_sets_connection_target() {
+        let limits = DepthAwareLimits::new(160, 3);
+
+        // A 24-peer network is connected to in full.
+        let small = limits.estimate_network_size(&[10, 5, 3, 1]);
+        assert_eq!(small, 24);
+        assert_eq!(limits.total_target_for(small), small);
+
+        // A thousand peers: the configured total already covers depth 7.
+        let medium = limits.estimate_network_size(&uniform_bins(1000));
+        assert!((992..=1024).contains(&medium), "estimate {medium}");
+        assert_eq!(limits.total_target_for(medium), 160);
+
+        // 2^24 peers: depth 21 needs 21 saturated bins plus the neighbourhood.
+        let large = limits.estimate_network_size(&uniform_bins(1 << 24));
+        assert_eq!(large, 1 << 24);
+        assert_eq!(limits.total_target_for(large), 8 * 21 + 8);
+
+        // No estimate keeps the configured total.
+        assert_eq!(limits.total_target_for(0), 160);
+        assert_eq!(limits.sized_for(small).total_target(), small);
+    }
 }
@@ -17,14 +17,14 @@ pub(crate) use candidates::{
     CandidateSelector, CandidateSnapshot, select_balanced_candidates,
     select_neighborhood_candidates,
 };
-pub use config::KademliaConfig;
+pub use config::{ConnectionStrategy, KademliaConfig};
 pub(crate) use config::{DEFAULT_MAX_BALANCED_CANDIDATES, DEFAULT_MAX_NEIGHBOR_CANDIDATES};
 pub(crate) use limits::DEFAULT_BOOTSTRAP_TARGET;
 pub(crate) use limits::DepthAwareLimits;
 pub(crate) use limits::LimitsSnapshot;
 pub use phase::TopologyPhase;
 pub(crate) use phase::{PhaseTracker, PhaseTransition};
-pub(crate) use routing::KademliaRouting;
+pub(crate) use routing::{KademliaRouting, LimitAdmission};
 pub(crate) use task::{RoutingEvaluatorHandle, spawn_evaluator};
 
 use vertex_swarm_api::SwarmIdentity;
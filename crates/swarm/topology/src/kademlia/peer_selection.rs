@@ -15,9 +15,10 @@ const MAX_PEERS_FOR_DISTANT: usize = 16;
 /// Number of peers close to recipient's overlay to include.
 const CLOSE_PEERS_COUNT: usize = 4;
 
-/// Active storer peers with proximity >= depth.
+/// Active storer peers with proximity >= depth, binned under the peer
+/// manager's distance metric.
 pub(crate) fn connected_neighbors<I: SwarmIdentity>(
-    local_overlay: &OverlayAddress,
+    _local_overlay: &OverlayAddress,
     peer_manager: &PeerManager<I>,
     connection_registry: &ConnectionRegistry,
     depth: NeighborhoodDepth,
@@ -26,7 +27,7 @@ pub(crate) fn connected_neighbors<I: SwarmIdentity>(
         .active_ids()
         .into_iter()
         .filter(|overlay| {
-            depth.contains(Bin::from(local_overlay.proximity(overlay)))
+            depth.contains(peer_manager.index().bin_for(overlay))
                 && peer_manager.node_type(overlay) == Some(SwarmNodeType::Storer)
         })
         .collect()
@@ -54,9 +55,10 @@ pub(crate) fn known_neighborhood_peers<I: SwarmIdentity>(
 
 /// 3-phase distant selection: close-to-recipient + per-bin + fill.
 ///
-/// Returns unfiltered peers with their bin; caller applies IP/scope filtering.
+/// Proximities come from the peer manager's distance metric. Returns
+/// unfiltered peers with their bin; caller applies IP/scope filtering.
 pub(crate) fn select_for_distant<I: SwarmIdentity>(
-    local_overlay: &OverlayAddress,
+    _local_overlay: &OverlayAddress,
     peer_manager: &PeerManager<I>,
     recipient: OverlayAddress,
 ) -> Vec<(SwarmPeer, u8)> {
@@ -65,13 +67,14 @@ pub(crate) fn select_for_distant<I: SwarmIdentity>(
     let mut added_bins: HashSet<u8> = HashSet::new();
 
     let all_storers = peer_manager.known_storer_overlays();
+    let index = peer_manager.index();
 
     let storers: Vec<_> = all_storers
         .iter()
         .filter_map(|overlay| {
             let peer = peer_manager.get_swarm_peer(overlay)?;
-            let proximity_to_recipient = recipient.proximity(overlay).get();
-            let bin = local_overlay.proximity(overlay).get();
+            let proximity_to_recipient = index.metric().proximity(&recipient, overlay).get();
+            let bin = index.bin_for(overlay).get();
             Some((peer, proximity_to_recipient, bin))
         })
         .collect();
@@ -131,9 +134,22 @@ pub(crate) fn select_for_distant<I: SwarmIdentity>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Arc;
+
     use crate::test_support::TopologyTestContext;
+    use vertex_swarm_primitives::{DistanceMetric, ProximityOrder};
     use vertex_swarm_test_utils::test_overlay;
 
+    /// Puts every pair of addresses at proximity order 5.
+    #[derive(Debug)]
+    struct FixedDistance;
+
+    impl DistanceMetric for FixedDistance {
+        fn proximity(&self, _a: &OverlayAddress, _b: &OverlayAddress) -> ProximityOrder {
+            ProximityOrder::new(5).expect("valid proximity order")
+        }
+    }
+
     #[test]
     fn test_connected_neighbors_empty_when_no_connections() {
         let ctx = TopologyTestContext::new().with_peers();
@@ -156,4 +172,16 @@ mod tests {
         let unique: HashSet<_> = selected.iter().map(|(p, _)| *p.overlay()).collect();
         assert_eq!(unique.len(), selected.len());
     }
+
+    #[test]
+    fn select_for_distant_bins_with_the_configured_metric() {
+        // XOR would spread peers 1..=10 across bins 4 to 7.
+        let ctx = TopologyTestContext::with_metric(Arc::new(FixedDistance)).with_peers();
+
+        let selected =
+            select_for_distant(&ctx.local_overlay, &ctx.peer_manager, test_overlay(0xFF));
+
+        assert!(!selected.is_empty());
+        assert!(selected.iter().all(|(_, bin)| *bin == 5));
+    }
 }
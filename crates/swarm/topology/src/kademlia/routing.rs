@@ -18,11 +18,11 @@ use crate::metrics::{phase, record_phase_transition, record_topology_phase_chang
 use nectar_primitives::{ChunkAddress, recompute_neighborhood_depth};
 use parking_lot::{Mutex, RwLock};
 use tracing::{debug, info, trace};
-use vertex_swarm_api::{SwarmIdentity, SwarmSpec};
-use vertex_swarm_peer_manager::{PeerManager, ProximityIndex};
+use vertex_swarm_api::{DepthExplanation, DepthLimit, SwarmIdentity, SwarmSpec};
+use vertex_swarm_peer_manager::{PeerManager, ProximityIndex, TrustLevel};
 use vertex_swarm_primitives::{
-    Bin, NeighborhoodDepth, OverlayAddress, ProximityOrder, SwarmNodeType, all_bins, balanced_bins,
-    neighborhood_bins,
+    Bin, DistanceMetric, NeighborhoodDepth, OverlayAddress, ProximityOrder, SwarmNodeType,
+    all_bins, balanced_bins, neighborhood_bins,
 };
 // The neighborhood stability clock is the timer-coherent monotonic clock from
 // `vertex_tasks::time` on both targets.
@@ -51,6 +51,18 @@ pub(crate) struct EvictionCandidate {
     pub(crate) phase: EvictionPhase,
 }
 
+/// Outcome of the total connection-limit check for an in-flight peer.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum LimitAdmission {
+    /// Below the limit, or no limit configured.
+    Within,
+    /// At the limit, but the peer fills a gap bin: admit it and evict this
+    /// redundant peer from an oversaturated bin.
+    Displace(OverlayAddress),
+    /// At the limit and the peer is not worth displacing anyone for.
+    Full,
+}
+
 /// The saturated-neighborhood state the stability clock is anchored to.
 ///
 /// Held while the neighborhood (bins at and above `depth`) collectively
@@ -98,12 +110,14 @@ fn make_atomic_vec(n: usize) -> Vec<AtomicUsize> {
 /// longest prefix (highest [`ProximityOrder`]) with any other remaining peer,
 /// so the retained set stays spread across the bin's sub-tries - the same
 /// balance goal candidate selection pursues when filling the bin. A residual
-/// tie falls back to overlay order so selection is deterministic.
+/// tie falls back to overlay order so selection is deterministic. Shared
+/// prefixes are measured with the routing table's [`DistanceMetric`].
 ///
 /// Greedy and incremental: one victim per round, with redundancy recomputed
 /// against the survivors, O(count * n^2) for a bin of `n` peers. Bins hold at
 /// most a few dozen peers, so the quadratic term is negligible.
 fn select_trim_victims<R: Ord>(
+    metric: &dyn DistanceMetric,
     mut pool: Vec<(OverlayAddress, R, f64)>,
     count: usize,
 ) -> Vec<OverlayAddress> {
@@ -132,7 +146,7 @@ fn select_trim_victims<R: Ord>(
             .map(|(overlay, _, _)| {
                 pool.iter()
                     .filter(|(other, _, _)| other != overlay)
-                    .map(|(other, _, _)| overlay.proximity(other))
+                    .map(|(other, _, _)| metric.proximity(overlay, other))
                     .max()
             })
             .collect();
@@ -176,6 +190,11 @@ pub(crate) struct KademliaRouting<I: SwarmIdentity> {
     /// published depth with a saturation deficit within
     /// [`DEPTH_LOWER_DEFICIT_TOLERANCE`].
     pending_depth_lower: Mutex<Option<Instant>>,
+    /// Connected peers still inside the depth warmup, keyed to their connect
+    /// time. Left out of the depth calculation until
+    /// [`KademliaConfig::with_depth_warmup`] has elapsed; empty when the
+    /// warmup is zero.
+    warming: Mutex<HashMap<OverlayAddress, Instant>>,
     config: KademliaConfig,
     candidate_queues: CandidateQueues,
     dialing_counts: Vec<AtomicUsize>,
@@ -217,10 +236,12 @@ impl<I: SwarmIdentity> KademliaRouting<I> {
             identity,
             max_po,
             // connected_peers is unbounded (controlled by routing capacity)
-            connected_peers: ProximityIndex::new(local_overlay, max_po, 0),
+            connected_peers: ProximityIndex::new(local_overlay, max_po, 0)
+                .with_metric(config.distance_metric.clone()),
             peer_manager,
             depth: AtomicU8::new(0),
             pending_depth_lower: Mutex::new(None),
+            warming: Mutex::new(HashMap::new()),
             config,
             candidate_queues: CandidateQueues::new(num_bins, queue_cap),
             dialing_counts: make_atomic_vec(num_bins),
@@ -263,8 +284,8 @@ impl<I: SwarmIdentity> KademliaRouting<I> {
     /// Returns `true` when the bin would still be within `ceiling`
     /// (target plus headroom) after counting the in-flight peer. Bins
     /// inside the neighborhood (where `ceiling == usize::MAX`) always
-    /// return `true`; eviction in oversaturated neighborhoods is a
-    /// separate concern handled by a future `AcceptEvict` decision.
+    /// return `true`. The total connection limit is checked separately by
+    /// [`Self::limit_admission`].
     pub(crate) fn admission_within_capacity(&self, overlay: &OverlayAddress, extra: usize) -> bool {
         let bin = self.bin_for(overlay);
         let depth = self.depth();
@@ -300,9 +321,34 @@ impl<I: SwarmIdentity> KademliaRouting<I> {
     }
 
     /// The [`Bin`] a peer occupies in this node's table (its proximity
-    /// order to the local overlay, capped at `max_po`).
-    fn bin_for(&self, peer: &OverlayAddress) -> Bin {
-        Bin::new(self.base().proximity(peer).get().min(self.max_po)).unwrap_or(Bin::MAX)
+    /// order to the local overlay under the configured metric, capped at
+    /// `max_po`).
+    pub(crate) fn bin_for(&self, peer: &OverlayAddress) -> Bin {
+        let proximity = self.config.distance_metric.proximity(&self.base(), peer);
+        Bin::new(proximity.get().min(self.max_po)).unwrap_or(Bin::MAX)
+    }
+
+    /// In-flight timeout for a dial to `peer`: the longer neighbor timeout
+    /// when the peer falls within the published depth, the shorter balanced
+    /// one otherwise (see [`KademliaConfig::with_dial_timeouts`]).
+    pub(crate) fn dial_timeout(&self, peer: &OverlayAddress) -> Duration {
+        if self.depth().contains(self.bin_for(peer)) {
+            self.config.neighbor_dial_timeout
+        } else {
+            self.config.balanced_dial_timeout
+        }
+    }
+
+    /// Network size estimated from known-peer bin densities.
+    pub(crate) fn estimated_network_size(&self) -> usize {
+        self.config
+            .limits
+            .estimate_network_size(&self.peer_manager.index().bin_sizes())
+    }
+
+    /// Limits with the total target sized for the estimated network.
+    fn sized_limits(&self) -> DepthAwareLimits {
+        self.config.limits.sized_for(self.estimated_network_size())
     }
 
     /// Capture state for candidate selection (lightweight: banned/backoff checked live).
@@ -318,7 +364,7 @@ impl<I: SwarmIdentity> KademliaRouting<I> {
         .collect();
 
         CandidateSnapshot {
-            limits: LimitsSnapshot::capture(&self.config.limits, effective_depth),
+            limits: LimitsSnapshot::capture(&self.sized_limits(), effective_depth),
             in_progress,
             queued: queued_set,
         }
@@ -348,6 +394,19 @@ impl<I: SwarmIdentity> KademliaRouting<I> {
         NeighborhoodDepth::new(Bin::new(depth.get().min(self.max_po)).unwrap_or(Bin::MAX))
     }
 
+    /// Break down the depth the current table computes: per-bin connected
+    /// counts (as fed to [`Self::recalc_depth`], warming and unreliable peers
+    /// excluded) and the rule that holds depth where it is.
+    pub(crate) fn depth_explanation(&self) -> DepthExplanation {
+        let sizes = self.depth_bin_sizes(Instant::now());
+        DepthExplanation::new(
+            &sizes,
+            u8::try_from(self.config.limits.saturation()).unwrap_or(u8::MAX),
+            self.identity.spec().neighborhood_low_watermark(),
+            self.max_bin(),
+        )
+    }
+
     /// Total saturation deficit (in peers) across the bins below `depth`,
     /// computed from the given connected-peer bin sizes.
     ///
@@ -390,7 +449,7 @@ impl<I: SwarmIdentity> KademliaRouting<I> {
     fn publish_depth_at(&self, now: Instant) -> bool {
         // One snapshot feeds both the depth recompute and the deficit so the
         // two can never disagree about the table state.
-        let sizes = self.connected_peers.bin_sizes();
+        let sizes = self.depth_bin_sizes(now);
         let raw = self.recalc_depth(&sizes);
         let published = self.depth();
 
@@ -423,13 +482,47 @@ impl<I: SwarmIdentity> KademliaRouting<I> {
         }
     }
 
+    /// Connected-peer counts per bin that count toward depth at `now`.
+    ///
+    /// Peers whose warmup has elapsed are promoted first; the rest are
+    /// subtracted from their bin. Under
+    /// [`KademliaConfig::with_quality_weighted_depth`] warmed-up peers not
+    /// scored above the threshold are subtracted too.
+    fn depth_bin_sizes(&self, now: Instant) -> Vec<usize> {
+        let mut sizes = self.connected_peers.bin_sizes();
+        let mut warming = self.warming.lock();
+        warming.retain(|_, since| now.duration_since(*since) < self.config.depth_warmup);
+        let unreliable = self
+            .config
+            .depth_min_score
+            .map_or_else(Vec::new, |min_score| {
+                self.connected_peers
+                    .all_peers()
+                    .into_iter()
+                    .filter(|peer| !warming.contains_key(peer))
+                    .filter(|peer| {
+                        self.peer_manager
+                            .get_peer_score(peer)
+                            .is_none_or(|score| score <= min_score)
+                    })
+                    .collect()
+            });
+        for peer in warming.keys().chain(&unreliable) {
+            if let Some(size) = sizes.get_mut(self.bin_for(peer).as_index()) {
+                *size = size.saturating_sub(1);
+            }
+        }
+        sizes
+    }
+
     /// Re-run the depth hysteresis against the current table.
     ///
-    /// Called from the topology behaviour's periodic tick so a pending
-    /// lower publishes once its stability window expires even when no
-    /// further connect or disconnect events arrive. The caller observes a
-    /// resulting change by comparing [`Self::depth`] before and after, the
-    /// same pattern the connection handlers use.
+    /// Called from the topology behaviour's periodic tick so a pending lower
+    /// publishes once its stability window expires, and a warmed-up or newly
+    /// reliable peer starts counting, even when no further connect or
+    /// disconnect events arrive. The caller observes a resulting change by
+    /// comparing [`Self::depth`] before and after, the same pattern the
+    /// connection handlers use.
     ///
     /// When the tick publishes a new depth there is no connect or disconnect
     /// to re-anchor the neighborhood-stability clock, so this path re-anchors
@@ -488,11 +581,29 @@ impl<I: SwarmIdentity> KademliaRouting<I> {
         );
     }
 
+    /// A [`select_trim_victims`] pool entry for `overlay`.
+    ///
+    /// The rank is prefixed with the peer's reputation: a peer scored below
+    /// the warn threshold sorts beneath every well-reputed peer, whatever the
+    /// caller's rank says, so a misbehaving peer is given up before a merely
+    /// less reachable one. Among poorly reputed peers the caller's rank and
+    /// then the score still decide, so the lowest-scored goes first.
+    fn trim_entry<R: Ord>(
+        &self,
+        overlay: OverlayAddress,
+        rank: &impl Fn(&OverlayAddress) -> R,
+    ) -> (OverlayAddress, (bool, R), f64) {
+        let well_reputed = !self.peer_manager.has_poor_reputation(&overlay);
+        let score = self.peer_manager.get_peer_score(&overlay).unwrap_or(0.0);
+        (overlay, (well_reputed, rank(&overlay)), score)
+    }
+
     /// Identify peers to evict from overpopulated bins.
     ///
     /// Order: handshaking peers first (not yet established), then active peers
-    /// least worth keeping. Active victims are chosen by rank, then score,
-    /// then prefix diversity: `rank(overlay)` (the lowest-ranked is evicted
+    /// least worth keeping. Poorly reputed peers (see [`Self::trim_entry`])
+    /// go before all others. Otherwise active victims are chosen by rank, then
+    /// score, then prefix diversity: `rank(overlay)` (the lowest-ranked is evicted
     /// soonest) breaks ties on the peer score, preferring to drop unreachable
     /// peers; peers tied on both are decided by [`select_trim_victims`], which
     /// prefers evicting a peer that shares a long address prefix with a
@@ -544,21 +655,21 @@ impl<I: SwarmIdentity> KademliaRouting<I> {
                 }
             }
 
-            // Phase 2: Active peers, lowest rank first, then lowest score,
-            // then prefix diversity among full ties.
+            // Phase 2: Active peers, poorly reputed first, then lowest rank,
+            // then lowest score, then prefix diversity among full ties.
             if remaining > 0 {
                 let active_in_bin: Vec<_> = self
                     .connected_peers
                     .peers_in_bin(bin)
                     .into_iter()
-                    .map(|overlay| {
-                        let rank = rank(&overlay);
-                        let score = self.peer_manager.get_peer_score(&overlay).unwrap_or(0.0);
-                        (overlay, rank, score)
-                    })
+                    .map(|overlay| self.trim_entry(overlay, &rank))
                     .collect();
 
-                for overlay in select_trim_victims(active_in_bin, remaining) {
+                for overlay in select_trim_victims(
+                    self.config.distance_metric.as_ref(),
+                    active_in_bin,
+                    remaining,
+                ) {
                     candidates.push(EvictionCandidate {
                         overlay,
                         bin,
@@ -571,6 +682,74 @@ impl<I: SwarmIdentity> KademliaRouting<I> {
         candidates
     }
 
+    /// Total-connection-limit decision for a peer whose handshake is in
+    /// progress. `extra` has the same meaning as in
+    /// [`Self::admission_within_capacity`]; `rank` orders victims as in
+    /// [`Self::eviction_candidates`].
+    ///
+    /// At the limit, a peer whose bin is below its target (every
+    /// neighborhood bin is) may displace one active peer from the balanced
+    /// bin furthest above its own target; within that bin the worst-reputed,
+    /// then worst-ranked, most prefix-redundant peer goes. Neighborhood bins never donate, and
+    /// bootnodes and trusted peers are never displaced.
+    pub(crate) fn limit_admission<R: Ord>(
+        &self,
+        overlay: &OverlayAddress,
+        extra: usize,
+        rank: impl Fn(&OverlayAddress) -> R,
+    ) -> LimitAdmission {
+        let Some(limit) = self.config.max_connections else {
+            return LimitAdmission::Within;
+        };
+        let total: usize = all_bins(self.max_bin())
+            .map(|bin| self.effective_count(bin))
+            .sum();
+        // Connections held besides the in-flight peer, which fits only while
+        // they stay strictly below the limit.
+        let others = total.saturating_add(extra).saturating_sub(1);
+        if others < limit {
+            return LimitAdmission::Within;
+        }
+
+        let depth = self.depth();
+        let bin = self.bin_for(overlay);
+        // Peers other than the in-flight one already held in its bin.
+        let others = (self.effective_count(bin) + extra).saturating_sub(1);
+        if !self.config.limits.needs_more(bin, depth, others) {
+            return LimitAdmission::Full;
+        }
+
+        let donor = balanced_bins(depth)
+            .filter(|&donor| donor != bin)
+            .map(|donor| {
+                let excess = self
+                    .evictable_count(donor)
+                    .saturating_sub(self.config.limits.target(donor, depth));
+                (donor, excess)
+            })
+            .filter(|&(_, excess)| excess > 0)
+            .max_by_key(|&(_, excess)| excess);
+        let Some((donor, _)) = donor else {
+            return LimitAdmission::Full;
+        };
+
+        let pool: Vec<_> = self
+            .connected_peers
+            .peers_in_bin(donor)
+            .into_iter()
+            .filter(|peer| peer != overlay)
+            .filter(|peer| {
+                self.peer_manager.node_type(peer) != Some(SwarmNodeType::Bootnode)
+                    && self.peer_manager.trust_level(peer) != TrustLevel::Trusted
+            })
+            .map(|peer| self.trim_entry(peer, &rank))
+            .collect();
+        select_trim_victims(self.config.distance_metric.as_ref(), pool, 1)
+            .into_iter()
+            .next()
+            .map_or(LimitAdmission::Full, LimitAdmission::Displace)
+    }
+
     /// The published neighborhood depth.
     ///
     /// This is the hysteresis-filtered value (see [`Self::publish_depth_at`]):
@@ -632,6 +811,11 @@ impl<I: SwarmIdentity> KademliaRouting<I> {
         self.config.neighborhood_stability_window
     }
 
+    /// Connected peers a storer needs within depth before it counts as warm.
+    pub(crate) fn min_storer_neighbors(&self) -> usize {
+        self.config.min_storer_neighbors
+    }
+
     /// Whether the neighborhood is saturated: a depth boundary is
     /// established and the bins inside it together hold at least the
     /// saturation threshold in connected peers, the same condition as
@@ -688,7 +872,8 @@ impl<I: SwarmIdentity> KademliaRouting<I> {
         result
     }
 
-    /// Top `count` connected peers closest to `address` by proximity.
+    /// Top `count` connected peers closest to `address` by proximity under
+    /// the configured metric.
     ///
     /// Reads the generation-cached membership snapshot (one `Arc` clone on the
     /// hot path, no per-bin relock and no fresh membership `Vec`), then scores
@@ -699,7 +884,7 @@ impl<I: SwarmIdentity> KademliaRouting<I> {
             .connected_peers
             .iter_by_proximity()
             .map(|(_, peer)| {
-                let proximity = address.proximity(&peer);
+                let proximity = self.config.distance_metric.proximity(address, &peer);
                 (peer, proximity)
             })
             .collect();
@@ -718,6 +903,11 @@ impl<I: SwarmIdentity> KademliaRouting<I> {
             .collect()
     }
 
+    /// Connected peers per bin, shallowest first.
+    pub(crate) fn connected_bin_sizes(&self) -> Vec<usize> {
+        self.connected_peers.bin_sizes()
+    }
+
     pub(crate) fn bin_sizes(&self) -> Vec<(usize, usize)> {
         let connected = self.connected_peers.bin_sizes();
         let known = self.peer_manager.index().bin_sizes();
@@ -786,8 +976,13 @@ impl<I: SwarmIdentity> KademliaRouting<I> {
         let bin = self.bin_for(&peer);
 
         if self.connected_peers.add(peer).is_ok() {
+            let now = Instant::now();
+            if !self.config.depth_warmup.is_zero() {
+                self.warming.lock().insert(peer, now);
+            }
+
             let old_depth = self.depth();
-            self.publish_depth_at(Instant::now());
+            self.publish_depth_at(now);
             let new_depth = self.depth();
             self.update_neighborhood_stability();
 
@@ -813,6 +1008,7 @@ impl<I: SwarmIdentity> KademliaRouting<I> {
     fn peer_disconnected(&self, peer: &OverlayAddress) {
         if self.connected_peers.remove(peer) {
             let bin = self.bin_for(peer);
+            self.warming.lock().remove(peer);
 
             let old_depth = self.depth();
             self.publish_depth_at(Instant::now());
@@ -843,6 +1039,11 @@ impl<I: SwarmIdentity> RoutingCapacity for KademliaRouting<I> {
     fn try_reserve_dial(&self, overlay: &OverlayAddress, _node_type: SwarmNodeType) -> bool {
         let bin = self.bin_for(overlay);
         let effective = self.effective_count(bin);
+        // A dial takes a slot the moment it is reserved, so it is held to the
+        // total connection limit just as inbound admission is.
+        if self.limit_admission(overlay, 1, |_| ()) == LimitAdmission::Full {
+            return false;
+        }
 
         let mut phases = self.connection_phases.write();
 
@@ -966,6 +1167,7 @@ impl<I: SwarmIdentity> SwarmRouting<I> for KademliaRouting<I> {
 
     fn remove_peer(&self, peer: &OverlayAddress) {
         self.connected_peers.remove(peer);
+        self.warming.lock().remove(peer);
         self.update_neighborhood_stability();
         debug!(%peer, "removed peer from routing");
     }
@@ -1023,9 +1225,12 @@ impl<I: SwarmIdentity + 'static> KademliaRouting<I> {
         );
         let neighbor_candidates = selector.len();
 
-        select_balanced_candidates(&mut selector, &self.peer_manager, |bin| {
-            self.effective_count(bin)
-        });
+        select_balanced_candidates(
+            &mut selector,
+            &self.peer_manager,
+            |bin| self.effective_count(bin),
+            self.config.connection_strategy,
+        );
         let balanced_candidates = selector.len() - neighbor_candidates;
 
         let new_candidates = selector.finish();
@@ -1051,12 +1256,45 @@ impl<I: SwarmIdentity + 'static> KademliaRouting<I> {
     }
 }
 
+#[cfg(test)]
+impl<I: SwarmIdentity> KademliaRouting<I> {
+    /// Connect synthetic peers so that bin `po` holds `count` connected peers
+    /// for every `(po, count)` in `spec`, and return them.
+    ///
+    /// Each peer shares exactly `po` leading bits with the local overlay; the
+    /// trailing two bytes tell peers in one bin apart, so `po` must stay below
+    /// 240.
+    pub(crate) fn seed_test_topology(&self, spec: &[(u8, usize)]) -> Vec<OverlayAddress> {
+        let base = self.base();
+        let mut seeded = Vec::new();
+        for &(po, count) in spec {
+            assert!(po < 240, "po {po} leaves no room for distinct peers");
+            for idx in 0..count {
+                let idx = u16::try_from(idx).expect("at most u16::MAX peers per bin");
+                let mut bytes = base.0.0;
+                if let Some(byte) = bytes.get_mut(usize::from(po / 8)) {
+                    *byte ^= 0x80 >> (po % 8);
+                }
+                let [.., tail_hi, tail_lo] = &mut bytes;
+                let [hi, lo] = idx.to_be_bytes();
+                *tail_hi ^= hi;
+                *tail_lo ^= lo;
+                let peer = OverlayAddress::from(bytes);
+                self.peer_connected(peer);
+                seeded.push(peer);
+            }
+        }
+        seeded
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::indexing_slicing)]
     use super::*;
     use nectar_primitives::SwarmAddress;
     use vertex_swarm_peer_manager::PeerManagerConfig;
+    use vertex_swarm_primitives::XorDistance;
     use vertex_swarm_test_utils::{MockIdentity, make_swarm_peer_minimal};
 
     fn b(n: u8) -> Bin {
@@ -1080,6 +1318,30 @@ mod tests {
         (routing, peer_manager)
     }
 
+    #[test]
+    fn test_bin_count_follows_spec_address_size() {
+        let base = SwarmAddress::with_first_byte(0x00);
+        let spec = Arc::new(
+            vertex_swarm_spec::SpecBuilder::dev()
+                .address_size(std::num::NonZeroUsize::new(2).expect("non-zero"))
+                .build(),
+        );
+        let identity = MockIdentity::with_overlay(base).with_spec(spec);
+        let pm = PeerManager::new(&identity, PeerManagerConfig::default());
+        let routing = KademliaRouting::new(identity, KademliaConfig::default(), pm.clone());
+
+        // 16-bit addresses: bins 0..=15 in both the connected and known index.
+        assert_eq!(routing.max_po, 15);
+        assert_eq!(pm.index().max_po(), 15);
+        assert_eq!(routing.bin_sizes().len(), 16);
+
+        // A peer sharing more than 15 leading bits lands in the deepest bin.
+        let mut bytes = [0u8; 32];
+        bytes[31] = 0x01;
+        SwarmRouting::connected(&*routing, SwarmAddress::from(bytes));
+        assert_eq!(routing.connected_bin_sizes().last(), Some(&1));
+    }
+
     #[test]
     fn test_routing_creation() {
         let base = SwarmAddress::with_first_byte(0x00);
@@ -1239,6 +1501,183 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_seed_test_topology_shapes_bins_and_depth() {
+        let base = SwarmAddress::with_first_byte(0x5a);
+        let (routing, _pm) = make_routing(base, KademliaConfig::default());
+
+        let seeded = routing.seed_test_topology(&[(0, 8), (1, 8), (2, 8), (3, 2), (6, 4)]);
+
+        assert_eq!(seeded.len(), 30);
+        let connected: Vec<usize> = routing.bin_sizes().iter().map(|(c, _)| *c).collect();
+        assert_eq!(&connected[..7], &[8, 8, 8, 2, 0, 0, 4]);
+        assert!(connected[7..].iter().all(|size| *size == 0));
+        // Bins 0-2 are saturated and bin 3 is not, so the frontier sits at 3;
+        // the six peers at or beyond it clear the low watermark.
+        assert_eq!(routing.depth().get(), 3);
+    }
+
+    #[test]
+    fn test_depth_explanation_names_the_unsaturated_bin() {
+        let base = SwarmAddress::with_first_byte(0x5a);
+        let (routing, _pm) = make_routing(base, KademliaConfig::default());
+        routing.seed_test_topology(&[(0, 8), (1, 8), (2, 8), (3, 2), (6, 4)]);
+
+        let explanation = routing.depth_explanation();
+
+        assert_eq!(explanation.depth, d(3));
+        assert_eq!(
+            explanation.limit,
+            DepthLimit::Unsaturated {
+                bin: b(3),
+                missing: 6
+            }
+        );
+        assert_eq!(explanation.bins.len(), usize::from(routing.max_po) + 1);
+        assert_eq!(explanation.bins[0].bin, routing.max_bin());
+        let bin3 = explanation
+            .bins
+            .iter()
+            .find(|bin| bin.bin == b(3))
+            .expect("bin 3 listed");
+        assert_eq!((bin3.connected, bin3.cumulative), (2, 6));
+        assert!(!bin3.saturated);
+    }
+
+    #[test]
+    fn test_depth_explanation_names_the_low_watermark() {
+        let base = SwarmAddress::with_first_byte(0x5a);
+        let (routing, _pm) = make_routing(base, KademliaConfig::default());
+        // Bins 0-3 are saturated, so the frontier sits at 4, but only two
+        // peers lie beyond bin 3: the watermark of three holds depth at 3.
+        routing.seed_test_topology(&[(0, 8), (1, 8), (2, 8), (3, 8), (7, 2)]);
+
+        let explanation = routing.depth_explanation();
+
+        assert_eq!(explanation.depth, routing.depth());
+        assert_eq!(explanation.depth, d(3));
+        assert_eq!(explanation.limit, DepthLimit::LowWatermark { missing: 1 });
+    }
+
+    #[test]
+    fn test_neighbor_dials_get_the_longer_timeout() {
+        let base = SwarmAddress::with_first_byte(0x00);
+        let neighbor_timeout = Duration::from_secs(45);
+        let balanced_timeout = Duration::from_secs(5);
+        let (routing, _pm) = make_routing(
+            base,
+            KademliaConfig::default().with_dial_timeouts(neighbor_timeout, balanced_timeout),
+        );
+        routing.depth.store(4, Ordering::Relaxed);
+
+        assert_eq!(routing.dial_timeout(&addr_in_bin(6, 0)), neighbor_timeout);
+        assert_eq!(routing.dial_timeout(&addr_in_bin(4, 0)), neighbor_timeout);
+        assert_eq!(routing.dial_timeout(&addr_in_bin(1, 0)), balanced_timeout);
+    }
+
+    const WARMUP: Duration = Duration::from_secs(10);
+
+    /// Bin 0 one short of saturation with bin 8 populated: depth 0, and the
+    /// eighth bin-0 peer raises it to 1 (see `test_depth_caps_at_gap`). Every
+    /// peer here has finished its warmup.
+    fn warmed_table_one_short() -> Arc<KademliaRouting<MockIdentity>> {
+        let base = SwarmAddress::with_first_byte(0x00);
+        let config = KademliaConfig::default().with_depth_warmup(WARMUP);
+        let (routing, _pm) = make_routing(base, config);
+
+        for idx in 0..7 {
+            SwarmRouting::connected(&*routing, addr_in_bin(0, idx));
+        }
+        for idx in 0..5 {
+            SwarmRouting::connected(&*routing, addr_in_bin(8, idx));
+        }
+        routing.refresh_depth_at(Instant::now() + WARMUP);
+        assert_eq!(routing.depth().get(), 0);
+        routing
+    }
+
+    #[test]
+    fn test_new_peer_counts_toward_depth_after_warmup() {
+        let routing = warmed_table_one_short();
+
+        SwarmRouting::connected(&*routing, addr_in_bin(0, 7));
+        assert_eq!(routing.connected_peers.len(), 13);
+        assert_eq!(routing.depth().get(), 0, "warming peer must not count");
+
+        routing.refresh_depth();
+        assert_eq!(routing.depth().get(), 0, "warmup has not elapsed");
+
+        routing.refresh_depth_at(Instant::now() + WARMUP);
+        assert_eq!(routing.depth().get(), 1, "warmed-up peer saturates bin 0");
+    }
+
+    #[test]
+    fn test_quality_weighted_depth_waits_for_reliable_peers() {
+        use vertex_swarm_api::{ReportSource, SwarmScoringEvent};
+
+        let base = SwarmAddress::with_first_byte(0x00);
+        // A saturated bin 0 (0x80..) and three neighbors in bin 1 (0x40..):
+        // depth 1 when every peer counts.
+        let bin0: Vec<u8> = (0x80..0x88).collect();
+        let bin1: Vec<u8> = (0x40..0x43).collect();
+
+        let (plain, pm) = make_routing(base, KademliaConfig::default());
+        for &byte in bin0.iter().chain(&bin1) {
+            let peer = pm.store_discovered_peer(make_swarm_peer_minimal(byte));
+            SwarmRouting::connected(&*plain, peer);
+        }
+        assert_eq!(plain.depth().get(), 1, "simple counting is the default");
+
+        let (routing, pm) = make_routing(
+            base,
+            KademliaConfig::default().with_quality_weighted_depth(0.0),
+        );
+        let peers: Vec<_> = bin0
+            .iter()
+            .chain(&bin1)
+            .map(|&byte| pm.store_discovered_peer(make_swarm_peer_minimal(byte)))
+            .collect();
+        for &peer in &peers {
+            SwarmRouting::connected(&*routing, peer);
+        }
+        assert_eq!(routing.depth().get(), 0, "unproven peers must not count");
+
+        let prove = |peer: &OverlayAddress| {
+            pm.report_peer(
+                peer,
+                SwarmScoringEvent::ConnectionSuccess { latency: None },
+                ReportSource::Topology,
+            );
+        };
+        // Every neighbor and all but one bin-0 peer prove reliable: bin 0
+        // is still one short of saturation.
+        let (last, reliable) = peers.split_first().expect("peers");
+        reliable.iter().for_each(prove);
+        routing.refresh_depth();
+        assert_eq!(routing.depth().get(), 0);
+
+        prove(last);
+        routing.refresh_depth();
+        assert_eq!(routing.depth().get(), 1, "enough reliable peers");
+    }
+
+    #[test]
+    fn test_peer_lost_during_warmup_never_moves_depth() {
+        let routing = warmed_table_one_short();
+
+        let peer = addr_in_bin(0, 7);
+        SwarmRouting::connected(&*routing, peer);
+        assert_eq!(routing.depth().get(), 0);
+
+        SwarmRouting::on_peer_disconnected(&*routing, &peer);
+        assert_eq!(routing.depth().get(), 0);
+        assert!(routing.pending_depth_lower.lock().is_none());
+
+        routing.refresh_depth_at(Instant::now() + WARMUP);
+        assert_eq!(routing.depth().get(), 0, "departed peer never counted");
+        assert!(routing.warming.lock().is_empty());
+    }
+
     #[test]
     fn test_depth_climbs_with_saturated_bins() {
         // Bins 0,1,2 saturated (>= 8) with bin 3 holding the low-watermark (3)
@@ -1591,6 +2030,48 @@ mod tests {
         assert_eq!(closest[0], peer_po2);
     }
 
+    /// Numeric distance on the first byte: proximity is the number of
+    /// leading zero bits of the absolute difference, so `0x7f` and `0x80`
+    /// are neighbours even though XOR puts them in opposite halves.
+    #[derive(Debug)]
+    struct FirstByteDistance;
+
+    impl DistanceMetric for FirstByteDistance {
+        fn proximity(&self, a: &SwarmAddress, b: &SwarmAddress) -> ProximityOrder {
+            let diff = a.as_slice()[0].abs_diff(b.as_slice()[0]);
+            ProximityOrder::new(diff.leading_zeros() as u8).expect("valid proximity order")
+        }
+    }
+
+    #[test]
+    fn custom_distance_metric_drives_bins_and_closest_to() {
+        let base = SwarmAddress::with_first_byte(0x80);
+        let config = KademliaConfig::default().with_distance_metric(Arc::new(FirstByteDistance));
+        let (routing, _pm) = make_routing(base, config);
+
+        // XOR bins: 0x7f -> 0, 0xc0 -> 1, 0x00 -> 0.
+        let adjacent = SwarmAddress::with_first_byte(0x7f);
+        let upper = SwarmAddress::with_first_byte(0xc0);
+        let far = SwarmAddress::with_first_byte(0x00);
+        for peer in [adjacent, upper, far] {
+            SwarmRouting::connected(&*routing, peer);
+        }
+
+        assert_eq!(routing.bin_for(&adjacent), b(7));
+        assert_eq!(routing.bin_for(&upper), b(1));
+        assert_eq!(routing.bin_for(&far), b(0));
+        let sizes = routing.connected_bin_sizes();
+        assert_eq!((sizes[0], sizes[1], sizes[7]), (1, 1, 1));
+
+        // XOR ranks 0xc0 closest to 0x81; the custom metric ranks 0x7f.
+        let target = ChunkAddress::with_first_byte(0x81);
+        assert_eq!(
+            XorDistance.proximity(&target, &upper),
+            ProximityOrder::new(1).expect("valid proximity order")
+        );
+        assert_eq!(routing.closest_to(&target, 1), vec![adjacent]);
+    }
+
     #[test]
     fn test_neighbors() {
         let base = SwarmAddress::with_first_byte(0x00);
@@ -1878,6 +2359,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_eviction_prefers_lowest_reputation() {
+        use vertex_swarm_api::{ReportSource, SwarmScoringEvent};
+
+        let base = SwarmAddress::with_first_byte(0x00);
+        // Trim floor pinned to 4; see test_eviction_candidates_handshaking_first.
+        let (routing, pm) = make_routing(
+            base,
+            KademliaConfig::default()
+                .with_bootstrap_target(4)
+                .with_oversaturation_peers(4)
+                .with_saturation(4),
+        );
+
+        // 6 active peers in bin 0; at depth 8 the target is 4, so surplus is 2.
+        let peers: Vec<_> = (0x80..0x86)
+            .map(|byte| pm.store_discovered_peer(make_swarm_peer_minimal(byte)))
+            .collect();
+        for &peer in &peers {
+            force_active(&routing, peer);
+        }
+        routing.depth.store(8, Ordering::Relaxed);
+
+        let report = |peer: OverlayAddress, event: SwarmScoringEvent, times: usize| {
+            for _ in 0..times {
+                pm.report_peer(&peer, event, ReportSource::Topology);
+            }
+        };
+        // Two peers fall below the warn threshold, one further than the
+        // other; a third takes a small penalty but stays well reputed.
+        report(peers[1], SwarmScoringEvent::AccountingViolation, 3);
+        report(peers[4], SwarmScoringEvent::AccountingViolation, 3);
+        report(peers[4], SwarmScoringEvent::ProtocolError, 1);
+        report(peers[2], SwarmScoringEvent::ProtocolError, 1);
+
+        // The poorly reputed peers are the most reachable; reputation still
+        // outranks reachability, and the lowest-scored goes first.
+        let poor = [peers[1], peers[4]];
+        let candidates =
+            routing.eviction_candidates(|overlay| if poor.contains(overlay) { 2 } else { 0 });
+
+        let evicted: Vec<_> = candidates.iter().map(|c| c.overlay).collect();
+        assert_eq!(evicted, vec![peers[4], peers[1]]);
+    }
+
     #[test]
     fn test_eviction_local_tiebreak_keeps_local_over_equal_remote() {
         use crate::PeerReachability;
@@ -1988,7 +2514,7 @@ mod tests {
             .map(|overlay| (*overlay, 1u8, 0.0))
             .collect();
 
-        let victims = select_trim_victims(pool, 2);
+        let victims = select_trim_victims(&XorDistance, pool, 2);
         assert_eq!(victims.len(), 2);
         for victim in &victims {
             assert!(
@@ -2013,7 +2539,7 @@ mod tests {
             (clustered_b, 2u8, 0.0),
             (diverse, 0u8, 0.0),
         ];
-        assert_eq!(select_trim_victims(pool, 1), vec![diverse]);
+        assert_eq!(select_trim_victims(&XorDistance, pool, 1), vec![diverse]);
 
         // Equal rank: lowest score loses despite diversity.
         let pool = vec![
@@ -2021,7 +2547,7 @@ mod tests {
             (clustered_b, 1u8, 0.0),
             (diverse, 1u8, -1.0),
         ];
-        assert_eq!(select_trim_victims(pool, 1), vec![diverse]);
+        assert_eq!(select_trim_victims(&XorDistance, pool, 1), vec![diverse]);
     }
 
     #[test]
@@ -2040,7 +2566,7 @@ mod tests {
             .map(|overlay| (*overlay, 1u8, 0.0))
             .collect();
 
-        let victims = select_trim_victims(pool, 2);
+        let victims = select_trim_victims(&XorDistance, pool, 2);
         assert_eq!(victims.len(), 2);
         assert_eq!(
             victims.iter().filter(|v| cluster_a.contains(v)).count(),
@@ -2142,4 +2668,107 @@ mod tests {
             );
         }
     }
+
+    /// Six active peers in bin 0 at depth 8 (target 4, two redundant) under
+    /// a total connection limit of `limit`.
+    fn routing_with_connection_limit(
+        limit: usize,
+    ) -> (
+        Arc<KademliaRouting<MockIdentity>>,
+        Arc<PeerManager<MockIdentity>>,
+        Vec<OverlayAddress>,
+    ) {
+        let base = SwarmAddress::with_first_byte(0x00);
+        // Trim floor pinned to 4; see test_eviction_candidates_handshaking_first.
+        let config = KademliaConfig::default()
+            .with_bootstrap_target(4)
+            .with_oversaturation_peers(4)
+            .with_saturation(4)
+            .with_max_connections(limit);
+        let (routing, pm) = make_routing(base, config);
+        let peers: Vec<_> = (0..6)
+            .map(|i| SwarmAddress::with_first_byte(0x80 + i))
+            .collect();
+        for &peer in &peers {
+            force_active(&routing, peer);
+        }
+        routing.depth.store(8, Ordering::Relaxed);
+        (routing, pm, peers)
+    }
+
+    #[test]
+    fn test_limit_admission_within_limit() {
+        let (routing, _pm, _peers) = routing_with_connection_limit(7);
+
+        // Six of seven slots held: an inbound peer for the full bin still fits.
+        let peer = SwarmAddress::with_first_byte(0x90);
+        assert_eq!(
+            routing.limit_admission(&peer, 1, |_| ()),
+            LimitAdmission::Within
+        );
+    }
+
+    #[test]
+    fn test_limit_admission_gap_peer_displaces_redundant_peer() {
+        let (routing, _pm, peers) = routing_with_connection_limit(6);
+
+        // Bin 1 is empty, so the newcomer fills a gap. The victim comes from
+        // the oversaturated bin 0, and the least reachable peer goes first.
+        let gap_peer = SwarmAddress::with_first_byte(0x40);
+        let decision =
+            routing.limit_admission(&gap_peer, 1, |overlay| u8::from(*overlay != peers[3]));
+        assert_eq!(decision, LimitAdmission::Displace(peers[3]));
+    }
+
+    #[test]
+    fn test_limit_admission_refuses_peer_filling_no_gap() {
+        let (routing, _pm, _peers) = routing_with_connection_limit(6);
+
+        // Another bin-0 peer only deepens the oversaturation.
+        let redundant = SwarmAddress::with_first_byte(0x90);
+        assert_eq!(
+            routing.limit_admission(&redundant, 1, |_| ()),
+            LimitAdmission::Full
+        );
+    }
+
+    #[test]
+    fn test_limit_admission_never_displaces_bootnodes() {
+        let (routing, pm, _peers) = routing_with_connection_limit(6);
+        for byte in 0x80..0x86 {
+            pm.on_peer_connected(
+                make_swarm_peer_minimal(byte),
+                SwarmNodeType::Bootnode,
+                vertex_net_peer_registry::ConnectionDirection::Inbound,
+                TrustLevel::Normal,
+            );
+        }
+
+        let gap_peer = SwarmAddress::with_first_byte(0x40);
+        assert_eq!(
+            routing.limit_admission(&gap_peer, 1, |_| ()),
+            LimitAdmission::Full
+        );
+    }
+
+    #[test]
+    fn test_dial_not_reserved_at_connection_limit() {
+        let (routing, pm, _peers) = routing_with_connection_limit(6);
+        for byte in 0x80..0x86 {
+            pm.on_peer_connected(
+                make_swarm_peer_minimal(byte),
+                SwarmNodeType::Bootnode,
+                vertex_net_peer_registry::ConnectionDirection::Inbound,
+                TrustLevel::Normal,
+            );
+        }
+
+        // Six of six slots held and no one to displace: no dial, not even
+        // for an empty bin.
+        let gap_peer = SwarmAddress::with_first_byte(0x40);
+        assert!(!routing.try_reserve_dial(&gap_peer, SwarmNodeType::Storer));
+
+        let (routing, _pm, _peers) = routing_with_connection_limit(7);
+        assert!(routing.try_reserve_dial(&gap_peer, SwarmNodeType::Storer));
+    }
 }
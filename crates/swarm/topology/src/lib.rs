@@ -43,8 +43,11 @@
 
 pub(crate) use vertex_net_utils::extract_peer_id;
 
+mod audit;
 mod behaviour;
+mod bootnode;
 mod builder;
+mod churn;
 mod connection_handlers;
 mod dialing;
 mod events;
@@ -52,14 +55,17 @@ mod handle;
 mod kademlia;
 pub mod metrics;
 mod nat_discovery;
+mod partition;
 mod protocol_handlers;
 
 mod composed;
 mod error;
 mod gossip;
+mod probe;
 mod profile;
 mod reachability;
 mod readiness;
+mod static_peers;
 // The interface watcher monitors netdev for subnet discovery, which only exists
 // natively. The browser has no interfaces to enumerate, so the wasm sibling is
 // a no-op driver with the same signature.
@@ -69,15 +75,20 @@ mod tasks;
 #[cfg(test)]
 pub(crate) mod test_support;
 
+pub use audit::AUDIT_TARGET;
 pub use behaviour::{TopologyBehaviour, TopologyConfig};
+pub use bootnode::DEFAULT_BOOTNODE_RETRY_BUDGET;
 pub use builder::TopologyBehaviourBuilder;
+pub use churn::CHURN_WINDOW;
 pub use error::{DialError, DisconnectReason, RejectionReason, TopologyError, TopologyResult};
 pub use events::{ConnectionDirection, DialReason, TopologyCommand, TopologyEvent};
-pub use gossip::GossipConfig;
+pub use gossip::{GossipConfig, MIN_EXCHANGE_INTERVAL};
 pub use handle::{BinStats, RoutingStats, TopologyHandle};
+pub use partition::PartitionSuspicion;
+pub use probe::PROBE_TIMEOUT;
 pub use profile::PacingProfile;
 
-pub use kademlia::{KademliaConfig, RoutingArgs, TopologyPhase};
+pub use kademlia::{ConnectionStrategy, KademliaConfig, RoutingArgs, TopologyPhase};
 pub use reachability::{FAILURE_DECAY, FAILURE_THRESHOLD, PeerReachability, ReachabilityTracker};
 pub use readiness::{BinReadiness, ReadinessSnapshot};
 
@@ -131,6 +131,16 @@ impl TopologyMetrics {
             TopologyEvent::PingCompleted { rtt, .. } => {
                 self.record_ping_completed(*rtt);
             }
+            TopologyEvent::ChurnExceeded { .. } => {
+                counter!("topology_churn_alerts_total").increment(1);
+            }
+            TopologyEvent::NetworkPartitionSuspected { reason } => {
+                let reason: &'static str = reason.into();
+                counter!("topology_partition_suspected_total", "reason" => reason).increment(1);
+            }
+            TopologyEvent::PingFailed { .. } => {
+                counter!("topology_pings_total", "outcome" => outcome::FAILURE).increment(1);
+            }
             TopologyEvent::PhaseChanged { .. } => {
                 // Recorded where the transition is committed
                 // (`record_topology_phase_change` in the routing layer),
@@ -351,6 +361,7 @@ mod tests {
     use crate::error::DialError;
     use crate::events::ConnectionDirection;
     use libp2p::Multiaddr;
+    use vertex_swarm_primitives::PeerCapabilities;
     use vertex_swarm_test_utils::{test_overlay, test_peer_id};
 
     #[test]
@@ -370,6 +381,7 @@ mod tests {
             peer_id: test_peer_id(1),
             node_type: SwarmNodeType::Storer,
             direction: ConnectionDirection::Outbound,
+            capabilities: PeerCapabilities::NONE,
         };
 
         metrics.record_event(&event);
@@ -381,6 +393,7 @@ mod tests {
             peer_id: test_peer_id(2),
             node_type: SwarmNodeType::Client,
             direction: ConnectionDirection::Inbound,
+            capabilities: PeerCapabilities::NONE,
         };
 
         metrics.record_event(&event);
@@ -455,12 +468,14 @@ mod tests {
             peer_id: test_peer_id(1),
             node_type: SwarmNodeType::Storer,
             direction: ConnectionDirection::Outbound,
+            capabilities: PeerCapabilities::NONE,
         });
         metrics.record_event(&TopologyEvent::PeerReady {
             overlay: test_overlay(2),
             peer_id: test_peer_id(2),
             node_type: SwarmNodeType::Client,
             direction: ConnectionDirection::Outbound,
+            capabilities: PeerCapabilities::NONE,
         });
         assert_eq!(metrics.connected_storers(), 1);
         assert_eq!(metrics.connected_clients(), 1);
@@ -491,6 +506,7 @@ mod tests {
                 peer_id: test_peer_id(i),
                 node_type: SwarmNodeType::Storer,
                 direction: ConnectionDirection::Outbound,
+                capabilities: PeerCapabilities::NONE,
             });
         }
         for i in 10..12 {
@@ -499,6 +515,7 @@ mod tests {
                 peer_id: test_peer_id(i),
                 node_type: SwarmNodeType::Client,
                 direction: ConnectionDirection::Inbound,
+                capabilities: PeerCapabilities::NONE,
             });
         }
         assert_eq!(metrics.connected_storers(), 3);
@@ -554,6 +571,7 @@ mod tests {
             peer_id: test_peer_id(1),
             node_type: pm.node_type(&overlay).unwrap(),
             direction: ConnectionDirection::Outbound,
+            capabilities: PeerCapabilities::NONE,
         });
         assert_eq!(metrics.connected_storers(), 1);
         assert_eq!(metrics.connected_clients(), 0);
@@ -581,6 +599,7 @@ mod tests {
         let event = TopologyEvent::PingCompleted {
             overlay: test_overlay(0),
             rtt: Duration::from_millis(50),
+            probe_id: None,
         };
 
         // Should not panic
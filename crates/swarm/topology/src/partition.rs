@@ -0,0 +1,150 @@
+//! Network-partition heuristic.
+//!
+//! A node cut off from most of the network tends to lose its balanced
+//! connections first: the shallow bins span the whole address space, while
+//! the neighborhood is a handful of peers that may sit on the same side of
+//! the split. [`PartitionDetector`] watches the connected bins and suspects
+//! a partition when every bin below depth has emptied while neighbors stay
+//! connected, or when the last connected peer is gone. Each excursion is
+//! reported once, so the behaviour can raise
+//! [`TopologyEvent::NetworkPartitionSuspected`] and re-bootstrap; the alert
+//! re-arms once a shallow peer connects again.
+//!
+//! Depth collapses as the shallow bins empty, so the split is judged against
+//! the depth last seen with shallow peers connected, not the current one.
+//!
+//! [`TopologyEvent::NetworkPartitionSuspected`]: crate::TopologyEvent::NetworkPartitionSuspected
+
+use vertex_swarm_primitives::{NeighborhoodDepth, balanced_bins};
+
+/// Why a network partition is suspected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum PartitionSuspicion {
+    /// Every bin below `depth` lost its peers while `neighbors` peers at or
+    /// beyond it stayed connected.
+    ShallowBinsLost {
+        /// The depth the shallow bins were last populated at.
+        depth: u8,
+        /// Connected peers left, all inside the neighborhood.
+        neighbors: usize,
+    },
+    /// No peer is connected any more.
+    AllPeersLost,
+}
+
+/// Watches the connected bins for the signature of a partition.
+#[derive(Debug, Default)]
+pub(crate) struct PartitionDetector {
+    /// Depth last observed with peers connected below it; `None` until then.
+    anchor: Option<NeighborhoodDepth>,
+    /// Whether any peer has been connected, so the empty table at startup is
+    /// not mistaken for a loss.
+    had_peers: bool,
+    /// Whether the current excursion was already reported.
+    alerting: bool,
+}
+
+impl PartitionDetector {
+    /// Evaluate the connected-peer counts per bin at the published `depth`.
+    /// Returns the suspicion when this observation starts an excursion.
+    pub(crate) fn evaluate(
+        &mut self,
+        connected: &[usize],
+        depth: NeighborhoodDepth,
+    ) -> Option<PartitionSuspicion> {
+        let below = |depth: NeighborhoodDepth| -> usize {
+            balanced_bins(depth)
+                .filter_map(|bin| connected.get(bin.as_index()))
+                .sum()
+        };
+        let total: usize = connected.iter().sum();
+
+        if below(depth) > 0 {
+            self.anchor = Some(depth);
+        }
+
+        let suspicion = if total == 0 {
+            self.had_peers.then_some(PartitionSuspicion::AllPeersLost)
+        } else {
+            self.anchor
+                .filter(|anchor| below(*anchor) == 0)
+                .map(|anchor| PartitionSuspicion::ShallowBinsLost {
+                    depth: anchor.get(),
+                    neighbors: total,
+                })
+        };
+        self.had_peers |= total > 0;
+
+        let Some(suspicion) = suspicion else {
+            self.alerting = false;
+            return None;
+        };
+        if self.alerting {
+            return None;
+        }
+        self.alerting = true;
+        Some(suspicion)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vertex_swarm_primitives::Bin;
+
+    use super::*;
+
+    fn depth(bin: u8) -> NeighborhoodDepth {
+        NeighborhoodDepth::new(Bin::new(bin).expect("valid bin"))
+    }
+
+    #[test]
+    fn losing_every_shallow_peer_is_suspected_once_and_rearms() {
+        let mut detector = PartitionDetector::default();
+
+        assert_eq!(detector.evaluate(&[3, 2, 4, 1], depth(2)), None);
+        assert_eq!(
+            detector.evaluate(&[0, 1, 4, 1], depth(1)),
+            None,
+            "a shallow peer is still connected"
+        );
+
+        // Depth collapses with the last shallow peer; the anchor still splits
+        // the table where the shallow bins were last populated.
+        assert_eq!(
+            detector.evaluate(&[0, 0, 4, 1], depth(0)),
+            Some(PartitionSuspicion::ShallowBinsLost {
+                depth: 1,
+                neighbors: 5,
+            })
+        );
+        assert_eq!(
+            detector.evaluate(&[0, 0, 3, 1], depth(0)),
+            None,
+            "one alert per excursion"
+        );
+
+        // A shallow peer reconnects: the alert re-arms.
+        assert_eq!(detector.evaluate(&[1, 0, 3, 1], depth(1)), None);
+        assert!(matches!(
+            detector.evaluate(&[0, 0, 3, 1], depth(0)),
+            Some(PartitionSuspicion::ShallowBinsLost { .. })
+        ));
+    }
+
+    #[test]
+    fn total_loss_is_suspected_only_after_having_peers() {
+        let mut detector = PartitionDetector::default();
+        assert_eq!(
+            detector.evaluate(&[0, 0, 0], depth(0)),
+            None,
+            "an empty table at startup is not a loss"
+        );
+
+        assert_eq!(detector.evaluate(&[0, 0, 2], depth(0)), None);
+        assert_eq!(
+            detector.evaluate(&[0, 0, 0], depth(0)),
+            Some(PartitionSuspicion::AllPeersLost)
+        );
+    }
+}
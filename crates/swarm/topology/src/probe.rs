@@ -0,0 +1,265 @@
+//! On-demand `/ipfs/ping` probe.
+//!
+//! The stock [`libp2p::ping`] behaviour pings every connection on its own
+//! schedule and cannot be asked for a round trip now. [`PingProbe`] sends a
+//! single ping over the same protocol when told to, so an operator can
+//! spot-check one peer. It only opens outbound streams: inbound pings stay with
+//! the stock behaviour, which answers them.
+
+use std::{
+    collections::VecDeque,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::{AsyncReadExt, AsyncWriteExt, FutureExt};
+use libp2p::{
+    Multiaddr, PeerId, Stream,
+    core::upgrade::{DeniedUpgrade, ReadyUpgrade},
+    ping,
+    swarm::{
+        ConnectionDenied, ConnectionId, FromSwarm, NetworkBehaviour, NotifyHandler, StreamProtocol,
+        StreamUpgradeError, SubstreamProtocol, THandler, THandlerInEvent, THandlerOutEvent,
+        ToSwarm,
+        handler::{
+            ConnectionEvent, ConnectionHandler, ConnectionHandlerEvent, DialUpgradeError,
+            FullyNegotiatedOutbound,
+        },
+    },
+};
+use rand::Rng;
+use strum::IntoStaticStr;
+use vertex_tasks::time::Instant;
+use vertex_util_runtime::rand::non_crypto_rng;
+
+/// Upper bound on negotiating a probe stream and reading the echo back.
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Probes in flight per connection; further requests fail as busy.
+const MAX_PROBES_PER_CONNECTION: usize = 4;
+
+/// Payload size fixed by the ping protocol.
+const PING_SIZE: usize = 32;
+
+/// Why a probe produced no round-trip time.
+#[derive(Debug, Clone, thiserror::Error, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum ProbeError {
+    /// The peer does not speak `/ipfs/ping`.
+    #[error("peer does not support ping")]
+    Unsupported,
+    /// No echo arrived within [`PROBE_TIMEOUT`].
+    #[error("ping timed out")]
+    Timeout,
+    /// The echo did not match the payload sent.
+    #[error("ping echo mismatch")]
+    Mismatch,
+    /// Too many probes already in flight on the connection.
+    #[error("too many pings in flight")]
+    Busy,
+    /// The stream failed.
+    #[error("ping stream error: {0}")]
+    Io(String),
+}
+
+/// A probe's outcome on one connection.
+#[derive(Debug)]
+pub struct ProbeEvent {
+    pub peer: PeerId,
+    pub connection: ConnectionId,
+    /// The id the probe was requested under.
+    pub id: u64,
+    pub result: Result<Duration, ProbeError>,
+}
+
+/// Behaviour sending one-off pings on request.
+#[derive(Default)]
+pub struct PingProbe {
+    events: VecDeque<ToSwarm<ProbeEvent, u64>>,
+}
+
+impl PingProbe {
+    /// Ping `peer` once on any of its connections; the result arrives as a
+    /// [`ProbeEvent`] carrying `id`.
+    pub fn probe(&mut self, peer: PeerId, id: u64) {
+        self.events.push_back(ToSwarm::NotifyHandler {
+            peer_id: peer,
+            handler: NotifyHandler::Any,
+            event: id,
+        });
+    }
+}
+
+impl NetworkBehaviour for PingProbe {
+    type ConnectionHandler = ProbeHandler;
+    type ToSwarm = ProbeEvent;
+
+    fn handle_established_inbound_connection(
+        &mut self,
+        _connection_id: ConnectionId,
+        _peer: PeerId,
+        _local_addr: &Multiaddr,
+        _remote_addr: &Multiaddr,
+    ) -> Result<THandler<Self>, ConnectionDenied> {
+        Ok(ProbeHandler::new())
+    }
+
+    fn handle_established_outbound_connection(
+        &mut self,
+        _connection_id: ConnectionId,
+        _peer: PeerId,
+        _addr: &Multiaddr,
+        _role_override: libp2p::core::Endpoint,
+        _port_use: libp2p::core::transport::PortUse,
+    ) -> Result<THandler<Self>, ConnectionDenied> {
+        Ok(ProbeHandler::new())
+    }
+
+    fn on_swarm_event(&mut self, _event: FromSwarm) {}
+
+    fn on_connection_handler_event(
+        &mut self,
+        peer: PeerId,
+        connection: ConnectionId,
+        (id, result): THandlerOutEvent<Self>,
+    ) {
+        self.events.push_back(ToSwarm::GenerateEvent(ProbeEvent {
+            peer,
+            connection,
+            id,
+            result,
+        }));
+    }
+
+    fn poll(&mut self, _cx: &mut Context<'_>) -> Poll<ToSwarm<ProbeEvent, THandlerInEvent<Self>>> {
+        match self.events.pop_front() {
+            Some(event) => Poll::Ready(event),
+            None => Poll::Pending,
+        }
+    }
+}
+
+/// Per-connection handler: one outbound stream per requested probe.
+pub struct ProbeHandler {
+    /// Ids of probes requested and not yet given a stream.
+    requested: VecDeque<u64>,
+    /// Outbound streams requested and not yet negotiated.
+    opening: usize,
+    probes: futures_bounded::FuturesMap<u64, Result<Duration, ProbeError>>,
+    events: VecDeque<(u64, Result<Duration, ProbeError>)>,
+}
+
+impl ProbeHandler {
+    fn new() -> Self {
+        Self {
+            requested: VecDeque::new(),
+            opening: 0,
+            probes: futures_bounded::FuturesMap::new(PROBE_TIMEOUT, MAX_PROBES_PER_CONNECTION),
+            events: VecDeque::new(),
+        }
+    }
+}
+
+impl ConnectionHandler for ProbeHandler {
+    type FromBehaviour = u64;
+    type ToBehaviour = (u64, Result<Duration, ProbeError>);
+    type InboundProtocol = DeniedUpgrade;
+    type OutboundProtocol = ReadyUpgrade<StreamProtocol>;
+    type InboundOpenInfo = ();
+    type OutboundOpenInfo = u64;
+
+    fn listen_protocol(&self) -> SubstreamProtocol<Self::InboundProtocol, Self::InboundOpenInfo> {
+        SubstreamProtocol::new(DeniedUpgrade, ())
+    }
+
+    fn connection_keep_alive(&self) -> bool {
+        !self.requested.is_empty() || self.opening > 0 || !self.probes.is_empty()
+    }
+
+    fn poll(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<
+        ConnectionHandlerEvent<Self::OutboundProtocol, Self::OutboundOpenInfo, Self::ToBehaviour>,
+    > {
+        if let Some(result) = self.events.pop_front() {
+            return Poll::Ready(ConnectionHandlerEvent::NotifyBehaviour(result));
+        }
+
+        if let Some(id) = self.requested.pop_front() {
+            self.opening += 1;
+            let protocol = ReadyUpgrade::new(ping::PROTOCOL_NAME);
+            return Poll::Ready(ConnectionHandlerEvent::OutboundSubstreamRequest {
+                protocol: SubstreamProtocol::new(protocol, id).with_timeout(PROBE_TIMEOUT),
+            });
+        }
+
+        if let Poll::Ready((id, ready)) = self.probes.poll_unpin(cx) {
+            let result = ready.unwrap_or(Err(ProbeError::Timeout));
+            return Poll::Ready(ConnectionHandlerEvent::NotifyBehaviour((id, result)));
+        }
+
+        Poll::Pending
+    }
+
+    fn on_behaviour_event(&mut self, id: Self::FromBehaviour) {
+        self.requested.push_back(id);
+    }
+
+    fn on_connection_event(
+        &mut self,
+        event: ConnectionEvent<
+            Self::InboundProtocol,
+            Self::OutboundProtocol,
+            Self::InboundOpenInfo,
+            Self::OutboundOpenInfo,
+        >,
+    ) {
+        match event {
+            ConnectionEvent::FullyNegotiatedOutbound(FullyNegotiatedOutbound {
+                protocol: stream,
+                info: id,
+            }) => {
+                self.opening = self.opening.saturating_sub(1);
+                if self.probes.try_push(id, send_ping(stream)).is_err() {
+                    self.events.push_back((id, Err(ProbeError::Busy)));
+                }
+            }
+
+            ConnectionEvent::DialUpgradeError(DialUpgradeError { info: id, error }) => {
+                self.opening = self.opening.saturating_sub(1);
+                let error = match error {
+                    StreamUpgradeError::NegotiationFailed => ProbeError::Unsupported,
+                    StreamUpgradeError::Timeout => ProbeError::Timeout,
+                    StreamUpgradeError::Io(e) => ProbeError::Io(e.to_string()),
+                    StreamUpgradeError::Apply(never) => match never {},
+                };
+                self.events.push_back((id, Err(error)));
+            }
+
+            _ => {}
+        }
+    }
+}
+
+/// Write a random payload, read the echo, and time the round trip.
+async fn send_ping(mut stream: Stream) -> Result<Duration, ProbeError> {
+    let io = |e: std::io::Error| ProbeError::Io(e.to_string());
+
+    let mut payload = [0u8; PING_SIZE];
+    non_crypto_rng().fill(&mut payload);
+
+    let started = Instant::now();
+    stream.write_all(&payload).await.map_err(io)?;
+    stream.flush().await.map_err(io)?;
+    let mut echo = [0u8; PING_SIZE];
+    stream.read_exact(&mut echo).await.map_err(io)?;
+    let rtt = started.elapsed();
+    // The remote keeps the stream open for further pings; close our side.
+    let _ = stream.close().await;
+
+    if echo != payload {
+        return Err(ProbeError::Mismatch);
+    }
+    Ok(rtt)
+}
@@ -9,18 +9,21 @@ use metrics::{counter, gauge};
 use tracing::{debug, info, trace, warn};
 use vertex_net_local::{AddressScope, classify_multiaddr};
 use vertex_net_peer_registry::ActivateResult;
-use vertex_swarm_api::{ReportSource, SwarmIdentity, SwarmScoringEvent};
+use vertex_swarm_api::{ConnectionAuditStage, ReportSource, SwarmIdentity, SwarmScoringEvent};
+use vertex_swarm_net_goodbye::{Goodbye, GoodbyeEvent};
 use vertex_swarm_net_handshake::HandshakeEvent;
+use vertex_swarm_net_headers::ProtocolStreamError;
 use vertex_swarm_net_hive::HiveEvent;
-use vertex_swarm_peer_manager::TrustLevel;
+use vertex_swarm_peer_manager::{ConnectionTransition, DiscoverySource, TrustLevel};
 use vertex_swarm_primitives::{OverlayAddress, SwarmNodeType};
 
 use crate::DialReason;
 use crate::composed::ProtocolEvent;
-use crate::error::{DisconnectReason, RejectionReason};
+use crate::error::{DialError, DisconnectReason, RejectionReason};
 use crate::events::{ConnectionDirection, TopologyEvent};
 use crate::gossip::GossipInput;
-use crate::kademlia::{RoutingCapacity, SwarmRouting};
+use crate::kademlia::{LimitAdmission, RoutingCapacity, SwarmRouting};
+use crate::probe::{ProbeError, ProbeEvent};
 
 use crate::behaviour::TopologyBehaviour;
 
@@ -42,12 +45,36 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
             ProtocolEvent::Hive(HiveEvent::PeersReceived { peers, .. }) => {
                 self.on_hive_peers_received(peer_id, peers);
             }
+            ProtocolEvent::Hive(HiveEvent::LimitExceeded { error, .. }) => {
+                self.on_hive_limit_exceeded(peer_id, &error);
+            }
             ProtocolEvent::Hive(HiveEvent::Error { error, .. }) => {
                 warn!(%peer_id, %error, "Hive error");
             }
+            ProtocolEvent::Goodbye(GoodbyeEvent::Received { goodbye, .. }) => {
+                self.on_goodbye_received(peer_id, goodbye);
+            }
             ProtocolEvent::Ping(ping::Event { result, .. }) => {
                 self.on_ping_result(peer_id, result);
             }
+            ProtocolEvent::Probe(ProbeEvent { id, result, .. }) => {
+                self.on_probe_result(peer_id, id, result);
+            }
+        }
+    }
+
+    /// Log the reason a peer gave for closing. The close itself follows from
+    /// the remote; nothing is scored here.
+    fn on_goodbye_received(&self, peer_id: PeerId, goodbye: Goodbye) {
+        let overlay = self.connection_registry.resolve_id(&peer_id);
+        match goodbye.reason() {
+            Some(reason) => info!(%peer_id, ?overlay, %reason, "Peer is closing the connection"),
+            None => info!(
+                %peer_id,
+                ?overlay,
+                code = goodbye.code,
+                "Peer is closing the connection with an unknown reason"
+            ),
         }
     }
 
@@ -61,6 +88,17 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         let overlay = OverlayAddress::from(*info.swarm_peer.overlay());
         let node_type = info.node_type;
 
+        // A completion for a connection that is not mid-handshake (already
+        // closed, or a duplicate) is dropped before it can touch routing or
+        // the registry.
+        if self
+            .connection_phases
+            .apply(connection_id, ConnectionTransition::Authenticate)
+            .is_err()
+        {
+            return;
+        }
+
         debug!(
             %peer_id,
             %overlay,
@@ -68,6 +106,12 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
             bin = self.bin_for(&overlay).get(),
             "Handshake completed"
         );
+        self.audit.record(
+            peer_id,
+            Some(overlay),
+            ConnectionAuditStage::HandshakeCompleted,
+            Some(node_type.into()),
+        );
 
         // Get dial info from connection registry before transitioning
         let current_state = self.connection_registry.get(&overlay).or_else(|| {
@@ -130,8 +174,10 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
 
         // For inbound connections, check bin capacity and reserve a slot before
         // transitioning to active. Outbound connections already reserved capacity
-        // at dial time via try_reserve_dial.
-        if direction == ConnectionDirection::Inbound {
+        // at dial time via try_reserve_dial. Static peers skip the bin and
+        // connection-limit checks but still take their slot.
+        let is_static = self.static_peers.contains(&overlay);
+        if direction == ConnectionDirection::Inbound && !is_static {
             let bin_at_capacity =
                 !RoutingCapacity::should_accept_inbound(&*self.routing, &overlay, node_type);
             if bin_at_capacity {
@@ -151,6 +197,58 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
                 self.close_peer(peer_id, DisconnectReason::BinSaturated);
                 return;
             }
+            // At the total connection limit a gap-filling peer displaces a
+            // redundant one; anyone else is refused.
+            let admission = self
+                .routing
+                .limit_admission(&overlay, 1, self.eviction_rank());
+            match admission {
+                LimitAdmission::Within => {}
+                LimitAdmission::Displace(victim) => {
+                    if let Some(victim_peer_id) = self.connection_registry.resolve_peer_id(&victim)
+                    {
+                        debug!(
+                            %peer_id,
+                            %overlay,
+                            %victim,
+                            %victim_peer_id,
+                            "Displacing redundant peer to admit gap-filling peer at connection limit"
+                        );
+                        self.close_peer(victim_peer_id, DisconnectReason::Displaced);
+                    }
+                }
+                LimitAdmission::Full => {
+                    debug!(
+                        %peer_id,
+                        %overlay,
+                        ?node_type,
+                        ?direction,
+                        "Rejecting inbound connection: connection limit reached"
+                    );
+                    self.emit_event(TopologyEvent::PeerRejected {
+                        overlay,
+                        peer_id,
+                        reason: RejectionReason::ConnectionLimit,
+                        direction,
+                    });
+                    self.close_peer(peer_id, DisconnectReason::ConnectionLimit);
+                    return;
+                }
+            }
+        }
+
+        // Admission passed. Activation is only acted on from Authenticated; any
+        // other phase means the connection moved on (or never authenticated),
+        // and nothing has been reserved or announced for it yet.
+        if self
+            .connection_phases
+            .apply(connection_id, ConnectionTransition::Activate)
+            .is_err()
+        {
+            return;
+        }
+
+        if direction == ConnectionDirection::Inbound {
             // Reserve inbound slot so handshake_completed can transition Handshaking->Active
             RoutingCapacity::reserve_inbound(&*self.routing, &overlay);
         }
@@ -228,8 +326,16 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         // configured peers dial with `DialReason::Trusted`) and the listen
         // addresses needed to judge subnet locality; the peer manager stores
         // the result so eviction ranking reads one atomic instead of
-        // re-deriving address scope per trim round.
-        let trust = if dial_reason == Some(DialReason::Trusted) {
+        // re-deriving address scope per trim round. Static peers are trusted
+        // by overlay, however the connection came about.
+        if dial_reason == Some(DialReason::Static) && !is_static {
+            warn!(
+                %peer_id,
+                %overlay,
+                "static peer address answered with an unconfigured overlay"
+            );
+        }
+        let trust = if dial_reason == Some(DialReason::Trusted) || is_static {
             TrustLevel::Trusted
         } else if crate::behaviour::peer_is_local(
             &info.swarm_peer,
@@ -245,6 +351,18 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
             direction,
             trust,
         );
+        // Capabilities are cached per overlay; a reconnect that negotiated a
+        // different set replaces the stale value before anyone reads it.
+        self.peer_manager
+            .record_capabilities(&overlay, info.capabilities);
+        // Only a peer first learned here takes this source; one gossiped or
+        // restored earlier keeps the source it was recorded with.
+        let source = if is_static {
+            DiscoverySource::Static
+        } else {
+            dial_reason.map_or(DiscoverySource::Inbound, DialReason::discovery_source)
+        };
+        self.peer_manager.record_discovery_source(&overlay, source);
 
         // Feed reachability BEFORE notifying routing: `trim_overpopulated_bins`
         // ranks eviction victims by reachability (least-reachable first), so the
@@ -290,12 +408,20 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         }
 
         self.refresh_topology_phase();
+        self.check_partition();
 
+        self.audit.record(
+            peer_id,
+            Some(overlay),
+            ConnectionAuditStage::Activated,
+            Some(direction.into()),
+        );
         self.emit_event(TopologyEvent::PeerReady {
             overlay,
             peer_id,
             node_type,
             direction,
+            capabilities: info.capabilities,
         });
 
         // Notify gossip task -- exchange happens immediately or after delay (for gossip dials)
@@ -339,10 +465,23 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         }
         let reason = state.as_ref().and_then(|s| *s.reason());
         let overlay = state.as_ref().and_then(|s| s.id());
+        self.audit.record(
+            peer_id,
+            overlay,
+            ConnectionAuditStage::HandshakeFailed,
+            Some((&error).into()),
+        );
 
+        let dial_error = dial_error_for_handshake(&error);
         if let Some(ref overlay) = overlay {
             self.routing.release_handshake(overlay);
-            self.peer_manager.record_dial_failure(overlay);
+            // A peer that does not speak our handshake is incompatible, not
+            // transiently unreachable: back it off on the longer schedule.
+            if dial_error == DialError::NegotiationFailed {
+                self.peer_manager.record_negotiation_failure(overlay);
+            } else {
+                self.peer_manager.record_dial_failure(overlay);
+            }
             // Score the failure only when the peer is unambiguously at
             // fault, mirroring the reachability gate above; our own
             // duplicate-connection evictions and shutdowns must not
@@ -359,7 +498,7 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         self.emit_event(TopologyEvent::DialFailed {
             overlay,
             addrs: Vec::new(),
-            error: crate::error::DialError::HandshakeFailed(error.to_string()),
+            error: dial_error,
             dial_duration: state
                 .as_ref()
                 .and_then(|s| s.started_at())
@@ -368,6 +507,20 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
         });
     }
 
+    /// Score down a peer whose hive response broke a configured cap; the
+    /// response was rejected before any record was validated.
+    fn on_hive_limit_exceeded(&mut self, peer_id: PeerId, error: &ProtocolStreamError) {
+        warn!(%peer_id, %error, "Hive response exceeded limits");
+        counter!("topology_gossip_rejected_total", "reason" => "limit_exceeded").increment(1);
+        if let Some(overlay) = self.connection_registry.resolve_id(&peer_id) {
+            self.peer_manager.report_peer(
+                &overlay,
+                SwarmScoringEvent::InvalidData,
+                ReportSource::Gossip,
+            );
+        }
+    }
+
     fn on_hive_peers_received(
         &mut self,
         peer_id: PeerId,
@@ -446,7 +599,11 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
                 if let Some(overlay) = self.connection_registry.resolve_id(&peer_id) {
                     self.peer_manager.record_latency(&overlay, rtt);
                     debug!(%peer_id, %overlay, ?rtt, "ping ok: liveness + rtt");
-                    self.emit_event(TopologyEvent::PingCompleted { overlay, rtt });
+                    self.emit_event(TopologyEvent::PingCompleted {
+                        overlay,
+                        rtt,
+                        probe_id: None,
+                    });
                 }
             }
             Err(failure) => {
@@ -455,6 +612,44 @@ impl<I: SwarmIdentity + Clone> TopologyBehaviour<I> {
             }
         }
     }
+
+    /// Handle the result of an operator-requested ping.
+    ///
+    /// A round trip counts like a periodic one. A failure is reported to the
+    /// waiting caller only: a single probe is not evidence enough to demote
+    /// the peer's reachability.
+    fn on_probe_result(
+        &mut self,
+        peer_id: PeerId,
+        probe_id: u64,
+        result: Result<Duration, ProbeError>,
+    ) {
+        let Some(overlay) = self.connection_registry.resolve_id(&peer_id) else {
+            return;
+        };
+        match result {
+            Ok(rtt) => {
+                self.nat_discovery
+                    .reachability()
+                    .update_from_ping(peer_id, true);
+                self.peer_manager.record_latency(&overlay, rtt);
+                debug!(%peer_id, %overlay, ?rtt, "probe ok");
+                self.emit_event(TopologyEvent::PingCompleted {
+                    overlay,
+                    rtt,
+                    probe_id: Some(probe_id),
+                });
+            }
+            Err(error) => {
+                debug!(%peer_id, %overlay, %error, "probe failed");
+                self.emit_event(TopologyEvent::PingFailed {
+                    overlay,
+                    probe_id,
+                    error: error.to_string(),
+                });
+            }
+        }
+    }
 }
 
 /// Whether a gossiped record carries at least one multiaddr this node can
@@ -481,6 +676,16 @@ fn record_dialable(
     }
 }
 
+/// Map a failed handshake to the dial error it surfaces as.
+fn dial_error_for_handshake(error: &vertex_swarm_net_handshake::HandshakeError) -> DialError {
+    match error {
+        vertex_swarm_net_handshake::HandshakeError::NegotiationFailed => {
+            DialError::NegotiationFailed
+        }
+        error => DialError::HandshakeFailed(error.to_string()),
+    }
+}
+
 /// Classify a handshake error: only protocol violations the peer is solely
 /// responsible for should feed the reachability tracker. Timeouts, IO
 /// errors, and bare connection-close events can be caused by our own side
@@ -575,4 +780,24 @@ mod tests {
         assert!(!record_dialable(&v6_only, capability));
         assert!(record_dialable(&record_with_addrs(&[TCP]), capability));
     }
+
+    /// A peer that connects but does not speak the handshake keeps its own
+    /// failure reason instead of collapsing into a generic handshake failure,
+    /// and is not scored as misbehaving.
+    #[test]
+    fn negotiation_failure_is_distinct_from_handshake_failure() {
+        use vertex_swarm_net_handshake::HandshakeError;
+
+        let negotiation = HandshakeError::NegotiationFailed;
+        assert_eq!(
+            dial_error_for_handshake(&negotiation),
+            DialError::NegotiationFailed
+        );
+        assert!(!is_peer_fault(&negotiation));
+
+        assert_eq!(
+            dial_error_for_handshake(&HandshakeError::Timeout),
+            DialError::HandshakeFailed("timeout".to_string())
+        );
+    }
 }
@@ -54,6 +54,9 @@ pub struct ReadinessSnapshot {
     /// Per-bin saturation target from the network spec; the threshold the
     /// neighborhood must reach for [`Self::is_saturated`].
     pub saturation_threshold: usize,
+    /// Connected peers a storer needs within depth before it is warm
+    /// (see [`Self::has_min_neighbors`]).
+    pub min_storer_neighbors: usize,
     /// Per-bin readiness, shallowest bin first.
     pub bins: Vec<BinReadiness>,
     /// Bins with a finite target whose connected count meets it.
@@ -103,6 +106,14 @@ impl ReadinessSnapshot {
             && self.neighborhood_connected >= self.saturation_threshold
     }
 
+    /// Whether the bins at or beyond the depth boundary hold at least
+    /// [`Self::min_storer_neighbors`] connected peers: enough neighbors for
+    /// a storer to replicate into. Peers in shallower bins do not count.
+    #[must_use]
+    pub fn has_min_neighbors(&self) -> bool {
+        self.neighborhood_connected >= self.min_storer_neighbors
+    }
+
     /// Whether the neighborhood is ready for pull-syncing: continuously
     /// saturated at an unchanged depth for at least
     /// [`Self::neighborhood_stability_window`].
@@ -122,14 +133,15 @@ impl ReadinessSnapshot {
 
     /// The composite warm signal for the local node type.
     ///
-    /// A storer is warm once it is routable and its neighborhood is
-    /// saturated; it needs the neighborhood to hold the chunks it is
-    /// responsible for. Every other node type is warm as soon as it is
+    /// A storer is warm once it is routable, its neighborhood is saturated,
+    /// and it has the configured minimum of neighbors; it needs the
+    /// neighborhood to hold and replicate the chunks it is responsible for.
+    /// Every other node type is warm as soon as it is
     /// routable, the point from which pushes and retrievals can proceed.
     #[must_use]
     pub fn is_warm(&self) -> bool {
         if self.local_node_type.requires_storage() {
-            self.is_routable() && self.is_saturated()
+            self.is_routable() && self.is_saturated() && self.has_min_neighbors()
         } else {
             self.is_routable()
         }
@@ -152,6 +164,7 @@ mod tests {
             depth: NeighborhoodDepth::ZERO,
             neighborhood_connected: 0,
             saturation_threshold: 8,
+            min_storer_neighbors: 0,
             bins: Vec::new(),
             bins_at_target: 0,
             neighborhood_stable_for: None,
@@ -218,6 +231,30 @@ mod tests {
         assert!(s.is_saturated());
     }
 
+    #[test]
+    fn storer_needs_the_neighbor_minimum_to_be_warm() {
+        let mut s = snapshot(SwarmNodeType::Storer);
+        s.min_storer_neighbors = 12;
+        s.connected_peers = 40;
+        s.connected_storers = 40;
+        s.depth = depth(3);
+        // Plenty of shallow peers and a saturated neighborhood, but too few
+        // neighbors to replicate into.
+        s.neighborhood_connected = 8;
+        assert!(s.is_saturated());
+        assert!(!s.has_min_neighbors());
+        assert!(!s.is_warm(), "a storer short of neighbors is not ready");
+
+        s.neighborhood_connected = 12;
+        assert!(s.is_warm());
+
+        // The floor is a storer concern only.
+        let mut client = s.clone();
+        client.local_node_type = SwarmNodeType::Client;
+        client.neighborhood_connected = 0;
+        assert!(client.is_warm());
+    }
+
     #[test]
     fn neighborhood_ready_requires_full_window() {
         let mut s = snapshot(SwarmNodeType::Storer);
@@ -0,0 +1,114 @@
+//! Operator-pinned peers the node always keeps connected.
+//!
+//! A static peer is configured as an overlay plus a dialable multiaddr (see
+//! [`TopologyConfig::with_static_peers`]). Unlike a trusted peer, which is
+//! dialed once at startup, a static peer is redialed whenever it drops and on
+//! every evaluation tick it is not connected or being dialed. A failed dial,
+//! or a connection the peer closes before the handshake completes, puts it in
+//! the ordinary dial backoff, and no redial is made until that expires.
+//! Kademlia's bin logic does not apply to it:
+//!
+//! - the handshake admission gate and the inbound bin and connection-limit
+//!   checks always admit it;
+//! - it is stored with [`TrustLevel::Trusted`], so bin trimming and
+//!   connection-limit displacement never pick it as a victim.
+//!
+//! The overlay is what grants the exemption. A static address that answers
+//! with a different overlay connects as an ordinary peer.
+//!
+//! [`TopologyConfig::with_static_peers`]: crate::TopologyConfig::with_static_peers
+//! [`TrustLevel::Trusted`]: vertex_swarm_peer_manager::TrustLevel::Trusted
+
+use libp2p::{Multiaddr, PeerId};
+use tracing::warn;
+use vertex_swarm_primitives::OverlayAddress;
+
+use crate::extract_peer_id;
+
+struct StaticPeer {
+    overlay: OverlayAddress,
+    peer_id: PeerId,
+    addr: Multiaddr,
+}
+
+/// The configured static peers, shared by the behaviour (which dials them)
+/// and the handshake admission gate (which always admits them).
+#[derive(Default)]
+pub(crate) struct StaticPeers {
+    peers: Vec<StaticPeer>,
+}
+
+impl StaticPeers {
+    /// Addresses without a `/p2p/` component cannot be dialed and are skipped.
+    pub(crate) fn new(peers: impl IntoIterator<Item = (OverlayAddress, Multiaddr)>) -> Self {
+        let peers = peers
+            .into_iter()
+            .filter_map(|(overlay, addr)| {
+                let Some(peer_id) = extract_peer_id(&addr) else {
+                    warn!(%overlay, %addr, "ignoring static peer without a /p2p/ component");
+                    return None;
+                };
+                Some(StaticPeer {
+                    overlay,
+                    peer_id,
+                    addr,
+                })
+            })
+            .collect();
+        Self { peers }
+    }
+
+    /// Whether `overlay` is a configured static peer.
+    pub(crate) fn contains(&self, overlay: &OverlayAddress) -> bool {
+        self.peers.iter().any(|peer| peer.overlay == *overlay)
+    }
+
+    /// Whether `peer_id` is the dial target of a configured static peer.
+    pub(crate) fn contains_peer_id(&self, peer_id: &PeerId) -> bool {
+        self.overlay_of(peer_id).is_some()
+    }
+
+    /// The configured overlay of the static peer dialed as `peer_id`.
+    pub(crate) fn overlay_of(&self, peer_id: &PeerId) -> Option<OverlayAddress> {
+        self.peers
+            .iter()
+            .find(|peer| peer.peer_id == *peer_id)
+            .map(|peer| peer.overlay)
+    }
+
+    /// Every static peer's overlay with its dial address.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&OverlayAddress, &Multiaddr)> {
+        self.peers.iter().map(|peer| (&peer.overlay, &peer.addr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vertex_swarm_test_utils::test_overlay;
+
+    use super::*;
+
+    #[test]
+    fn addresses_without_a_peer_id_are_skipped() {
+        let peer_id = PeerId::random();
+        let dialable: Multiaddr = format!("/ip4/203.0.113.10/tcp/1634/p2p/{peer_id}")
+            .parse()
+            .expect("valid");
+        let static_peers = StaticPeers::new([
+            (test_overlay(1), dialable.clone()),
+            (
+                test_overlay(2),
+                "/ip4/203.0.113.11/tcp/1634".parse().expect("valid"),
+            ),
+        ]);
+
+        assert!(static_peers.contains(&test_overlay(1)));
+        assert!(static_peers.contains_peer_id(&peer_id));
+        assert_eq!(static_peers.overlay_of(&peer_id), Some(test_overlay(1)));
+        assert!(!static_peers.contains(&test_overlay(2)));
+        assert_eq!(
+            static_peers.iter().collect::<Vec<_>>(),
+            vec![(&test_overlay(1), &dialable)]
+        );
+    }
+}
@@ -4,7 +4,7 @@ use std::sync::Arc;
 
 use vertex_swarm_api::SwarmNodeType;
 use vertex_swarm_peer_manager::{PeerManager, PeerManagerConfig};
-use vertex_swarm_primitives::OverlayAddress;
+use vertex_swarm_primitives::{DistanceMetric, OverlayAddress, XorDistance};
 use vertex_swarm_test_utils::{MockIdentity, test_overlay, test_swarm_peer};
 
 use crate::behaviour::ConnectionRegistry;
@@ -17,9 +17,18 @@ pub(crate) struct TopologyTestContext {
 
 impl TopologyTestContext {
     pub(crate) fn new() -> Self {
+        Self::with_metric(Arc::new(XorDistance))
+    }
+
+    /// A context whose peer manager bins peers with `metric`.
+    pub(crate) fn with_metric(metric: Arc<dyn DistanceMetric>) -> Self {
         let local = test_overlay(0);
         let identity = MockIdentity::with_overlay(local);
-        let pm = PeerManager::new(&identity, PeerManagerConfig::default());
+        let config = PeerManagerConfig {
+            distance_metric: metric,
+            ..PeerManagerConfig::default()
+        };
+        let pm = PeerManager::new(&identity, config);
         let cr = Arc::new(ConnectionRegistry::new());
         Self {
             local_overlay: local,